@@ -36,6 +36,7 @@ struct Handler {
     allow_list: oxicrab_core::config::schema::DenyByDefaultList,
     allow_groups: oxicrab_core::config::schema::DenyByDefaultList,
     dm_policy: oxicrab_core::config::schema::DmPolicy,
+    unknown_sender_policy: Option<oxicrab_core::config::schema::UnknownSenderPolicy>,
     http_client: reqwest::Client,
     commands: Vec<DiscordCommand>,
     dispatch_store: Arc<crate::dispatch::DispatchContextStore>,
@@ -61,7 +62,14 @@ impl Handler {
 
         // DM access check for non-guild interactions
         if cmd.guild_id.is_none() {
-            match check_dm_access(&sender_id, &self.allow_list, "discord", &self.dm_policy) {
+            match check_dm_access(
+                &sender_id,
+                &self.allow_list,
+                "discord",
+                &self.dm_policy,
+                self.unknown_sender_policy,
+                &format!("/{}", cmd.data.name),
+            ) {
                 DmCheckResult::Allowed => {}
                 DmCheckResult::PairingRequired { code } => {
                     let reply = format_pairing_reply("discord", &sender_id, &code);
@@ -160,7 +168,14 @@ impl Handler {
 
         // DM access check for non-guild interactions
         if comp.guild_id.is_none() {
-            match check_dm_access(&sender_id, &self.allow_list, "discord", &self.dm_policy) {
+            match check_dm_access(
+                &sender_id,
+                &self.allow_list,
+                "discord",
+                &self.dm_policy,
+                self.unknown_sender_policy,
+                "",
+            ) {
                 DmCheckResult::Allowed => {}
                 DmCheckResult::PairingRequired { code } => {
                     let reply = format_pairing_reply("discord", &sender_id, &code);
@@ -274,7 +289,14 @@ impl EventHandler for Handler {
         }
         // DM access check (skipped for group messages)
         if !is_group {
-            match check_dm_access(&sender_id, &self.allow_list, "discord", &self.dm_policy) {
+            match check_dm_access(
+                &sender_id,
+                &self.allow_list,
+                "discord",
+                &self.dm_policy,
+                self.unknown_sender_policy,
+                &msg.content,
+            ) {
                 DmCheckResult::Allowed => {}
                 DmCheckResult::PairingRequired { code } => {
                     let reply = format_pairing_reply("discord", &sender_id, &code);
@@ -400,6 +422,10 @@ impl EventHandler for Handler {
             oxicrab_core::bus::events::meta::IS_GROUP.to_string(),
             serde_json::Value::Bool(is_group),
         );
+        metadata.insert(
+            oxicrab_core::bus::events::meta::MESSAGE_ID.to_string(),
+            serde_json::Value::String(msg.id.to_string()),
+        );
         let inbound_msg =
             InboundMessage::builder("discord", sender_id, msg.channel_id.to_string(), content)
                 .media(media_paths)
@@ -491,6 +517,16 @@ impl DiscordChannel {
     }
 }
 
+/// Build a reply reference from an outbound `reply_to` message ID.
+/// Returns `None` when `reply_to` is absent or not a Discord snowflake.
+fn reply_reference(
+    channel_id: serenity::model::id::ChannelId,
+    reply_to: Option<&str>,
+) -> Option<serenity::model::channel::MessageReference> {
+    let id = reply_to?.parse::<u64>().ok().filter(|id| *id != 0)?;
+    Some((channel_id, serenity::model::id::MessageId::new(id)).into())
+}
+
 /// Send a followup message via Discord's webhook API for deferred interactions.
 /// Retries once on 429 rate limit responses.
 async fn send_interaction_followup(
@@ -629,6 +665,7 @@ impl BaseChannel for DiscordChannel {
         let allow_from = self.config.allow_from.clone();
         let allow_groups = self.config.allow_groups.clone();
         let dm_policy = self.config.dm_policy.clone();
+        let unknown_sender_policy = self.config.unknown_sender_policy;
         let commands = self.config.commands.clone();
         let mention_only = self.config.mention_only;
         let inbound_tx = self.inbound_tx.clone();
@@ -650,6 +687,7 @@ impl BaseChannel for DiscordChannel {
                     allow_list: allow_from.clone(),
                     allow_groups: allow_groups.clone(),
                     dm_policy: dm_policy.clone(),
+                    unknown_sender_policy,
                     http_client: reqwest::Client::builder()
                         .connect_timeout(std::time::Duration::from_secs(10))
                         .timeout(std::time::Duration::from_secs(30))
@@ -778,7 +816,7 @@ impl BaseChannel for DiscordChannel {
 
         // Regular channel message path
         let id_val = msg.chat_id.parse::<u64>()?;
-        let chunks = split_message(&msg.content, 2000);
+        let chunks = split_message(&msg.content, self.config.max_message_length);
         let http = &self.serenity_http;
 
         // Check if chat_id is a user ID (from allow_from) — if so, open a DM channel
@@ -828,17 +866,26 @@ impl BaseChannel for DiscordChannel {
         let embeds = parse_embeds_from_metadata(&msg.metadata);
         let components = parse_components_from_metadata(&msg.metadata, Some(&self.dispatch_store));
 
-        // Send text content (attach embeds/components to the last chunk)
+        // Send text content (reply reference on the first chunk,
+        // embeds/components on the last chunk)
+        let mut reply_ref = reply_reference(target_channel_id, msg.reply_to.as_deref());
         let chunk_count = chunks.len();
         for (i, chunk) in chunks.iter().enumerate() {
             let is_last = i == chunk_count - 1;
-            if is_last && (!embeds.is_empty() || !components.is_empty()) {
+            let reference = reply_ref.take();
+            let has_extras = is_last && (!embeds.is_empty() || !components.is_empty());
+            if has_extras || reference.is_some() {
                 let mut builder = CreateMessage::new().content(chunk);
-                for embed in &embeds {
-                    builder = builder.embed(embed.clone());
+                if let Some(r) = reference {
+                    builder = builder.reference_message(r);
                 }
-                if !components.is_empty() {
-                    builder = builder.components(components.clone());
+                if has_extras {
+                    for embed in &embeds {
+                        builder = builder.embed(embed.clone());
+                    }
+                    if !components.is_empty() {
+                        builder = builder.components(components.clone());
+                    }
                 }
                 target_channel_id
                     .send_message(&http, builder)
@@ -895,20 +942,28 @@ impl BaseChannel for DiscordChannel {
         } else {
             serenity::model::id::ChannelId::new(id_val)
         };
-        let chunks = split_message(&msg.content, 2000);
+        let chunks = split_message(&msg.content, self.config.max_message_length);
         let embeds = parse_embeds_from_metadata(&msg.metadata);
         let components = parse_components_from_metadata(&msg.metadata, Some(&self.dispatch_store));
+        let mut reply_ref = reply_reference(target, msg.reply_to.as_deref());
         let chunk_count = chunks.len();
         let mut last_id = None;
         for (i, chunk) in chunks.iter().enumerate() {
             let is_last = i == chunk_count - 1;
-            let sent = if is_last && (!embeds.is_empty() || !components.is_empty()) {
+            let reference = reply_ref.take();
+            let has_extras = is_last && (!embeds.is_empty() || !components.is_empty());
+            let sent = if has_extras || reference.is_some() {
                 let mut builder = CreateMessage::new().content(chunk);
-                for embed in &embeds {
-                    builder = builder.embed(embed.clone());
+                if let Some(r) = reference {
+                    builder = builder.reference_message(r);
                 }
-                if !components.is_empty() {
-                    builder = builder.components(components.clone());
+                if has_extras {
+                    for embed in &embeds {
+                        builder = builder.embed(embed.clone());
+                    }
+                    if !components.is_empty() {
+                        builder = builder.components(components.clone());
+                    }
                 }
                 target
                     .send_message(&self.serenity_http, builder)
@@ -961,6 +1016,21 @@ impl BaseChannel for DiscordChannel {
             .map_err(|e| anyhow::anyhow!("Failed to delete Discord message: {e}"))?;
         Ok(())
     }
+
+    async fn react(&self, chat_id: &str, message_id: &str, emoji: &str) -> Result<()> {
+        let channel_id = chat_id.parse::<u64>()?;
+        let msg_id = message_id.parse::<u64>()?;
+        let channel = serenity::model::id::ChannelId::new(channel_id);
+        channel
+            .create_reaction(
+                &self.serenity_http,
+                serenity::model::id::MessageId::new(msg_id),
+                serenity::model::channel::ReactionType::Unicode(emoji.to_string()),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to react to Discord message: {e}"))?;
+        Ok(())
+    }
 }
 
 impl DiscordChannel {
@@ -970,7 +1040,7 @@ impl DiscordChannel {
         app_id: &str,
         token: &str,
     ) -> Result<()> {
-        let chunks = split_message(&msg.content, 2000);
+        let chunks = split_message(&msg.content, self.config.max_message_length);
         let embeds = parse_embeds_from_metadata(&msg.metadata);
         let components = parse_components_from_metadata(&msg.metadata, Some(&self.dispatch_store));
         let api_components = components_to_api_json(&msg.metadata);
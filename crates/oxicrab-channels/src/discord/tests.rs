@@ -216,3 +216,17 @@ fn test_components_to_api_json_unified() {
     assert_eq!(buttons[1]["custom_id"], "no");
     assert_eq!(buttons[1]["style"], 4); // danger = 4
 }
+
+#[test]
+fn test_reply_reference_parses_snowflake() {
+    let channel = serenity::model::id::ChannelId::new(42);
+    let reference = reply_reference(channel, Some("123456789")).unwrap();
+    assert_eq!(reference.channel_id, channel);
+    assert_eq!(
+        reference.message_id,
+        Some(serenity::model::id::MessageId::new(123_456_789))
+    );
+    assert!(reply_reference(channel, None).is_none());
+    assert!(reply_reference(channel, Some("not-a-number")).is_none());
+    assert!(reply_reference(channel, Some("0")).is_none());
+}
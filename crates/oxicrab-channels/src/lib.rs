@@ -32,8 +32,32 @@ pub trait PairingRequester: Send + Sync {
     /// Request a pairing code for a sender on a channel.
     /// Returns `Some(code)` if a new code was issued, `None` if rate-limited or failed.
     fn request_pairing(&self, channel: &str, sender_id: &str) -> Option<String>;
+
+    /// Forward an unknown sender's pairing request and first message to the
+    /// admin. Returns `false` if no admin target is configured or delivery failed.
+    fn notify_admin(&self, _channel: &str, _sender_id: &str, _code: &str, _message: &str) -> bool {
+        false
+    }
+
+    /// Approve (or deny) the pairing code of an admin notice button clicked
+    /// by `sender_id` in `channel:chat_id`. Returns the outcome to show on the
+    /// notice, or `None` if that chat is not the admin target.
+    fn resolve_from_admin(
+        &self,
+        _channel: &str,
+        _chat_id: &str,
+        _sender_id: &str,
+        _code: &str,
+        _approve: bool,
+    ) -> Option<String> {
+        None
+    }
 }
 
+/// Button action IDs on admin pairing notices; the button context is the code.
+pub const PAIRING_APPROVE_ACTION: &str = "pairing_approve";
+pub const PAIRING_DENY_ACTION: &str = "pairing_deny";
+
 /// Global pairing requester, set by the main crate at startup.
 static PAIRING_REQUESTER: std::sync::OnceLock<Box<dyn PairingRequester>> =
     std::sync::OnceLock::new();
@@ -48,3 +72,33 @@ pub fn set_pairing_requester(requester: Box<dyn PairingRequester>) {
 pub fn get_pairing_requester() -> Option<&'static dyn PairingRequester> {
     PAIRING_REQUESTER.get().map(AsRef::as_ref)
 }
+
+/// Last observed health of each running channel, keyed by channel name.
+/// Maintained by `ChannelManager` (start, supervisor checks, stop) so that
+/// diagnostics outside the gateway can report live channel state.
+static CHANNEL_HEALTH: std::sync::LazyLock<
+    std::sync::Mutex<std::collections::BTreeMap<String, bool>>,
+> = std::sync::LazyLock::new(Default::default);
+
+/// Snapshot of channel health: `true` = running, `false` = down.
+/// Empty when no channels have been started in this process.
+pub fn channel_health() -> std::collections::BTreeMap<String, bool> {
+    lock_channel_health().clone()
+}
+
+pub(crate) fn record_channel_health(name: &str, healthy: bool) {
+    lock_channel_health().insert(name.to_string(), healthy);
+}
+
+pub(crate) fn forget_channel_health(name: &str) {
+    lock_channel_health().remove(name);
+}
+
+/// Each update is a single map operation, so a panic while holding the lock
+/// cannot leave the map inconsistent.
+fn lock_channel_health() -> std::sync::MutexGuard<'static, std::collections::BTreeMap<String, bool>>
+{
+    CHANNEL_HEALTH
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
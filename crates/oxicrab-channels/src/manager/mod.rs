@@ -11,7 +11,7 @@ use crate::whatsapp::WhatsAppChannel;
 use anyhow::Result;
 use oxicrab_core::bus::events::{InboundMessage, OutboundMessage};
 use oxicrab_core::channels::base::BaseChannel;
-use oxicrab_core::config::schema::Config;
+use oxicrab_core::config::schema::{Config, SendRetryConfig};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
@@ -20,6 +20,7 @@ pub struct ChannelManager {
     channels: Vec<Box<dyn BaseChannel>>,
     enabled_channels: Vec<String>,
     supervisor_handle: Option<tokio::task::JoinHandle<()>>,
+    send_retry: SendRetryConfig,
 }
 
 impl ChannelManager {
@@ -123,6 +124,7 @@ impl ChannelManager {
             channels,
             enabled_channels: enabled,
             supervisor_handle: None,
+            send_retry: config.channels.send_retry.clone(),
         }
     }
 
@@ -133,6 +135,7 @@ impl ChannelManager {
             channels,
             enabled_channels: enabled,
             supervisor_handle: None,
+            send_retry: SendRetryConfig::default(),
         }
     }
 
@@ -165,10 +168,12 @@ impl ChannelManager {
                 Ok((name, channel, result)) => match result {
                     Ok(()) => {
                         info!("channel {} started successfully", name);
+                        crate::record_channel_health(&name, true);
                         started.push(channel);
                     }
                     Err(e) => {
                         error!("failed to start channel {}: {}", name, e);
+                        crate::record_channel_health(&name, false);
                         failed = Some((name, e));
                         // Don't break — let other handles complete to avoid orphaned tasks
                     }
@@ -201,7 +206,9 @@ impl ChannelManager {
     pub async fn check_and_restart_unhealthy(&mut self) -> usize {
         let mut restarted = 0;
         for channel in &mut self.channels {
-            if !channel.is_healthy().await {
+            if channel.is_healthy().await {
+                crate::record_channel_health(channel.name(), true);
+            } else {
                 let name = channel.name().to_string();
                 warn!("channel {} is unhealthy, attempting restart", name);
 
@@ -214,9 +221,11 @@ impl ChannelManager {
                 match channel.start().await {
                     Ok(()) => {
                         info!("channel {} restarted successfully", name);
+                        crate::record_channel_health(&name, true);
                         restarted += 1;
                     }
                     Err(e) => {
+                        crate::record_channel_health(&name, false);
                         error!(
                             "failed to restart channel {}: {} (will retry on next check)",
                             name, e
@@ -233,6 +242,7 @@ impl ChannelManager {
             handle.abort();
         }
         for channel in &mut self.channels {
+            crate::forget_channel_health(channel.name());
             if let Err(e) = channel.stop().await {
                 tracing::warn!("error stopping channel {}: {}", channel.name(), e);
             }
@@ -250,7 +260,7 @@ impl ChannelManager {
         for channel in &self.channels {
             if channel.name() == msg.channel {
                 info!("Found matching channel: {}", channel.name());
-                let max_attempts = 3;
+                let max_attempts = self.send_retry.max_attempts.max(1);
                 let mut last_err = None;
                 for attempt in 1..=max_attempts {
                     match channel.send(msg).await {
@@ -264,10 +274,7 @@ impl ChannelManager {
                                     "Send to {} failed (attempt {}/{}): {}, retrying...",
                                     msg.channel, attempt, max_attempts, e
                                 );
-                                tokio::time::sleep(tokio::time::Duration::from_secs(
-                                    attempt as u64,
-                                ))
-                                .await;
+                                tokio::time::sleep(self.send_retry.delay_after(attempt)).await;
                             } else if !is_retryable_channel_error(&e) {
                                 return Err(anyhow::anyhow!(
                                     "Failed to send message to {} channel: {}",
@@ -308,6 +315,13 @@ impl ChannelManager {
         }
     }
 
+    /// Whether `channel` opted in to streamed replies.
+    pub fn supports_streaming(&self, channel: &str) -> bool {
+        self.channels
+            .iter()
+            .any(|ch| ch.name() == channel && ch.supports_streaming())
+    }
+
     pub async fn send_and_get_id(&self, msg: &OutboundMessage) -> Result<Option<String>> {
         for channel in &self.channels {
             if channel.name() == msg.channel {
@@ -345,6 +359,21 @@ impl ChannelManager {
         }
         Ok(())
     }
+
+    pub async fn react(
+        &self,
+        channel: &str,
+        chat_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> Result<()> {
+        for ch in &self.channels {
+            if ch.name() == channel {
+                return ch.react(chat_id, message_id, emoji).await;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Heuristic check for non-retryable channel errors.
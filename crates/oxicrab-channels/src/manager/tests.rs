@@ -177,6 +177,29 @@ async fn test_send_exhausts_retries() {
     assert!(result.unwrap_err().to_string().contains("after 3 attempts"));
 }
 
+#[tokio::test]
+async fn test_send_uses_configured_retry_policy() {
+    let channel = MockChannel::new("test", 4);
+    let send_attempts = channel.send_attempts.clone();
+    let mut mgr = ChannelManager::with_channels(vec![Box::new(channel)]);
+    mgr.send_retry = SendRetryConfig {
+        max_attempts: 5,
+        initial_delay_ms: 1,
+        max_delay_ms: 2,
+    };
+    assert!(mgr.send(&make_outbound("test")).await.is_ok());
+    assert_eq!(send_attempts.load(Ordering::SeqCst), 5);
+
+    // A single attempt disables retry
+    let channel = MockChannel::new("test", 1);
+    let send_attempts = channel.send_attempts.clone();
+    let mut mgr = ChannelManager::with_channels(vec![Box::new(channel)]);
+    mgr.send_retry.max_attempts = 1;
+    let result = mgr.send(&make_outbound("test")).await;
+    assert!(result.unwrap_err().to_string().contains("after 1 attempts"));
+    assert_eq!(send_attempts.load(Ordering::SeqCst), 1);
+}
+
 #[tokio::test]
 async fn test_enabled_channels_empty_by_default() {
     let mgr = ChannelManager::with_channels(vec![]);
@@ -488,3 +511,36 @@ fn test_non_retryable_permission() {
     let err = anyhow::anyhow!("Permission denied");
     assert!(!is_retryable_channel_error(&err));
 }
+
+#[tokio::test]
+async fn test_channel_health_tracks_supervisor_state() {
+    let up = SupervisorMockChannel::new("health-up");
+    let down = SupervisorMockChannel::new("health-down").with_start_fails();
+    down.healthy.store(false, Ordering::SeqCst);
+    let mut mgr = ChannelManager::with_channels(vec![Box::new(up), Box::new(down)]);
+
+    mgr.check_and_restart_unhealthy().await;
+    let health = crate::channel_health();
+    assert_eq!(health.get("health-up"), Some(&true));
+    assert_eq!(health.get("health-down"), Some(&false));
+
+    mgr.stop_all().await.unwrap();
+    let health = crate::channel_health();
+    assert!(!health.contains_key("health-up"));
+    assert!(!health.contains_key("health-down"));
+}
+
+#[test]
+fn test_channel_health_survives_poisoned_lock() {
+    let _ = std::thread::spawn(|| {
+        let _guard = crate::CHANNEL_HEALTH.lock().unwrap();
+        panic!("poison the channel health lock");
+    })
+    .join();
+    assert!(crate::CHANNEL_HEALTH.is_poisoned());
+
+    crate::record_channel_health("health-poisoned", true);
+    assert_eq!(crate::channel_health().get("health-poisoned"), Some(&true));
+    crate::forget_channel_health("health-poisoned");
+    assert!(!crate::channel_health().contains_key("health-poisoned"));
+}
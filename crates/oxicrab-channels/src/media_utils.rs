@@ -30,27 +30,3 @@ pub fn safe_filename(name: &str) -> String {
         })
         .collect()
 }
-
-/// Check if bytes start with known image magic bytes.
-pub fn is_image_magic_bytes(data: &[u8]) -> bool {
-    if data.len() < 4 {
-        return false;
-    }
-    // PNG: 89 50 4E 47
-    if data.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
-        return true;
-    }
-    // JPEG: FF D8 FF
-    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
-        return true;
-    }
-    // GIF: GIF87a or GIF89a
-    if data.starts_with(b"GIF8") {
-        return true;
-    }
-    // WebP: RIFF....WEBP
-    if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
-        return true;
-    }
-    false
-}
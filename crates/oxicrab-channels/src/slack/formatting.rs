@@ -160,3 +160,45 @@ fn flush_table(lines: &[&str], out: &mut String) {
         }
     }
 }
+
+/// Slack reaction name for a Unicode emoji, e.g. "✅" → `white_check_mark`.
+/// A name given directly ("tada" or ":tada:") is passed through. Returns
+/// `None` for emoji outside the common set.
+pub(super) fn slack_emoji_name(emoji: &str) -> Option<String> {
+    let name = emoji.trim_matches(':');
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+'))
+    {
+        return Some(name.to_string());
+    }
+    let bare: String = emoji.chars().filter(|&c| c != '\u{fe0f}').collect();
+    let name = match bare.as_str() {
+        "✅" => "white_check_mark",
+        "✔" => "heavy_check_mark",
+        "☑" => "ballot_box_with_check",
+        "❌" => "x",
+        "👍" => "+1",
+        "👎" => "-1",
+        "👌" => "ok_hand",
+        "👏" => "clap",
+        "🙏" => "pray",
+        "👀" => "eyes",
+        "❤" => "heart",
+        "🔥" => "fire",
+        "🎉" => "tada",
+        "💯" => "100",
+        "🚀" => "rocket",
+        "⭐" => "star",
+        "😂" => "joy",
+        "😊" => "blush",
+        "🤔" => "thinking_face",
+        "⚠" => "warning",
+        "📝" => "memo",
+        "🧠" => "brain",
+        "📌" => "pushpin",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
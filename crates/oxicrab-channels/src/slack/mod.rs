@@ -12,12 +12,16 @@ use oxicrab_core::config::schema::SlackConfig;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 mod formatting;
 
 const MAX_USER_CACHE: usize = 1000;
+/// Hard cap on remembered message ids so a flood inside one dedup window
+/// cannot grow the set without bound.
+const MAX_SEEN_MESSAGES: usize = 50_000;
 
 /// Subtypes to ignore when processing Slack message events.
 /// Unknown subtypes are allowed through (safe default = process).
@@ -115,7 +119,7 @@ pub struct SlackChannel {
     mention_regex: Arc<tokio::sync::Mutex<Option<regex::Regex>>>,
     running: Arc<tokio::sync::Mutex<bool>>,
     ws_handle: Option<tokio::task::JoinHandle<()>>,
-    seen_messages: Arc<tokio::sync::Mutex<indexmap::IndexSet<String>>>,
+    seen_messages: Arc<tokio::sync::Mutex<indexmap::IndexMap<String, Instant>>>,
     user_cache: Arc<tokio::sync::Mutex<lru::LruCache<String, String>>>,
     client: reqwest::Client,
 }
@@ -129,7 +133,7 @@ impl SlackChannel {
             mention_regex: Arc::new(tokio::sync::Mutex::new(None)),
             running: Arc::new(tokio::sync::Mutex::new(false)),
             ws_handle: None,
-            seen_messages: Arc::new(tokio::sync::Mutex::new(indexmap::IndexSet::new())),
+            seen_messages: Arc::new(tokio::sync::Mutex::new(indexmap::IndexMap::new())),
             user_cache: Arc::new(tokio::sync::Mutex::new(lru::LruCache::new(
                 std::num::NonZeroUsize::new(MAX_USER_CACHE).unwrap(),
             ))),
@@ -437,6 +441,7 @@ impl BaseChannel for SlackChannel {
         let config_allow = self.config.allow_from.clone();
         let config_allow_groups = self.config.allow_groups.clone();
         let dm_policy = self.config.dm_policy.clone();
+        let unknown_sender_policy = self.config.unknown_sender_policy;
         let inbound_tx = self.inbound_tx.clone();
         let bot_user_id = self.bot_user_id.clone();
         let mention_regex = self.mention_regex.clone();
@@ -445,6 +450,7 @@ impl BaseChannel for SlackChannel {
         let ws_client = self.client.clone();
         let running = self.running.clone();
         let thinking_emoji = self.config.thinking_emoji.clone();
+        let dedup_window = Duration::from_secs(self.config.dedup_window_secs);
 
         let ws_task = tokio::spawn(async move {
             use futures_util::StreamExt;
@@ -713,6 +719,7 @@ impl BaseChannel for SlackChannel {
                                                 &config_allow,
                                                 &config_allow_groups,
                                                 &dm_policy,
+                                                unknown_sender_policy,
                                                 &bot_token,
                                                 &ws_client,
                                                 &thinking_emoji,
@@ -747,9 +754,11 @@ impl BaseChannel for SlackChannel {
                                                         &config_allow,
                                                         &config_allow_groups,
                                                         &dm_policy,
+                                                        unknown_sender_policy,
                                                         &bot_token,
                                                         &ws_client,
                                                         &thinking_emoji,
+                                                        dedup_window,
                                                     )
                                                     .await
                                                     {
@@ -874,14 +883,14 @@ impl BaseChannel for SlackChannel {
         let content = Self::format_for_slack(&msg.content);
         let buttons = convert_buttons_to_blocks(&msg.metadata);
 
-        // Split long messages (Slack limit is ~40k but 4000 is more readable)
+        // Split long messages (Slack limit is ~40k; maxMessageLength defaults to 4000 for readability)
         // Thread replies: use reply_to or inbound ts metadata for threading
         let thread_ts = msg.reply_to.as_deref().or_else(|| {
             msg.metadata
                 .get(oxicrab_core::bus::events::meta::TS)
                 .and_then(|v| v.as_str())
         });
-        let chunks = split_message(&content, 4000);
+        let chunks = split_message(&content, self.config.max_message_length);
         let chunk_count = chunks.len();
         for (i, chunk) in chunks.iter().enumerate() {
             let is_last = i == chunk_count - 1;
@@ -1012,6 +1021,10 @@ impl BaseChannel for SlackChannel {
             .map(ToString::to_string))
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     async fn edit_message(&self, chat_id: &str, message_id: &str, content: &str) -> Result<()> {
         let content = Self::format_for_slack(content);
         let mut params = HashMap::new();
@@ -1030,6 +1043,24 @@ impl BaseChannel for SlackChannel {
         self.send_slack_api("chat.delete", &params).await?;
         Ok(())
     }
+
+    async fn react(&self, chat_id: &str, message_id: &str, emoji: &str) -> Result<()> {
+        let name = formatting::slack_emoji_name(emoji)
+            .ok_or_else(|| anyhow::anyhow!("no Slack reaction name for {emoji}"))?;
+        let mut params = HashMap::new();
+        params.insert("channel", Value::String(chat_id.to_string()));
+        params.insert("timestamp", Value::String(message_id.to_string()));
+        // The agent's reaction replaces the thinking reaction added on receipt
+        params.insert("name", Value::String(self.config.thinking_emoji.clone()));
+        if let Err(e) = self.send_slack_api("reactions.remove", &params).await {
+            debug!("slack: failed to remove thinking reaction: {}", e);
+        }
+        params.insert("name", Value::String(name));
+        match self.send_slack_api("reactions.add", &params).await {
+            Err(e) if !e.to_string().contains("already_reacted") => Err(e),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Check if a URL belongs to a Slack-owned domain.
@@ -1163,8 +1194,7 @@ fn resolve_slack_redirect(location: &str) -> String {
     location.to_string()
 }
 
-/// Check if bytes start with known image magic bytes.
-use crate::media_utils::is_image_magic_bytes;
+use oxicrab_core::utils::media::detect_media_kind;
 
 /// Convert unified `metadata["buttons"]` to Slack Block Kit action blocks.
 ///
@@ -1230,6 +1260,7 @@ async fn handle_interactive_payload(
     allow_from: &oxicrab_core::config::schema::DenyByDefaultList,
     allow_groups: &oxicrab_core::config::schema::DenyByDefaultList,
     dm_policy: &oxicrab_core::config::schema::DmPolicy,
+    unknown_sender_policy: Option<oxicrab_core::config::schema::UnknownSenderPolicy>,
     bot_token: &str,
     client: &reqwest::Client,
     thinking_emoji: &str,
@@ -1265,7 +1296,14 @@ async fn handle_interactive_payload(
         return Ok(());
     }
     if is_dm {
-        match check_dm_access(user_id, allow_from, "slack", dm_policy) {
+        match check_dm_access(
+            user_id,
+            allow_from,
+            "slack",
+            dm_policy,
+            unknown_sender_policy,
+            "",
+        ) {
             DmCheckResult::Allowed => {}
             DmCheckResult::PairingRequired { code } => {
                 let reply = format_pairing_reply("slack", user_id, &code);
@@ -1318,10 +1356,15 @@ async fn handle_interactive_payload(
         builder = builder.meta("button_context", Value::String(action_value.to_string()));
     }
     if !message_ts.is_empty() {
-        builder = builder.meta(
-            oxicrab_core::bus::events::meta::TS,
-            Value::String(message_ts.to_string()),
-        );
+        builder = builder
+            .meta(
+                oxicrab_core::bus::events::meta::TS,
+                Value::String(message_ts.to_string()),
+            )
+            .meta(
+                oxicrab_core::bus::events::meta::MESSAGE_ID,
+                Value::String(message_ts.to_string()),
+            );
     }
     if let Some(d) = dispatch {
         builder = builder.action(d);
@@ -1358,21 +1401,51 @@ async fn handle_interactive_payload(
     Ok(())
 }
 
+/// Remember `msg_key` as received at `now`, first forgetting ids older than
+/// `window`. Returns `false` if the id was already seen inside the window.
+fn record_seen_message(
+    seen: &mut indexmap::IndexMap<String, Instant>,
+    msg_key: &str,
+    now: Instant,
+    window: Duration,
+) -> bool {
+    // Entries are kept in arrival order, so the expired ones are a prefix
+    let expired = seen
+        .values()
+        .take_while(|received| now.duration_since(**received) > window)
+        .count();
+    if expired > 0 {
+        seen.drain(..expired);
+    }
+    if seen.contains_key(msg_key) {
+        return false;
+    }
+    seen.insert(msg_key.to_string(), now);
+    if seen.len() > MAX_SEEN_MESSAGES {
+        let excess = seen.len() - MAX_SEEN_MESSAGES;
+        seen.drain(..excess);
+        debug!("Pruned Slack dedup set to {} entries", seen.len());
+    }
+    true
+}
+
 /// Standalone message handler that uses shared state instead of constructing a new `SlackChannel`.
 #[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 async fn handle_slack_event(
     event: &Value,
     bot_user_id: &Arc<tokio::sync::Mutex<Option<String>>>,
     mention_regex: &Arc<tokio::sync::Mutex<Option<regex::Regex>>>,
-    seen_messages: &Arc<tokio::sync::Mutex<indexmap::IndexSet<String>>>,
+    seen_messages: &Arc<tokio::sync::Mutex<indexmap::IndexMap<String, Instant>>>,
     user_cache: &Arc<tokio::sync::Mutex<lru::LruCache<String, String>>>,
     inbound_tx: &Arc<mpsc::Sender<InboundMessage>>,
     allow_from: &oxicrab_core::config::schema::DenyByDefaultList,
     allow_groups: &oxicrab_core::config::schema::DenyByDefaultList,
     dm_policy: &oxicrab_core::config::schema::DmPolicy,
+    unknown_sender_policy: Option<oxicrab_core::config::schema::UnknownSenderPolicy>,
     bot_token: &str,
     client: &reqwest::Client,
     thinking_emoji: &str,
+    dedup_window: Duration,
 ) -> Result<()> {
     // Ignore well-known non-user subtypes. Unknown subtypes pass through (safe default).
     if let Some(subtype) = event.get("subtype").and_then(Value::as_str)
@@ -1407,23 +1480,14 @@ async fn handle_slack_event(
         return Ok(());
     }
 
-    // Deduplicate messages
+    // Deduplicate messages (Slack redelivers events it considers unacknowledged)
     if let Some(ts) = event.get("ts").and_then(Value::as_str) {
         let mut seen = seen_messages.lock().await;
         let msg_key = format!("{channel_id}:{user_id}:{ts}");
-        if seen.contains(&msg_key) {
+        if !record_seen_message(&mut seen, &msg_key, Instant::now(), dedup_window) {
             debug!("Ignoring duplicate Slack message: {}", msg_key);
             return Ok(());
         }
-        seen.insert(msg_key.clone());
-        // Evict oldest entries when set grows too large (keep ~5000 most
-        // recent). Higher retain count reduces the replay window in burst
-        // scenarios where rapid eviction could allow reprocessing.
-        if seen.len() > 5500 {
-            let drain_count = seen.len() - 5000;
-            seen.drain(..drain_count);
-            debug!("Pruned Slack dedup set to {} entries", seen.len());
-        }
     }
 
     info!("Slack: received message from {} in {}", user_id, channel_id);
@@ -1441,7 +1505,17 @@ async fn handle_slack_event(
     }
     // DM access check
     if is_dm {
-        match check_dm_access(user_id, allow_from, "slack", dm_policy) {
+        match check_dm_access(
+            user_id,
+            allow_from,
+            "slack",
+            dm_policy,
+            unknown_sender_policy,
+            event
+                .get("text")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+        ) {
             DmCheckResult::Allowed => {}
             DmCheckResult::PairingRequired { code } => {
                 let reply = format_pairing_reply("slack", user_id, &code);
@@ -1518,18 +1592,10 @@ async fn handle_slack_event(
                         file.get("url_private_download").and_then(Value::as_str),
                         file.get("id").and_then(Value::as_str),
                     ) {
-                        let ext = match mimetype {
-                            "image/jpeg" => ".jpg",
-                            "image/png" => ".png",
-                            "image/gif" => ".gif",
-                            "image/webp" => ".webp",
-                            _ => ".bin",
-                        };
                         let Ok(media_dir) = crate::media_utils::media_dir() else {
                             warn!("Failed to create media directory");
                             continue;
                         };
-                        let file_path = media_dir.join(format!("slack_{file_id}{ext}"));
 
                         // Download with manual redirect following.
                         // Slack redirects through multiple hops (files.slack.com
@@ -1538,14 +1604,19 @@ async fn handle_slack_event(
                         // Slack's ?redir= login-page URLs to direct file paths.
                         match download_slack_file(client, bot_token, file_url).await {
                             Ok(bytes) => {
+                                let kind = detect_media_kind(&bytes);
                                 if bytes.len() > MAX_IMAGE_DOWNLOAD {
                                     warn!(
                                         "Slack file too large ({} bytes, max {}), skipping",
                                         bytes.len(),
                                         MAX_IMAGE_DOWNLOAD
                                     );
-                                } else if is_image_magic_bytes(&bytes) {
+                                } else if let Some(ext) =
+                                    kind.extension().filter(|_| kind.is_image())
+                                {
                                     info!("Downloaded Slack image: {} bytes", bytes.len());
+                                    let file_path =
+                                        media_dir.join(format!("slack_{file_id}.{ext}"));
                                     let fp = file_path.clone();
                                     let b = bytes.clone();
                                     if let Err(e) =
@@ -1560,9 +1631,8 @@ async fn handle_slack_event(
                                     content_parts.push(format!("[image: {path_str}]"));
                                 } else {
                                     warn!(
-                                        "Slack file doesn't look like an image (first bytes: {:02x?}, {} bytes)",
-                                        &bytes[..8.min(bytes.len())],
-                                        bytes.len()
+                                        "Slack file labeled {} is not an image ({:?}), skipping",
+                                        mimetype, kind
                                     );
                                 }
                             }
@@ -1574,19 +1644,10 @@ async fn handle_slack_event(
                         file.get("url_private_download").and_then(Value::as_str),
                         file.get("id").and_then(Value::as_str),
                     ) {
-                        let ext = match mimetype {
-                            "audio/mpeg" => ".mp3",
-                            "audio/wav" => ".wav",
-                            "audio/webm" | "video/webm" => ".webm",
-                            "audio/mp4" | "video/mp4" => ".mp4",
-                            "audio/flac" => ".flac",
-                            _ => ".ogg",
-                        };
                         let Ok(media_dir) = crate::media_utils::media_dir() else {
                             warn!("Failed to create media directory");
                             continue;
                         };
-                        let file_path = media_dir.join(format!("slack_{file_id}{ext}"));
 
                         match download_slack_file(client, bot_token, file_url).await {
                             Ok(bytes) => {
@@ -1598,6 +1659,15 @@ async fn handle_slack_event(
                                     );
                                     continue;
                                 }
+                                let kind = detect_media_kind(&bytes);
+                                let Some(ext) = kind.extension().filter(|_| kind.is_audio()) else {
+                                    warn!(
+                                        "Slack file labeled {} is not audio ({:?}), skipping",
+                                        mimetype, kind
+                                    );
+                                    continue;
+                                };
+                                let file_path = media_dir.join(format!("slack_{file_id}.{ext}"));
                                 info!("Downloaded Slack audio: {} bytes", bytes.len());
                                 let fp = file_path.clone();
                                 let b = bytes.clone();
@@ -1659,7 +1729,10 @@ async fn handle_slack_event(
         .meta("user_id", Value::String(user_id.to_string()))
         .is_group(is_group);
     if let Some(ts) = event.get("ts").and_then(Value::as_str) {
-        builder = builder.meta("ts", Value::String(ts.to_string()));
+        builder = builder.meta("ts", Value::String(ts.to_string())).meta(
+            oxicrab_core::bus::events::meta::MESSAGE_ID,
+            Value::String(ts.to_string()),
+        );
     }
     let inbound_msg = builder.build();
 
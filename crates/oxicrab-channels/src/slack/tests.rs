@@ -39,8 +39,6 @@ fn test_resolve_slack_redirect() {
     );
 }
 
-// --- is_image_magic_bytes tests ---
-
 // --- download_slack_file tests (wiremock) ---
 
 #[tokio::test]
@@ -545,6 +543,54 @@ fn test_mention_regex_pattern() {
     assert!(!re.is_match("<@U99999>"));
 }
 
+// --- record_seen_message tests ---
+
+#[test]
+fn test_record_seen_message_evicts_by_age() {
+    let window = Duration::from_secs(3600);
+    let start = Instant::now();
+    let mut seen = indexmap::IndexMap::new();
+
+    assert!(record_seen_message(&mut seen, "C1:U1:1.0", start, window));
+    assert!(record_seen_message(
+        &mut seen,
+        "C1:U1:2.0",
+        start + Duration::from_secs(1800),
+        window
+    ));
+    // A retry inside the window is a duplicate, however quiet the channel is
+    assert!(!record_seen_message(
+        &mut seen,
+        "C1:U1:1.0",
+        start + Duration::from_secs(3500),
+        window
+    ));
+
+    // Past the window the first id is forgotten, the second is still kept
+    let later = start + Duration::from_secs(3700);
+    assert!(record_seen_message(&mut seen, "C1:U1:1.0", later, window));
+    assert!(!record_seen_message(&mut seen, "C1:U1:2.0", later, window));
+    assert_eq!(seen.len(), 2);
+}
+
+#[test]
+fn test_record_seen_message_burst_keeps_recent_ids() {
+    let window = Duration::from_secs(3600);
+    let now = Instant::now();
+    let mut seen = indexmap::IndexMap::new();
+    for i in 0..10_000 {
+        assert!(record_seen_message(
+            &mut seen,
+            &format!("C1:U1:{i}"),
+            now,
+            window
+        ));
+    }
+    // The old count-based pruning (keep ~5000) would have forgotten id 0
+    assert!(!record_seen_message(&mut seen, "C1:U1:0", now, window));
+    assert_eq!(seen.len(), 10_000);
+}
+
 // --- classify_slack_error tests ---
 
 #[test]
@@ -745,3 +791,13 @@ fn test_is_slack_domain() {
     assert!(!is_slack_domain("https://attacker.com/slack.com"));
     assert!(!is_slack_domain("not-a-url"));
 }
+
+#[test]
+fn test_slack_emoji_name() {
+    use super::formatting::slack_emoji_name;
+    assert_eq!(slack_emoji_name("✅").as_deref(), Some("white_check_mark"));
+    assert_eq!(slack_emoji_name("❤\u{fe0f}").as_deref(), Some("heart"));
+    assert_eq!(slack_emoji_name("👍").as_deref(), Some("+1"));
+    assert_eq!(slack_emoji_name(":tada:").as_deref(), Some("tada"));
+    assert_eq!(slack_emoji_name("🦀"), None);
+}
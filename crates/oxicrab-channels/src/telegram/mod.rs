@@ -174,6 +174,7 @@ impl BaseChannel for TelegramChannel {
         let allow_list = self.config.allow_from.clone();
         let allow_groups = self.config.allow_groups.clone();
         let dm_policy = self.config.dm_policy.clone();
+        let unknown_sender_policy = self.config.unknown_sender_policy;
         let mention_only = self.config.mention_only;
         let running = self.running.clone();
 
@@ -239,6 +240,7 @@ impl BaseChannel for TelegramChannel {
                                 &allow_list,
                                 &allow_groups,
                                 &dm_policy,
+                                unknown_sender_policy,
                                 mention_only,
                                 &bot_username,
                                 &bot_user_id,
@@ -355,9 +357,9 @@ impl BaseChannel for TelegramChannel {
 
         // Fix #7: convert markdown to HTML first, THEN split
         let html_content = markdown_to_telegram_html(&msg.content);
-        let html_chunks = split_message(&html_content, 4096);
+        let html_chunks = split_message(&html_content, self.config.max_message_length);
         // Also split raw content for fallback (matched by index)
-        let raw_chunks = split_message(&msg.content, 4096);
+        let raw_chunks = split_message(&msg.content, self.config.max_message_length);
 
         // Fix #1: build inline keyboard from unified button metadata
         let keyboard = build_inline_keyboard(msg, Some(&self.dispatch_store));
@@ -400,8 +402,8 @@ impl BaseChannel for TelegramChannel {
 
         // Fix #7: convert then split
         let html_content = markdown_to_telegram_html(&msg.content);
-        let html_chunks = split_message(&html_content, 4096);
-        let raw_chunks = split_message(&msg.content, 4096);
+        let html_chunks = split_message(&html_content, self.config.max_message_length);
+        let raw_chunks = split_message(&msg.content, self.config.max_message_length);
 
         let keyboard = build_inline_keyboard(msg, Some(&self.dispatch_store));
 
@@ -425,6 +427,10 @@ impl BaseChannel for TelegramChannel {
         Ok(last_id)
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     async fn edit_message(&self, chat_id: &str, message_id: &str, content: &str) -> Result<()> {
         let chat_id = chat_id.parse::<i64>()?;
         let msg_id = message_id.parse::<i32>()?;
@@ -454,6 +460,29 @@ impl BaseChannel for TelegramChannel {
             .await?;
         Ok(())
     }
+
+    async fn react(&self, chat_id: &str, message_id: &str, emoji: &str) -> Result<()> {
+        let chat_id = chat_id.parse::<i64>()?;
+        let msg_id = message_id.parse::<i32>()?;
+        self.bot
+            .set_message_reaction(ChatId(chat_id), teloxide::types::MessageId(msg_id))
+            .reaction(vec![teloxide::types::ReactionType::Emoji {
+                emoji: telegram_reaction_emoji(emoji),
+            }])
+            .await?;
+        Ok(())
+    }
+}
+
+/// Adapt an emoji to Telegram's fixed reaction set: variation selectors are
+/// dropped ("❤️" is listed as "❤") and check marks, which are not in the set,
+/// become a thumbs up.
+fn telegram_reaction_emoji(emoji: &str) -> String {
+    let bare: String = emoji.chars().filter(|&c| c != '\u{fe0f}').collect();
+    match bare.as_str() {
+        "✅" | "✔" | "☑" => "👍".to_string(),
+        _ => bare,
+    }
 }
 
 /// Send media attachments (photos, documents) for an outbound message.
@@ -499,6 +528,7 @@ async fn handle_message(
     allow_list: &oxicrab_core::config::schema::DenyByDefaultList,
     allow_groups: &oxicrab_core::config::schema::DenyByDefaultList,
     dm_policy: &oxicrab_core::config::schema::DmPolicy,
+    unknown_sender_policy: Option<oxicrab_core::config::schema::UnknownSenderPolicy>,
     mention_only: bool,
     bot_username: &Arc<tokio::sync::Mutex<Option<String>>>,
     bot_user_id: &Arc<tokio::sync::Mutex<Option<u64>>>,
@@ -532,7 +562,14 @@ async fn handle_message(
 
     // DM access check (skipped for group messages)
     if !is_group {
-        match check_dm_access(&sender_id, allow_list, "telegram", dm_policy) {
+        match check_dm_access(
+            &sender_id,
+            allow_list,
+            "telegram",
+            dm_policy,
+            unknown_sender_policy,
+            msg.text().or(msg.caption()).unwrap_or_default(),
+        ) {
             DmCheckResult::Allowed => {}
             DmCheckResult::PairingRequired { code } => {
                 let reply = format_pairing_reply("telegram", &sender_id, &code);
@@ -562,6 +599,10 @@ async fn handle_message(
             .media(media)
             .is_group(is_group)
             .meta(meta::TS, serde_json::Value::String(msg.id.0.to_string()))
+            .meta(
+                meta::MESSAGE_ID,
+                serde_json::Value::String(msg.id.0.to_string()),
+            )
     };
 
     // Handle photos
@@ -806,6 +847,25 @@ async fn handle_message(
     Ok(())
 }
 
+/// Whether `action_id` is the Approve (`true`) or Deny (`false`) button of
+/// an admin pairing notice.
+fn pairing_button_decision(action_id: &str) -> Option<bool> {
+    match action_id {
+        crate::PAIRING_APPROVE_ACTION => Some(true),
+        crate::PAIRING_DENY_ACTION => Some(false),
+        _ => None,
+    }
+}
+
+/// Text of a pairing notice once its buttons were used.
+fn resolved_notice_text(original: &str, outcome: &str) -> String {
+    if original.is_empty() {
+        outcome.to_string()
+    } else {
+        format!("{original}\n\n{outcome}")
+    }
+}
+
 /// Handle callback queries from inline keyboard button clicks.
 async fn handle_callback_query(
     bot: Bot,
@@ -841,7 +901,15 @@ async fn handle_callback_query(
         return Ok(());
     }
     if !is_group {
-        match check_dm_access(&sender_id, allow_list, "telegram", dm_policy) {
+        // Button clicks never start pairing: unknown senders are ignored here
+        match check_dm_access(
+            &sender_id,
+            allow_list,
+            "telegram",
+            dm_policy,
+            Some(oxicrab_core::config::schema::UnknownSenderPolicy::Ignore),
+            "",
+        ) {
             DmCheckResult::Allowed => {}
             DmCheckResult::PairingRequired { .. } | DmCheckResult::Denied => {
                 let _ = bot.answer_callback_query(q.id.clone()).await;
@@ -855,6 +923,41 @@ async fn handle_callback_query(
         .split_once('|')
         .map_or((callback_data, ""), |(id, ctx)| (id, ctx));
 
+    // Approve/Deny on an admin pairing notice: resolved here, never reaches the agent
+    if let Some(approve) = pairing_button_decision(action_id) {
+        let _ = bot.answer_callback_query(q.id.clone()).await;
+        let outcome = crate::get_pairing_requester().and_then(|requester| {
+            requester.resolve_from_admin(
+                "telegram",
+                &chat_id.to_string(),
+                &sender_id,
+                context_str,
+                approve,
+            )
+        });
+        let Some(outcome) = outcome else {
+            debug!("telegram: ignoring pairing button outside the admin chat {chat_id}");
+            return Ok(());
+        };
+        let original = message
+            .regular_message()
+            .and_then(TgMessage::text)
+            .unwrap_or_default();
+        // Editing without a reply markup also removes the buttons
+        if let Err(e) = bot
+            .edit_message_text(
+                chat_id,
+                message.id(),
+                resolved_notice_text(original, &outcome),
+            )
+            .await
+        {
+            warn!("failed to update Telegram pairing notice: {}", e);
+        }
+        info!("telegram: pairing button {action_id} from user={sender_id} in chat={chat_id}");
+        return Ok(());
+    }
+
     // Try to parse context as ActionDispatchPayload for direct dispatch
     let (content, dispatch) = if context_str.is_empty() {
         // No inline context — check dispatch store (used when context exceeded 64 bytes)
@@ -183,3 +183,33 @@ fn test_build_inline_keyboard_truncates_callback_data() {
         panic!("expected CallbackData");
     }
 }
+
+#[test]
+fn test_pairing_button_decision() {
+    assert_eq!(
+        pairing_button_decision(crate::PAIRING_APPROVE_ACTION),
+        Some(true)
+    );
+    assert_eq!(
+        pairing_button_decision(crate::PAIRING_DENY_ACTION),
+        Some(false)
+    );
+    assert_eq!(pairing_button_decision("approve_abc"), None);
+}
+
+#[test]
+fn test_resolved_notice_text_appends_outcome() {
+    assert_eq!(
+        resolved_notice_text("New sender on telegram", "Approved telegram:1."),
+        "New sender on telegram\n\nApproved telegram:1."
+    );
+    assert_eq!(resolved_notice_text("", "Denied."), "Denied.");
+}
+
+#[test]
+fn test_telegram_reaction_emoji() {
+    assert_eq!(telegram_reaction_emoji("✅"), "👍");
+    assert_eq!(telegram_reaction_emoji("✔\u{fe0f}"), "👍");
+    assert_eq!(telegram_reaction_emoji("❤\u{fe0f}"), "❤");
+    assert_eq!(telegram_reaction_emoji("🎉"), "🎉");
+}
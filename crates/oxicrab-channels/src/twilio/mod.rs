@@ -55,6 +55,7 @@ struct WebhookState {
     allow_from: oxicrab_core::config::schema::DenyByDefaultList,
     allow_groups: oxicrab_core::config::schema::DenyByDefaultList,
     dm_policy: oxicrab_core::config::schema::DmPolicy,
+    unknown_sender_policy: Option<oxicrab_core::config::schema::UnknownSenderPolicy>,
     inbound_tx: Arc<mpsc::Sender<InboundMessage>>,
     client: reqwest::Client,
     account_sid: String,
@@ -266,7 +267,14 @@ async fn webhook_handler(
 
     // Check access based on dmPolicy (skip for group messages, consistent with other channels)
     if !is_group {
-        match check_dm_access(&sender, &state.allow_from, "twilio", &state.dm_policy) {
+        match check_dm_access(
+            &sender,
+            &state.allow_from,
+            "twilio",
+            &state.dm_policy,
+            state.unknown_sender_policy,
+            &body_text,
+        ) {
             DmCheckResult::Allowed => {}
             DmCheckResult::PairingRequired { code } => {
                 let reply = format_pairing_reply("twilio", &sender, &code);
@@ -438,6 +446,7 @@ impl BaseChannel for TwilioChannel {
             allow_from: self.config.allow_from.clone(),
             allow_groups: self.config.allow_groups.clone(),
             dm_policy: self.config.dm_policy.clone(),
+            unknown_sender_policy: self.config.unknown_sender_policy,
             inbound_tx: self.inbound_tx.clone(),
             client: self.client.clone(),
             account_sid: self.config.account_sid.clone(),
@@ -508,7 +517,7 @@ impl BaseChannel for TwilioChannel {
             );
         }
 
-        let chunks = split_message(&msg.content, 1600);
+        let chunks = split_message(&msg.content, self.config.max_message_length);
 
         for chunk in chunks {
             if msg.chat_id.starts_with('+') {
@@ -127,11 +127,16 @@ pub enum DmCheckResult {
     PairingRequired { code: String },
 }
 
-/// Check DM access based on the channel's `dmPolicy`.
+/// Check DM access based on the channel's `dmPolicy` and `unknownSenderPolicy`.
 ///
 /// - `"open"` — allow all senders unconditionally
-/// - `"allowlist"` — check config allowFrom + pairing store; silently deny unknown
-/// - `"pairing"` — check config allowFrom + pairing store; issue a pairing code for unknown
+/// - `"allowlist"` / `"pairing"` — check config allowFrom + pairing store
+///
+/// Unknown senders are then handled by the resolved `UnknownSenderPolicy`:
+/// - `ignore` — silently deny
+/// - `instruct` — issue a pairing code for the channel to send back
+/// - `notifyAdmin` — issue a pairing code and forward it with `message` to
+///   the admin; the sender gets no reply
 #[cfg(any(
     feature = "channel-telegram",
     feature = "channel-discord",
@@ -144,8 +149,10 @@ pub fn check_dm_access(
     allow_list: &oxicrab_core::config::schema::DenyByDefaultList,
     channel: &str,
     dm_policy: &oxicrab_core::config::schema::DmPolicy,
+    unknown_sender_policy: Option<oxicrab_core::config::schema::UnknownSenderPolicy>,
+    message: &str,
 ) -> DmCheckResult {
-    use oxicrab_core::config::schema::DmPolicy;
+    use oxicrab_core::config::schema::{DmPolicy, UnknownSenderPolicy};
 
     if *dm_policy == DmPolicy::Open {
         return DmCheckResult::Allowed;
@@ -155,18 +162,32 @@ pub fn check_dm_access(
         return DmCheckResult::Allowed;
     }
 
-    if *dm_policy == DmPolicy::Pairing {
-        if let Some(requester) = crate::get_pairing_requester() {
-            if let Some(code) = requester.request_pairing(channel, sender) {
-                return DmCheckResult::PairingRequired { code };
-            }
-            tracing::debug!("pairing request rate-limited for {} on {}", sender, channel);
-        } else {
-            tracing::debug!("no pairing requester configured, denying {}", sender);
+    let policy = UnknownSenderPolicy::resolve(unknown_sender_policy, dm_policy);
+    if policy == UnknownSenderPolicy::Ignore {
+        return DmCheckResult::Denied;
+    }
+
+    let Some(requester) = crate::get_pairing_requester() else {
+        tracing::debug!("no pairing requester configured, denying {}", sender);
+        return DmCheckResult::Denied;
+    };
+    let Some(code) = requester.request_pairing(channel, sender) else {
+        tracing::debug!("pairing request rate-limited for {} on {}", sender, channel);
+        return DmCheckResult::Denied;
+    };
+
+    if policy == UnknownSenderPolicy::NotifyAdmin {
+        if !requester.notify_admin(channel, sender, &code, message) {
+            tracing::warn!(
+                "could not notify admin about unknown sender {} on {}",
+                sender,
+                channel
+            );
         }
+        return DmCheckResult::Denied;
     }
 
-    DmCheckResult::Denied
+    DmCheckResult::PairingRequired { code }
 }
 
 /// Format a pairing reply message for an unrecognized sender.
@@ -192,7 +192,9 @@ fn test_dm_access_open_allows_all() {
             "anyone",
             &DenyByDefaultList::default(),
             "test",
-            &DmPolicy::Open
+            &DmPolicy::Open,
+            None,
+            ""
         ),
         DmCheckResult::Allowed
     ));
@@ -205,7 +207,9 @@ fn test_dm_access_allowlist_denies_unknown() {
             "unknown",
             &DenyByDefaultList::default(),
             "test",
-            &DmPolicy::Allowlist
+            &DmPolicy::Allowlist,
+            None,
+            ""
         ),
         DmCheckResult::Denied
     ));
@@ -215,7 +219,7 @@ fn test_dm_access_allowlist_denies_unknown() {
 fn test_dm_access_allowlist_allows_known() {
     let l = list(&["alice"]);
     assert!(matches!(
-        check_dm_access("alice", &l, "test", &DmPolicy::Allowlist),
+        check_dm_access("alice", &l, "test", &DmPolicy::Allowlist, None, ""),
         DmCheckResult::Allowed
     ));
 }
@@ -224,7 +228,7 @@ fn test_dm_access_allowlist_allows_known() {
 fn test_dm_access_pairing_allows_known() {
     let l = list(&["bob"]);
     assert!(matches!(
-        check_dm_access("bob", &l, "test", &DmPolicy::Pairing),
+        check_dm_access("bob", &l, "test", &DmPolicy::Pairing, None, ""),
         DmCheckResult::Allowed
     ));
 }
@@ -374,7 +378,9 @@ fn test_dm_access_pairing_denies_without_requester() {
             "unknown",
             &DenyByDefaultList::default(),
             "test",
-            &DmPolicy::Pairing
+            &DmPolicy::Pairing,
+            None,
+            ""
         ),
         DmCheckResult::Denied
     ));
@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use oxicrab_core::bus::events::meta;
 use oxicrab_core::bus::events::{InboundMessage, OutboundMessage};
 use oxicrab_core::channels::base::BaseChannel;
-use oxicrab_core::config::schema::WhatsAppConfig;
+use oxicrab_core::config::schema::{UnknownSenderPolicy, WhatsAppConfig};
 use serde_json::Value;
 use std::collections::VecDeque;
 use std::path::PathBuf;
@@ -21,8 +21,92 @@ use whatsapp_rust::proto_helpers::MessageExt;
 
 /// WhatsApp-specific metadata key for the original message timestamp (millis).
 const META_WHATSAPP_TIMESTAMP: &str = "whatsapp_timestamp";
-/// WhatsApp-specific metadata key for the provider message ID.
-const META_MESSAGE_ID: &str = "message_id";
+
+/// Most outbound messages held while the client is disconnected.
+const MAX_QUEUED_MESSAGES: usize = 1000;
+
+/// Outbound messages held while the client is disconnected, sent in order
+/// once it reconnects.
+#[derive(Default)]
+struct OutboundQueue {
+    messages: VecDeque<OutboundMessage>,
+}
+
+impl OutboundQueue {
+    /// Queue `msg` unless an identical message is already waiting. When
+    /// full, the oldest message is dropped.
+    fn push(&mut self, msg: &OutboundMessage) {
+        let duplicate = self.messages.iter().any(|q| {
+            q.chat_id == msg.chat_id
+                && q.content == msg.content
+                && q.media == msg.media
+                && q.reply_to == msg.reply_to
+        });
+        if duplicate {
+            debug!(
+                "whatsapp: identical message already queued for {}",
+                msg.chat_id
+            );
+            return;
+        }
+        if self.messages.len() >= MAX_QUEUED_MESSAGES
+            && let Some(dropped) = self.messages.pop_front()
+        {
+            warn!(
+                "whatsapp: message queue full ({MAX_QUEUED_MESSAGES}), dropping oldest message to {}",
+                dropped.chat_id
+            );
+        }
+        self.messages.push_back(msg.clone());
+        debug!(
+            "whatsapp: queued message (queue size: {})",
+            self.messages.len()
+        );
+    }
+
+    fn take_all(&mut self) -> Vec<OutboundMessage> {
+        self.messages.drain(..).collect()
+    }
+
+    /// Put unsent messages back at the front, ahead of anything queued since.
+    fn requeue_front(&mut self, unsent: Vec<OutboundMessage>) {
+        for msg in unsent.into_iter().rev() {
+            self.messages.push_front(msg);
+        }
+        while self.messages.len() > MAX_QUEUED_MESSAGES {
+            if let Some(dropped) = self.messages.pop_back() {
+                warn!(
+                    "whatsapp: message queue full ({MAX_QUEUED_MESSAGES}), dropping newest message to {}",
+                    dropped.chat_id
+                );
+            }
+        }
+    }
+}
+
+/// Send every queued message in order. Stops at the first failure and
+/// requeues it with the rest, so nothing is lost or reordered.
+async fn drain_queue(
+    client: &Arc<whatsapp_rust::client::Client>,
+    queue: &tokio::sync::Mutex<OutboundQueue>,
+    max_message_length: usize,
+) {
+    let queued = queue.lock().await.take_all();
+    if queued.is_empty() {
+        return;
+    }
+    info!("Processing {} queued WhatsApp messages", queued.len());
+    let mut pending = queued.into_iter();
+    while let Some(msg) = pending.next() {
+        if let Err(e) = Box::pin(send_whatsapp_message(client, &msg, max_message_length)).await {
+            error!("Failed to send queued WhatsApp message: {}", e);
+            let mut unsent = vec![msg];
+            unsent.extend(pending);
+            queue.lock().await.requeue_front(unsent);
+            return;
+        }
+    }
+}
 
 /// Normalize a JID by stripping the device suffix and ensuring
 /// a domain part is present.
@@ -52,7 +136,7 @@ pub struct WhatsAppChannel {
     running: Arc<tokio::sync::Mutex<bool>>,
     session_path: PathBuf,
     client: Arc<tokio::sync::Mutex<Option<Arc<whatsapp_rust::client::Client>>>>,
-    message_queue: Arc<tokio::sync::Mutex<VecDeque<OutboundMessage>>>,
+    message_queue: Arc<tokio::sync::Mutex<OutboundQueue>>,
 }
 
 impl WhatsAppChannel {
@@ -70,7 +154,7 @@ impl WhatsAppChannel {
             running: Arc::new(tokio::sync::Mutex::new(false)),
             session_path,
             client: Arc::new(tokio::sync::Mutex::new(None)),
-            message_queue: Arc::new(tokio::sync::Mutex::new(VecDeque::new())),
+            message_queue: Arc::new(tokio::sync::Mutex::new(OutboundQueue::default())),
         }
     }
 }
@@ -114,7 +198,10 @@ impl BaseChannel for WhatsAppChannel {
         let config_allow = self.config.allow_from.clone();
         let config_allow_groups = self.config.allow_groups.clone();
         let dm_policy = self.config.dm_policy.clone();
+        let unknown_sender_policy = self.config.unknown_sender_policy;
         let client_for_storage = self.client.clone();
+        let message_queue = self.message_queue.clone();
+        let max_message_length = self.config.max_message_length;
 
         *self.running.lock().await = true;
 
@@ -151,6 +238,7 @@ impl BaseChannel for WhatsAppChannel {
                 let config_allow_groups_clone = config_allow_groups.clone();
                 let dm_policy_clone = dm_policy.clone();
                 let client_storage_clone = client_for_storage.clone();
+                let message_queue_clone = message_queue.clone();
 
                 let bot_builder = whatsapp_rust::bot::Bot::builder()
                     .with_backend(backend.clone())
@@ -164,13 +252,8 @@ impl BaseChannel for WhatsAppChannel {
                         let config_allow_groups = config_allow_groups_clone.clone();
                         let dm_policy = dm_policy_clone.clone();
                         let client_storage = client_storage_clone.clone();
+                        let message_queue = message_queue_clone.clone();
                         async move {
-                            // Store client for sending messages
-                            {
-                                let mut client_guard = client_storage.lock().await;
-                                *client_guard = Some(client.clone());
-                            }
-
                             // Process events
                             debug!("WhatsApp event received: type={:?}", std::mem::discriminant(&event));
                             match &event {
@@ -228,12 +311,17 @@ impl BaseChannel for WhatsAppChannel {
 
                                     // DM access check (skipped for group messages, consistent with other channels)
                                     if !is_group {
-                                        // Check access based on dmPolicy — try phone number first, then raw sender
-                                        let access = check_dm_access(&phone_number, &config_allow, "whatsapp", &dm_policy);
-                                        let access = if matches!(access, DmCheckResult::Allowed | DmCheckResult::PairingRequired { .. }) {
-                                            access
+                                        // Check access based on dmPolicy — try phone number first, then raw sender.
+                                        // The unknown-sender policy only runs once both identities are unknown.
+                                        let known = matches!(
+                                            check_dm_access(&phone_number, &config_allow, "whatsapp", &dm_policy, Some(UnknownSenderPolicy::Ignore), ""),
+                                            DmCheckResult::Allowed
+                                        ) || check_allowed_sender(&sender_jid, &config_allow, "whatsapp");
+                                        let access = if known {
+                                            DmCheckResult::Allowed
                                         } else {
-                                            check_dm_access(&sender_jid, &config_allow, "whatsapp", &dm_policy)
+                                            let text = msg.get_base_message().conversation.as_deref().unwrap_or_default();
+                                            check_dm_access(&phone_number, &config_allow, "whatsapp", &dm_policy, unknown_sender_policy, text)
                                         };
                                         match access {
                                             DmCheckResult::Allowed => {}
@@ -347,7 +435,7 @@ impl BaseChannel for WhatsAppChannel {
 
                                     let inbound_msg = InboundMessage::builder("whatsapp", phone_number, session_chat_id, content)
                                         .media(media_paths)
-                                        .meta(META_MESSAGE_ID, Value::String(info.id.clone()))
+                                        .meta(meta::MESSAGE_ID, Value::String(info.id.clone()))
                                         .meta(META_WHATSAPP_TIMESTAMP, Value::Number(serde_json::Number::from(info.timestamp.timestamp_millis())))
                                         .meta(meta::TS, Value::String(info.timestamp.timestamp_millis().to_string()))
                                         .is_group(is_group)
@@ -404,12 +492,17 @@ impl BaseChannel for WhatsAppChannel {
                                 }
                                 whatsapp_rust::types::events::Event::Disconnected(_disconnected) => {
                                     warn!("WhatsApp disconnected");
+                                    // Queue outbound messages until the next Connected
+                                    *client_storage.lock().await = None;
                                     if *running.lock().await {
                                         info!("Will attempt to reconnect...");
                                     }
                                 }
                                 whatsapp_rust::types::events::Event::Connected(_connected) => {
                                     info!("WhatsApp connected");
+                                    // Only an authenticated client is used for sending
+                                    *client_storage.lock().await = Some(client.clone());
+                                    drain_queue(&client, &message_queue, max_message_length).await;
                                 }
                                 _ => {
                                     debug!("WhatsApp event (not handled): {:?}", std::mem::discriminant(&event));
@@ -439,6 +532,7 @@ impl BaseChannel for WhatsAppChannel {
                         error!("Failed to build WhatsApp bot: {}", e);
                     }
                 }
+                *client_for_storage.lock().await = None;
 
                 if *running.lock().await {
                     // Reset backoff if connection lasted more than 2 minutes (not a transient failure)
@@ -498,15 +592,15 @@ impl BaseChannel for WhatsAppChannel {
 
         let client_guard = self.client.lock().await;
         if let Some(client) = client_guard.as_ref() {
-            Box::pin(send_whatsapp_message(client, msg)).await
+            Box::pin(send_whatsapp_message(
+                client,
+                msg,
+                self.config.max_message_length,
+            ))
+            .await
         } else {
             warn!("WhatsApp client not available yet, queuing message");
-            let mut queue = self.message_queue.lock().await;
-            if queue.len() >= 1000 {
-                warn!("whatsapp: message queue full (1000), dropping oldest message");
-                queue.pop_front();
-            }
-            queue.push_back(msg.clone());
+            self.message_queue.lock().await.push(msg);
             Ok(None)
         }
     }
@@ -520,13 +614,6 @@ impl BaseChannel for WhatsAppChannel {
             return Ok(());
         }
 
-        if !msg.media.is_empty() {
-            warn!(
-                "whatsapp: outbound media not yet supported, {} file(s) skipped",
-                msg.media.len()
-            );
-        }
-
         debug!(
             "WhatsApp send: chat_id={}, content_len={}",
             msg.chat_id,
@@ -539,35 +626,19 @@ impl BaseChannel for WhatsAppChannel {
         };
         if let Some(client) = client_arc.as_ref() {
             // Process any queued messages first
-            let mut queue = self.message_queue.lock().await;
-            let queued: Vec<_> = queue.drain(..).collect();
-            let queue_size = queued.len();
-            drop(queue);
-
-            if queue_size > 0 {
-                info!("Processing {} queued WhatsApp messages", queue_size);
-            }
-
-            // Send queued messages
-            for queued_msg in queued {
-                if let Err(e) = Box::pin(send_whatsapp_message(client, &queued_msg)).await {
-                    error!("Failed to send queued WhatsApp message: {}", e);
-                }
-            }
+            drain_queue(client, &self.message_queue, self.config.max_message_length).await;
 
             // Send current message
-            Box::pin(send_whatsapp_message(client, msg))
-                .await
-                .map(|_| ())
+            Box::pin(send_whatsapp_message(
+                client,
+                msg,
+                self.config.max_message_length,
+            ))
+            .await
+            .map(|_| ())
         } else {
             warn!("WhatsApp client not available yet, queuing message");
-            let mut queue = self.message_queue.lock().await;
-            if queue.len() >= 1000 {
-                warn!("whatsapp: message queue full (1000), dropping oldest message");
-                queue.pop_front();
-            }
-            queue.push_back(msg.clone());
-            debug!("WhatsApp: queued message (queue size: {})", queue.len());
+            self.message_queue.lock().await.push(msg);
             Ok(())
         }
     }
@@ -576,6 +647,7 @@ impl BaseChannel for WhatsAppChannel {
 async fn send_whatsapp_message(
     client: &Arc<whatsapp_rust::client::Client>,
     msg: &OutboundMessage,
+    max_message_length: usize,
 ) -> Result<Option<String>> {
     // Format chat_id - normalize JID (strip device suffix, ensure domain)
     let chat_id_str = normalize_jid(&msg.chat_id);
@@ -589,36 +661,167 @@ async fn send_whatsapp_message(
     let jid = whatsapp_rust::Jid::from_str(&chat_id_str)
         .map_err(|e| anyhow::anyhow!("Invalid WhatsApp chat_id '{chat_id_str}': {e}"))?;
 
-    // Split long messages using UTF-8 safe splitting
-    let chunks = oxicrab_core::channels::base::split_message(&msg.content, 4096);
+    let send_text = |text: String| {
+        let jid = jid.clone();
+        async move {
+            let text_message = whatsapp_rust::waproto::whatsapp::Message {
+                conversation: Some(text),
+                ..Default::default()
+            };
+            Box::pin(client.send_message(jid, text_message))
+                .await
+                .map_err(|e| anyhow::anyhow!("WhatsApp send error: {e}"))
+        }
+    };
+    let send_media = |path: String, caption: Option<String>| {
+        let jid = jid.clone();
+        async move { Box::pin(send_whatsapp_media(client, &jid, &path, caption.as_deref())).await }
+    };
+    send_message_parts(msg, max_message_length, send_text, send_media).await
+}
+
+/// Send the text and attachments of `msg` through `send_text` and
+/// `send_media`, returning the ID of the last message sent.
+///
+/// A single attachment carries short text as its caption instead of a
+/// separate message. If that attachment fails, the text is sent on its own
+/// so the reply is not lost; other attachment failures are logged.
+async fn send_message_parts<TF, MF>(
+    msg: &OutboundMessage,
+    max_message_length: usize,
+    send_text: impl Fn(String) -> TF,
+    send_media: impl Fn(String, Option<String>) -> MF,
+) -> Result<Option<String>>
+where
+    TF: std::future::Future<Output = Result<String>>,
+    MF: std::future::Future<Output = Result<String>>,
+{
+    let caption = media_caption(msg);
+    let chunks = if caption.is_some() || (msg.content.is_empty() && !msg.media.is_empty()) {
+        Vec::new()
+    } else {
+        // Split long messages using UTF-8 safe splitting
+        oxicrab_core::channels::base::split_message(&msg.content, max_message_length)
+    };
 
     let mut last_id = None;
-    for (i, chunk) in chunks.iter().enumerate() {
+    for (i, chunk) in chunks.into_iter().enumerate() {
         debug!(
-            "send_whatsapp_message: chunk {}/{} ({} bytes)",
+            "send_whatsapp_message: chunk {} ({} bytes)",
             i + 1,
-            chunks.len(),
             chunk.len(),
         );
-        let text_message = whatsapp_rust::waproto::whatsapp::Message {
-            conversation: Some(chunk.clone()),
-            ..Default::default()
-        };
-
-        match Box::pin(client.send_message(jid.clone(), text_message)).await {
+        match send_text(chunk).await {
+            Ok(msg_id) => {
+                info!("WhatsApp message sent to {}: id={}", msg.chat_id, msg_id);
+                last_id = Some(msg_id);
+            }
+            Err(e) => {
+                error!("WhatsApp send to {} failed: {}", msg.chat_id, e);
+                return Err(e);
+            }
+        }
+    }
+    for path in &msg.media {
+        match send_media(path.clone(), caption.map(str::to_string)).await {
             Ok(msg_id) => {
-                info!("WhatsApp message sent to {}: id={}", jid, msg_id);
+                info!("WhatsApp media sent to {}: id={}", msg.chat_id, msg_id);
                 last_id = Some(msg_id);
             }
             Err(e) => {
-                error!("WhatsApp send to {} failed: {}", jid, e);
-                return Err(anyhow::anyhow!("WhatsApp send error: {e}"));
+                warn!("whatsapp: failed to send media {}: {}", path, e);
+                if let Some(text) = caption {
+                    last_id = Some(send_text(text.to_string()).await?);
+                }
             }
         }
     }
     Ok(last_id)
 }
 
+/// Longest text sent as a media caption; longer text goes in its own message.
+const MAX_MEDIA_CAPTION_CHARS: usize = 1024;
+
+/// Text to send as the caption of the message's only attachment, if it fits.
+fn media_caption(msg: &OutboundMessage) -> Option<&str> {
+    let text = msg.content.trim();
+    (msg.media.len() == 1 && !text.is_empty() && text.chars().count() <= MAX_MEDIA_CAPTION_CHARS)
+        .then_some(text)
+}
+
+/// MIME type of an image attachment sent inline, by file extension.
+/// Anything else is sent as a document.
+fn outbound_image_mime(path: &std::path::Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Upload a local file and send it as an image or document message.
+async fn send_whatsapp_media(
+    client: &Arc<whatsapp_rust::client::Client>,
+    jid: &whatsapp_rust::Jid,
+    path: &str,
+    caption: Option<&str>,
+) -> Result<String> {
+    use whatsapp_rust::download::MediaType;
+    use whatsapp_rust::waproto::whatsapp as wa;
+
+    let file_path = std::path::Path::new(path);
+    let size = tokio::fs::metadata(file_path).await?.len();
+    if size > MAX_MEDIA_DOWNLOAD as u64 {
+        anyhow::bail!("file is {size} bytes, over the {MAX_MEDIA_DOWNLOAD} byte limit");
+    }
+    let data = tokio::fs::read(file_path).await?;
+    let caption = caption.map(str::to_string);
+    let message = if let Some(mimetype) = outbound_image_mime(file_path) {
+        let upload = client.upload(data, MediaType::Image).await?;
+        wa::Message {
+            image_message: Some(Box::new(wa::message::ImageMessage {
+                url: Some(upload.url),
+                direct_path: Some(upload.direct_path),
+                media_key: Some(upload.media_key),
+                file_sha256: Some(upload.file_sha256),
+                file_enc_sha256: Some(upload.file_enc_sha256),
+                file_length: Some(upload.file_length),
+                mimetype: Some(mimetype.to_string()),
+                caption,
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    } else {
+        let upload = client.upload(data, MediaType::Document).await?;
+        let file_name = file_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string());
+        wa::Message {
+            document_message: Some(Box::new(wa::message::DocumentMessage {
+                url: Some(upload.url),
+                direct_path: Some(upload.direct_path),
+                media_key: Some(upload.media_key),
+                file_sha256: Some(upload.file_sha256),
+                file_enc_sha256: Some(upload.file_enc_sha256),
+                file_length: Some(upload.file_length),
+                mimetype: Some("application/octet-stream".to_string()),
+                title: file_name.clone(),
+                file_name,
+                caption,
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    };
+    Box::pin(client.send_message(jid.clone(), message))
+        .await
+        .map_err(|e| anyhow::anyhow!("WhatsApp media send error: {e}"))
+}
+
 const MAX_MEDIA_DOWNLOAD: usize = 50 * 1024 * 1024; // 50 MB
 
 /// Download a `WhatsApp` media file and save to ~/.oxicrab/media/.
@@ -176,3 +176,126 @@ fn test_is_image_mime_none() {
 fn test_is_image_mime_empty() {
     assert!(!is_image_mime(Some("")));
 }
+
+// --- outbound media tests ---
+
+#[test]
+fn test_media_caption_single_attachment_with_short_text() {
+    let msg = OutboundMessage::builder("whatsapp", "123", "  Here's the chart  ")
+        .media(vec!["/tmp/chart.png".to_string()])
+        .build();
+    assert_eq!(media_caption(&msg), Some("Here's the chart"));
+}
+
+#[test]
+fn test_media_caption_falls_back_to_separate_text() {
+    let two = OutboundMessage::builder("whatsapp", "123", "two files")
+        .media(vec!["/tmp/a.png".to_string(), "/tmp/b.png".to_string()])
+        .build();
+    assert_eq!(media_caption(&two), None);
+
+    let long = OutboundMessage::builder("whatsapp", "123", "x".repeat(MAX_MEDIA_CAPTION_CHARS + 1))
+        .media(vec!["/tmp/a.png".to_string()])
+        .build();
+    assert_eq!(media_caption(&long), None);
+
+    let empty = OutboundMessage::builder("whatsapp", "123", "")
+        .media(vec!["/tmp/a.png".to_string()])
+        .build();
+    assert_eq!(media_caption(&empty), None);
+
+    let text_only = OutboundMessage::builder("whatsapp", "123", "hi").build();
+    assert_eq!(media_caption(&text_only), None);
+}
+
+/// Run `send_message_parts` with a text sender that records what it sent
+/// and a media sender that always fails.
+async fn send_with_failing_media(msg: &OutboundMessage) -> (Result<Option<String>>, Vec<String>) {
+    let sent = std::sync::Mutex::new(Vec::new());
+    let result = send_message_parts(
+        msg,
+        4096,
+        |text: String| {
+            sent.lock().unwrap().push(text);
+            async { Ok("text-id".to_string()) }
+        },
+        |_path: String, _caption: Option<String>| async { Err(anyhow::anyhow!("upload failed")) },
+    )
+    .await;
+    (result, sent.into_inner().unwrap())
+}
+
+#[tokio::test]
+async fn test_failed_captioned_attachment_sends_text() {
+    let msg = OutboundMessage::builder("whatsapp", "123", "Here's the chart")
+        .media(vec!["/tmp/chart.png".to_string()])
+        .build();
+    let (result, sent) = send_with_failing_media(&msg).await;
+    assert_eq!(result.unwrap().as_deref(), Some("text-id"));
+    assert_eq!(sent, vec!["Here's the chart".to_string()]);
+
+    // Text already sent separately is not repeated
+    let two = OutboundMessage::builder("whatsapp", "123", "two files")
+        .media(vec!["/tmp/a.png".to_string(), "/tmp/b.png".to_string()])
+        .build();
+    let (_, sent) = send_with_failing_media(&two).await;
+    assert_eq!(sent, vec!["two files".to_string()]);
+}
+
+#[test]
+fn test_outbound_image_mime() {
+    use std::path::Path;
+    assert_eq!(outbound_image_mime(Path::new("a.JPG")), Some("image/jpeg"));
+    assert_eq!(outbound_image_mime(Path::new("a.webp")), Some("image/webp"));
+    assert_eq!(outbound_image_mime(Path::new("report.pdf")), None);
+    assert_eq!(outbound_image_mime(Path::new("noext")), None);
+}
+
+fn queued_contents(queue: &OutboundQueue) -> Vec<&str> {
+    queue.messages.iter().map(|m| m.content.as_str()).collect()
+}
+
+#[test]
+fn test_outbound_queue_dedups_and_keeps_order() {
+    let mut queue = OutboundQueue::default();
+    let briefing = OutboundMessage::builder("whatsapp", "123", "morning briefing").build();
+    queue.push(&briefing);
+    queue.push(&OutboundMessage::builder("whatsapp", "456", "morning briefing").build());
+    queue.push(&briefing);
+    queue.push(&OutboundMessage::builder("whatsapp", "123", "second").build());
+    assert_eq!(
+        queued_contents(&queue),
+        ["morning briefing", "morning briefing", "second"]
+    );
+
+    // Unsent messages go back ahead of anything queued in the meantime
+    let taken = queue.take_all();
+    queue.push(&OutboundMessage::builder("whatsapp", "123", "later").build());
+    queue.requeue_front(taken[1..].to_vec());
+    assert_eq!(
+        queued_contents(&queue),
+        ["morning briefing", "second", "later"]
+    );
+    assert_eq!(queue.messages[0].chat_id, "456");
+}
+
+#[test]
+fn test_outbound_queue_caps_length() {
+    let mut queue = OutboundQueue::default();
+    for i in 0..=MAX_QUEUED_MESSAGES {
+        queue.push(&OutboundMessage::builder("whatsapp", "123", i.to_string()).build());
+    }
+    assert_eq!(queue.messages.len(), MAX_QUEUED_MESSAGES);
+    assert_eq!(queue.messages[0].content, "1");
+
+    // Requeued messages win over the newest ones
+    queue.requeue_front(vec![
+        OutboundMessage::builder("whatsapp", "123", "0").build(),
+    ]);
+    assert_eq!(queue.messages.len(), MAX_QUEUED_MESSAGES);
+    assert_eq!(queue.messages[0].content, "0");
+    assert_eq!(
+        queue.messages.back().unwrap().content,
+        (MAX_QUEUED_MESSAGES - 1).to_string()
+    );
+}
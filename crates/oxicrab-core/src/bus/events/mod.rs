@@ -10,14 +10,25 @@ pub mod meta {
     pub const IS_GROUP: &str = "is_group";
     /// Slack/Telegram message timestamp for threading (`string`).
     pub const TS: &str = "ts";
+    /// Platform ID of the inbound message, used as the outbound `reply_to`
+    /// so replies are threaded to the user's message (`string`).
+    pub const MESSAGE_ID: &str = "message_id";
     /// Slack thread timestamp for reply threading (`string`).
     pub const THREAD_TS: &str = "thread_ts";
     /// Whether this outbound message is a streaming status update (`bool`).
     pub const STATUS: &str = "status";
+    /// Identifies one streamed reply; set on its partial updates and on the
+    /// final message that replaces them (`string`).
+    pub const STREAM_ID: &str = "stream_id";
+    /// Whether this outbound message is the text of a streamed reply so far
+    /// rather than the finished reply (`bool`). Empty content withdraws it.
+    pub const STREAM_PARTIAL: &str = "stream_partial";
     /// Gateway HTTP session ID for conversation continuity (`string`).
     pub const SESSION_ID: &str = "session_id";
     /// Requested response format from the HTTP API (`json`).
     pub const RESPONSE_FORMAT: &str = "response_format";
+    /// Requested reasoning budget from the HTTP API (`low`, `medium`, `high`).
+    pub const REASONING_EFFORT: &str = "reasoning_effort";
     /// Name of the webhook that triggered this inbound message (`string`).
     pub const WEBHOOK_NAME: &str = "webhook_name";
     /// Provider-reported input tokens from the last LLM call (`u64`).
@@ -26,6 +37,15 @@ pub mod meta {
     pub const TOOLS_USED: &str = "tools_used";
     /// Whether this execution originates from a cron job (`bool`).
     pub const IS_CRON_JOB: &str = "is_cron_job";
+    /// Set on outbound messages nobody is waiting for, such as cron job
+    /// output and digests, so quiet hours can hold them (`bool`).
+    pub const BACKGROUND: &str = "background";
+    /// Set on the synthetic "continue" message the agent publishes to resume
+    /// a turn that hit the iteration cap (`bool`).
+    pub const AUTO_CONTINUATION: &str = "auto_continuation";
+    /// Row ID in the offline queue of a message replayed after a provider
+    /// outage (`i64`).
+    pub const OFFLINE_QUEUE_ID: &str = "offline_queue_id";
     /// Interactive buttons to attach to the outbound message (`array`).
     /// Unified format: `[{"id": "...", "label": "...", "style": "primary|danger|success|secondary"}]`
     pub const BUTTONS: &str = "buttons";
@@ -37,6 +57,34 @@ pub mod meta {
     pub const ACTION_DIRECTIVES: &str = "action_directives";
 }
 
+/// Processing priority of an [`InboundMessage`]. The agent takes higher
+/// priorities first; messages of equal priority keep arrival order.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MessagePriority {
+    /// Background work: subagent announcements, automatic continuations.
+    Low,
+    /// Messages from users on regular channels.
+    #[default]
+    Normal,
+    /// Messages from the configured admin target.
+    High,
+}
+
+impl MessagePriority {
+    /// Default priority for messages from `channel`: the internal `system`
+    /// channel is background work, everything else is user traffic.
+    pub fn for_channel(channel: &str) -> Self {
+        if channel == "system" {
+            Self::Low
+        } else {
+            Self::Normal
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct InboundMessage {
     pub channel: String,
@@ -46,6 +94,8 @@ pub struct InboundMessage {
     pub timestamp: DateTime<Utc>,
     pub media: Vec<String>,
     pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub priority: MessagePriority,
     #[serde(skip)]
     pub action: Option<crate::dispatch::ActionDispatch>,
 }
@@ -55,17 +105,28 @@ impl InboundMessage {
         format!("{}:{}", self.channel, self.chat_id)
     }
 
+    /// Platform message ID of this inbound message, if the channel recorded one.
+    pub fn message_id(&self) -> Option<&str> {
+        self.metadata
+            .get(meta::MESSAGE_ID)
+            .and_then(serde_json::Value::as_str)
+            .filter(|id| !id.is_empty())
+    }
+
     /// Start building an `InboundMessage` with the required fields.
-    /// Timestamp defaults to `Utc::now()`.
+    /// Timestamp defaults to `Utc::now()`, priority to
+    /// [`MessagePriority::for_channel`].
     pub fn builder(
         channel: impl Into<String>,
         sender_id: impl Into<String>,
         chat_id: impl Into<String>,
         content: impl Into<String>,
     ) -> InboundMessageBuilder {
+        let channel = channel.into();
         InboundMessageBuilder {
             inner: InboundMessage {
-                channel: channel.into(),
+                priority: MessagePriority::for_channel(&channel),
+                channel,
                 sender_id: sender_id.into(),
                 chat_id: chat_id.into(),
                 content: content.into(),
@@ -112,6 +173,11 @@ impl InboundMessageBuilder {
         self.meta(meta::IS_GROUP, serde_json::Value::Bool(flag))
     }
 
+    pub fn priority(mut self, priority: MessagePriority) -> Self {
+        self.inner.priority = priority;
+        self
+    }
+
     pub fn action(mut self, dispatch: crate::dispatch::ActionDispatch) -> Self {
         self.inner.action = Some(dispatch);
         self
@@ -152,21 +218,26 @@ impl OutboundMessage {
     }
 
     /// Build from an inbound message, moving `channel`, `chat_id`, and `metadata`.
+    /// `reply_to` is set from the inbound [`meta::MESSAGE_ID`] so channels that
+    /// support it can thread the reply to the user's message.
     ///
-    /// Inbound-only metadata keys (`IS_CRON_JOB`, `RESPONSE_FORMAT`, `WEBHOOK_NAME`)
+    /// Inbound-only metadata keys (`IS_CRON_JOB`, `RESPONSE_FORMAT`,
+    /// `REASONING_EFFORT`, `WEBHOOK_NAME`)
     /// are stripped so they don't leak to outbound consumers.
     pub fn from_inbound(msg: InboundMessage, content: impl Into<String>) -> OutboundMessageBuilder {
+        let reply_to = msg.message_id().map(str::to_string);
         let mut metadata = msg.metadata;
         // Remove inbound-only metadata that shouldn't appear on outbound messages
         metadata.remove(meta::IS_CRON_JOB);
         metadata.remove(meta::RESPONSE_FORMAT);
+        metadata.remove(meta::REASONING_EFFORT);
         metadata.remove(meta::WEBHOOK_NAME);
         OutboundMessageBuilder {
             inner: OutboundMessage {
                 channel: msg.channel,
                 chat_id: msg.chat_id,
                 content: content.into(),
-                reply_to: None,
+                reply_to,
                 media: Vec::new(),
                 metadata,
             },
@@ -103,6 +103,21 @@ fn test_outbound_from_inbound() {
     assert!(outbound.media.is_empty());
 }
 
+#[test]
+fn test_outbound_from_inbound_sets_reply_to_from_message_id() {
+    let inbound = InboundMessage::builder("discord", "user1", "chan", "question")
+        .meta(meta::MESSAGE_ID, serde_json::json!("123456"))
+        .build();
+    assert_eq!(inbound.message_id(), Some("123456"));
+    let outbound = OutboundMessage::from_inbound(inbound, "answer").build();
+    assert_eq!(outbound.reply_to.as_deref(), Some("123456"));
+
+    let empty = InboundMessage::builder("discord", "user1", "chan", "question")
+        .meta(meta::MESSAGE_ID, serde_json::json!(""))
+        .build();
+    assert!(empty.message_id().is_none());
+}
+
 #[test]
 fn test_outbound_builder_defaults() {
     let msg = OutboundMessage::builder("discord", "general", "hello").build();
@@ -164,3 +179,17 @@ fn test_merge_metadata_empty_is_noop() {
     assert_eq!(msg.metadata.len(), 1);
     assert_eq!(msg.metadata[meta::TS], serde_json::json!("123"));
 }
+
+#[test]
+fn test_builder_priority_defaults_from_channel() {
+    let user = InboundMessage::builder("telegram", "u1", "c1", "hi").build();
+    assert_eq!(user.priority, MessagePriority::Normal);
+    let system = InboundMessage::builder("system", "subagent", "telegram:c1", "done").build();
+    assert_eq!(system.priority, MessagePriority::Low);
+    let raised = InboundMessage::builder("system", "subagent", "telegram:c1", "done")
+        .priority(MessagePriority::High)
+        .build();
+    assert_eq!(raised.priority, MessagePriority::High);
+    assert!(MessagePriority::High > MessagePriority::Normal);
+    assert!(MessagePriority::Normal > MessagePriority::Low);
+}
@@ -1,3 +1,3 @@
 pub mod events;
 
-pub use events::{InboundMessage, OutboundMessage, meta};
+pub use events::{InboundMessage, MessagePriority, OutboundMessage, meta};
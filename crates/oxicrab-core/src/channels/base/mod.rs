@@ -40,14 +40,56 @@ pub trait BaseChannel: Send + Sync {
         Ok(())
     }
 
+    /// Whether a reply may be shown while it is generated: sent once and then
+    /// edited in place as text arrives. Requires working `send_and_get_id`
+    /// and `edit_message`. Default: false (the finished reply is sent).
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
     /// Delete a previously sent message by its platform-specific ID.
     /// Default: no-op for channels that don't support deletion.
     async fn delete_message(&self, _chat_id: &str, _message_id: &str) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// React to a message with a Unicode emoji (e.g. "✅").
+    /// Default: no-op for channels that don't support reactions.
+    async fn react(&self, _chat_id: &str, _message_id: &str, _emoji: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
-/// Split a message into chunks respecting UTF-8 character boundaries.
+/// Opening of the reaction marker the agent may put at the start of a reply:
+/// `[REACT:✅] rest of the reply`.
+pub const REACT_MARKER_PREFIX: &str = "[REACT:";
+
+/// Split a leading `[REACT:<emoji>]` marker off a reply. Returns the emoji and
+/// the remaining text (trimmed, possibly empty), or `None` when the reply does
+/// not start with a well-formed marker.
+pub fn split_react_marker(content: &str) -> Option<(&str, &str)> {
+    let rest = content.trim_start().strip_prefix(REACT_MARKER_PREFIX)?;
+    let end = rest.find(']')?;
+    let emoji = rest[..end].trim();
+    // An emoji sequence is a few code points; anything longer is not a marker
+    if emoji.is_empty() || emoji.chars().count() > 8 || emoji.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((emoji, rest[end + 1..].trim()))
+}
+
+/// Closing fence appended to a chunk that ends inside a fenced code block.
+const FENCE_CLOSE: &str = "\n```";
+
+/// Split a message into chunks of at most `limit` bytes for platforms with a
+/// message size cap.
+///
+/// Split points are chosen in order of preference: paragraph break outside a
+/// code block, line break outside a code block, line break inside a code
+/// block, sentence end, whitespace, and finally a hard cut at a UTF-8 character
+/// boundary. A fenced code block that spans chunks is closed at the end of one
+/// chunk and reopened (with its language tag) at the start of the next; the
+/// added fences count against `limit`.
 pub fn split_message(text: &str, limit: usize) -> Vec<String> {
     if text.is_empty() {
         return vec![];
@@ -58,78 +100,141 @@ pub fn split_message(text: &str, limit: usize) -> Vec<String> {
 
     let mut chunks = Vec::new();
     let mut remaining = text;
-
-    while remaining.len() > limit {
-        let mut split_at = remaining.floor_char_boundary(limit);
-        if split_at == 0 {
-            // Degenerate case: single character wider than limit
-            split_at = remaining
-                .char_indices()
-                .nth(1)
-                .map_or(remaining.len(), |(i, _)| i);
+    // Language tag of a code block left open by the previous chunk
+    let mut open_fence: Option<String> = None;
+
+    while !remaining.is_empty() {
+        let reopen = open_fence
+            .as_ref()
+            .map(|lang| format!("```{lang}\n"))
+            .unwrap_or_default();
+        if reopen.len() + remaining.len() <= limit {
+            chunks.push(format!("{reopen}{}", remaining.trim_end()));
+            break;
         }
 
-        // Try paragraph boundary within the safe range
-        if let Some(idx) = remaining[..split_at].rfind("\n\n") {
-            chunks.push(remaining[..idx].trim().to_string());
-            remaining = &remaining[idx + 2..];
-            continue;
+        let budget = limit.saturating_sub(reopen.len());
+        let mut split_at = find_split_point(remaining, budget, open_fence.is_some());
+        let mut fence_after = fence_state_after(&remaining[..split_at], open_fence.as_deref());
+        // Leave room to close a code block that is still open at the split
+        if fence_after.is_some()
+            && reopen.len() + remaining[..split_at].trim_end().len() + FENCE_CLOSE.len() > limit
+        {
+            split_at = find_split_point(
+                remaining,
+                budget.saturating_sub(FENCE_CLOSE.len()),
+                open_fence.is_some(),
+            );
+            fence_after = fence_state_after(&remaining[..split_at], open_fence.as_deref());
         }
 
-        // Try single newline
-        if let Some(idx) = remaining[..split_at].rfind('\n') {
-            chunks.push(remaining[..idx].trim().to_string());
-            remaining = &remaining[idx + 1..];
-            continue;
+        let head = remaining[..split_at].trim_end();
+        if !head.trim().is_empty() {
+            let mut chunk = format!("{reopen}{head}");
+            if fence_after.is_some() {
+                chunk.push_str(FENCE_CLOSE);
+            }
+            chunks.push(chunk);
         }
-
-        // Hard cut at char boundary
-        chunks.push(remaining[..split_at].to_string());
-        remaining = &remaining[split_at..];
+        open_fence = fence_after;
+
+        // Drop the separator; keep leading indentation of the next line
+        let tail = &remaining[split_at..];
+        remaining = tail
+            .strip_prefix(' ')
+            .unwrap_or(tail)
+            .trim_start_matches('\n');
     }
 
-    if !remaining.is_empty() {
-        chunks.push(remaining.trim().to_string());
-    }
+    chunks
+}
 
-    // Filter out empty chunks (e.g., from leading "\n\n" producing a trimmed empty string)
-    let chunks: Vec<String> = chunks.into_iter().filter(|c| !c.is_empty()).collect();
+/// Whether a line opens or closes a fenced code block.
+fn is_fence_line(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
 
-    // Repair split code blocks: if a chunk has an odd number of ``` fences,
-    // close the code block in the current chunk and re-open it in the next.
-    repair_code_fences(chunks)
+/// Code block state after `text`, given the state before it. Returns the
+/// language tag of the block left open, or `None` when no block is open.
+fn fence_state_after(text: &str, open_before: Option<&str>) -> Option<String> {
+    let mut open = open_before.map(str::to_string);
+    for line in text.lines() {
+        if is_fence_line(line) {
+            open = match open {
+                Some(_) => None,
+                None => Some(line.trim_start()[3..].trim().to_string()),
+            };
+        }
+    }
+    open
 }
 
-/// Ensure each chunk has balanced ``` fences. An odd count means a code block
-/// was split across chunks. We close it at the end of the current chunk and
-/// re-open it at the start of the next.
-fn repair_code_fences(chunks: Vec<String>) -> Vec<String> {
-    if chunks.len() <= 1 {
-        return chunks;
+/// Pick the byte offset to split `text` at so that `text[..offset]` fits in
+/// `budget` bytes. Always returns a non-zero offset on a char boundary.
+fn find_split_point(text: &str, budget: usize, starts_in_fence: bool) -> usize {
+    let window_end = text.floor_char_boundary(budget);
+    if window_end == 0 {
+        // Degenerate case: single character wider than the budget
+        return text.char_indices().nth(1).map_or(text.len(), |(i, _)| i);
     }
-    let mut result = Vec::with_capacity(chunks.len());
-    let mut carry_lang = String::new();
-    for chunk in chunks {
-        let mut c = if carry_lang.is_empty() {
-            chunk
-        } else {
-            // Re-open code block from previous chunk
-            format!("```{carry_lang}\n{chunk}")
-        };
-        carry_lang.clear();
-        let fence_count = c.matches("```").count();
-        if fence_count % 2 == 1 {
-            // Extract the language tag from the opening fence (e.g. ```rust)
-            if let Some(pos) = c.rfind("```") {
-                let after_fence = &c[pos + 3..];
-                let lang = after_fence.lines().next().unwrap_or("").trim().to_string();
-                carry_lang = lang;
+    let window = &text[..window_end];
+
+    let mut paragraphs = Vec::new();
+    let mut lines_outside = Vec::new();
+    let mut lines_inside = Vec::new();
+    let mut sentences = Vec::new();
+    let mut in_fence = starts_in_fence;
+    let mut line_start = 0;
+    for line in window.split_inclusive('\n') {
+        let content = line.trim_end_matches('\n');
+        let was_in_fence = in_fence;
+        if is_fence_line(content) {
+            in_fence = !in_fence;
+        }
+        if !was_in_fence && !is_fence_line(content) {
+            for (idx, _) in content.match_indices(['.', '!', '?']) {
+                if content[idx + 1..].starts_with(' ') {
+                    sentences.push(line_start + idx + 1);
+                }
+            }
+        }
+        // A line ends here if it has a newline inside the window or the
+        // text continues with one right after the window
+        let line_end = line_start + content.len();
+        let ends_line = line.ends_with('\n') || text[line_end..].starts_with('\n');
+        if ends_line && line_end > 0 {
+            if in_fence {
+                lines_inside.push(line_end);
+            } else {
+                lines_outside.push(line_end);
+                if content.is_empty() && line_start > 0 {
+                    paragraphs.push(line_start - 1);
+                }
             }
-            c.push_str("\n```");
         }
-        result.push(c);
+        line_start += line.len();
     }
-    result
+    let spaces: Vec<usize> = window
+        .match_indices(' ')
+        .map(|(i, _)| i)
+        .filter(|&i| i > 0)
+        .collect();
+
+    let classes = [
+        &paragraphs,
+        &lines_outside,
+        &lines_inside,
+        &sentences,
+        &spaces,
+    ];
+    // Prefer a good boundary that keeps the chunk at least half full, then
+    // any boundary at all, then a hard cut
+    let min_pos = window_end / 2;
+    classes
+        .iter()
+        .find_map(|c| c.iter().rev().find(|&&p| p >= min_pos).copied())
+        .or_else(|| classes.iter().find_map(|c| c.last().copied()))
+        .unwrap_or(window_end)
 }
 
 #[cfg(test)]
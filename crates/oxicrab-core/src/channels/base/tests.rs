@@ -86,3 +86,75 @@ fn test_multiple_chunks() {
     let result = split_message(msg, 10);
     assert!(result.len() >= 4);
 }
+
+#[test]
+fn test_code_fence_reopened_with_language() {
+    let code: String = (0..30).map(|i| format!("let x{i} = {i};\n")).collect();
+    let msg = format!("Here is the code:\n\n```rust\n{code}```\n\nDone.");
+    let result = split_message(&msg, 120);
+    assert!(result.len() > 1);
+    for chunk in &result {
+        assert!(chunk.len() <= 120, "chunk over limit: {chunk:?}");
+        // Every chunk has balanced fences
+        assert_eq!(chunk.matches("```").count() % 2, 0, "unbalanced: {chunk:?}");
+    }
+    for chunk in &result[1..] {
+        if chunk.contains("let x") {
+            assert!(chunk.starts_with("```rust\n"), "not reopened: {chunk:?}");
+        }
+    }
+    assert!(result.last().unwrap().ends_with("Done."));
+}
+
+#[test]
+fn test_sentence_preferred_over_word() {
+    let msg = "This is the first sentence. This is the second sentence that runs on";
+    let result = split_message(msg, 50);
+    assert_eq!(result[0], "This is the first sentence.");
+    assert_eq!(result[1], "This is the second sentence that runs on");
+}
+
+#[test]
+fn test_word_boundary_before_hard_cut() {
+    let msg = "alpha beta gamma delta epsilon zeta eta theta iota kappa";
+    let result = split_message(msg, 20);
+    for chunk in &result {
+        assert!(chunk.len() <= 20);
+        assert!(!chunk.starts_with(' ') && !chunk.ends_with(' '));
+    }
+    assert_eq!(result.join(" "), msg);
+}
+
+#[test]
+fn test_code_indentation_preserved() {
+    let msg = "```\nfn main() {\n    let a = 1;\n    let b = 2;\n    let c = 3;\n}\n```";
+    let result = split_message(msg, 40);
+    assert!(result.len() > 1);
+    assert!(result.iter().any(|c| c.contains("\n    let")));
+    for chunk in &result[1..] {
+        let body = chunk.strip_prefix("```\n").unwrap_or(chunk);
+        assert!(!body.starts_with("let"), "indentation lost: {chunk:?}");
+    }
+}
+
+#[test]
+fn test_split_react_marker() {
+    assert_eq!(split_react_marker("[REACT:✅]"), Some(("✅", "")));
+    assert_eq!(
+        split_react_marker("  [REACT:👍] Noted, I'll remember that."),
+        Some(("👍", "Noted, I'll remember that."))
+    );
+    assert_eq!(
+        split_react_marker("[REACT: 🎉 ]\nDone"),
+        Some(("🎉", "Done"))
+    );
+}
+
+#[test]
+fn test_split_react_marker_rejects_malformed() {
+    assert_eq!(split_react_marker("Sure [REACT:✅]"), None);
+    assert_eq!(split_react_marker("[REACT:✅"), None);
+    assert_eq!(split_react_marker("[REACT:]"), None);
+    assert_eq!(split_react_marker("[REACT:not an emoji at all]"), None);
+    assert_eq!(split_react_marker("[Link](https://example.com)"), None);
+}
@@ -1,3 +1,4 @@
+use crate::providers::base::ReasoningEffort;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -57,6 +58,11 @@ pub struct CompactionConfig {
     pub enabled: bool,
     #[serde(default = "default_threshold_tokens", rename = "thresholdTokens")]
     pub threshold_tokens: u32,
+    /// Also compact once the session holds this many messages, regardless of
+    /// the token count. A predictable trigger for providers that do not report
+    /// input tokens reliably.
+    #[serde(default, rename = "maxMessages")]
+    pub max_messages: Option<usize>,
     #[serde(default = "default_keep_recent", rename = "keepRecent")]
     pub keep_recent: usize,
     #[serde(default, rename = "keepRecentTurns")]
@@ -65,10 +71,25 @@ pub struct CompactionConfig {
     pub extraction_enabled: bool,
     #[serde(default)]
     pub model: Option<String>,
+    /// Model for background fact extraction. Falls back to `model`, so a
+    /// cheaper model can be used for extraction than for summaries.
+    #[serde(default, rename = "extractionModel")]
+    pub extraction_model: Option<String>,
+    /// Checkpoint summaries (the compaction call itself).
+    #[serde(default)]
+    pub checkpoint: CompactionCheckpointConfig,
+    /// How often background fact extraction runs per session. Exchanges in
+    /// between are batched into the next extraction call.
+    #[serde(default, rename = "extractionInterval")]
+    pub extraction_interval: ExtractionIntervalConfig,
     /// Before compaction, make a silent LLM call to extract important context
     /// from about-to-be-compacted messages and persist to daily notes.
     #[serde(default, rename = "preFlushEnabled")]
     pub pre_flush_enabled: bool,
+    /// With compaction disabled, the history (its last 50 messages) also
+    /// loses its oldest turns until the estimated tokens fit this budget.
+    #[serde(default, rename = "maxHistoryTokens")]
+    pub max_history_tokens: Option<usize>,
 }
 
 impl Default for CompactionConfig {
@@ -76,15 +97,52 @@ impl Default for CompactionConfig {
         Self {
             enabled: true,
             threshold_tokens: default_threshold_tokens(),
+            max_messages: None,
             keep_recent: default_keep_recent(),
             keep_recent_turns: None,
             extraction_enabled: true,
             model: None,
+            extraction_model: None,
+            checkpoint: CompactionCheckpointConfig::default(),
+            extraction_interval: ExtractionIntervalConfig::default(),
             pre_flush_enabled: false,
+            max_history_tokens: None,
+        }
+    }
+}
+
+/// Checkpoint summary settings. `model` falls back to the compaction
+/// `model`, so summaries can run on a cheaper dedicated model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompactionCheckpointConfig {
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// Per-session fact extraction throttle. Extraction runs once `turns`
+/// exchanges are pending, or on the first turn after `minutes` have passed
+/// since the session's last extraction. Zero disables either trigger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionIntervalConfig {
+    #[serde(default = "default_extraction_turns")]
+    pub turns: usize,
+    #[serde(default)]
+    pub minutes: u64,
+}
+
+impl Default for ExtractionIntervalConfig {
+    fn default() -> Self {
+        Self {
+            turns: default_extraction_turns(),
+            minutes: 0,
         }
     }
 }
 
+fn default_extraction_turns() -> usize {
+    1
+}
+
 fn default_threshold_tokens() -> u32 {
     40000
 }
@@ -113,6 +171,26 @@ impl std::fmt::Display for PromptGuardAction {
     }
 }
 
+/// Category reported for a custom prompt guard pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptGuardCategory {
+    RoleSwitch,
+    InstructionOverride,
+    SecretExtraction,
+    Jailbreak,
+}
+
+/// A custom prompt guard pattern, checked alongside the built-in ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptGuardPattern {
+    /// Reported as the match's pattern name.
+    pub name: String,
+    pub category: PromptGuardCategory,
+    /// Regex matched against the normalized text, e.g. `(?i)\bsend .{0,20} to pastebin\b`.
+    pub regex: String,
+}
+
 fn default_prompt_guard_action() -> PromptGuardAction {
     PromptGuardAction::default()
 }
@@ -124,6 +202,10 @@ pub struct PromptGuardConfig {
     /// Action on detection: `Warn` (log + continue) or `Block` (reject message)
     #[serde(default = "default_prompt_guard_action")]
     pub action: PromptGuardAction,
+    /// Custom patterns added to the built-ins. Invalid regexes are skipped
+    /// with a warning.
+    #[serde(default, rename = "extraPatterns")]
+    pub extra_patterns: Vec<PromptGuardPattern>,
 }
 
 impl PromptGuardConfig {
@@ -138,6 +220,225 @@ impl Default for PromptGuardConfig {
         Self {
             enabled: true,
             action: default_prompt_guard_action(),
+            extra_patterns: Vec::new(),
+        }
+    }
+}
+
+/// Reasoning budgets (Anthropic extended thinking, OpenAI `reasoning_effort`)
+/// per kind of LLM call. Unset entries leave the provider default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReasoningConfig {
+    /// Main agent turns before any tool call, and the final answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat: Option<ReasoningEffort>,
+    /// Main agent iterations after a tool call (falls back to `chat`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<ReasoningEffort>,
+    /// Subagent runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subagent: Option<ReasoningEffort>,
+    /// Cron job turns (replaces `chat` and `tools`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<ReasoningEffort>,
+}
+
+impl ReasoningConfig {
+    /// Effort for a routed task type (`cron`, `subagent`), replacing the
+    /// per-phase efforts for the whole run.
+    pub fn for_task(&self, task_type: &str) -> Option<ReasoningEffort> {
+        match task_type {
+            "cron" => self.cron,
+            "subagent" => self.subagent,
+            _ => None,
+        }
+    }
+
+    /// Effort for a main-loop iteration, by whether a tool was already called.
+    pub fn for_phase(&self, after_tool_calls: bool) -> Option<ReasoningEffort> {
+        if after_tool_calls {
+            self.tools.or(self.chat)
+        } else {
+            self.chat
+        }
+    }
+}
+
+/// What the agent loop does once every retry of an empty LLM response is used up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmptyResponseAction {
+    /// Give up with the generic "wasn't able to generate a response" reply.
+    #[default]
+    Generic,
+    /// Give up with a reply naming the model and what went wrong.
+    Diagnostic,
+    /// Ask the same model once more with a short "answer in plain text" nudge.
+    Reprompt,
+    /// Try `fallbackModel` once.
+    Fallback,
+}
+
+impl std::fmt::Display for EmptyResponseAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Generic => write!(f, "generic"),
+            Self::Diagnostic => write!(f, "diagnostic"),
+            Self::Reprompt => write!(f, "reprompt"),
+            Self::Fallback => write!(f, "fallback"),
+        }
+    }
+}
+
+/// Handling of LLM calls that keep returning nothing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmptyResponseConfig {
+    #[serde(default)]
+    pub action: EmptyResponseAction,
+    /// `provider/model` tried once by the `fallback` action.
+    #[serde(
+        default,
+        rename = "fallbackModel",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fallback_model: Option<String>,
+}
+
+/// What the agent loop does when the model keeps repeating the same tool call.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolLoopAction {
+    /// Skip the repeat and tell the model to change approach; a second loop
+    /// in the same turn ends it like `finalize`.
+    #[default]
+    Nudge,
+    /// Skip the repeat and make the model answer without further tool calls.
+    Finalize,
+}
+
+impl std::fmt::Display for ToolLoopAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Nudge => write!(f, "nudge"),
+            Self::Finalize => write!(f, "finalize"),
+        }
+    }
+}
+
+fn default_tool_loop_threshold() -> u32 {
+    3
+}
+
+/// Detection of the model calling one tool with the same arguments over and over.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolLoopConfig {
+    /// Consecutive identical calls (same tool, same arguments) that count as
+    /// a loop. The call that reaches it is not run. 0 disables detection.
+    #[serde(default = "default_tool_loop_threshold")]
+    pub threshold: u32,
+    #[serde(default)]
+    pub action: ToolLoopAction,
+}
+
+impl Default for ToolLoopConfig {
+    fn default() -> Self {
+        Self {
+            threshold: default_tool_loop_threshold(),
+            action: ToolLoopAction::default(),
+        }
+    }
+}
+
+fn default_tool_summary_threshold() -> usize {
+    6000
+}
+
+/// Summarizing large tool results with a cheap model before they enter the
+/// conversation. The full result is kept in a workspace temp file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolResultSummaryConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Results longer than this many characters are summarized.
+    #[serde(default = "default_tool_summary_threshold", rename = "thresholdChars")]
+    pub threshold_chars: usize,
+    /// `provider/model` that writes the summaries. Unset = the compaction model.
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+impl Default for ToolResultSummaryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_chars: default_tool_summary_threshold(),
+            model: None,
+        }
+    }
+}
+
+/// Action to take when an inbound message matches the content filter.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFilterAction {
+    /// Log the match and process the message normally.
+    Flag,
+    /// Reply with the configured notice instead of processing the message.
+    Notice,
+    /// Discard the message without replying (default).
+    #[default]
+    Drop,
+}
+
+impl std::fmt::Display for ContentFilterAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Flag => write!(f, "flag"),
+            Self::Notice => write!(f, "notice"),
+            Self::Drop => write!(f, "drop"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentFilterRule {
+    /// Regex matched against the message text, e.g. `(?i)\bfree crypto\b`.
+    pub pattern: String,
+    #[serde(default)]
+    pub action: ContentFilterAction,
+}
+
+fn default_content_filter_notice() -> String {
+    "Sorry, I can't process this message.".to_string()
+}
+
+/// Content policy applied to inbound messages before they reach the LLM.
+/// When several rules match, the strictest action wins (drop, then notice,
+/// then flag).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentFilterConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rules: Vec<ContentFilterRule>,
+    /// Messages longer than this many characters match the length rule.
+    #[serde(default, rename = "maxLength")]
+    pub max_length: Option<usize>,
+    #[serde(default, rename = "lengthAction")]
+    pub length_action: ContentFilterAction,
+    /// Reply sent for the `notice` action.
+    #[serde(default = "default_content_filter_notice")]
+    pub notice: String,
+}
+
+impl Default for ContentFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: vec![],
+            max_length: None,
+            length_action: ContentFilterAction::default(),
+            notice: default_content_filter_notice(),
         }
     }
 }
@@ -200,6 +501,30 @@ pub enum FusionStrategy {
     Rrf,
 }
 
+/// Where memory embeddings are computed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmbeddingsBackend {
+    /// Local ONNX model (`embeddingsModel`), available in builds with the
+    /// `embeddings-onnx` feature (default).
+    #[default]
+    #[serde(rename = "onnx")]
+    Onnx,
+    /// OpenAI `/embeddings` API, using `providers.openai` credentials.
+    #[serde(rename = "openai")]
+    OpenAi,
+}
+
+/// What hybrid memory search does when the query embedding can't be produced.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingFailureMode {
+    /// Log the degradation and run keyword-only search instead (default).
+    #[default]
+    Degrade,
+    /// Return the error to the caller.
+    Error,
+}
+
 fn default_rrf_k() -> u32 {
     60
 }
@@ -228,12 +553,22 @@ fn default_recency_half_life_days() -> u32 {
     90
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MemoryConfig {
     #[serde(default = "default_embeddings_enabled", rename = "embeddingsEnabled")]
     pub embeddings_enabled: bool,
+    /// `onnx` (local model, default) or `openai`.
+    #[serde(default, rename = "embeddingsBackend")]
+    pub embeddings_backend: EmbeddingsBackend,
+    /// ONNX model name, or the OpenAI embedding model with the `openai` backend.
     #[serde(default = "default_embeddings_model", rename = "embeddingsModel")]
     pub embeddings_model: String,
+    /// Runtime-injected from providers.openai.apiKey
+    #[serde(skip)]
+    pub openai_api_key: Option<String>,
+    /// Runtime-injected from providers.openai.apiBase
+    #[serde(skip)]
+    pub openai_api_base: Option<String>,
     /// 0.0 = keyword only, 1.0 = vector only, 0.5 = equal blend (used with `WeightedScore`)
     #[serde(default = "default_hybrid_weight", rename = "hybridWeight")]
     pub hybrid_weight: f32,
@@ -243,6 +578,10 @@ pub struct MemoryConfig {
     /// Constant k for RRF (higher = less emphasis on top ranks). Default 60.
     #[serde(default = "default_rrf_k", rename = "rrfK")]
     pub rrf_k: u32,
+    /// Behavior when the query embedding fails or comes back empty:
+    /// `degrade` to keyword-only search (default) or `error`.
+    #[serde(default, rename = "onEmbeddingFailure")]
+    pub on_embedding_failure: EmbeddingFailureMode,
     /// LRU cache size for query embeddings. Default 10,000.
     #[serde(
         default = "default_embedding_cache_size",
@@ -268,21 +607,162 @@ pub struct MemoryConfig {
     /// the context window.
     #[serde(default = "default_max_context_chars", rename = "maxContextChars")]
     pub max_context_chars: usize,
+    /// Background contradiction detection over stored memories.
+    #[serde(default)]
+    pub gardener: MemoryGardenerConfig,
+    /// Periodic digest of extracted facts sent back to the chat they came from.
+    #[serde(default, rename = "factDigest")]
+    pub fact_digest: FactDigestConfig,
+    /// Categories of facts that are only written to memory after the user
+    /// confirms them. Applies to "remember that..." and background fact
+    /// extraction. Empty = write everything without asking.
+    #[serde(default, rename = "confirmCategories")]
+    pub confirm_categories: Vec<MemoryCategory>,
+}
+
+redact_debug!(
+    MemoryConfig,
+    embeddings_enabled,
+    embeddings_backend,
+    embeddings_model,
+    redact_option(openai_api_key),
+    openai_api_base,
+    hybrid_weight,
+    fusion_strategy,
+    rrf_k,
+    on_embedding_failure,
+    embedding_cache_size,
+    recency_half_life_days,
+    search_result_limit,
+    retention_days,
+    max_context_chars,
+    gardener,
+    fact_digest,
+    confirm_categories,
+);
+
+/// Sensitive fact categories for [`MemoryConfig::confirm_categories`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryCategory {
+    /// Health conditions, medication, allergies, diagnoses.
+    Medical,
+    /// Accounts, cards, income, debts, taxes.
+    Financial,
+    /// Passwords, PINs, recovery codes, keys.
+    Security,
+}
+
+impl std::fmt::Display for MemoryCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Medical => "medical",
+            Self::Financial => "financial",
+            Self::Security => "security",
+        })
+    }
+}
+
+fn default_gardener_interval_hours() -> u64 {
+    24
+}
+
+fn default_gardener_similarity_threshold() -> f32 {
+    0.85
+}
+
+fn default_gardener_max_pairs() -> usize {
+    10
+}
+
+/// Memory gardener: periodically finds highly similar memory entries and asks
+/// the LLM whether they contradict each other. Contradictions are recorded for
+/// the user to resolve via `memory_search`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryGardenerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Hours between gardener runs (default: 24).
+    #[serde(default = "default_gardener_interval_hours", rename = "intervalHours")]
+    pub interval_hours: u64,
+    /// Minimum cosine similarity for a pair to be checked (default: 0.85).
+    #[serde(
+        default = "default_gardener_similarity_threshold",
+        rename = "similarityThreshold"
+    )]
+    pub similarity_threshold: f32,
+    /// Maximum pairs sent to the LLM per run (default: 10).
+    #[serde(default = "default_gardener_max_pairs", rename = "maxPairsPerRun")]
+    pub max_pairs_per_run: usize,
+}
+
+impl Default for MemoryGardenerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_gardener_interval_hours(),
+            similarity_threshold: default_gardener_similarity_threshold(),
+            max_pairs_per_run: default_gardener_max_pairs(),
+        }
+    }
+}
+
+fn default_fact_digest_interval_hours() -> u64 {
+    24
+}
+
+fn default_fact_digest_max_facts() -> usize {
+    20
+}
+
+/// Fact digest: periodically lists newly extracted facts in the chat they were
+/// extracted from, so the user can confirm them or reply "forget #N".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactDigestConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Hours between digests (default: 24).
+    #[serde(
+        default = "default_fact_digest_interval_hours",
+        rename = "intervalHours"
+    )]
+    pub interval_hours: u64,
+    /// Maximum facts listed per chat per digest; the rest wait for the next
+    /// one (default: 20).
+    #[serde(default = "default_fact_digest_max_facts", rename = "maxFacts")]
+    pub max_facts: usize,
+}
+
+impl Default for FactDigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_fact_digest_interval_hours(),
+            max_facts: default_fact_digest_max_facts(),
+        }
+    }
 }
 
 impl Default for MemoryConfig {
     fn default() -> Self {
         Self {
             embeddings_enabled: default_embeddings_enabled(),
+            embeddings_backend: EmbeddingsBackend::default(),
             embeddings_model: default_embeddings_model(),
+            openai_api_key: None,
+            openai_api_base: None,
             hybrid_weight: default_hybrid_weight(),
             fusion_strategy: FusionStrategy::default(),
             rrf_k: default_rrf_k(),
+            on_embedding_failure: EmbeddingFailureMode::default(),
             embedding_cache_size: default_embedding_cache_size(),
             recency_half_life_days: default_recency_half_life_days(),
             search_result_limit: default_search_result_limit(),
             retention_days: default_retention_days(),
             max_context_chars: default_max_context_chars(),
+            gardener: MemoryGardenerConfig::default(),
+            fact_digest: FactDigestConfig::default(),
+            confirm_categories: Vec::new(),
         }
     }
 }
@@ -291,6 +771,198 @@ fn default_media_ttl_days() -> u32 {
     7
 }
 
+fn default_max_images_per_message() -> usize {
+    5
+}
+
+fn default_max_documents_per_message() -> usize {
+    5
+}
+
+fn default_description_ttl_days() -> u32 {
+    30
+}
+
+fn default_max_image_dimension() -> u32 {
+    1568
+}
+
+fn default_jpeg_quality() -> u8 {
+    85
+}
+
+/// Per-message caps on inbound attachments forwarded to the LLM.
+/// Attachments beyond the cap are dropped and the user is told so.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundMediaConfig {
+    #[serde(default = "default_max_images_per_message", rename = "maxImages")]
+    pub max_images: usize,
+    #[serde(default = "default_max_documents_per_message", rename = "maxDocuments")]
+    pub max_documents: usize,
+    /// Describe each new inbound image once and reuse the cached text (keyed
+    /// by content hash) instead of re-sending the image when it reappears.
+    #[serde(default, rename = "cacheDescriptions")]
+    pub cache_descriptions: bool,
+    /// Days a cached image description stays valid.
+    #[serde(
+        default = "default_description_ttl_days",
+        rename = "descriptionTtlDays"
+    )]
+    pub description_ttl_days: u32,
+    /// Model used to describe images. Defaults to the agent's model.
+    #[serde(default, rename = "descriptionModel")]
+    pub description_model: Option<String>,
+    /// Images whose longer side exceeds this many pixels are downscaled to
+    /// fit and re-encoded as JPEG before being sent. 0 sends them as-is.
+    #[serde(default = "default_max_image_dimension", rename = "maxImageDimension")]
+    pub max_image_dimension: u32,
+    /// JPEG quality (1-100) of downscaled images.
+    #[serde(default = "default_jpeg_quality", rename = "jpegQuality")]
+    pub jpeg_quality: u8,
+}
+
+impl Default for InboundMediaConfig {
+    fn default() -> Self {
+        Self {
+            max_images: default_max_images_per_message(),
+            max_documents: default_max_documents_per_message(),
+            cache_descriptions: false,
+            description_ttl_days: default_description_ttl_days(),
+            description_model: None,
+            max_image_dimension: default_max_image_dimension(),
+            jpeg_quality: default_jpeg_quality(),
+        }
+    }
+}
+
+fn default_batching_max_wait_ms() -> u64 {
+    10_000
+}
+
+/// Coalescing of quick successive messages from one session into one turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageBatchingConfig {
+    /// Quiet period after a message before its session's batch is handled.
+    /// Each new message restarts it. 0 disables batching.
+    #[serde(default, rename = "windowMs")]
+    pub window_ms: u64,
+    /// Longest a batch is held after its first message, however often new
+    /// messages arrive.
+    #[serde(default = "default_batching_max_wait_ms", rename = "maxWaitMs")]
+    pub max_wait_ms: u64,
+}
+
+impl Default for MessageBatchingConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 0,
+            max_wait_ms: default_batching_max_wait_ms(),
+        }
+    }
+}
+
+fn default_offline_reply() -> String {
+    "I'm temporarily unable to reach my model; I'll respond when I'm back.".to_string()
+}
+
+fn default_offline_retry_interval_secs() -> u64 {
+    60
+}
+
+fn default_offline_max_queued() -> usize {
+    100
+}
+
+/// Holding inbound messages while the circuit breaker keeps the provider
+/// out of service, and answering them once it is back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineModeConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Sent to a chat when its first message is queued.
+    #[serde(default = "default_offline_reply")]
+    pub reply: String,
+    /// How often the oldest queued message is retried.
+    #[serde(
+        default = "default_offline_retry_interval_secs",
+        rename = "retryIntervalSecs"
+    )]
+    pub retry_interval_secs: u64,
+    /// Queued messages across all chats; further messages get the usual
+    /// error reply.
+    #[serde(default = "default_offline_max_queued", rename = "maxQueued")]
+    pub max_queued: usize,
+}
+
+impl Default for OfflineModeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reply: default_offline_reply(),
+            retry_interval_secs: default_offline_retry_interval_secs(),
+            max_queued: default_offline_max_queued(),
+        }
+    }
+}
+
+fn default_stream_edit_interval_ms() -> u64 {
+    1000
+}
+
+/// Showing a reply while it is being generated, on channels that can edit
+/// a sent message (Slack, Telegram).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minimum time between edits of the streamed message.
+    #[serde(default = "default_stream_edit_interval_ms", rename = "editIntervalMs")]
+    pub edit_interval_ms: u64,
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            edit_interval_ms: default_stream_edit_interval_ms(),
+        }
+    }
+}
+
+/// How the main agent hands work to background subagents (`spawn`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationConfig {
+    /// Most subagents one turn may start. 0 = no limit.
+    #[serde(default, rename = "maxPerTurn")]
+    pub max_per_turn: usize,
+    /// Tool calls in one turn after which the model is reminded that it can
+    /// hand the remaining work to a subagent. 0 = never.
+    #[serde(default, rename = "suggestAfterToolCalls")]
+    pub suggest_after_tool_calls: usize,
+    /// Post a status line in the chat when a subagent is started.
+    #[serde(default = "super::default_true")]
+    pub announce: bool,
+    /// Post each tool a subagent calls as a status line in the origin chat.
+    #[serde(default, rename = "streamToolActivity")]
+    pub stream_tool_activity: bool,
+    /// Only these tools are available to subagents, e.g. read-only tools for
+    /// untrusted delegated tasks. Unset = every tool subagents may use.
+    #[serde(default, rename = "allowedTools")]
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+impl Default for DelegationConfig {
+    fn default() -> Self {
+        Self {
+            max_per_turn: 0,
+            suggest_after_tool_calls: 0,
+            announce: true,
+            stream_tool_activity: false,
+            allowed_tools: None,
+        }
+    }
+}
+
 // Serde default functions must match the field type (`Option<u64>`).
 #[allow(clippy::unnecessary_wraps)]
 fn default_ttl_temp() -> Option<u64> {
@@ -370,6 +1042,10 @@ fn default_max_concurrent_subagents() -> usize {
     5
 }
 
+fn default_max_concurrent_sessions() -> usize {
+    4
+}
+
 fn default_session_ttl_days() -> u32 {
     30
 }
@@ -452,12 +1128,12 @@ pub struct ChatRoutingConfig {
 }
 
 /// Score thresholds for chat complexity escalation.
-/// Below `standard` → use the default model.
+/// Below `standard` → use `models.light` (or the default model when unset).
 /// Between `standard` and `heavy` → use `models.standard`.
 /// At or above `heavy` → use `models.heavy`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatThresholds {
-    /// Below this score → default model (default 0.3).
+    /// Below this score → light/default model (default 0.3).
     #[serde(default = "default_standard_threshold")]
     pub standard: f64,
     /// At or above this score → heavy model (default 0.65).
@@ -482,9 +1158,13 @@ impl Default for ChatThresholds {
     }
 }
 
-/// Models for chat complexity escalation tiers (above the default model).
+/// Models for chat complexity escalation tiers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatModels {
+    /// Optional cheap model for low-complexity messages (greetings, simple
+    /// lookups). When unset, low-complexity messages use the default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub light: Option<String>,
     /// Model used for medium-complexity messages.
     pub standard: String,
     /// Model used for high-complexity messages.
@@ -685,23 +1365,77 @@ pub struct AgentDefaults {
     pub temperature: Option<f32>,
     #[serde(default = "default_max_tool_iterations", rename = "maxToolIterations")]
     pub max_tool_iterations: usize,
+    /// Consecutive clarifying-question replies (without any tool call) allowed
+    /// on action requests before the agent is told to act or explain why it
+    /// can't. 0 disables the budget.
+    #[serde(default, rename = "clarificationBudget")]
+    pub clarification_budget: u32,
+    /// Automatic continuations after a turn hits `maxToolIterations` with
+    /// tool calls still pending. 0 = only resume when the user says "continue".
+    #[serde(default, rename = "autoContinue")]
+    pub auto_continue: u32,
+    /// Per-sender preferences (name, language, timezone, verbosity) set via
+    /// the `set_preference` tool and added to the system prompt.
+    #[serde(default = "super::default_true", rename = "senderPreferences")]
+    pub sender_preferences: bool,
+    /// Record an estimated per-turn token breakdown (system prompt, tool
+    /// definitions, history, current message, tool results, assistant turns)
+    /// for `oxicrab stats tokens --session` and the `token_breakdown` tool.
+    #[serde(default, rename = "tokenBreakdown")]
+    pub token_breakdown: bool,
     #[serde(default)]
     pub compaction: CompactionConfig,
     #[serde(default = "default_session_ttl_days", rename = "sessionTtlDays")]
     pub session_ttl_days: u32,
+    /// Days of inactivity after which a session is archived: compacted to a
+    /// summary (also written to memory) and its message history dropped. The
+    /// next message rehydrates the session from that summary. 0 = off.
+    #[serde(default, rename = "sessionArchiveDays")]
+    pub session_archive_days: u32,
     #[serde(default = "default_media_ttl_days", rename = "mediaTtlDays")]
     pub media_ttl_days: u32,
+    #[serde(default, rename = "inboundMedia")]
+    pub inbound_media: InboundMediaConfig,
     #[serde(
         default = "default_max_concurrent_subagents",
         rename = "maxConcurrentSubagents"
     )]
     pub max_concurrent_subagents: usize,
     #[serde(default)]
+    pub delegation: DelegationConfig,
+    #[serde(default, rename = "offlineMode")]
+    pub offline_mode: OfflineModeConfig,
+    #[serde(default)]
+    pub streaming: StreamingConfig,
+    /// Inbound messages processed at the same time. Messages of one session
+    /// are always handled in order; 1 serializes all sessions.
+    #[serde(
+        default = "default_max_concurrent_sessions",
+        rename = "maxConcurrentSessions"
+    )]
+    pub max_concurrent_sessions: usize,
+    #[serde(default)]
     pub memory: MemoryConfig,
     #[serde(default)]
     pub cognitive: CognitiveConfig,
     #[serde(default, rename = "promptGuard")]
     pub prompt_guard: PromptGuardConfig,
+    #[serde(default, rename = "contentFilter")]
+    pub content_filter: ContentFilterConfig,
+    #[serde(default)]
+    pub reasoning: ReasoningConfig,
+    #[serde(default, rename = "emptyResponse")]
+    pub empty_response: EmptyResponseConfig,
+    #[serde(default, rename = "toolLoop")]
+    pub tool_loop: ToolLoopConfig,
+    #[serde(default, rename = "toolResultSummary")]
+    pub tool_result_summary: ToolResultSummaryConfig,
+    #[serde(default, rename = "messageBatching")]
+    pub message_batching: MessageBatchingConfig,
+    /// Named tool sets a session can switch to with `/focus <name>`, e.g.
+    /// `research = ["web_search", "web_fetch", "memory_search"]`.
+    #[serde(default, rename = "toolPresets")]
+    pub tool_presets: std::collections::HashMap<String, Vec<String>>,
     #[serde(default, rename = "contextProviders")]
     pub context_providers: Vec<ContextProviderConfig>,
     #[serde(default, rename = "workspaceTtl")]
@@ -719,13 +1453,30 @@ impl Default for AgentDefaults {
             max_tokens: default_max_tokens(),
             temperature: default_temperature(),
             max_tool_iterations: default_max_tool_iterations(),
+            clarification_budget: 0,
+            auto_continue: 0,
+            sender_preferences: true,
+            token_breakdown: false,
             compaction: CompactionConfig::default(),
             session_ttl_days: default_session_ttl_days(),
+            session_archive_days: 0,
             media_ttl_days: default_media_ttl_days(),
+            inbound_media: InboundMediaConfig::default(),
             max_concurrent_subagents: default_max_concurrent_subagents(),
+            delegation: DelegationConfig::default(),
+            offline_mode: OfflineModeConfig::default(),
+            streaming: StreamingConfig::default(),
+            max_concurrent_sessions: default_max_concurrent_sessions(),
             memory: MemoryConfig::default(),
             cognitive: CognitiveConfig::default(),
             prompt_guard: PromptGuardConfig::default(),
+            content_filter: ContentFilterConfig::default(),
+            reasoning: ReasoningConfig::default(),
+            empty_response: EmptyResponseConfig::default(),
+            tool_loop: ToolLoopConfig::default(),
+            tool_result_summary: ToolResultSummaryConfig::default(),
+            message_batching: MessageBatchingConfig::default(),
+            tool_presets: std::collections::HashMap::new(),
             context_providers: vec![],
             workspace_ttl: WorkspaceTtlConfig::default(),
             model_routing: ModelRoutingConfig::default(),
@@ -734,9 +1485,24 @@ impl Default for AgentDefaults {
     }
 }
 
+/// Where a context provider's output comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextProviderKind {
+    /// Output of `command` run with `args`.
+    #[default]
+    Command,
+    /// One-line current conditions from `tools.weather`.
+    Weather,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextProviderConfig {
     pub name: String,
+    #[serde(default)]
+    pub kind: ContextProviderKind,
+    /// Required for `kind = "command"`; ignored otherwise.
+    #[serde(default)]
     pub command: String,
     #[serde(default)]
     pub args: Vec<String>,
@@ -744,8 +1510,9 @@ pub struct ContextProviderConfig {
     pub enabled: bool,
     #[serde(default = "default_context_provider_timeout")]
     pub timeout: u64,
-    #[serde(default = "default_context_provider_ttl")]
-    pub ttl: u64,
+    /// Cache lifetime in seconds; see [`Self::effective_ttl`] for the default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
     #[serde(default, rename = "requiresBins")]
     pub requires_bins: Vec<String>,
     #[serde(default, rename = "requiresEnv")]
@@ -756,14 +1523,59 @@ fn default_context_provider_timeout() -> u64 {
     5
 }
 
-fn default_context_provider_ttl() -> u64 {
-    300
+impl ContextProviderConfig {
+    /// Configured `ttl`, or the kind's default: 300s for commands, 30
+    /// minutes for weather.
+    pub fn effective_ttl(&self) -> u64 {
+        self.ttl.unwrap_or(match self.kind {
+            ContextProviderKind::Command => 300,
+            ContextProviderKind::Weather => 30 * 60,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AgentsConfig {
     #[serde(default)]
     pub defaults: AgentDefaults,
+    /// Named workspaces selected by channel and chat. Messages matching no
+    /// profile use `defaults.workspace`.
+    #[serde(default)]
+    pub profiles: Vec<ProfileConfig>,
+}
+
+/// A workspace with its own sessions, memory database and prompt files,
+/// used for messages from the listed channels and chats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    pub name: String,
+    /// Workspace directory. Relative paths resolve under the default
+    /// workspace; unset means `profiles/<name>` next to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Channel names (`telegram`, `slack`, ...). Empty matches any channel.
+    #[serde(default)]
+    pub channels: Vec<String>,
+    /// Chat IDs on those channels; a trailing `*` matches any suffix. Empty
+    /// matches every chat. In direct messages the chat ID is the sender.
+    #[serde(default)]
+    pub chats: Vec<String>,
+}
+
+impl ProfileConfig {
+    /// Whether a message on `channel` in `chat_id` belongs to this profile.
+    pub fn matches(&self, channel: &str, chat_id: &str) -> bool {
+        let channel_ok = self.channels.is_empty() || self.channels.iter().any(|c| c == channel);
+        let chat_ok = self.chats.is_empty()
+            || self
+                .chats
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => chat_id.starts_with(prefix),
+                    None => pattern == chat_id,
+                });
+        channel_ok && chat_ok
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use super::agent::ChannelTarget;
 use serde::{Deserialize, Serialize};
 
 /// An access control list where empty means "deny all".
@@ -46,6 +47,89 @@ impl DenyByDefaultList {
     }
 }
 
+fn default_whatsapp_max_message_length() -> usize {
+    4096
+}
+
+fn default_telegram_max_message_length() -> usize {
+    4096
+}
+
+fn default_discord_max_message_length() -> usize {
+    2000
+}
+
+fn default_slack_max_message_length() -> usize {
+    4000
+}
+
+fn default_slack_dedup_window_secs() -> u64 {
+    3600
+}
+
+fn default_twilio_max_message_length() -> usize {
+    1600
+}
+
+fn default_typing_interval_secs() -> u64 {
+    4
+}
+
+/// How a channel shows that a reply is being worked on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypingIndicatorStyle {
+    /// The platform's typing action (Telegram `sendChatAction`, Discord
+    /// typing), repeated every `intervalSecs` while the reply is generated.
+    #[default]
+    Native,
+    /// One editable status message, removed when the reply is sent. For
+    /// platforms without a bot typing API (Slack).
+    Status,
+}
+
+impl std::fmt::Display for TypingIndicatorStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Native => "native",
+            Self::Status => "status",
+        })
+    }
+}
+
+/// Per-channel typing indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypingIndicatorConfig {
+    #[serde(default = "super::default_true")]
+    pub enabled: bool,
+    /// Seconds between native typing actions (default: 4; Telegram's
+    /// indicator lasts about 5 seconds).
+    #[serde(default = "default_typing_interval_secs", rename = "intervalSecs")]
+    pub interval_secs: u64,
+    #[serde(default)]
+    pub style: TypingIndicatorStyle,
+}
+
+impl Default for TypingIndicatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: default_typing_interval_secs(),
+            style: TypingIndicatorStyle::Native,
+        }
+    }
+}
+
+/// Slack has no typing API for bots, so its indicator is an opt-in status
+/// message.
+fn default_slack_typing_indicator() -> TypingIndicatorConfig {
+    TypingIndicatorConfig {
+        enabled: false,
+        style: TypingIndicatorStyle::Status,
+        ..TypingIndicatorConfig::default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WhatsAppConfig {
     #[serde(default)]
@@ -57,6 +141,23 @@ pub struct WhatsAppConfig {
     pub allow_groups: DenyByDefaultList,
     #[serde(default = "default_dm_policy", rename = "dmPolicy")]
     pub dm_policy: DmPolicy,
+    /// What to do with DMs from unknown senders. Defaults to the behavior
+    /// implied by `dmPolicy` (`pairing` → instruct, `allowlist` → ignore).
+    #[serde(default, rename = "unknownSenderPolicy")]
+    pub unknown_sender_policy: Option<UnknownSenderPolicy>,
+    /// Maximum outbound message size in bytes; longer replies are split into
+    /// several messages.
+    #[serde(
+        default = "default_whatsapp_max_message_length",
+        rename = "maxMessageLength"
+    )]
+    pub max_message_length: usize,
+    #[serde(default, rename = "typingIndicator")]
+    pub typing_indicator: TypingIndicatorConfig,
+    /// Appended to the system prompt for messages on this channel, e.g. a
+    /// tone to use. Empty or unset leaves the prompt unchanged.
+    #[serde(default, rename = "personaSuffix")]
+    pub persona_suffix: Option<String>,
 }
 
 impl Default for WhatsAppConfig {
@@ -66,6 +167,10 @@ impl Default for WhatsAppConfig {
             allow_from: DenyByDefaultList::default(),
             allow_groups: DenyByDefaultList::default(),
             dm_policy: default_dm_policy(),
+            unknown_sender_policy: None,
+            max_message_length: default_whatsapp_max_message_length(),
+            typing_indicator: TypingIndicatorConfig::default(),
+            persona_suffix: None,
         }
     }
 }
@@ -83,9 +188,26 @@ pub struct TelegramConfig {
     pub allow_groups: DenyByDefaultList,
     #[serde(default = "default_dm_policy", rename = "dmPolicy")]
     pub dm_policy: DmPolicy,
+    /// What to do with DMs from unknown senders. Defaults to the behavior
+    /// implied by `dmPolicy` (`pairing` → instruct, `allowlist` → ignore).
+    #[serde(default, rename = "unknownSenderPolicy")]
+    pub unknown_sender_policy: Option<UnknownSenderPolicy>,
     /// When true, only respond in groups when the bot is @mentioned or replied to.
     #[serde(default, rename = "mentionOnly")]
     pub mention_only: bool,
+    /// Maximum outbound message size in bytes; longer replies are split into
+    /// several messages.
+    #[serde(
+        default = "default_telegram_max_message_length",
+        rename = "maxMessageLength"
+    )]
+    pub max_message_length: usize,
+    #[serde(default, rename = "typingIndicator")]
+    pub typing_indicator: TypingIndicatorConfig,
+    /// Appended to the system prompt for messages on this channel, e.g. a
+    /// tone to use. Empty or unset leaves the prompt unchanged.
+    #[serde(default, rename = "personaSuffix")]
+    pub persona_suffix: Option<String>,
 }
 
 impl Default for TelegramConfig {
@@ -96,7 +218,11 @@ impl Default for TelegramConfig {
             allow_from: DenyByDefaultList::default(),
             allow_groups: DenyByDefaultList::default(),
             dm_policy: default_dm_policy(),
+            unknown_sender_policy: None,
             mention_only: false,
+            max_message_length: default_telegram_max_message_length(),
+            typing_indicator: TypingIndicatorConfig::default(),
+            persona_suffix: None,
         }
     }
 }
@@ -108,7 +234,11 @@ redact_debug!(
     allow_from,
     allow_groups,
     dm_policy,
+    unknown_sender_policy,
     mention_only,
+    max_message_length,
+    typing_indicator,
+    persona_suffix,
 );
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -154,9 +284,26 @@ pub struct DiscordConfig {
     pub commands: Vec<DiscordCommand>,
     #[serde(default = "default_dm_policy", rename = "dmPolicy")]
     pub dm_policy: DmPolicy,
+    /// What to do with DMs from unknown senders. Defaults to the behavior
+    /// implied by `dmPolicy` (`pairing` → instruct, `allowlist` → ignore).
+    #[serde(default, rename = "unknownSenderPolicy")]
+    pub unknown_sender_policy: Option<UnknownSenderPolicy>,
     /// When true, only respond in guilds when the bot is @mentioned. DMs are unaffected.
     #[serde(default, rename = "mentionOnly")]
     pub mention_only: bool,
+    /// Maximum outbound message size in bytes; longer replies are split into
+    /// several messages.
+    #[serde(
+        default = "default_discord_max_message_length",
+        rename = "maxMessageLength"
+    )]
+    pub max_message_length: usize,
+    #[serde(default, rename = "typingIndicator")]
+    pub typing_indicator: TypingIndicatorConfig,
+    /// Appended to the system prompt for messages on this channel, e.g. a
+    /// tone to use. Empty or unset leaves the prompt unchanged.
+    #[serde(default, rename = "personaSuffix")]
+    pub persona_suffix: Option<String>,
 }
 
 impl Default for DiscordConfig {
@@ -168,7 +315,11 @@ impl Default for DiscordConfig {
             allow_groups: DenyByDefaultList::default(),
             commands: default_discord_commands(),
             dm_policy: default_dm_policy(),
+            unknown_sender_policy: None,
             mention_only: false,
+            max_message_length: default_discord_max_message_length(),
+            typing_indicator: TypingIndicatorConfig::default(),
+            persona_suffix: None,
         }
     }
 }
@@ -181,7 +332,11 @@ redact_debug!(
     allow_groups,
     commands,
     dm_policy,
+    unknown_sender_policy,
     mention_only,
+    max_message_length,
+    typing_indicator,
+    persona_suffix,
 );
 
 fn default_thinking_emoji() -> String {
@@ -207,12 +362,37 @@ pub struct SlackConfig {
     pub allow_groups: DenyByDefaultList,
     #[serde(default = "default_dm_policy", rename = "dmPolicy")]
     pub dm_policy: DmPolicy,
+    /// What to do with DMs from unknown senders. Defaults to the behavior
+    /// implied by `dmPolicy` (`pairing` → instruct, `allowlist` → ignore).
+    #[serde(default, rename = "unknownSenderPolicy")]
+    pub unknown_sender_policy: Option<UnknownSenderPolicy>,
     /// Emoji added when a message is received (default: "eyes")
     #[serde(default = "default_thinking_emoji", rename = "thinkingEmoji")]
     pub thinking_emoji: String,
     /// Emoji added after response is sent (default: `white_check_mark`).
     #[serde(default = "default_done_emoji", rename = "doneEmoji")]
     pub done_emoji: String,
+    /// Maximum outbound message size in bytes; longer replies are split into
+    /// several messages.
+    #[serde(
+        default = "default_slack_max_message_length",
+        rename = "maxMessageLength"
+    )]
+    pub max_message_length: usize,
+    /// How long a received message id is remembered to drop Slack's
+    /// redelivery retries of the same event (default: 1 hour).
+    #[serde(
+        default = "default_slack_dedup_window_secs",
+        rename = "dedupWindowSecs"
+    )]
+    pub dedup_window_secs: u64,
+    /// Off by default: the thinking emoji already marks received messages.
+    #[serde(default = "default_slack_typing_indicator", rename = "typingIndicator")]
+    pub typing_indicator: TypingIndicatorConfig,
+    /// Appended to the system prompt for messages on this channel, e.g. a
+    /// tone to use. Empty or unset leaves the prompt unchanged.
+    #[serde(default, rename = "personaSuffix")]
+    pub persona_suffix: Option<String>,
 }
 
 impl Default for SlackConfig {
@@ -224,8 +404,13 @@ impl Default for SlackConfig {
             allow_from: DenyByDefaultList::default(),
             allow_groups: DenyByDefaultList::default(),
             dm_policy: default_dm_policy(),
+            unknown_sender_policy: None,
             thinking_emoji: default_thinking_emoji(),
             done_emoji: default_done_emoji(),
+            max_message_length: default_slack_max_message_length(),
+            dedup_window_secs: default_slack_dedup_window_secs(),
+            typing_indicator: default_slack_typing_indicator(),
+            persona_suffix: None,
         }
     }
 }
@@ -238,8 +423,13 @@ redact_debug!(
     allow_from,
     allow_groups,
     dm_policy,
+    unknown_sender_policy,
     thinking_emoji,
     done_emoji,
+    max_message_length,
+    dedup_window_secs,
+    typing_indicator,
+    persona_suffix,
 );
 
 fn default_webhook_port() -> u16 {
@@ -277,6 +467,40 @@ impl std::fmt::Display for DmPolicy {
     }
 }
 
+/// How to respond to a DM from a sender that `dmPolicy` does not admit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnknownSenderPolicy {
+    /// Drop the message silently.
+    Ignore,
+    /// Reply with a pairing code and approval instructions.
+    Instruct,
+    /// Issue a pairing code and forward the sender's ID and message to
+    /// `channels.adminTarget`; nothing is sent back to the sender.
+    NotifyAdmin,
+}
+
+impl UnknownSenderPolicy {
+    /// Effective policy for a channel: the configured value, or the legacy
+    /// behavior implied by `dm_policy` when unset.
+    pub fn resolve(configured: Option<Self>, dm_policy: &DmPolicy) -> Self {
+        configured.unwrap_or(match dm_policy {
+            DmPolicy::Pairing => Self::Instruct,
+            DmPolicy::Allowlist | DmPolicy::Open => Self::Ignore,
+        })
+    }
+}
+
+impl std::fmt::Display for UnknownSenderPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ignore => write!(f, "ignore"),
+            Self::Instruct => write!(f, "instruct"),
+            Self::NotifyAdmin => write!(f, "notifyAdmin"),
+        }
+    }
+}
+
 fn default_dm_policy() -> DmPolicy {
     DmPolicy::default()
 }
@@ -306,6 +530,21 @@ pub struct TwilioConfig {
     pub allow_groups: DenyByDefaultList,
     #[serde(default = "default_dm_policy", rename = "dmPolicy")]
     pub dm_policy: DmPolicy,
+    /// What to do with DMs from unknown senders. Defaults to the behavior
+    /// implied by `dmPolicy` (`pairing` → instruct, `allowlist` → ignore).
+    #[serde(default, rename = "unknownSenderPolicy")]
+    pub unknown_sender_policy: Option<UnknownSenderPolicy>,
+    /// Maximum outbound message size in bytes; longer replies are split into
+    /// several messages.
+    #[serde(
+        default = "default_twilio_max_message_length",
+        rename = "maxMessageLength"
+    )]
+    pub max_message_length: usize,
+    /// Appended to the system prompt for messages on this channel, e.g. a
+    /// tone to use. Empty or unset leaves the prompt unchanged.
+    #[serde(default, rename = "personaSuffix")]
+    pub persona_suffix: Option<String>,
 }
 
 impl Default for TwilioConfig {
@@ -322,6 +561,9 @@ impl Default for TwilioConfig {
             allow_from: DenyByDefaultList::default(),
             allow_groups: DenyByDefaultList::default(),
             dm_policy: default_dm_policy(),
+            unknown_sender_policy: None,
+            max_message_length: default_twilio_max_message_length(),
+            persona_suffix: None,
         }
     }
 }
@@ -339,6 +581,9 @@ redact_debug!(
     allow_from,
     allow_groups,
     dm_policy,
+    unknown_sender_policy,
+    max_message_length,
+    persona_suffix,
 );
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -353,4 +598,189 @@ pub struct ChannelsConfig {
     pub slack: SlackConfig,
     #[serde(default)]
     pub twilio: TwilioConfig,
+    /// Where unknown-sender notifications go (`"channel_type:chat_id"`),
+    /// used by channels with `unknownSenderPolicy = "notifyAdmin"`.
+    #[serde(default, rename = "adminTarget")]
+    pub admin_target: Option<ChannelTarget>,
+    /// Senders (`"channel:sender_id"`) allowed to manage pairing from chat
+    /// with the `pairing` tool. Empty = the tool is not registered.
+    #[serde(default, rename = "adminSenders")]
+    pub admin_senders: Vec<String>,
+    #[serde(default, rename = "sendRetry")]
+    pub send_retry: SendRetryConfig,
+    #[serde(default, rename = "rateLimit")]
+    pub rate_limit: ChannelRateLimitConfig,
+    #[serde(default, rename = "quietHours")]
+    pub quiet_hours: QuietHoursConfig,
+}
+
+impl ChannelsConfig {
+    /// Names of the channels enabled in config.
+    pub fn enabled_names(&self) -> Vec<&'static str> {
+        [
+            ("telegram", self.telegram.enabled),
+            ("discord", self.discord.enabled),
+            ("slack", self.slack.enabled),
+            ("whatsapp", self.whatsapp.enabled),
+            ("twilio", self.twilio.enabled),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+    }
+
+    /// Non-empty `personaSuffix` of each channel, by channel name.
+    pub fn persona_suffixes(&self) -> std::collections::HashMap<String, String> {
+        [
+            ("whatsapp", &self.whatsapp.persona_suffix),
+            ("telegram", &self.telegram.persona_suffix),
+            ("discord", &self.discord.persona_suffix),
+            ("slack", &self.slack.persona_suffix),
+            ("twilio", &self.twilio.persona_suffix),
+        ]
+        .into_iter()
+        .filter_map(|(name, suffix)| {
+            let suffix = suffix.as_deref()?.trim();
+            (!suffix.is_empty()).then(|| (name.to_string(), suffix.to_string()))
+        })
+        .collect()
+    }
+
+    /// Typing indicator settings of each channel that has them, by channel name.
+    pub fn typing_indicators(&self) -> std::collections::HashMap<String, TypingIndicatorConfig> {
+        std::collections::HashMap::from([
+            ("whatsapp".to_string(), self.whatsapp.typing_indicator),
+            ("telegram".to_string(), self.telegram.typing_indicator),
+            ("discord".to_string(), self.discord.typing_indicator),
+            ("slack".to_string(), self.slack.typing_indicator),
+        ])
+    }
+}
+
+/// Retry policy for outbound sends. Transient failures are retried with
+/// exponential backoff; messages that still fail are recorded in the outbound
+/// failure log (`oxicrab audit outbound`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendRetryConfig {
+    /// Total send attempts per message, including the first (1 disables retry).
+    #[serde(default = "default_send_retry_max_attempts", rename = "maxAttempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled on each further retry.
+    #[serde(
+        default = "default_send_retry_initial_delay_ms",
+        rename = "initialDelayMs"
+    )]
+    pub initial_delay_ms: u64,
+    /// Upper bound on the delay between attempts.
+    #[serde(default = "default_send_retry_max_delay_ms", rename = "maxDelayMs")]
+    pub max_delay_ms: u64,
+}
+
+impl Default for SendRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_send_retry_max_attempts(),
+            initial_delay_ms: default_send_retry_initial_delay_ms(),
+            max_delay_ms: default_send_retry_max_delay_ms(),
+        }
+    }
+}
+
+impl SendRetryConfig {
+    /// Delay before attempt `attempt + 1`, where `attempt` (1-based) just failed.
+    pub fn delay_after(&self, attempt: u32) -> std::time::Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        std::time::Duration::from_millis(
+            self.initial_delay_ms
+                .saturating_mul(factor)
+                .min(self.max_delay_ms),
+        )
+    }
+}
+
+/// Outbound pacing per chat: a token bucket per `(channel, chat_id)` that
+/// delays sends beyond the allowance instead of dropping them. Status lines
+/// are not counted.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChannelRateLimitConfig {
+    /// Sustained sends per minute to one chat (0 disables pacing).
+    #[serde(default, rename = "messagesPerMinute")]
+    pub messages_per_minute: u32,
+    /// Sends allowed back to back before pacing starts.
+    #[serde(default = "default_rate_limit_burst")]
+    pub burst: u32,
+}
+
+impl Default for ChannelRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            messages_per_minute: 0,
+            burst: default_rate_limit_burst(),
+        }
+    }
+}
+
+/// Hours during which background messages (cron jobs, digests) are held
+/// and delivered when the period ends. Replies to the user are never held.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHoursConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Local time quiet hours begin (`HH:MM`).
+    #[serde(default = "default_quiet_hours_start")]
+    pub start: String,
+    /// Local time quiet hours end (`HH:MM`). May be earlier than `start`
+    /// for a period that spans midnight.
+    #[serde(default = "default_quiet_hours_end")]
+    pub end: String,
+    /// IANA timezone of `start` and `end`. Defaults to the system timezone.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Channels the quiet hours apply to. Empty = all channels.
+    #[serde(default)]
+    pub channels: Vec<String>,
+}
+
+impl Default for QuietHoursConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: default_quiet_hours_start(),
+            end: default_quiet_hours_end(),
+            timezone: None,
+            channels: Vec::new(),
+        }
+    }
+}
+
+impl QuietHoursConfig {
+    /// `start` and `end` parsed as times of day.
+    pub fn times(&self) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+        let parse = |t: &str| chrono::NaiveTime::parse_from_str(t.trim(), "%H:%M").ok();
+        Some((parse(&self.start)?, parse(&self.end)?))
+    }
+}
+
+fn default_quiet_hours_start() -> String {
+    "22:00".to_string()
+}
+
+fn default_quiet_hours_end() -> String {
+    "07:00".to_string()
+}
+
+fn default_rate_limit_burst() -> u32 {
+    3
+}
+
+fn default_send_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_send_retry_initial_delay_ms() -> u64 {
+    1000
+}
+
+fn default_send_retry_max_delay_ms() -> u64 {
+    30_000
 }
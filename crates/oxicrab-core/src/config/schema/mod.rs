@@ -91,6 +91,10 @@ fn default_port() -> u16 {
     18790
 }
 
+fn default_drain_timeout_secs() -> u64 {
+    30
+}
+
 fn default_rps() -> u32 {
     10
 }
@@ -149,6 +153,16 @@ pub struct GatewayConfig {
     pub a2a: A2aConfig,
     #[serde(default, rename = "rateLimit")]
     pub rate_limit: RateLimitConfig,
+    /// Seconds to wait on shutdown for the in-flight agent turn to finish and
+    /// its response to be handed to the channels. 0 stops immediately.
+    #[serde(default = "default_drain_timeout_secs", rename = "drainTimeoutSecs")]
+    pub drain_timeout_secs: u64,
+    /// HTTP API tenants keyed by tenant id. Each is served by its own agent
+    /// loop with an isolated workspace, memory database, and sessions.
+    #[serde(default)]
+    pub tenants: HashMap<String, TenantConfig>,
+    #[serde(default)]
+    pub metrics: GatewayMetricsConfig,
 }
 
 impl std::fmt::Debug for GatewayConfig {
@@ -168,6 +182,9 @@ impl std::fmt::Debug for GatewayConfig {
             .field("webhooks", &self.webhooks)
             .field("a2a", &self.a2a)
             .field("rate_limit", &self.rate_limit)
+            .field("drain_timeout_secs", &self.drain_timeout_secs)
+            .field("tenants", &self.tenants)
+            .field("metrics", &self.metrics)
             .finish()
     }
 }
@@ -182,10 +199,40 @@ impl Default for GatewayConfig {
             webhooks: HashMap::new(),
             a2a: A2aConfig::default(),
             rate_limit: RateLimitConfig::default(),
+            drain_timeout_secs: default_drain_timeout_secs(),
+            tenants: HashMap::new(),
+            metrics: GatewayMetricsConfig::default(),
         }
     }
 }
 
+/// Prometheus scrape endpoint served by the gateway itself.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GatewayMetricsConfig {
+    /// Serve `GET /metrics` on the gateway port. Requires the API key when
+    /// one is configured.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// A gateway tenant: requests to `/api/chat` authenticated with `api_key`
+/// are handled by a dedicated agent loop whose memory, sessions, and files
+/// live in the tenant's own workspace.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TenantConfig {
+    /// Bearer / `X-API-Key` credential identifying this tenant.
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    /// Workspace directory. Defaults to `<workspace>/tenants/<id>`.
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Model for this tenant's agent. Defaults to the operator's model.
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+redact_debug!(TenantConfig, redact(api_key), workspace, model,);
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct A2aConfig {
     #[serde(default)]
@@ -205,7 +252,12 @@ pub struct WebhookConfig {
     #[serde(default = "default_true")]
     pub enabled: bool,
     /// HMAC-SHA256 secret for signature validation.
+    #[serde(default)]
     pub secret: String,
+    /// Signature scheme with its own secret. Takes the place of `secret`
+    /// for senders with a fixed header format (e.g. GitHub).
+    #[serde(default)]
+    pub auth: Option<WebhookAuth>,
     /// Template for the message sent to the agent. Use `{{key}}` for JSON payload fields,
     /// `{{body}}` for the raw body.
     #[serde(default = "default_webhook_template")]
@@ -226,17 +278,68 @@ redact_debug!(
     WebhookConfig,
     enabled,
     redact(secret),
+    auth,
     template,
     targets,
     agent_turn,
     dispatch,
 );
 
+impl WebhookConfig {
+    /// The signature scheme to verify requests with: `auth` when set,
+    /// otherwise the generic HMAC scheme keyed by `secret`.
+    pub fn resolved_auth(&self) -> WebhookAuth {
+        self.auth
+            .clone()
+            .unwrap_or_else(|| WebhookAuth::HmacSha256 {
+                secret: self.secret.clone(),
+            })
+    }
+}
+
+/// Webhook signature verification scheme, selected by `scheme`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "scheme")]
+pub enum WebhookAuth {
+    /// Hex HMAC-SHA256 of the body in `X-Signature-256`, `X-Hub-Signature-256`
+    /// or `X-Webhook-Signature`, with an optional `sha256=` prefix.
+    #[serde(rename = "hmac-sha256")]
+    HmacSha256 { secret: String },
+    /// GitHub's `X-Hub-Signature-256: sha256=<hex hmac>` header only.
+    /// Mismatches are answered with an empty 401.
+    #[serde(rename = "github-sha256")]
+    GithubSha256 { secret: String },
+}
+
+impl WebhookAuth {
+    pub fn secret(&self) -> &str {
+        match self {
+            Self::HmacSha256 { secret } | Self::GithubSha256 { secret } => secret,
+        }
+    }
+}
+
+impl std::fmt::Debug for WebhookAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scheme = match self {
+            Self::HmacSha256 { .. } => "HmacSha256",
+            Self::GithubSha256 { .. } => "GithubSha256",
+        };
+        let secret = if self.secret().is_empty() {
+            "[empty]"
+        } else {
+            "[REDACTED]"
+        };
+        f.debug_struct(scheme).field("secret", &secret).finish()
+    }
+}
+
 impl Default for WebhookConfig {
     fn default() -> Self {
         Self {
             enabled: true,
             secret: String::new(),
+            auth: None,
             template: default_webhook_template(),
             targets: vec![],
             agent_turn: false,
@@ -318,6 +421,8 @@ pub struct Config {
     #[serde(default)]
     pub providers: ProvidersConfig,
     #[serde(default)]
+    pub models: ModelsConfig,
+    #[serde(default)]
     pub gateway: GatewayConfig,
     #[serde(default)]
     pub tools: ToolsConfig,
@@ -336,20 +441,62 @@ impl Config {
         crate::utils::get_workspace_path(&self.agents.defaults.workspace)
     }
 
+    /// Workspace of gateway tenant `id`: its own `workspace`, else
+    /// `<workspace>/tenants/<id>`.
+    pub fn tenant_workspace_path(&self, id: &str, tenant: &TenantConfig) -> PathBuf {
+        tenant.workspace.as_deref().map_or_else(
+            || self.workspace_path().join("tenants").join(id),
+            crate::utils::get_workspace_path,
+        )
+    }
+
     /// Validate configuration values
     pub fn validate(&self) -> Result<(), crate::errors::OxicrabError> {
         self.validate_agent_defaults()?;
         self.validate_compaction()?;
+        self.validate_content_filter()?;
         self.validate_memory()?;
         self.validate_cognitive()?;
         self.validate_gateway()?;
         self.validate_router()?;
         self.validate_tools()?;
         self.validate_channels()?;
+        self.validate_model_aliases()?;
         self.validate_model_routing()?;
         self.validate_provider_temperatures()?;
+        self.validate_provider_retry()?;
+        self.validate_provider_failover()?;
         self.validate_observability()?;
         self.validate_context_providers()?;
+        self.validate_profiles()?;
+        Ok(())
+    }
+
+    fn validate_profiles(&self) -> Result<(), crate::errors::OxicrabError> {
+        use crate::errors::OxicrabError;
+        let mut seen = std::collections::HashSet::new();
+        for profile in &self.agents.profiles {
+            let name = &profile.name;
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(OxicrabError::Config(format!(
+                    "agents.profiles: name '{name}' must be non-empty and use only letters, digits, '-' and '_'"
+                )));
+            }
+            if name == "default" || !seen.insert(name.as_str()) {
+                return Err(OxicrabError::Config(format!(
+                    "agents.profiles: name '{name}' is reserved or used twice"
+                )));
+            }
+            if profile.channels.is_empty() && profile.chats.is_empty() {
+                return Err(OxicrabError::Config(format!(
+                    "agents.profiles.{name}: set channels or chats, otherwise it would take every message"
+                )));
+            }
+        }
         Ok(())
     }
 
@@ -384,6 +531,126 @@ impl Config {
                 "agents.defaults.maxToolIterations is unreasonably large (> 1000)".into(),
             ));
         }
+        if !(1..=64).contains(&d.max_concurrent_sessions) {
+            return Err(OxicrabError::Config(
+                "agents.defaults.maxConcurrentSessions must be between 1 and 64".into(),
+            ));
+        }
+        if d.inbound_media.cache_descriptions && d.inbound_media.description_ttl_days == 0 {
+            return Err(OxicrabError::Config(
+                "agents.defaults.inboundMedia.descriptionTtlDays must be > 0 when \
+                 cacheDescriptions is enabled"
+                    .into(),
+            ));
+        }
+        if !(1..=100).contains(&d.inbound_media.jpeg_quality) {
+            return Err(OxicrabError::Config(
+                "agents.defaults.inboundMedia.jpegQuality must be between 1 and 100".into(),
+            ));
+        }
+        if d.session_archive_days > 0 {
+            if !d.compaction.enabled {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.sessionArchiveDays requires compaction.enabled".into(),
+                ));
+            }
+            if d.session_ttl_days > 0 && d.session_archive_days >= d.session_ttl_days {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.sessionArchiveDays must be less than sessionTtlDays, \
+                     or sessions are deleted before they can be archived"
+                        .into(),
+                ));
+            }
+        }
+        let empty = &d.empty_response;
+        if empty.action == EmptyResponseAction::Fallback
+            && empty.fallback_model.as_deref().is_none_or(str::is_empty)
+        {
+            return Err(OxicrabError::Config(
+                "agents.defaults.emptyResponse.fallbackModel must be set when action is \"fallback\""
+                    .into(),
+            ));
+        }
+        if d.tool_loop.threshold == 1 {
+            return Err(OxicrabError::Config(
+                "agents.defaults.toolLoop.threshold must be 0 (off) or at least 2".into(),
+            ));
+        }
+        let summary = &d.tool_result_summary;
+        if summary.enabled && summary.threshold_chars == 0 {
+            return Err(OxicrabError::Config(
+                "agents.defaults.toolResultSummary.thresholdChars must be greater than 0".into(),
+            ));
+        }
+        let batching = &d.message_batching;
+        if batching.window_ms > 0 && batching.max_wait_ms < batching.window_ms {
+            return Err(OxicrabError::Config(
+                "agents.defaults.messageBatching.maxWaitMs must be at least windowMs".into(),
+            ));
+        }
+        for (name, tools) in &d.tool_presets {
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(OxicrabError::Config(format!(
+                    "agents.defaults.toolPresets: invalid preset name '{name}' (must be a single word)"
+                )));
+            }
+            if tools.is_empty() {
+                return Err(OxicrabError::Config(format!(
+                    "agents.defaults.toolPresets.{name} must list at least one tool"
+                )));
+            }
+        }
+        let offline = &d.offline_mode;
+        if offline.enabled {
+            if !self.providers.circuit_breaker.enabled {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.offlineMode requires providers.circuitBreaker.enabled".into(),
+                ));
+            }
+            if offline.retry_interval_secs == 0 || offline.max_queued == 0 {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.offlineMode.retryIntervalSecs and maxQueued must be greater than 0"
+                        .into(),
+                ));
+            }
+        }
+        if d.streaming.enabled && d.streaming.edit_interval_ms == 0 {
+            return Err(OxicrabError::Config(
+                "agents.defaults.streaming.editIntervalMs must be greater than 0".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_content_filter(&self) -> Result<(), crate::errors::OxicrabError> {
+        use crate::errors::OxicrabError;
+        let f = &self.agents.defaults.content_filter;
+
+        if !f.enabled {
+            return Ok(());
+        }
+        if f.rules.is_empty() && f.max_length.is_none() {
+            return Err(OxicrabError::Config(
+                "agents.defaults.contentFilter is enabled but has no rules or maxLength".into(),
+            ));
+        }
+        if f.max_length == Some(0) {
+            return Err(OxicrabError::Config(
+                "agents.defaults.contentFilter.maxLength must be > 0".into(),
+            ));
+        }
+        for (i, rule) in f.rules.iter().enumerate() {
+            if rule.pattern.is_empty() {
+                return Err(OxicrabError::Config(format!(
+                    "agents.defaults.contentFilter.rules[{i}].pattern must not be empty"
+                )));
+            }
+            if let Err(e) = regex::Regex::new(&rule.pattern) {
+                return Err(OxicrabError::Config(format!(
+                    "agents.defaults.contentFilter.rules[{i}].pattern is not a valid regex: {e}"
+                )));
+            }
+        }
         Ok(())
     }
 
@@ -402,6 +669,28 @@ impl Config {
                     "agents.defaults.compaction.keepRecent must be > 0 when enabled".into(),
                 ));
             }
+            if let Some(max) = c.max_messages
+                && max <= c.keep_recent
+            {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.compaction.maxMessages must be greater than keepRecent".into(),
+                ));
+            }
+            if c.extraction_enabled
+                && c.extraction_interval.turns == 0
+                && c.extraction_interval.minutes == 0
+            {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.compaction.extractionInterval needs turns or minutes > 0 \
+                     when extraction is enabled"
+                        .into(),
+                ));
+            }
+        }
+        if c.max_history_tokens == Some(0) {
+            return Err(OxicrabError::Config(
+                "agents.defaults.compaction.maxHistoryTokens must be > 0".into(),
+            ));
         }
         Ok(())
     }
@@ -425,6 +714,37 @@ impl Config {
                     .into(),
             ));
         }
+        if m.embeddings_enabled && m.embeddings_backend == EmbeddingsBackend::OpenAi {
+            if self.providers.openai.api_key.is_empty() {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.memory.embeddingsBackend = \"openai\" requires providers.openai.apiKey"
+                        .into(),
+                ));
+            }
+            if m.embeddings_model.starts_with("BAAI/") {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.memory.embeddingsModel must be an OpenAI embedding model (e.g. text-embedding-3-small) with the openai backend"
+                        .into(),
+                ));
+            }
+        }
+        let g = &m.gardener;
+        if g.enabled {
+            if g.interval_hours == 0 {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.memory.gardener.intervalHours must be > 0 when enabled".into(),
+                ));
+            }
+            if !g.similarity_threshold.is_finite()
+                || g.similarity_threshold <= 0.0
+                || g.similarity_threshold > 1.0
+            {
+                return Err(OxicrabError::Config(
+                    "agents.defaults.memory.gardener.similarityThreshold must be in (0.0, 1.0]"
+                        .into(),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -513,11 +833,43 @@ impl Config {
                 )));
             }
         }
+        if !self.gateway.tenants.is_empty() && self.gateway.api_key.is_empty() {
+            return Err(OxicrabError::Config(
+                "gateway.apiKey is required when gateway.tenants is set".into(),
+            ));
+        }
+        let mut tenant_keys = std::collections::HashSet::new();
+        for (id, tenant) in &self.gateway.tenants {
+            if id.is_empty()
+                || !id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(OxicrabError::Config(format!(
+                    "gateway.tenants: id '{id}' must contain only letters, digits, '-' and '_'"
+                )));
+            }
+            if tenant.api_key.trim().is_empty() {
+                return Err(OxicrabError::Config(format!(
+                    "gateway.tenants.{id}.apiKey is required"
+                )));
+            }
+            if tenant.api_key == self.gateway.api_key || !tenant_keys.insert(&tenant.api_key) {
+                return Err(OxicrabError::Config(format!(
+                    "gateway.tenants.{id}.apiKey must differ from gateway.apiKey and other tenants' keys"
+                )));
+            }
+        }
         for (name, webhook) in &self.gateway.webhooks {
             if !webhook.enabled {
                 continue;
             }
-            if webhook.secret.trim().is_empty() {
+            if webhook.auth.is_some() && !webhook.secret.is_empty() {
+                return Err(OxicrabError::Config(format!(
+                    "gateway.webhooks.{name}: set either secret or auth, not both"
+                )));
+            }
+            if webhook.resolved_auth().secret().trim().is_empty() {
                 return Err(OxicrabError::Config(format!(
                     "gateway.webhooks.{name}.secret is required when webhook is enabled"
                 )));
@@ -645,6 +997,49 @@ impl Config {
         if self.tools.web_search.max_results > 100 {
             warn!("tools.web_search.maxResults is very large (> 100), this may be slow");
         }
+        for (tool, limit) in &self.tools.rate_limits {
+            if limit.max_calls == 0 || limit.window_secs == 0 {
+                return Err(OxicrabError::Config(format!(
+                    "tools.rateLimits.{tool}: maxCalls and windowSecs must be > 0"
+                )));
+            }
+        }
+        for (tool, secs) in &self.tools.timeouts {
+            if *secs == 0 {
+                return Err(OxicrabError::Config(format!(
+                    "tools.timeouts.{tool} must be > 0"
+                )));
+            }
+        }
+        if self.tools.default_timeout_secs == Some(0) {
+            return Err(OxicrabError::Config(
+                "tools.defaultTimeoutSecs must be > 0".into(),
+            ));
+        }
+        if self.tools.scratchpad.enabled && self.tools.scratchpad.max_chars == 0 {
+            return Err(OxicrabError::Config(
+                "tools.scratchpad.maxChars must be > 0 when the scratchpad is enabled".into(),
+            ));
+        }
+        let weather = &self.tools.weather;
+        if weather.alerts.enabled {
+            if weather.locations.is_empty() {
+                return Err(OxicrabError::Config(
+                    "tools.weather.locations is required when weather alerts are enabled".into(),
+                ));
+            }
+            if weather.alerts.targets.is_empty() {
+                return Err(OxicrabError::Config(
+                    "tools.weather.alerts.targets is required when weather alerts are enabled"
+                        .into(),
+                ));
+            }
+            if weather.alerts.interval_minutes == 0 {
+                return Err(OxicrabError::Config(
+                    "tools.weather.alerts.intervalMinutes must be > 0".into(),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -672,6 +1067,11 @@ impl Config {
                     "channels.slack.appToken is required when slack is enabled".into(),
                 ));
             }
+            if self.channels.slack.dedup_window_secs == 0 {
+                return Err(OxicrabError::Config(
+                    "channels.slack.dedupWindowSecs must be > 0".into(),
+                ));
+            }
         }
 
         let tw = &self.channels.twilio;
@@ -702,6 +1102,123 @@ impl Config {
                 ));
             }
         }
+
+        let ch = &self.channels;
+        for (name, enabled, policy) in [
+            (
+                "telegram",
+                ch.telegram.enabled,
+                ch.telegram.unknown_sender_policy,
+            ),
+            (
+                "discord",
+                ch.discord.enabled,
+                ch.discord.unknown_sender_policy,
+            ),
+            ("slack", ch.slack.enabled, ch.slack.unknown_sender_policy),
+            (
+                "whatsapp",
+                ch.whatsapp.enabled,
+                ch.whatsapp.unknown_sender_policy,
+            ),
+            ("twilio", ch.twilio.enabled, ch.twilio.unknown_sender_policy),
+        ] {
+            if enabled
+                && policy == Some(UnknownSenderPolicy::NotifyAdmin)
+                && ch.admin_target.is_none()
+            {
+                return Err(OxicrabError::Config(format!(
+                    "channels.adminTarget is required when channels.{name}.unknownSenderPolicy is \"notifyAdmin\""
+                )));
+            }
+        }
+
+        if !(1..=10).contains(&ch.send_retry.max_attempts) {
+            return Err(OxicrabError::Config(
+                "channels.sendRetry.maxAttempts must be between 1 and 10".into(),
+            ));
+        }
+        if ch.send_retry.max_delay_ms < ch.send_retry.initial_delay_ms {
+            return Err(OxicrabError::Config(
+                "channels.sendRetry.maxDelayMs must be >= initialDelayMs".into(),
+            ));
+        }
+        if ch.rate_limit.messages_per_minute > 0 && ch.rate_limit.burst == 0 {
+            return Err(OxicrabError::Config(
+                "channels.rateLimit.burst must be > 0 when messagesPerMinute is set".into(),
+            ));
+        }
+        if ch.quiet_hours.enabled {
+            match ch.quiet_hours.times() {
+                None => {
+                    return Err(OxicrabError::Config(
+                        "channels.quietHours.start and end must be HH:MM times".into(),
+                    ));
+                }
+                Some((start, end)) if start == end => {
+                    return Err(OxicrabError::Config(
+                        "channels.quietHours.start and end must differ".into(),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+
+        // Platform hard caps on a single message
+        for (name, len, cap) in [
+            ("telegram", ch.telegram.max_message_length, 4096),
+            ("discord", ch.discord.max_message_length, 2000),
+            ("slack", ch.slack.max_message_length, 40_000),
+            ("whatsapp", ch.whatsapp.max_message_length, 65_536),
+            ("twilio", ch.twilio.max_message_length, 1600),
+        ] {
+            if len == 0 || len > cap {
+                return Err(OxicrabError::Config(format!(
+                    "channels.{name}.maxMessageLength must be between 1 and {cap}"
+                )));
+            }
+        }
+        for sender in &ch.admin_senders {
+            if !sender
+                .split_once(':')
+                .is_some_and(|(channel, id)| !channel.is_empty() && !id.is_empty())
+            {
+                return Err(OxicrabError::Config(format!(
+                    "channels.adminSenders entry '{sender}' must be 'channel:sender_id'"
+                )));
+            }
+        }
+        for (name, typing) in ch.typing_indicators() {
+            if typing.enabled && typing.interval_secs == 0 {
+                return Err(OxicrabError::Config(format!(
+                    "channels.{name}.typingIndicator.intervalSecs must be > 0"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_model_aliases(&self) -> Result<(), crate::errors::OxicrabError> {
+        use crate::errors::OxicrabError;
+
+        for (alias, target) in &self.models.aliases {
+            if alias.is_empty() || alias.contains('/') {
+                return Err(OxicrabError::Config(format!(
+                    "models.aliases: alias '{alias}' must be a non-empty name without '/'"
+                )));
+            }
+            if target.is_empty() {
+                return Err(OxicrabError::Config(format!(
+                    "models.aliases.{alias} must not be empty"
+                )));
+            }
+            if self.models.aliases.contains_key(target) {
+                return Err(OxicrabError::Config(format!(
+                    "models.aliases.{alias} points to another alias '{target}'; \
+                     aliases must name a model directly"
+                )));
+            }
+        }
         Ok(())
     }
 
@@ -761,6 +1278,17 @@ impl Config {
                     }
 
                     // Models must be non-empty
+                    if chat_config
+                        .models
+                        .light
+                        .as_deref()
+                        .is_some_and(str::is_empty)
+                    {
+                        return Err(OxicrabError::Config(
+                            "modelRouting.tasks.chat.models.light must not be empty when set"
+                                .to_string(),
+                        ));
+                    }
                     if chat_config.models.standard.is_empty() {
                         return Err(OxicrabError::Config(
                             "modelRouting.tasks.chat.models.standard must not be empty".to_string(),
@@ -840,6 +1368,44 @@ impl Config {
         Ok(())
     }
 
+    fn validate_provider_failover(&self) -> Result<(), crate::errors::OxicrabError> {
+        let failover = &self.providers.failover;
+        let Some(secondary) = failover.secondary.as_deref() else {
+            return Ok(());
+        };
+        if !is_known_provider(secondary) {
+            return Err(crate::errors::OxicrabError::Config(format!(
+                "providers.failover.secondary: unknown provider '{secondary}'"
+            )));
+        }
+        if failover.secondary_model_ref().is_none() {
+            return Err(crate::errors::OxicrabError::Config(format!(
+                "providers.failover.model is required for secondary provider '{secondary}'"
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_provider_retry(&self) -> Result<(), crate::errors::OxicrabError> {
+        let retry = &self.providers.retry;
+        if retry.max_retries > 10 {
+            return Err(crate::errors::OxicrabError::Config(
+                "providers.retry.maxRetries must be at most 10".into(),
+            ));
+        }
+        if retry.base_delay_ms == 0 || retry.max_delay_ms < retry.base_delay_ms {
+            return Err(crate::errors::OxicrabError::Config(
+                "providers.retry.baseDelayMs must be > 0 and at most maxDelayMs".into(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&retry.jitter) {
+            return Err(crate::errors::OxicrabError::Config(
+                "providers.retry.jitter must be between 0.0 and 1.0".into(),
+            ));
+        }
+        Ok(())
+    }
+
     fn validate_context_providers(&self) -> Result<(), crate::errors::OxicrabError> {
         use crate::errors::OxicrabError;
 
@@ -848,6 +1414,20 @@ impl Config {
         }
 
         for (i, cp) in self.agents.defaults.context_providers.iter().enumerate() {
+            if cp.kind == ContextProviderKind::Command && cp.command.trim().is_empty() {
+                return Err(OxicrabError::Config(format!(
+                    "agents.defaults.contextProviders[{i}].command is required for kind \"command\""
+                )));
+            }
+            if cp.kind == ContextProviderKind::Weather && cp.enabled {
+                let weather = &self.tools.weather;
+                if !weather.enabled || weather.api_key.is_empty() || weather.locations.is_empty() {
+                    warn!(
+                        "context provider '{}' has kind \"weather\" but tools.weather is not configured with an apiKey and locations, it will be skipped",
+                        cp.name
+                    );
+                }
+            }
             for (j, bin) in cp.requires_bins.iter().enumerate() {
                 if bin.is_empty() {
                     return Err(OxicrabError::Config(format!(
@@ -889,7 +1469,7 @@ impl Config {
 
     pub fn get_api_key(&self, model: Option<&str>) -> Option<&str> {
         let model = model.unwrap_or(&self.agents.defaults.model_routing.default);
-        self.providers.get_api_key(model)
+        self.providers.get_api_key(self.models.resolve(model))
     }
 
     /// Collect all non-empty secret values for leak detection.
@@ -943,11 +1523,22 @@ impl Config {
             }
         }
 
+        for tenant in self.gateway.tenants.values() {
+            if !tenant.api_key.is_empty() {
+                secrets.push(("gateway_tenant_api_key", tenant.api_key.as_str()));
+            }
+        }
+
         // Include webhook HMAC secrets
         for wh in self.gateway.webhooks.values() {
             if !wh.secret.is_empty() {
                 secrets.push(("webhook_secret", wh.secret.as_str()));
             }
+            if let Some(auth) = &wh.auth
+                && !auth.secret().is_empty()
+            {
+                secrets.push(("webhook_secret", auth.secret()));
+            }
         }
 
         // Include custom header values from all providers (may contain auth tokens)
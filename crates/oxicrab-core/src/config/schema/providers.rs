@@ -93,6 +93,21 @@ const KNOWN_PREFIXES: &[&str] = &[
     "ollama",
 ];
 
+/// Model a provider uses when none is requested, for providers that have one.
+pub fn provider_default_model(provider: &str) -> Option<&'static str> {
+    match normalize_provider(provider).as_ref() {
+        "anthropic" => Some("claude-sonnet-4-5-20250929"),
+        "openai" => Some("gpt-4o"),
+        "gemini" => Some("gemini-pro"),
+        _ => None,
+    }
+}
+
+/// Whether `provider` names a provider that can be configured.
+pub fn is_known_provider(provider: &str) -> bool {
+    KNOWN_PREFIXES.contains(&normalize_provider(provider).as_ref())
+}
+
 /// Parse `"provider/model"` notation. Returns `provider=None` if there is no
 /// slash or if the part before the slash isn't a recognized provider prefix.
 ///
@@ -143,6 +158,21 @@ pub fn infer_provider_from_model(model: &str) -> Option<&'static str> {
     None
 }
 
+/// Model alias registry (`models.aliases`): short names such as `"fast"` that
+/// stand for a full `provider/model` string anywhere a model is configured.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModelsConfig {
+    #[serde(default)]
+    pub aliases: std::collections::HashMap<String, String>,
+}
+
+impl ModelsConfig {
+    /// The model an alias stands for, or `model` itself when it is not an alias.
+    pub fn resolve<'a>(&'a self, model: &'a str) -> &'a str {
+        self.aliases.get(model).map_or(model, String::as_str)
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Default)]
 pub struct ProviderConfig {
     #[serde(default, rename = "apiKey")]
@@ -193,6 +223,15 @@ pub struct LocalProviderConfig {
     /// and parses `<tool_call>` XML blocks from text responses.
     #[serde(default, rename = "promptGuidedTools")]
     pub prompt_guided_tools: bool,
+    /// How long Ollama keeps the model loaded after a request (`"30m"`,
+    /// `"-1m"` for indefinitely), sent as `keep_alive`. Ollama's own default
+    /// (5 minutes) when unset. Ignored by vLLM.
+    #[serde(default, rename = "keepAlive")]
+    pub keep_alive: Option<String>,
+    /// Wait for the startup warmup request to load the model, however long
+    /// that takes, so the first user message doesn't pay the load time.
+    #[serde(default, rename = "preloadOnStartup")]
+    pub preload_on_startup: bool,
 }
 
 impl std::fmt::Debug for LocalProviderConfig {
@@ -215,6 +254,8 @@ impl std::fmt::Debug for LocalProviderConfig {
             .field("api_base", &self.base.api_base)
             .field("headers", &redacted_headers)
             .field("prompt_guided_tools", &self.prompt_guided_tools)
+            .field("keep_alive", &self.keep_alive)
+            .field("preload_on_startup", &self.preload_on_startup)
             .finish()
     }
 }
@@ -283,6 +324,10 @@ pub struct CircuitBreakerConfig {
     pub recovery_timeout_secs: u64,
     #[serde(default = "default_half_open_probes", rename = "halfOpenProbes")]
     pub half_open_probes: u32,
+    /// Persist breaker state to the memory database so an open circuit stays
+    /// open across restarts (until `recoveryTimeoutSecs` has passed).
+    #[serde(default, rename = "persistState")]
+    pub persist_state: bool,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -292,6 +337,79 @@ impl Default for CircuitBreakerConfig {
             failure_threshold: default_failure_threshold(),
             recovery_timeout_secs: default_recovery_timeout_secs(),
             half_open_probes: default_half_open_probes(),
+            persist_state: false,
+        }
+    }
+}
+
+/// Failover to a second provider when the primary's circuit is open or a call
+/// fails (`providers.failover`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FailoverConfig {
+    /// Provider to fail over to (e.g. `"openai"`). Unset disables failover.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<String>,
+    /// Model to request from the secondary. Defaults to the provider's
+    /// default model; required for providers without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl FailoverConfig {
+    /// The secondary as `provider/model`, or `None` when failover is off or
+    /// no model is known for the provider.
+    pub fn secondary_model_ref(&self) -> Option<String> {
+        let provider = normalize_provider(self.secondary.as_deref()?);
+        let model = self
+            .model
+            .as_deref()
+            .or_else(|| provider_default_model(&provider))?;
+        Some(format!("{provider}/{model}"))
+    }
+}
+
+fn default_provider_max_retries() -> u32 {
+    3
+}
+
+fn default_provider_base_delay_ms() -> u64 {
+    1000
+}
+
+fn default_provider_max_delay_ms() -> u64 {
+    10_000
+}
+
+fn default_provider_jitter() -> f64 {
+    0.25
+}
+
+/// Retry policy for LLM calls made by the agent. Transient failures are
+/// retried with exponential backoff; a rate limit that says how long to wait
+/// (`Retry-After`) is waited out instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderRetryConfig {
+    /// Retries after the first attempt (0 disables retry).
+    #[serde(default = "default_provider_max_retries", rename = "maxRetries")]
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on each further retry.
+    #[serde(default = "default_provider_base_delay_ms", rename = "baseDelayMs")]
+    pub base_delay_ms: u64,
+    /// Upper bound on a computed delay.
+    #[serde(default = "default_provider_max_delay_ms", rename = "maxDelayMs")]
+    pub max_delay_ms: u64,
+    /// Random spread of each computed delay, as a fraction (0.25 = ±25%).
+    #[serde(default = "default_provider_jitter")]
+    pub jitter: f64,
+}
+
+impl Default for ProviderRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_provider_max_retries(),
+            base_delay_ms: default_provider_base_delay_ms(),
+            max_delay_ms: default_provider_max_delay_ms(),
+            jitter: default_provider_jitter(),
         }
     }
 }
@@ -326,6 +444,10 @@ pub struct ProvidersConfig {
     pub ollama: LocalProviderConfig,
     #[serde(default, rename = "circuitBreaker")]
     pub circuit_breaker: CircuitBreakerConfig,
+    #[serde(default)]
+    pub retry: ProviderRetryConfig,
+    #[serde(default)]
+    pub failover: FailoverConfig,
 }
 
 impl ProvidersConfig {
@@ -356,9 +356,99 @@ pub struct WeatherConfig {
     pub enabled: bool,
     #[serde(default, rename = "apiKey")]
     pub api_key: String,
+    /// Locations watched for alerts and described in the system prompt,
+    /// e.g. `"London,GB"`.
+    #[serde(default)]
+    pub locations: Vec<String>,
+    /// Units for current conditions and alert thresholds.
+    #[serde(default)]
+    pub units: WeatherUnits,
+    /// Add the current conditions at each location to the system prompt.
+    #[serde(default, rename = "contextConditions")]
+    pub context_conditions: bool,
+    /// Proactive messages when the forecast crosses a threshold.
+    #[serde(default)]
+    pub alerts: WeatherAlertsConfig,
+}
+
+redact_debug!(
+    WeatherConfig,
+    enabled,
+    redact(api_key),
+    locations,
+    units,
+    context_conditions,
+    alerts,
+);
+
+/// OpenWeatherMap unit system.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherUnits {
+    /// °F and mph.
+    #[default]
+    Imperial,
+    /// °C and m/s.
+    Metric,
+}
+
+impl std::fmt::Display for WeatherUnits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Imperial => "imperial",
+            Self::Metric => "metric",
+        })
+    }
 }
 
-redact_debug!(WeatherConfig, enabled, redact(api_key),);
+/// Severe-weather alerts: the 24h forecast for each of
+/// [`WeatherConfig::locations`] is checked every `interval_minutes`, and each
+/// new alert is sent once to every target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherAlertsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minutes between forecast checks (default: 60).
+    #[serde(
+        default = "default_weather_alert_interval_minutes",
+        rename = "intervalMinutes"
+    )]
+    pub interval_minutes: u64,
+    /// Chats the alerts are sent to, as `"channel:chat_id"`.
+    #[serde(default)]
+    pub targets: Vec<super::agent::ChannelTarget>,
+    /// Alert when the temperature is forecast above this.
+    #[serde(default, rename = "maxTemp")]
+    pub max_temp: Option<f64>,
+    /// Alert when the temperature is forecast below this.
+    #[serde(default, rename = "minTemp")]
+    pub min_temp: Option<f64>,
+    /// Alert when the wind speed is forecast above this.
+    #[serde(default, rename = "maxWind")]
+    pub max_wind: Option<f64>,
+    /// Alert on thunderstorms, heavy rain or snow, freezing rain, squalls and
+    /// tornadoes (default: true).
+    #[serde(default = "default_true", rename = "severeConditions")]
+    pub severe_conditions: bool,
+}
+
+fn default_weather_alert_interval_minutes() -> u64 {
+    60
+}
+
+impl Default for WeatherAlertsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: default_weather_alert_interval_minutes(),
+            targets: Vec::new(),
+            max_temp: None,
+            min_temp: None,
+            max_wind: None,
+            severe_conditions: true,
+        }
+    }
+}
 
 #[derive(Clone, Serialize, Deserialize, Default)]
 pub struct TodoistConfig {
@@ -659,6 +749,45 @@ fn default_rss_covariance_inflation() -> f64 {
     0.01
 }
 
+/// Turn-scoped working notes for the model (`scratchpad_append` /
+/// `scratchpad_read`). Notes are cleared when the turn ends and never sent to
+/// the user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScratchpadConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Maximum characters kept per turn; appends beyond this are rejected.
+    #[serde(default = "default_scratchpad_max_chars", rename = "maxChars")]
+    pub max_chars: usize,
+}
+
+impl Default for ScratchpadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_chars: default_scratchpad_max_chars(),
+        }
+    }
+}
+
+fn default_scratchpad_max_chars() -> usize {
+    16_000
+}
+
+/// The `tmux` tool. Its sessions run commands as the gateway user, outside
+/// the exec allowlist and sandbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TmuxConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+impl Default for TmuxConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ToolsConfig {
     #[serde(default, rename = "webSearch")]
@@ -667,6 +796,11 @@ pub struct ToolsConfig {
     pub exec: ExecToolConfig,
     #[serde(default, rename = "restrictToWorkspace")]
     pub restrict_to_workspace: bool,
+    /// Runtime-set for gateway tenants: the file tools get only the
+    /// workspace, not the oxicrab home holding the operator's config and the
+    /// other tenants' workspaces
+    #[serde(skip)]
+    pub workspace_only: bool,
     #[serde(default)]
     pub google: GoogleConfig,
     #[serde(default)]
@@ -689,4 +823,36 @@ pub struct ToolsConfig {
     pub exfiltration_guard: ExfiltrationGuardConfig,
     #[serde(default)]
     pub rss: RssConfig,
+    #[serde(default)]
+    pub scratchpad: ScratchpadConfig,
+    #[serde(default)]
+    pub tmux: TmuxConfig,
+    /// Per-tool call budgets keyed by tool name (e.g. `web_search`, `github`).
+    #[serde(default, rename = "rateLimits")]
+    pub rate_limits: std::collections::HashMap<String, ToolRateLimitConfig>,
+    /// Execution timeouts in seconds keyed by tool name. Take precedence
+    /// over the tool's built-in timeout.
+    #[serde(default)]
+    pub timeouts: std::collections::HashMap<String, u64>,
+    /// Timeout in seconds for tools without a `timeouts` entry or a
+    /// built-in timeout of their own. `None` keeps the 120s default.
+    #[serde(default, rename = "defaultTimeoutSecs")]
+    pub default_timeout_secs: Option<u64>,
+}
+
+fn default_rate_limit_window_secs() -> u64 {
+    60
+}
+
+/// Token-bucket limit for a single tool: at most `max_calls` executions per
+/// `window_secs`, refilled continuously. Calls over the limit wait up to
+/// `max_wait_secs` for a token, otherwise they fail with a rate-limit error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolRateLimitConfig {
+    #[serde(rename = "maxCalls")]
+    pub max_calls: u32,
+    #[serde(default = "default_rate_limit_window_secs", rename = "windowSecs")]
+    pub window_secs: u64,
+    #[serde(default, rename = "maxWaitSecs")]
+    pub max_wait_secs: u64,
 }
@@ -26,12 +26,65 @@ pub enum CronSchedule {
         /// Optional channel filter (only fire for messages from this channel).
         channel: Option<String>,
     },
+    /// Fires daily at sunrise or sunset for a location, shifted by
+    /// `offset_minutes` (negative fires before the event).
+    #[serde(rename = "solar")]
+    Solar {
+        event: SolarEvent,
+        lat: f64,
+        lon: f64,
+        #[serde(rename = "offsetMinutes", default)]
+        offset_minutes: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SolarEvent {
+    Sunrise,
+    Sunset,
+}
+
+impl SolarEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sunrise => "sunrise",
+            Self::Sunset => "sunset",
+        }
+    }
+}
+
+impl std::str::FromStr for SolarEvent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "sunrise" => Ok(Self::Sunrise),
+            "sunset" => Ok(Self::Sunset),
+            other => anyhow::bail!("unknown solar event '{other}': expected sunrise or sunset"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CronTarget {
     pub channel: String,
     pub to: String,
+    /// Sent (or, for agent jobs, run) instead of `payload.message` for this
+    /// target.
+    #[serde(
+        rename = "messageOverride",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub message_override: Option<String>,
+}
+
+impl CronTarget {
+    /// The message for this target: its override, or the job's message.
+    pub fn message<'a>(&'a self, payload_message: &'a str) -> &'a str {
+        self.message_override.as_deref().unwrap_or(payload_message)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -150,6 +203,19 @@ impl CronSchedule {
                     format!("event /{pat}/")
                 }
             }
+            CronSchedule::Solar {
+                event,
+                lat,
+                lon,
+                offset_minutes,
+            } => {
+                let at = match offset_minutes.signum() {
+                    0 => event.as_str().to_string(),
+                    1 => format!("{offset_minutes}m after {}", event.as_str()),
+                    _ => format!("{}m before {}", offset_minutes.abs(), event.as_str()),
+                };
+                format!("daily {at} at {lat:.4},{lon:.4}")
+            }
         }
     }
 }
@@ -17,10 +17,12 @@ fn test_cron_job_full_roundtrip() {
                 CronTarget {
                     channel: "telegram".to_string(),
                     to: "user123".to_string(),
+                    message_override: None,
                 },
                 CronTarget {
                     channel: "slack".to_string(),
                     to: "U08G6HBC89X".to_string(),
+                    message_override: None,
                 },
             ],
         },
@@ -87,6 +89,25 @@ fn test_backward_compat_missing_new_fields() {
     assert_eq!(job.state.run_count, 0);
 }
 
+#[test]
+fn test_cron_target_message_override() {
+    // Targets stored before overrides existed use the job's message
+    let plain: CronTarget = serde_json::from_str(r#"{"channel": "slack", "to": "C1"}"#).unwrap();
+    assert_eq!(plain.message_override, None);
+    assert_eq!(plain.message("briefing"), "briefing");
+    assert!(
+        !serde_json::to_string(&plain)
+            .unwrap()
+            .contains("messageOverride")
+    );
+
+    let custom: CronTarget = serde_json::from_str(
+        r#"{"channel": "whatsapp", "to": "123", "messageOverride": "short briefing"}"#,
+    )
+    .unwrap();
+    assert_eq!(custom.message("briefing"), "short briefing");
+}
+
 #[test]
 fn test_expires_at_and_max_runs_omitted_from_json_when_none() {
     let job = CronJob {
@@ -136,3 +157,40 @@ fn test_cron_schedule_cron_missing_tz() {
         _ => panic!("Expected Cron variant"),
     }
 }
+
+#[test]
+fn test_solar_schedule_serde_and_describe() {
+    let schedule: CronSchedule = serde_json::from_value(serde_json::json!({
+        "kind": "solar",
+        "event": "sunset",
+        "lat": 51.5,
+        "lon": -0.1275,
+        "offsetMinutes": -30
+    }))
+    .unwrap();
+    let CronSchedule::Solar {
+        event,
+        offset_minutes,
+        ..
+    } = schedule
+    else {
+        panic!("expected solar schedule");
+    };
+    assert_eq!(event, SolarEvent::Sunset);
+    assert_eq!(offset_minutes, -30);
+    assert_eq!(
+        schedule.describe(),
+        "daily 30m before sunset at 51.5000,-0.1275"
+    );
+
+    // offsetMinutes defaults to 0
+    let json = serde_json::json!({"kind": "solar", "event": "sunrise", "lat": 0.0, "lon": 0.0});
+    let schedule: CronSchedule = serde_json::from_value(json).unwrap();
+    assert_eq!(schedule.describe(), "daily sunrise at 0.0000,0.0000");
+
+    assert_eq!(
+        "Sunrise".parse::<SolarEvent>().unwrap(),
+        SolarEvent::Sunrise
+    );
+    assert!("noon".parse::<SolarEvent>().is_err());
+}
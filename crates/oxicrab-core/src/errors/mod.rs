@@ -10,14 +10,8 @@ pub enum OxicrabError {
     #[error("Configuration error: {0}")]
     Config(String),
 
-    #[error("Provider error: {message}")]
-    Provider { message: String, retryable: bool },
-
-    #[error("Rate limit exceeded")]
-    RateLimit { retry_after: Option<u64> },
-
-    #[error("Authentication failed: {0}")]
-    Auth(String),
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
 
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
@@ -27,9 +21,99 @@ impl OxicrabError {
     /// Whether this error is transient and the operation should be retried.
     pub fn is_retryable(&self) -> bool {
         match self {
-            Self::Provider { retryable, .. } => *retryable,
-            Self::RateLimit { .. } | Self::Internal(_) => true,
-            Self::Auth(_) | Self::Config(_) => false,
+            Self::Provider(e) => e.is_retryable(),
+            Self::Internal(_) => true,
+            Self::Config(_) => false,
+        }
+    }
+}
+
+/// Classified LLM provider failure.
+///
+/// Providers return these (as `OxicrabError::Provider` inside `anyhow::Error`)
+/// so the retry layer, circuit breaker, fallback chain and user-facing
+/// messages can react to the kind of failure. Use [`ProviderError::find`]
+/// to recover one from an `anyhow::Error`.
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    #[error("Rate limit exceeded")]
+    RateLimit { retry_after: Option<u64> },
+
+    /// The request does not fit the model's context window.
+    #[error("Context length exceeded: {0}")]
+    ContextLength(String),
+
+    /// The provider refused the request or blocked the response under its
+    /// content policy.
+    #[error("Blocked by content policy: {0}")]
+    ContentPolicy(String),
+
+    /// Network failures, timeouts and 5xx responses.
+    #[error("Transient provider error: {0}")]
+    Transient(String),
+
+    /// The provider is not being called at all, because the circuit breaker
+    /// is open after repeated failures.
+    #[error("Provider unavailable: {0}")]
+    Unavailable(String),
+
+    /// Any other API error.
+    #[error("Provider error: {message}")]
+    Api { message: String, retryable: bool },
+}
+
+impl ProviderError {
+    /// Whether the same request may succeed if retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimit { .. } | Self::Transient(_) => true,
+            Self::Api { retryable, .. } => *retryable,
+            Self::Auth(_)
+            | Self::ContextLength(_)
+            | Self::ContentPolicy(_)
+            | Self::Unavailable(_) => false,
+        }
+    }
+
+    /// Find a provider error anywhere in an `anyhow` error chain.
+    pub fn find(err: &anyhow::Error) -> Option<&Self> {
+        err.chain().find_map(|e| {
+            e.downcast_ref::<Self>()
+                .or_else(|| match e.downcast_ref::<OxicrabError>() {
+                    Some(OxicrabError::Provider(p)) => Some(p),
+                    _ => None,
+                })
+        })
+    }
+
+    /// Message to show the user when a turn fails with this error, or
+    /// `None` for generic API errors.
+    pub fn user_message(&self) -> Option<&'static str> {
+        match self {
+            Self::Auth(_) => Some(
+                "The LLM provider rejected the credentials — check the API key in your config.",
+            ),
+            Self::RateLimit { .. } => {
+                Some("Rate limited by the LLM provider — please try again in a moment.")
+            }
+            Self::ContextLength(_) => Some(
+                "This conversation no longer fits in the model's context window — \
+                 please start a new session.",
+            ),
+            Self::ContentPolicy(_) => {
+                Some("The LLM provider declined this request under its content policy.")
+            }
+            Self::Transient(_) => {
+                Some("The LLM provider is temporarily unavailable — please try again shortly.")
+            }
+            Self::Unavailable(_) => Some(
+                "The LLM provider has been failing repeatedly, so requests are paused for now — \
+                 please try again in a few minutes.",
+            ),
+            Self::Api { .. } => None,
         }
     }
 }
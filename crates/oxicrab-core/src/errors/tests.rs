@@ -2,31 +2,59 @@ use super::*;
 
 #[test]
 fn is_retryable_provider() {
-    let retryable = OxicrabError::Provider {
+    let retryable = OxicrabError::from(ProviderError::Api {
         message: "timeout".into(),
         retryable: true,
-    };
+    });
     assert!(retryable.is_retryable());
 
-    let not_retryable = OxicrabError::Provider {
+    let not_retryable = OxicrabError::from(ProviderError::Api {
         message: "bad request".into(),
         retryable: false,
-    };
+    });
     assert!(!not_retryable.is_retryable());
 }
 
 #[test]
 fn is_retryable_auth_config() {
-    assert!(!OxicrabError::Auth("bad key".into()).is_retryable());
+    assert!(!OxicrabError::from(ProviderError::Auth("bad key".into())).is_retryable());
     assert!(!OxicrabError::Config("missing field".into()).is_retryable());
 }
 
 #[test]
 fn is_retryable_rate_limit() {
     assert!(
-        OxicrabError::RateLimit {
+        OxicrabError::from(ProviderError::RateLimit {
             retry_after: Some(30)
-        }
+        })
         .is_retryable()
     );
 }
+
+#[test]
+fn is_retryable_by_provider_error_kind() {
+    assert!(ProviderError::Transient("503".into()).is_retryable());
+    assert!(!ProviderError::ContextLength("too long".into()).is_retryable());
+    assert!(!ProviderError::ContentPolicy("blocked".into()).is_retryable());
+    assert!(!ProviderError::Unavailable("circuit open".into()).is_retryable());
+}
+
+#[test]
+fn find_provider_error_through_context() {
+    let err = anyhow::Error::from(OxicrabError::from(ProviderError::ContentPolicy(
+        "flagged".into(),
+    )))
+    .context("chat failed");
+    assert!(matches!(
+        ProviderError::find(&err),
+        Some(ProviderError::ContentPolicy(_))
+    ));
+
+    let err = anyhow::Error::from(ProviderError::RateLimit { retry_after: None });
+    assert!(matches!(
+        ProviderError::find(&err),
+        Some(ProviderError::RateLimit { .. })
+    ));
+
+    assert!(ProviderError::find(&anyhow::anyhow!("plain")).is_none());
+}
@@ -35,6 +35,9 @@ pub struct LLMResponse {
     /// a non-primary provider handles the request; `None` means the originally
     /// requested model served it.
     pub actual_model: Option<String>,
+    /// Provider that served this response instead of the configured one. Set
+    /// by `FailoverProvider`; `None` means the primary served it.
+    pub served_by: Option<String>,
     /// Why the model stopped generating. Values vary by provider
     /// (e.g., `"stop"`, `"length"`, `"max_tokens"`, `"end_turn"`).
     /// Used to detect truncated output in pre-compaction flush.
@@ -152,6 +155,8 @@ pub struct RetryConfig {
     pub initial_delay_ms: u64,
     pub max_delay_ms: u64,
     pub backoff_multiplier: f64,
+    /// Random spread of each backoff delay, as a fraction of it.
+    pub jitter: f64,
 }
 
 impl Default for RetryConfig {
@@ -161,6 +166,19 @@ impl Default for RetryConfig {
             initial_delay_ms: 1000,
             max_delay_ms: 10000,
             backoff_multiplier: 2.0,
+            jitter: 0.25,
+        }
+    }
+}
+
+impl From<&crate::config::schema::ProviderRetryConfig> for RetryConfig {
+    fn from(config: &crate::config::schema::ProviderRetryConfig) -> Self {
+        Self {
+            max_retries: config.max_retries as usize,
+            initial_delay_ms: config.base_delay_ms,
+            max_delay_ms: config.max_delay_ms,
+            jitter: config.jitter.clamp(0.0, 1.0),
+            ..Self::default()
         }
     }
 }
@@ -178,6 +196,53 @@ pub enum ResponseFormat {
     JsonSchema { name: String, schema: Value },
 }
 
+/// How much the model should reason before answering. Providers translate it:
+/// - Anthropic: extended thinking with [`Self::thinking_budget_tokens`]
+/// - OpenAI/compatible: `reasoning_effort`
+/// - Others: ignored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// Anthropic extended-thinking budget (1024 is the API minimum).
+    pub fn thinking_budget_tokens(self) -> u32 {
+        match self {
+            Self::Low => 1024,
+            Self::Medium => 4096,
+            Self::High => 16_384,
+        }
+    }
+
+    /// Parse `low`, `medium` or `high` (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "medium" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ReasoningEffort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Parameters for a chat request to an LLM provider.
 #[derive(Debug, Clone, Default)]
 pub struct ChatRequest {
@@ -190,6 +255,8 @@ pub struct ChatRequest {
     pub tool_choice: Option<String>,
     /// Optional response format constraint (JSON mode, structured output).
     pub response_format: Option<ResponseFormat>,
+    /// Reasoning budget; `None` leaves the provider default.
+    pub reasoning_effort: Option<ReasoningEffort>,
 }
 
 impl ChatRequest {
@@ -237,15 +304,68 @@ impl ChatRequestBuilder {
         self
     }
 
+    pub fn reasoning_effort(mut self, effort: ReasoningEffort) -> Self {
+        self.inner.reasoning_effort = Some(effort);
+        self
+    }
+
     pub fn build(self) -> ChatRequest {
         self.inner
     }
 }
 
+/// Receives the text of a response while it is being generated (see
+/// [`LLMProvider::chat_stream`]). Readers get the text so far from the
+/// [`watch::Receiver`](tokio::sync::watch::Receiver) returned by
+/// [`StreamSink::new`]; the stream ends when the last sink is dropped.
+#[derive(Clone)]
+pub struct StreamSink {
+    text: Arc<tokio::sync::watch::Sender<String>>,
+}
+
+impl StreamSink {
+    pub fn new() -> (Self, tokio::sync::watch::Receiver<String>) {
+        let (tx, rx) = tokio::sync::watch::channel(String::new());
+        (Self { text: Arc::new(tx) }, rx)
+    }
+
+    /// Append newly generated text.
+    pub fn push(&self, delta: &str) {
+        if !delta.is_empty() {
+            self.text.send_modify(|text| text.push_str(delta));
+        }
+    }
+
+    /// Discard the text so far, before a retried or re-routed request streams
+    /// its own.
+    pub fn reset(&self) {
+        self.text.send_if_modified(|text| {
+            let had_text = !text.is_empty();
+            text.clear();
+            had_text
+        });
+    }
+}
+
 #[async_trait]
 pub trait LLMProvider: Send + Sync {
     async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse>;
 
+    /// Like [`chat`](Self::chat), but pushes the response text to `sink` as
+    /// it arrives. The complete response is still returned. The default
+    /// makes a regular request and pushes its text in one piece.
+    async fn chat_stream(
+        &self,
+        req: &ChatRequest,
+        sink: StreamSink,
+    ) -> anyhow::Result<LLMResponse> {
+        let response = self.chat(req).await?;
+        if let Some(ref content) = response.content {
+            sink.push(content);
+        }
+        Ok(response)
+    }
+
     fn default_model(&self) -> &str;
 
     /// Pre-warm the provider's HTTP connection (TLS handshake, HTTP/2 negotiation).
@@ -260,83 +380,104 @@ pub trait LLMProvider: Send + Sync {
         req: &ChatRequest,
         retry_config: Option<RetryConfig>,
     ) -> anyhow::Result<LLMResponse> {
-        let config = retry_config.unwrap_or_default();
-        let mut last_error = None;
-        let mut next_backoff_ms = config.initial_delay_ms.max(1);
-
-        for attempt in 0..=config.max_retries {
-            if attempt > 0 {
-                warn!(
-                    "Provider retry attempt {}/{} after error: {}",
-                    attempt,
-                    config.max_retries,
-                    last_error
-                        .as_ref()
-                        .map(|e: &anyhow::Error| e.to_string())
-                        .unwrap_or_default()
-                );
+        with_retry(retry_config.unwrap_or_default(), || self.chat(req)).await
+    }
+
+    /// [`chat_stream`](Self::chat_stream) with automatic retry on transient
+    /// errors. Text streamed by a failed attempt is discarded.
+    async fn chat_stream_with_retry(
+        &self,
+        req: &ChatRequest,
+        retry_config: Option<RetryConfig>,
+        sink: StreamSink,
+    ) -> anyhow::Result<LLMResponse> {
+        with_retry(retry_config.unwrap_or_default(), || {
+            sink.reset();
+            self.chat_stream(req, sink.clone())
+        })
+        .await
+    }
+}
+
+/// Delay before the next retry: the rate limit's `retry_after` hint (in
+/// seconds) when there is one, otherwise `backoff_ms` with jitter, capped at
+/// `max_delay_ms`.
+fn retry_delay(config: &RetryConfig, backoff_ms: u64, retry_after: Option<u64>) -> Duration {
+    if let Some(retry_secs) = retry_after {
+        debug!("Using retry-after hint: {}s", retry_secs);
+        return Duration::from_millis(retry_secs.saturating_mul(1000).max(1000));
+    }
+    let jitter = 1.0 + config.jitter * (fastrand::f64() * 2.0 - 1.0);
+    let jittered_ms = ((backoff_ms as f64) * jitter)
+        .round()
+        .clamp(1.0, config.max_delay_ms.max(1) as f64) as u64;
+    Duration::from_millis(jittered_ms)
+}
+
+/// Run `attempt` until it succeeds, fails with a non-transient error, or the
+/// retries in `config` are used up.
+async fn with_retry<F, Fut>(config: RetryConfig, mut attempt_fn: F) -> anyhow::Result<LLMResponse>
+where
+    F: FnMut() -> Fut + Send,
+    Fut: std::future::Future<Output = anyhow::Result<LLMResponse>> + Send,
+{
+    let mut last_error = None;
+    let mut next_backoff_ms = config.initial_delay_ms.max(1);
+
+    for attempt in 0..=config.max_retries {
+        if attempt > 0 {
+            warn!(
+                "Provider retry attempt {}/{} after error: {}",
+                attempt,
+                config.max_retries,
+                last_error
+                    .as_ref()
+                    .map(|e: &anyhow::Error| e.to_string())
+                    .unwrap_or_default()
+            );
+        }
+        debug!("Sending chat request (attempt {})", attempt);
+        let result = attempt_fn().await;
+        match result {
+            Ok(response) => {
+                debug!("Chat request succeeded on attempt {}", attempt);
+                return Ok(response);
             }
-            debug!("Sending chat request (attempt {})", attempt);
-            let result = self.chat(req).await;
-            match result {
-                Ok(response) => {
-                    debug!("Chat request succeeded on attempt {}", attempt);
-                    return Ok(response);
+            Err(e) => {
+                // Check for rate limit with retry_after hint
+                let provider_error = crate::errors::ProviderError::find(&e);
+                let rate_limit_delay = provider_error.and_then(|pe| match pe {
+                    crate::errors::ProviderError::RateLimit { retry_after } => *retry_after,
+                    _ => None,
+                });
+
+                // Don't retry non-transient errors (auth, context length,
+                // content policy, bad request); untyped errors are retried
+                let is_transient = e.downcast_ref::<crate::errors::OxicrabError>().map_or_else(
+                    || provider_error.is_none_or(crate::errors::ProviderError::is_retryable),
+                    crate::errors::OxicrabError::is_retryable,
+                );
+                warn!("Chat request failed on attempt {}: {}", attempt, e);
+                if !is_transient {
+                    return Err(e);
                 }
-                Err(e) => {
-                    // Check for rate limit with retry_after hint
-                    let rate_limit_delay = e
-                        .downcast_ref::<crate::errors::OxicrabError>()
-                        .and_then(|ox| match ox {
-                            crate::errors::OxicrabError::RateLimit { retry_after } => *retry_after,
-                            _ => None,
-                        });
-
-                    // Don't retry non-transient errors (but do retry rate limits)
-                    let is_transient =
-                        e.downcast_ref::<crate::errors::OxicrabError>()
-                            .is_none_or(|ox| match ox {
-                                crate::errors::OxicrabError::Provider { retryable, .. } => {
-                                    *retryable
-                                }
-                                crate::errors::OxicrabError::Auth(_)
-                                | crate::errors::OxicrabError::Config(_) => false,
-                                crate::errors::OxicrabError::RateLimit { .. }
-                                | crate::errors::OxicrabError::Internal(_) => true,
-                            });
-                    warn!("Chat request failed on attempt {}: {}", attempt, e);
-                    if !is_transient {
-                        return Err(e);
-                    }
-                    last_error = Some(e);
-                    if attempt < config.max_retries {
-                        // Use retry_after from rate limit if available, otherwise exponential backoff
-                        let delay = if let Some(retry_secs) = rate_limit_delay {
-                            debug!("Using retry-after hint: {}s", retry_secs);
-                            Duration::from_millis(retry_secs.saturating_mul(1000).max(1000))
-                        } else {
-                            // Jitter in +/- 25% range.
-                            let jitter = 1.0 + ((fastrand::f64() * 0.5) - 0.25);
-                            let jittered_ms = ((next_backoff_ms as f64) * jitter)
-                                .round()
-                                .clamp(1.0, config.max_delay_ms.max(1) as f64)
-                                as u64;
-                            let delay = Duration::from_millis(jittered_ms);
-                            let multiplied =
-                                ((next_backoff_ms as f64) * config.backoff_multiplier).round();
-                            next_backoff_ms =
-                                multiplied.clamp(1.0, config.max_delay_ms.max(1) as f64) as u64;
-                            delay
-                        };
-                        debug!("Waiting {}ms before retry", delay.as_millis());
-                        tokio::time::sleep(delay).await;
+                last_error = Some(e);
+                if attempt < config.max_retries {
+                    let delay = retry_delay(&config, next_backoff_ms, rate_limit_delay);
+                    if rate_limit_delay.is_none() {
+                        let multiplied =
+                            ((next_backoff_ms as f64) * config.backoff_multiplier).round();
+                        next_backoff_ms =
+                            multiplied.clamp(1.0, config.max_delay_ms.max(1) as f64) as u64;
                     }
+                    debug!("Waiting {}ms before retry", delay.as_millis());
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
-
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All retry attempts failed")))
     }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All retry attempts failed")))
 }
 
 #[cfg(test)]
@@ -85,3 +85,100 @@ fn test_chat_request_builder_all_setters() {
         Some(ResponseFormat::JsonObject)
     ));
 }
+
+/// Streams part of an answer and then fails on the first call.
+struct FlakyStreamProvider {
+    calls: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait]
+impl LLMProvider for FlakyStreamProvider {
+    async fn chat(&self, _req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        unreachable!("only streamed in this test")
+    }
+
+    async fn chat_stream(
+        &self,
+        _req: &ChatRequest,
+        sink: StreamSink,
+    ) -> anyhow::Result<LLMResponse> {
+        if self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+            sink.push("stale par");
+            return Err(crate::errors::ProviderError::Transient("dropped".into()).into());
+        }
+        sink.push("fresh answer");
+        Ok(LLMResponse {
+            content: Some("fresh answer".to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn default_model(&self) -> &str {
+        "mock"
+    }
+}
+
+#[tokio::test]
+async fn test_chat_stream_with_retry_discards_failed_attempt() {
+    let provider = FlakyStreamProvider {
+        calls: std::sync::atomic::AtomicUsize::new(0),
+    };
+    let retry = RetryConfig {
+        initial_delay_ms: 1,
+        max_delay_ms: 1,
+        ..Default::default()
+    };
+    let (sink, text) = StreamSink::new();
+    let response = provider
+        .chat_stream_with_retry(&ChatRequest::builder(vec![], 16).build(), Some(retry), sink)
+        .await
+        .unwrap();
+    assert_eq!(response.content.as_deref(), Some("fresh answer"));
+    assert_eq!(*text.borrow(), "fresh answer");
+}
+
+#[test]
+fn retry_delay_prefers_retry_after_hint() {
+    let config = RetryConfig {
+        jitter: 0.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        retry_delay(&config, 1000, None),
+        Duration::from_millis(1000)
+    );
+    // Rate limits with an explicit hint wait it out, even beyond maxDelayMs
+    assert_eq!(
+        retry_delay(&config, 1000, Some(30)),
+        Duration::from_secs(30)
+    );
+    // Computed backoff stays under the cap
+    assert_eq!(
+        retry_delay(&config, 60_000, None),
+        Duration::from_millis(10_000)
+    );
+
+    let config = RetryConfig {
+        jitter: 0.5,
+        ..Default::default()
+    };
+    for _ in 0..50 {
+        let ms = retry_delay(&config, 2000, None).as_millis();
+        assert!((1000..=3000).contains(&ms), "{ms}ms outside ±50%");
+    }
+}
+
+#[test]
+fn retry_config_from_provider_retry_config() {
+    let config = RetryConfig::from(&crate::config::schema::ProviderRetryConfig {
+        max_retries: 5,
+        base_delay_ms: 250,
+        max_delay_ms: 4000,
+        jitter: 0.1,
+    });
+    assert_eq!(config.max_retries, 5);
+    assert_eq!(config.initial_delay_ms, 250);
+    assert_eq!(config.max_delay_ms, 4000);
+    assert!((config.jitter - 0.1).abs() < f64::EPSILON);
+    assert!((config.backoff_multiplier - 2.0).abs() < f64::EPSILON);
+}
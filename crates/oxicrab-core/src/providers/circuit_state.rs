@@ -0,0 +1,41 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+/// A row from the `provider_health` table: circuit breaker state persisted
+/// across restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitStateRow {
+    pub provider: String,
+    /// `"closed"`, `"open"`, or `"half_open"`.
+    pub state: String,
+    pub consecutive_failures: u32,
+    /// Unix milliseconds when the circuit last opened. Set for `"open"`.
+    pub opened_at_ms: Option<i64>,
+}
+
+/// Storage abstraction for circuit breaker state.
+pub trait CircuitStateStore: Send + Sync {
+    fn load_circuit_state(&self, provider: &str) -> Result<Option<CircuitStateRow>>;
+    fn save_circuit_state(&self, row: &CircuitStateRow) -> Result<()>;
+}
+
+/// Live state of a circuit breaker, served by the gateway's
+/// `GET /health/provider`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CircuitSnapshot {
+    /// Default model of the wrapped provider.
+    pub provider: String,
+    /// `"closed"`, `"open"`, or `"half_open"`.
+    pub state: &'static str,
+    pub consecutive_failures: u32,
+    /// Seconds until an open circuit lets the next probe through. `None`
+    /// unless open.
+    pub next_probe_secs: Option<u64>,
+}
+
+/// Read access to a running circuit breaker.
+#[async_trait]
+pub trait CircuitHealth: Send + Sync {
+    async fn state_snapshot(&self) -> CircuitSnapshot;
+}
@@ -5,6 +5,10 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt::Write as _;
 
+/// Execution timeout for tools that set no `Tool::execution_timeout` and have
+/// no configured timeout.
+pub const DEFAULT_EXECUTION_TIMEOUT: std::time::Duration = std::time::Duration::from_mins(2);
+
 /// Shorthand for declaring `ActionDescriptor` vecs.
 ///
 /// Usage: `actions![list: ro, create, delete]` produces a `Vec<ActionDescriptor>`.
@@ -180,9 +184,10 @@ pub trait Tool: Send + Sync {
         self.requires_approval()
     }
 
-    /// Per-tool execution timeout. Overrides the registry-level default.
-    fn execution_timeout(&self) -> std::time::Duration {
-        std::time::Duration::from_mins(2)
+    /// Per-tool execution timeout. Overrides the registry-level default;
+    /// `None` leaves the tool on that default.
+    fn execution_timeout(&self) -> Option<std::time::Duration> {
+        None
     }
 
     /// Capability metadata for this tool. Used by subagent builder,
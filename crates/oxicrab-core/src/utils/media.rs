@@ -2,6 +2,7 @@
 
 use anyhow::{Context, Result, bail};
 use std::path::PathBuf;
+use tracing::warn;
 
 const MAX_MEDIA_SIZE: usize = 20 * 1024 * 1024; // 20MB
 
@@ -119,28 +120,111 @@ pub fn extension_from_content_type(ct: &str) -> Option<&'static str> {
     None
 }
 
-/// Check if bytes start with known image magic bytes.
-pub fn is_image_magic_bytes(data: &[u8]) -> bool {
-    if data.len() < 4 {
-        return false;
-    }
-    // PNG: 89 50 4E 47
-    if data.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
-        return true;
+/// Media format recognized from a file's leading bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Mp3,
+    Ogg,
+    Wav,
+    Flac,
+    /// MP4 / M4A container (`ftyp` box).
+    Mp4,
+    /// WebM / Matroska container.
+    Webm,
+    Pdf,
+    Unknown,
+}
+
+impl MediaKind {
+    /// File extension for the format, without the dot. `None` for `Unknown`.
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            Self::Png => Some("png"),
+            Self::Jpeg => Some("jpg"),
+            Self::Gif => Some("gif"),
+            Self::Webp => Some("webp"),
+            Self::Mp3 => Some("mp3"),
+            Self::Ogg => Some("ogg"),
+            Self::Wav => Some("wav"),
+            Self::Flac => Some("flac"),
+            Self::Mp4 => Some("mp4"),
+            Self::Webm => Some("webm"),
+            Self::Pdf => Some("pdf"),
+            Self::Unknown => None,
+        }
+    }
+
+    pub fn is_image(self) -> bool {
+        matches!(self, Self::Png | Self::Jpeg | Self::Gif | Self::Webp)
+    }
+
+    /// Audio formats, including the MP4 and WebM containers voice clips
+    /// arrive in.
+    pub fn is_audio(self) -> bool {
+        matches!(
+            self,
+            Self::Mp3 | Self::Ogg | Self::Wav | Self::Flac | Self::Mp4 | Self::Webm
+        )
     }
-    // JPEG: FF D8 FF
-    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
-        return true;
+}
+
+/// Identify downloaded media by its magic bytes, so the saved file gets the
+/// right extension and a mislabeled download (e.g. an HTML login page served
+/// for a missing scope) is caught. Logs a warning for unrecognized bytes.
+pub fn detect_media_kind(data: &[u8]) -> MediaKind {
+    let kind = sniff_media_kind(data);
+    if kind == MediaKind::Unknown {
+        warn!(
+            "unrecognized media format (first bytes: {:02x?}, {} bytes)",
+            &data[..8.min(data.len())],
+            data.len()
+        );
     }
-    // GIF: GIF87a or GIF89a
-    if data.starts_with(b"GIF8") {
-        return true;
+    kind
+}
+
+fn sniff_media_kind(data: &[u8]) -> MediaKind {
+    if data.len() < 4 {
+        return MediaKind::Unknown;
     }
-    // WebP: RIFF....WEBP
-    if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
-        return true;
+    let riff_form = (data.len() >= 12 && data.starts_with(b"RIFF")).then(|| &data[8..12]);
+    if data.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
+        MediaKind::Png
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        MediaKind::Jpeg
+    } else if data.starts_with(b"GIF8") {
+        MediaKind::Gif
+    } else if riff_form == Some(b"WEBP") {
+        MediaKind::Webp
+    } else if riff_form == Some(b"WAVE") {
+        MediaKind::Wav
+    } else if data.starts_with(b"%PDF-") {
+        MediaKind::Pdf
+    } else if data.starts_with(b"OggS") {
+        MediaKind::Ogg
+    } else if data.starts_with(b"fLaC") {
+        MediaKind::Flac
+    } else if data.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        MediaKind::Webm
+    } else if data.len() >= 8 && &data[4..8] == b"ftyp" {
+        MediaKind::Mp4
+    } else if data.starts_with(b"ID3")
+        // MPEG audio frame sync with a non-reserved layer (excludes AAC ADTS)
+        || (data[0] == 0xFF && data[1] & 0xE0 == 0xE0 && data[1] & 0x06 != 0)
+    {
+        MediaKind::Mp3
+    } else {
+        MediaKind::Unknown
     }
-    false
+}
+
+/// Check if bytes start with known image magic bytes.
+pub fn is_image_magic_bytes(data: &[u8]) -> bool {
+    sniff_media_kind(data).is_image()
 }
 
 #[cfg(test)]
@@ -177,6 +261,40 @@ mod tests {
         assert!(!is_image_magic_bytes(&[0x00, 0x01]));
     }
 
+    #[test]
+    fn test_detect_media_kind() {
+        assert_eq!(detect_media_kind(b"ID3\x04\x00"), MediaKind::Mp3);
+        assert_eq!(detect_media_kind(&[0xFF, 0xFB, 0x90, 0x64]), MediaKind::Mp3);
+        assert_eq!(detect_media_kind(b"OggS\x00\x02"), MediaKind::Ogg);
+        assert_eq!(detect_media_kind(b"fLaC\x00"), MediaKind::Flac);
+        assert_eq!(detect_media_kind(b"RIFF\0\0\0\0WAVEfmt "), MediaKind::Wav);
+        assert_eq!(detect_media_kind(b"\0\0\0\x20ftypM4A "), MediaKind::Mp4);
+        assert_eq!(
+            detect_media_kind(&[0x1A, 0x45, 0xDF, 0xA3, 0x9F]),
+            MediaKind::Webm
+        );
+        assert_eq!(detect_media_kind(b"%PDF-1.7"), MediaKind::Pdf);
+        assert_eq!(
+            detect_media_kind(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            MediaKind::Jpeg
+        );
+        assert!(detect_media_kind(b"GIF89a").is_image());
+        assert!(detect_media_kind(b"OggS").is_audio());
+    }
+
+    #[test]
+    fn test_detect_media_kind_rejects_html_and_aac() {
+        let html = b"<!DOCTYPE html><html><head><title>Slack</title>";
+        assert_eq!(detect_media_kind(html), MediaKind::Unknown);
+        assert_eq!(MediaKind::Unknown.extension(), None);
+        // AAC ADTS shares the frame sync but has layer bits 00
+        assert_eq!(
+            detect_media_kind(&[0xFF, 0xF1, 0x50, 0x80]),
+            MediaKind::Unknown
+        );
+        assert_eq!(detect_media_kind(&[0xFF]), MediaKind::Unknown);
+    }
+
     #[test]
     fn test_ext_png() {
         assert_eq!(extension_from_content_type("image/png"), Some("png"));
@@ -46,7 +46,8 @@ use uuid::Uuid;
 
 use oxicrab_core::bus::InboundMessage;
 use oxicrab_core::bus::OutboundMessage;
-use oxicrab_core::config::schema::{WebhookConfig, WebhookTarget};
+use oxicrab_core::config::schema::{WebhookAuth, WebhookConfig, WebhookTarget};
+use oxicrab_core::providers::circuit_state::CircuitHealth;
 use oxicrab_core::safety::LeakRedactor;
 
 type HmacSha256 = Hmac<Sha256>;
@@ -94,6 +95,32 @@ pub struct HttpApiState {
     /// True when running in echo mode (no agent loop). Distinguishes
     /// "permanently unavailable" from "still initializing" in the status handler.
     pub echo_mode: bool,
+    /// Circuit breaker around the agent's provider, reported by
+    /// `/health/provider`. `None` when the breaker is disabled.
+    provider_health: Option<Arc<dyn CircuitHealth>>,
+    /// Renders the process-wide metrics registry for `/metrics`. `None` when
+    /// `gateway.metrics.enabled` is off.
+    metrics: Option<MetricsRenderer>,
+}
+
+/// Renders all recorded metrics in the Prometheus text exposition format.
+pub type MetricsRenderer = Arc<dyn Fn() -> String + Send + Sync>;
+
+/// An HTTP API tenant. `/api/chat` requests authenticated with the tenant's
+/// key are published to `inbound_tx` (the tenant's own agent loop) instead of
+/// the operator's agent.
+#[derive(Clone)]
+pub struct TenantRoute {
+    pub id: String,
+    pub api_key: String,
+    pub inbound_tx: Arc<mpsc::Sender<InboundMessage>>,
+}
+
+/// Keys accepted by [`api_key_auth`]: the operator key, plus tenant keys on
+/// routes that serve tenants.
+struct ApiAuth {
+    key: Arc<String>,
+    tenants: Arc<Vec<TenantRoute>>,
 }
 
 /// Drop guard that removes a pending response entry when the handler is dropped
@@ -131,6 +158,10 @@ pub struct ChatRequest {
     /// When omitted, the LLM responds in its default format (prose).
     #[serde(default, rename = "responseFormat")]
     pub response_format: Option<GatewayResponseFormat>,
+    /// Reasoning budget for this turn: `"low"`, `"medium"` or `"high"`.
+    /// Replaces the configured `agents.defaults.reasoning` efforts.
+    #[serde(default, rename = "reasoningEffort")]
+    pub reasoning_effort: Option<oxicrab_core::providers::base::ReasoningEffort>,
 }
 
 /// Gateway-level response format specification, parsed from the HTTP request body.
@@ -185,11 +216,12 @@ pub struct ErrorResponse {
 /// API key authentication middleware.
 ///
 /// Checks `Authorization: Bearer <key>` or `X-API-Key: <key>` headers.
+/// A tenant key attaches the matching [`TenantRoute`] as a request extension.
 /// Returns 401 if the key is missing or incorrect.
 async fn api_key_auth(
-    State(expected): State<Arc<String>>,
+    State(auth): State<Arc<ApiAuth>>,
     headers: HeaderMap,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> axum::response::Response {
     let provided = headers
@@ -205,23 +237,32 @@ async fn api_key_auth(
         })
         .or_else(|| headers.get("x-api-key").and_then(|v| v.to_str().ok()));
 
-    match provided {
-        Some(key) if key.as_bytes().ct_eq(expected.as_bytes()).into() => next.run(request).await,
-        _ => {
-            warn!(
-                "security: rejected unauthenticated request to {}",
-                request.uri()
-            );
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    error: "unauthorized — set Authorization: Bearer <apiKey> or X-API-Key header"
-                        .to_string(),
-                }),
-            )
-                .into_response()
+    if let Some(key) = provided {
+        if bool::from(key.as_bytes().ct_eq(auth.key.as_bytes())) {
+            return next.run(request).await;
+        }
+        if let Some(tenant) = auth
+            .tenants
+            .iter()
+            .find(|t| bool::from(key.as_bytes().ct_eq(t.api_key.as_bytes())))
+        {
+            request.extensions_mut().insert(tenant.clone());
+            return next.run(request).await;
         }
     }
+
+    warn!(
+        "security: rejected unauthenticated request to {}",
+        request.uri()
+    );
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ErrorResponse {
+            error: "unauthorized — set Authorization: Bearer <apiKey> or X-API-Key header"
+                .to_string(),
+        }),
+    )
+        .into_response()
 }
 
 /// Shared state for the rate limiting middleware.
@@ -300,29 +341,51 @@ fn extract_rate_limit_client_ip(
     socket_ip.map_or_else(|| "unknown".to_string(), |ip| ip.to_string())
 }
 
-/// Build the HTTP API router.
+/// Build the HTTP API router. Tenant keys are only accepted on `/api/chat`
+/// (and only when an operator API key is configured); status and A2A stay
+/// operator-only.
 #[allow(clippy::needless_pass_by_value)] // api_key is Arc-cloned into middleware layers
 fn build_router(
     state: HttpApiState,
     a2a_state: Option<a2a::A2aState>,
     api_key: Option<Arc<String>>,
+    tenants: Vec<TenantRoute>,
     rate_limiter: Option<RateLimitState>,
 ) -> Router {
+    let operator_auth = api_key.map(|key| {
+        Arc::new(ApiAuth {
+            key,
+            tenants: Arc::default(),
+        })
+    });
+
     // Routes that require auth when an API key is configured
-    let mut authed_routes = Router::new()
+    let mut chat_routes = Router::new()
         .route("/api/chat", post(chat_handler))
+        .with_state(state.clone());
+    let mut authed_routes = Router::new()
         .route("/api/status", get(status::status_json_handler))
         .route("/status", get(status::status_html_handler))
+        .route("/metrics", get(metrics_handler))
         .with_state(state.clone());
 
-    if let Some(ref key) = api_key {
+    if let Some(ref auth) = operator_auth {
+        let chat_auth = Arc::new(ApiAuth {
+            key: auth.key.clone(),
+            tenants: Arc::new(tenants),
+        });
+        chat_routes = chat_routes.layer(middleware::from_fn_with_state(chat_auth, api_key_auth));
         authed_routes =
-            authed_routes.layer(middleware::from_fn_with_state(key.clone(), api_key_auth));
+            authed_routes.layer(middleware::from_fn_with_state(auth.clone(), api_key_auth));
+    } else if !tenants.is_empty() {
+        warn!("gateway tenants ignored: tenant routing requires gateway.apiKey");
     }
+    let authed_routes = authed_routes.merge(chat_routes);
 
     // Public routes (health, webhooks with their own HMAC auth)
     let public_routes = Router::new()
         .route("/api/health", get(health_handler))
+        .route("/health/provider", get(provider_health_handler))
         .route("/api/webhook/{name}", post(webhook_handler))
         .with_state(state);
 
@@ -341,9 +404,9 @@ fn build_router(
             .route("/a2a/tasks/{id}", get(a2a::get_task_handler))
             .with_state(a2a);
 
-        if let Some(ref key) = api_key {
+        if let Some(ref auth) = operator_auth {
             authed_a2a =
-                authed_a2a.layer(middleware::from_fn_with_state(key.clone(), api_key_auth));
+                authed_a2a.layer(middleware::from_fn_with_state(auth.clone(), api_key_auth));
         }
 
         let a2a_router = authed_a2a
@@ -366,6 +429,7 @@ fn build_router(
 /// POST /api/chat — send a message and receive the agent's response.
 async fn chat_handler(
     State(state): State<HttpApiState>,
+    tenant: Option<axum::Extension<TenantRoute>>,
     Json(mut body): Json<ChatRequest>,
 ) -> impl IntoResponse {
     let session_id = body
@@ -380,8 +444,10 @@ async fn chat_handler(
         );
     }
 
+    let tenant_id = tenant.as_ref().map_or("-", |t| t.id.as_str());
     debug!(
-        "HTTP API chat request: session={}, content_len={}",
+        "HTTP API chat request: tenant={}, session={}, content_len={}",
+        tenant_id,
         session_id,
         body.message.len()
     );
@@ -462,9 +528,16 @@ async fn chat_handler(
             response_format_to_json(rf),
         );
     }
+    if let Some(effort) = body.reasoning_effort {
+        builder = builder.meta(
+            oxicrab_core::bus::meta::REASONING_EFFORT,
+            serde_json::Value::String(effort.as_str().to_string()),
+        );
+    }
     let msg = builder.build();
 
-    if let Err(e) = state.inbound_tx.send(msg).await {
+    let inbound_tx = tenant.as_ref().map_or(&state.inbound_tx, |t| &t.inbound_tx);
+    if let Err(e) = inbound_tx.send(msg).await {
         // Clean up pending entry
         let mut pending = state.pending.lock().unwrap_or_else(|poison| {
             warn!("gateway pending map mutex was poisoned, recovering");
@@ -526,25 +599,82 @@ async fn health_handler(State(state): State<HttpApiState>) -> impl IntoResponse
     }))
 }
 
+/// GET /health/provider — circuit breaker state of the agent's provider.
+///
+/// 200 while the circuit is closed or half-open, 503 while it is open, so
+/// load balancers and alerting can scrape it. 404 when the circuit breaker
+/// is disabled.
+async fn provider_health_handler(State(state): State<HttpApiState>) -> axum::response::Response {
+    let Some(health) = state.provider_health else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "provider circuit breaker is disabled"})),
+        )
+            .into_response();
+    };
+    let snapshot = health.state_snapshot().await;
+    let code = if snapshot.state == "open" {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (code, Json(snapshot)).into_response()
+}
+
+/// GET /metrics — Prometheus scrape endpoint. Covers everything recorded
+/// through the `metrics` facade: messages per channel, LLM requests and
+/// tokens, hallucination detections, tool executions. 404 unless
+/// `gateway.metrics.enabled` is set.
+async fn metrics_handler(State(state): State<HttpApiState>) -> axum::response::Response {
+    let Some(render) = state.metrics else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "metrics endpoint is disabled"})),
+        )
+            .into_response();
+    };
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        render(),
+    )
+        .into_response()
+}
+
 /// Validate HMAC-SHA256 signature against a payload.
 ///
 /// Accepts lowercase or uppercase hex (and optional `sha256=` prefix). Compares
 /// decoded bytes with `subtle::ConstantTimeEq` so verification does not depend
 /// on hex letter case in the header value.
 pub fn validate_webhook_signature(secret: &str, signature: &str, body: &[u8]) -> bool {
-    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
-        return false;
-    };
-    mac.update(body);
-    let result = mac.finalize();
-    let expected_bytes = result.into_bytes();
-
     // Support both raw hex and "sha256=..." prefix (GitHub-style)
     let sig = signature
         .strip_prefix("sha256=")
         .unwrap_or(signature)
         .trim();
-    let Ok(provided_bytes) = hex::decode(sig) else {
+    hmac_hex_matches(secret, sig, body)
+}
+
+/// Validate GitHub's `X-Hub-Signature-256` header value (`sha256=<hex hmac>`).
+///
+/// Stricter than [`validate_webhook_signature`]: the `sha256=` prefix is
+/// required, since GitHub always sends it.
+pub fn validate_github_signature(secret: &str, header: &str, body: &[u8]) -> bool {
+    header
+        .strip_prefix("sha256=")
+        .is_some_and(|sig| hmac_hex_matches(secret, sig, body))
+}
+
+/// Constant-time comparison of a hex HMAC-SHA256 against the body's MAC.
+fn hmac_hex_matches(secret: &str, provided_hex: &str, body: &[u8]) -> bool {
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    let expected_bytes = mac.finalize().into_bytes();
+    let Ok(provided_bytes) = hex::decode(provided_hex) else {
         return false;
     };
     expected_bytes.as_slice().ct_eq(&provided_bytes).into()
@@ -631,7 +761,8 @@ async fn webhook_handler(
     // Reject webhooks with empty secrets (deny-by-default). An empty HMAC
     // secret would accept any signature, so we refuse to process the request
     // until the operator configures a proper secret.
-    if config.secret.is_empty() {
+    let auth = config.resolved_auth();
+    if auth.secret().is_empty() {
         warn!("webhook {name}: no secret configured, rejecting all requests");
         return StatusCode::FORBIDDEN.into_response();
     }
@@ -642,22 +773,36 @@ async fn webhook_handler(
         return StatusCode::PAYLOAD_TOO_LARGE.into_response();
     }
 
-    // Extract signature from headers (check common header names)
-    let signature = headers
-        .get("X-Signature-256")
-        .or_else(|| headers.get("X-Hub-Signature-256"))
-        .or_else(|| headers.get("X-Webhook-Signature"))
-        .and_then(|v| v.to_str().ok());
-
-    let Some(signature) = signature else {
-        warn!("security: webhook {}: missing signature header", name);
-        return StatusCode::FORBIDDEN.into_response();
-    };
-
-    // Validate HMAC-SHA256 signature
-    if !validate_webhook_signature(&config.secret, signature, &body) {
-        warn!("security: webhook {name}: invalid signature");
-        return StatusCode::FORBIDDEN.into_response();
+    match &auth {
+        WebhookAuth::GithubSha256 { secret } => {
+            let valid = headers
+                .get("X-Hub-Signature-256")
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|sig| validate_github_signature(secret, sig, &body));
+            if !valid {
+                warn!("security: webhook {name}: missing or invalid GitHub signature");
+                return StatusCode::UNAUTHORIZED.into_response();
+            }
+        }
+        WebhookAuth::HmacSha256 { secret } => {
+            // Extract signature from headers (check common header names)
+            let signature = headers
+                .get("X-Signature-256")
+                .or_else(|| headers.get("X-Hub-Signature-256"))
+                .or_else(|| headers.get("X-Webhook-Signature"))
+                .and_then(|v| v.to_str().ok());
+
+            let Some(signature) = signature else {
+                warn!("security: webhook {}: missing signature header", name);
+                return StatusCode::FORBIDDEN.into_response();
+            };
+
+            // Validate HMAC-SHA256 signature
+            if !validate_webhook_signature(secret, signature, &body) {
+                warn!("security: webhook {name}: invalid signature");
+                return StatusCode::FORBIDDEN.into_response();
+            }
+        }
     }
 
     // Replay protection: reject payloads with timestamps older than 5 minutes.
@@ -917,10 +1062,13 @@ pub async fn start<S: BuildHasher>(
     webhooks: HashMap<String, WebhookConfig, S>,
     a2a_config: Option<oxicrab_core::config::schema::A2aConfig>,
     api_key: Option<String>,
+    tenants: Vec<TenantRoute>,
     rate_limit: &oxicrab_core::config::schema::RateLimitConfig,
     leak_detector: Arc<dyn LeakRedactor>,
     ready: Arc<AtomicBool>,
     status: Arc<OnceLock<status::StatusState>>,
+    provider_health: Option<Arc<dyn CircuitHealth>>,
+    metrics: Option<MetricsRenderer>,
     echo_mode: bool,
 ) -> Result<(tokio::task::JoinHandle<()>, HttpApiState)> {
     let webhook_map: HashMap<String, WebhookConfig> = webhooks.into_iter().collect();
@@ -948,6 +1096,8 @@ pub async fn start<S: BuildHasher>(
         ready,
         status,
         echo_mode,
+        provider_health,
+        metrics,
     };
 
     // Set up A2A state if enabled
@@ -970,6 +1120,9 @@ pub async fn start<S: BuildHasher>(
     if key.is_some() {
         info!("HTTP API authentication enabled (Bearer / X-API-Key)");
     }
+    if !tenants.is_empty() {
+        info!("HTTP API serving {} tenant(s)", tenants.len());
+    }
     let trusted_proxies = rate_limit
         .trusted_proxies
         .iter()
@@ -1012,7 +1165,7 @@ pub async fn start<S: BuildHasher>(
     } else {
         None
     };
-    let app = build_router(state.clone(), a2a_state, key, rate_limiter);
+    let app = build_router(state.clone(), a2a_state, key, tenants, rate_limiter);
     let addr = format!("{host}:{port}");
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     info!("HTTP API listening on {}", addr);
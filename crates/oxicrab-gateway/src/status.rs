@@ -34,6 +34,8 @@ pub struct ModelsSnapshot {
 
 #[derive(Clone, Serialize)]
 pub struct ChatRoutingSnapshot {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub light: Option<String>,
     pub standard: String,
     pub heavy: String,
     pub thresholds: ChatThresholdsSnapshot,
@@ -121,6 +123,7 @@ impl StatusConfigSnapshot {
                 }
                 TaskRouting::Chat(chat) => {
                     chat_routing = Some(ChatRoutingSnapshot {
+                        light: chat.models.light.clone(),
                         standard: chat.models.standard.clone(),
                         heavy: chat.models.heavy.clone(),
                         thresholds: ChatThresholdsSnapshot {
@@ -238,11 +241,18 @@ pub async fn status_json_handler(State(state): State<HttpApiState>) -> impl Into
         let cron_jobs = db.list_cron_jobs(true).unwrap_or_default();
         let dlq_count = db.list_dlq_entries(None).map_or(0, |v| v.len());
         let search_stats = db.get_search_stats().ok();
-        (tokens, cron_jobs, dlq_count, search_stats)
+        let undelivered_count = db.count_outbound_dlq_entries().unwrap_or_default();
+        (
+            tokens,
+            cron_jobs,
+            dlq_count,
+            search_stats,
+            undelivered_count,
+        )
     })
     .await;
 
-    let (tokens, cron_jobs, dlq_count, search_stats) = match db_result {
+    let (tokens, cron_jobs, dlq_count, search_stats, undelivered_count) = match db_result {
         Ok(v) => v,
         Err(e) => {
             tracing::error!("status handler: spawn_blocking failed: {e}");
@@ -317,6 +327,9 @@ pub async fn status_json_handler(State(state): State<HttpApiState>) -> impl Into
             "jobs": jobs,
             "dlq_count": dlq_count,
         },
+        "outbound": {
+            "undelivered_count": undelivered_count,
+        },
         "safety": status.config_snapshot.safety,
         "gateway": status.config_snapshot.gateway,
         "memory": {
@@ -13,6 +13,8 @@ fn make_state() -> HttpApiState {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     }
 }
 
@@ -22,7 +24,7 @@ async fn test_health_endpoint_returns_json() {
     use tower::ServiceExt;
 
     let state = make_state();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("GET")
@@ -46,7 +48,7 @@ async fn test_health_endpoint_starting_before_ready() {
 
     let mut state = make_state();
     state.ready = Arc::new(AtomicBool::new(false));
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("GET")
@@ -62,6 +64,125 @@ async fn test_health_endpoint_starting_before_ready() {
     assert_eq!(json["status"], "starting");
 }
 
+struct FixedCircuit(oxicrab_core::providers::circuit_state::CircuitSnapshot);
+
+#[async_trait::async_trait]
+impl CircuitHealth for FixedCircuit {
+    async fn state_snapshot(&self) -> oxicrab_core::providers::circuit_state::CircuitSnapshot {
+        self.0.clone()
+    }
+}
+
+async fn get_provider_health(state: HttpApiState) -> (StatusCode, serde_json::Value) {
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    let app = build_router(state, None, None, vec![], None);
+    let req = Request::builder()
+        .method("GET")
+        .uri("/health/provider")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp: axum::http::Response<_> = app.oneshot(req).await.unwrap();
+    let status = resp.status();
+    let body = axum::body::to_bytes(resp.into_body(), 4096).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_provider_health_without_breaker_is_not_found() {
+    let (status, _) = get_provider_health(make_state()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_provider_health_reports_circuit_state() {
+    let mut state = make_state();
+    state.provider_health = Some(Arc::new(FixedCircuit(
+        oxicrab_core::providers::circuit_state::CircuitSnapshot {
+            provider: "claude-sonnet-4-6".to_string(),
+            state: "closed",
+            consecutive_failures: 2,
+            next_probe_secs: None,
+        },
+    )));
+    let (status, json) = get_provider_health(state).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["state"], "closed");
+    assert_eq!(json["consecutive_failures"], 2);
+    assert!(json["next_probe_secs"].is_null());
+
+    let mut state = make_state();
+    state.provider_health = Some(Arc::new(FixedCircuit(
+        oxicrab_core::providers::circuit_state::CircuitSnapshot {
+            provider: "claude-sonnet-4-6".to_string(),
+            state: "open",
+            consecutive_failures: 5,
+            next_probe_secs: Some(42),
+        },
+    )));
+    let (status, json) = get_provider_health(state).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(json["state"], "open");
+    assert_eq!(json["next_probe_secs"], 42);
+}
+
+async fn get_metrics(state: HttpApiState, api_key: Option<&str>) -> (StatusCode, String, String) {
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    let app = build_router(
+        state,
+        None,
+        Some(Arc::new("secret-key".to_string())),
+        vec![],
+        None,
+    );
+    let mut req = Request::builder().method("GET").uri("/metrics");
+    if let Some(key) = api_key {
+        req = req.header("Authorization", format!("Bearer {key}"));
+    }
+    let resp: axum::http::Response<_> = app
+        .oneshot(req.body(axum::body::Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = axum::body::to_bytes(resp.into_body(), 4096).await.unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_metrics_disabled_is_not_found() {
+    let (status, _, _) = get_metrics(make_state(), Some("secret-key")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_metrics_renders_prometheus_text_behind_auth() {
+    let mut state = make_state();
+    state.metrics = Some(Arc::new(|| {
+        "oxicrab_messages_received_total{channel=\"telegram\"} 3\n".to_string()
+    }));
+
+    let (status, _, _) = get_metrics(state.clone(), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, content_type, body) = get_metrics(state, Some("secret-key")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/plain; version=0.0.4"));
+    assert!(body.contains("oxicrab_messages_received_total{channel=\"telegram\"} 3"));
+}
+
 #[test]
 fn test_route_response_non_http_returns_false() {
     let state = make_state();
@@ -180,6 +301,8 @@ fn make_state_with_webhooks(webhooks: HashMap<String, WebhookConfig>) -> HttpApi
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     }
 }
 
@@ -197,6 +320,8 @@ fn make_state_with_webhooks_and_outbound(
             ready: Arc::new(AtomicBool::new(true)),
             status: Arc::new(OnceLock::new()),
             echo_mode: false,
+            provider_health: None,
+            metrics: None,
         },
         outbound_rx,
     )
@@ -219,7 +344,7 @@ async fn test_webhook_disabled_returns_404() {
         make_webhook_config(false, "secret123"),
     );
     let (state, _outbound_rx) = make_state_with_webhooks_and_outbound(webhooks);
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let body = b"payload";
     let sig = sign_body("secret123", body);
@@ -245,7 +370,7 @@ async fn test_webhook_enabled_validates_signature() {
         make_webhook_config(true, "secret123"),
     );
     let (state, _outbound_rx) = make_state_with_webhooks_and_outbound(webhooks);
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let body = b"payload";
     let sig = sign_body("secret123", body);
@@ -272,7 +397,7 @@ async fn test_webhook_bad_signature_returns_forbidden() {
         make_webhook_config(true, "secret123"),
     );
     let (state, _outbound_rx) = make_state_with_webhooks_and_outbound(webhooks);
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("POST")
@@ -285,13 +410,72 @@ async fn test_webhook_bad_signature_returns_forbidden() {
     assert_eq!(resp.status(), StatusCode::FORBIDDEN);
 }
 
+#[test]
+fn test_validate_github_signature_requires_prefix() {
+    let sig = sign_body("gh-secret", b"{}");
+    assert!(validate_github_signature(
+        "gh-secret",
+        &format!("sha256={sig}"),
+        b"{}"
+    ));
+    assert!(!validate_github_signature("gh-secret", &sig, b"{}"));
+    assert!(!validate_github_signature(
+        "other-secret",
+        &format!("sha256={sig}"),
+        b"{}"
+    ));
+}
+
+#[tokio::test]
+async fn test_webhook_github_scheme_verifies_hub_signature() {
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    let mut config = make_webhook_config(true, "");
+    config.auth = Some(WebhookAuth::GithubSha256 {
+        secret: "gh-secret".to_string(),
+    });
+    let mut webhooks = HashMap::new();
+    webhooks.insert("github".to_string(), config);
+    let (state, _outbound_rx) = make_state_with_webhooks_and_outbound(webhooks);
+    let app = build_router(state, None, None, vec![], None);
+
+    let body = br#"{"ref":"refs/heads/main"}"#;
+    let valid = format!("sha256={}", sign_body("gh-secret", body));
+    let wrong = format!("sha256={}", sign_body("wrong", body));
+    let cases = [
+        ("X-Hub-Signature-256", valid.as_str(), StatusCode::OK),
+        (
+            "X-Hub-Signature-256",
+            wrong.as_str(),
+            StatusCode::UNAUTHORIZED,
+        ),
+        // Only GitHub's header is accepted for this scheme
+        ("X-Signature-256", valid.as_str(), StatusCode::UNAUTHORIZED),
+    ];
+    for (header, sig, expected) in cases {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/api/webhook/github")
+            .header(header, sig)
+            .body(axum::body::Body::from(&body[..]))
+            .unwrap();
+        let resp: axum::http::Response<_> = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), expected, "{header}: {sig}");
+        if expected == StatusCode::UNAUTHORIZED {
+            let bytes = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+            assert!(bytes.is_empty());
+        }
+    }
+}
+
 #[tokio::test]
 async fn test_webhook_unknown_name_returns_404() {
     use axum::http::Request;
     use tower::ServiceExt;
 
     let state = make_state_with_webhooks(HashMap::new());
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("POST")
@@ -315,7 +499,7 @@ async fn test_webhook_missing_signature_returns_forbidden() {
         make_webhook_config(true, "secret123"),
     );
     let state = make_state_with_webhooks(webhooks);
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     // No signature header at all
     let req = Request::builder()
@@ -339,7 +523,7 @@ async fn test_webhook_payload_too_large_returns_413() {
         make_webhook_config(true, "secret123"),
     );
     let state = make_state_with_webhooks(webhooks);
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let oversized = vec![b'x'; WEBHOOK_MAX_BODY + 1];
     let sig = sign_body("secret123", &oversized);
@@ -370,7 +554,7 @@ async fn test_webhook_alternative_signature_headers() {
             make_webhook_config(true, "secret123"),
         );
         let (state, _outbound_rx) = make_state_with_webhooks_and_outbound(webhooks);
-        let app = build_router(state, None, None, None);
+        let app = build_router(state, None, None, vec![], None);
 
         let req = Request::builder()
             .method("POST")
@@ -424,8 +608,10 @@ async fn test_webhook_direct_delivery_to_targets() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let body = b"v2.0 released";
     let sig = sign_body("deploy-secret", body);
@@ -490,9 +676,11 @@ async fn test_webhook_agent_turn_routes_through_agent() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
     let pending = state.pending.clone();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let body = b"server down";
     let sig = sign_body("alert-secret", body);
@@ -551,9 +739,11 @@ async fn test_chat_handler_sends_inbound_and_returns_response() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
     let pending = state.pending.clone();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("POST")
@@ -600,9 +790,11 @@ async fn test_chat_handler_with_session_id() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
     let pending = state.pending.clone();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("POST")
@@ -644,9 +836,11 @@ async fn test_chat_handler_creates_pending_and_publishes_inbound() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
     let pending = state.pending.clone();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("POST")
@@ -680,6 +874,8 @@ async fn test_deliver_to_targets_no_outbound_tx() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
     let targets = vec![WebhookTarget {
         channel: "slack".to_string(),
@@ -773,8 +969,10 @@ async fn test_webhook_direct_delivery_returns_error_when_no_targets_accept_deliv
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let body = b"v2.0 released";
     let sig = sign_body("deploy-secret", body);
@@ -824,8 +1022,10 @@ async fn test_webhook_template_with_json_fields() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let body = serde_json::to_vec(&serde_json::json!({"action":"push","repo":"test"})).unwrap();
     let sig = sign_body("json-secret", &body);
@@ -869,8 +1069,10 @@ async fn test_chat_handler_rejects_oversized_message() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let big_msg = "x".repeat(MAX_MESSAGE_SIZE + 1);
     let body = serde_json::json!({"message": big_msg});
@@ -903,8 +1105,10 @@ async fn test_chat_handler_inbound_send_fails() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("POST")
@@ -950,6 +1154,19 @@ fn test_gateway_response_format_deserialize_absent() {
     assert!(body.response_format.is_none());
 }
 
+#[test]
+fn test_gateway_reasoning_effort_deserialize() {
+    let body: ChatRequest =
+        serde_json::from_str(r#"{"message":"hi","reasoningEffort":"high"}"#).unwrap();
+    assert_eq!(
+        body.reasoning_effort,
+        Some(oxicrab_core::providers::base::ReasoningEffort::High)
+    );
+    assert!(
+        serde_json::from_str::<ChatRequest>(r#"{"message":"hi","reasoningEffort":"max"}"#).is_err()
+    );
+}
+
 #[test]
 fn test_response_format_json_roundtrip_json_object() {
     use oxicrab_core::providers::base::ResponseFormat;
@@ -1038,9 +1255,11 @@ async fn test_chat_handler_with_response_format_metadata() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
     let pending = state.pending.clone();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("POST")
@@ -1087,9 +1306,11 @@ async fn test_chat_handler_without_response_format_no_metadata() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
     let pending = state.pending.clone();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("POST")
@@ -1130,8 +1351,10 @@ async fn test_chat_handler_rejects_oversized_schema() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     // Create a schema that exceeds MAX_SCHEMA_SIZE (100 KB)
     let large_schema = serde_json::json!({
@@ -1186,8 +1409,10 @@ async fn test_chat_handler_rejects_oversized_schema_name() {
         ready: Arc::new(AtomicBool::new(true)),
         status: Arc::new(OnceLock::new()),
         echo_mode: false,
+        provider_health: None,
+        metrics: None,
     };
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req_body = serde_json::json!({
         "message": "test",
@@ -1232,7 +1457,7 @@ async fn test_webhook_replay_protection_rejects_old_timestamp() {
         make_webhook_config(true, "secret123"),
     );
     let (state, _outbound_rx) = make_state_with_webhooks_and_outbound(webhooks);
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let body = b"payload";
     let sig = sign_body("secret123", body);
@@ -1261,7 +1486,7 @@ async fn test_webhook_replay_protection_accepts_recent_timestamp() {
         make_webhook_config(true, "secret123"),
     );
     let (state, _outbound_rx) = make_state_with_webhooks_and_outbound(webhooks);
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let body = b"payload";
     let sig = sign_body("secret123", body);
@@ -1285,7 +1510,7 @@ async fn test_status_json_initializing() {
     use tower::ServiceExt;
 
     let state = make_state();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("GET")
@@ -1309,7 +1534,7 @@ async fn test_status_json_true_echo_mode() {
 
     let mut state = make_state();
     state.echo_mode = true;
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("GET")
@@ -1330,7 +1555,7 @@ async fn test_status_html_endpoint() {
     use tower::ServiceExt;
 
     let state = make_state();
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("GET")
@@ -1356,7 +1581,13 @@ async fn test_status_html_requires_auth_when_key_set() {
     use tower::ServiceExt;
 
     let state = make_state();
-    let app = build_router(state, None, Some(Arc::new("test-key".to_string())), None);
+    let app = build_router(
+        state,
+        None,
+        Some(Arc::new("test-key".to_string())),
+        vec![],
+        None,
+    );
 
     let req = Request::builder()
         .method("GET")
@@ -1374,7 +1605,13 @@ async fn test_status_json_requires_auth_when_key_set() {
     use tower::ServiceExt;
 
     let state = make_state();
-    let app = build_router(state, None, Some(Arc::new("test-key".to_string())), None);
+    let app = build_router(
+        state,
+        None,
+        Some(Arc::new("test-key".to_string())),
+        vec![],
+        None,
+    );
 
     let req = Request::builder()
         .method("GET")
@@ -1447,7 +1684,7 @@ async fn test_status_json_with_populated_state() {
 
     let mut state = make_state();
     state.status = lock;
-    let app = build_router(state, None, None, None);
+    let app = build_router(state, None, None, vec![], None);
 
     let req = Request::builder()
         .method("GET")
@@ -1469,3 +1706,74 @@ async fn test_status_json_with_populated_state() {
     assert!(json["tokens"]["today"]["input"].is_number());
     assert!(json["cron"]["jobs"].is_array());
 }
+
+#[tokio::test]
+async fn test_chat_with_tenant_key_routes_to_tenant_agent() {
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    let (inbound_tx, mut inbound_rx) = mpsc::channel(16);
+    let (tenant_tx, mut tenant_rx) = mpsc::channel(16);
+    let state = HttpApiState {
+        inbound_tx: Arc::new(inbound_tx),
+        ..make_state()
+    };
+    let pending = state.pending.clone();
+    let tenants = vec![TenantRoute {
+        id: "alice".to_string(),
+        api_key: "alice-key".to_string(),
+        inbound_tx: Arc::new(tenant_tx),
+    }];
+    let app = build_router(
+        state,
+        None,
+        Some(Arc::new("test-key".to_string())),
+        tenants,
+        None,
+    );
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/chat")
+        .header("Content-Type", "application/json")
+        .header("X-API-Key", "alice-key")
+        .body(axum::body::Body::from(r#"{"message":"hello"}"#))
+        .unwrap();
+    let handle = tokio::spawn({
+        let app = app.clone();
+        async move { app.oneshot(req).await.unwrap() }
+    });
+
+    let msg = tenant_rx.recv().await.unwrap();
+    assert_eq!(msg.content, "hello");
+    assert!(
+        inbound_rx.try_recv().is_err(),
+        "operator agent got tenant message"
+    );
+    let tx = pending.lock().unwrap().remove(&msg.chat_id).unwrap();
+    tx.send(OutboundMessage::builder("http", msg.chat_id, "hi alice").build())
+        .unwrap();
+    let resp = handle.await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Tenant keys do not open the operator-only status endpoints
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/status")
+        .header("X-API-Key", "alice-key")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    // Unknown keys are rejected
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/chat")
+        .header("Content-Type", "application/json")
+        .header("Authorization", "Bearer bob-key")
+        .body(axum::body::Body::from(r#"{"message":"hello"}"#))
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -1,4 +1,5 @@
-/// Embedding utilities and optional local embedding generation via fastembed.
+/// Embedding utilities and pluggable embedding backends (`memory.embeddingsBackend`):
+/// a local ONNX model via fastembed (`embeddings-onnx` feature) or the OpenAI API.
 use anyhow::Result;
 
 #[cfg(feature = "embeddings")]
@@ -7,68 +8,140 @@ use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "embeddings")]
-use fastembed::{EmbeddingModel, TextEmbedding, TextInitOptions};
+use crate::memory_db::MemoryDB;
 #[cfg(feature = "embeddings")]
 use lru::LruCache;
 #[cfg(feature = "embeddings")]
+use oxicrab_core::config::schema::{EmbeddingsBackend, MemoryConfig};
+#[cfg(feature = "embeddings")]
 use tracing::{debug, info, warn};
 
+#[cfg(feature = "embeddings-onnx")]
+mod onnx;
+#[cfg(feature = "embeddings")]
+mod openai;
+
+#[cfg(feature = "embeddings-onnx")]
+pub use onnx::OnnxEmbeddings;
+#[cfg(feature = "embeddings")]
+pub use openai::OpenAiEmbeddings;
+
 #[cfg(feature = "embeddings")]
 const DEFAULT_CACHE_SIZE: usize = 10_000;
 
+/// A backend that turns texts into embedding vectors.
+#[cfg(feature = "embeddings")]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Embed `texts`, returning one vector per text in the same order.
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Build the backend selected by `memory.embeddingsBackend`.
+#[cfg(feature = "embeddings")]
+pub fn backend_from_config(config: &MemoryConfig) -> Result<Box<dyn EmbeddingProvider>> {
+    match config.embeddings_backend {
+        #[cfg(feature = "embeddings-onnx")]
+        EmbeddingsBackend::Onnx => Ok(Box::new(OnnxEmbeddings::new(&config.embeddings_model)?)),
+        #[cfg(not(feature = "embeddings-onnx"))]
+        EmbeddingsBackend::Onnx => {
+            anyhow::bail!(
+                "the onnx embeddings backend needs a build with the embeddings-onnx feature"
+            )
+        }
+        EmbeddingsBackend::OpenAi => {
+            let api_key = config
+                .openai_api_key
+                .clone()
+                .filter(|k| !k.is_empty())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("the openai embeddings backend needs providers.openai.apiKey")
+                })?;
+            Ok(Box::new(OpenAiEmbeddings::new(
+                api_key,
+                config.openai_api_base.as_deref(),
+                &config.embeddings_model,
+            )))
+        }
+    }
+}
+
 #[cfg(feature = "embeddings")]
 pub struct EmbeddingService {
-    model: Mutex<TextEmbedding>,
+    backend: Box<dyn EmbeddingProvider>,
+    dimensions: usize,
     cache: Mutex<LruCache<String, Vec<f32>>>,
 }
 
 #[cfg(feature = "embeddings")]
 impl EmbeddingService {
-    /// Load embedding model. This downloads the model on first use (~30MB).
-    pub fn new(model_name: &str) -> Result<Self> {
-        Self::with_cache_size(model_name, DEFAULT_CACHE_SIZE)
+    /// Wrap `backend` with the default embedding cache size.
+    pub fn new(backend: Box<dyn EmbeddingProvider>) -> Result<Self> {
+        Self::with_cache_size(backend, DEFAULT_CACHE_SIZE)
     }
 
-    /// Load embedding model with a custom query embedding cache size.
-    pub fn with_cache_size(model_name: &str, cache_size: usize) -> Result<Self> {
-        let model_type = match model_name {
-            "BAAI/bge-small-en-v1.5" => EmbeddingModel::BGESmallENV15,
-            "BAAI/bge-base-en-v1.5" => EmbeddingModel::BGEBaseENV15,
-            _ => {
-                anyhow::bail!(
-                    "unsupported embedding model '{model_name}'; use BAAI/bge-small-en-v1.5 or BAAI/bge-base-en-v1.5"
-                );
-            }
-        };
-
-        let model = TextEmbedding::try_new(
-            TextInitOptions::new(model_type).with_show_download_progress(true),
-        )?;
-        info!(
-            "embedding model loaded: {} (cache_size={})",
-            model_name, cache_size
-        );
-
+    /// Wrap `backend` with an LRU cache of `cache_size` embeddings. Embeds a
+    /// probe text to learn the vector dimension.
+    pub fn with_cache_size(backend: Box<dyn EmbeddingProvider>, cache_size: usize) -> Result<Self> {
+        let dimensions = backend
+            .embed(&["dimension probe".to_string()])?
+            .into_iter()
+            .next()
+            .map_or(0, |v| v.len());
+        if dimensions == 0 {
+            anyhow::bail!("embedding backend returned an empty vector");
+        }
         let cap = NonZeroUsize::new(cache_size.max(1)).unwrap();
         Ok(Self {
-            model: Mutex::new(model),
+            backend,
+            dimensions,
             cache: Mutex::new(LruCache::new(cap)),
         })
     }
 
-    /// Embed multiple texts (batch). Returns one vector per text.
-    /// These are not cached (used for indexing, where each text is unique).
+    /// Length of the vectors this service produces.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Embed multiple texts (batch). Returns one vector per text. Cached
+    /// texts are served from the cache; only the rest reach the backend.
     pub fn embed_texts(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
-        let docs: Vec<String> = texts.iter().map(std::string::ToString::to_string).collect();
-        let mut model = self.model.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-        let embeddings = model.embed(docs, None)?;
-        Ok(embeddings)
+        let mut out: Vec<Option<Vec<f32>>> = {
+            let mut cache = self.cache.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+            texts.iter().map(|t| cache.get(*t).cloned()).collect()
+        };
+        let missing: Vec<String> = texts
+            .iter()
+            .zip(&out)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(t, _)| (*t).to_string())
+            .collect();
+        if !missing.is_empty() {
+            let vectors = self.backend.embed(&missing)?;
+            if vectors.len() != missing.len() {
+                anyhow::bail!(
+                    "embedding backend returned {} vectors for {} texts",
+                    vectors.len(),
+                    missing.len()
+                );
+            }
+            let mut cache = self.cache.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+            let mut vectors = vectors.into_iter();
+            for (text, slot) in texts.iter().zip(out.iter_mut()) {
+                if slot.is_none()
+                    && let Some(v) = vectors.next()
+                {
+                    cache.put((*text).to_string(), v.clone());
+                    *slot = Some(v);
+                }
+            }
+        }
+        Ok(out.into_iter().map(Option::unwrap_or_default).collect())
     }
 
     /// Embed a single query string. Results are cached in an LRU cache
-    /// to avoid redundant ONNX inference for repeated queries.
+    /// to avoid redundant inference for repeated queries.
     pub fn embed_query(&self, query: &str) -> Result<Vec<f32>> {
-        // Check cache first
         {
             let mut cache = self.cache.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
             if let Some(cached) = cache.get(query) {
@@ -76,28 +149,14 @@ impl EmbeddingService {
                 return Ok(cached.clone());
             }
         }
-
-        // Cache miss — compute embedding
-        let embedding = {
-            let mut model = self.model.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-            let embeddings = model.embed(vec![query.to_string()], None)?;
-            embeddings
-                .into_iter()
-                .next()
-                .ok_or_else(|| anyhow::anyhow!("empty embedding result"))?
-        };
-
-        // Store in cache
-        {
-            let mut cache = self.cache.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-            cache.put(query.to_string(), embedding.clone());
-        }
-
-        Ok(embedding)
+        self.embed_texts(&[query])?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("empty embedding result"))
     }
 }
 
-/// Lazy wrapper that initializes the embedding model in a background task.
+/// Lazy wrapper that initializes the embedding backend in a background task.
 /// Callers can check readiness via `get()` or `is_ready()`.
 #[cfg(feature = "embeddings")]
 pub struct LazyEmbeddingService {
@@ -106,13 +165,28 @@ pub struct LazyEmbeddingService {
 
 #[cfg(feature = "embeddings")]
 impl LazyEmbeddingService {
-    /// Spawn background initialization of the embedding model.
-    pub fn new(model_name: String, cache_size: usize) -> Self {
+    /// Spawn background initialization of the configured backend. Once it is
+    /// ready, stored embeddings of another dimension (from a previous
+    /// backend or model) are dropped and re-embedded.
+    pub fn new(config: &MemoryConfig, db: Arc<MemoryDB>) -> Self {
         let cell = Arc::new(tokio::sync::OnceCell::new());
         let cell_clone = cell.clone();
+        let config = config.clone();
         tokio::spawn(async move {
             match tokio::task::spawn_blocking(move || {
-                EmbeddingService::with_cache_size(&model_name, cache_size)
+                let svc = EmbeddingService::with_cache_size(
+                    backend_from_config(&config)?,
+                    config.embedding_cache_size,
+                )?;
+                info!(
+                    "embedding backend ready: {:?} {} ({} dims, cache_size={})",
+                    config.embeddings_backend,
+                    config.embeddings_model,
+                    svc.dimensions(),
+                    config.embedding_cache_size
+                );
+                reconcile_dimensions(&db, &svc);
+                Ok::<_, anyhow::Error>(svc)
             })
             .await
             {
@@ -138,8 +212,62 @@ impl LazyEmbeddingService {
     }
 }
 
-/// Cosine similarity between two vectors. fastembed produces normalized vectors,
-/// so dot product equals cosine similarity.
+/// Drop stored embeddings whose dimension differs from `svc`'s, so they are
+/// re-embedded instead of silently never matching a query.
+#[cfg(feature = "embeddings")]
+fn reconcile_dimensions(db: &MemoryDB, svc: &EmbeddingService) {
+    match db.delete_embeddings_not_of_dimension(svc.dimensions()) {
+        Ok(0) => {}
+        Ok(n) => {
+            warn!(
+                "{n} stored embeddings do not have {} dimensions (embedding backend or model changed), re-embedding",
+                svc.dimensions()
+            );
+            backfill_missing_embeddings(db, svc);
+        }
+        Err(e) => warn!("failed to check stored embedding dimensions: {e}"),
+    }
+}
+
+/// Entries embedded per backend call when back-filling. Keeps each request
+/// well under the OpenAI embeddings API's 2048-input limit.
+pub const EMBED_BATCH_SIZE: usize = 32;
+
+/// Generate embeddings for any entries that don't have them yet, in batches
+/// of [`EMBED_BATCH_SIZE`]. Best-effort: logs warnings on failure but never
+/// errors out.
+#[cfg(feature = "embeddings")]
+pub(crate) fn backfill_missing_embeddings(db: &MemoryDB, svc: &EmbeddingService) {
+    let entries = match db.get_entries_missing_embeddings() {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("failed to check for missing embeddings: {e}");
+            return;
+        }
+    };
+    let mut stored = 0;
+    for batch in entries.chunks(EMBED_BATCH_SIZE) {
+        let texts: Vec<&str> = batch.iter().map(|(_, _, c)| c.as_str()).collect();
+        match svc.embed_texts(&texts) {
+            Ok(vectors) => {
+                for ((id, _, _), vec) in batch.iter().zip(vectors.iter()) {
+                    let bytes = serialize_embedding(vec);
+                    match db.store_embedding(*id, &bytes) {
+                        Ok(()) => stored += 1,
+                        Err(e) => warn!("failed to store embedding for entry {id}: {e}"),
+                    }
+                }
+            }
+            Err(e) => warn!("embedding back-fill failed: {e}"),
+        }
+    }
+    if stored > 0 {
+        debug!("back-filled {stored} embeddings");
+    }
+}
+
+/// Cosine similarity between two vectors. Both backends produce normalized
+/// vectors, so dot product equals cosine similarity.
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() {
         return 0.0;
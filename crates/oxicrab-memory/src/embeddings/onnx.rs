@@ -0,0 +1,40 @@
+use super::EmbeddingProvider;
+use anyhow::Result;
+use fastembed::{EmbeddingModel, TextEmbedding, TextInitOptions};
+use std::sync::Mutex;
+use tracing::info;
+
+/// Local embeddings from an ONNX model run by fastembed.
+pub struct OnnxEmbeddings {
+    model: Mutex<TextEmbedding>,
+}
+
+impl OnnxEmbeddings {
+    /// Load embedding model. This downloads the model on first use (~30MB).
+    pub fn new(model_name: &str) -> Result<Self> {
+        let model_type = match model_name {
+            "BAAI/bge-small-en-v1.5" => EmbeddingModel::BGESmallENV15,
+            "BAAI/bge-base-en-v1.5" => EmbeddingModel::BGEBaseENV15,
+            _ => {
+                anyhow::bail!(
+                    "unsupported embedding model '{model_name}'; use BAAI/bge-small-en-v1.5 or BAAI/bge-base-en-v1.5"
+                );
+            }
+        };
+
+        let model = TextEmbedding::try_new(
+            TextInitOptions::new(model_type).with_show_download_progress(true),
+        )?;
+        info!("embedding model loaded: {}", model_name);
+        Ok(Self {
+            model: Mutex::new(model),
+        })
+    }
+}
+
+impl EmbeddingProvider for OnnxEmbeddings {
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut model = self.model.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok(model.embed(texts, None)?)
+    }
+}
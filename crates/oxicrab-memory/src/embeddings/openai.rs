@@ -0,0 +1,94 @@
+use super::EmbeddingProvider;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::time::Duration;
+
+const DEFAULT_EMBEDDINGS_URL: &str = "https://api.openai.com/v1/embeddings";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Embeddings from the OpenAI `/embeddings` API.
+pub struct OpenAiEmbeddings {
+    agent: ureq::Agent,
+    url: String,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiEmbeddings {
+    /// `api_base` is `providers.openai.apiBase` (a chat-completions URL) when set.
+    pub fn new(api_key: String, api_base: Option<&str>, model: &str) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(REQUEST_TIMEOUT))
+            .http_status_as_error(false)
+            .build()
+            .new_agent();
+        Self {
+            agent,
+            url: embeddings_url(api_base),
+            api_key,
+            model: model.to_string(),
+        }
+    }
+}
+
+impl EmbeddingProvider for OpenAiEmbeddings {
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let body = serde_json::json!({ "model": self.model, "input": texts });
+        let mut response = self
+            .agent
+            .post(&self.url)
+            .header("Authorization", &format!("Bearer {}", self.api_key))
+            .send_json(&body)
+            .context("openai embeddings request failed")?;
+        let status = response.status();
+        let text = response
+            .body_mut()
+            .read_to_string()
+            .context("failed to read openai embeddings response")?;
+        if !status.is_success() {
+            anyhow::bail!(
+                "openai embeddings returned HTTP {status}: {}",
+                oxicrab_core::utils::truncate_chars(&text, 200, "...")
+            );
+        }
+        parse_embeddings_response(&text, texts.len())
+    }
+}
+
+/// Derive the embeddings endpoint from the configured OpenAI API base, which
+/// points at chat completions (`…/v1/chat/completions`).
+pub(super) fn embeddings_url(api_base: Option<&str>) -> String {
+    let Some(base) = api_base.map(|b| b.trim_end_matches('/')) else {
+        return DEFAULT_EMBEDDINGS_URL.to_string();
+    };
+    let root = base
+        .strip_suffix("/chat/completions")
+        .or_else(|| base.strip_suffix("/embeddings"))
+        .unwrap_or(base);
+    format!("{root}/embeddings")
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Parse an `/embeddings` response into vectors in input order.
+pub(super) fn parse_embeddings_response(body: &str, expected: usize) -> Result<Vec<Vec<f32>>> {
+    let mut parsed: EmbeddingsResponse =
+        serde_json::from_str(body).context("invalid openai embeddings response")?;
+    if parsed.data.len() != expected {
+        anyhow::bail!(
+            "openai embeddings returned {} vectors for {expected} inputs",
+            parsed.data.len()
+        );
+    }
+    parsed.data.sort_by_key(|d| d.index);
+    Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+}
@@ -77,3 +77,124 @@ fn test_embedding_cache_size_config_serde() {
     let config: oxicrab_core::config::schema::MemoryConfig = serde_json::from_str(json).unwrap();
     assert_eq!(config.embedding_cache_size, 5000);
 }
+
+#[cfg(feature = "embeddings")]
+mod backend {
+    use super::super::openai::{embeddings_url, parse_embeddings_response};
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Fake backend: a 3-dim vector per text, counting embedded texts.
+    struct FakeProvider(Arc<AtomicUsize>);
+
+    impl EmbeddingProvider for FakeProvider {
+        fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.0.fetch_add(texts.len(), Ordering::SeqCst);
+            Ok(texts
+                .iter()
+                .map(|t| vec![t.len() as f32, 0.0, 1.0])
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_service_probes_dimensions() {
+        let svc = EmbeddingService::new(Box::new(FakeProvider(Arc::default()))).unwrap();
+        assert_eq!(svc.dimensions(), 3);
+    }
+
+    #[test]
+    fn test_embed_texts_only_sends_uncached_texts() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let svc = EmbeddingService::new(Box::new(FakeProvider(calls.clone()))).unwrap();
+        calls.store(0, Ordering::SeqCst);
+
+        svc.embed_query("ab").unwrap();
+        let vectors = svc.embed_texts(&["ab", "abcd"]).unwrap();
+        assert_eq!(vectors, vec![vec![2.0, 0.0, 1.0], vec![4.0, 0.0, 1.0]]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        svc.embed_texts(&["abcd", "ab"]).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Fake backend that records the size of each call.
+    struct BatchRecorder(Arc<std::sync::Mutex<Vec<usize>>>);
+
+    impl EmbeddingProvider for BatchRecorder {
+        fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.0.lock().unwrap().push(texts.len());
+            Ok(texts.iter().map(|_| vec![1.0, 0.0, 0.0]).collect())
+        }
+    }
+
+    #[test]
+    fn test_backfill_embeds_in_batches() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        let total = EMBED_BATCH_SIZE * 2 + 5;
+        for i in 0..total {
+            db.insert_memory("daily:2026-01-01", &format!("fact number {i}"))
+                .unwrap();
+        }
+        let batches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let svc = EmbeddingService::new(Box::new(BatchRecorder(batches.clone()))).unwrap();
+        batches.lock().unwrap().clear();
+
+        backfill_missing_embeddings(&db, &svc);
+        assert_eq!(
+            *batches.lock().unwrap(),
+            vec![EMBED_BATCH_SIZE, EMBED_BATCH_SIZE, 5]
+        );
+        assert!(db.get_entries_missing_embeddings().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_onnx_backend_rejects_unknown_model() {
+        let config = MemoryConfig {
+            embeddings_model: "text-embedding-3-small".to_string(),
+            ..MemoryConfig::default()
+        };
+        assert!(backend_from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_openai_backend_requires_api_key() {
+        let config = MemoryConfig {
+            embeddings_backend: EmbeddingsBackend::OpenAi,
+            embeddings_model: "text-embedding-3-small".to_string(),
+            ..MemoryConfig::default()
+        };
+        let err = backend_from_config(&config).err().unwrap();
+        assert!(err.to_string().contains("apiKey"));
+    }
+
+    #[test]
+    fn test_embeddings_url() {
+        assert_eq!(embeddings_url(None), "https://api.openai.com/v1/embeddings");
+        assert_eq!(
+            embeddings_url(Some("https://proxy.example/v1/chat/completions")),
+            "https://proxy.example/v1/embeddings"
+        );
+        assert_eq!(
+            embeddings_url(Some("https://proxy.example/v1/")),
+            "https://proxy.example/v1/embeddings"
+        );
+    }
+
+    #[test]
+    fn test_parse_embeddings_response_orders_by_index() {
+        let body = r#"{"object":"list","data":[
+            {"object":"embedding","index":1,"embedding":[0.0,1.0]},
+            {"object":"embedding","index":0,"embedding":[1.0,0.0]}
+        ],"model":"text-embedding-3-small"}"#;
+        let vectors = parse_embeddings_response(body, 2).unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_parse_embeddings_response_count_mismatch() {
+        let body = r#"{"data":[{"index":0,"embedding":[1.0]}]}"#;
+        assert!(parse_embeddings_response(body, 2).is_err());
+        assert!(parse_embeddings_response("not json", 1).is_err());
+    }
+}
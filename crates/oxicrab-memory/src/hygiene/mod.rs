@@ -35,7 +35,8 @@ pub fn cleanup_workspace_files<S: ::std::hash::BuildHasher>(
 }
 
 /// Run all hygiene tasks (purge old search logs, intent metrics,
-/// complexity routing logs, cost logs, and stale memory entries).
+/// complexity routing logs, empty response logs, cost logs, tool audit logs,
+/// and stale memory entries).
 ///
 /// `memory_retention_days` controls how long memory entries are kept
 /// (default 180). Knowledge entries are never purged.
@@ -55,11 +56,26 @@ pub fn run_hygiene(db: &MemoryDB, purge_log_days: u32, memory_retention_days: u3
         Err(e) => warn!("complexity log purge failed: {}", e),
         _ => {}
     }
+    match db.purge_old_empty_response_logs(purge_log_days) {
+        Ok(n) if n > 0 => info!("purged {} old empty response log entries", n),
+        Err(e) => warn!("empty response log purge failed: {}", e),
+        _ => {}
+    }
+    match db.purge_old_token_breakdowns(purge_log_days) {
+        Ok(n) if n > 0 => info!("purged {} old token breakdown entries", n),
+        Err(e) => warn!("token breakdown purge failed: {}", e),
+        _ => {}
+    }
     match db.purge_old_cost_logs(purge_log_days) {
         Ok(n) if n > 0 => info!("purged {} old cost log entries", n),
         Err(e) => warn!("cost log purge failed: {}", e),
         _ => {}
     }
+    match db.purge_old_tool_audit(purge_log_days) {
+        Ok(n) if n > 0 => info!("purged {} old tool audit entries", n),
+        Err(e) => warn!("tool audit purge failed: {}", e),
+        _ => {}
+    }
     // Purge old memory entries (keep knowledge: prefixed sources).
     match db.purge_old_memory_entries(memory_retention_days) {
         Ok(n) if n > 0 => info!("purged {} old memory entries", n),
@@ -0,0 +1,279 @@
+use super::MemoryDB;
+use crate::embeddings::cosine_similarity;
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, params};
+use std::collections::HashSet;
+
+/// Only the most recent entries are compared pairwise, bounding the
+/// O(n²) similarity scan on large memory stores.
+const MAX_GARDEN_ENTRIES: usize = 2000;
+
+/// Status values stored in `memory_conflicts.status`.
+pub mod conflict_status {
+    /// LLM judged the pair contradictory; awaiting user resolution.
+    pub const OPEN: &str = "open";
+    /// LLM judged the pair compatible; kept so it is not re-checked.
+    pub const CONSISTENT: &str = "consistent";
+    /// User resolved the conflict by removing one entry.
+    pub const RESOLVED: &str = "resolved";
+    /// User chose to keep both entries.
+    pub const DISMISSED: &str = "dismissed";
+}
+
+/// Two memory entries whose embeddings are highly similar.
+/// `older_*` always refers to the entry with the lower ID.
+#[derive(Debug, Clone)]
+pub struct SimilarPair {
+    pub older_id: i64,
+    pub older_source_key: String,
+    pub older_content: String,
+    pub newer_id: i64,
+    pub newer_source_key: String,
+    pub newer_content: String,
+    pub similarity: f32,
+}
+
+/// A recorded contradiction between two memory entries.
+#[derive(Debug, Clone)]
+pub struct MemoryConflict {
+    pub id: i64,
+    pub older_entry_id: i64,
+    pub newer_entry_id: i64,
+    pub older_source_key: String,
+    pub newer_source_key: String,
+    pub older_content: String,
+    pub newer_content: String,
+    pub similarity: f64,
+    pub status: String,
+    pub explanation: Option<String>,
+    pub detected_at: String,
+}
+
+/// How the user chose to resolve a conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Keep the newer entry and delete the older one.
+    KeepNewer,
+    /// Keep the older entry and delete the newer one.
+    KeepOlder,
+    /// Keep both entries; the conflict is dismissed.
+    KeepBoth,
+}
+
+impl MemoryDB {
+    /// Embedding dimension of the most recently stored embedding, used to
+    /// filter out vectors left over from a previous embedding model.
+    fn latest_embedding_dim(&self) -> Result<Option<usize>> {
+        let conn = self.lock_conn()?;
+        let len: Option<usize> = conn
+            .query_row(
+                "SELECT length(embedding) FROM memory_embeddings ORDER BY entry_id DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(len.map(|l| l / 4))
+    }
+
+    /// Pairs already judged by the gardener (any status).
+    fn judged_conflict_pairs(&self) -> Result<HashSet<(i64, i64)>> {
+        let conn = self.lock_conn()?;
+        let mut stmt =
+            conn.prepare("SELECT older_entry_id, newer_entry_id FROM memory_conflicts")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Find pairs of memory entries with cosine similarity at or above
+    /// `min_similarity` that have not been judged before. Identical content is
+    /// skipped (that is duplication, not contradiction). Results are sorted by
+    /// similarity, highest first, and capped at `max_pairs`.
+    pub fn find_similar_pairs(
+        &self,
+        min_similarity: f32,
+        max_pairs: usize,
+    ) -> Result<Vec<SimilarPair>> {
+        let Some(dim) = self.latest_embedding_dim()? else {
+            return Ok(vec![]);
+        };
+        let mut entries = self.get_cached_embeddings(None, dim)?;
+        entries.sort_by_key(|e| e.entry_id);
+        if entries.len() > MAX_GARDEN_ENTRIES {
+            entries.drain(..entries.len() - MAX_GARDEN_ENTRIES);
+        }
+        let judged = self.judged_conflict_pairs()?;
+
+        let mut pairs = Vec::new();
+        for (i, older) in entries.iter().enumerate() {
+            for newer in &entries[i + 1..] {
+                if older.content == newer.content
+                    || judged.contains(&(older.entry_id, newer.entry_id))
+                {
+                    continue;
+                }
+                let similarity = cosine_similarity(&older.embedding, &newer.embedding);
+                if similarity >= min_similarity {
+                    pairs.push(SimilarPair {
+                        older_id: older.entry_id,
+                        older_source_key: older.source_key.clone(),
+                        older_content: older.content.clone(),
+                        newer_id: newer.entry_id,
+                        newer_source_key: newer.source_key.clone(),
+                        newer_content: newer.content.clone(),
+                        similarity,
+                    });
+                }
+            }
+        }
+        pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        pairs.truncate(max_pairs);
+        Ok(pairs)
+    }
+
+    /// Record the gardener's verdict for a pair. Duplicate pairs are ignored.
+    pub fn record_memory_conflict(
+        &self,
+        pair: &SimilarPair,
+        status: &str,
+        explanation: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT OR IGNORE INTO memory_conflicts
+             (older_entry_id, newer_entry_id, older_source_key, newer_source_key,
+              older_content, newer_content, similarity, status, explanation, detected_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                pair.older_id,
+                pair.newer_id,
+                pair.older_source_key,
+                pair.newer_source_key,
+                pair.older_content,
+                pair.newer_content,
+                f64::from(pair.similarity),
+                status,
+                explanation,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// List recorded conflicts, optionally filtered by status (newest first).
+    pub fn list_memory_conflicts(&self, status: Option<&str>) -> Result<Vec<MemoryConflict>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, older_entry_id, newer_entry_id, older_source_key, newer_source_key,
+                    older_content, newer_content, similarity, status, explanation, detected_at
+             FROM memory_conflicts
+             WHERE ?1 IS NULL OR status = ?1
+             ORDER BY id DESC",
+        )?;
+        let rows = stmt
+            .query_map(params![status], |row| {
+                Ok(MemoryConflict {
+                    id: row.get(0)?,
+                    older_entry_id: row.get(1)?,
+                    newer_entry_id: row.get(2)?,
+                    older_source_key: row.get(3)?,
+                    newer_source_key: row.get(4)?,
+                    older_content: row.get(5)?,
+                    newer_content: row.get(6)?,
+                    similarity: row.get(7)?,
+                    status: row.get(8)?,
+                    explanation: row.get(9)?,
+                    detected_at: row.get(10)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Resolve an open conflict. Removes the stale entry (and its embedding)
+    /// unless `KeepBoth` is chosen. Other open conflicts that reference the
+    /// removed entry are closed too. Returns the removed entry's content.
+    pub fn resolve_memory_conflict(
+        &self,
+        id: i64,
+        resolution: ConflictResolution,
+    ) -> Result<Option<String>> {
+        let mut conn = self.lock_conn()?;
+        let tx = conn.transaction()?;
+        let row: Option<(i64, i64, String, String, String, String)> = tx
+            .query_row(
+                "SELECT older_entry_id, newer_entry_id, older_source_key, newer_source_key,
+                        older_content, newer_content
+                 FROM memory_conflicts WHERE id = ?1 AND status = ?2",
+                params![id, conflict_status::OPEN],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((older_id, newer_id, older_source, newer_source, older_content, newer_content)) =
+            row
+        else {
+            anyhow::bail!("no open memory conflict with id {id}");
+        };
+
+        let now = Utc::now().to_rfc3339();
+        let stale = match resolution {
+            ConflictResolution::KeepNewer => Some((older_id, older_source, older_content)),
+            ConflictResolution::KeepOlder => Some((newer_id, newer_source, newer_content)),
+            ConflictResolution::KeepBoth => None,
+        };
+
+        let Some((stale_id, stale_source, stale_content)) = stale else {
+            tx.execute(
+                "UPDATE memory_conflicts SET status = ?1, resolved_at = ?2 WHERE id = ?3",
+                params![conflict_status::DISMISSED, now, id],
+            )?;
+            tx.commit()?;
+            return Ok(None);
+        };
+
+        if stale_source.starts_with("knowledge:") {
+            anyhow::bail!("cannot remove knowledge entries; they are protected from deletion");
+        }
+
+        tx.execute(
+            "DELETE FROM memory_embeddings WHERE entry_id = ?1",
+            params![stale_id],
+        )?;
+        tx.execute(
+            "DELETE FROM memory_entries WHERE id = ?1",
+            params![stale_id],
+        )?;
+        tx.execute(
+            "DELETE FROM memory_sources WHERE source_key = ?1
+             AND source_key NOT IN (SELECT DISTINCT source_key FROM memory_entries)",
+            params![stale_source],
+        )?;
+        tx.execute(
+            "UPDATE memory_conflicts SET status = ?1, resolved_at = ?2
+             WHERE id = ?3
+                OR (status = ?4 AND (older_entry_id = ?5 OR newer_entry_id = ?5))",
+            params![
+                conflict_status::RESOLVED,
+                now,
+                id,
+                conflict_status::OPEN,
+                stale_id
+            ],
+        )?;
+        tx.commit()?;
+        drop(conn);
+        self.invalidate_embedding_cache();
+        Ok(Some(stale_content))
+    }
+}
@@ -13,6 +13,53 @@ pub struct TokenSummaryRow {
     pub call_count: i64,
 }
 
+/// Estimated input tokens of one agent turn, by where they came from. Summed
+/// over every LLM call of the turn, since each call resends the whole context.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenBreakdown {
+    pub calls: u64,
+    /// System prompt and framework-injected instructions.
+    pub system: u64,
+    pub tool_definitions: u64,
+    /// Conversation history loaded for the turn.
+    pub history: u64,
+    /// The user message that started the turn.
+    pub message: u64,
+    pub tool_results: u64,
+    /// Assistant text and tool calls produced during the turn.
+    pub assistant: u64,
+    /// Input tokens the provider reported for the same calls (0 if unreported).
+    pub provider_input: u64,
+}
+
+impl TokenBreakdown {
+    /// Estimated categories with display labels, in display order.
+    pub fn categories(&self) -> [(&'static str, u64); 6] {
+        [
+            ("system prompt", self.system),
+            ("tool definitions", self.tool_definitions),
+            ("history", self.history),
+            ("current message", self.message),
+            ("tool results", self.tool_results),
+            ("assistant", self.assistant),
+        ]
+    }
+
+    pub fn estimated_total(&self) -> u64 {
+        self.categories().iter().map(|(_, tokens)| tokens).sum()
+    }
+}
+
+/// A recorded [`TokenBreakdown`] for one turn.
+#[derive(Debug, Clone)]
+pub struct TokenBreakdownRow {
+    pub timestamp: String,
+    pub session_key: String,
+    pub request_id: Option<String>,
+    pub model: String,
+    pub breakdown: TokenBreakdown,
+}
+
 impl MemoryDB {
     /// Record LLM token usage.
     #[allow(clippy::too_many_arguments)]
@@ -61,8 +108,30 @@ impl MemoryDB {
 
     /// Get token usage summary grouped by date and model since a given date (YYYY-MM-DD).
     pub fn get_token_summary(&self, since_date: &str) -> Result<Vec<TokenSummaryRow>> {
+        self.token_summary(since_date, None)
+    }
+
+    /// Get token usage summary grouped by date and model for the days from
+    /// `since_date` through `until_date` (both YYYY-MM-DD, inclusive).
+    pub fn get_token_summary_range(
+        &self,
+        since_date: &str,
+        until_date: &str,
+    ) -> Result<Vec<TokenSummaryRow>> {
+        self.token_summary(since_date, Some(until_date))
+    }
+
+    fn token_summary(
+        &self,
+        since_date: &str,
+        until_date: Option<&str>,
+    ) -> Result<Vec<TokenSummaryRow>> {
         let conn = self.lock_conn()?;
         let since_datetime = format!("{since_date} 00:00:00");
+        let until_datetime = until_date.map_or_else(
+            || "9999-12-31 23:59:59".to_string(),
+            |d| format!("{d} 23:59:59"),
+        );
         let mut stmt = conn.prepare(
             "SELECT DATE(timestamp) as day, model,
                     SUM(input_tokens) as total_input,
@@ -71,12 +140,12 @@ impl MemoryDB {
                     COALESCE(SUM(cache_read_tokens), 0) as total_cache_read,
                     COUNT(*) as call_count
              FROM llm_cost_log
-             WHERE timestamp >= ?
+             WHERE timestamp >= ? AND timestamp <= ?
              GROUP BY day, model
              ORDER BY day DESC, total_input DESC",
         )?;
         let rows: Result<Vec<_>, _> = stmt
-            .query_map([&since_datetime], |row| {
+            .query_map(params![since_datetime, until_datetime], |row| {
                 Ok(TokenSummaryRow {
                     date: row.get(0)?,
                     model: row.get(1)?,
@@ -90,4 +159,89 @@ impl MemoryDB {
             .collect();
         rows.map_err(|e| anyhow::anyhow!("failed to get token summary: {e}"))
     }
+
+    /// Record the token breakdown of one turn of `session_key`.
+    pub fn record_token_breakdown(
+        &self,
+        session_key: &str,
+        request_id: Option<&str>,
+        model: &str,
+        breakdown: &TokenBreakdown,
+    ) -> Result<()> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT INTO token_breakdown_log
+             (session_key, request_id, model, calls, system_tokens, tool_definition_tokens,
+              history_tokens, message_tokens, tool_result_tokens, assistant_tokens,
+              provider_input_tokens)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                session_key,
+                request_id,
+                model,
+                breakdown.calls as i64,
+                breakdown.system as i64,
+                breakdown.tool_definitions as i64,
+                breakdown.history as i64,
+                breakdown.message as i64,
+                breakdown.tool_results as i64,
+                breakdown.assistant as i64,
+                breakdown.provider_input as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get the `limit` most recent turn breakdowns of `session_key`, newest first.
+    pub fn get_token_breakdowns(
+        &self,
+        session_key: &str,
+        limit: usize,
+    ) -> Result<Vec<TokenBreakdownRow>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT timestamp, session_key, request_id, model, calls, system_tokens,
+                    tool_definition_tokens, history_tokens, message_tokens,
+                    tool_result_tokens, assistant_tokens, provider_input_tokens
+             FROM token_breakdown_log
+             WHERE session_key = ?
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?",
+        )?;
+        let rows: Result<Vec<_>, _> = stmt
+            .query_map(params![session_key, limit as i64], |row| {
+                let count = |i: usize| row.get::<_, i64>(i).map(|n| n.max(0) as u64);
+                Ok(TokenBreakdownRow {
+                    timestamp: row.get(0)?,
+                    session_key: row.get(1)?,
+                    request_id: row.get(2)?,
+                    model: row.get(3)?,
+                    breakdown: TokenBreakdown {
+                        calls: count(4)?,
+                        system: count(5)?,
+                        tool_definitions: count(6)?,
+                        history: count(7)?,
+                        message: count(8)?,
+                        tool_results: count(9)?,
+                        assistant: count(10)?,
+                        provider_input: count(11)?,
+                    },
+                })
+            })?
+            .collect();
+        rows.map_err(|e| anyhow::anyhow!("failed to get token breakdowns: {e}"))
+    }
+
+    /// Purge token breakdowns older than `days`. Returns number of rows deleted.
+    pub fn purge_old_token_breakdowns(&self, days: u32) -> Result<usize> {
+        if days == 0 {
+            return Ok(0);
+        }
+        let conn = self.lock_conn()?;
+        let deleted = conn.execute(
+            "DELETE FROM token_breakdown_log WHERE timestamp < datetime('now', ?1)",
+            params![format!("-{days} days")],
+        )?;
+        Ok(deleted)
+    }
 }
@@ -1,7 +1,7 @@
 use super::MemoryDB;
 use anyhow::Result;
 use oxicrab_core::cron_types::{
-    CronJob, CronJobState, CronPayload, CronSchedule, CronTarget, UpdateJobParams,
+    CronJob, CronJobState, CronPayload, CronSchedule, CronTarget, SolarEvent, UpdateJobParams,
 };
 use rusqlite::params;
 use std::collections::HashMap;
@@ -12,9 +12,19 @@ fn schedule_type_str(schedule: &CronSchedule) -> &'static str {
         CronSchedule::Every { .. } => "every",
         CronSchedule::Cron { .. } => "cron",
         CronSchedule::Event { .. } => "event",
+        CronSchedule::Solar { .. } => "solar",
     }
 }
 
+/// `solar_*` columns of a `cron_jobs` row.
+struct SolarColumns {
+    event: Option<String>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    offset_minutes: Option<i64>,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn schedule_from_row(
     schedule_type: &str,
     at_ms: Option<i64>,
@@ -23,6 +33,7 @@ fn schedule_from_row(
     cron_tz: Option<String>,
     event_pattern: Option<String>,
     event_channel: Option<String>,
+    solar: SolarColumns,
 ) -> Result<CronSchedule> {
     match schedule_type {
         "at" => Ok(CronSchedule::At { at_ms }),
@@ -35,6 +46,17 @@ fn schedule_from_row(
             pattern: event_pattern,
             channel: event_channel,
         }),
+        "solar" => {
+            let (Some(event), Some(lat), Some(lon)) = (solar.event, solar.lat, solar.lon) else {
+                anyhow::bail!("solar schedule is missing its event or coordinates");
+            };
+            Ok(CronSchedule::Solar {
+                event: event.parse::<SolarEvent>()?,
+                lat,
+                lon,
+                offset_minutes: solar.offset_minutes.unwrap_or(0),
+            })
+        }
         other => anyhow::bail!("unknown schedule_type: {other}"),
     }
 }
@@ -46,6 +68,10 @@ struct ScheduleColumns<'a> {
     cron_tz: Option<&'a str>,
     event_pattern: Option<&'a str>,
     event_channel: Option<&'a str>,
+    solar_event: Option<&'static str>,
+    solar_lat: Option<f64>,
+    solar_lon: Option<f64>,
+    solar_offset_minutes: Option<i64>,
 }
 
 fn schedule_columns(schedule: &CronSchedule) -> ScheduleColumns<'_> {
@@ -57,6 +83,10 @@ fn schedule_columns(schedule: &CronSchedule) -> ScheduleColumns<'_> {
             cron_tz: None,
             event_pattern: None,
             event_channel: None,
+            solar_event: None,
+            solar_lat: None,
+            solar_lon: None,
+            solar_offset_minutes: None,
         },
         CronSchedule::Every { every_ms } => ScheduleColumns {
             at_ms: None,
@@ -65,6 +95,10 @@ fn schedule_columns(schedule: &CronSchedule) -> ScheduleColumns<'_> {
             cron_tz: None,
             event_pattern: None,
             event_channel: None,
+            solar_event: None,
+            solar_lat: None,
+            solar_lon: None,
+            solar_offset_minutes: None,
         },
         CronSchedule::Cron { expr, tz } => ScheduleColumns {
             at_ms: None,
@@ -73,6 +107,10 @@ fn schedule_columns(schedule: &CronSchedule) -> ScheduleColumns<'_> {
             cron_tz: tz.as_deref(),
             event_pattern: None,
             event_channel: None,
+            solar_event: None,
+            solar_lat: None,
+            solar_lon: None,
+            solar_offset_minutes: None,
         },
         CronSchedule::Event { pattern, channel } => ScheduleColumns {
             at_ms: None,
@@ -81,6 +119,27 @@ fn schedule_columns(schedule: &CronSchedule) -> ScheduleColumns<'_> {
             cron_tz: None,
             event_pattern: pattern.as_deref(),
             event_channel: channel.as_deref(),
+            solar_event: None,
+            solar_lat: None,
+            solar_lon: None,
+            solar_offset_minutes: None,
+        },
+        CronSchedule::Solar {
+            event,
+            lat,
+            lon,
+            offset_minutes,
+        } => ScheduleColumns {
+            at_ms: None,
+            every_ms: None,
+            cron_expr: None,
+            cron_tz: None,
+            event_pattern: None,
+            event_channel: None,
+            solar_event: Some(event.as_str()),
+            solar_lat: Some(*lat),
+            solar_lon: Some(*lon),
+            solar_offset_minutes: Some(*offset_minutes),
         },
     }
 }
@@ -100,7 +159,8 @@ impl MemoryDB {
                     next_run_at_ms, last_run_at_ms, last_status, last_error,
                     run_count, last_fired_at_ms,
                     created_at_ms, updated_at_ms, delete_after_run,
-                    expires_at_ms, max_runs, cooldown_secs, max_concurrent
+                    expires_at_ms, max_runs, cooldown_secs, max_concurrent,
+                    solar_event, solar_lat, solar_lon, solar_offset_minutes
                 ) VALUES (
                     ?1, ?2, ?3, ?4,
                     ?5, ?6, ?7, ?8, ?9, ?10,
@@ -108,7 +168,8 @@ impl MemoryDB {
                     ?14, ?15, ?16, ?17,
                     ?18, ?19,
                     ?20, ?21, ?22,
-                    ?23, ?24, ?25, ?26
+                    ?23, ?24, ?25, ?26,
+                    ?27, ?28, ?29, ?30
                 )",
             params![
                 job.id,
@@ -137,14 +198,18 @@ impl MemoryDB {
                 job.max_runs,
                 job.cooldown_secs.map(|v| v as i64),
                 job.max_concurrent,
+                cols.solar_event,
+                cols.solar_lat,
+                cols.solar_lon,
+                cols.solar_offset_minutes,
             ],
         )?;
 
         for target in &job.payload.targets {
             tx.execute(
-                "INSERT INTO cron_job_targets (job_id, channel, target)
-                     VALUES (?1, ?2, ?3)",
-                params![job.id, target.channel, target.to],
+                "INSERT INTO cron_job_targets (job_id, channel, target, message_override)
+                     VALUES (?1, ?2, ?3, ?4)",
+                params![job.id, target.channel, target.to, target.message_override],
             )?;
         }
         tx.commit()?;
@@ -167,7 +232,8 @@ impl MemoryDB {
                     next_run_at_ms, last_run_at_ms, last_status, last_error,
                     run_count, last_fired_at_ms,
                     created_at_ms, updated_at_ms, delete_after_run,
-                    expires_at_ms, max_runs, cooldown_secs, max_concurrent
+                    expires_at_ms, max_runs, cooldown_secs, max_concurrent,
+                    solar_event, solar_lat, solar_lon, solar_offset_minutes
              FROM cron_jobs ORDER BY created_at_ms"
         } else {
             "SELECT id, name, enabled, schedule_type,
@@ -176,15 +242,16 @@ impl MemoryDB {
                     next_run_at_ms, last_run_at_ms, last_status, last_error,
                     run_count, last_fired_at_ms,
                     created_at_ms, updated_at_ms, delete_after_run,
-                    expires_at_ms, max_runs, cooldown_secs, max_concurrent
+                    expires_at_ms, max_runs, cooldown_secs, max_concurrent,
+                    solar_event, solar_lat, solar_lon, solar_offset_minutes
              FROM cron_jobs WHERE enabled = 1 ORDER BY created_at_ms"
         };
 
         // Load targets only for jobs matching the filter
         let target_sql = if include_disabled {
-            "SELECT job_id, channel, target FROM cron_job_targets ORDER BY rowid"
+            "SELECT job_id, channel, target, message_override FROM cron_job_targets ORDER BY rowid"
         } else {
-            "SELECT t.job_id, t.channel, t.target FROM cron_job_targets t
+            "SELECT t.job_id, t.channel, t.target, t.message_override FROM cron_job_targets t
              INNER JOIN cron_jobs j ON j.id = t.job_id
              WHERE j.enabled = 1
              ORDER BY t.rowid"
@@ -198,6 +265,7 @@ impl MemoryDB {
                     CronTarget {
                         channel: row.get(1)?,
                         to: row.get(2)?,
+                        message_override: row.get(3)?,
                     },
                 ))
             })?;
@@ -236,6 +304,12 @@ impl MemoryDB {
                 max_runs: row.get(23)?,
                 cooldown_secs: row.get(24)?,
                 max_concurrent: row.get(25)?,
+                solar: SolarColumns {
+                    event: row.get(26)?,
+                    lat: row.get(27)?,
+                    lon: row.get(28)?,
+                    offset_minutes: row.get(29)?,
+                },
             })
         })?;
 
@@ -250,6 +324,7 @@ impl MemoryDB {
                 r.cron_tz,
                 r.event_pattern,
                 r.event_channel,
+                r.solar,
             )
             .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
             let targets = target_map.remove(&r.id).unwrap_or_default();
@@ -295,7 +370,8 @@ impl MemoryDB {
                     next_run_at_ms, last_run_at_ms, last_status, last_error,
                     run_count, last_fired_at_ms,
                     created_at_ms, updated_at_ms, delete_after_run,
-                    expires_at_ms, max_runs, cooldown_secs, max_concurrent
+                    expires_at_ms, max_runs, cooldown_secs, max_concurrent,
+                    solar_event, solar_lat, solar_lon, solar_offset_minutes
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -313,19 +389,27 @@ impl MemoryDB {
             row.get(7)?,
             row.get(8)?,
             row.get(9)?,
+            SolarColumns {
+                event: row.get(26)?,
+                lat: row.get(27)?,
+                lon: row.get(28)?,
+                offset_minutes: row.get(29)?,
+            },
         )?;
 
         let job_id: String = row.get(0)?;
 
         // Load targets for this job
         let mut target_stmt = conn.prepare(
-            "SELECT channel, target FROM cron_job_targets WHERE job_id = ?1 ORDER BY rowid",
+            "SELECT channel, target, message_override FROM cron_job_targets
+             WHERE job_id = ?1 ORDER BY rowid",
         )?;
         let targets = target_stmt
             .query_map(params![job_id], |r| {
                 Ok(CronTarget {
                     channel: r.get(0)?,
                     to: r.get(1)?,
+                    message_override: r.get(2)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -485,6 +569,18 @@ impl MemoryDB {
 
             param_values.push(Box::new(cols.event_channel.map(ToString::to_string)));
             set_clauses.push(format!("event_channel = ?{}", param_values.len()));
+
+            param_values.push(Box::new(cols.solar_event));
+            set_clauses.push(format!("solar_event = ?{}", param_values.len()));
+
+            param_values.push(Box::new(cols.solar_lat));
+            set_clauses.push(format!("solar_lat = ?{}", param_values.len()));
+
+            param_values.push(Box::new(cols.solar_lon));
+            set_clauses.push(format!("solar_lon = ?{}", param_values.len()));
+
+            param_values.push(Box::new(cols.solar_offset_minutes));
+            set_clauses.push(format!("solar_offset_minutes = ?{}", param_values.len()));
         }
 
         if let Some(next_run) = next_run_at_ms {
@@ -516,9 +612,9 @@ impl MemoryDB {
             )?;
             for target in targets {
                 tx.execute(
-                    "INSERT INTO cron_job_targets (job_id, channel, target)
-                         VALUES (?1, ?2, ?3)",
-                    params![id, target.channel, target.to],
+                    "INSERT INTO cron_job_targets (job_id, channel, target, message_override)
+                         VALUES (?1, ?2, ?3, ?4)",
+                    params![id, target.channel, target.to, target.message_override],
                 )?;
             }
         }
@@ -643,13 +739,14 @@ struct CronJobRow {
     max_runs: Option<u32>,
     cooldown_secs: Option<i64>,
     max_concurrent: Option<u32>,
+    solar: SolarColumns,
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::MemoryDB;
     use oxicrab_core::cron_types::{
-        CronJob, CronJobState, CronPayload, CronSchedule, CronTarget, UpdateJobParams,
+        CronJob, CronJobState, CronPayload, CronSchedule, CronTarget, SolarEvent, UpdateJobParams,
     };
 
     fn make_test_job(id: &str, name: &str, schedule: CronSchedule) -> CronJob {
@@ -665,6 +762,7 @@ mod tests {
                 targets: vec![CronTarget {
                     channel: "slack".to_string(),
                     to: "C123".to_string(),
+                    message_override: None,
                 }],
             },
             state: CronJobState::default(),
@@ -762,6 +860,58 @@ mod tests {
         assert!(db.get_cron_job("no-such-job").unwrap().is_none());
     }
 
+    #[test]
+    fn test_solar_schedule_roundtrip() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        let job = make_test_job(
+            "job-solar",
+            "dawn",
+            CronSchedule::Solar {
+                event: SolarEvent::Sunrise,
+                lat: 51.5074,
+                lon: -0.1278,
+                offset_minutes: -15,
+            },
+        );
+        db.insert_cron_job(&job).unwrap();
+
+        let got = db.get_cron_job("job-solar").unwrap().unwrap();
+        let CronSchedule::Solar {
+            event,
+            lat,
+            lon,
+            offset_minutes,
+        } = got.schedule
+        else {
+            panic!("expected Solar schedule");
+        };
+        assert_eq!(event, SolarEvent::Sunrise);
+        assert!((lat - 51.5074).abs() < f64::EPSILON);
+        assert!((lon + 0.1278).abs() < f64::EPSILON);
+        assert_eq!(offset_minutes, -15);
+
+        // Switching to another schedule kind clears the solar columns
+        let params = UpdateJobParams {
+            schedule: Some(CronSchedule::Every {
+                every_ms: Some(60_000),
+            }),
+            ..Default::default()
+        };
+        db.update_cron_job("job-solar", &params, None, 2000)
+            .unwrap();
+        let jobs = db.list_cron_jobs(true).unwrap();
+        assert!(matches!(jobs[0].schedule, CronSchedule::Every { .. }));
+        let conn = db.lock_conn().unwrap();
+        let solar_event: Option<String> = conn
+            .query_row(
+                "SELECT solar_event FROM cron_jobs WHERE id = 'job-solar'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(solar_event.is_none());
+    }
+
     #[test]
     fn test_list_excludes_disabled() {
         let db = MemoryDB::new(":memory:").unwrap();
@@ -906,10 +1056,12 @@ mod tests {
                 CronTarget {
                     channel: "telegram".to_string(),
                     to: "12345".to_string(),
+                    message_override: None,
                 },
                 CronTarget {
                     channel: "discord".to_string(),
                     to: "67890".to_string(),
+                    message_override: Some("Good morning, Discord".to_string()),
                 },
             ]),
             ..Default::default()
@@ -928,6 +1080,17 @@ mod tests {
             .collect();
         assert!(channels.contains(&"telegram"));
         assert!(channels.contains(&"discord"));
+        assert_eq!(got.payload.targets[0].message_override, None);
+        assert_eq!(
+            got.payload.targets[1].message_override.as_deref(),
+            Some("Good morning, Discord")
+        );
+
+        let listed = db.list_cron_jobs(true).unwrap();
+        assert_eq!(
+            listed[0].payload.targets[1].message("hello world"),
+            "Good morning, Discord"
+        );
     }
 
     #[test]
@@ -11,6 +11,7 @@ pub(super) struct CachedEmbedding {
     pub entry_id: i64,
     pub source_key: String,
     pub content: String,
+    pub importance: u8,
     pub embedding: Vec<f32>,
 }
 
@@ -29,16 +30,31 @@ impl MemoryDB {
         Ok(())
     }
 
+    /// Delete stored embeddings that are not `dimensions` floats long, e.g.
+    /// after switching embedding backend or model. Returns how many were
+    /// deleted; the entries are left for re-embedding.
+    pub fn delete_embeddings_not_of_dimension(&self, dimensions: usize) -> Result<usize> {
+        let conn = self.lock_conn()?;
+        let deleted = conn.execute(
+            "DELETE FROM memory_embeddings WHERE length(embedding) != ?",
+            params![dimensions * 4],
+        )?;
+        if deleted > 0 {
+            self.invalidate_embedding_cache();
+        }
+        Ok(deleted)
+    }
+
     /// Get all embeddings, optionally excluding certain source keys.
-    /// Returns (`entry_id`, `source_key`, content, `embedding_blob`).
+    /// Returns (`entry_id`, `source_key`, content, importance, `embedding_blob`).
     #[allow(clippy::type_complexity)]
     pub(super) fn get_all_embeddings(
         &self,
         exclude_sources: Option<&std::collections::HashSet<String>>,
-    ) -> Result<Vec<(i64, String, String, Vec<u8>)>> {
+    ) -> Result<Vec<(i64, String, String, u8, Vec<u8>)>> {
         let conn = self.lock_conn()?;
         let mut stmt = conn.prepare(
-            "SELECT me.id, me.source_key, me.content, emb.embedding
+            "SELECT me.id, me.source_key, me.content, me.importance, emb.embedding
              FROM memory_embeddings emb
              JOIN memory_entries me ON emb.entry_id = me.id",
         )?;
@@ -52,7 +68,8 @@ impl MemoryDB {
                     row.get::<_, i64>(0)?,
                     row.get::<_, String>(1)?,
                     row.get::<_, String>(2)?,
-                    row.get::<_, Vec<u8>>(3)?,
+                    row.get::<_, u8>(3)?,
+                    row.get::<_, Vec<u8>>(4)?,
                 ))
             })?
             .collect();
@@ -60,7 +77,7 @@ impl MemoryDB {
         Ok(rows
             .map_err(|e| anyhow::anyhow!("Failed to get embeddings: {e}"))?
             .into_iter()
-            .filter(|(_, source_key, _, _)| !exclude.contains(source_key))
+            .filter(|(_, source_key, _, _, _)| !exclude.contains(source_key))
             .collect())
     }
 
@@ -123,7 +140,7 @@ impl MemoryDB {
         // Cache miss or stale — load from DB, deserialize, and cache
         let raw = self.get_all_embeddings(None)?;
         let mut entries = Vec::with_capacity(raw.len());
-        for (entry_id, source_key, content, emb_bytes) in raw {
+        for (entry_id, source_key, content, importance, emb_bytes) in raw {
             match deserialize_embedding(&emb_bytes) {
                 Ok(embedding) => {
                     if embedding.len() != expected_dim {
@@ -139,6 +156,7 @@ impl MemoryDB {
                         entry_id,
                         source_key,
                         content,
+                        importance,
                         embedding,
                     });
                 }
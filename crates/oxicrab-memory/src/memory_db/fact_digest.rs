@@ -0,0 +1,121 @@
+use super::MemoryDB;
+use anyhow::Result;
+use rusqlite::params;
+
+/// An extracted fact waiting to be listed in a fact digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingFact {
+    pub entry_id: i64,
+    pub channel: String,
+    pub chat_id: String,
+    pub content: String,
+}
+
+impl MemoryDB {
+    /// Queue a memory entry for the next fact digest sent to `channel`/`chat_id`.
+    pub fn queue_fact_digest(&self, entry_id: i64, channel: &str, chat_id: &str) -> Result<()> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT OR IGNORE INTO fact_digest (entry_id, channel, chat_id) VALUES (?1, ?2, ?3)",
+            params![entry_id, channel, chat_id],
+        )?;
+        Ok(())
+    }
+
+    /// Facts not yet included in a digest, oldest first. Entries deleted since
+    /// they were queued are skipped.
+    pub fn list_pending_fact_digest(&self) -> Result<Vec<PendingFact>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT fd.entry_id, fd.channel, fd.chat_id, me.content
+             FROM fact_digest fd
+             JOIN memory_entries me ON me.id = fd.entry_id
+             WHERE fd.digested_at IS NULL
+             ORDER BY fd.entry_id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(PendingFact {
+                    entry_id: row.get(0)?,
+                    channel: row.get(1)?,
+                    chat_id: row.get(2)?,
+                    content: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Mark facts as included in a sent digest.
+    pub fn mark_fact_digest_sent(&self, entry_ids: &[i64]) -> Result<()> {
+        let mut conn = self.lock_conn()?;
+        let tx = conn.transaction()?;
+        for id in entry_ids {
+            tx.execute(
+                "UPDATE fact_digest SET digested_at = datetime('now') WHERE entry_id = ?1",
+                params![id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MemoryDB;
+
+    #[test]
+    fn test_fact_digest_queue_roundtrip() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        let a = db
+            .insert_memory_entry("daily:2024-01-01:Facts", "User has a dog named Rex")
+            .unwrap()
+            .unwrap();
+        let b = db
+            .insert_memory_entry("daily:2024-01-01:Facts", "User works night shifts")
+            .unwrap()
+            .unwrap();
+        // Duplicate content under the same source is ignored
+        assert!(
+            db.insert_memory_entry("daily:2024-01-01:Facts", "User has a dog named Rex")
+                .unwrap()
+                .is_none()
+        );
+
+        db.queue_fact_digest(a, "telegram", "42").unwrap();
+        db.queue_fact_digest(b, "slack", "C1").unwrap();
+        let pending = db.list_pending_fact_digest().unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].entry_id, a);
+        assert_eq!(pending[0].chat_id, "42");
+        assert_eq!(pending[1].content, "User works night shifts");
+
+        db.mark_fact_digest_sent(&[a]).unwrap();
+        let pending = db.list_pending_fact_digest().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].entry_id, b);
+
+        // Forgetting a fact drops it from the digest queue
+        assert_eq!(
+            db.delete_memory_entry(b).unwrap().as_deref(),
+            Some("User works night shifts")
+        );
+        assert!(db.list_pending_fact_digest().unwrap().is_empty());
+        assert!(db.delete_memory_entry(b).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_delete_memory_entry_protects_knowledge() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        let id = db
+            .insert_memory_entry("knowledge:faq.md", "Office hours are 9-5")
+            .unwrap()
+            .unwrap();
+        assert!(db.delete_memory_entry(id).is_err());
+        assert_eq!(
+            db.get_recent_entries("knowledge:faq.md", 10).unwrap().len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use super::MemoryDB;
+use anyhow::Result;
+use rusqlite::{OptionalExtension, params};
+
+impl MemoryDB {
+    /// Cached description for the image with content hash `hash`, if one was
+    /// stored within the last `ttl_days` days. A hit refreshes `last_used_at`.
+    pub fn get_image_description(&self, hash: &str, ttl_days: u32) -> Result<Option<String>> {
+        let conn = self.lock_conn()?;
+        let cutoff = format!("-{ttl_days} days");
+        let description: Option<String> = conn
+            .query_row(
+                "SELECT description FROM image_descriptions
+                 WHERE hash = ?1 AND created_at >= datetime('now', ?2)",
+                params![hash, cutoff],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if description.is_some() {
+            conn.execute(
+                "UPDATE image_descriptions SET last_used_at = datetime('now') WHERE hash = ?1",
+                params![hash],
+            )?;
+        }
+        Ok(description)
+    }
+
+    /// Store (or replace) the description for `hash`, purging entries older
+    /// than `ttl_days` days.
+    pub fn put_image_description(
+        &self,
+        hash: &str,
+        description: &str,
+        ttl_days: u32,
+    ) -> Result<()> {
+        let mut conn = self.lock_conn()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO image_descriptions (hash, description) VALUES (?1, ?2)",
+            params![hash, description],
+        )?;
+        tx.execute(
+            "DELETE FROM image_descriptions WHERE created_at < datetime('now', ?1)",
+            params![format!("-{ttl_days} days")],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MemoryDB;
+
+    #[test]
+    fn test_image_description_roundtrip_and_ttl() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        assert!(db.get_image_description("abc", 30).unwrap().is_none());
+
+        db.put_image_description("abc", "a red bicycle", 30)
+            .unwrap();
+        assert_eq!(
+            db.get_image_description("abc", 30).unwrap().as_deref(),
+            Some("a red bicycle")
+        );
+
+        // Entries older than the TTL are neither returned nor kept
+        db.lock_conn()
+            .unwrap()
+            .execute(
+                "UPDATE image_descriptions SET created_at = datetime('now', '-40 days')",
+                [],
+            )
+            .unwrap();
+        assert!(db.get_image_description("abc", 30).unwrap().is_none());
+        db.put_image_description("def", "a cat", 30).unwrap();
+        let count: i64 = db
+            .lock_conn()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM image_descriptions", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
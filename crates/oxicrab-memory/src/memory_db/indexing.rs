@@ -1,38 +1,93 @@
 use super::{MemoryDB, hash_text};
+use crate::quality::score_importance;
 use anyhow::Result;
 use chrono::Utc;
-use rusqlite::params;
+use rusqlite::{OptionalExtension, params};
 
 impl MemoryDB {
     /// Insert a single memory entry directly into the DB (no backing file required).
-    /// Empty or whitespace-only content is silently ignored.
+    /// Empty or whitespace-only content is silently ignored. The entry's
+    /// importance is scored from its content by [`score_importance`].
     ///
     /// NOTE: Memory entries are not user-scoped. In a multi-user deployment,
     /// all users share the same memory pool. This is by design for a single-user
     /// personal agent. Multi-tenant isolation would require adding a scope/owner
     /// column and filtering on it in all search queries.
     pub fn insert_memory(&self, source_key: &str, content: &str) -> Result<()> {
+        self.insert_memory_entry(source_key, content).map(|_| ())
+    }
+
+    /// Like [`insert_memory`](Self::insert_memory), but returns the new entry's
+    /// ID. Returns `None` for empty content or when an identical entry already
+    /// exists under the same source key.
+    pub fn insert_memory_entry(&self, source_key: &str, content: &str) -> Result<Option<i64>> {
         if content.trim().is_empty() {
-            return Ok(());
+            return Ok(None);
         }
         let now = Utc::now().to_rfc3339();
         let hash = hash_text(content);
+        let importance = score_importance(content);
         let mut conn = self.lock_conn()?;
         let tx = conn.transaction()?;
-        tx.execute(
-            "INSERT OR IGNORE INTO memory_entries (source_key, content, content_hash, created_at) VALUES (?, ?, ?, ?)",
-            params![source_key, content, hash, now],
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO memory_entries (source_key, content, content_hash, created_at, importance) VALUES (?, ?, ?, ?, ?)",
+            params![source_key, content, hash, now, importance],
         )?;
+        let entry_id = (inserted > 0).then(|| tx.last_insert_rowid());
         tx.execute(
             "INSERT INTO memory_sources (source_key, mtime_ns, updated_at) VALUES (?, 0, ?) ON CONFLICT(source_key) DO UPDATE SET updated_at = excluded.updated_at",
             params![source_key, now],
         )?;
         tx.commit()?;
         self.invalidate_embedding_cache();
-        Ok(())
+        Ok(entry_id)
+    }
+
+    /// Delete a single memory entry by ID, with its embedding. Knowledge
+    /// entries are protected. Returns the deleted content, or `None` when no
+    /// entry has that ID.
+    pub fn delete_memory_entry(&self, entry_id: i64) -> Result<Option<String>> {
+        let mut conn = self.lock_conn()?;
+        let tx = conn.transaction()?;
+        let row: Option<(String, String)> = tx
+            .query_row(
+                "SELECT source_key, content FROM memory_entries WHERE id = ?1",
+                params![entry_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((source_key, content)) = row else {
+            return Ok(None);
+        };
+        if source_key.starts_with("knowledge:") {
+            anyhow::bail!("cannot remove knowledge entries; they are protected from deletion");
+        }
+        tx.execute(
+            "DELETE FROM memory_embeddings WHERE entry_id = ?1",
+            params![entry_id],
+        )?;
+        tx.execute(
+            "DELETE FROM memory_entries WHERE id = ?1",
+            params![entry_id],
+        )?;
+        tx.execute(
+            "DELETE FROM fact_digest WHERE entry_id = ?1",
+            params![entry_id],
+        )?;
+        tx.execute(
+            "DELETE FROM memory_sources WHERE source_key = ?1
+             AND source_key NOT IN (SELECT DISTINCT source_key FROM memory_entries)",
+            params![source_key],
+        )?;
+        tx.commit()?;
+        drop(conn);
+        self.invalidate_embedding_cache();
+        Ok(Some(content))
     }
 
     /// Purge memory entries older than `days`, keeping `knowledge:` prefixed sources.
+    /// Importance scales retention: importance-4 entries are kept for twice as
+    /// long and importance-5 entries are never purged.
     /// Also cleans up orphaned embeddings. Returns number of entries deleted.
     pub fn purge_old_memory_entries(&self, days: u32) -> Result<usize> {
         if days == 0 {
@@ -41,10 +96,13 @@ impl MemoryDB {
         let mut conn = self.lock_conn()?;
         let cutoff = Utc::now() - chrono::Duration::days(i64::from(days));
         let cutoff_str = cutoff.to_rfc3339();
+        let high_cutoff = Utc::now() - chrono::Duration::days(2 * i64::from(days));
+        let high_cutoff_str = high_cutoff.to_rfc3339();
         let tx = conn.transaction()?;
         let deleted = tx.execute(
-            "DELETE FROM memory_entries WHERE source_key NOT LIKE 'knowledge:%' AND created_at < ?",
-            params![cutoff_str],
+            "DELETE FROM memory_entries WHERE source_key NOT LIKE 'knowledge:%'
+             AND ((importance < 4 AND created_at < ?1) OR (importance = 4 AND created_at < ?2))",
+            params![cutoff_str, high_cutoff_str],
         )?;
         if deleted > 0 {
             // Clean up orphaned embeddings
@@ -57,6 +115,10 @@ impl MemoryDB {
                 "DELETE FROM memory_sources WHERE source_key NOT IN (SELECT DISTINCT source_key FROM memory_entries)",
                 [],
             )?;
+            tx.execute(
+                "DELETE FROM fact_digest WHERE entry_id NOT IN (SELECT id FROM memory_entries)",
+                [],
+            )?;
         }
         tx.commit()?;
         if deleted > 0 {
@@ -89,6 +151,21 @@ impl MemoryDB {
         rows.map_err(|e| anyhow::anyhow!("failed to get recent daily entries: {e}"))
     }
 
+    /// Like [`get_recent_daily_entries`](Self::get_recent_daily_entries), but
+    /// with each entry's ID so a single entry can be deleted.
+    pub fn get_recent_daily_entries_with_ids(&self, limit: usize) -> Result<Vec<(i64, String)>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, content FROM memory_entries WHERE source_key LIKE 'daily:%' ORDER BY created_at DESC LIMIT ?",
+        )?;
+        let rows: Result<Vec<_>, _> = stmt
+            .query_map(rusqlite::params![limit], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect();
+        rows.map_err(|e| anyhow::anyhow!("failed to get recent daily entries: {e}"))
+    }
+
     /// Delete all memory entries for a given source key.
     /// Also removes associated embeddings and the source record.
     /// Returns number of entries deleted.
@@ -128,4 +205,23 @@ impl MemoryDB {
             .collect();
         rows.map_err(|e| anyhow::anyhow!("failed to list sources: {e}"))
     }
+
+    /// Rebuild the full-text index from `memory_entries`, repairing any drift
+    /// between the FTS table and its content table. Returns the number of
+    /// sources and entries indexed; a no-op when FTS5 is unavailable.
+    ///
+    /// Runs in a single statement under the connection lock, so it is safe
+    /// while the gateway holds the same database (WAL + busy timeout).
+    pub fn rebuild_fts_index(&self) -> Result<(usize, usize)> {
+        let conn = self.lock_conn()?;
+        if self.has_fts {
+            conn.execute("INSERT INTO memory_fts(memory_fts) VALUES ('rebuild')", [])?;
+        }
+        let counts = conn.query_row(
+            "SELECT COUNT(DISTINCT source_key), COUNT(*) FROM memory_entries",
+            [],
+            |row| Ok((row.get::<_, usize>(0)?, row.get::<_, usize>(1)?)),
+        )?;
+        Ok(counts)
+    }
 }
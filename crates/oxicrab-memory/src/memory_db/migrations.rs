@@ -92,6 +92,200 @@ pub fn apply_migrations(conn: &Connection) -> Result<()> {
         conn.execute("PRAGMA user_version = 5", [])?;
     }
 
+    if user_version(conn)? < 6 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS memory_conflicts (
+                id INTEGER PRIMARY KEY,
+                older_entry_id INTEGER NOT NULL,
+                newer_entry_id INTEGER NOT NULL,
+                older_source_key TEXT NOT NULL,
+                newer_source_key TEXT NOT NULL,
+                older_content TEXT NOT NULL,
+                newer_content TEXT NOT NULL,
+                similarity REAL NOT NULL,
+                status TEXT NOT NULL,
+                explanation TEXT,
+                detected_at TEXT NOT NULL,
+                resolved_at TEXT,
+                UNIQUE (older_entry_id, newer_entry_id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_memory_conflicts_status ON memory_conflicts(status);",
+        )?;
+        conn.execute("PRAGMA user_version = 6", [])?;
+    }
+
+    if user_version(conn)? < 7 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS tool_audit_log (
+                id INTEGER PRIMARY KEY,
+                timestamp TEXT NOT NULL DEFAULT (datetime('now')),
+                session_key TEXT NOT NULL,
+                request_id TEXT,
+                tool_name TEXT NOT NULL,
+                arguments TEXT NOT NULL,
+                result_summary TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                duration_ms INTEGER NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_tool_audit_log_timestamp ON tool_audit_log(timestamp);",
+        )?;
+        conn.execute("PRAGMA user_version = 7", [])?;
+    }
+
+    if user_version(conn)? < 8 {
+        add_column_if_missing(
+            conn,
+            "memory_entries",
+            "importance",
+            "INTEGER NOT NULL DEFAULT 3",
+        )?;
+        conn.execute("PRAGMA user_version = 8", [])?;
+    }
+
+    if user_version(conn)? < 9 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS fact_digest (
+                entry_id INTEGER PRIMARY KEY,
+                channel TEXT NOT NULL,
+                chat_id TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                digested_at TEXT
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_fact_digest_pending ON fact_digest(digested_at);",
+        )?;
+        conn.execute("PRAGMA user_version = 9", [])?;
+    }
+
+    if user_version(conn)? < 10 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS provider_health (
+                provider TEXT PRIMARY KEY,
+                state TEXT NOT NULL,
+                consecutive_failures INTEGER NOT NULL DEFAULT 0,
+                opened_at_ms INTEGER,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )?;
+        conn.execute("PRAGMA user_version = 10", [])?;
+    }
+
+    if user_version(conn)? < 11 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS outbound_dlq (
+                id INTEGER PRIMARY KEY,
+                channel TEXT NOT NULL,
+                chat_id TEXT NOT NULL,
+                content TEXT NOT NULL,
+                error_message TEXT NOT NULL,
+                failed_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )?;
+        conn.execute("PRAGMA user_version = 11", [])?;
+    }
+
+    if user_version(conn)? < 12 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS image_descriptions (
+                hash TEXT PRIMARY KEY,
+                description TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                last_used_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )?;
+        conn.execute("PRAGMA user_version = 12", [])?;
+    }
+
+    if user_version(conn)? < 13 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sender_preferences (
+                sender TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (sender, key)
+            );",
+        )?;
+        conn.execute("PRAGMA user_version = 13", [])?;
+    }
+
+    if user_version(conn)? < 14 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS empty_response_log (
+                id INTEGER PRIMARY KEY,
+                timestamp TEXT NOT NULL DEFAULT (datetime('now')),
+                model TEXT NOT NULL,
+                action TEXT NOT NULL,
+                request_id TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_empty_response_log_ts ON empty_response_log(timestamp);",
+        )?;
+        conn.execute("PRAGMA user_version = 14", [])?;
+    }
+
+    if user_version(conn)? < 15 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS token_breakdown_log (
+                id INTEGER PRIMARY KEY,
+                timestamp TEXT NOT NULL DEFAULT (datetime('now')),
+                session_key TEXT NOT NULL,
+                request_id TEXT,
+                model TEXT NOT NULL,
+                calls INTEGER NOT NULL,
+                system_tokens INTEGER NOT NULL,
+                tool_definition_tokens INTEGER NOT NULL,
+                history_tokens INTEGER NOT NULL,
+                message_tokens INTEGER NOT NULL,
+                tool_result_tokens INTEGER NOT NULL,
+                assistant_tokens INTEGER NOT NULL,
+                provider_input_tokens INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_token_breakdown_log_session
+                ON token_breakdown_log(session_key, timestamp);",
+        )?;
+        conn.execute("PRAGMA user_version = 15", [])?;
+    }
+
+    if user_version(conn)? < 16 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS offline_queue (
+                id INTEGER PRIMARY KEY,
+                session_key TEXT NOT NULL,
+                message TEXT NOT NULL,
+                queued_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE INDEX IF NOT EXISTS idx_offline_queue_session ON offline_queue(session_key);",
+        )?;
+        conn.execute("PRAGMA user_version = 16", [])?;
+    }
+
+    if user_version(conn)? < 17 {
+        add_column_if_missing(conn, "cron_job_targets", "message_override", "TEXT")?;
+        conn.execute("PRAGMA user_version = 17", [])?;
+    }
+
+    if user_version(conn)? < 18 {
+        add_column_if_missing(conn, "cron_jobs", "solar_event", "TEXT")?;
+        add_column_if_missing(conn, "cron_jobs", "solar_lat", "REAL")?;
+        add_column_if_missing(conn, "cron_jobs", "solar_lon", "REAL")?;
+        add_column_if_missing(conn, "cron_jobs", "solar_offset_minutes", "INTEGER")?;
+        conn.execute("PRAGMA user_version = 18", [])?;
+    }
+
+    if user_version(conn)? < 19 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS quiet_hours_queue (
+                id INTEGER PRIMARY KEY,
+                channel TEXT NOT NULL,
+                message TEXT NOT NULL,
+                queued_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )?;
+        conn.execute("PRAGMA user_version = 19", [])?;
+    }
+
     Ok(())
 }
 
@@ -179,7 +373,11 @@ fn ensure_allowed_column_addition(
             "llm_cost_log" | "intent_metrics" | "memory_access_log",
             "request_id",
             "TEXT"
-        )
+        ) | ("memory_entries", "importance", "INTEGER NOT NULL DEFAULT 3")
+            | ("cron_job_targets", "message_override", "TEXT")
+            | ("cron_jobs", "solar_event", "TEXT")
+            | ("cron_jobs", "solar_lat" | "solar_lon", "REAL")
+            | ("cron_jobs", "solar_offset_minutes", "INTEGER")
     ) {
         return Ok(());
     }
@@ -197,7 +395,7 @@ mod tests {
         let v: u32 = conn
             .query_row("PRAGMA user_version", [], |row| row.get(0))
             .unwrap();
-        assert_eq!(v, 5);
+        assert_eq!(v, 19);
     }
 
     #[test]
@@ -333,4 +531,208 @@ mod tests {
             "sessions updated_at index should exist, found: {indexes:?}",
         );
     }
+
+    #[test]
+    fn test_migration_v6_creates_memory_conflicts() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 5", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        assert!(user_version(&conn).unwrap() >= 6);
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='memory_conflicts'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v7_creates_tool_audit_log() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 6", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        assert!(user_version(&conn).unwrap() >= 7);
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='tool_audit_log'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v8_adds_memory_importance() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 7", []).unwrap();
+        conn.execute(
+            "INSERT INTO memory_entries (source_key, content, content_hash, created_at)
+             VALUES ('daily:2024-01-01', 'old fact', 'h', '2024-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        apply_migrations(&conn).unwrap();
+        assert!(user_version(&conn).unwrap() >= 8);
+        // Existing rows get the default importance
+        let importance: i64 = conn
+            .query_row("SELECT importance FROM memory_entries", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(importance, 3);
+        // Re-running is a no-op
+        apply_migrations(&conn).unwrap();
+    }
+
+    #[test]
+    fn test_migration_v9_creates_fact_digest() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 8", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        assert!(user_version(&conn).unwrap() >= 9);
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='fact_digest'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v10_creates_provider_health() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 9", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='provider_health'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v11_creates_outbound_dlq() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 10", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='outbound_dlq'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v12_creates_image_descriptions() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 11", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='image_descriptions'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v13_creates_sender_preferences() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 12", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='sender_preferences'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v14_creates_empty_response_log() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 13", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='empty_response_log'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v15_creates_token_breakdown_log() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 14", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='token_breakdown_log'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v16_creates_offline_queue() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 15", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='offline_queue'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_migration_v17_adds_cron_target_message_override() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATION_0001_BASE).unwrap();
+        conn.execute("PRAGMA user_version = 16", []).unwrap();
+        apply_migrations(&conn).unwrap();
+        let has_column: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('cron_job_targets') \
+                 WHERE name = 'message_override'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(has_column);
+    }
 }
@@ -4,32 +4,47 @@ use sha2::{Digest, Sha256};
 use std::path::Path;
 use tracing::warn;
 
+mod conflicts;
 mod cost;
 mod cron;
 mod dlq;
 mod embeddings;
+mod fact_digest;
+mod image_descriptions;
 mod indexing;
 mod migrations;
 mod oauth;
 pub mod obsidian;
+mod offline_queue;
+mod outbound_dlq;
 mod pairing;
+mod provider_health;
+mod quiet_hours_queue;
 #[cfg(feature = "rss")]
 pub mod rss;
 mod search;
+mod sender_preferences;
 mod stats;
 mod subagent_log;
+mod tool_audit;
 mod workspace;
 
-pub use cost::TokenSummaryRow;
+pub use conflicts::{ConflictResolution, MemoryConflict, SimilarPair, conflict_status};
+pub use cost::{TokenBreakdown, TokenBreakdownRow, TokenSummaryRow};
 pub use dlq::DlqEntry;
+pub use fact_digest::PendingFact;
+pub use offline_queue::OfflineQueueEntry;
+pub use outbound_dlq::OutboundDlqEntry;
 pub use oxicrab_core::credential_store::OAuthTokenRow;
 pub use pairing::DbPendingRequest;
 pub use search::MemoryHit;
 pub use stats::SearchDetails;
 pub use stats::{
-    ComplexityEvent, ComplexityForceCount, ComplexityStats, ComplexityTierStats, SearchStats,
+    ComplexityEvent, ComplexityForceCount, ComplexityStats, ComplexityTierStats,
+    EmptyResponseStats, SearchStats,
 };
 pub use subagent_log::SubagentLogEntry;
+pub use tool_audit::{ToolAuditEntry, ToolAuditStats};
 pub use workspace::WorkspaceFileEntry;
 
 use embeddings::CachedEmbedding;
@@ -50,6 +65,14 @@ pub fn recency_decay(age_days: f64, half_life_days: u32) -> f32 {
     (0.5_f64.powf(age_days / f64::from(half_life_days))) as f32
 }
 
+/// Ranking multiplier for an entry's importance (1-5).
+///
+/// Linear around the default importance of 3 (multiplier 1.0): importance 1
+/// scores 0.8x, importance 5 scores 1.2x. Out-of-range values are clamped.
+pub fn importance_boost(importance: u8) -> f32 {
+    0.7 + 0.1 * f32::from(importance.clamp(1, 5))
+}
+
 pub struct MemoryDB {
     pub(crate) conn: std::sync::Mutex<Connection>,
     has_fts: bool,
@@ -126,6 +149,11 @@ impl MemoryDB {
         Ok(())
     }
 
+    /// Whether FTS5 full-text search is available (otherwise `LIKE` fallback).
+    pub fn has_fts(&self) -> bool {
+        self.has_fts
+    }
+
     pub fn lock_conn(&self) -> Result<std::sync::MutexGuard<'_, Connection>> {
         self.conn
             .lock()
@@ -155,6 +183,19 @@ impl MemoryDB {
         conn.execute_batch("PRAGMA optimize")?;
         Ok(())
     }
+
+    /// Verify the database accepts writes by inserting a row inside a
+    /// transaction that is rolled back. Used by diagnostics.
+    pub fn check_writable(&self) -> Result<()> {
+        let mut conn = self.lock_conn()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO sessions (key, data, updated_at) VALUES ('__self_test__', '', datetime('now'))",
+            [],
+        )?;
+        tx.rollback()?;
+        Ok(())
+    }
 }
 
 // --- Session storage ---
@@ -183,6 +224,20 @@ impl MemoryDB {
         Ok(())
     }
 
+    /// Keys of sessions not updated within `idle_days`, oldest first.
+    pub fn idle_session_keys(&self, idle_days: u32) -> Result<Vec<String>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT key FROM sessions WHERE updated_at < datetime('now', ?1) ORDER BY updated_at",
+        )?;
+        let keys = stmt
+            .query_map(rusqlite::params![format!("-{idle_days} days")], |row| {
+                row.get(0)
+            })?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(keys)
+    }
+
     /// Delete sessions not updated within `ttl_days`. Returns count deleted.
     /// A TTL of 0 deletes all sessions.
     pub fn cleanup_sessions(&self, ttl_days: u32) -> Result<usize> {
@@ -0,0 +1,97 @@
+use super::MemoryDB;
+use anyhow::Result;
+use rusqlite::{OptionalExtension, params};
+
+/// An inbound message held while the LLM provider was unavailable.
+#[derive(Debug, Clone)]
+pub struct OfflineQueueEntry {
+    pub id: i64,
+    pub session_key: String,
+    /// The serialized inbound message.
+    pub message: String,
+    pub queued_at: String,
+}
+
+impl MemoryDB {
+    /// Queue a serialized inbound message until the provider is back.
+    pub fn enqueue_offline_message(&self, session_key: &str, message: &str) -> Result<i64> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT INTO offline_queue (session_key, message) VALUES (?1, ?2)",
+            params![session_key, message],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// The message queued first, if any.
+    pub fn oldest_offline_message(&self) -> Result<Option<OfflineQueueEntry>> {
+        let conn = self.lock_conn()?;
+        let entry = conn
+            .query_row(
+                "SELECT id, session_key, message, queued_at
+                 FROM offline_queue ORDER BY id LIMIT 1",
+                [],
+                |row| {
+                    Ok(OfflineQueueEntry {
+                        id: row.get(0)?,
+                        session_key: row.get(1)?,
+                        message: row.get(2)?,
+                        queued_at: row.get(3)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(entry)
+    }
+
+    pub fn delete_offline_message(&self, id: i64) -> Result<()> {
+        let conn = self.lock_conn()?;
+        conn.execute("DELETE FROM offline_queue WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Queued messages, in total or for one session.
+    pub fn count_offline_messages(&self, session_key: Option<&str>) -> Result<usize> {
+        let conn = self.lock_conn()?;
+        let count: i64 = match session_key {
+            Some(key) => conn.query_row(
+                "SELECT COUNT(*) FROM offline_queue WHERE session_key = ?1",
+                [key],
+                |row| row.get(0),
+            )?,
+            None => conn.query_row("SELECT COUNT(*) FROM offline_queue", [], |row| row.get(0))?,
+        };
+        Ok(usize::try_from(count).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MemoryDB;
+
+    #[test]
+    fn test_offline_queue_fifo_and_counts() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        assert!(db.oldest_offline_message().unwrap().is_none());
+
+        let first = db
+            .enqueue_offline_message("telegram:1", "{\"a\":1}")
+            .unwrap();
+        db.enqueue_offline_message("slack:C1", "{\"b\":2}").unwrap();
+        db.enqueue_offline_message("telegram:1", "{\"c\":3}")
+            .unwrap();
+        assert_eq!(db.count_offline_messages(None).unwrap(), 3);
+        assert_eq!(db.count_offline_messages(Some("telegram:1")).unwrap(), 2);
+        assert_eq!(db.count_offline_messages(Some("discord:9")).unwrap(), 0);
+
+        let oldest = db.oldest_offline_message().unwrap().unwrap();
+        assert_eq!(oldest.id, first);
+        assert_eq!(oldest.session_key, "telegram:1");
+        assert_eq!(oldest.message, "{\"a\":1}");
+
+        db.delete_offline_message(first).unwrap();
+        let next = db.oldest_offline_message().unwrap().unwrap();
+        assert_eq!(next.session_key, "slack:C1");
+        assert_eq!(db.count_offline_messages(Some("telegram:1")).unwrap(), 1);
+    }
+}
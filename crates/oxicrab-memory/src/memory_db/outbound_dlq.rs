@@ -0,0 +1,114 @@
+use super::MemoryDB;
+use anyhow::Result;
+use rusqlite::params;
+
+/// An outbound message that could not be delivered after all send retries.
+#[derive(Debug, Clone)]
+pub struct OutboundDlqEntry {
+    pub id: i64,
+    pub channel: String,
+    pub chat_id: String,
+    pub content: String,
+    pub error_message: String,
+    pub failed_at: String,
+}
+
+impl MemoryDB {
+    /// Record an undelivered outbound message. Keeps the 100 most recent entries.
+    pub fn insert_outbound_dlq_entry(
+        &self,
+        channel: &str,
+        chat_id: &str,
+        content: &str,
+        error_message: &str,
+    ) -> Result<i64> {
+        let mut conn = self.lock_conn()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO outbound_dlq (channel, chat_id, content, error_message)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![channel, chat_id, content, error_message],
+        )?;
+        let id = tx.last_insert_rowid();
+
+        // Auto-purge: keep only 100 most recent entries
+        tx.execute(
+            "DELETE FROM outbound_dlq WHERE id NOT IN (
+                SELECT id FROM outbound_dlq ORDER BY id DESC LIMIT 100
+            )",
+            [],
+        )?;
+        tx.commit()?;
+
+        Ok(id)
+    }
+
+    /// List undelivered messages recorded at or after `since`
+    /// (`YYYY-MM-DD HH:MM:SS`, UTC), newest first, capped at `limit`.
+    pub fn list_outbound_dlq_entries(
+        &self,
+        since: &str,
+        limit: usize,
+    ) -> Result<Vec<OutboundDlqEntry>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, channel, chat_id, content, error_message, failed_at
+             FROM outbound_dlq
+             WHERE failed_at >= ?1
+             ORDER BY id DESC LIMIT ?2",
+        )?;
+        let rows = stmt
+            .query_map(params![since, limit as i64], |row| {
+                Ok(OutboundDlqEntry {
+                    id: row.get(0)?,
+                    channel: row.get(1)?,
+                    chat_id: row.get(2)?,
+                    content: row.get(3)?,
+                    error_message: row.get(4)?,
+                    failed_at: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    pub fn count_outbound_dlq_entries(&self) -> Result<usize> {
+        let conn = self.lock_conn()?;
+        let count: i64 =
+            conn.query_row("SELECT COUNT(*) FROM outbound_dlq", [], |row| row.get(0))?;
+        Ok(usize::try_from(count).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MemoryDB;
+
+    #[test]
+    fn test_outbound_dlq_insert_list_and_purge() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        db.insert_outbound_dlq_entry("slack", "C1", "first reply", "HTTP 503")
+            .unwrap();
+        db.insert_outbound_dlq_entry("telegram", "42", "second reply", "timeout")
+            .unwrap();
+
+        let entries = db
+            .list_outbound_dlq_entries("1970-01-01 00:00:00", 10)
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].channel, "telegram");
+        assert_eq!(entries[0].content, "second reply");
+        assert_eq!(entries[1].error_message, "HTTP 503");
+        assert!(
+            db.list_outbound_dlq_entries("2999-01-01 00:00:00", 10)
+                .unwrap()
+                .is_empty()
+        );
+
+        for i in 0..110 {
+            db.insert_outbound_dlq_entry("slack", "C1", &format!("m{i}"), "err")
+                .unwrap();
+        }
+        assert_eq!(db.count_outbound_dlq_entries().unwrap(), 100);
+    }
+}
@@ -0,0 +1,83 @@
+use super::MemoryDB;
+use anyhow::Result;
+use oxicrab_core::providers::circuit_state::{CircuitStateRow, CircuitStateStore};
+use rusqlite::params;
+
+impl MemoryDB {
+    /// Save (insert or replace) the circuit breaker state for a provider.
+    pub fn save_provider_health(&self, row: &CircuitStateRow) -> Result<()> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO provider_health
+             (provider, state, consecutive_failures, opened_at_ms, updated_at)
+             VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+            params![
+                row.provider,
+                row.state,
+                row.consecutive_failures,
+                row.opened_at_ms
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Load the circuit breaker state for a provider. Returns `None` if not found.
+    pub fn load_provider_health(&self, provider: &str) -> Result<Option<CircuitStateRow>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT provider, state, consecutive_failures, opened_at_ms
+             FROM provider_health WHERE provider = ?1",
+        )?;
+        let mut rows = stmt.query(params![provider])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(CircuitStateRow {
+                provider: row.get(0)?,
+                state: row.get(1)?,
+                consecutive_failures: row.get(2)?,
+                opened_at_ms: row.get(3)?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl CircuitStateStore for MemoryDB {
+    fn load_circuit_state(&self, provider: &str) -> Result<Option<CircuitStateRow>> {
+        self.load_provider_health(provider)
+    }
+
+    fn save_circuit_state(&self, row: &CircuitStateRow) -> Result<()> {
+        self.save_provider_health(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MemoryDB;
+    use oxicrab_core::providers::circuit_state::CircuitStateRow;
+
+    #[test]
+    fn test_save_and_load_provider_health() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        assert!(db.load_provider_health("claude").unwrap().is_none());
+
+        let mut row = CircuitStateRow {
+            provider: "claude".to_string(),
+            state: "open".to_string(),
+            consecutive_failures: 5,
+            opened_at_ms: Some(1_700_000_000_000),
+        };
+        db.save_provider_health(&row).unwrap();
+        assert_eq!(
+            db.load_provider_health("claude").unwrap(),
+            Some(row.clone())
+        );
+
+        row.state = "closed".to_string();
+        row.consecutive_failures = 0;
+        row.opened_at_ms = None;
+        db.save_provider_health(&row).unwrap();
+        assert_eq!(db.load_provider_health("claude").unwrap(), Some(row));
+    }
+}
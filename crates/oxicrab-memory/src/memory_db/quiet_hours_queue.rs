@@ -0,0 +1,58 @@
+use super::MemoryDB;
+use anyhow::Result;
+use rusqlite::params;
+
+impl MemoryDB {
+    /// Hold a serialized outbound message until quiet hours end.
+    pub fn enqueue_quiet_hours_message(&self, channel: &str, message: &str) -> Result<i64> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT INTO quiet_hours_queue (channel, message) VALUES (?1, ?2)",
+            params![channel, message],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Remove and return the held messages, oldest first.
+    pub fn drain_quiet_hours_messages(&self) -> Result<Vec<String>> {
+        let mut conn = self.lock_conn()?;
+        let tx = conn.transaction()?;
+        let messages = {
+            let mut stmt = tx.prepare("SELECT message FROM quiet_hours_queue ORDER BY id")?;
+            stmt.query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<String>>>()?
+        };
+        tx.execute("DELETE FROM quiet_hours_queue", [])?;
+        tx.commit()?;
+        Ok(messages)
+    }
+
+    pub fn count_quiet_hours_messages(&self) -> Result<usize> {
+        let conn = self.lock_conn()?;
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM quiet_hours_queue", [], |row| {
+            row.get(0)
+        })?;
+        Ok(usize::try_from(count).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MemoryDB;
+
+    #[test]
+    fn test_quiet_hours_queue_drains_in_order() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        assert!(db.drain_quiet_hours_messages().unwrap().is_empty());
+
+        db.enqueue_quiet_hours_message("telegram", "first").unwrap();
+        db.enqueue_quiet_hours_message("slack", "second").unwrap();
+        assert_eq!(db.count_quiet_hours_messages().unwrap(), 2);
+
+        assert_eq!(
+            db.drain_quiet_hours_messages().unwrap(),
+            vec!["first".to_string(), "second".to_string()]
+        );
+        assert_eq!(db.count_quiet_hours_messages().unwrap(), 0);
+    }
+}
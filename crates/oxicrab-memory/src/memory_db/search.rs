@@ -1,5 +1,5 @@
 use super::MemoryDB;
-use super::recency_decay;
+use super::{importance_boost, recency_decay};
 use anyhow::Result;
 use chrono::Utc;
 use oxicrab_core::config::schema::FusionStrategy;
@@ -9,17 +9,47 @@ use tracing::{debug, warn};
 pub struct MemoryHit {
     pub source_key: String,
     pub content: String,
+    /// Excerpt around the matched terms, which are wrapped in `<b>`/`</b>`.
+    /// Only set when the search was asked to highlight; hits without a
+    /// keyword match get the start of the content instead.
+    pub snippet: Option<String>,
 }
 
 /// Maximum number of unique terms used in FTS queries
 pub(super) const MAX_FTS_TERMS: usize = 16;
 
+/// Tokens per FTS5 `snippet()`, roughly 200 characters of text.
+const SNIPPET_TOKENS: u32 = 30;
+/// Length of the leading-substring snippet used without an FTS match.
+const SNIPPET_FALLBACK_CHARS: usize = 200;
+
+/// SQL expression for the highlighted snippet of a `memory_fts` match, or
+/// NULL when not highlighting.
+fn snippet_column(highlight: bool) -> String {
+    if highlight {
+        format!("snippet(memory_fts, 0, '<b>', '</b>', '...', {SNIPPET_TOKENS})")
+    } else {
+        "NULL".to_string()
+    }
+}
+
+/// Snippet for a hit without an FTS match: the start of its content.
+fn fallback_snippet(content: &str) -> String {
+    oxicrab_core::utils::truncate_chars(content, SNIPPET_FALLBACK_CHARS, "...")
+}
+
 impl MemoryDB {
     /// Hybrid search combining FTS5 BM25 and vector cosine similarity.
     /// `keyword_weight` controls blending: 1.0 = keyword only, 0.0 = vector only.
     /// `fusion_strategy` selects the score combination method:
     /// - `WeightedScore`: linear blend of normalized scores
     /// - `Rrf`: reciprocal rank fusion (ignores raw scores, merges by rank)
+    ///
+    /// Both the keyword and vector scores are scaled by each entry's
+    /// [`importance_boost`] before fusion, so important facts outrank trivia
+    /// of similar relevance.
+    ///
+    /// With `highlight`, each hit carries a [`MemoryHit::snippet`].
     #[allow(clippy::too_many_arguments)]
     pub fn hybrid_search(
         &self,
@@ -31,6 +61,7 @@ impl MemoryDB {
         fusion_strategy: FusionStrategy,
         rrf_k: u32,
         recency_half_life_days: u32,
+        highlight: bool,
     ) -> Result<Vec<MemoryHit>> {
         use crate::embeddings::cosine_similarity;
 
@@ -44,19 +75,21 @@ impl MemoryDB {
         // 1. Get FTS5 results with BM25 scores
         let mut fts_scores: std::collections::HashMap<i64, (f32, String, String)> =
             std::collections::HashMap::new();
+        let mut snippets: std::collections::HashMap<i64, String> = std::collections::HashMap::new();
 
         if keyword_weight > 0.0 {
             let query = fts_query(query_text);
             if !query.is_empty() && self.has_fts {
                 let conn = self.lock_conn()?;
-                let mut stmt = conn.prepare(
-                    "SELECT me.id, me.source_key, me.content, bm25(memory_fts, 10.0, 1.0) as score, me.created_at
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT me.id, me.source_key, me.content, bm25(memory_fts, 10.0, 1.0) as score, me.created_at, me.importance, {}
                      FROM memory_fts
                      JOIN memory_entries me ON memory_fts.rowid = me.id
                      WHERE memory_fts MATCH ?
                      ORDER BY bm25(memory_fts, 10.0, 1.0)
                      LIMIT 100",
-                )?;
+                    snippet_column(highlight)
+                ))?;
 
                 let now = Utc::now();
                 let rows: Vec<_> = stmt
@@ -67,26 +100,31 @@ impl MemoryDB {
                             row.get::<_, String>(2)?,
                             row.get::<_, f64>(3)?,
                             row.get::<_, String>(4)?,
+                            row.get::<_, u8>(5)?,
+                            row.get::<_, Option<String>>(6)?,
                         ))
                     })?
                     .filter_map(std::result::Result::ok)
-                    .filter(|(_, key, _, _, _)| !exclude.contains(key))
+                    .filter(|(_, key, _, _, _, _, _)| !exclude.contains(key))
                     .collect();
 
                 // BM25 scores are negative (more negative = better match).
-                // Normalize to 0..1 range, then apply recency decay.
+                // Normalize to 0..1 range, then apply recency decay and importance.
                 if !rows.is_empty() {
                     let min_score = rows
                         .iter()
-                        .map(|(_, _, _, s, _)| *s)
+                        .map(|(_, _, _, s, _, _, _)| *s)
                         .fold(f64::INFINITY, f64::min);
                     let max_score = rows
                         .iter()
-                        .map(|(_, _, _, s, _)| *s)
+                        .map(|(_, _, _, s, _, _, _)| *s)
                         .fold(f64::NEG_INFINITY, f64::max);
                     let range = max_score - min_score;
 
-                    for (id, key, content, score, created_at) in rows {
+                    for (id, key, content, score, created_at, importance, snippet) in rows {
+                        if let Some(snippet) = snippet {
+                            snippets.insert(id, snippet);
+                        }
                         let normalized = if range.abs() < 1e-10 {
                             1.0
                         } else {
@@ -104,7 +142,9 @@ impl MemoryDB {
                                 .map(|ndt| ndt.and_utc())
                             })
                             .map_or(0.0, |dt| (now - dt).num_seconds() as f64 / 86400.0);
-                        let decayed = normalized * recency_decay(age_days, recency_half_life_days);
+                        let decayed = normalized
+                            * recency_decay(age_days, recency_half_life_days)
+                            * importance_boost(importance);
                         fts_scores.insert(id, (decayed, key, content));
                     }
                 }
@@ -120,7 +160,7 @@ impl MemoryDB {
             for entry in &cached {
                 let sim = cosine_similarity(query_embedding, &entry.embedding);
                 // Cosine similarity is already in [-1, 1]; clamp to [0, 1]
-                let score = sim.max(0.0);
+                let score = sim.max(0.0) * importance_boost(entry.importance);
                 vec_scores.insert(
                     entry.entry_id,
                     (score, entry.source_key.clone(), entry.content.clone()),
@@ -133,7 +173,7 @@ impl MemoryDB {
         all_ids.extend(fts_scores.keys());
         all_ids.extend(vec_scores.keys());
 
-        let mut scored: Vec<(f32, String, String)> = match fusion_strategy {
+        let mut scored: Vec<(f32, i64, String, String)> = match fusion_strategy {
             FusionStrategy::WeightedScore => all_ids
                 .into_iter()
                 .map(|id| {
@@ -159,7 +199,7 @@ impl MemoryDB {
                     } else {
                         fts_content
                     };
-                    (combined, key, content)
+                    (combined, id, key, content)
                 })
                 .collect(),
 
@@ -222,7 +262,7 @@ impl MemoryDB {
                         } else {
                             fts_content
                         };
-                        (rrf_score, key, content)
+                        (rrf_score, id, key, content)
                     })
                     .collect()
             }
@@ -230,13 +270,21 @@ impl MemoryDB {
 
         scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
 
-        let top_score = scored.first().map(|(s, _, _)| f64::from(*s));
+        let top_score = scored.first().map(|(s, _, _, _)| f64::from(*s));
         let hits: Vec<MemoryHit> = scored
             .into_iter()
             .take(limit)
-            .map(|(_, source_key, content)| MemoryHit {
-                source_key,
-                content,
+            .map(|(_, id, source_key, content)| {
+                let snippet = highlight.then(|| {
+                    snippets
+                        .remove(&id)
+                        .unwrap_or_else(|| fallback_snippet(&content))
+                });
+                MemoryHit {
+                    source_key,
+                    content,
+                    snippet,
+                }
             })
             .collect();
 
@@ -266,13 +314,16 @@ impl MemoryDB {
         keys.map_err(|e| anyhow::anyhow!("failed to list daily source keys: {e}"))
     }
 
+    /// Keyword search (FTS5, or LIKE without it). With `highlight`, each hit
+    /// carries a [`MemoryHit::snippet`].
     pub fn search(
         &self,
         query_text: &str,
         limit: usize,
         exclude_sources: Option<&std::collections::HashSet<String>>,
+        highlight: bool,
     ) -> Result<Vec<MemoryHit>> {
-        let hits = self.search_inner(query_text, limit, exclude_sources)?;
+        let hits = self.search_inner(query_text, limit, exclude_sources, highlight)?;
         // Log search asynchronously (best-effort, don't fail the search)
         if let Err(e) = self.log_search(query_text, "keyword", &hits, None, None) {
             debug!("failed to log search: {}", e);
@@ -285,6 +336,7 @@ impl MemoryDB {
         query_text: &str,
         limit: usize,
         exclude_sources: Option<&std::collections::HashSet<String>>,
+        highlight: bool,
     ) -> Result<Vec<MemoryHit>> {
         let query = fts_query(query_text);
         if query.is_empty() {
@@ -296,18 +348,25 @@ impl MemoryDB {
         let conn = self.lock_conn()?;
 
         if self.has_fts {
-            let mut stmt = conn.prepare(
-                "SELECT me.source_key, me.content
+            // BM25 is negative (lower = better), so scaling by the importance
+            // boost moves important entries up the ranking.
+            let mut stmt = conn.prepare(&format!(
+                "SELECT me.source_key, me.content, {}
                 FROM memory_fts
                 JOIN memory_entries me ON memory_fts.rowid = me.id
                 WHERE memory_fts MATCH ?
-                ORDER BY bm25(memory_fts, 10.0, 1.0)
+                ORDER BY bm25(memory_fts, 10.0, 1.0) * (0.7 + 0.1 * me.importance)
                 LIMIT ?",
-            )?;
+                snippet_column(highlight)
+            ))?;
 
             let rows: Result<Vec<_>, _> = stmt
                 .query_map([&query, &(limit + exclude.len()).to_string()], |row| {
-                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
                 })?
                 .collect();
 
@@ -315,11 +374,12 @@ impl MemoryDB {
                 Ok(rows) => {
                     let hits: Vec<MemoryHit> = rows
                         .into_iter()
-                        .filter(|(key, _)| !exclude.contains(key))
+                        .filter(|(key, _, _)| !exclude.contains(key))
                         .take(limit)
-                        .map(|(source_key, content)| MemoryHit {
+                        .map(|(source_key, content, snippet)| MemoryHit {
                             source_key,
                             content,
+                            snippet,
                         })
                         .collect();
                     return Ok(hits);
@@ -347,6 +407,7 @@ impl MemoryDB {
             "SELECT source_key, content
             FROM memory_entries
             WHERE content LIKE ? ESCAPE '\\'
+            ORDER BY importance DESC
             LIMIT ?",
         )?;
 
@@ -362,6 +423,7 @@ impl MemoryDB {
                 .filter(|(key, _)| !exclude.contains(key))
                 .take(limit)
                 .map(|(source_key, content)| MemoryHit {
+                    snippet: highlight.then(|| fallback_snippet(&content)),
                     source_key,
                     content,
                 })
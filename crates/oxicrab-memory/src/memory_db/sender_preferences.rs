@@ -0,0 +1,73 @@
+use super::MemoryDB;
+use anyhow::Result;
+use rusqlite::params;
+
+impl MemoryDB {
+    /// Set (or overwrite) one preference for `sender` (`channel:sender_id`).
+    pub fn set_sender_preference(&self, sender: &str, key: &str, value: &str) -> Result<()> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT INTO sender_preferences (sender, key, value) VALUES (?1, ?2, ?3)
+             ON CONFLICT(sender, key) DO UPDATE SET value = ?3, updated_at = datetime('now')",
+            params![sender, key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Remove one preference. Returns whether it existed.
+    pub fn delete_sender_preference(&self, sender: &str, key: &str) -> Result<bool> {
+        let conn = self.lock_conn()?;
+        let deleted = conn.execute(
+            "DELETE FROM sender_preferences WHERE sender = ?1 AND key = ?2",
+            params![sender, key],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// All preferences of `sender` as `(key, value)`, ordered by key.
+    pub fn get_sender_preferences(&self, sender: &str) -> Result<Vec<(String, String)>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn
+            .prepare("SELECT key, value FROM sender_preferences WHERE sender = ?1 ORDER BY key")?;
+        let rows = stmt
+            .query_map(params![sender], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MemoryDB;
+
+    #[test]
+    fn test_sender_preferences_set_overwrite_delete() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        db.set_sender_preference("telegram:1", "verbosity", "terse")
+            .unwrap();
+        db.set_sender_preference("telegram:1", "language", "German")
+            .unwrap();
+        db.set_sender_preference("telegram:2", "verbosity", "detailed")
+            .unwrap();
+        db.set_sender_preference("telegram:1", "verbosity", "normal")
+            .unwrap();
+
+        assert_eq!(
+            db.get_sender_preferences("telegram:1").unwrap(),
+            vec![
+                ("language".to_string(), "German".to_string()),
+                ("verbosity".to_string(), "normal".to_string()),
+            ]
+        );
+        assert!(
+            db.delete_sender_preference("telegram:1", "language")
+                .unwrap()
+        );
+        assert!(
+            !db.delete_sender_preference("telegram:1", "language")
+                .unwrap()
+        );
+        assert_eq!(db.get_sender_preferences("telegram:1").unwrap().len(), 1);
+        assert_eq!(db.get_sender_preferences("telegram:2").unwrap().len(), 1);
+    }
+}
@@ -44,6 +44,15 @@ pub struct ComplexityEvent {
     pub message_preview: Option<String>,
 }
 
+/// Exhausted empty-response retries for one model and recovery action.
+#[derive(Debug, Clone)]
+pub struct EmptyResponseStats {
+    pub model: String,
+    pub action: String,
+    pub count: u64,
+    pub last_seen: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct ComplexityStats {
     pub total_scored: u64,
@@ -315,6 +324,59 @@ impl MemoryDB {
         rows.map_err(|e| anyhow::anyhow!("recent complexity events query failed: {e}"))
     }
 
+    /// Record that a model kept returning empty responses until retries ran out,
+    /// and which recovery `action` the agent loop took.
+    pub fn record_empty_response(
+        &self,
+        model: &str,
+        action: &str,
+        request_id: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT INTO empty_response_log (model, action, request_id) VALUES (?, ?, ?)",
+            params![model, action, request_id],
+        )?;
+        Ok(())
+    }
+
+    /// Get exhausted empty-response counts per model and action since `since_date`.
+    pub fn get_empty_response_stats(&self, since_date: &str) -> Result<Vec<EmptyResponseStats>> {
+        let conn = self.lock_conn()?;
+        let since_datetime = format!("{since_date} 00:00:00");
+        let mut stmt = conn.prepare(
+            "SELECT model, action, COUNT(*) as cnt, MAX(timestamp)
+             FROM empty_response_log
+             WHERE timestamp >= ?
+             GROUP BY model, action
+             ORDER BY cnt DESC",
+        )?;
+        let rows: Result<Vec<_>, _> = stmt
+            .query_map([&since_datetime], |row| {
+                Ok(EmptyResponseStats {
+                    model: row.get(0)?,
+                    action: row.get(1)?,
+                    count: row.get::<_, i64>(2)? as u64,
+                    last_seen: row.get(3)?,
+                })
+            })?
+            .collect();
+        rows.map_err(|e| anyhow::anyhow!("empty response stats query failed: {e}"))
+    }
+
+    /// Purge empty-response logs older than `days`. Returns number of rows deleted.
+    pub fn purge_old_empty_response_logs(&self, days: u32) -> Result<usize> {
+        if days == 0 {
+            return Ok(0);
+        }
+        let conn = self.lock_conn()?;
+        let deleted = conn.execute(
+            "DELETE FROM empty_response_log WHERE timestamp < datetime('now', ?1)",
+            params![format!("-{days} days")],
+        )?;
+        Ok(deleted)
+    }
+
     /// Purge intent metrics older than `days`. Returns number of rows deleted.
     pub fn purge_old_intent_metrics(&self, days: u32) -> Result<usize> {
         if days == 0 {
@@ -58,7 +58,7 @@ fn test_memory_db_new_creates_schema() {
     let db_path = dir.path().join("test_memory.db");
     let db = MemoryDB::new(&db_path).unwrap();
     // Should be able to search without error
-    let results = db.search("anything", 10, None).unwrap();
+    let results = db.search("anything", 10, None, false).unwrap();
     assert!(results.is_empty());
 }
 
@@ -76,7 +76,7 @@ fn test_insert_memory_and_search() {
     )
     .unwrap();
 
-    let results = db.search("Rust programming", 10, None).unwrap();
+    let results = db.search("Rust programming", 10, None, false).unwrap();
     assert!(!results.is_empty());
     assert!(results[0].content.contains("Rust"));
 }
@@ -92,7 +92,7 @@ fn test_search_no_results() {
     db.insert_memory("notes.md", "Another paragraph about food")
         .unwrap();
 
-    let results = db.search("quantum physics", 10, None).unwrap();
+    let results = db.search("quantum physics", 10, None, false).unwrap();
     assert!(results.is_empty());
 }
 
@@ -110,7 +110,7 @@ fn test_insert_memory_exclude_sources() {
     let mut exclude = std::collections::HashSet::new();
     exclude.insert("notes1.md".to_string());
 
-    let results = db.search("Rust", 10, Some(&exclude)).unwrap();
+    let results = db.search("Rust", 10, Some(&exclude), false).unwrap();
     // notes1.md should be excluded
     for hit in &results {
         assert_ne!(hit.source_key, "notes1.md");
@@ -130,7 +130,7 @@ fn test_insert_memory_clone_works() {
     .unwrap();
 
     let db2 = MemoryDB::new(&db_path).unwrap();
-    let results = db2.search("cloning", 10, None).unwrap();
+    let results = db2.search("cloning", 10, None, false).unwrap();
     assert!(!results.is_empty());
 }
 
@@ -147,7 +147,7 @@ fn test_search_logging_roundtrip() {
     .unwrap();
 
     // search() internally calls log_search()
-    let results = db.search("Rust programming", 10, None).unwrap();
+    let results = db.search("Rust programming", 10, None, false).unwrap();
     assert!(!results.is_empty());
 
     let stats = db.get_search_stats().unwrap();
@@ -171,14 +171,14 @@ fn test_source_hit_count() {
     assert_eq!(db.get_source_hit_count("notes.md").unwrap(), 0);
 
     // Search triggers a log
-    let _ = db.search("Rust", 10, None).unwrap();
+    let _ = db.search("Rust", 10, None, false).unwrap();
 
     // Now notes.md should have hits
     let count = db.get_source_hit_count("notes.md").unwrap();
     assert!(count > 0);
 
     // Search again
-    let _ = db.search("programming", 10, None).unwrap();
+    let _ = db.search("programming", 10, None, false).unwrap();
     let count2 = db.get_source_hit_count("notes.md").unwrap();
     assert!(count2 >= count);
 }
@@ -207,6 +207,57 @@ fn test_entries_missing_embeddings() {
     assert_eq!(missing_after.len(), missing.len() - 1);
 }
 
+#[test]
+fn test_delete_embeddings_not_of_dimension() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("test_memory.db");
+    let db = MemoryDB::new(&db_path).unwrap();
+
+    db.insert_memory("notes.md", "This is about embeddings and vector search")
+        .unwrap();
+    db.insert_memory("notes.md", "Another paragraph about neural networks")
+        .unwrap();
+    let missing = db.get_entries_missing_embeddings().unwrap();
+    assert_eq!(missing.len(), 2);
+
+    // 32 floats vs 4 floats, e.g. written by different embedding models
+    db.store_embedding(missing[0].0, &[0u8; 128]).unwrap();
+    db.store_embedding(missing[1].0, &[0u8; 16]).unwrap();
+
+    assert_eq!(db.delete_embeddings_not_of_dimension(32).unwrap(), 1);
+    let missing_after = db.get_entries_missing_embeddings().unwrap();
+    assert_eq!(missing_after.len(), 1);
+    assert_eq!(missing_after[0].0, missing[1].0);
+
+    assert_eq!(db.delete_embeddings_not_of_dimension(32).unwrap(), 0);
+}
+
+#[test]
+fn test_rebuild_fts_index_restores_search() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("test_memory.db");
+    let db = MemoryDB::new(&db_path).unwrap();
+
+    db.insert_memory("notes.md", "The deployment runbook lives in the wiki")
+        .unwrap();
+    db.insert_memory("daily.md", "Deployment went fine today")
+        .unwrap();
+    db.insert_memory("daily.md", "Lunch with the team").unwrap();
+
+    // Simulate a drifted index: wipe FTS without touching the entries
+    db.lock_conn()
+        .unwrap()
+        .execute(
+            "INSERT INTO memory_fts(memory_fts) VALUES ('delete-all')",
+            [],
+        )
+        .unwrap();
+    assert!(db.search("deployment", 10, None, false).unwrap().is_empty());
+
+    assert_eq!(db.rebuild_fts_index().unwrap(), (2, 3));
+    assert_eq!(db.search("deployment", 10, None, false).unwrap().len(), 2);
+}
+
 #[test]
 fn test_insert_memory_empty_content_ignored() {
     let dir = tempfile::tempdir().unwrap();
@@ -269,6 +320,23 @@ fn test_get_recent_entries_limit() {
     assert_eq!(entries.len(), 3, "limit should cap results");
 }
 
+#[test]
+fn test_search_highlight_snippets() {
+    let db = MemoryDB::new(":memory:").unwrap();
+    let filler = "some unrelated filler text ".repeat(20);
+    db.insert_memory("notes.md", &format!("{filler}Rust ownership rules"))
+        .unwrap();
+
+    let hits = db.search("ownership", 10, None, true).unwrap();
+    let snippet = hits[0].snippet.as_deref().unwrap();
+    assert!(snippet.contains("<b>ownership</b>"), "snippet: {snippet}");
+    assert!(snippet.starts_with("..."), "snippet: {snippet}");
+    assert!(snippet.chars().count() < hits[0].content.chars().count());
+
+    let hits = db.search("ownership", 10, None, false).unwrap();
+    assert!(hits[0].snippet.is_none());
+}
+
 #[test]
 fn test_token_record_and_summary() {
     let dir = tempfile::tempdir().unwrap();
@@ -294,6 +362,28 @@ fn test_token_record_and_summary() {
     assert_eq!(summary[0].call_count, 2);
 }
 
+#[test]
+fn test_token_summary_range() {
+    let db = MemoryDB::new(":memory:").unwrap();
+    for day in ["2026-08-31", "2026-09-01", "2026-09-30", "2026-10-01"] {
+        db.record_tokens("gpt-4o", 100, 10, 0, 0, "main", None)
+            .unwrap();
+        let conn = db.lock_conn().unwrap();
+        conn.execute(
+            "UPDATE llm_cost_log SET timestamp = ? WHERE id = last_insert_rowid()",
+            [format!("{day} 23:30:00")],
+        )
+        .unwrap();
+    }
+
+    let summary = db
+        .get_token_summary_range("2026-09-01", "2026-09-30")
+        .unwrap();
+    let days: Vec<&str> = summary.iter().map(|r| r.date.as_str()).collect();
+    assert_eq!(days, vec!["2026-09-30", "2026-09-01"]);
+    assert_eq!(db.get_token_summary("2026-09-01").unwrap().len(), 3);
+}
+
 #[test]
 fn test_fusion_strategy_serde_roundtrip() {
     let rrf = oxicrab_core::config::schema::FusionStrategy::Rrf;
@@ -954,6 +1044,7 @@ fn test_hybrid_search_with_manual_embeddings() {
             FusionStrategy::WeightedScore,
             60,
             0,
+            false,
         )
         .unwrap();
     assert!(!hits.is_empty(), "hybrid search should return results");
@@ -975,6 +1066,7 @@ fn test_hybrid_search_with_manual_embeddings() {
             FusionStrategy::Rrf,
             60,
             0,
+            false,
         )
         .unwrap();
     assert!(
@@ -1000,6 +1092,7 @@ fn test_hybrid_search_with_manual_embeddings() {
             FusionStrategy::WeightedScore,
             60,
             0,
+            false,
         )
         .unwrap();
     for hit in &filtered_hits {
@@ -1041,6 +1134,73 @@ fn test_purge_old_complexity_logs() {
     assert_eq!(db.purge_old_complexity_logs(365).unwrap(), 0);
 }
 
+#[test]
+fn test_empty_response_stats() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = MemoryDB::new(dir.path().join("test.db")).unwrap();
+
+    db.record_empty_response("model-a", "generic", Some("req-1"))
+        .unwrap();
+    db.record_empty_response("model-a", "generic", None)
+        .unwrap();
+    db.record_empty_response("model-b", "fallback", None)
+        .unwrap();
+
+    let stats = db.get_empty_response_stats("2000-01-01").unwrap();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].model, "model-a");
+    assert_eq!(stats[0].action, "generic");
+    assert_eq!(stats[0].count, 2);
+    assert_eq!(stats[1].model, "model-b");
+    assert_eq!(stats[1].count, 1);
+
+    assert!(
+        db.get_empty_response_stats("2999-01-01")
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(db.purge_old_empty_response_logs(0).unwrap(), 0);
+    assert_eq!(db.purge_old_empty_response_logs(365).unwrap(), 0);
+}
+
+#[test]
+fn test_token_breakdowns() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = MemoryDB::new(dir.path().join("test.db")).unwrap();
+
+    let first = TokenBreakdown {
+        calls: 1,
+        system: 900,
+        history: 100,
+        message: 10,
+        provider_input: 1100,
+        ..Default::default()
+    };
+    let second = TokenBreakdown {
+        calls: 2,
+        tool_results: 4000,
+        ..first
+    };
+    db.record_token_breakdown("telegram:1", Some("req-1"), "model-a", &first)
+        .unwrap();
+    db.record_token_breakdown("telegram:1", None, "model-a", &second)
+        .unwrap();
+    db.record_token_breakdown("telegram:2", None, "model-a", &first)
+        .unwrap();
+
+    let rows = db.get_token_breakdowns("telegram:1", 10).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].breakdown, second);
+    assert_eq!(rows[0].request_id, None);
+    assert_eq!(rows[1].breakdown, first);
+    assert_eq!(rows[1].request_id.as_deref(), Some("req-1"));
+    assert_eq!(second.estimated_total(), 5010);
+    assert_eq!(db.get_token_breakdowns("telegram:1", 1).unwrap().len(), 1);
+    assert!(db.get_token_breakdowns("slack:1", 10).unwrap().is_empty());
+    assert_eq!(db.purge_old_token_breakdowns(0).unwrap(), 0);
+    assert_eq!(db.purge_old_token_breakdowns(365).unwrap(), 0);
+}
+
 #[test]
 fn test_purge_old_cost_logs() {
     let dir = tempfile::tempdir().unwrap();
@@ -1124,3 +1284,175 @@ fn test_purge_old_memory_entries() {
         );
     }
 }
+
+#[test]
+fn test_purge_old_memory_entries_respects_importance() {
+    let db = MemoryDB::new(":memory:").unwrap();
+    // Importance is scored from content: 3 (default), 4 (birthday), 5 (allergy)
+    db.insert_memory("notes.md", "User was reading about Rust traits")
+        .unwrap();
+    db.insert_memory("notes.md", "Alice's birthday is on June 12th")
+        .unwrap();
+    db.insert_memory("notes.md", "User is allergic to shellfish")
+        .unwrap();
+    {
+        let conn = db.conn.lock().unwrap();
+        let old_ts = (chrono::Utc::now() - chrono::Duration::days(45)).to_rfc3339();
+        conn.execute(
+            "UPDATE memory_entries SET created_at = ?1",
+            rusqlite::params![old_ts],
+        )
+        .unwrap();
+    }
+
+    // 45 days old with 30-day retention: only the default-importance entry goes;
+    // the importance-4 entry has 60 days
+    assert_eq!(db.purge_old_memory_entries(30).unwrap(), 1);
+    // With 10-day retention the importance-4 entry goes too; importance 5 never does
+    assert_eq!(db.purge_old_memory_entries(10).unwrap(), 1);
+    let remaining = db.get_recent_entries("notes.md", 10).unwrap();
+    assert_eq!(remaining, vec!["User is allergic to shellfish".to_string()]);
+}
+
+#[test]
+fn test_search_ranks_important_entries_higher() {
+    let db = MemoryDB::new(":memory:").unwrap();
+    db.insert_memory("notes.md", "Peanut butter sandwich recipe with honey")
+        .unwrap();
+    db.insert_memory("notes.md", "Bob is allergic to peanut butter")
+        .unwrap();
+
+    let results = db.search("peanut butter", 10, None, false).unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].content.contains("allergic"));
+}
+
+#[test]
+fn test_importance_boost_range() {
+    assert!((importance_boost(3) - 1.0).abs() < 1e-6);
+    assert!(importance_boost(5) > importance_boost(4));
+    assert!(importance_boost(1) < 1.0);
+    assert!((importance_boost(0) - importance_boost(1)).abs() < 1e-6);
+    assert!((importance_boost(9) - importance_boost(5)).abs() < 1e-6);
+}
+
+/// Insert entries with hand-made embeddings; returns their IDs in insert order.
+fn insert_with_embeddings(db: &MemoryDB, entries: &[(&str, &str, [f32; 3])]) -> Vec<i64> {
+    for (source, content, _) in entries {
+        db.insert_memory(source, content).unwrap();
+    }
+    let mut missing = db.get_entries_missing_embeddings().unwrap();
+    missing.sort_by_key(|(id, _, _)| *id);
+    for ((id, _, _), (_, _, emb)) in missing.iter().zip(entries) {
+        let bytes = crate::embeddings::serialize_embedding(emb);
+        db.store_embedding(*id, &bytes).unwrap();
+    }
+    missing.iter().map(|(id, _, _)| *id).collect()
+}
+
+#[test]
+fn test_find_similar_pairs_and_resolve_conflict() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = MemoryDB::new(dir.path().join("test_memory.db")).unwrap();
+    let ids = insert_with_embeddings(
+        &db,
+        &[
+            (
+                "daily:2026-01-01:Facts",
+                "User prefers tea",
+                [1.0, 0.0, 0.0],
+            ),
+            (
+                "daily:2026-02-01:Facts",
+                "User prefers coffee",
+                [0.99, 0.141, 0.0],
+            ),
+            (
+                "daily:2026-02-01:Facts",
+                "User lives in Paris",
+                [0.0, 0.0, 1.0],
+            ),
+        ],
+    );
+
+    let pairs = db.find_similar_pairs(0.9, 10).unwrap();
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].older_id, ids[0]);
+    assert_eq!(pairs[0].newer_id, ids[1]);
+
+    db.record_memory_conflict(&pairs[0], conflict_status::OPEN, Some("tea vs coffee"))
+        .unwrap();
+    // Judged pairs are not returned again.
+    assert!(db.find_similar_pairs(0.9, 10).unwrap().is_empty());
+
+    let open = db
+        .list_memory_conflicts(Some(conflict_status::OPEN))
+        .unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].explanation.as_deref(), Some("tea vs coffee"));
+
+    let removed = db
+        .resolve_memory_conflict(open[0].id, ConflictResolution::KeepNewer)
+        .unwrap();
+    assert_eq!(removed.as_deref(), Some("User prefers tea"));
+    assert!(
+        db.get_recent_entries("daily:2026-01-01:Facts", 10)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        db.list_memory_conflicts(Some(conflict_status::OPEN))
+            .unwrap()
+            .is_empty()
+    );
+    // Resolving again fails: the conflict is no longer open.
+    assert!(
+        db.resolve_memory_conflict(open[0].id, ConflictResolution::KeepNewer)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_resolve_conflict_keep_both_and_knowledge_protection() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = MemoryDB::new(dir.path().join("test_memory.db")).unwrap();
+    insert_with_embeddings(
+        &db,
+        &[
+            (
+                "knowledge:profile.md",
+                "Office is on floor 3",
+                [1.0, 0.0, 0.0],
+            ),
+            (
+                "daily:2026-03-01:Facts",
+                "Office is on floor 5",
+                [1.0, 0.01, 0.0],
+            ),
+        ],
+    );
+    let pair = db.find_similar_pairs(0.9, 10).unwrap().remove(0);
+    db.record_memory_conflict(&pair, conflict_status::OPEN, None)
+        .unwrap();
+    let id = db.list_memory_conflicts(None).unwrap()[0].id;
+
+    let err = db
+        .resolve_memory_conflict(id, ConflictResolution::KeepNewer)
+        .unwrap_err();
+    assert!(err.to_string().contains("protected"));
+
+    let removed = db
+        .resolve_memory_conflict(id, ConflictResolution::KeepBoth)
+        .unwrap();
+    assert!(removed.is_none());
+    let all = db.list_memory_conflicts(None).unwrap();
+    assert_eq!(all[0].status, conflict_status::DISMISSED);
+}
+
+#[test]
+fn test_check_writable_leaves_no_row() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = MemoryDB::new(dir.path().join("test_memory.db")).unwrap();
+    db.check_writable().unwrap();
+    assert!(db.load_session("__self_test__").unwrap().is_none());
+}
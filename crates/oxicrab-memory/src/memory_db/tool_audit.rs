@@ -0,0 +1,227 @@
+use super::MemoryDB;
+use anyhow::Result;
+use rusqlite::params;
+
+/// One recorded tool call. Arguments and result summary are stored already
+/// redacted by the caller.
+#[derive(Debug, Clone)]
+pub struct ToolAuditEntry {
+    pub id: i64,
+    pub timestamp: String,
+    pub session_key: String,
+    pub request_id: Option<String>,
+    pub tool_name: String,
+    pub arguments: String,
+    pub result_summary: String,
+    pub success: bool,
+    pub duration_ms: i64,
+}
+
+/// Call counts of one tool over a period of the audit log.
+#[derive(Debug, Clone)]
+pub struct ToolAuditStats {
+    pub tool_name: String,
+    pub calls: u64,
+    pub failures: u64,
+    pub avg_duration_ms: f64,
+}
+
+impl ToolAuditStats {
+    /// Share of calls that failed, from 0.0 to 1.0.
+    pub fn failure_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.calls as f64
+        }
+    }
+}
+
+impl MemoryDB {
+    /// Record a tool call in the audit log.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_tool_audit(
+        &self,
+        session_key: &str,
+        request_id: Option<&str>,
+        tool_name: &str,
+        arguments: &str,
+        result_summary: &str,
+        success: bool,
+        duration_ms: u64,
+    ) -> Result<()> {
+        let conn = self.lock_conn()?;
+        conn.execute(
+            "INSERT INTO tool_audit_log
+             (session_key, request_id, tool_name, arguments, result_summary, success, duration_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                session_key,
+                request_id,
+                tool_name,
+                arguments,
+                result_summary,
+                success,
+                duration_ms as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// List audit entries recorded at or after `since` (`YYYY-MM-DD HH:MM:SS`,
+    /// UTC), newest first, capped at `limit`.
+    pub fn list_tool_audit(&self, since: &str, limit: usize) -> Result<Vec<ToolAuditEntry>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp, session_key, request_id, tool_name, arguments,
+                    result_summary, success, duration_ms
+             FROM tool_audit_log
+             WHERE timestamp >= ?1
+             ORDER BY id DESC LIMIT ?2",
+        )?;
+        let rows = stmt
+            .query_map(params![since, limit as i64], |row| {
+                Ok(ToolAuditEntry {
+                    id: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    session_key: row.get(2)?,
+                    request_id: row.get(3)?,
+                    tool_name: row.get(4)?,
+                    arguments: row.get(5)?,
+                    result_summary: row.get(6)?,
+                    success: row.get(7)?,
+                    duration_ms: row.get(8)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Per-tool call and failure counts since `since_date` (`YYYY-MM-DD`,
+    /// UTC), most called first.
+    pub fn get_tool_audit_stats(&self, since_date: &str) -> Result<Vec<ToolAuditStats>> {
+        let conn = self.lock_conn()?;
+        let since_datetime = format!("{since_date} 00:00:00");
+        let mut stmt = conn.prepare(
+            "SELECT tool_name, COUNT(*) as cnt, SUM(CASE WHEN success THEN 0 ELSE 1 END),
+                    AVG(duration_ms)
+             FROM tool_audit_log
+             WHERE timestamp >= ?
+             GROUP BY tool_name
+             ORDER BY cnt DESC, tool_name",
+        )?;
+        let rows = stmt
+            .query_map([&since_datetime], |row| {
+                Ok(ToolAuditStats {
+                    tool_name: row.get(0)?,
+                    calls: row.get::<_, i64>(1)? as u64,
+                    failures: row.get::<_, i64>(2)? as u64,
+                    avg_duration_ms: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Purge audit entries older than `days`. Returns number of rows deleted.
+    pub fn purge_old_tool_audit(&self, days: u32) -> Result<usize> {
+        if days == 0 {
+            return Ok(0);
+        }
+        let conn = self.lock_conn()?;
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+        let cutoff_str = cutoff.format("%Y-%m-%d %H:%M:%S").to_string();
+        let deleted = conn.execute(
+            "DELETE FROM tool_audit_log WHERE timestamp < ?",
+            [&cutoff_str],
+        )?;
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MemoryDB;
+
+    #[test]
+    fn test_record_and_list_tool_audit() {
+        let db = MemoryDB::new(":memory:").unwrap();
+
+        db.record_tool_audit(
+            "telegram:42",
+            Some("req-1"),
+            "read_file",
+            r#"{"path":"notes.md"}"#,
+            "hello",
+            true,
+            12,
+        )
+        .unwrap();
+        db.record_tool_audit("telegram:42", None, "exec", "{}", "boom", false, 3)
+            .unwrap();
+
+        let entries = db.list_tool_audit("1970-01-01 00:00:00", 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        // Newest first
+        assert_eq!(entries[0].tool_name, "exec");
+        assert!(!entries[0].success);
+        assert!(entries[0].request_id.is_none());
+        assert_eq!(entries[1].tool_name, "read_file");
+        assert!(entries[1].success);
+        assert_eq!(entries[1].request_id.as_deref(), Some("req-1"));
+        assert_eq!(entries[1].duration_ms, 12);
+
+        let limited = db.list_tool_audit("1970-01-01 00:00:00", 1).unwrap();
+        assert_eq!(limited.len(), 1);
+        assert!(
+            db.list_tool_audit("2999-01-01 00:00:00", 10)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_tool_audit_stats() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        db.record_tool_audit("telegram:42", None, "exec", "{}", "ok", true, 10)
+            .unwrap();
+        db.record_tool_audit("telegram:42", None, "exec", "{}", "boom", false, 30)
+            .unwrap();
+        db.record_tool_audit("slack:C1", None, "read_file", "{}", "ok", true, 2)
+            .unwrap();
+
+        let stats = db.get_tool_audit_stats("1970-01-01").unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].tool_name, "exec");
+        assert_eq!(stats[0].calls, 2);
+        assert_eq!(stats[0].failures, 1);
+        assert!((stats[0].failure_rate() - 0.5).abs() < f64::EPSILON);
+        assert!((stats[0].avg_duration_ms - 20.0).abs() < f64::EPSILON);
+        assert_eq!(stats[1].tool_name, "read_file");
+        assert_eq!(stats[1].failures, 0);
+        assert!(db.get_tool_audit_stats("2999-01-01").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_purge_old_tool_audit() {
+        let db = MemoryDB::new(":memory:").unwrap();
+        db.record_tool_audit("cli:default", None, "exec", "{}", "ok", true, 1)
+            .unwrap();
+        {
+            let conn = db.lock_conn().unwrap();
+            conn.execute(
+                "UPDATE tool_audit_log SET timestamp = '2000-01-01 00:00:00'",
+                [],
+            )
+            .unwrap();
+        }
+        db.record_tool_audit("cli:default", None, "read_file", "{}", "ok", true, 1)
+            .unwrap();
+
+        assert_eq!(db.purge_old_tool_audit(0).unwrap(), 0);
+        assert_eq!(db.purge_old_tool_audit(30).unwrap(), 1);
+        let entries = db.list_tool_audit("1970-01-01 00:00:00", 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tool_name, "read_file");
+    }
+}
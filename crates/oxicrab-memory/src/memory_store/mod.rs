@@ -10,6 +10,30 @@ use tracing::debug;
 #[cfg(feature = "embeddings")]
 use tracing::warn;
 
+/// How many recent daily notes [`MemoryStore::forget_matching`] searches.
+const FORGET_SEARCH_LIMIT: usize = 200;
+
+/// Per-call overrides of the configured search settings; `None` keeps the
+/// value from the memory config.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SearchTuning {
+    pub limit: Option<usize>,
+    /// 1.0 = keyword only, 0.0 = vector only.
+    pub keyword_weight: Option<f32>,
+    pub fusion: Option<oxicrab_core::config::schema::FusionStrategy>,
+    /// Fill [`MemoryHit::snippet`](crate::memory_db::MemoryHit::snippet) with
+    /// highlighted excerpts.
+    pub highlight: bool,
+}
+
+/// Hits of [`MemoryStore::ranked_search`], best first.
+pub struct RankedSearch {
+    pub hits: Vec<crate::memory_db::MemoryHit>,
+    /// Fusion used, or `None` when only keyword search ran because no
+    /// query embedding was available.
+    pub fusion: Option<oxicrab_core::config::schema::FusionStrategy>,
+}
+
 pub struct MemoryStore {
     db: Arc<MemoryDB>,
     #[cfg(feature = "embeddings")]
@@ -21,6 +45,8 @@ pub struct MemoryStore {
     #[cfg(feature = "embeddings")]
     rrf_k: u32,
     #[cfg(feature = "embeddings")]
+    on_embedding_failure: oxicrab_core::config::schema::EmbeddingFailureMode,
+    #[cfg(feature = "embeddings")]
     recency_half_life_days: u32,
     search_result_limit: usize,
     max_context_chars: usize,
@@ -66,6 +92,8 @@ impl MemoryStore {
             #[cfg(feature = "embeddings")]
             rrf_k: 60,
             #[cfg(feature = "embeddings")]
+            on_embedding_failure: oxicrab_core::config::schema::EmbeddingFailureMode::default(),
+            #[cfg(feature = "embeddings")]
             recency_half_life_days: 90,
             search_result_limit: 8,
             max_context_chars: 4000,
@@ -86,6 +114,8 @@ impl MemoryStore {
             #[cfg(feature = "embeddings")]
             rrf_k: 60,
             #[cfg(feature = "embeddings")]
+            on_embedding_failure: oxicrab_core::config::schema::EmbeddingFailureMode::default(),
+            #[cfg(feature = "embeddings")]
             recency_half_life_days: 90,
             search_result_limit: 8,
             max_context_chars: 4000,
@@ -99,8 +129,8 @@ impl MemoryStore {
         #[cfg(feature = "embeddings")]
         let embedding_service = if memory_config.embeddings_enabled {
             Some(Arc::new(LazyEmbeddingService::new(
-                memory_config.embeddings_model.clone(),
-                memory_config.embedding_cache_size,
+                memory_config,
+                db.clone(),
             )))
         } else {
             None
@@ -117,6 +147,8 @@ impl MemoryStore {
             #[cfg(feature = "embeddings")]
             rrf_k: memory_config.rrf_k,
             #[cfg(feature = "embeddings")]
+            on_embedding_failure: memory_config.on_embedding_failure,
+            #[cfg(feature = "embeddings")]
             recency_half_life_days: memory_config.recency_half_life_days,
             search_result_limit: memory_config.search_result_limit,
             max_context_chars: memory_config.max_context_chars,
@@ -156,8 +188,8 @@ impl MemoryStore {
         #[cfg(feature = "embeddings")]
         let embedding_service = if memory_config.embeddings_enabled {
             Some(Arc::new(LazyEmbeddingService::new(
-                memory_config.embeddings_model.clone(),
-                memory_config.embedding_cache_size,
+                memory_config,
+                db.clone(),
             )))
         } else {
             None
@@ -174,6 +206,8 @@ impl MemoryStore {
             #[cfg(feature = "embeddings")]
             rrf_k: memory_config.rrf_k,
             #[cfg(feature = "embeddings")]
+            on_embedding_failure: memory_config.on_embedding_failure,
+            #[cfg(feature = "embeddings")]
             recency_half_life_days: memory_config.recency_half_life_days,
             search_result_limit: memory_config.search_result_limit,
             max_context_chars: memory_config.max_context_chars,
@@ -204,6 +238,10 @@ impl MemoryStore {
     }
 
     /// Hybrid search combining keyword and vector similarity.
+    ///
+    /// If the query embedding can't be produced (service not ready, embed
+    /// error, empty vector), `memory.onEmbeddingFailure` decides whether to
+    /// run keyword-only search instead or return the error.
     #[cfg(feature = "embeddings")]
     pub fn hybrid_search(
         &self,
@@ -211,24 +249,49 @@ impl MemoryStore {
         limit: usize,
         exclude_sources: Option<&HashSet<String>>,
     ) -> Result<Vec<crate::memory_db::MemoryHit>> {
-        let emb_svc = self
-            .embedding_service
-            .as_ref()
-            .and_then(|lazy| lazy.get())
-            .ok_or_else(|| anyhow::anyhow!("embeddings not available"))?;
-        let query_embedding = emb_svc.embed_query(query)?;
+        let query_embedding = match self.embed_query(query) {
+            Ok(embedding) => embedding,
+            Err(e) => {
+                if self.on_embedding_failure
+                    == oxicrab_core::config::schema::EmbeddingFailureMode::Error
+                {
+                    return Err(e);
+                }
+                warn!("query embedding failed, degrading to keyword search: {e}");
+                return self.db.search(query, limit, exclude_sources, false);
+            }
+        };
+        self.hybrid_hits(
+            query,
+            &query_embedding,
+            limit,
+            exclude_sources,
+            SearchTuning::default(),
+        )
+    }
+
+    #[cfg(feature = "embeddings")]
+    fn hybrid_hits(
+        &self,
+        query: &str,
+        query_embedding: &[f32],
+        limit: usize,
+        exclude_sources: Option<&HashSet<String>>,
+        tuning: SearchTuning,
+    ) -> Result<Vec<crate::memory_db::MemoryHit>> {
         // Config `hybridWeight` semantics: 0.0 = keyword only, 1.0 = vector only.
         // The DB `hybrid_search` takes `keyword_weight`, so invert.
-        let keyword_weight = 1.0 - self.hybrid_weight;
+        let keyword_weight = tuning.keyword_weight.unwrap_or(1.0 - self.hybrid_weight);
         let hits = self.db.hybrid_search(
             query,
-            &query_embedding,
+            query_embedding,
             limit,
             exclude_sources,
             keyword_weight,
-            self.fusion_strategy,
+            tuning.fusion.unwrap_or(self.fusion_strategy),
             self.rrf_k,
             self.recency_half_life_days,
+            tuning.highlight,
         )?;
         debug!(
             "memory hybrid search: query_len={}, results={}",
@@ -238,6 +301,51 @@ impl MemoryStore {
         Ok(hits)
     }
 
+    /// Search all memory with the configured settings, overridden by
+    /// `tuning`. Runs hybrid search when embeddings are available and falls
+    /// back to keyword search otherwise (or when the query embedding fails
+    /// and `memory.onEmbeddingFailure` allows it).
+    pub fn ranked_search(&self, query: &str, tuning: SearchTuning) -> Result<RankedSearch> {
+        let limit = tuning.limit.unwrap_or(self.search_result_limit);
+        #[cfg(feature = "embeddings")]
+        if self.has_embeddings() {
+            match self.embed_query(query) {
+                Ok(embedding) => {
+                    let hits = self.hybrid_hits(query, &embedding, limit, None, tuning)?;
+                    return Ok(RankedSearch {
+                        hits,
+                        fusion: Some(tuning.fusion.unwrap_or(self.fusion_strategy)),
+                    });
+                }
+                Err(e)
+                    if self.on_embedding_failure
+                        == oxicrab_core::config::schema::EmbeddingFailureMode::Error =>
+                {
+                    return Err(e);
+                }
+                Err(e) => warn!("query embedding failed, degrading to keyword search: {e}"),
+            }
+        }
+        Ok(RankedSearch {
+            hits: self.db.search(query, limit, None, tuning.highlight)?,
+            fusion: None,
+        })
+    }
+
+    #[cfg(feature = "embeddings")]
+    fn embed_query(&self, query: &str) -> Result<Vec<f32>> {
+        let emb_svc = self
+            .embedding_service
+            .as_ref()
+            .and_then(|lazy| lazy.get())
+            .ok_or_else(|| anyhow::anyhow!("embeddings not available"))?;
+        let embedding = emb_svc.embed_query(query)?;
+        if embedding.is_empty() {
+            anyhow::bail!("query embedding is empty");
+        }
+        Ok(embedding)
+    }
+
     pub fn get_memory_context(&self, query: Option<&str>) -> Result<String> {
         self.get_memory_context_scoped(query, false)
     }
@@ -264,20 +372,14 @@ impl MemoryStore {
             let initial_hits = if self.has_embeddings() {
                 #[cfg(feature = "embeddings")]
                 {
-                    match self.hybrid_search(query, result_limit, Some(&exclude)) {
-                        Ok(h) => h,
-                        Err(e) => {
-                            warn!("hybrid search failed, falling back to keyword: {}", e);
-                            self.db.search(query, result_limit, Some(&exclude))?
-                        }
-                    }
+                    self.hybrid_search(query, result_limit, Some(&exclude))?
                 }
                 #[cfg(not(feature = "embeddings"))]
                 {
-                    self.db.search(query, result_limit, Some(&exclude))?
+                    self.db.search(query, result_limit, Some(&exclude), false)?
                 }
             } else {
-                self.db.search(query, result_limit, Some(&exclude))?
+                self.db.search(query, result_limit, Some(&exclude), false)?
             };
 
             // If initial search returned no results and query has multiple terms,
@@ -285,7 +387,7 @@ impl MemoryStore {
             let hits = if initial_hits.is_empty() && query.split_whitespace().count() >= 2 {
                 debug!("memory search: no results for full query, retrying with relaxed search");
                 // Try keyword-only search which may match partial terms
-                self.db.search(query, result_limit, Some(&exclude))?
+                self.db.search(query, result_limit, Some(&exclude), false)?
             } else {
                 initial_hits
             };
@@ -339,31 +441,37 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Append content under a named section for today's notes, one entry per
+    /// line so each can be forgotten on its own. Blank lines and headers are
+    /// skipped. Returns the IDs of newly inserted entries.
+    pub fn append_lines_to_section(&self, section: &str, content: &str) -> Result<Vec<i64>> {
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let source_key = format!("daily:{today}:{section}");
+        let mut ids = Vec::new();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(id) = self.db.insert_memory_entry(&source_key, line)? {
+                ids.push(id);
+            }
+        }
+        debug!(
+            "daily notes appended: section={}, {} entries",
+            section,
+            ids.len()
+        );
+        self.backfill_embeddings();
+        Ok(ids)
+    }
+
     /// Generate embeddings for any entries that don't have them yet.
     /// Best-effort: logs warnings on failure but never errors out.
     #[cfg_attr(not(feature = "embeddings"), allow(clippy::unused_self))]
     fn backfill_embeddings(&self) {
         #[cfg(feature = "embeddings")]
         if let Some(svc) = self.embedding_service() {
-            match self.db.get_entries_missing_embeddings() {
-                Ok(entries) if !entries.is_empty() => {
-                    let texts: Vec<&str> = entries.iter().map(|(_, _, c)| c.as_str()).collect();
-                    match svc.embed_texts(&texts) {
-                        Ok(vectors) => {
-                            for ((id, _, _), vec) in entries.iter().zip(vectors.iter()) {
-                                let bytes = crate::embeddings::serialize_embedding(vec);
-                                if let Err(e) = self.db.store_embedding(*id, &bytes) {
-                                    warn!("failed to store embedding for entry {id}: {e}");
-                                }
-                            }
-                            debug!("back-filled {} embeddings", entries.len());
-                        }
-                        Err(e) => warn!("embedding back-fill failed: {e}"),
-                    }
-                }
-                Err(e) => warn!("failed to check for missing embeddings: {e}"),
-                _ => {}
-            }
+            crate::embeddings::backfill_missing_embeddings(&self.db, svc);
         }
     }
 
@@ -392,6 +500,7 @@ impl MemoryStore {
                 oxicrab_core::config::schema::FusionStrategy::WeightedScore,
                 60,
                 0, // no recency decay for dedup
+                false,
             ) {
                 Ok(hits) => {
                     // Check if any hit from daily: sources has high similarity
@@ -427,6 +536,29 @@ impl MemoryStore {
         self.db.get_recent_daily_entries(limit)
     }
 
+    /// Delete the recent daily note that contains every word of `target` (see
+    /// [`best_forget_match`](crate::remember::best_forget_match)). Returns the
+    /// deleted content, or `None` when no note matches exactly.
+    pub fn forget_matching(&self, target: &str) -> Result<Option<String>> {
+        let entries = self
+            .db
+            .get_recent_daily_entries_with_ids(FORGET_SEARCH_LIMIT)?;
+        let Some((id, _)) = crate::remember::best_forget_match(target, &entries) else {
+            return Ok(None);
+        };
+        self.db.delete_memory_entry(*id)
+    }
+
+    /// The recent daily note closest to `target` when
+    /// [`forget_matching`](Self::forget_matching) finds nothing, so the caller
+    /// can ask the user to confirm it instead of deleting it.
+    pub fn closest_forget_candidate(&self, target: &str) -> Result<Option<(i64, String)>> {
+        let entries = self
+            .db
+            .get_recent_daily_entries_with_ids(FORGET_SEARCH_LIMIT)?;
+        Ok(crate::remember::closest_forget_candidate(target, &entries).cloned())
+    }
+
     /// Read entries from a specific section of today's notes.
     pub fn read_today_section(&self, section: &str) -> Result<String> {
         let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
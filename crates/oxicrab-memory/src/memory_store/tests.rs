@@ -19,6 +19,35 @@ async fn test_with_config_wires_fusion_strategy() {
     assert!((store.hybrid_weight - 0.3).abs() < f32::EPSILON);
 }
 
+#[cfg(feature = "embeddings")]
+#[test]
+fn test_hybrid_search_embedding_failure_mode() {
+    use oxicrab_core::config::schema::EmbeddingFailureMode;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    // No embedding service, so every query embedding fails
+    let mut config = oxicrab_core::config::schema::MemoryConfig {
+        embeddings_enabled: false,
+        ..Default::default()
+    };
+
+    let store = MemoryStore::with_config(tmp.path(), &config).unwrap();
+    store
+        .append_today("the wifi password is on the fridge")
+        .unwrap();
+    let hits = store.hybrid_search("wifi password", 5, None).unwrap();
+    assert_eq!(
+        hits.len(),
+        1,
+        "degrade mode should fall back to keyword search"
+    );
+
+    config.on_embedding_failure = EmbeddingFailureMode::Error;
+    let store = MemoryStore::with_db_and_config(store.db(), &config);
+    let err = store.hybrid_search("wifi password", 5, None).unwrap_err();
+    assert!(err.to_string().contains("embeddings not available"));
+}
+
 #[test]
 fn test_append_today_inserts_to_db() {
     let tmp = tempfile::TempDir::new().unwrap();
@@ -60,6 +89,30 @@ fn test_append_to_section_and_read() {
     assert!(section.contains("user prefers dark mode"));
 }
 
+#[test]
+fn test_append_lines_to_section_one_entry_per_line() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let store = MemoryStore::new(tmp.path()).unwrap();
+
+    let ids = store
+        .append_lines_to_section(
+            "Facts",
+            "## Facts\n- user likes Rust\n\n- user has two cats",
+        )
+        .unwrap();
+    assert_eq!(ids.len(), 2);
+    // Repeating a fact inserts nothing new
+    let again = store
+        .append_lines_to_section("Facts", "- user likes Rust")
+        .unwrap();
+    assert!(again.is_empty());
+
+    let section = store.read_today_section("Facts").unwrap();
+    assert!(section.contains("- user likes Rust"));
+    assert!(section.contains("- user has two cats"));
+    assert!(!section.contains("## Facts"));
+}
+
 #[test]
 fn test_group_memory_context_excludes_daily() {
     let tmp = tempfile::TempDir::new().unwrap();
@@ -76,3 +129,53 @@ fn test_group_memory_context_excludes_daily() {
         "group context should NOT include daily notes"
     );
 }
+
+#[test]
+fn test_ranked_search_without_embeddings_is_keyword_only() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let store = MemoryStore::new(tmp.path()).unwrap();
+    store
+        .append_to_section("Home", "the garage code is 4512")
+        .unwrap();
+    store
+        .append_to_section("Work", "the office door code is 9981")
+        .unwrap();
+
+    let result = store
+        .ranked_search("code", SearchTuning::default())
+        .unwrap();
+    assert!(result.fusion.is_none());
+    assert_eq!(result.hits.len(), 2);
+
+    let result = store
+        .ranked_search(
+            "code",
+            SearchTuning {
+                limit: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(result.hits.len(), 1);
+}
+
+#[test]
+fn test_forget_matching_deletes_best_entry() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let store = MemoryStore::new(tmp.path()).unwrap();
+
+    store.append_today("User's gate code is 4821").unwrap();
+    store.append_today("User prefers tea over coffee").unwrap();
+
+    let forgotten = store.forget_matching("gate code is 4821").unwrap();
+    assert_eq!(forgotten.as_deref(), Some("User's gate code is 4821"));
+    assert!(
+        store
+            .forget_matching("gate code is 4821")
+            .unwrap()
+            .is_none()
+    );
+
+    let entries = store.get_recent_daily_entries(10).unwrap();
+    assert_eq!(entries, vec!["User prefers tea over coffee".to_string()]);
+}
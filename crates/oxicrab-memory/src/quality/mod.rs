@@ -1,6 +1,8 @@
 //! Memory quality gates: filter low-signal content and reframe negative memories
 //! before they are persisted to daily notes or memory storage.
 
+use oxicrab_core::config::schema::MemoryCategory;
+
 /// Result of running content through quality gates.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum QualityVerdict {
@@ -10,6 +12,12 @@ pub enum QualityVerdict {
     Reframed(String),
     /// Content was rejected as low-signal.
     Reject(RejectReason),
+    /// Content (possibly reframed) falls in a category the user must confirm
+    /// before it is written.
+    NeedsConfirmation {
+        content: String,
+        category: MemoryCategory,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -100,9 +108,156 @@ const NEGATIVE_PATTERNS: &[&str] = &[
     "was down",
 ];
 
+/// Importance assigned to entries that match no importance signal.
+pub const DEFAULT_IMPORTANCE: u8 = 3;
+
+/// Facts whose loss would hurt the user: health, safety, and where secrets live.
+const CRITICAL_PATTERNS: &[&str] = &[
+    "allergic",
+    "allergy",
+    "allergies",
+    "anaphyla",
+    "epipen",
+    "medication",
+    "medical",
+    "diagnosed",
+    "blood type",
+    "emergency contact",
+    "password",
+    "passcode",
+    "recovery code",
+    "seed phrase",
+    "pin code",
+];
+
+/// Durable personal facts and standing instructions.
+const HIGH_PATTERNS: &[&str] = &[
+    "birthday",
+    "anniversary",
+    "address is",
+    "lives at",
+    "lives in",
+    "deadline",
+    "important",
+    "always ",
+    "never ",
+    "prefers",
+    "preference",
+    "phone number",
+];
+
+const MEDICAL_PATTERNS: &[&str] = &[
+    "allergic",
+    "allergy",
+    "allergies",
+    "anaphyla",
+    "epipen",
+    "medication",
+    "medicine",
+    "prescription",
+    "medical",
+    "diagnosed",
+    "diagnosis",
+    "blood type",
+    "blood pressure",
+    "surgery",
+    "therapy",
+    "therapist",
+    "pregnan",
+    "disease",
+    "insulin",
+];
+
+const FINANCIAL_PATTERNS: &[&str] = &[
+    "bank account",
+    "account number",
+    "routing number",
+    "iban",
+    "credit card",
+    "debit card",
+    "card number",
+    "salary",
+    "income",
+    "mortgage",
+    "loan",
+    "debt",
+    "tax return",
+    "net worth",
+    "portfolio",
+];
+
+const SECURITY_PATTERNS: &[&str] = &[
+    "password",
+    "passcode",
+    "pin code",
+    "pin is",
+    "recovery code",
+    "backup code",
+    "seed phrase",
+    "private key",
+    "api key",
+    "ssh key",
+    "security question",
+    "2fa",
+];
+
+/// Sensitive category `content` falls into, if any. Keyword heuristic; the
+/// first matching category in medical, financial, security order wins.
+pub fn sensitive_category(content: &str) -> Option<MemoryCategory> {
+    let lower = content.to_lowercase();
+    [
+        (MemoryCategory::Medical, MEDICAL_PATTERNS),
+        (MemoryCategory::Financial, FINANCIAL_PATTERNS),
+        (MemoryCategory::Security, SECURITY_PATTERNS),
+    ]
+    .into_iter()
+    .find(|(_, patterns)| patterns.iter().any(|p| lower.contains(p)))
+    .map(|(category, _)| category)
+}
+
+/// Score how important a memory is on a 1-5 scale. Cheap keyword heuristic:
+/// health/safety facts and secret locations score 5, durable personal facts
+/// and standing instructions score 4, reports of transient breakage score 2,
+/// everything else gets [`DEFAULT_IMPORTANCE`].
+pub fn score_importance(content: &str) -> u8 {
+    let lower = content.to_lowercase();
+    if CRITICAL_PATTERNS.iter().any(|p| lower.contains(p)) {
+        return 5;
+    }
+    if HIGH_PATTERNS.iter().any(|p| lower.contains(p)) {
+        return 4;
+    }
+    if NEGATIVE_PATTERNS.iter().any(|p| lower.contains(p)) {
+        return 2;
+    }
+    DEFAULT_IMPORTANCE
+}
+
 /// Run content through all quality gates. Returns a verdict indicating whether
 /// the content should pass, be reframed, or be rejected.
 pub fn check_quality(content: &str) -> QualityVerdict {
+    check_quality_with(content, &[])
+}
+
+/// [`check_quality`], additionally flagging content in any of `confirm` as
+/// [`QualityVerdict::NeedsConfirmation`].
+pub fn check_quality_with(content: &str, confirm: &[MemoryCategory]) -> QualityVerdict {
+    let verdict = check_gates(content);
+    let candidate = match &verdict {
+        QualityVerdict::Pass => content.trim(),
+        QualityVerdict::Reframed(reframed) => reframed.as_str(),
+        _ => return verdict,
+    };
+    match sensitive_category(candidate) {
+        Some(category) if confirm.contains(&category) => QualityVerdict::NeedsConfirmation {
+            content: candidate.to_string(),
+            category,
+        },
+        _ => verdict,
+    }
+}
+
+fn check_gates(content: &str) -> QualityVerdict {
     let trimmed = content.trim();
 
     if trimmed.len() < MIN_USEFUL_LEN {
@@ -166,7 +321,17 @@ fn try_reframe_negative(lower: &str, original: &str) -> Option<String> {
 /// Each line is checked independently. Rejected lines are dropped, reframed lines
 /// are replaced. Returns the filtered content (may be empty if all lines rejected).
 pub fn filter_lines(content: &str) -> String {
+    filter_lines_with(content, &[]).0
+}
+
+/// [`filter_lines`], also pulling out lines in any of `confirm`. Returns the
+/// content safe to write and the held-back facts with their category.
+pub fn filter_lines_with(
+    content: &str,
+    confirm: &[MemoryCategory],
+) -> (String, Vec<(String, MemoryCategory)>) {
     let mut output = Vec::new();
+    let mut held = Vec::new();
     for line in content.lines() {
         let trimmed = line.trim();
         // Preserve headers and empty lines
@@ -180,8 +345,11 @@ pub fn filter_lines(content: &str) -> String {
         } else {
             ("", trimmed)
         };
-        match check_quality(check_text) {
+        match check_quality_with(check_text, confirm) {
             QualityVerdict::Pass => output.push(line.to_string()),
+            QualityVerdict::NeedsConfirmation { content, category } => {
+                held.push((content, category));
+            }
             QualityVerdict::Reframed(reframed) => {
                 let indent = line.len() - line.trim_start().len();
                 output.push(format!("{}{}{}", &line[..indent], prefix, reframed));
@@ -197,7 +365,7 @@ pub fn filter_lines(content: &str) -> String {
         output.pop();
     }
 
-    output.join("\n")
+    (output.join("\n"), held)
 }
 
 #[cfg(test)]
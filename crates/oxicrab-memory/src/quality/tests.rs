@@ -247,3 +247,89 @@ fn filter_lines_preserves_indentation_on_reframe() {
     assert!(result.starts_with("  - NOTE (reframed):"));
     assert!(result.contains("API was broken"));
 }
+
+// --- Importance tests ---
+
+#[test]
+fn importance_critical_facts() {
+    assert_eq!(score_importance("Sarah is allergic to peanuts"), 5);
+    assert_eq!(
+        score_importance("The router password is in the blue notebook"),
+        5
+    );
+    // Health facts stay critical even when phrased as a failure
+    assert_eq!(score_importance("The allergy medication failed to help"), 5);
+}
+
+#[test]
+fn importance_durable_and_transient() {
+    assert_eq!(score_importance("Mom's birthday is March 3rd"), 4);
+    assert_eq!(score_importance("User always wants metric units"), 4);
+    assert_eq!(
+        score_importance("The staging server was down this morning"),
+        2
+    );
+    assert_eq!(
+        score_importance("User is working on a rust project"),
+        DEFAULT_IMPORTANCE
+    );
+}
+
+// --- Confirmation tests ---
+
+#[test]
+fn sensitive_category_detection() {
+    assert_eq!(
+        sensitive_category("Sarah is allergic to peanuts"),
+        Some(MemoryCategory::Medical)
+    );
+    assert_eq!(
+        sensitive_category("The mortgage payment is due on the 1st"),
+        Some(MemoryCategory::Financial)
+    );
+    assert_eq!(
+        sensitive_category("The wifi password is on the fridge"),
+        Some(MemoryCategory::Security)
+    );
+    assert_eq!(sensitive_category("User prefers dark mode"), None);
+}
+
+#[test]
+fn check_quality_with_holds_listed_categories() {
+    let confirm = [MemoryCategory::Medical];
+    assert_eq!(
+        check_quality_with("Sarah is allergic to peanuts", &confirm),
+        QualityVerdict::NeedsConfirmation {
+            content: "Sarah is allergic to peanuts".to_string(),
+            category: MemoryCategory::Medical,
+        }
+    );
+    // Unlisted categories and low-signal content are unaffected
+    assert_eq!(
+        check_quality_with("The wifi password is on the fridge", &confirm),
+        QualityVerdict::Pass
+    );
+    assert!(matches!(
+        check_quality_with("ok", &confirm),
+        QualityVerdict::Reject(_)
+    ));
+    assert_eq!(
+        check_quality("Sarah is allergic to peanuts"),
+        QualityVerdict::Pass
+    );
+}
+
+#[test]
+fn filter_lines_with_holds_back_sensitive_lines() {
+    let input = "- User prefers dark mode\n- Sarah is allergic to peanuts";
+    let (kept, held) = filter_lines_with(input, &[MemoryCategory::Medical]);
+    assert_eq!(kept, "- User prefers dark mode");
+    assert_eq!(
+        held,
+        vec![(
+            "Sarah is allergic to peanuts".to_string(),
+            MemoryCategory::Medical
+        )]
+    );
+    assert_eq!(filter_lines(input), input);
+}
@@ -11,6 +11,34 @@ const REMEMBER_PATTERNS: &[&str] = &[
 
 const MIN_CONTENT_LEN: usize = 8;
 
+const FORGET_PATTERNS: &[&str] = &[
+    "forget that ",
+    "forget: ",
+    "please forget ",
+    "forget about ",
+];
+
+/// Targets too broad for the forget fast path ("forget about everything").
+const BROAD_FORGET_TARGETS: &[&str] = &[
+    "everything",
+    "all of it",
+    "all of that",
+    "all of this",
+    "all my memories",
+    "all memories",
+    "my memories",
+    "your memory",
+    "what i said",
+    "what i told you",
+];
+
+/// Minimum score for [`closest_forget_candidate`] to suggest an entry.
+pub const FORGET_MATCH_THRESHOLD: f64 = 0.6;
+
+/// First-person words ignored when matching forget targets, since notes are
+/// stored as "User's ..." rather than "my ...".
+const FORGET_IGNORED_WORDS: &[&str] = &["i", "i'm", "me", "my", "mine"];
+
 /// Extract memorable content from a message that starts with a "remember" trigger.
 /// Returns `None` if the message doesn't match or should be rejected.
 pub fn extract_remember_content(message: &str) -> Option<String> {
@@ -46,6 +74,94 @@ pub fn extract_remember_content(message: &str) -> Option<String> {
     None
 }
 
+/// Extract the memory to forget from a message that starts with a "forget"
+/// trigger. Returns `None` if the message doesn't match or the target is too
+/// short or too broad to pick out a single memory.
+pub fn extract_forget_content(message: &str) -> Option<String> {
+    let lower = message.to_lowercase();
+    let trimmed = lower.trim();
+
+    for pattern in FORGET_PATTERNS {
+        if let Some(rest) = trimmed.strip_prefix(pattern) {
+            let content = rest.trim().trim_end_matches(['.', '!']);
+            if content.len() < MIN_CONTENT_LEN || content.ends_with('?') {
+                return None;
+            }
+            let broad = BROAD_FORGET_TARGETS.iter().any(|t| {
+                content == *t
+                    || content
+                        .strip_prefix(t)
+                        .is_some_and(|r| r.starts_with(' ') || r.starts_with(','))
+            });
+            if broad || content.split_whitespace().count() < 2 {
+                return None;
+            }
+            // FORGET_PATTERNS are ASCII, so the prefix length matches the original
+            let original_trimmed = message.trim();
+            let original_rest = original_trimmed
+                .get(pattern.len()..)
+                .unwrap_or(original_trimmed);
+            return Some(
+                original_rest
+                    .trim()
+                    .trim_end_matches(['.', '!'])
+                    .to_string(),
+            );
+        }
+    }
+
+    None
+}
+
+/// How well a stored memory `entry` matches a forget `target`, from 0 to 1.
+/// An entry containing every word of the target scores 1; otherwise this is
+/// the Jaccard similarity. Punctuation, first-person words and a leading
+/// `"- "` are ignored.
+pub fn forget_match_score(target: &str, entry: &str) -> f64 {
+    let entry = entry.trim();
+    let entry = entry.strip_prefix("- ").unwrap_or(entry);
+    let target_words = forget_words(target);
+    let entry_words = forget_words(entry);
+    if target_words.is_empty() || entry_words.is_empty() {
+        return 0.0;
+    }
+    if target_words.is_subset(&entry_words) {
+        return 1.0;
+    }
+    let intersection = target_words.intersection(&entry_words).count();
+    let union = target_words.union(&entry_words).count();
+    intersection as f64 / union as f64
+}
+
+/// Pick the first entry containing every word of a forget `target`. Partial
+/// overlaps never count: "my wifi password is abc123" must not delete
+/// "my wifi password is hunter2".
+pub fn best_forget_match<'a>(
+    target: &str,
+    entries: &'a [(i64, String)],
+) -> Option<&'a (i64, String)> {
+    entries
+        .iter()
+        .find(|entry| forget_match_score(target, &entry.1) >= 1.0)
+}
+
+/// Pick the closest entry to a forget `target` that scores at least
+/// [`FORGET_MATCH_THRESHOLD`], for asking the user to confirm when
+/// [`best_forget_match`] finds nothing. Ties go to the earliest entry.
+pub fn closest_forget_candidate<'a>(
+    target: &str,
+    entries: &'a [(i64, String)],
+) -> Option<&'a (i64, String)> {
+    let mut best: Option<(&(i64, String), f64)> = None;
+    for entry in entries {
+        let score = forget_match_score(target, &entry.1);
+        if score >= FORGET_MATCH_THRESHOLD && best.is_none_or(|(_, s)| score > s) {
+            best = Some((entry, score));
+        }
+    }
+    best.map(|(entry, _)| entry)
+}
+
 /// Compute Jaccard similarity between two strings using word-level unigrams.
 pub fn jaccard_similarity(a: &str, b: &str) -> f64 {
     let words_a = word_set(a);
@@ -86,5 +202,21 @@ fn word_set(text: &str) -> HashSet<String> {
     text.split_whitespace().map(str::to_lowercase).collect()
 }
 
+fn normalized_words(text: &str) -> HashSet<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+fn forget_words(text: &str) -> HashSet<String> {
+    let mut words = normalized_words(text);
+    words.retain(|w| !FORGET_IGNORED_WORDS.contains(&w.as_str()));
+    words
+}
+
 #[cfg(test)]
 mod tests;
@@ -96,3 +96,67 @@ fn test_jaccard_single_word() {
     let sim = jaccard_similarity("hello", "world");
     assert!((sim - 0.0).abs() < f64::EPSILON);
 }
+
+#[test]
+fn test_extract_forget_content() {
+    assert_eq!(
+        extract_forget_content("Forget that my favorite color is Blue."),
+        Some("my favorite color is Blue".to_string())
+    );
+    assert_eq!(
+        extract_forget_content("please forget the gate code for the shed"),
+        Some("the gate code for the shed".to_string())
+    );
+    assert_eq!(
+        extract_forget_content("forget about my dentist appointment"),
+        Some("my dentist appointment".to_string())
+    );
+}
+
+#[test]
+fn test_extract_forget_rejects_broad_or_vague_targets() {
+    assert_eq!(extract_forget_content("forget about everything"), None);
+    assert_eq!(
+        extract_forget_content("forget about everything you know"),
+        None
+    );
+    assert_eq!(extract_forget_content("please forget all of it!"), None);
+    assert_eq!(extract_forget_content("forget that password"), None);
+    assert_eq!(extract_forget_content("forget it"), None);
+    assert_eq!(
+        extract_forget_content("forget that the meeting moved?"),
+        None
+    );
+    assert_eq!(
+        extract_forget_content("don't forget the milk at the store"),
+        None
+    );
+}
+
+#[test]
+fn test_best_forget_match() {
+    let entries = vec![
+        (1, "- User's favorite color is blue.".to_string()),
+        (2, "User works at Acme on the billing team".to_string()),
+        (3, "User's favorite food is ramen".to_string()),
+    ];
+    let hit = best_forget_match("favorite color is blue", &entries);
+    assert_eq!(hit.map(|e| e.0), Some(1));
+    let hit = best_forget_match("my favorite color is blue", &entries);
+    assert_eq!(hit.map(|e| e.0), Some(1));
+    // "work" is not "works": close, but only a confirmation candidate
+    assert!(best_forget_match("I work at Acme on the billing team", &entries).is_none());
+    let hit = closest_forget_candidate("I work at Acme on the billing team", &entries);
+    assert_eq!(hit.map(|e| e.0), Some(2));
+    assert!(best_forget_match("the weather in Paris", &entries).is_none());
+    assert!(closest_forget_candidate("the weather in Paris", &entries).is_none());
+}
+
+#[test]
+fn test_best_forget_match_keeps_near_miss() {
+    let entries = vec![(1, "- my wifi password is hunter2".to_string())];
+    let target = extract_forget_content("forget that my wifi password is abc123").unwrap();
+    assert!(best_forget_match(&target, &entries).is_none());
+    let hit = closest_forget_candidate(&target, &entries);
+    assert_eq!(hit.map(|e| e.0), Some(1));
+}
@@ -1,9 +1,9 @@
 use crate::anthropic_common;
 use crate::errors::ProviderErrorHandler;
 use crate::{PROVIDER_REQUEST_TIMEOUT_SECS, provider_http_client};
-use anyhow::{Context, Result};
+use anyhow::Result;
 use async_trait::async_trait;
-use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse};
+use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse, StreamSink};
 use reqwest::Client;
 use serde_json::json;
 use std::time::Duration;
@@ -63,9 +63,9 @@ impl AnthropicProvider {
     }
 }
 
-#[async_trait]
-impl LLMProvider for AnthropicProvider {
-    async fn chat(&self, req: &ChatRequest) -> Result<LLMResponse> {
+impl AnthropicProvider {
+    /// Send a Messages API request; streamed to `sink` when one is given.
+    async fn send(&self, req: &ChatRequest, sink: Option<&StreamSink>) -> Result<LLMResponse> {
         debug!(
             "anthropic chat: model={}",
             req.model.as_deref().unwrap_or(&self.default_model)
@@ -120,6 +120,10 @@ impl LLMProvider for AnthropicProvider {
                 }
             };
         }
+        anthropic_common::apply_thinking(&mut payload, req.reasoning_effort);
+        if sink.is_some() {
+            payload["stream"] = json!(true);
+        }
 
         let mut req_builder = self
             .client
@@ -136,17 +140,31 @@ impl LLMProvider for AnthropicProvider {
             .timeout(Duration::from_secs(PROVIDER_REQUEST_TIMEOUT_SECS))
             .send()
             .await
-            .context("Failed to send request to Anthropic API")?;
-
-        let json = ProviderErrorHandler::check_response(resp, "Anthropic").await?;
+            .map_err(|e| ProviderErrorHandler::network_error(&e, "Anthropic"))?;
 
-        let response = anthropic_common::parse_response(&json);
+        let response = if let Some(sink) = sink {
+            anthropic_common::read_stream(resp, "Anthropic", sink).await?
+        } else {
+            let json = ProviderErrorHandler::check_response(resp, "Anthropic").await?;
+            anthropic_common::parse_response(&json)
+        };
         debug!(
             "anthropic chat complete: input_tokens={:?}, output_tokens={:?}",
             response.input_tokens, response.output_tokens
         );
         Ok(response)
     }
+}
+
+#[async_trait]
+impl LLMProvider for AnthropicProvider {
+    async fn chat(&self, req: &ChatRequest) -> Result<LLMResponse> {
+        self.send(req, None).await
+    }
+
+    async fn chat_stream(&self, req: &ChatRequest, sink: StreamSink) -> Result<LLMResponse> {
+        self.send(req, Some(&sink)).await
+    }
 
     fn default_model(&self) -> &str {
         &self.default_model
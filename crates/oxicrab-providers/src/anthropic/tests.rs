@@ -152,3 +152,81 @@ async fn test_chat_with_system_message() {
 
     assert_eq!(result.content.unwrap(), "I am a helpful assistant.");
 }
+
+#[tokio::test]
+async fn test_chat_stream_pushes_text_and_rebuilds_response() {
+    let events = [
+        json!({"type": "message_start", "message": {"role": "assistant", "content": [],
+            "usage": {"input_tokens": 12, "output_tokens": 1}}}),
+        json!({"type": "content_block_start", "index": 0,
+            "content_block": {"type": "text", "text": ""}}),
+        json!({"type": "content_block_delta", "index": 0,
+            "delta": {"type": "text_delta", "text": "Checking "}}),
+        json!({"type": "content_block_delta", "index": 0,
+            "delta": {"type": "text_delta", "text": "the weather."}}),
+        json!({"type": "content_block_stop", "index": 0}),
+        json!({"type": "content_block_start", "index": 1,
+            "content_block": {"type": "tool_use", "id": "tc_1", "name": "weather", "input": {}}}),
+        json!({"type": "content_block_delta", "index": 1,
+            "delta": {"type": "input_json_delta", "partial_json": "{\"city\": "}}),
+        json!({"type": "content_block_delta", "index": 1,
+            "delta": {"type": "input_json_delta", "partial_json": "\"NYC\"}"}}),
+        json!({"type": "content_block_stop", "index": 1}),
+        json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"},
+            "usage": {"output_tokens": 20}}),
+        json!({"type": "message_stop"}),
+    ];
+    let body: String = events
+        .iter()
+        .map(|e| format!("event: {}\ndata: {e}\n\n", e["type"].as_str().unwrap()))
+        .collect();
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(wiremock::matchers::body_partial_json(
+            json!({"stream": true}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .mount(&server)
+        .await;
+
+    let provider = AnthropicProvider::with_base_url("test_key".to_string(), None, server.uri());
+    let (sink, text) = StreamSink::new();
+    let result = provider
+        .chat_stream(&simple_chat_request("Weather in NYC?"), sink)
+        .await
+        .unwrap();
+
+    assert_eq!(*text.borrow(), "Checking the weather.");
+    assert_eq!(result.content.as_deref(), Some("Checking the weather."));
+    assert_eq!(result.tool_calls[0].name, "weather");
+    assert_eq!(result.tool_calls[0].arguments, json!({"city": "NYC"}));
+    assert_eq!(result.input_tokens, Some(12));
+    assert_eq!(result.output_tokens, Some(20));
+    assert_eq!(result.finish_reason.as_deref(), Some("tool_use"));
+}
+
+#[tokio::test]
+async fn test_chat_stream_error_event_is_transient() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "event: error\ndata: {\"type\": \"error\", \"error\": {\"type\": \"overloaded_error\", \"message\": \"Overloaded\"}}\n\n",
+            "text/event-stream",
+        ))
+        .mount(&server)
+        .await;
+
+    let provider = AnthropicProvider::with_base_url("test_key".to_string(), None, server.uri());
+    let (sink, _text) = StreamSink::new();
+    let err = provider
+        .chat_stream(&simple_chat_request("Hi"), sink)
+        .await
+        .unwrap_err();
+    assert!(
+        oxicrab_core::errors::ProviderError::find(&err)
+            .is_some_and(oxicrab_core::errors::ProviderError::is_retryable),
+        "error: {err}"
+    );
+}
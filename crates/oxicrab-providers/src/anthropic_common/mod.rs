@@ -1,7 +1,11 @@
-use oxicrab_core::providers::base::{LLMResponse, Message, ToolCallRequest, ToolDefinition};
+use crate::errors::ProviderErrorHandler;
+use oxicrab_core::providers::base::{
+    LLMResponse, Message, ReasoningEffort, StreamSink, ToolCallRequest, ToolDefinition,
+};
 use serde::Serialize;
 use serde_json::{Value, json};
-use tracing::warn;
+use std::collections::HashMap;
+use tracing::{debug, warn};
 
 #[derive(Debug, Serialize)]
 pub struct AnthropicMessage {
@@ -203,6 +207,54 @@ pub fn system_to_content_blocks(system: &str) -> Value {
     }])
 }
 
+/// Whether the request continues a tool-use turn whose assistant message has
+/// no thinking block. Thinking cannot be switched on in the middle of a turn.
+fn continues_turn_without_thinking(messages: &Value) -> bool {
+    let Some(messages) = messages.as_array() else {
+        return false;
+    };
+    let [.., assistant, last] = messages.as_slice() else {
+        return false;
+    };
+    let is_tool_result = last["content"]
+        .as_array()
+        .is_some_and(|blocks| blocks.iter().any(|b| b["type"] == "tool_result"));
+    let starts_with_thinking = matches!(
+        assistant["content"][0]["type"].as_str(),
+        Some("thinking" | "redacted_thinking")
+    );
+    is_tool_result && assistant["role"] == "assistant" && !starts_with_thinking
+}
+
+/// Enable extended thinking on a built request payload. The thinking budget
+/// is added on top of `max_tokens` so it does not eat into the answer, and
+/// `temperature` is removed because the API only accepts the default with
+/// thinking. Forced tool use and tool-use turns started without thinking are
+/// incompatible with it, so such requests are sent without.
+pub fn apply_thinking(payload: &mut Value, effort: Option<ReasoningEffort>) {
+    let Some(effort) = effort else {
+        return;
+    };
+    if matches!(
+        payload["tool_choice"]["type"].as_str(),
+        Some("any" | "tool")
+    ) {
+        debug!("anthropic: forced tool choice, sending request without extended thinking");
+        return;
+    }
+    if continues_turn_without_thinking(&payload["messages"]) {
+        debug!("anthropic: turn started without thinking, sending request without it");
+        return;
+    }
+    let budget = effort.thinking_budget_tokens();
+    let max_tokens = payload["max_tokens"].as_u64().unwrap_or_default();
+    payload["max_tokens"] = json!(max_tokens + u64::from(budget));
+    payload["thinking"] = json!({"type": "enabled", "budget_tokens": budget});
+    if let Some(obj) = payload.as_object_mut() {
+        obj.remove("temperature");
+    }
+}
+
 /// Parse an Anthropic API response into a generic [`LLMResponse`].
 pub fn parse_response(json: &Value) -> LLMResponse {
     let content = json["content"].as_array().and_then(|arr| {
@@ -306,5 +358,130 @@ pub fn parse_response(json: &Value) -> LLMResponse {
     }
 }
 
+/// Rebuilds a Messages API response from the events of a streamed one
+/// (`"stream": true`), so it can go through [`parse_response`].
+#[derive(Default)]
+pub struct StreamAccumulator {
+    message: Value,
+    blocks: Vec<Value>,
+    /// Partial JSON of `tool_use` inputs, by block index.
+    tool_inputs: HashMap<usize, String>,
+    has_text: bool,
+}
+
+impl StreamAccumulator {
+    /// Apply one event. Returns the text it adds to the answer, if any.
+    pub fn apply(&mut self, event: &Value) -> anyhow::Result<Option<String>> {
+        let index = event["index"].as_u64().unwrap_or_default() as usize;
+        match event["type"].as_str() {
+            Some("message_start") => self.message = event["message"].clone(),
+            Some("content_block_start") => {
+                let block = event["content_block"].clone();
+                // Text blocks are joined with a blank line, as in parse_response()
+                let separator = (block["type"] == "text" && self.has_text).then(|| "\n\n".into());
+                if self.blocks.len() <= index {
+                    self.blocks.resize(index + 1, Value::Null);
+                }
+                self.blocks[index] = block;
+                return Ok(separator);
+            }
+            Some("content_block_delta") => {
+                let Some(block) = self.blocks.get_mut(index) else {
+                    return Ok(None);
+                };
+                let delta = &event["delta"];
+                match delta["type"].as_str() {
+                    Some("text_delta") => {
+                        let text = delta["text"].as_str().unwrap_or_default();
+                        append(block, "text", text);
+                        self.has_text |= !text.is_empty();
+                        return Ok(Some(text.to_string()));
+                    }
+                    Some("thinking_delta") => {
+                        append(
+                            block,
+                            "thinking",
+                            delta["thinking"].as_str().unwrap_or_default(),
+                        );
+                    }
+                    Some("signature_delta") => block["signature"] = delta["signature"].clone(),
+                    Some("input_json_delta") => self
+                        .tool_inputs
+                        .entry(index)
+                        .or_default()
+                        .push_str(delta["partial_json"].as_str().unwrap_or_default()),
+                    _ => {}
+                }
+            }
+            Some("content_block_stop") => {
+                if let (Some(input), Some(block)) =
+                    (self.tool_inputs.remove(&index), self.blocks.get_mut(index))
+                    && !input.trim().is_empty()
+                {
+                    block["input"] = serde_json::from_str(&input).unwrap_or_else(|e| {
+                        warn!("unparseable streamed tool input: {}", e);
+                        json!({})
+                    });
+                }
+            }
+            Some("message_delta") => {
+                if let Some(reason) = event["delta"]["stop_reason"].as_str() {
+                    self.message["stop_reason"] = json!(reason);
+                }
+                if let Some(output) = event["usage"]["output_tokens"].as_u64() {
+                    self.message["usage"]["output_tokens"] = json!(output);
+                }
+            }
+            Some("error") => {
+                // Mid-stream errors are server-side (usually overloaded_error)
+                let status = if event["error"]["type"] == "overloaded_error" {
+                    529
+                } else {
+                    500
+                };
+                return Err(
+                    ProviderErrorHandler::parse_api_error(status, &event.to_string())
+                        .unwrap_err()
+                        .into(),
+                );
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// The complete response, in the shape of a non-streamed one.
+    pub fn finish(mut self) -> Value {
+        self.blocks.retain(|block| !block.is_null());
+        self.message["content"] = Value::Array(self.blocks);
+        self.message
+    }
+}
+
+fn append(block: &mut Value, field: &str, text: &str) {
+    let mut current = block[field].as_str().unwrap_or_default().to_string();
+    current.push_str(text);
+    block[field] = json!(current);
+}
+
+/// Read a streamed Messages API response, pushing its text to `sink`.
+pub async fn read_stream(
+    resp: reqwest::Response,
+    provider: &str,
+    sink: &StreamSink,
+) -> anyhow::Result<LLMResponse> {
+    let resp = ProviderErrorHandler::check_http_status(resp, provider).await?;
+    let mut acc = StreamAccumulator::default();
+    crate::sse::read_events(resp, provider, |data| {
+        let event: Value = serde_json::from_str(data)?;
+        if let Some(text) = acc.apply(&event)? {
+            sink.push(&text);
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(parse_response(&acc.finish()))
+}
+
 #[cfg(test)]
 mod tests;
@@ -527,3 +527,54 @@ fn test_thinking_roundtrip_parse_then_convert() {
     assert_eq!(content[1]["type"], "text");
     assert_eq!(content[1]["text"], "The result is 7.");
 }
+
+#[test]
+fn test_apply_thinking_adds_budget_and_drops_temperature() {
+    let mut payload = json!({"model": "m", "max_tokens": 8192, "temperature": 0.7});
+    apply_thinking(&mut payload, Some(ReasoningEffort::Medium));
+    assert_eq!(
+        payload["thinking"],
+        json!({"type": "enabled", "budget_tokens": 4096})
+    );
+    assert_eq!(payload["max_tokens"], 8192 + 4096);
+    assert!(payload.get("temperature").is_none());
+
+    let mut payload = json!({"max_tokens": 8192, "temperature": 0.7});
+    apply_thinking(&mut payload, None);
+    assert!(payload.get("thinking").is_none());
+    assert_eq!(payload["temperature"], 0.7);
+}
+
+#[test]
+fn test_apply_thinking_skipped_for_forced_tool_choice() {
+    let mut payload = json!({"max_tokens": 1024, "tool_choice": {"type": "any"}});
+    apply_thinking(&mut payload, Some(ReasoningEffort::High));
+    assert!(payload.get("thinking").is_none());
+    assert_eq!(payload["max_tokens"], 1024);
+
+    let mut payload = json!({"max_tokens": 1024, "tool_choice": {"type": "auto"}});
+    apply_thinking(&mut payload, Some(ReasoningEffort::Low));
+    assert_eq!(payload["thinking"]["budget_tokens"], 1024);
+}
+
+#[test]
+fn test_apply_thinking_skipped_mid_turn_without_thinking() {
+    let mut payload = json!({
+        "max_tokens": 1024,
+        "messages": [
+            {"role": "user", "content": "list files"},
+            {"role": "assistant", "content": [{"type": "tool_use", "id": "t1", "name": "ls", "input": {}}]},
+            {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "t1", "content": "a.txt"}]}
+        ]
+    });
+    apply_thinking(&mut payload, Some(ReasoningEffort::High));
+    assert!(payload.get("thinking").is_none());
+
+    // The turn already thinks, so thinking stays on
+    payload["messages"][1]["content"] = json!([
+        {"type": "thinking", "thinking": "use ls", "signature": "sig"},
+        {"type": "tool_use", "id": "t1", "name": "ls", "input": {}}
+    ]);
+    apply_thinking(&mut payload, Some(ReasoningEffort::High));
+    assert!(payload.get("thinking").is_some());
+}
@@ -3,7 +3,8 @@ use crate::errors::ProviderErrorHandler;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use oxicrab_core::credential_store::{self, OAuthTokenStore};
-use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse};
+use oxicrab_core::errors::{OxicrabError, ProviderError};
+use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse, StreamSink};
 use reqwest::Client;
 use serde_json::{Value, json};
 use std::path::{Path, PathBuf};
@@ -307,7 +308,7 @@ impl AnthropicOAuthProvider {
             .json(payload)
             .send()
             .await
-            .context("failed to send request to Anthropic OAuth API")
+            .map_err(|e| ProviderErrorHandler::network_error(&e, "Anthropic OAuth"))
     }
 
     async fn save_credentials(&self) {
@@ -547,9 +548,9 @@ impl AnthropicOAuthProvider {
     }
 }
 
-#[async_trait]
-impl LLMProvider for AnthropicOAuthProvider {
-    async fn chat(&self, req: &ChatRequest) -> Result<LLMResponse> {
+impl AnthropicOAuthProvider {
+    /// Send a Messages API request; streamed to `sink` when one is given.
+    async fn send(&self, req: &ChatRequest, sink: Option<&StreamSink>) -> Result<LLMResponse> {
         let model = req.model.as_deref().map(|m| {
             // Strip provider prefix (e.g. "anthropic/claude-opus-4-6" -> "claude-opus-4-6")
             if m.contains('/') {
@@ -612,6 +613,10 @@ impl LLMProvider for AnthropicOAuthProvider {
                 }
             };
         }
+        anthropic_common::apply_thinking(&mut payload, req.reasoning_effort);
+        if sink.is_some() {
+            payload["stream"] = json!(true);
+        }
 
         // Try the request, and on 401 refresh the token and retry once.
         // This handles clock skew and stale expires_at timestamps that
@@ -626,10 +631,7 @@ impl LLMProvider for AnthropicOAuthProvider {
                     Ok(()) => {
                         let new_token = self.access_token.lock().await.clone();
                         let retry_resp = self.send_chat_request(&new_token, &payload).await?;
-                        let json =
-                            ProviderErrorHandler::check_response(retry_resp, "AnthropicOAuth")
-                                .await?;
-                        return Ok(anthropic_common::parse_response(&json));
+                        return Self::read_response(retry_resp, sink).await;
                     }
                     Err(e) => {
                         warn!("token refresh failed after 401: {}", e);
@@ -637,15 +639,38 @@ impl LLMProvider for AnthropicOAuthProvider {
                 }
             }
             // No refresh token or refresh failed — surface as auth error
-            anyhow::bail!(
+            return Err(OxicrabError::from(ProviderError::Auth(
                 "OAuth token expired and refresh failed. \
                  Re-authenticate with: oxicrab auth login"
-            );
+                    .to_string(),
+            ))
+            .into());
         }
 
+        Self::read_response(resp, sink).await
+    }
+
+    async fn read_response(
+        resp: reqwest::Response,
+        sink: Option<&StreamSink>,
+    ) -> Result<LLMResponse> {
+        if let Some(sink) = sink {
+            return anthropic_common::read_stream(resp, "AnthropicOAuth", sink).await;
+        }
         let json = ProviderErrorHandler::check_response(resp, "AnthropicOAuth").await?;
         Ok(anthropic_common::parse_response(&json))
     }
+}
+
+#[async_trait]
+impl LLMProvider for AnthropicOAuthProvider {
+    async fn chat(&self, req: &ChatRequest) -> Result<LLMResponse> {
+        self.send(req, None).await
+    }
+
+    async fn chat_stream(&self, req: &ChatRequest, sink: StreamSink) -> Result<LLMResponse> {
+        self.send(req, Some(&sink)).await
+    }
 
     fn default_model(&self) -> &str {
         &self.default_model
@@ -1,8 +1,12 @@
 use async_trait::async_trait;
 use oxicrab_core::config::schema::CircuitBreakerConfig;
-use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse};
+use oxicrab_core::errors::ProviderError;
+use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse, StreamSink};
+use oxicrab_core::providers::circuit_state::{
+    CircuitHealth, CircuitSnapshot, CircuitStateRow, CircuitStateStore,
+};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 use tracing::{info, warn};
 
@@ -13,6 +17,17 @@ enum CircuitState {
     HalfOpen { successes: u32 },
 }
 
+impl CircuitState {
+    /// Label stored in the `provider_health` table.
+    fn persisted_label(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open { .. } => "open",
+            Self::HalfOpen { .. } => "half_open",
+        }
+    }
+}
+
 impl std::fmt::Display for CircuitState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -32,10 +47,24 @@ struct BreakerState {
     active_probes: u32,
 }
 
+impl BreakerState {
+    /// The persisted part of the state, used to skip redundant writes.
+    fn snapshot(&self) -> (&'static str, u32) {
+        (self.state.persisted_label(), self.consecutive_failures)
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+}
+
 pub struct CircuitBreakerProvider {
     inner: Arc<dyn LLMProvider>,
     breaker: Mutex<BreakerState>,
     config: CircuitBreakerConfig,
+    store: Option<Arc<dyn CircuitStateStore>>,
 }
 
 impl CircuitBreakerProvider {
@@ -43,21 +72,125 @@ impl CircuitBreakerProvider {
         inner: Arc<dyn LLMProvider>,
         config: &CircuitBreakerConfig,
     ) -> Arc<dyn LLMProvider> {
+        Self::wrap_with_store(inner, config, None)
+    }
+
+    /// Like [`wrap`](Self::wrap), but restores the breaker state saved in
+    /// `store` (keyed by the inner provider's default model) and saves every
+    /// state transition back to it.
+    pub fn wrap_with_store(
+        inner: Arc<dyn LLMProvider>,
+        config: &CircuitBreakerConfig,
+        store: Option<Arc<dyn CircuitStateStore>>,
+    ) -> Arc<dyn LLMProvider> {
+        Self::with_store(inner, config, store)
+    }
+
+    /// Like [`wrap_with_store`](Self::wrap_with_store), but keeps the concrete
+    /// type so the caller can also hand it out as a [`CircuitHealth`].
+    pub fn with_store(
+        inner: Arc<dyn LLMProvider>,
+        config: &CircuitBreakerConfig,
+        store: Option<Arc<dyn CircuitStateStore>>,
+    ) -> Arc<Self> {
         // Clamp half_open_probes to at least 1 — zero would permanently lock
         // the circuit in Open state since no probes could ever succeed.
         let mut config = config.clone();
         config.half_open_probes = config.half_open_probes.max(1);
-        Arc::new(Self {
-            inner,
-            breaker: Mutex::new(BreakerState {
+        let state = store.as_deref().map_or(
+            BreakerState {
                 state: CircuitState::Closed,
                 consecutive_failures: 0,
                 active_probes: 0,
-            }),
+            },
+            |store| Self::restore_state(store, inner.default_model(), &config),
+        );
+        Arc::new(Self {
+            inner,
+            breaker: Mutex::new(state),
             config,
+            store,
         })
     }
 
+    /// Rebuild the in-memory state from a persisted row. An open circuit whose
+    /// recovery timeout already elapsed while the process was down is treated
+    /// as stale and starts closed.
+    fn restore_state(
+        store: &dyn CircuitStateStore,
+        key: &str,
+        config: &CircuitBreakerConfig,
+    ) -> BreakerState {
+        let mut restored = BreakerState {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            active_probes: 0,
+        };
+        let row = match store.load_circuit_state(key) {
+            Ok(Some(row)) => row,
+            Ok(None) => return restored,
+            Err(e) => {
+                warn!("failed to load circuit breaker state for {}: {}", key, e);
+                return restored;
+            }
+        };
+        match row.state.as_str() {
+            "open" => {
+                let age_ms = now_ms()
+                    .saturating_sub(row.opened_at_ms.unwrap_or(0))
+                    .max(0);
+                let age = Duration::from_millis(age_ms.unsigned_abs());
+                if age.as_secs() >= config.recovery_timeout_secs {
+                    info!(
+                        "ignoring stale persisted open circuit for {} (opened {}s ago)",
+                        key,
+                        age.as_secs()
+                    );
+                    return restored;
+                }
+                warn!(
+                    "restored open circuit for {} ({}s until recovery probe)",
+                    key,
+                    config.recovery_timeout_secs - age.as_secs()
+                );
+                restored.state = CircuitState::Open {
+                    since: Instant::now().checked_sub(age).unwrap_or_else(Instant::now),
+                };
+            }
+            "half_open" => restored.state = CircuitState::HalfOpen { successes: 0 },
+            _ => {}
+        }
+        restored.consecutive_failures = row.consecutive_failures;
+        restored
+    }
+
+    /// Save the breaker state if it changed since `before`. Storage errors are
+    /// logged and otherwise ignored.
+    fn persist_if_changed(&self, before: (&'static str, u32), breaker: &BreakerState) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        if breaker.snapshot() == before {
+            return;
+        }
+        let opened_at_ms = match &breaker.state {
+            CircuitState::Open { since } => {
+                let elapsed = i64::try_from(since.elapsed().as_millis()).unwrap_or(0);
+                Some(now_ms() - elapsed)
+            }
+            _ => None,
+        };
+        let row = CircuitStateRow {
+            provider: self.inner.default_model().to_string(),
+            state: breaker.state.persisted_label().to_string(),
+            consecutive_failures: breaker.consecutive_failures,
+            opened_at_ms,
+        };
+        if let Err(e) = store.save_circuit_state(&row) {
+            warn!("failed to persist circuit breaker state: {}", e);
+        }
+    }
+
     fn is_transient(error: &str) -> bool {
         // Classify error transience via string pattern matching.
         // Known non-transient patterns (auth, model, config) → not retryable.
@@ -116,10 +249,11 @@ impl CircuitBreakerProvider {
             CircuitState::HalfOpen { successes } => {
                 // Limit concurrent probes: only allow half_open_probes in-flight at once
                 if breaker.active_probes + successes >= self.config.half_open_probes {
-                    Err(anyhow::anyhow!(
+                    Err(ProviderError::Unavailable(format!(
                         "Circuit breaker is half-open with {} active probe(s). Waiting for results.",
                         breaker.active_probes
                     ))
+                    .into())
                 } else {
                     breaker.active_probes += 1;
                     Ok(())
@@ -132,14 +266,17 @@ impl CircuitBreakerProvider {
                         "circuit breaker transitioning Open -> HalfOpen after {}s",
                         elapsed.as_secs()
                     );
+                    let before = breaker.snapshot();
                     breaker.state = CircuitState::HalfOpen { successes: 0 };
                     breaker.active_probes = 1; // This request is the first probe
+                    self.persist_if_changed(before, &breaker);
                     Ok(())
                 } else {
-                    Err(anyhow::anyhow!(
+                    Err(ProviderError::Unavailable(format!(
                         "Circuit breaker is open ({}s remaining). Provider appears to be down.",
                         self.config.recovery_timeout_secs - elapsed.as_secs()
                     ))
+                    .into())
                 }
             }
         }
@@ -147,6 +284,7 @@ impl CircuitBreakerProvider {
 
     async fn record_success(&self) {
         let mut breaker = self.breaker.lock().await;
+        let before = breaker.snapshot();
         breaker.consecutive_failures = 0;
         if let CircuitState::HalfOpen { successes } = breaker.state {
             breaker.active_probes = breaker.active_probes.saturating_sub(1);
@@ -164,6 +302,7 @@ impl CircuitBreakerProvider {
                 };
             }
         }
+        self.persist_if_changed(before, &breaker);
     }
 
     async fn record_failure(&self, is_transient: bool) {
@@ -171,6 +310,7 @@ impl CircuitBreakerProvider {
             return;
         }
         let mut breaker = self.breaker.lock().await;
+        let before = breaker.snapshot();
         breaker.consecutive_failures += 1;
         let failures = breaker.consecutive_failures;
 
@@ -196,6 +336,28 @@ impl CircuitBreakerProvider {
             }
             CircuitState::Open { .. } => {}
         }
+        self.persist_if_changed(before, &breaker);
+    }
+}
+
+#[async_trait]
+impl CircuitHealth for CircuitBreakerProvider {
+    async fn state_snapshot(&self) -> CircuitSnapshot {
+        let breaker = self.breaker.lock().await;
+        let next_probe_secs = match &breaker.state {
+            CircuitState::Open { since } => Some(
+                self.config
+                    .recovery_timeout_secs
+                    .saturating_sub(since.elapsed().as_secs()),
+            ),
+            _ => None,
+        };
+        CircuitSnapshot {
+            provider: self.inner.default_model().to_string(),
+            state: breaker.state.persisted_label(),
+            consecutive_failures: breaker.consecutive_failures,
+            next_probe_secs,
+        }
     }
 }
 
@@ -203,8 +365,33 @@ impl CircuitBreakerProvider {
 impl LLMProvider for CircuitBreakerProvider {
     async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
         self.should_allow().await?;
+        let result = self.inner.chat(req).await;
+        self.record(result).await
+    }
 
-        match self.inner.chat(req).await {
+    async fn chat_stream(
+        &self,
+        req: &ChatRequest,
+        sink: StreamSink,
+    ) -> anyhow::Result<LLMResponse> {
+        self.should_allow().await?;
+        let result = self.inner.chat_stream(req, sink).await;
+        self.record(result).await
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+}
+
+impl CircuitBreakerProvider {
+    /// Update the breaker with the outcome of a request and pass it on.
+    async fn record(&self, result: anyhow::Result<LLMResponse>) -> anyhow::Result<LLMResponse> {
+        match result {
             Ok(response) => {
                 self.record_success().await;
                 Ok(response)
@@ -223,14 +410,6 @@ impl LLMProvider for CircuitBreakerProvider {
             }
         }
     }
-
-    fn default_model(&self) -> &str {
-        self.inner.default_model()
-    }
-
-    async fn warmup(&self) -> anyhow::Result<()> {
-        self.inner.warmup().await
-    }
 }
 
 #[cfg(test)]
@@ -55,6 +55,7 @@ fn test_config() -> CircuitBreakerConfig {
         failure_threshold: 3,
         recovery_timeout_secs: 1,
         half_open_probes: 2,
+        persist_state: false,
     }
 }
 
@@ -104,6 +105,7 @@ async fn test_open_rejects_immediately() {
         failure_threshold: 1,
         recovery_timeout_secs: 60, // long timeout so it stays open
         half_open_probes: 1,
+        persist_state: false,
     };
     let provider = CircuitBreakerProvider::wrap(inner, &config);
 
@@ -113,12 +115,37 @@ async fn test_open_rejects_immediately() {
     // Should reject immediately without reaching inner provider
     let result = provider.chat(&make_request()).await;
     assert!(result.is_err());
-    assert!(
-        result
-            .unwrap_err()
-            .to_string()
-            .contains("Circuit breaker is open")
-    );
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("Circuit breaker is open"));
+    assert!(matches!(
+        oxicrab_core::errors::ProviderError::find(&err),
+        Some(oxicrab_core::errors::ProviderError::Unavailable(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_state_snapshot_reports_open_circuit() {
+    let responses = vec![Err("timeout error".to_string())];
+    let inner = MockProvider::with_responses(responses);
+    let config = CircuitBreakerConfig {
+        enabled: true,
+        failure_threshold: 1,
+        recovery_timeout_secs: 60,
+        half_open_probes: 1,
+        persist_state: false,
+    };
+    let provider = CircuitBreakerProvider::with_store(inner, &config, None);
+
+    let snapshot = provider.state_snapshot().await;
+    assert_eq!(snapshot.state, "closed");
+    assert_eq!(snapshot.consecutive_failures, 0);
+    assert_eq!(snapshot.next_probe_secs, None);
+
+    let _ = provider.chat(&make_request()).await;
+    let snapshot = provider.state_snapshot().await;
+    assert_eq!(snapshot.state, "open");
+    assert_eq!(snapshot.consecutive_failures, 1);
+    assert!(snapshot.next_probe_secs.is_some_and(|s| s > 0 && s <= 60));
 }
 
 #[tokio::test]
@@ -138,6 +165,7 @@ async fn test_half_open_after_timeout() {
         failure_threshold: 3,
         recovery_timeout_secs: 0, // immediate recovery for test
         half_open_probes: 2,
+        persist_state: false,
     };
     let provider = CircuitBreakerProvider::wrap(inner, &config);
 
@@ -171,6 +199,7 @@ async fn test_half_open_success_closes() {
         failure_threshold: 3,
         recovery_timeout_secs: 0,
         half_open_probes: 2,
+        persist_state: false,
     };
     let provider = CircuitBreakerProvider::wrap(inner.clone(), &config);
 
@@ -205,6 +234,7 @@ async fn test_half_open_failure_reopens() {
         failure_threshold: 3,
         recovery_timeout_secs: 0,
         half_open_probes: 2,
+        persist_state: false,
     };
     let provider = CircuitBreakerProvider::wrap(inner, &config);
 
@@ -236,6 +266,7 @@ async fn test_non_transient_errors_dont_trip() {
         failure_threshold: 3,
         recovery_timeout_secs: 60,
         half_open_probes: 2,
+        persist_state: false,
     };
     let provider = CircuitBreakerProvider::wrap(inner, &config);
 
@@ -323,6 +354,7 @@ async fn test_success_resets_counter() {
         failure_threshold: 3,
         recovery_timeout_secs: 60,
         half_open_probes: 2,
+        persist_state: false,
     };
     let provider = CircuitBreakerProvider::wrap(inner, &config);
 
@@ -354,6 +386,7 @@ async fn test_zero_half_open_probes_clamped_to_one() {
         failure_threshold: 3,
         recovery_timeout_secs: 0, // immediate recovery
         half_open_probes: 0,      // would cause permanent lockout without clamping
+        persist_state: false,
     };
     let provider = CircuitBreakerProvider::wrap(mock.clone(), &config);
 
@@ -374,3 +407,111 @@ async fn test_zero_half_open_probes_clamped_to_one() {
     let result = provider.chat(&make_request()).await;
     assert!(result.is_ok(), "should be fully closed after recovery");
 }
+
+#[derive(Default)]
+struct MemoryStateStore {
+    rows: std::sync::Mutex<std::collections::HashMap<String, CircuitStateRow>>,
+}
+
+impl CircuitStateStore for MemoryStateStore {
+    fn load_circuit_state(&self, provider: &str) -> anyhow::Result<Option<CircuitStateRow>> {
+        Ok(self.rows.lock().unwrap().get(provider).cloned())
+    }
+
+    fn save_circuit_state(&self, row: &CircuitStateRow) -> anyhow::Result<()> {
+        self.rows
+            .lock()
+            .unwrap()
+            .insert(row.provider.clone(), row.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_open_state_survives_restart() {
+    let store = Arc::new(MemoryStateStore::default());
+    let config = CircuitBreakerConfig {
+        recovery_timeout_secs: 60,
+        persist_state: true,
+        ..test_config()
+    };
+    let provider = CircuitBreakerProvider::wrap_with_store(
+        MockProvider::with_responses(vec![
+            Err("timeout".into()),
+            Err("timeout".into()),
+            Err("timeout".into()),
+        ]),
+        &config,
+        Some(store.clone()),
+    );
+    for _ in 0..3 {
+        let _ = provider.chat(&make_request()).await;
+    }
+    let row = store.load_circuit_state("mock").unwrap().unwrap();
+    assert_eq!(row.state, "open");
+    assert_eq!(row.consecutive_failures, 3);
+    assert!(row.opened_at_ms.is_some());
+
+    // "Restart": a fresh breaker over a healthy provider stays open
+    let inner = MockProvider::always_ok();
+    let provider = CircuitBreakerProvider::wrap_with_store(
+        inner.clone(),
+        &config,
+        Some(store.clone() as Arc<dyn CircuitStateStore>),
+    );
+    let err = provider.chat(&make_request()).await.unwrap_err();
+    assert!(err.to_string().contains("Circuit breaker is open"));
+    assert_eq!(inner.call_count.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_stale_open_state_is_ignored() {
+    let store = Arc::new(MemoryStateStore::default());
+    store
+        .save_circuit_state(&CircuitStateRow {
+            provider: "mock".to_string(),
+            state: "open".to_string(),
+            consecutive_failures: 5,
+            opened_at_ms: Some(now_ms() - 120_000),
+        })
+        .unwrap();
+    let config = CircuitBreakerConfig {
+        recovery_timeout_secs: 60,
+        persist_state: true,
+        ..test_config()
+    };
+    let inner = MockProvider::always_ok();
+    let provider = CircuitBreakerProvider::wrap_with_store(
+        inner.clone(),
+        &config,
+        Some(store.clone() as Arc<dyn CircuitStateStore>),
+    );
+    assert!(provider.chat(&make_request()).await.is_ok());
+    assert_eq!(inner.call_count.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_restored_failure_count_counts_toward_threshold() {
+    let store = Arc::new(MemoryStateStore::default());
+    store
+        .save_circuit_state(&CircuitStateRow {
+            provider: "mock".to_string(),
+            state: "closed".to_string(),
+            consecutive_failures: 2,
+            opened_at_ms: None,
+        })
+        .unwrap();
+    let provider = CircuitBreakerProvider::wrap_with_store(
+        MockProvider::with_responses(vec![Err("timeout".into())]),
+        &test_config(),
+        Some(store.clone() as Arc<dyn CircuitStateStore>),
+    );
+    // One more failure reaches the threshold of 3
+    let _ = provider.chat(&make_request()).await;
+    let err = provider.chat(&make_request()).await.unwrap_err();
+    assert!(err.to_string().contains("Circuit breaker is open"));
+    assert_eq!(
+        store.load_circuit_state("mock").unwrap().unwrap().state,
+        "open"
+    );
+}
@@ -1,4 +1,4 @@
-use oxicrab_core::errors::OxicrabError;
+use oxicrab_core::errors::{OxicrabError, ProviderError};
 use serde_json::Value;
 use tracing::{error, warn};
 
@@ -19,6 +19,7 @@ impl ProviderErrorHandler {
                 .get("type")
                 .and_then(|v| v.as_str())
                 .unwrap_or("unknown");
+            let error_code = err.get("code").and_then(|v| v.as_str()).unwrap_or_default();
             let error_msg = err
                 .get("message")
                 .and_then(|v| v.as_str())
@@ -27,7 +28,7 @@ impl ProviderErrorHandler {
             // Provide helpful message for model not found errors
             if error_type == "not_found_error" && error_msg.contains("model:") {
                 let model_name = error_msg.replace("model: ", "").trim().to_string();
-                return Err(OxicrabError::Provider {
+                return Err(ProviderError::Api {
                     message: format!(
                         "Model '{model_name}' not found. This model may be deprecated or incorrect.\n\
                             Please update your config file (~/.oxicrab/config.toml) to use a valid model:\n\
@@ -38,35 +39,84 @@ impl ProviderErrorHandler {
                             Or remove the 'model' field from your config to use the default."
                     ),
                     retryable: false,
-                });
+                }
+                .into());
             }
 
-            let retryable = status == 402
-                || status == 429
-                || status == 500
-                || status == 502
-                || status == 503
-                || status == 504
-                || status == 529;
             // Truncate error message to prevent leaking request content back to the LLM
             let safe_msg: String = error_msg.chars().take(500).collect();
-            return Err(OxicrabError::Provider {
-                message: format!("API error ({error_type}): {safe_msg}"),
-                retryable,
-            });
+            if let Some(err) = Self::classify(status, error_code, &safe_msg) {
+                return Err(err.into());
+            }
+            // 402 (out of credits) is retryable so the fallback chain moves on
+            let message = format!("API error ({error_type}): {safe_msg}");
+            return Err(if status == 402 {
+                ProviderError::Api {
+                    message,
+                    retryable: true,
+                }
+            } else {
+                Self::by_status(status, message)
+            }
+            .into());
         }
 
-        let retryable = status == 429
-            || status == 500
-            || status == 502
-            || status == 503
-            || status == 504
-            || status == 529;
         let safe_text: String = error_text.chars().take(500).collect();
-        Err(OxicrabError::Provider {
-            message: format!("API error ({status}): {safe_text}"),
-            retryable,
-        })
+        if let Some(err) = Self::classify(status, "", &safe_text) {
+            return Err(err.into());
+        }
+        Err(Self::by_status(status, format!("API error ({status}): {safe_text}")).into())
+    }
+
+    /// Recognize context-length and content-policy failures from the error
+    /// code or message. Providers report both as plain 400s.
+    fn classify(status: u16, code: &str, message: &str) -> Option<ProviderError> {
+        const CONTEXT_PHRASES: &[&str] = &[
+            "prompt is too long",
+            "context length",
+            "context window",
+            "maximum context",
+            "too many tokens",
+            "input token count",
+        ];
+        const POLICY_PHRASES: &[&str] = &[
+            "content policy",
+            "content management policy",
+            "usage policies",
+            "safety system",
+        ];
+        let lower = message.to_lowercase();
+        if status == 413
+            || code == "context_length_exceeded"
+            || CONTEXT_PHRASES.iter().any(|p| lower.contains(p))
+        {
+            return Some(ProviderError::ContextLength(message.to_string()));
+        }
+        if matches!(code, "content_policy_violation" | "content_filter")
+            || POLICY_PHRASES.iter().any(|p| lower.contains(p))
+        {
+            return Some(ProviderError::ContentPolicy(message.to_string()));
+        }
+        None
+    }
+
+    fn by_status(status: u16, message: String) -> ProviderError {
+        if matches!(status, 500 | 502 | 503 | 504 | 529) {
+            ProviderError::Transient(message)
+        } else {
+            ProviderError::Api {
+                message,
+                retryable: false,
+            }
+        }
+    }
+
+    /// Map a failed request send (connect error, timeout) to a transient error.
+    pub fn network_error(err: &reqwest::Error, provider: &str) -> anyhow::Error {
+        OxicrabError::from(ProviderError::Transient(format!(
+            "Failed to send request to {provider} API: {err}"
+        )))
+        .into()
     }
 
     /// Log and handle provider errors consistently
@@ -84,15 +134,28 @@ impl ProviderErrorHandler {
         } else {
             warn!("Rate limit hit (status: {})", status);
         }
-        Err(OxicrabError::RateLimit { retry_after })
+        Err(ProviderError::RateLimit { retry_after }.into())
+    }
+
+    /// Seconds to wait from a `Retry-After` header: either a number of
+    /// seconds or an HTTP date.
+    pub fn parse_retry_after(value: &str) -> Option<u64> {
+        let value = value.trim();
+        if let Ok(secs) = value.parse::<u64>() {
+            return Some(secs);
+        }
+        let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        let secs = (at.timestamp() - chrono::Utc::now().timestamp()).max(0);
+        Some(secs as u64)
     }
 
     /// Handle authentication errors
     pub fn handle_auth_error(status: u16, error_text: &str) -> Result<(), OxicrabError> {
         warn!("Authentication error (status: {}): {}", status, error_text);
-        Err(OxicrabError::Auth(format!(
+        Err(ProviderError::Auth(format!(
             "Authentication failed. Please check your API key or credentials. Error: {error_text}"
-        )))
+        ))
+        .into())
     }
 
     /// Check HTTP status and return a typed error if the response is not successful.
@@ -111,7 +174,7 @@ impl ProviderErrorHandler {
             .headers()
             .get("retry-after")
             .and_then(|h| h.to_str().ok())
-            .and_then(|s| s.parse::<u64>().ok());
+            .and_then(Self::parse_retry_after);
 
         let error_text = resp
             .text()
@@ -1,5 +1,5 @@
 use super::*;
-use oxicrab_core::errors::OxicrabError;
+use oxicrab_core::errors::{OxicrabError, ProviderError};
 
 #[test]
 fn test_parse_api_error_with_json_body() {
@@ -7,7 +7,7 @@ fn test_parse_api_error_with_json_body() {
     let result = ProviderErrorHandler::parse_api_error(400, error_json);
     let err = result.unwrap_err();
     match err {
-        OxicrabError::Provider { message, retryable } => {
+        OxicrabError::Provider(ProviderError::Api { message, retryable }) => {
             assert!(message.contains("invalid_request"));
             assert!(message.contains("bad request"));
             assert!(!retryable);
@@ -22,8 +22,8 @@ fn test_parse_api_error_retryable_500() {
     let result = ProviderErrorHandler::parse_api_error(500, error_json);
     let err = result.unwrap_err();
     match err {
-        OxicrabError::Provider { retryable, .. } => assert!(retryable),
-        _ => panic!("expected Provider error"),
+        OxicrabError::Provider(ProviderError::Transient(_)) => {}
+        _ => panic!("expected Transient error, got {err:?}"),
     }
 }
 
@@ -33,8 +33,8 @@ fn test_parse_api_error_retryable_502() {
     let result = ProviderErrorHandler::parse_api_error(502, error_json);
     let err = result.unwrap_err();
     match err {
-        OxicrabError::Provider { retryable, .. } => assert!(retryable),
-        _ => panic!("expected Provider error"),
+        OxicrabError::Provider(ProviderError::Transient(_)) => {}
+        _ => panic!("expected Transient error, got {err:?}"),
     }
 }
 
@@ -44,8 +44,8 @@ fn test_parse_api_error_retryable_503() {
     let result = ProviderErrorHandler::parse_api_error(503, error_json);
     let err = result.unwrap_err();
     match err {
-        OxicrabError::Provider { retryable, .. } => assert!(retryable),
-        _ => panic!("expected Provider error"),
+        OxicrabError::Provider(ProviderError::Transient(_)) => {}
+        _ => panic!("expected Transient error, got {err:?}"),
     }
 }
 
@@ -55,7 +55,7 @@ fn test_parse_api_error_not_retryable_400() {
     let result = ProviderErrorHandler::parse_api_error(400, error_json);
     let err = result.unwrap_err();
     match err {
-        OxicrabError::Provider { retryable, .. } => assert!(!retryable),
+        OxicrabError::Provider(ProviderError::Api { retryable, .. }) => assert!(!retryable),
         _ => panic!("expected Provider error"),
     }
 }
@@ -65,12 +65,11 @@ fn test_parse_api_error_non_json_body() {
     let result = ProviderErrorHandler::parse_api_error(500, "plain text error");
     let err = result.unwrap_err();
     match err {
-        OxicrabError::Provider { message, retryable } => {
+        OxicrabError::Provider(ProviderError::Transient(message)) => {
             assert!(message.contains("500"));
             assert!(message.contains("plain text error"));
-            assert!(retryable);
         }
-        _ => panic!("expected Provider error"),
+        _ => panic!("expected Transient error, got {err:?}"),
     }
 }
 
@@ -80,7 +79,7 @@ fn test_parse_api_error_model_not_found() {
     let result = ProviderErrorHandler::parse_api_error(404, error_json);
     let err = result.unwrap_err();
     match err {
-        OxicrabError::Provider { message, retryable } => {
+        OxicrabError::Provider(ProviderError::Api { message, retryable }) => {
             assert!(message.contains("not found"));
             assert!(message.contains("claude-sonnet-4-6"));
             assert!(!retryable);
@@ -94,19 +93,34 @@ fn test_handle_rate_limit_with_retry_after() {
     let result = ProviderErrorHandler::handle_rate_limit(429, Some(30));
     let err = result.unwrap_err();
     match err {
-        OxicrabError::RateLimit { retry_after } => {
+        OxicrabError::Provider(ProviderError::RateLimit { retry_after }) => {
             assert_eq!(retry_after, Some(30));
         }
         _ => panic!("expected RateLimit error"),
     }
 }
 
+#[test]
+fn test_parse_retry_after() {
+    assert_eq!(ProviderErrorHandler::parse_retry_after("12"), Some(12));
+    assert_eq!(ProviderErrorHandler::parse_retry_after(" 7 "), Some(7));
+    // HTTP dates in the past mean "retry now"
+    assert_eq!(
+        ProviderErrorHandler::parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+        Some(0)
+    );
+    let soon = (chrono::Utc::now() + chrono::Duration::seconds(90)).to_rfc2822();
+    let secs = ProviderErrorHandler::parse_retry_after(&soon).unwrap();
+    assert!((85..=90).contains(&secs), "{secs}");
+    assert_eq!(ProviderErrorHandler::parse_retry_after("soon"), None);
+}
+
 #[test]
 fn test_handle_rate_limit_without_retry_after() {
     let result = ProviderErrorHandler::handle_rate_limit(429, None);
     let err = result.unwrap_err();
     match err {
-        OxicrabError::RateLimit { retry_after } => {
+        OxicrabError::Provider(ProviderError::RateLimit { retry_after }) => {
             assert_eq!(retry_after, None);
         }
         _ => panic!("expected RateLimit error"),
@@ -118,10 +132,42 @@ fn test_handle_auth_error() {
     let result = ProviderErrorHandler::handle_auth_error(401, "invalid token");
     let err = result.unwrap_err();
     match err {
-        OxicrabError::Auth(msg) => {
+        OxicrabError::Provider(ProviderError::Auth(msg)) => {
             assert!(msg.contains("invalid token"));
             assert!(msg.contains("Authentication failed"));
         }
         _ => panic!("expected Auth error"),
     }
 }
+
+#[test]
+fn test_parse_api_error_context_length() {
+    let openai = r#"{"error": {"type": "invalid_request_error", "code": "context_length_exceeded", "message": "This model's maximum context length is 128000 tokens."}}"#;
+    let anthropic = r#"{"error": {"type": "invalid_request_error", "message": "prompt is too long: 210000 tokens > 200000 maximum"}}"#;
+    for body in [openai, anthropic] {
+        let err = ProviderErrorHandler::parse_api_error(400, body).unwrap_err();
+        assert!(
+            matches!(err, OxicrabError::Provider(ProviderError::ContextLength(_))),
+            "got {err:?}"
+        );
+        assert!(!err.is_retryable());
+    }
+}
+
+#[test]
+fn test_parse_api_error_content_policy() {
+    let body = r#"{"error": {"type": "invalid_request_error", "code": "content_policy_violation", "message": "Your request was rejected by our safety system."}}"#;
+    let err = ProviderErrorHandler::parse_api_error(400, body).unwrap_err();
+    assert!(
+        matches!(err, OxicrabError::Provider(ProviderError::ContentPolicy(_))),
+        "got {err:?}"
+    );
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn test_parse_api_error_402_stays_retryable() {
+    let body = r#"{"error": {"type": "payment_required", "message": "insufficient credits"}}"#;
+    let err = ProviderErrorHandler::parse_api_error(402, body).unwrap_err();
+    assert!(err.is_retryable());
+}
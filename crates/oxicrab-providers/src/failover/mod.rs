@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use oxicrab_core::errors::ProviderError;
+use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse, StreamSink};
+use std::sync::Arc;
+use tracing::warn;
+
+/// An LLM provider that sends each request to a primary provider and, when
+/// the primary's circuit is open or the call fails, retries it once on a
+/// secondary provider.
+///
+/// Unlike [`FallbackProvider`](crate::fallback::FallbackProvider), which walks
+/// a chain of models, this wraps the primary *after* its circuit breaker so
+/// an open circuit (`ProviderError::Unavailable`) fails over immediately
+/// instead of erroring.
+pub struct FailoverProvider {
+    primary: Arc<dyn LLMProvider>,
+    secondary: Arc<dyn LLMProvider>,
+    /// Provider name of the secondary, reported in `LLMResponse::served_by`.
+    secondary_name: String,
+}
+
+impl FailoverProvider {
+    pub fn wrap(
+        primary: Arc<dyn LLMProvider>,
+        secondary: Arc<dyn LLMProvider>,
+        secondary_name: String,
+    ) -> Arc<dyn LLMProvider> {
+        Arc::new(Self {
+            primary,
+            secondary,
+            secondary_name,
+        })
+    }
+
+    /// Run the request on the primary, then on the secondary if the primary
+    /// failed with an error the secondary might not share. The secondary gets
+    /// `model: None` so it uses its own default model.
+    async fn chat_inner(
+        &self,
+        req: &ChatRequest,
+        sink: Option<&StreamSink>,
+    ) -> anyhow::Result<LLMResponse> {
+        let primary = match sink {
+            Some(sink) => self.primary.chat_stream(req, sink.clone()).await,
+            None => self.primary.chat(req).await,
+        };
+        let primary_err = match primary {
+            Ok(response) => return Ok(response),
+            Err(e) if !should_fail_over(&e) => return Err(e),
+            Err(e) => e,
+        };
+
+        warn!(
+            "primary provider ({}) failed, failing over to {}: {}",
+            self.primary.default_model(),
+            self.secondary_name,
+            primary_err
+        );
+        let secondary_req = ChatRequest {
+            model: None,
+            ..req.clone()
+        };
+        let secondary = match sink {
+            Some(sink) => {
+                sink.reset();
+                self.secondary
+                    .chat_stream(&secondary_req, sink.clone())
+                    .await
+            }
+            None => self.secondary.chat(&secondary_req).await,
+        };
+        match secondary {
+            Ok(mut response) => {
+                response.actual_model = Some(self.secondary.default_model().to_string());
+                response.served_by = Some(self.secondary_name.clone());
+                Ok(response)
+            }
+            Err(e) => {
+                warn!("failover provider ({}) failed: {}", self.secondary_name, e);
+                let context = format!(
+                    "primary provider failed ({primary_err}), failover to {} also failed",
+                    self.secondary_name
+                );
+                if is_request_error(&e) {
+                    return Err(e.context(context));
+                }
+                // Neither provider is serving: report it as an outage, so
+                // offline mode queues the message
+                Err(ProviderError::Unavailable(format!("{context}: {e}")).into())
+            }
+        }
+    }
+}
+
+/// Whether a primary error is worth retrying on the secondary. Content policy
+/// blocks and non-retryable API errors (malformed requests) would fail there
+/// too; auth failures, an open circuit and context overflows may not.
+fn should_fail_over(err: &anyhow::Error) -> bool {
+    match ProviderError::find(err) {
+        Some(ProviderError::ContentPolicy(_)) => false,
+        Some(ProviderError::Api { retryable, .. }) => *retryable,
+        _ => true,
+    }
+}
+
+/// Whether a secondary error is about this request rather than the
+/// provider's availability, so it is reported as is.
+fn is_request_error(err: &anyhow::Error) -> bool {
+    matches!(
+        ProviderError::find(err),
+        Some(
+            ProviderError::ContextLength(_)
+                | ProviderError::ContentPolicy(_)
+                | ProviderError::Api {
+                    retryable: false,
+                    ..
+                }
+        )
+    )
+}
+
+#[async_trait]
+impl LLMProvider for FailoverProvider {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        self.chat_inner(req, None).await
+    }
+
+    async fn chat_stream(
+        &self,
+        req: &ChatRequest,
+        sink: StreamSink,
+    ) -> anyhow::Result<LLMResponse> {
+        self.chat_inner(req, Some(&sink)).await
+    }
+
+    fn default_model(&self) -> &str {
+        self.primary.default_model()
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        let (primary, secondary) = tokio::join!(self.primary.warmup(), self.secondary.warmup());
+        if let Err(e) = secondary {
+            warn!(
+                "failover provider ({}) warmup failed: {}",
+                self.secondary_name, e
+            );
+        }
+        primary
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,135 @@
+use super::*;
+use std::sync::Mutex;
+
+/// A mock provider that returns a fixed result and records the model of
+/// each request it receives.
+struct MockProvider {
+    model: String,
+    error: Option<fn() -> anyhow::Error>,
+    seen_models: Mutex<Vec<Option<String>>>,
+}
+
+impl MockProvider {
+    fn ok(model: &str) -> Arc<Self> {
+        Arc::new(Self {
+            model: model.to_string(),
+            error: None,
+            seen_models: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn err(model: &str, error: fn() -> anyhow::Error) -> Arc<Self> {
+        Arc::new(Self {
+            model: model.to_string(),
+            error: Some(error),
+            seen_models: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn calls(&self) -> usize {
+        self.seen_models.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl LLMProvider for MockProvider {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        self.seen_models.lock().unwrap().push(req.model.clone());
+        if let Some(error) = self.error {
+            return Err(error());
+        }
+        Ok(LLMResponse {
+            content: Some(format!("from {}", self.model)),
+            ..Default::default()
+        })
+    }
+
+    fn default_model(&self) -> &str {
+        &self.model
+    }
+}
+
+fn circuit_open() -> anyhow::Error {
+    ProviderError::Unavailable("circuit open".into()).into()
+}
+
+fn content_policy() -> anyhow::Error {
+    ProviderError::ContentPolicy("blocked".into()).into()
+}
+
+fn request() -> ChatRequest {
+    ChatRequest::builder(vec![], 1024)
+        .model("claude-sonnet-4-5")
+        .build()
+}
+
+#[tokio::test]
+async fn test_primary_success_is_not_tagged() {
+    let primary = MockProvider::ok("claude-sonnet-4-5");
+    let secondary = MockProvider::ok("gpt-4o");
+    let provider = FailoverProvider::wrap(primary.clone(), secondary.clone(), "openai".into());
+
+    let response = provider.chat(&request()).await.unwrap();
+    assert_eq!(response.content.as_deref(), Some("from claude-sonnet-4-5"));
+    assert!(response.served_by.is_none());
+    assert!(response.actual_model.is_none());
+    assert_eq!(secondary.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_open_circuit_fails_over_with_secondary_default_model() {
+    let primary = MockProvider::err("claude-sonnet-4-5", circuit_open);
+    let secondary = MockProvider::ok("gpt-4o");
+    let provider = FailoverProvider::wrap(primary, secondary.clone(), "openai".into());
+
+    let response = provider.chat(&request()).await.unwrap();
+    assert_eq!(response.content.as_deref(), Some("from gpt-4o"));
+    assert_eq!(response.served_by.as_deref(), Some("openai"));
+    assert_eq!(response.actual_model.as_deref(), Some("gpt-4o"));
+    assert_eq!(*secondary.seen_models.lock().unwrap(), vec![None]);
+}
+
+#[tokio::test]
+async fn test_content_policy_does_not_fail_over() {
+    let primary = MockProvider::err("claude-sonnet-4-5", content_policy);
+    let secondary = MockProvider::ok("gpt-4o");
+    let provider = FailoverProvider::wrap(primary, secondary.clone(), "openai".into());
+
+    let err = provider.chat(&request()).await.unwrap_err();
+    assert!(matches!(
+        ProviderError::find(&err),
+        Some(ProviderError::ContentPolicy(_))
+    ));
+    assert_eq!(secondary.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_both_failing_reports_unavailable() {
+    let primary = MockProvider::err("claude-sonnet-4-5", circuit_open);
+    let secondary = MockProvider::err("gpt-4o", || ProviderError::Transient("503".into()).into());
+    let provider = FailoverProvider::wrap(primary, secondary, "openai".into());
+
+    let err = provider.chat(&request()).await.unwrap_err();
+    assert!(err.to_string().contains("failover to openai also failed"));
+    assert!(err.to_string().contains("503"));
+    assert!(matches!(
+        ProviderError::find(&err),
+        Some(ProviderError::Unavailable(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_secondary_request_error_is_kept() {
+    let primary = MockProvider::err("claude-sonnet-4-5", circuit_open);
+    let secondary = MockProvider::err("gpt-4o", || {
+        ProviderError::ContextLength("too long".into()).into()
+    });
+    let provider = FailoverProvider::wrap(primary, secondary, "openai".into());
+
+    let err = provider.chat(&request()).await.unwrap_err();
+    assert!(err.to_string().contains("failover to openai also failed"));
+    assert!(matches!(
+        ProviderError::find(&err),
+        Some(ProviderError::ContextLength(_))
+    ));
+}
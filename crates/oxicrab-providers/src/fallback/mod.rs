@@ -1,7 +1,9 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use oxicrab_core::errors::OxicrabError;
-use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse, ToolCallRequest};
+use oxicrab_core::errors::{OxicrabError, ProviderError};
+use oxicrab_core::providers::base::{
+    ChatRequest, LLMProvider, LLMResponse, StreamSink, ToolCallRequest,
+};
 use std::sync::Arc;
 use tracing::warn;
 
@@ -47,15 +49,19 @@ fn validate_tool_calls(tool_calls: &[ToolCallRequest]) -> bool {
     true
 }
 
-#[async_trait]
-impl LLMProvider for FallbackProvider {
+impl FallbackProvider {
     /// Try each provider in the chain until one succeeds with valid tool calls.
     ///
     /// `req.model` is intentionally ignored — each provider in the chain has
     /// its own pre-configured model name (stored alongside the provider Arc).
     /// The request is cloned with `model: None` for each attempt so the
-    /// provider uses its own `default_model()`.
-    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+    /// provider uses its own `default_model()`. With a `sink`, each attempt
+    /// streams to it, starting from empty text.
+    async fn try_chain(
+        &self,
+        req: &ChatRequest,
+        sink: Option<&StreamSink>,
+    ) -> anyhow::Result<LLMResponse> {
         let mut errors: Vec<String> = Vec::new();
         for (i, (provider, model_name)) in self.providers.iter().enumerate() {
             let is_last = i == self.providers.len() - 1;
@@ -67,9 +73,16 @@ impl LLMProvider for FallbackProvider {
                 temperature: req.temperature,
                 tool_choice: req.tool_choice.clone(),
                 response_format: req.response_format.clone(),
+                reasoning_effort: req.reasoning_effort,
             };
 
-            match provider.chat(&attempt_req).await {
+            let result = if let Some(sink) = sink {
+                sink.reset();
+                provider.chat_stream(&attempt_req, sink.clone()).await
+            } else {
+                provider.chat(&attempt_req).await
+            };
+            match result {
                 Ok(mut response) => {
                     if response.has_tool_calls() && !validate_tool_calls(&response.tool_calls) {
                         warn!(
@@ -94,10 +107,16 @@ impl LLMProvider for FallbackProvider {
                     return Ok(response);
                 }
                 Err(e) => {
-                    // Don't fall back on non-transient errors (auth, config, bad request)
-                    let is_non_transient = e
-                        .downcast_ref::<OxicrabError>()
-                        .is_some_and(|ox| !ox.is_retryable());
+                    // Don't fall back on non-transient errors (auth, config, bad
+                    // request); a context-length error may still fit a later
+                    // model's window
+                    let is_non_transient = e.downcast_ref::<OxicrabError>().is_some_and(|ox| {
+                        !ox.is_retryable()
+                            && !matches!(
+                                ox,
+                                OxicrabError::Provider(ProviderError::ContextLength(_))
+                            )
+                    });
                     if is_non_transient {
                         warn!(
                             "provider {} ({}) failed with non-transient error, not falling back: {}",
@@ -128,6 +147,21 @@ impl LLMProvider for FallbackProvider {
             errors.join("\n")
         ))
     }
+}
+
+#[async_trait]
+impl LLMProvider for FallbackProvider {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        self.try_chain(req, None).await
+    }
+
+    async fn chat_stream(
+        &self,
+        req: &ChatRequest,
+        sink: StreamSink,
+    ) -> anyhow::Result<LLMResponse> {
+        self.try_chain(req, Some(&sink)).await
+    }
 
     fn default_model(&self) -> &str {
         &self.providers[0].1
@@ -320,3 +320,62 @@ async fn test_single_provider_chain() {
     let result = provider.chat(&make_request()).await.unwrap();
     assert_eq!(result.content.as_deref(), Some("only provider"));
 }
+
+/// A provider that fails with a typed provider error.
+struct TypedErrorProvider(fn() -> ProviderError);
+
+#[async_trait]
+impl LLMProvider for TypedErrorProvider {
+    async fn chat(&self, _req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        Err(OxicrabError::from((self.0)()).into())
+    }
+
+    fn default_model(&self) -> &'static str {
+        "typed-error"
+    }
+}
+
+#[tokio::test]
+async fn test_context_length_falls_back_but_auth_does_not() {
+    let too_long = || ProviderError::ContextLength("prompt is too long".into());
+    let provider = FallbackProvider::pair(
+        Arc::new(TypedErrorProvider(too_long)),
+        MockProvider::ok("big-model", text_response("fits here")),
+        "small-model".to_string(),
+        "big-model".to_string(),
+    );
+    let result = provider.chat(&make_request()).await.unwrap();
+    assert_eq!(result.content.as_deref(), Some("fits here"));
+
+    let bad_key = || ProviderError::Auth("invalid key".into());
+    let provider = FallbackProvider::pair(
+        Arc::new(TypedErrorProvider(bad_key)),
+        MockProvider::ok("other-model", text_response("unreachable")),
+        "primary".to_string(),
+        "other-model".to_string(),
+    );
+    let err = provider.chat(&make_request()).await.unwrap_err();
+    assert!(matches!(
+        ProviderError::find(&err),
+        Some(ProviderError::Auth(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_stream_restarts_for_next_provider() {
+    let bad_response = LLMResponse {
+        content: Some("half an answer".to_string()),
+        ..tool_response("", json!({}))
+    };
+    let provider = FallbackProvider::pair(
+        MockProvider::ok("local-model", bad_response),
+        MockProvider::ok("cloud-model", text_response("hello from cloud")),
+        "local-model".to_string(),
+        "cloud-model".to_string(),
+    );
+
+    let (sink, text) = StreamSink::new();
+    let result = provider.chat_stream(&make_request(), sink).await.unwrap();
+    assert_eq!(result.content.as_deref(), Some("hello from cloud"));
+    assert_eq!(*text.borrow(), "hello from cloud");
+}
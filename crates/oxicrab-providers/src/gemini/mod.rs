@@ -2,6 +2,7 @@ use crate::errors::ProviderErrorHandler;
 use crate::provider_http_client;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use oxicrab_core::errors::{OxicrabError, ProviderError};
 use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse, ToolCallRequest};
 use reqwest::Client;
 use serde_json::{Value, json};
@@ -56,6 +57,14 @@ impl GeminiProvider {
     }
 
     fn parse_response(json: &Value) -> Result<LLMResponse> {
+        // The prompt itself was blocked; no candidates are returned
+        if let Some(reason) = json["promptFeedback"]["blockReason"].as_str() {
+            return Err(OxicrabError::from(ProviderError::ContentPolicy(format!(
+                "Gemini prompt blocked (blockReason: {reason})"
+            )))
+            .into());
+        }
+
         let candidate = json["candidates"]
             .as_array()
             .and_then(|arr| arr.first())
@@ -68,7 +77,10 @@ impl GeminiProvider {
                 "SAFETY" | "BLOCKED" | "RECITATION" | "PROHIBITED_CONTENT" | "SPII"
             )
         {
-            anyhow::bail!("Gemini response blocked (finishReason: {reason})");
+            return Err(OxicrabError::from(ProviderError::ContentPolicy(format!(
+                "Gemini response blocked (finishReason: {reason})"
+            )))
+            .into());
         }
 
         // Separate thinking parts (thought: true) from regular text parts.
@@ -327,7 +339,7 @@ impl LLMProvider for GeminiProvider {
             .json(&payload)
             .send()
             .await
-            .context("Failed to send request to Gemini API")?;
+            .map_err(|e| ProviderErrorHandler::network_error(&e, "Gemini"))?;
 
         let json = ProviderErrorHandler::check_response(resp, "Gemini").await?;
 
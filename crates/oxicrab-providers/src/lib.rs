@@ -1,17 +1,19 @@
 //! LLM provider implementations for the oxicrab framework.
 //!
 //! This crate contains all provider-specific code: Anthropic, OpenAI, Gemini,
-//! circuit breaker, fallback, and prompt-guided wrappers.
+//! circuit breaker, fallback, failover, and prompt-guided wrappers.
 
 pub mod anthropic;
 pub mod anthropic_common;
 pub mod anthropic_oauth;
 pub mod circuit_breaker;
 pub mod errors;
+pub mod failover;
 pub mod fallback;
 pub mod gemini;
 pub mod openai;
 pub mod prompt_guided;
+mod sse;
 pub mod strategy;
 mod utils;
 
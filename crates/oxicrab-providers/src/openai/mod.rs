@@ -2,13 +2,17 @@ use crate::errors::ProviderErrorHandler;
 use crate::provider_http_client;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use oxicrab_core::providers::base::{ChatRequest, LLMProvider, LLMResponse, ToolCallRequest};
+use oxicrab_core::errors::{OxicrabError, ProviderError};
+use oxicrab_core::providers::base::{
+    ChatRequest, LLMProvider, LLMResponse, StreamSink, ToolCallRequest,
+};
 use reqwest::Client;
 use serde_json::{Value, json};
 use std::time::Duration;
 use tracing::{debug, info, warn};
 
 const API_URL: &str = "https://api.openai.com/v1/chat/completions";
+const WARMUP_TIMEOUT: Duration = Duration::from_secs(15);
 
 pub struct OpenAIProvider {
     api_key: String,
@@ -17,6 +21,9 @@ pub struct OpenAIProvider {
     provider_name: String,
     client: Client,
     custom_headers: std::collections::HashMap<String, String>,
+    /// Ollama `keep_alive` sent with every request.
+    keep_alive: Option<String>,
+    warmup_timeout: Duration,
 }
 
 impl OpenAIProvider {
@@ -28,6 +35,8 @@ impl OpenAIProvider {
             provider_name: "OpenAI".to_string(),
             client: provider_http_client(),
             custom_headers: std::collections::HashMap::new(),
+            keep_alive: None,
+            warmup_timeout: WARMUP_TIMEOUT,
         }
     }
 
@@ -44,6 +53,8 @@ impl OpenAIProvider {
             provider_name,
             client: provider_http_client(),
             custom_headers: std::collections::HashMap::new(),
+            keep_alive: None,
+            warmup_timeout: WARMUP_TIMEOUT,
         }
     }
 
@@ -62,9 +73,26 @@ impl OpenAIProvider {
             provider_name,
             client: provider_http_client(),
             custom_headers,
+            keep_alive: None,
+            warmup_timeout: WARMUP_TIMEOUT,
         }
     }
 
+    /// Send Ollama's `keep_alive` with every request, including the warmup.
+    #[must_use]
+    pub fn with_keep_alive(mut self, keep_alive: Option<String>) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// How long `warmup()` waits for its response. Local servers may need
+    /// minutes to load a model on the first request.
+    #[must_use]
+    pub fn with_warmup_timeout(mut self, timeout: Duration) -> Self {
+        self.warmup_timeout = timeout;
+        self
+    }
+
     #[cfg(test)]
     fn with_base_url(api_key: String, default_model: Option<String>, base_url: String) -> Self {
         Self {
@@ -74,6 +102,8 @@ impl OpenAIProvider {
             provider_name: "OpenAI".to_string(),
             client: provider_http_client(),
             custom_headers: std::collections::HashMap::new(),
+            keep_alive: None,
+            warmup_timeout: WARMUP_TIMEOUT,
         }
     }
 
@@ -88,6 +118,17 @@ impl OpenAIProvider {
             .as_str()
             .map(std::string::ToString::to_string);
 
+        // Azure OpenAI and others end a filtered completion with no content
+        if choice["finish_reason"].as_str() == Some("content_filter")
+            && content.as_deref().is_none_or(str::is_empty)
+            && message["tool_calls"].as_array().is_none_or(Vec::is_empty)
+        {
+            return Err(OxicrabError::from(ProviderError::ContentPolicy(
+                "response withheld by the provider's content filter".to_string(),
+            ))
+            .into());
+        }
+
         let mut tool_calls = Vec::new();
         if let Some(tool_calls_array) = message["tool_calls"].as_array() {
             for tc in tool_calls_array {
@@ -147,9 +188,90 @@ impl OpenAIProvider {
     }
 }
 
-#[async_trait]
-impl LLMProvider for OpenAIProvider {
-    async fn chat(&self, req: &ChatRequest) -> Result<LLMResponse> {
+/// Rebuilds a chat completion from the chunks of a streamed one
+/// (`"stream": true`), so it can go through `parse_response()`.
+#[derive(Default)]
+struct StreamAccumulator {
+    content: String,
+    reasoning: String,
+    /// `{id, type, function: {name, arguments}}` by tool call index.
+    tool_calls: Vec<Value>,
+    finish_reason: Option<String>,
+    usage: Option<Value>,
+}
+
+impl StreamAccumulator {
+    /// Apply one chunk. Returns the text it adds to the answer, if any.
+    fn apply(&mut self, chunk: &Value) -> Result<Option<String>> {
+        if let Some(error) = chunk.get("error") {
+            return Err(ProviderErrorHandler::parse_api_error(
+                500,
+                &json!({ "error": error }).to_string(),
+            )
+            .unwrap_err()
+            .into());
+        }
+        if chunk["usage"].is_object() {
+            self.usage = Some(chunk["usage"].clone());
+        }
+        let Some(choice) = chunk["choices"].as_array().and_then(|c| c.first()) else {
+            return Ok(None);
+        };
+        if let Some(reason) = choice["finish_reason"].as_str() {
+            self.finish_reason = Some(reason.to_string());
+        }
+        let delta = &choice["delta"];
+        if let Some(reasoning) = delta["reasoning_content"].as_str() {
+            self.reasoning.push_str(reasoning);
+        }
+        for tc in delta["tool_calls"].as_array().into_iter().flatten() {
+            let index = tc["index"].as_u64().unwrap_or_default() as usize;
+            if self.tool_calls.len() <= index {
+                self.tool_calls.resize(
+                    index + 1,
+                    json!({"type": "function", "function": {"name": "", "arguments": ""}}),
+                );
+            }
+            let call = &mut self.tool_calls[index];
+            if let Some(id) = tc["id"].as_str() {
+                call["id"] = json!(id);
+            }
+            for field in ["name", "arguments"] {
+                if let Some(part) = tc["function"][field].as_str() {
+                    let joined = format!(
+                        "{}{part}",
+                        call["function"][field].as_str().unwrap_or_default()
+                    );
+                    call["function"][field] = json!(joined);
+                }
+            }
+        }
+        let text = delta["content"].as_str().unwrap_or_default();
+        self.content.push_str(text);
+        Ok((!text.is_empty()).then(|| text.to_string()))
+    }
+
+    /// The complete response, in the shape of a non-streamed one.
+    fn finish(self) -> Value {
+        let mut message = json!({
+            "content": (!self.content.is_empty()).then_some(self.content),
+        });
+        if !self.reasoning.is_empty() {
+            message["reasoning_content"] = json!(self.reasoning);
+        }
+        if !self.tool_calls.is_empty() {
+            message["tool_calls"] = Value::Array(self.tool_calls);
+        }
+        json!({
+            "choices": [{"message": message, "finish_reason": self.finish_reason}],
+            "usage": self.usage,
+        })
+    }
+}
+
+impl OpenAIProvider {
+    /// Send a chat completion request; streamed to `sink` when one is given.
+    async fn send(&self, req: &ChatRequest, sink: Option<&StreamSink>) -> Result<LLMResponse> {
         debug!(
             "{} chat: model={}",
             self.provider_name,
@@ -238,10 +360,16 @@ impl LLMProvider for OpenAIProvider {
         let mut payload = json!({
             "model": req.model.as_deref().unwrap_or(&self.default_model),
             "messages": openai_messages,
-            "max_tokens": req.max_tokens,
         });
-        if let Some(temp) = req.temperature {
-            payload["temperature"] = json!(temp);
+        if let Some(effort) = req.reasoning_effort {
+            // Reasoning models reject max_tokens and any non-default temperature
+            payload["reasoning_effort"] = json!(effort.as_str());
+            payload["max_completion_tokens"] = json!(req.max_tokens);
+        } else {
+            payload["max_tokens"] = json!(req.max_tokens);
+            if let Some(temp) = req.temperature {
+                payload["temperature"] = json!(temp);
+            }
         }
 
         if let Some(ref format) = req.response_format {
@@ -286,6 +414,15 @@ impl LLMProvider for OpenAIProvider {
             }
         }
 
+        if let Some(ref keep_alive) = self.keep_alive {
+            payload["keep_alive"] = json!(keep_alive);
+        }
+
+        if sink.is_some() {
+            payload["stream"] = json!(true);
+            payload["stream_options"] = json!({"include_usage": true});
+        }
+
         let mut req = self
             .client
             .post(&self.base_url)
@@ -300,9 +437,23 @@ impl LLMProvider for OpenAIProvider {
             .json(&payload)
             .send()
             .await
-            .with_context(|| format!("Failed to send request to {provider_name} API"))?;
+            .map_err(|e| ProviderErrorHandler::network_error(&e, provider_name))?;
 
-        let json = ProviderErrorHandler::check_response(resp, &self.provider_name).await?;
+        let json = if let Some(sink) = sink {
+            let resp = ProviderErrorHandler::check_http_status(resp, provider_name).await?;
+            let mut acc = StreamAccumulator::default();
+            crate::sse::read_events(resp, provider_name, |data| {
+                let chunk: Value = serde_json::from_str(data)?;
+                if let Some(text) = acc.apply(&chunk)? {
+                    sink.push(&text);
+                }
+                Ok(())
+            })
+            .await?;
+            acc.finish()
+        } else {
+            ProviderErrorHandler::check_response(resp, &self.provider_name).await?
+        };
 
         let response = Self::parse_response(&json)?;
         debug!(
@@ -311,6 +462,17 @@ impl LLMProvider for OpenAIProvider {
         );
         Ok(response)
     }
+}
+
+#[async_trait]
+impl LLMProvider for OpenAIProvider {
+    async fn chat(&self, req: &ChatRequest) -> Result<LLMResponse> {
+        self.send(req, None).await
+    }
+
+    async fn chat_stream(&self, req: &ChatRequest, sink: StreamSink) -> Result<LLMResponse> {
+        self.send(req, Some(&sink)).await
+    }
 
     fn default_model(&self) -> &str {
         &self.default_model
@@ -318,17 +480,20 @@ impl LLMProvider for OpenAIProvider {
 
     async fn warmup(&self) -> anyhow::Result<()> {
         let start = std::time::Instant::now();
-        let payload = json!({
+        let mut payload = json!({
             "model": self.default_model,
             "messages": [{"role": "user", "content": "hi"}],
             "max_tokens": 1,
         });
+        if let Some(ref keep_alive) = self.keep_alive {
+            payload["keep_alive"] = json!(keep_alive);
+        }
         let mut req = self
             .client
             .post(&self.base_url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
-            .timeout(Duration::from_secs(15));
+            .timeout(self.warmup_timeout);
         req = req.header("x-session-affinity", crate::session_affinity_id());
         for (k, v) in &self.custom_headers {
             req = req.header(k.as_str(), v.as_str());
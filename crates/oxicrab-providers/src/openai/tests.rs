@@ -530,3 +530,98 @@ fn test_default_model_with_config() {
     );
     assert_eq!(provider.default_model(), "deepseek-r1");
 }
+
+#[tokio::test]
+async fn test_chat_reasoning_effort() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "choices": [{
+                "message": {"role": "assistant", "content": "42"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = OpenAIProvider::with_base_url("test_key".to_string(), None, server.uri());
+    let req = ChatRequest::builder(vec![Message::user("think hard")], 2048)
+        .temperature(0.7)
+        .reasoning_effort(oxicrab_core::providers::base::ReasoningEffort::High)
+        .build();
+    provider.chat(&req).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["reasoning_effort"], "high");
+    assert_eq!(body["max_completion_tokens"], 2048);
+    assert!(body.get("max_tokens").is_none());
+    assert!(body.get("temperature").is_none());
+}
+
+#[tokio::test]
+async fn test_chat_stream_pushes_text_and_rebuilds_response() {
+    let chunks = [
+        json!({"choices": [{"index": 0, "delta": {"role": "assistant", "content": "Let me "}}]}),
+        json!({"choices": [{"index": 0, "delta": {"content": "check."}}]}),
+        json!({"choices": [{"index": 0, "delta": {"tool_calls": [{"index": 0, "id": "call_1",
+            "type": "function", "function": {"name": "weather", "arguments": "{\"ci"}}]}}]}),
+        json!({"choices": [{"index": 0, "delta": {"tool_calls": [{"index": 0,
+            "function": {"arguments": "ty\": \"NYC\"}"}}]}}]}),
+        json!({"choices": [{"index": 0, "delta": {}, "finish_reason": "tool_calls"}]}),
+        json!({"choices": [], "usage": {"prompt_tokens": 30, "completion_tokens": 9}}),
+    ];
+    let mut body: String = chunks.iter().map(|c| format!("data: {c}\n\n")).collect();
+    body.push_str("data: [DONE]\n\n");
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(wiremock::matchers::body_partial_json(json!({
+            "stream": true,
+            "stream_options": {"include_usage": true}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .mount(&server)
+        .await;
+
+    let provider = OpenAIProvider::with_base_url("test_key".to_string(), None, server.uri());
+    let (sink, text) = StreamSink::new();
+    let result = provider
+        .chat_stream(&simple_chat_request("Weather in NYC?"), sink)
+        .await
+        .unwrap();
+
+    assert_eq!(*text.borrow(), "Let me check.");
+    assert_eq!(result.content.as_deref(), Some("Let me check."));
+    assert_eq!(result.tool_calls[0].id, "call_1");
+    assert_eq!(result.tool_calls[0].arguments, json!({"city": "NYC"}));
+    assert_eq!(result.input_tokens, Some(30));
+    assert_eq!(result.output_tokens, Some(9));
+    assert_eq!(result.finish_reason.as_deref(), Some("tool_calls"));
+}
+
+#[tokio::test]
+async fn test_keep_alive_sent_with_chat_and_warmup() {
+    use wiremock::matchers::body_partial_json;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(json!({"keep_alive": "30m"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "choices": [{"message": {"content": "hi"}, "finish_reason": "stop"}]
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let provider = OpenAIProvider::with_base_url("".to_string(), None, server.uri())
+        .with_keep_alive(Some("30m".to_string()));
+    provider.warmup().await.unwrap();
+    let req = ChatRequest::builder(vec![Message::user("hello")], 64).build();
+    assert_eq!(
+        provider.chat(&req).await.unwrap().content.as_deref(),
+        Some("hi")
+    );
+}
@@ -296,6 +296,7 @@ impl PromptGuidedToolsProvider {
             temperature: req.temperature,
             tool_choice: None,
             response_format: req.response_format.clone(),
+            reasoning_effort: req.reasoning_effort,
         }
     }
 }
@@ -0,0 +1,76 @@
+//! Minimal `text/event-stream` reader for streaming chat responses.
+
+use crate::errors::ProviderErrorHandler;
+use anyhow::Result;
+use futures_util::StreamExt;
+
+/// Splits a server-sent event stream into the `data` payloads of its events.
+/// Chunks may end anywhere, including inside a line.
+#[derive(Default)]
+pub(crate) struct SseParser {
+    line: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed the next chunk of the body; returns the events it completed.
+    pub(crate) fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            if byte != b'\n' {
+                self.line.push(byte);
+                continue;
+            }
+            let line = std::mem::take(&mut self.line);
+            let line = String::from_utf8_lossy(&line);
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            if line.is_empty() {
+                events.extend(self.dispatch());
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+        }
+        events
+    }
+
+    /// The last event, when the body ends without a blank line.
+    pub(crate) fn finish(mut self) -> Option<String> {
+        if !self.line.is_empty() {
+            self.feed(b"\n");
+        }
+        self.dispatch()
+    }
+
+    fn dispatch(&mut self) -> Option<String> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let data = std::mem::take(&mut self.data).join("\n");
+        (data != "[DONE]").then_some(data)
+    }
+}
+
+/// Read a streaming response to the end, calling `on_event` with the `data`
+/// payload of each event.
+pub(crate) async fn read_events(
+    resp: reqwest::Response,
+    provider: &str,
+    mut on_event: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut parser = SseParser::default();
+    let mut body = resp.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| ProviderErrorHandler::network_error(&e, provider))?;
+        for event in parser.feed(&chunk) {
+            on_event(&event)?;
+        }
+    }
+    if let Some(event) = parser.finish() {
+        on_event(&event)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,16 @@
+use super::*;
+
+#[test]
+fn test_events_split_across_chunks() {
+    let mut parser = SseParser::default();
+    assert!(
+        parser
+            .feed(b"event: message_start\r\ndata: {\"a\"")
+            .is_empty()
+    );
+    assert_eq!(parser.feed(b":1}\r\n\r\ndata: x\n"), vec!["{\"a\":1}"]);
+    assert_eq!(parser.feed(b"data: y\n\ndata: [DONE]\n\n"), vec!["x\ny"]);
+    assert!(parser.feed(b": keep-alive\n\n").is_empty());
+    assert!(parser.feed(b"data: tail").is_empty());
+    assert_eq!(parser.finish().as_deref(), Some("tail"));
+}
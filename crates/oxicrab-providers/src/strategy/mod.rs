@@ -1,5 +1,5 @@
 use oxicrab_core::config::schema::{
-    AnthropicOAuthConfig, ProviderConfig, ProvidersConfig, normalize_provider,
+    AnthropicOAuthConfig, ModelsConfig, ProviderConfig, ProvidersConfig, normalize_provider,
 };
 // Re-export model resolution functions for backward compatibility.
 use anyhow::Result;
@@ -38,6 +38,10 @@ const OPENAI_COMPAT_URLS: &[(&str, &str)] = &[
     ("ollama", "http://localhost:11434/v1/chat/completions"),
 ];
 
+/// Warmup timeout for local providers with `preloadOnStartup`: loading a
+/// large model from disk can take minutes.
+const PRELOAD_WARMUP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
 // ---------------------------------------------------------------------------
 // Provider factory — 2-tier resolution
 // ---------------------------------------------------------------------------
@@ -46,9 +50,12 @@ const OPENAI_COMPAT_URLS: &[(&str, &str)] = &[
 ///
 /// 1. **Prefix notation** — `provider/model` syntax (e.g. `groq/llama-3.1-70b`)
 /// 2. **Model-name inference** — known prefixes like `claude-*` → Anthropic
+///
+/// Model aliases (`models.aliases`) are resolved before either tier.
 pub struct ProviderFactory {
     providers_config: ProvidersConfig,
     oauth_config: AnthropicOAuthConfig,
+    models: ModelsConfig,
     db: Option<Arc<dyn OAuthTokenStore>>,
 }
 
@@ -57,6 +64,7 @@ impl ProviderFactory {
         Self {
             providers_config: config.providers.clone(),
             oauth_config: config.providers.anthropic_oauth.clone(),
+            models: config.models.clone(),
             db: None,
         }
     }
@@ -68,12 +76,14 @@ impl ProviderFactory {
         Self {
             providers_config: config.providers.clone(),
             oauth_config: config.providers.anthropic_oauth.clone(),
+            models: config.models.clone(),
             db,
         }
     }
 
     pub fn create_provider(&self, model: &str) -> Result<Arc<dyn LLMProvider>> {
-        // Step 1: Parse model reference for prefix notation
+        // Step 1: Resolve aliases, then parse the model reference for prefix notation
+        let model = self.models.resolve(model);
         let model_ref = parse_model_ref(model);
         let bare_model = model_ref.model;
 
@@ -275,22 +285,35 @@ impl ProviderFactory {
             display_name, model
         );
 
-        if provider_config.headers.is_empty() {
-            Ok(Arc::new(OpenAIProvider::with_config(
+        let mut provider = if provider_config.headers.is_empty() {
+            OpenAIProvider::with_config(
                 provider_config.api_key.clone(),
                 model.to_string(),
                 base_url,
                 display_name,
-            )))
+            )
         } else {
-            Ok(Arc::new(OpenAIProvider::with_config_and_headers(
+            OpenAIProvider::with_config_and_headers(
                 provider_config.api_key.clone(),
                 model.to_string(),
                 base_url,
                 display_name,
                 provider_config.headers.clone(),
-            )))
+            )
+        };
+        let local = match provider_name {
+            "ollama" => {
+                let ollama = &self.providers_config.ollama;
+                provider = provider.with_keep_alive(ollama.keep_alive.clone());
+                Some(ollama)
+            }
+            "vllm" => Some(&self.providers_config.vllm),
+            _ => None,
+        };
+        if local.is_some_and(|l| l.preload_on_startup) {
+            provider = provider.with_warmup_timeout(PRELOAD_WARMUP_TIMEOUT);
         }
+        Ok(Arc::new(provider))
     }
 
     /// Look up the `ProviderConfig` for a given canonical provider name.
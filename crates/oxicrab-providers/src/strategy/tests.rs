@@ -142,6 +142,7 @@ fn factory_with_config(providers: &ProvidersConfig) -> ProviderFactory {
     ProviderFactory {
         providers_config: providers.clone(),
         oauth_config: providers.anthropic_oauth.clone(),
+        models: ModelsConfig::default(),
         db: None,
     }
 }
@@ -190,6 +191,22 @@ fn test_vllm_routing_no_api_key() {
     assert_eq!(provider.default_model(), "my-model");
 }
 
+#[test]
+fn test_model_alias_routing() {
+    let mut config = ProvidersConfig::default();
+    config.groq.api_key = "gsk-test".to_string();
+    let mut factory = factory_with_config(&config);
+    factory
+        .models
+        .aliases
+        .insert("fast".to_string(), "groq/llama-3.1-8b".to_string());
+    let provider = factory.create_provider("fast").unwrap();
+    assert_eq!(provider.default_model(), "llama-3.1-8b");
+    // Names that are not aliases resolve as before
+    let provider = factory.create_provider("groq/llama-3.1-70b").unwrap();
+    assert_eq!(provider.default_model(), "llama-3.1-70b");
+}
+
 #[test]
 fn test_deepseek_no_api_key_errors() {
     let factory = factory_with_config(&ProvidersConfig::default());
@@ -49,6 +49,7 @@ pub enum DispatchSource {
     StaticRule,
     ConfigRule,
     RememberFastPath,
+    ForgetFastPath,
     Webhook,
     Cron,
     Command,
@@ -63,6 +64,7 @@ impl DispatchSource {
             Self::StaticRule => "rule",
             Self::ConfigRule => "config_rule",
             Self::RememberFastPath => "remember",
+            Self::ForgetFastPath => "forget",
             Self::Webhook => "webhook",
             Self::Cron => "cron",
             Self::Command => "command_dispatch",
@@ -74,6 +76,9 @@ impl DispatchSource {
 /// Callback type for detecting "remember" fast-path messages.
 type RememberChecker = Box<dyn Fn(&str) -> bool + Send + Sync>;
 
+/// Callback type for detecting "forget" fast-path messages.
+type ForgetChecker = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
 /// Priority-ordered message router.
 pub struct MessageRouter {
     static_rules: Vec<rules::StaticRule>,
@@ -82,6 +87,7 @@ pub struct MessageRouter {
     static_literal_to_index: HashMap<String, usize>,
     static_pattern_indices: Vec<usize>,
     remember_checker: Option<RememberChecker>,
+    forget_checker: Option<ForgetChecker>,
 }
 
 impl MessageRouter {
@@ -156,9 +162,17 @@ impl MessageRouter {
             static_literal_to_index,
             static_pattern_indices,
             remember_checker,
+            forget_checker: None,
         }
     }
 
+    /// Enable the forget fast path, checked just before the remember fast path.
+    #[must_use]
+    pub fn with_forget_checker(mut self, forget_checker: ForgetChecker) -> Self {
+        self.forget_checker = Some(forget_checker);
+        self
+    }
+
     /// Route a message. Checks in priority order:
     ///
     /// 1. Explicit `ActionDispatch` (button / webhook / cron)
@@ -166,7 +180,7 @@ impl MessageRouter {
     /// 3. Live `ActionDirective` match
     /// 4. Prefix command → `ConfigRule`
     /// 5. `StaticRule` match
-    /// 6. Remember fast path
+    /// 6. Forget / remember fast paths
     /// 7. Active tool context → `GuidedLLM`
     /// 8. `FullLLM`
     pub fn route(
@@ -356,7 +370,25 @@ impl MessageRouter {
             }
         }
 
-        // 6. Remember fast path.
+        // 6. Forget fast path, then remember fast path. Forget goes first so
+        // "forget that ..." never gets stored as a new memory.
+        trace!(
+            enabled = self.forget_checker.is_some(),
+            "router: priority=6 forget fast path"
+        );
+        if let Some(ref checker) = self.forget_checker
+            && checker(message)
+        {
+            trace!("router: forget fast path matched");
+            info!("router: decision=DirectDispatch tool=_forget source=ForgetFastPath");
+            metrics::record_direct_dispatch();
+            return RoutingDecision::DirectDispatch {
+                tool: "_forget".into(),
+                params: serde_json::json!({"content": message}),
+                source: DispatchSource::ForgetFastPath,
+                directive_index: None,
+            };
+        }
         trace!(
             enabled = self.remember_checker.is_some(),
             "router: priority=6 remember fast path"
@@ -808,6 +840,42 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_route_forget_fast_path_wins_over_remember() {
+        let router = MessageRouter::with_remember_checker(
+            vec![],
+            vec![],
+            "!".into(),
+            Some(Box::new(|msg: &str| msg.to_lowercase().contains("that"))),
+        )
+        .with_forget_checker(Box::new(|msg: &str| {
+            msg.to_lowercase().starts_with("forget that")
+        }));
+        let ctx = context::RouterContext::default();
+        let decision = router.route("forget that my favorite color is blue", &ctx, None);
+        match decision {
+            RoutingDecision::DirectDispatch {
+                tool,
+                params,
+                source: DispatchSource::ForgetFastPath,
+                ..
+            } => {
+                assert_eq!(tool, "_forget");
+                assert_eq!(params["content"], "forget that my favorite color is blue");
+            }
+            other => panic!("expected forget dispatch, got {other:?}"),
+        }
+
+        let decision = router.route("remember that I like tea", &ctx, None);
+        assert!(matches!(
+            decision,
+            RoutingDecision::DirectDispatch {
+                source: DispatchSource::RememberFastPath,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn test_route_empty_message() {
         let router = make_router();
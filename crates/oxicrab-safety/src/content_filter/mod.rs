@@ -0,0 +1,68 @@
+use oxicrab_core::config::schema::{ContentFilterAction, ContentFilterConfig};
+use regex::Regex;
+
+/// Why a message matched the content filter and what to do about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentFilterMatch {
+    pub action: ContentFilterAction,
+    /// The matching rule pattern, or `maxLength` for the length rule.
+    pub rule: String,
+}
+
+/// Operator-configured content policy for inbound messages: regex rules and
+/// an optional length limit, each with a drop/notice/flag action.
+pub struct ContentFilter {
+    rules: Vec<(Regex, ContentFilterAction)>,
+    max_length: Option<(usize, ContentFilterAction)>,
+    notice: String,
+}
+
+impl ContentFilter {
+    /// Compile the configured rules. Fails on an invalid pattern, which
+    /// config validation already rejects.
+    pub fn new(config: &ContentFilterConfig) -> Result<Self, regex::Error> {
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| Ok((Regex::new(&rule.pattern)?, rule.action)))
+            .collect::<Result<Vec<_>, regex::Error>>()?;
+        Ok(Self {
+            rules,
+            max_length: config.max_length.map(|max| (max, config.length_action)),
+            notice: config.notice.clone(),
+        })
+    }
+
+    /// Reply text for the `notice` action.
+    pub fn notice(&self) -> &str {
+        &self.notice
+    }
+
+    /// Check `text` against all rules. When several match, the strictest
+    /// action wins; `None` means the message passes.
+    pub fn check(&self, text: &str) -> Option<ContentFilterMatch> {
+        let mut verdict: Option<ContentFilterMatch> = None;
+        let mut consider = |action: ContentFilterAction, rule: &str| {
+            if verdict.as_ref().is_none_or(|v| action > v.action) {
+                verdict = Some(ContentFilterMatch {
+                    action,
+                    rule: rule.to_string(),
+                });
+            }
+        };
+        if let Some((max, action)) = self.max_length
+            && text.chars().count() > max
+        {
+            consider(action, "maxLength");
+        }
+        for (regex, action) in &self.rules {
+            if regex.is_match(text) {
+                consider(*action, regex.as_str());
+            }
+        }
+        verdict
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,82 @@
+use super::*;
+use oxicrab_core::config::schema::ContentFilterRule;
+
+fn filter(rules: &[(&str, ContentFilterAction)], max_length: Option<usize>) -> ContentFilter {
+    ContentFilter::new(&ContentFilterConfig {
+        enabled: true,
+        rules: rules
+            .iter()
+            .map(|(pattern, action)| ContentFilterRule {
+                pattern: (*pattern).to_string(),
+                action: *action,
+            })
+            .collect(),
+        max_length,
+        length_action: ContentFilterAction::Notice,
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_no_match_passes() {
+    let f = filter(
+        &[(r"(?i)\bfree crypto\b", ContentFilterAction::Drop)],
+        Some(100),
+    );
+    assert_eq!(f.check("what's the weather tomorrow?"), None);
+}
+
+#[test]
+fn test_pattern_match() {
+    let f = filter(&[(r"(?i)\bfree crypto\b", ContentFilterAction::Drop)], None);
+    let hit = f.check("Get FREE CRYPTO now!!!").unwrap();
+    assert_eq!(hit.action, ContentFilterAction::Drop);
+    assert_eq!(hit.rule, r"(?i)\bfree crypto\b");
+}
+
+#[test]
+fn test_max_length_counts_chars() {
+    let f = filter(&[], Some(3));
+    assert_eq!(f.check("äöü"), None);
+    let hit = f.check("äöüß").unwrap();
+    assert_eq!(hit.action, ContentFilterAction::Notice);
+    assert_eq!(hit.rule, "maxLength");
+}
+
+#[test]
+fn test_strictest_action_wins() {
+    let f = filter(
+        &[
+            ("casino", ContentFilterAction::Flag),
+            ("bonus", ContentFilterAction::Drop),
+            ("win", ContentFilterAction::Notice),
+        ],
+        None,
+    );
+    assert_eq!(
+        f.check("casino bonus, win big").unwrap().action,
+        ContentFilterAction::Drop
+    );
+    assert_eq!(
+        f.check("casino night").unwrap().action,
+        ContentFilterAction::Flag
+    );
+    assert_eq!(
+        f.check("casino win").unwrap().action,
+        ContentFilterAction::Notice
+    );
+}
+
+#[test]
+fn test_invalid_pattern_rejected() {
+    let config = ContentFilterConfig {
+        enabled: true,
+        rules: vec![ContentFilterRule {
+            pattern: "(unclosed".to_string(),
+            action: ContentFilterAction::Drop,
+        }],
+        ..Default::default()
+    };
+    assert!(ContentFilter::new(&config).is_err());
+}
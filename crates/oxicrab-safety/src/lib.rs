@@ -1,5 +1,7 @@
+pub mod content_filter;
 pub mod leak_detector;
 pub mod prompt_guard;
 
+pub use content_filter::ContentFilter;
 pub use leak_detector::LeakDetector;
 pub use prompt_guard::PromptGuard;
@@ -1,5 +1,7 @@
+use std::borrow::Cow;
 use std::sync::LazyLock;
 
+use oxicrab_core::config::schema::{PromptGuardCategory, PromptGuardPattern};
 use regex::Regex;
 use tracing::warn;
 
@@ -12,17 +14,29 @@ pub enum InjectionCategory {
     Jailbreak,
 }
 
+impl From<PromptGuardCategory> for InjectionCategory {
+    fn from(category: PromptGuardCategory) -> Self {
+        match category {
+            PromptGuardCategory::RoleSwitch => Self::RoleSwitch,
+            PromptGuardCategory::InstructionOverride => Self::InstructionOverride,
+            PromptGuardCategory::SecretExtraction => Self::SecretExtraction,
+            PromptGuardCategory::Jailbreak => Self::Jailbreak,
+        }
+    }
+}
+
 /// A prompt injection pattern match.
 #[derive(Debug)]
 pub struct InjectionMatch {
     pub category: InjectionCategory,
-    pub pattern_name: &'static str,
+    /// Built-in pattern name, or the `name` of a custom pattern.
+    pub pattern_name: Cow<'static, str>,
     pub matched_text: String,
 }
 
 struct GuardPattern {
     category: InjectionCategory,
-    name: &'static str,
+    name: Cow<'static, str>,
     regex: Regex,
 }
 
@@ -110,20 +124,35 @@ static GUARD_PATTERNS: LazyLock<Vec<GuardPattern>> = LazyLock::new(|| {
 
     pattern_defs
         .into_iter()
-        .filter_map(|(category, name, pattern)| match Regex::new(pattern) {
-            Ok(regex) => Some(GuardPattern {
-                category,
-                name,
-                regex,
-            }),
-            Err(e) => {
-                warn!("failed to compile prompt guard pattern '{}': {}", name, e);
-                None
-            }
-        })
+        .filter_map(|(category, name, pattern)| compile(category, Cow::Borrowed(name), pattern))
         .collect()
 });
 
+fn compile(
+    category: InjectionCategory,
+    name: Cow<'static, str>,
+    pattern: &str,
+) -> Option<GuardPattern> {
+    match Regex::new(pattern) {
+        Ok(regex) => Some(GuardPattern {
+            category,
+            name,
+            regex,
+        }),
+        Err(e) => {
+            warn!("failed to compile prompt guard pattern '{}': {}", name, e);
+            None
+        }
+    }
+}
+
+fn compile_custom(patterns: &[PromptGuardPattern]) -> Vec<GuardPattern> {
+    patterns
+        .iter()
+        .filter_map(|p| compile(p.category.into(), Cow::Owned(p.name.clone()), &p.regex))
+        .collect()
+}
+
 /// Regex-based prompt injection detection guard.
 ///
 /// Scans text for patterns across 4 categories:
@@ -132,19 +161,35 @@ static GUARD_PATTERNS: LazyLock<Vec<GuardPattern>> = LazyLock::new(|| {
 /// 3. Secret extraction — attempts to extract system prompts or secrets
 /// 4. Jailbreak patterns — common jailbreak prefixes
 ///
-/// Patterns are compiled once globally via `LazyLock` and shared across
-/// all instances. Disabled by default; enabled via `agents.defaults.promptGuard.enabled`.
-pub struct PromptGuard;
+/// Built-in patterns are compiled once globally via `LazyLock` and shared
+/// across all instances; custom patterns (`agents.defaults.promptGuard.extraPatterns`)
+/// are compiled per instance. Enabled via `agents.defaults.promptGuard.enabled`.
+pub struct PromptGuard {
+    builtins: bool,
+    custom: Vec<GuardPattern>,
+}
 
 impl Default for PromptGuard {
     fn default() -> Self {
-        Self::new()
+        Self::new(&[])
     }
 }
 
 impl PromptGuard {
-    pub fn new() -> Self {
-        Self
+    /// Built-in patterns plus `extra_patterns`.
+    pub fn new(extra_patterns: &[PromptGuardPattern]) -> Self {
+        Self {
+            builtins: true,
+            custom: compile_custom(extra_patterns),
+        }
+    }
+
+    /// Only `patterns`, without the built-ins.
+    pub fn with_patterns(patterns: &[PromptGuardPattern]) -> Self {
+        Self {
+            builtins: false,
+            custom: compile_custom(patterns),
+        }
     }
 
     /// Strip zero-width, invisible, and combining Unicode characters that attackers
@@ -212,11 +257,12 @@ impl PromptGuard {
     pub fn scan(&self, text: &str) -> Vec<InjectionMatch> {
         let normalized = Self::normalize(text);
         let mut matches = Vec::new();
-        for pattern in GUARD_PATTERNS.iter() {
+        let builtins: &[GuardPattern] = if self.builtins { &GUARD_PATTERNS } else { &[] };
+        for pattern in builtins.iter().chain(&self.custom) {
             for m in pattern.regex.find_iter(&normalized) {
                 matches.push(InjectionMatch {
                     category: pattern.category.clone(),
-                    pattern_name: pattern.name,
+                    pattern_name: pattern.name.clone(),
                     matched_text: m.as_str().to_string(),
                 });
             }
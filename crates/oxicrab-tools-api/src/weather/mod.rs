@@ -1,6 +1,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use oxicrab_core::actions;
+use oxicrab_core::config::schema::{WeatherAlertsConfig, WeatherUnits};
 use oxicrab_core::tools::base::{ExecutionContext, SubagentAccess, ToolCapabilities, ToolCategory};
 use oxicrab_core::tools::base::{Tool, ToolResult};
 use reqwest::Client;
@@ -24,8 +25,8 @@ impl WeatherTool {
         }
     }
 
-    #[cfg(test)]
-    fn with_base_url(api_key: String, base_url: String) -> Self {
+    /// Use a different API root, e.g. a mock server.
+    pub fn with_base_url(api_key: String, base_url: String) -> Self {
         Self {
             api_key,
             base_url,
@@ -33,7 +34,8 @@ impl WeatherTool {
         }
     }
 
-    async fn current(&self, location: &str, units: &str) -> Result<String> {
+    /// Raw current conditions at `location`.
+    async fn current_json(&self, location: &str, units: &str) -> Result<Value> {
         let resp = self
             .client
             .get(format!("{}/weather", self.base_url))
@@ -48,7 +50,12 @@ impl WeatherTool {
             let msg = json["message"].as_str().unwrap_or("Unknown error");
             anyhow::bail!("OpenWeatherMap: {msg}");
         }
+        Ok(json)
+    }
 
+    /// Current conditions at `location`, as shown by the `current` action.
+    pub async fn current(&self, location: &str, units: &str) -> Result<String> {
+        let json = self.current_json(location, units).await?;
         let temp = json["main"]["temp"].as_f64().unwrap_or_default();
         let feels_like = json["main"]["feels_like"].as_f64().unwrap_or_default();
         let humidity = json["main"]["humidity"].as_u64().unwrap_or(0);
@@ -59,11 +66,7 @@ impl WeatherTool {
         let city = json["name"].as_str().unwrap_or(location);
         let country = json["sys"]["country"].as_str().unwrap_or_default();
 
-        let unit_label = match units {
-            "imperial" => "°F",
-            "metric" => "°C",
-            _ => "K",
-        };
+        let unit_label = temp_unit_label(units);
         let wind_unit = if units == "imperial" { "mph" } else { "m/s" };
 
         Ok(format!(
@@ -71,7 +74,21 @@ impl WeatherTool {
         ))
     }
 
-    async fn forecast(&self, location: &str, units: &str) -> Result<String> {
+    /// One-line current conditions at `location`, e.g. `12°C, light rain`.
+    pub async fn current_brief(&self, location: &str, units: &str) -> Result<String> {
+        let json = self.current_json(location, units).await?;
+        let temp = json["main"]["temp"].as_f64().unwrap_or_default();
+        let description = json["weather"][0]["description"]
+            .as_str()
+            .unwrap_or("unknown");
+        Ok(format!(
+            "{temp:.0}{}, {description}",
+            temp_unit_label(units)
+        ))
+    }
+
+    /// Raw 24h forecast (eight 3-hour slots).
+    async fn forecast_json(&self, location: &str, units: &str) -> Result<Value> {
         let resp = self
             .client
             .get(format!("{}/forecast", self.base_url))
@@ -91,6 +108,22 @@ impl WeatherTool {
             let msg = json["message"].as_str().unwrap_or("Unknown error");
             anyhow::bail!("OpenWeatherMap: {msg}");
         }
+        Ok(json)
+    }
+
+    /// Alerts for the next 24 hours at `location` under `config`.
+    pub async fn alerts(
+        &self,
+        location: &str,
+        units: WeatherUnits,
+        config: &WeatherAlertsConfig,
+    ) -> Result<Vec<WeatherAlert>> {
+        let json = self.forecast_json(location, &units.to_string()).await?;
+        Ok(forecast_alerts(&json, location, units, config))
+    }
+
+    async fn forecast(&self, location: &str, units: &str) -> Result<String> {
+        let json = self.forecast_json(location, units).await?;
 
         let city = json["city"]["name"].as_str().unwrap_or(location);
         let country = json["city"]["country"].as_str().unwrap_or_default();
@@ -125,6 +158,119 @@ impl WeatherTool {
     }
 }
 
+/// Forecast conditions that cross a configured threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeatherAlert {
+    /// The configured location the alert is for.
+    pub location: String,
+    /// `heat`, `cold`, `wind` or `severe`.
+    pub kind: &'static str,
+    /// Forecast slot the condition is first expected in (`YYYY-MM-DD HH:MM:SS`).
+    pub at: String,
+    pub message: String,
+}
+
+impl WeatherAlert {
+    /// Identifies the alert for deduplication: one per location, kind and day.
+    pub fn key(&self) -> String {
+        let day = self.at.get(..10).unwrap_or(&self.at);
+        format!("{}|{}|{}", self.location, self.kind, day)
+    }
+}
+
+fn temp_unit_label(units: &str) -> &'static str {
+    match units {
+        "imperial" => "°F",
+        "metric" => "°C",
+        _ => "K",
+    }
+}
+
+/// OpenWeatherMap condition codes treated as severe: thunderstorms, heavy or
+/// freezing rain, heavy snow, squalls and tornadoes.
+fn is_severe(code: u64) -> bool {
+    matches!(code, 200..=232 | 502..=504 | 511 | 522 | 602 | 622 | 771 | 781)
+}
+
+/// Evaluate a `/forecast` response against `config`, reporting the earliest
+/// slot crossing each threshold.
+pub fn forecast_alerts(
+    json: &Value,
+    location: &str,
+    units: WeatherUnits,
+    config: &WeatherAlertsConfig,
+) -> Vec<WeatherAlert> {
+    let (temp_unit, wind_unit) = match units {
+        WeatherUnits::Imperial => ("°F", "mph"),
+        WeatherUnits::Metric => ("°C", "m/s"),
+    };
+    let city = json["city"]["name"].as_str().unwrap_or(location);
+    let list = json["list"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut alerts: Vec<WeatherAlert> = Vec::new();
+    let mut push = |kind: &'static str, at: &str, message: String| {
+        if !alerts.iter().any(|a| a.kind == kind) {
+            alerts.push(WeatherAlert {
+                location: location.to_string(),
+                kind,
+                at: at.to_string(),
+                message,
+            });
+        }
+    };
+    for entry in list {
+        let at = entry["dt_txt"].as_str().unwrap_or("?");
+        let temp = entry["main"]["temp"].as_f64();
+        let wind = entry["wind"]["speed"].as_f64();
+        if let (Some(max), Some(t)) = (config.max_temp, temp)
+            && t > max
+        {
+            push(
+                "heat",
+                at,
+                format!(
+                    "{city}: {t:.0}{temp_unit} expected around {at} (above {max:.0}{temp_unit})"
+                ),
+            );
+        }
+        if let (Some(min), Some(t)) = (config.min_temp, temp)
+            && t < min
+        {
+            push(
+                "cold",
+                at,
+                format!(
+                    "{city}: {t:.0}{temp_unit} expected around {at} (below {min:.0}{temp_unit})"
+                ),
+            );
+        }
+        if let (Some(max), Some(w)) = (config.max_wind, wind)
+            && w > max
+        {
+            push(
+                "wind",
+                at,
+                format!(
+                    "{city}: wind of {w:.0} {wind_unit} expected around {at} (above {max:.0} {wind_unit})"
+                ),
+            );
+        }
+        if config.severe_conditions
+            && let Some(code) = entry["weather"][0]["id"].as_u64()
+            && is_severe(code)
+        {
+            let desc = entry["weather"][0]["description"]
+                .as_str()
+                .unwrap_or("severe weather");
+            push("severe", at, format!("{city}: {desc} expected around {at}"));
+        }
+    }
+    alerts
+}
+
 #[async_trait]
 impl Tool for WeatherTool {
     fn name(&self) -> &'static str {
@@ -100,6 +100,27 @@ async fn test_current_weather_imperial_units() {
     assert!(result.content.contains("mph"));
 }
 
+#[tokio::test]
+async fn test_current_brief() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/weather"))
+        .and(query_param("units", "metric"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "main": {"temp": 11.6, "feels_like": 10.0, "humidity": 80},
+            "weather": [{"description": "light rain"}],
+            "wind": {"speed": 3.0},
+            "name": "London",
+            "sys": {"country": "GB"}
+        })))
+        .mount(&server)
+        .await;
+
+    let tool = WeatherTool::with_base_url("test_key".to_string(), server.uri());
+    let brief = tool.current_brief("London", "metric").await.unwrap();
+    assert_eq!(brief, "12°C, light rain");
+}
+
 #[tokio::test]
 async fn test_forecast_success() {
     let server = MockServer::start().await;
@@ -289,3 +310,97 @@ fn test_weather_actions_match_schema() {
         );
     }
 }
+
+fn forecast_slot(at: &str, temp: f64, wind: f64, code: u64, desc: &str) -> Value {
+    serde_json::json!({
+        "dt_txt": at,
+        "main": {"temp": temp},
+        "wind": {"speed": wind},
+        "weather": [{"id": code, "description": desc}],
+    })
+}
+
+#[test]
+fn test_forecast_alerts_reports_first_slot_per_threshold() {
+    let json = serde_json::json!({
+        "city": {"name": "Phoenix"},
+        "list": [
+            forecast_slot("2026-07-01 09:00:00", 90.0, 5.0, 800, "clear sky"),
+            forecast_slot("2026-07-01 12:00:00", 104.0, 8.0, 800, "clear sky"),
+            forecast_slot("2026-07-01 15:00:00", 108.0, 30.0, 211, "thunderstorm"),
+            forecast_slot("2026-07-01 18:00:00", 101.0, 35.0, 202, "heavy thunderstorm"),
+        ]
+    });
+    let config = WeatherAlertsConfig {
+        max_temp: Some(100.0),
+        min_temp: Some(32.0),
+        max_wind: Some(25.0),
+        ..Default::default()
+    };
+
+    let alerts = forecast_alerts(&json, "Phoenix,US", WeatherUnits::Imperial, &config);
+    let kinds: Vec<&str> = alerts.iter().map(|a| a.kind).collect();
+    assert_eq!(kinds, ["heat", "wind", "severe"]);
+    assert_eq!(alerts[0].at, "2026-07-01 12:00:00");
+    assert_eq!(
+        alerts[0].message,
+        "Phoenix: 104°F expected around 2026-07-01 12:00:00 (above 100°F)"
+    );
+    assert_eq!(
+        alerts[2].message,
+        "Phoenix: thunderstorm expected around 2026-07-01 15:00:00"
+    );
+    assert_eq!(alerts[0].key(), "Phoenix,US|heat|2026-07-01");
+}
+
+#[test]
+fn test_forecast_alerts_without_thresholds() {
+    let json = serde_json::json!({
+        "city": {"name": "Oslo"},
+        "list": [forecast_slot("2026-01-05 06:00:00", -12.0, 4.0, 602, "heavy snow")]
+    });
+    let mut config = WeatherAlertsConfig::default();
+    let alerts = forecast_alerts(&json, "Oslo", WeatherUnits::Metric, &config);
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].kind, "severe");
+
+    config.severe_conditions = false;
+    assert!(forecast_alerts(&json, "Oslo", WeatherUnits::Metric, &config).is_empty());
+
+    config.min_temp = Some(-10.0);
+    let alerts = forecast_alerts(&json, "Oslo", WeatherUnits::Metric, &config);
+    assert_eq!(
+        alerts[0].message,
+        "Oslo: -12°C expected around 2026-01-05 06:00:00 (below -10°C)"
+    );
+}
+
+#[tokio::test]
+async fn test_alerts_fetches_forecast_in_configured_units() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/forecast"))
+        .and(query_param("q", "Oslo"))
+        .and(query_param("units", "metric"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "city": {"name": "Oslo"},
+            "list": [forecast_slot("2026-01-05 06:00:00", -2.0, 4.0, 781, "tornado")]
+        })))
+        .mount(&server)
+        .await;
+
+    let tool = WeatherTool::with_base_url("test_key".to_string(), server.uri());
+    let alerts = tool
+        .alerts(
+            "Oslo",
+            WeatherUnits::Metric,
+            &WeatherAlertsConfig::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(
+        alerts[0].message,
+        "Oslo: tornado expected around 2026-01-05 06:00:00"
+    );
+}
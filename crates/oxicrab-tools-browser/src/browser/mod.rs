@@ -3,6 +3,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use chromiumoxide::Page;
 use chromiumoxide::browser::{Browser, BrowserConfig as ChromeBrowserConfig};
+use chromiumoxide::cdp::browser_protocol::page::CaptureScreenshotFormat;
 use chromiumoxide::page::ScreenshotParams;
 use futures_util::StreamExt;
 use oxicrab_core::actions;
@@ -15,6 +16,10 @@ use std::time::Duration;
 use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
+mod screenshot;
+
+pub use screenshot::{ScreenshotArchive, ScreenshotTool};
+
 struct BrowserSession {
     browser: Browser,
     page: Page,
@@ -49,6 +54,41 @@ async fn is_url_blocked(session: &BrowserSession) -> Option<String> {
     }
 }
 
+/// Capture the whole page as PNG.
+async fn capture_page(page: &Page) -> Result<Vec<u8>, String> {
+    // Query actual content dimensions, clamped to prevent OOM.
+    // full_page(true) is NOT used because chromiumoxide overwrites the clip
+    // viewport when full_page is set, making the height clamp ineffective.
+    let content_height: f64 = page
+        .evaluate("Math.min(document.documentElement.scrollHeight || document.body.scrollHeight || 768, 10080)")
+        .await
+        .ok()
+        .and_then(|v| v.into_value().ok())
+        .unwrap_or(768.0);
+    let content_width: f64 = page
+        .evaluate("Math.min(document.documentElement.scrollWidth || document.body.scrollWidth || 1280, 1920)")
+        .await
+        .ok()
+        .and_then(|v| v.into_value().ok())
+        .unwrap_or(1280.0);
+
+    page.screenshot(
+        ScreenshotParams::builder()
+            .format(CaptureScreenshotFormat::Png)
+            .clip(chromiumoxide::cdp::browser_protocol::page::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: content_width,
+                height: content_height,
+                scale: 1.0,
+            })
+            .build(),
+    )
+    .await
+    .map_err(|e| format!("screenshot failed: {e}"))
+}
+
+#[derive(Clone)]
 pub struct BrowserTool {
     session: Arc<Mutex<Option<BrowserSession>>>,
     headless: bool,
@@ -363,43 +403,7 @@ impl BrowserTool {
 
         let result = self
             .with_timeout(async {
-                // Query actual content dimensions, clamped to prevent OOM.
-                // full_page(true) is NOT used because chromiumoxide overwrites the clip
-                // viewport when full_page is set, making the height clamp ineffective.
-                let content_height: f64 = session
-                    .page
-                    .evaluate("Math.min(document.documentElement.scrollHeight || document.body.scrollHeight || 768, 10080)")
-                    .await
-                    .ok()
-                    .and_then(|v| v.into_value().ok())
-                    .unwrap_or(768.0);
-                let content_width: f64 = session
-                    .page
-                    .evaluate("Math.min(document.documentElement.scrollWidth || document.body.scrollWidth || 1280, 1920)")
-                    .await
-                    .ok()
-                    .and_then(|v| v.into_value().ok())
-                    .unwrap_or(1280.0);
-
-                let bytes = session
-                    .page
-                    .screenshot(
-                        ScreenshotParams::builder()
-                            .format(
-                                chromiumoxide::cdp::browser_protocol::page::CaptureScreenshotFormat::Png,
-                            )
-                            .clip(chromiumoxide::cdp::browser_protocol::page::Viewport {
-                                x: 0.0,
-                                y: 0.0,
-                                width: content_width,
-                                height: content_height,
-                                scale: 1.0,
-                            })
-                            .build(),
-                    )
-                    .await
-                    .map_err(|e| format!("screenshot failed: {e}"))?;
-
+                let bytes = capture_page(&session.page).await?;
                 let path = save_media_file(&bytes, "screenshot", "png")
                     .map_err(|e| format!("failed to save screenshot: {e}"))?;
                 Ok(format!(
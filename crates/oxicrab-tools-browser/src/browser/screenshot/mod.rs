@@ -0,0 +1,231 @@
+use super::{BrowserTool, capture_page, is_url_blocked};
+use crate::utils::media::save_media_file;
+use anyhow::Result;
+use async_trait::async_trait;
+use chromiumoxide::Page;
+use chromiumoxide::cdp::browser_protocol::page::CaptureScreenshotFormat;
+use oxicrab_core::tools::base::{ExecutionContext, ToolCapabilities, ToolCategory};
+use oxicrab_core::tools::base::{Tool, ToolResult};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+const SESSION_KEY_META_KEY: &str = "session_key";
+const HIGHLIGHT_ATTR: &str = "data-oxicrab-highlight";
+const HIGHLIGHT_STYLE_ID: &str = "oxicrab-highlight-style";
+
+/// Keeps a copy of each screenshot in the workspace file manifest.
+pub trait ScreenshotArchive: Send + Sync {
+    /// Copy the saved media file into the workspace, register it, and return
+    /// the workspace path.
+    fn archive(&self, media_path: &Path, session_key: Option<&str>) -> Result<PathBuf>;
+}
+
+/// One-step page capture on top of the browser tool's session: optionally
+/// opens a URL, outlines elements, and attaches the PNG to the reply.
+pub struct ScreenshotTool {
+    browser: BrowserTool,
+    archive: Option<Arc<dyn ScreenshotArchive>>,
+}
+
+impl ScreenshotTool {
+    /// Share `browser`'s session so pages opened there can be captured here.
+    pub fn new(browser: &BrowserTool, archive: Option<Arc<dyn ScreenshotArchive>>) -> Self {
+        Self {
+            browser: browser.clone(),
+            archive,
+        }
+    }
+
+    async fn capture(
+        &self,
+        selector: Option<&str>,
+        highlight: Option<&str>,
+        session_key: &str,
+    ) -> Result<ToolResult> {
+        let mut guard = self.browser.session.lock().await;
+        let Some(session) = guard.as_mut() else {
+            return Ok(ToolResult::error(
+                "no browser session. Pass 'url' or use the browser tool's 'open' action first"
+                    .to_string(),
+            ));
+        };
+
+        if let Some(err) = is_url_blocked(session).await {
+            return Ok(ToolResult::error(err));
+        }
+
+        let page = &session.page;
+        let result = self
+            .browser
+            .with_timeout(async {
+                if let Some(sel) = highlight
+                    && apply_highlight(page, sel).await? == 0
+                {
+                    clear_highlight(page).await;
+                    return Err(format!("no elements match highlight selector '{sel}'"));
+                }
+                let bytes = capture_target(page, selector).await;
+                if highlight.is_some() {
+                    clear_highlight(page).await;
+                }
+                let bytes = bytes?;
+                let path = save_media_file(&bytes, "screenshot", "png")
+                    .map_err(|e| format!("failed to save screenshot: {e}"))?;
+                Ok((path, bytes.len()))
+            })
+            .await;
+
+        let (path, size) = match result {
+            Ok(saved) => saved,
+            Err(e) => return Ok(ToolResult::error(e)),
+        };
+        let mut out = format!(
+            "Screenshot saved to: {path}\nSize: {size} bytes\nThe screenshot will be attached to your response automatically."
+        );
+        if let Some(ref archive) = self.archive {
+            match archive.archive(Path::new(&path), Some(session_key)) {
+                Ok(copy) => {
+                    let _ = write!(out, "\nWorkspace copy: {}", copy.display());
+                }
+                Err(e) => warn!("failed to archive screenshot in workspace: {e}"),
+            }
+        }
+        Ok(ToolResult::new(out))
+    }
+}
+
+/// Capture one element when `selector` is set, otherwise the whole page.
+async fn capture_target(page: &Page, selector: Option<&str>) -> Result<Vec<u8>, String> {
+    let Some(sel) = selector else {
+        return capture_page(page).await;
+    };
+    page.find_element(sel)
+        .await
+        .map_err(|e| format!("element not found '{sel}': {e}"))?
+        .screenshot(CaptureScreenshotFormat::Png)
+        .await
+        .map_err(|e| format!("element screenshot failed: {e}"))
+}
+
+/// JavaScript that outlines every element matching `selector` and returns how
+/// many matched.
+fn highlight_js(selector: &str) -> String {
+    format!(
+        r"
+        (() => {{
+            const els = document.querySelectorAll({selector});
+            if (els.length && !document.getElementById('{HIGHLIGHT_STYLE_ID}')) {{
+                const style = document.createElement('style');
+                style.id = '{HIGHLIGHT_STYLE_ID}';
+                style.textContent = '[{HIGHLIGHT_ATTR}] {{ outline: 3px solid #ff2d55 !important; outline-offset: 2px !important; }}';
+                document.head.appendChild(style);
+            }}
+            els.forEach(el => el.setAttribute('{HIGHLIGHT_ATTR}', ''));
+            return els.length;
+        }})()
+        ",
+        selector = serde_json::to_string(selector).unwrap_or_default(),
+    )
+}
+
+/// JavaScript that removes everything `highlight_js` added.
+fn clear_highlight_js() -> String {
+    format!(
+        r"
+        (() => {{
+            document.querySelectorAll('[{HIGHLIGHT_ATTR}]').forEach(el => el.removeAttribute('{HIGHLIGHT_ATTR}'));
+            document.getElementById('{HIGHLIGHT_STYLE_ID}')?.remove();
+        }})()
+        "
+    )
+}
+
+async fn apply_highlight(page: &Page, selector: &str) -> Result<u64, String> {
+    page.evaluate(highlight_js(selector))
+        .await
+        .map_err(|e| format!("highlight failed: {e}"))?
+        .into_value()
+        .map_err(|e| format!("failed to parse highlight result: {e}"))
+}
+
+async fn clear_highlight(page: &Page) {
+    if let Err(e) = page.evaluate(clear_highlight_js()).await {
+        debug!("failed to clear screenshot highlight: {e}");
+    }
+}
+
+fn non_empty<'a>(params: &'a Value, key: &str) -> Option<&'a str> {
+    params[key].as_str().filter(|s| !s.trim().is_empty())
+}
+
+#[async_trait]
+impl Tool for ScreenshotTool {
+    fn name(&self) -> &'static str {
+        "screenshot"
+    }
+
+    fn description(&self) -> &'static str {
+        "Take a screenshot of the current browser page, or of one element, and attach it to \
+         your response. Pass 'url' to open a page first. Use 'highlight' to outline elements \
+         you want the user to notice."
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            network_outbound: true,
+            category: ToolCategory::Web,
+            ..Default::default()
+        }
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "URL to open before capturing. Omit to capture the page already open in the browser"
+                },
+                "selector": {
+                    "type": "string",
+                    "description": "CSS selector of a single element to capture instead of the whole page"
+                },
+                "highlight": {
+                    "type": "string",
+                    "description": "CSS selector of elements to outline in the screenshot"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, params: Value, ctx: &ExecutionContext) -> Result<ToolResult> {
+        if let Some(url) = non_empty(&params, "url") {
+            let opened = self.browser.action_open(url).await?;
+            if opened.is_error {
+                return Ok(opened);
+            }
+        }
+
+        let session_key = ctx
+            .metadata
+            .get(SESSION_KEY_META_KEY)
+            .and_then(Value::as_str)
+            .map_or_else(
+                || format!("{}:{}", ctx.channel, ctx.chat_id),
+                str::to_string,
+            );
+        self.capture(
+            non_empty(&params, "selector"),
+            non_empty(&params, "highlight"),
+            &session_key,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests;
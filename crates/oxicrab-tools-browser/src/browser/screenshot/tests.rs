@@ -0,0 +1,60 @@
+use super::*;
+use std::sync::Mutex as StdMutex;
+
+#[derive(Default)]
+struct RecordingArchive {
+    calls: StdMutex<Vec<PathBuf>>,
+}
+
+impl ScreenshotArchive for RecordingArchive {
+    fn archive(&self, media_path: &Path, _session_key: Option<&str>) -> Result<PathBuf> {
+        self.calls.lock().unwrap().push(media_path.to_path_buf());
+        Ok(media_path.to_path_buf())
+    }
+}
+
+#[tokio::test]
+async fn test_screenshot_no_session() {
+    let archive = Arc::new(RecordingArchive::default());
+    let tool = ScreenshotTool::new(&BrowserTool::for_testing(), Some(archive.clone()));
+    let result = tool
+        .execute(serde_json::json!({}), &ExecutionContext::default())
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("no browser session"));
+    assert!(archive.calls.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_screenshot_url_ssrf_blocked() {
+    let tool = ScreenshotTool::new(&BrowserTool::for_testing(), None);
+    let params = serde_json::json!({"url": "http://169.254.169.254/latest/meta-data"});
+    let result = tool
+        .execute(params, &ExecutionContext::default())
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("security policy") || result.content.contains("blocked"));
+}
+
+#[test]
+fn test_highlight_js_escapes_selector() {
+    let js = highlight_js("a[href='x'] \"quoted\"");
+    assert!(js.contains(r#"querySelectorAll("a[href='x'] \"quoted\"")"#));
+    assert!(js.contains(HIGHLIGHT_ATTR));
+    assert!(clear_highlight_js().contains(HIGHLIGHT_STYLE_ID));
+}
+
+#[test]
+fn test_screenshot_tool_metadata() {
+    let tool = ScreenshotTool::new(&BrowserTool::for_testing(), None);
+    assert_eq!(tool.name(), "screenshot");
+    let caps = tool.capabilities();
+    assert!(caps.built_in);
+    assert!(caps.actions.is_empty());
+    let params = tool.parameters();
+    for key in ["url", "selector", "highlight"] {
+        assert!(params["properties"][key].is_object(), "missing {key}");
+    }
+}
@@ -3,11 +3,18 @@
 pub mod browser;
 mod utils;
 
+pub use browser::ScreenshotArchive;
+
 use oxicrab_core::config::schema::BrowserConfig;
 use oxicrab_core::tools::base::Tool;
 use std::sync::Arc;
 
-/// Create the browser tool.
-pub fn create_browser_tool(config: &BrowserConfig) -> Arc<dyn Tool> {
-    Arc::new(browser::BrowserTool::new(config))
+/// Create the browser tool and the screenshot tool that shares its session.
+pub fn create_browser_tools(
+    config: &BrowserConfig,
+    archive: Option<Arc<dyn ScreenshotArchive>>,
+) -> Vec<Arc<dyn Tool>> {
+    let browser = browser::BrowserTool::new(config);
+    let screenshot = browser::ScreenshotTool::new(&browser, archive);
+    vec![Arc::new(browser), Arc::new(screenshot)]
 }
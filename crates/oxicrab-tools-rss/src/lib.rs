@@ -96,8 +96,8 @@ impl Tool for RssTool {
         false
     }
 
-    fn execution_timeout(&self) -> Duration {
-        Duration::from_mins(5)
+    fn execution_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_mins(5))
     }
 
     fn capabilities(&self) -> ToolCapabilities {
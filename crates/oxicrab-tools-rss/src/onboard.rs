@@ -169,6 +169,7 @@ pub fn handle_onboard(
                             targets: vec![CronTarget {
                                 channel: ctx.channel.clone(),
                                 to: ctx.chat_id.clone(),
+                                message_override: None,
                             }],
                         },
                         state: CronJobState::default(),
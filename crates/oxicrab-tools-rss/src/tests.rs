@@ -123,7 +123,7 @@ fn test_tool_capabilities() {
 #[test]
 fn test_execution_timeout() {
     let tool = RssTool::new_for_test();
-    assert_eq!(tool.execution_timeout(), Duration::from_mins(5));
+    assert_eq!(tool.execution_timeout(), Some(Duration::from_mins(5)));
 }
 
 #[test]
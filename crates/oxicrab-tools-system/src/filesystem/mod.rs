@@ -11,10 +11,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::warn;
 
+mod patch;
+
 /// Maximum file size that `read_file` will load (10 MB).
 const MAX_READ_BYTES: u64 = 10 * 1024 * 1024;
 
-fn resolve_path(file_path: &Path) -> PathBuf {
+pub(crate) fn resolve_path(file_path: &Path) -> PathBuf {
     file_path.canonicalize().unwrap_or_else(|_| {
         if let (Some(parent), Some(file_name)) = (file_path.parent(), file_path.file_name())
             && let Ok(parent_resolved) = parent.canonicalize()
@@ -25,7 +27,10 @@ fn resolve_path(file_path: &Path) -> PathBuf {
     })
 }
 
-fn check_path_allowed(file_path: &Path, allowed_roots: Option<&Vec<PathBuf>>) -> Result<()> {
+pub(crate) fn check_path_allowed(
+    file_path: &Path,
+    allowed_roots: Option<&Vec<PathBuf>>,
+) -> Result<()> {
     if let Some(roots) = allowed_roots {
         let resolved = resolve_path(file_path);
         for root in roots {
@@ -49,6 +54,28 @@ fn check_path_allowed(file_path: &Path, allowed_roots: Option<&Vec<PathBuf>>) ->
     Ok(())
 }
 
+/// Reject paths inside any of `denied` (files or directories), even when an
+/// allowed root contains them. Symlinks are resolved first, so a link
+/// inside the workspace cannot reach a denied location.
+pub(crate) fn check_path_not_denied(file_path: &Path, denied: &[PathBuf]) -> Result<()> {
+    if denied.is_empty() {
+        return Ok(());
+    }
+    let resolved = resolve_path(file_path);
+    for path in denied {
+        let denied_resolved = path
+            .canonicalize()
+            .unwrap_or_else(|_| lexical_normalize(path));
+        if resolved.starts_with(&denied_resolved) {
+            anyhow::bail!(
+                "Error: Path '{}' is in a protected location",
+                file_path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
 fn open_confined(target: &Path, allowed_roots: &[PathBuf]) -> Result<(cap_std::fs::Dir, PathBuf)> {
     let resolved = resolve_path(target);
 
@@ -152,6 +179,7 @@ pub struct ReadFileTool {
     allowed_roots: Option<Vec<PathBuf>>,
     workspace: Option<PathBuf>,
     workspace_manager: Option<Arc<dyn WorkspaceFileTracker>>,
+    denied_paths: Vec<PathBuf>,
 }
 
 impl ReadFileTool {
@@ -160,6 +188,7 @@ impl ReadFileTool {
             allowed_roots,
             workspace,
             workspace_manager: None,
+            denied_paths: Vec::new(),
         }
     }
 
@@ -168,6 +197,13 @@ impl ReadFileTool {
         self.workspace_manager = Some(mgr);
         self
     }
+
+    /// Refuse paths inside `denied` even when an allowed root contains them.
+    #[must_use]
+    pub fn with_denied_paths(mut self, denied: Vec<PathBuf>) -> Self {
+        self.denied_paths = denied;
+        self
+    }
 }
 
 #[async_trait]
@@ -219,6 +255,9 @@ impl Tool for ReadFileTool {
         })?;
 
         let ws = self.workspace.as_deref();
+        if let Err(err) = check_path_not_denied(&expanded, &self.denied_paths) {
+            return Ok(ToolResult::error(sanitize_err(&err.to_string(), ws)));
+        }
 
         let result = if let Some(ref roots) = self.allowed_roots {
             let (dir, relative) = match open_confined(&expanded, roots) {
@@ -311,6 +350,7 @@ pub struct WriteFileTool {
     backup_dir: Option<PathBuf>,
     workspace: Option<PathBuf>,
     workspace_manager: Option<Arc<dyn WorkspaceFileTracker>>,
+    denied_paths: Vec<PathBuf>,
 }
 
 impl WriteFileTool {
@@ -324,6 +364,7 @@ impl WriteFileTool {
             backup_dir,
             workspace,
             workspace_manager: None,
+            denied_paths: Vec::new(),
         }
     }
 
@@ -332,6 +373,13 @@ impl WriteFileTool {
         self.workspace_manager = Some(mgr);
         self
     }
+
+    /// Refuse paths inside `denied` even when an allowed root contains them.
+    #[must_use]
+    pub fn with_denied_paths(mut self, denied: Vec<PathBuf>) -> Self {
+        self.denied_paths = denied;
+        self
+    }
 }
 
 #[async_trait]
@@ -388,6 +436,9 @@ impl Tool for WriteFileTool {
         })?;
 
         let ws = self.workspace.as_deref();
+        if let Err(err) = check_path_not_denied(&expanded, &self.denied_paths) {
+            return Ok(ToolResult::error(sanitize_err(&err.to_string(), ws)));
+        }
 
         let result = if let Some(ref roots) = self.allowed_roots {
             let (dir, relative) = match open_confined(&expanded, roots) {
@@ -454,10 +505,75 @@ impl Tool for WriteFileTool {
     }
 }
 
+/// Change requested from `edit_file`: an exact replacement or a unified diff.
+enum FileEdit {
+    Replace { old_text: String, new_text: String },
+    Patch(String),
+}
+
+impl FileEdit {
+    fn from_params(params: &Value) -> std::result::Result<Self, String> {
+        if let Some(patch) = params["patch"].as_str() {
+            if params.get("old_text").is_some() || params.get("new_text").is_some() {
+                return Err("pass either patch or old_text/new_text, not both".to_string());
+            }
+            return Ok(Self::Patch(patch.to_string()));
+        }
+        match (params["old_text"].as_str(), params["new_text"].as_str()) {
+            (Some(old_text), Some(new_text)) => Ok(Self::Replace {
+                old_text: old_text.to_string(),
+                new_text: new_text.to_string(),
+            }),
+            _ => Err("missing parameters: pass old_text and new_text, or patch".to_string()),
+        }
+    }
+
+    /// Apply to `content`, returning the new content and the success message.
+    fn apply(
+        &self,
+        content: &str,
+        path_str: &str,
+    ) -> std::result::Result<(String, String), String> {
+        match self {
+            Self::Replace { old_text, new_text } => {
+                if !content.contains(old_text.as_str()) {
+                    return Err(
+                        "old_text not found in file. Make sure it matches exactly".to_string()
+                    );
+                }
+
+                let count = content.matches(old_text.as_str()).count();
+                if count > 1 {
+                    return Err(format!(
+                        "old_text appears {count} times. Please provide more context to make it unique"
+                    ));
+                }
+
+                Ok((
+                    content.replacen(old_text.as_str(), new_text, 1),
+                    format!("Successfully edited {path_str}"),
+                ))
+            }
+            Self::Patch(patch) => {
+                let (new_content, summary) = patch::apply_unified_diff(content, patch)
+                    .map_err(|e| format!("patch not applied, file unchanged: {e}"))?;
+                Ok((
+                    new_content,
+                    format!(
+                        "Successfully patched {path_str}: {} hunk(s), +{} -{} lines",
+                        summary.hunks, summary.added, summary.removed
+                    ),
+                ))
+            }
+        }
+    }
+}
+
 pub struct EditFileTool {
     allowed_roots: Option<Vec<PathBuf>>,
     backup_dir: Option<PathBuf>,
     workspace: Option<PathBuf>,
+    denied_paths: Vec<PathBuf>,
 }
 
 impl EditFileTool {
@@ -470,8 +586,16 @@ impl EditFileTool {
             allowed_roots,
             backup_dir,
             workspace,
+            denied_paths: Vec::new(),
         }
     }
+
+    /// Refuse paths inside `denied` even when an allowed root contains them.
+    #[must_use]
+    pub fn with_denied_paths(mut self, denied: Vec<PathBuf>) -> Self {
+        self.denied_paths = denied;
+        self
+    }
 }
 
 #[async_trait]
@@ -481,7 +605,9 @@ impl Tool for EditFileTool {
     }
 
     fn description(&self) -> &str {
-        "Edit a file by replacing old_text with new_text. The old_text must exist exactly in the file."
+        "Edit a file by replacing old_text with new_text (old_text must appear exactly once), \
+         or by applying a unified diff via patch. Prefer this over write_file for changes to \
+         existing files: nothing is written unless the edit applies cleanly."
     }
 
     fn capabilities(&self) -> ToolCapabilities {
@@ -509,16 +635,24 @@ impl Tool for EditFileTool {
                 "new_text": {
                     "type": "string",
                     "description": "The text to replace with"
+                },
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff to apply instead of old_text/new_text. \
+                     Hunks start with '@@ -line' headers; context (' ') and removed ('-') \
+                     lines must match the file exactly. Use for several edits at once."
                 }
             },
-            "required": ["path", "old_text", "new_text"]
+            "required": ["path"]
         })
     }
 
     async fn execute(&self, params: Value, _ctx: &ExecutionContext) -> Result<ToolResult> {
         let path_str = require_param!(params, "path");
-        let old_text = require_param!(params, "old_text");
-        let new_text = require_param!(params, "new_text");
+        let edit = match FileEdit::from_params(&params) {
+            Ok(e) => e,
+            Err(e) => return Ok(ToolResult::error(e)),
+        };
 
         let file_path = PathBuf::from(path_str);
         let expanded = tokio::fs::canonicalize(&file_path).await.or_else(|_| {
@@ -533,6 +667,9 @@ impl Tool for EditFileTool {
         })?;
 
         let ws = self.workspace.as_deref();
+        if let Err(err) = check_path_not_denied(&expanded, &self.denied_paths) {
+            return Ok(ToolResult::error(sanitize_err(&err.to_string(), ws)));
+        }
 
         if let Some(ref roots) = self.allowed_roots {
             let (dir, relative) = match open_confined(&expanded, roots) {
@@ -544,8 +681,6 @@ impl Tool for EditFileTool {
                 backup_file(&expanded, backup_dir).await;
             }
 
-            let old_text_owned = old_text.to_string();
-            let new_text_owned = new_text.to_string();
             let path_str_owned = path_str.to_string();
             let ws_owned = ws.map(Path::to_path_buf);
             return tokio::task::spawn_blocking(move || {
@@ -571,24 +706,12 @@ impl Tool for EditFileTool {
                     )));
                 };
 
-                if !content.contains(&*old_text_owned) {
-                    return Ok(ToolResult::error(
-                        "old_text not found in file. Make sure it matches exactly".to_string(),
-                    ));
-                }
-
-                let count = content.matches(&*old_text_owned).count();
-                if count > 1 {
-                    return Ok(ToolResult::error(format!(
-                        "old_text appears {count} times. Please provide more context to make it unique"
-                    )));
-                }
-
-                let new_content = content.replacen(&*old_text_owned, &new_text_owned, 1);
+                let (new_content, message) = match edit.apply(&content, &path_str_owned) {
+                    Ok(v) => v,
+                    Err(e) => return Ok(ToolResult::error(e)),
+                };
                 match dir.write(&relative, &new_content) {
-                    Ok(()) => Ok(ToolResult::new(format!(
-                        "Successfully edited {path_str_owned}"
-                    ))),
+                    Ok(()) => Ok(ToolResult::new(message)),
                     Err(e) => Ok(ToolResult::error(sanitize_err(
                         &format!("error writing file: {e}"),
                         ws_ref,
@@ -618,26 +741,17 @@ impl Tool for EditFileTool {
 
         match tokio::fs::read_to_string(&expanded).await {
             Ok(content) => {
-                if !content.contains(old_text) {
-                    return Ok(ToolResult::error(
-                        "old_text not found in file. Make sure it matches exactly".to_string(),
-                    ));
-                }
-
-                let count = content.matches(old_text).count();
-                if count > 1 {
-                    return Ok(ToolResult::error(format!(
-                        "old_text appears {count} times. Please provide more context to make it unique"
-                    )));
-                }
+                let (new_content, message) = match edit.apply(&content, path_str) {
+                    Ok(v) => v,
+                    Err(e) => return Ok(ToolResult::error(e)),
+                };
 
                 if let Some(ref backup_dir) = self.backup_dir {
                     backup_file(&expanded, backup_dir).await;
                 }
 
-                let new_content = content.replacen(old_text, new_text, 1);
                 match tokio::fs::write(&expanded, new_content).await {
-                    Ok(()) => Ok(ToolResult::new(format!("Successfully edited {path_str}"))),
+                    Ok(()) => Ok(ToolResult::new(message)),
                     Err(e) => Ok(ToolResult::error(sanitize_err(
                         &format!("error writing file: {e}"),
                         ws,
@@ -655,6 +769,7 @@ impl Tool for EditFileTool {
 pub struct ListDirTool {
     allowed_roots: Option<Vec<PathBuf>>,
     workspace: Option<PathBuf>,
+    denied_paths: Vec<PathBuf>,
 }
 
 impl ListDirTool {
@@ -662,8 +777,16 @@ impl ListDirTool {
         Self {
             allowed_roots,
             workspace,
+            denied_paths: Vec::new(),
         }
     }
+
+    /// Refuse paths inside `denied` even when an allowed root contains them.
+    #[must_use]
+    pub fn with_denied_paths(mut self, denied: Vec<PathBuf>) -> Self {
+        self.denied_paths = denied;
+        self
+    }
 }
 
 #[async_trait]
@@ -715,6 +838,9 @@ impl Tool for ListDirTool {
         })?;
 
         let ws = self.workspace.as_deref();
+        if let Err(err) = check_path_not_denied(&expanded, &self.denied_paths) {
+            return Ok(ToolResult::error(sanitize_err(&err.to_string(), ws)));
+        }
 
         if let Some(ref roots) = self.allowed_roots {
             let (dir, relative) = match open_confined(&expanded, roots) {
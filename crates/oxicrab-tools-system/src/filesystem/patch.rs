@@ -0,0 +1,209 @@
+//! Minimal unified-diff applier for `edit_file`'s `patch` parameter.
+//!
+//! Hunk line counts in `@@` headers are ignored (models often get them wrong);
+//! the hunk body is authoritative. Each hunk must match the file exactly,
+//! but may sit at a different line than the header claims — the match
+//! closest to the claimed line wins. Nothing is written unless every hunk
+//! applies.
+
+/// Outcome of a successful patch.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct PatchSummary {
+    pub hunks: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+struct Hunk {
+    /// 1-based start line in the original file, from the `@@ -N` header.
+    old_start: usize,
+    old_lines: Vec<String>,
+    new_lines: Vec<String>,
+    added: usize,
+    removed: usize,
+}
+
+fn parse_hunk_start(header: &str) -> Result<usize, String> {
+    header
+        .strip_prefix("@@ -")
+        .and_then(|rest| rest.split([',', ' ']).next())
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("malformed hunk header: {header}"))
+}
+
+fn parse(patch: &str) -> Result<Vec<Hunk>, String> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut lines = patch.trim_end_matches(['\n', '\r']).lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.starts_with("@@") {
+            hunks.push(Hunk {
+                old_start: parse_hunk_start(line)?,
+                old_lines: Vec::new(),
+                new_lines: Vec::new(),
+                added: 0,
+                removed: 0,
+            });
+            continue;
+        }
+        // File headers, `diff --git` lines and prose before the first hunk
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        // `--- a/file` directly followed by `+++ b/file` is a file header, not
+        // a removed line that happens to start with "-- "
+        let file_header =
+            line.starts_with("--- ") && lines.peek().is_some_and(|next| next.starts_with("+++ "));
+        if file_header {
+            lines.next();
+            continue;
+        }
+        // "\ No newline at end of file"
+        if line.starts_with('\\') {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('+') {
+            hunk.new_lines.push(rest.to_string());
+            hunk.added += 1;
+        } else if let Some(rest) = line.strip_prefix('-') {
+            hunk.old_lines.push(rest.to_string());
+            hunk.removed += 1;
+        } else {
+            // Context; editors and models often drop the leading space on blank lines
+            let rest = line.strip_prefix(' ').unwrap_or(line);
+            hunk.old_lines.push(rest.to_string());
+            hunk.new_lines.push(rest.to_string());
+        }
+    }
+    if hunks.is_empty() {
+        return Err("patch contains no hunks (expected lines starting with '@@')".to_string());
+    }
+    Ok(hunks)
+}
+
+/// Find where `needle` occurs in `lines` at or after `from`, preferring the
+/// occurrence closest to `hint`.
+fn locate(lines: &[String], needle: &[String], from: usize, hint: usize) -> Option<usize> {
+    if needle.len() > lines.len() {
+        return None;
+    }
+    (from..=lines.len() - needle.len())
+        .filter(|&i| lines[i..i + needle.len()] == *needle)
+        .min_by_key(|&i| i.abs_diff(hint))
+}
+
+/// Apply a unified diff to `content`, returning the new content.
+pub(super) fn apply_unified_diff(
+    content: &str,
+    patch: &str,
+) -> Result<(String, PatchSummary), String> {
+    let hunks = parse(patch)?;
+    let trailing_newline = content.ends_with('\n');
+    let line_ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut lines: Vec<String> = content.lines().map(ToString::to_string).collect();
+
+    let mut summary = PatchSummary {
+        hunks: hunks.len(),
+        added: 0,
+        removed: 0,
+    };
+    // Hunks apply in order; `cursor` keeps later hunks from matching above earlier ones
+    let mut cursor = 0;
+    // Line shift from hunks already applied
+    let mut offset: isize = 0;
+    for (n, hunk) in hunks.iter().enumerate() {
+        let hint = (hunk.old_start.saturating_sub(1) as isize + offset).max(0) as usize;
+        let at = if hunk.old_lines.is_empty() {
+            // Pure insertion: `@@ -N,0` inserts after line N
+            (hunk.old_start as isize + offset).clamp(cursor as isize, lines.len() as isize) as usize
+        } else {
+            locate(&lines, &hunk.old_lines, cursor, hint).ok_or_else(|| {
+                format!(
+                    "hunk {} (@@ -{}) does not match the file; re-read the file and \
+                     regenerate the patch",
+                    n + 1,
+                    hunk.old_start
+                )
+            })?
+        };
+        lines.splice(
+            at..at + hunk.old_lines.len(),
+            hunk.new_lines.iter().cloned(),
+        );
+        cursor = at + hunk.new_lines.len();
+        offset += hunk.new_lines.len() as isize - hunk.old_lines.len() as isize;
+        summary.added += hunk.added;
+        summary.removed += hunk.removed;
+    }
+
+    let mut out = lines.join(line_ending);
+    if trailing_newline && !out.is_empty() {
+        out.push_str(line_ending);
+    }
+    Ok((out, summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Title\n\nintro\n\n## One\nalpha\nbeta\n\n## Two\ngamma\n";
+
+    #[test]
+    fn applies_hunks_and_counts_changes() {
+        let patch = "--- a/doc.md\n+++ b/doc.md\n@@ -5,3 +5,3 @@\n ## One\n-alpha\n+ALPHA\n beta\n@@ -9,2 +9,3 @@\n ## Two\n gamma\n+delta\n";
+        let (out, summary) = apply_unified_diff(DOC, patch).unwrap();
+        assert_eq!(
+            out,
+            "# Title\n\nintro\n\n## One\nALPHA\nbeta\n\n## Two\ngamma\ndelta\n"
+        );
+        assert_eq!(
+            summary,
+            PatchSummary {
+                hunks: 2,
+                added: 2,
+                removed: 1
+            }
+        );
+    }
+
+    #[test]
+    fn tolerates_wrong_line_numbers() {
+        let patch = "@@ -1,2 +1,2 @@\n ## Two\n-gamma\n+GAMMA\n";
+        let (out, _) = apply_unified_diff(DOC, patch).unwrap();
+        assert!(out.ends_with("## Two\nGAMMA\n"));
+    }
+
+    #[test]
+    fn rejects_mismatched_context() {
+        let patch = "@@ -5,2 +5,2 @@\n ## One\n-omega\n+OMEGA\n";
+        let err = apply_unified_diff(DOC, patch).unwrap_err();
+        assert!(err.contains("hunk 1"), "{err}");
+    }
+
+    #[test]
+    fn rejects_text_without_hunks() {
+        assert!(apply_unified_diff(DOC, "just rewrite it").is_err());
+    }
+
+    #[test]
+    fn removed_sql_comment_is_not_a_file_header() {
+        let content = "SELECT 1;\n-- old note\nSELECT 2;\n";
+        let patch = "@@ -1,3 +1,2 @@\n SELECT 1;\n--- old note\n SELECT 2;\n";
+        let (out, summary) = apply_unified_diff(content, patch).unwrap();
+        assert_eq!(out, "SELECT 1;\nSELECT 2;\n");
+        assert_eq!(summary.removed, 1);
+    }
+
+    #[test]
+    fn inserts_into_empty_file_and_keeps_crlf() {
+        let (out, _) = apply_unified_diff("", "@@ -0,0 +1,2 @@\n+one\n+two\n").unwrap();
+        assert_eq!(out, "one\ntwo");
+
+        let (out, _) = apply_unified_diff("a\r\nb\r\n", "@@ -1,2 +1,2 @@\n a\n-b\n+c\n").unwrap();
+        assert_eq!(out, "a\r\nc\r\n");
+    }
+}
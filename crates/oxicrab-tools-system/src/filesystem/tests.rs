@@ -92,6 +92,38 @@ async fn test_read_file_success() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[tokio::test]
+async fn test_read_file_denied_path_rejected() {
+    let tmp = tempfile::tempdir().unwrap();
+    let workspace = tmp.path().join("workspace");
+    let other = tmp.path().join("profiles").join("work");
+    fs::create_dir_all(&workspace).unwrap();
+    fs::create_dir_all(other.join("memory")).unwrap();
+    fs::write(other.join("memory").join("memory.sqlite3"), "secret").unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&other, workspace.join("link")).unwrap();
+
+    let tool = ReadFileTool::new(
+        Some(vec![tmp.path().to_path_buf()]),
+        Some(workspace.clone()),
+    )
+    .with_denied_paths(vec![other.clone()]);
+    let mut paths = vec![other.join("memory").join("memory.sqlite3")];
+    #[cfg(unix)]
+    paths.push(workspace.join("link").join("memory").join("memory.sqlite3"));
+    for path in paths {
+        let result = tool
+            .execute(
+                serde_json::json!({"path": path.to_str().unwrap()}),
+                &ExecutionContext::default(),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error, "read of {} should fail", path.display());
+        assert!(result.content.contains("protected location"));
+    }
+}
+
 #[tokio::test]
 async fn test_read_file_not_found() {
     let tool = ReadFileTool::new(None, None);
@@ -152,6 +184,76 @@ async fn test_edit_file_success() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[tokio::test]
+async fn test_edit_file_patch_confined_with_backup() {
+    let dir = tempfile::tempdir().unwrap();
+    let backup_dir = dir.path().join("backups");
+    let file = dir.path().join("notes.md");
+    fs::write(&file, "# Notes\n\n- milk\n- eggs\n").unwrap();
+
+    let tool = EditFileTool::new(
+        Some(vec![dir.path().to_path_buf()]),
+        Some(backup_dir.clone()),
+        None,
+    );
+    let ctx = ExecutionContext::default();
+
+    let bad = tool
+        .execute(
+            serde_json::json!({
+                "path": file.to_str().unwrap(),
+                "patch": "@@ -3,2 +3,2 @@\n-- bread\n+- butter\n"
+            }),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(bad.is_error);
+    assert!(bad.content.contains("file unchanged"), "{}", bad.content);
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "# Notes\n\n- milk\n- eggs\n"
+    );
+
+    let result = tool
+        .execute(
+            serde_json::json!({
+                "path": file.to_str().unwrap(),
+                "patch": "--- a/notes.md\n+++ b/notes.md\n@@ -3,2 +3,3 @@\n - milk\n-- eggs\n+- eggs (dozen)\n+- bread\n"
+            }),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error, "{}", result.content);
+    assert!(result.content.contains("+2 -1"), "{}", result.content);
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "# Notes\n\n- milk\n- eggs (dozen)\n- bread\n"
+    );
+    let backups: Vec<_> = fs::read_dir(&backup_dir).unwrap().flatten().collect();
+    assert!(!backups.is_empty(), "original should be backed up");
+}
+
+#[tokio::test]
+async fn test_edit_file_rejects_patch_with_old_text() {
+    let tool = EditFileTool::new(None, None, None);
+    let result = tool
+        .execute(
+            serde_json::json!({
+                "path": "/tmp/whatever.txt",
+                "old_text": "a",
+                "new_text": "b",
+                "patch": "@@ -1 +1 @@\n-a\n+b\n"
+            }),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("not both"));
+}
+
 #[tokio::test]
 async fn test_list_dir_success() {
     let dir = std::env::temp_dir().join("oxicrab_test_listdir_sys");
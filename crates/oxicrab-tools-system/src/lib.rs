@@ -1,10 +1,11 @@
 //! System tools for the oxicrab framework.
 //!
-//! This crate provides filesystem, shell, and tmux tools,
+//! This crate provides filesystem, shell, sqlite, and tmux tools,
 //! extracted from the main binary crate for modularity.
 
 pub mod filesystem;
 pub mod shell;
+pub mod sqlite;
 pub mod tmux;
 mod utils;
 
@@ -17,18 +18,23 @@ use std::sync::Arc;
 ///
 /// - `workspace`: working directory for the agent
 /// - `roots`: if `Some`, restricts filesystem access to these directories
+/// - `denied`: files and directories refused even inside `roots`
 /// - `backup_dir`: if `Some`, backups are created before writes/edits
 pub fn create_filesystem_tools(
     workspace: &Path,
     roots: Option<Vec<PathBuf>>,
+    denied: Vec<PathBuf>,
     backup_dir: Option<PathBuf>,
 ) -> Vec<Arc<dyn Tool>> {
     let ws = Some(workspace.to_path_buf());
 
-    let read = filesystem::ReadFileTool::new(roots.clone(), ws.clone());
-    let write = filesystem::WriteFileTool::new(roots.clone(), backup_dir.clone(), ws.clone());
-    let edit = filesystem::EditFileTool::new(roots.clone(), backup_dir, ws.clone());
-    let list = filesystem::ListDirTool::new(roots, ws);
+    let read =
+        filesystem::ReadFileTool::new(roots.clone(), ws.clone()).with_denied_paths(denied.clone());
+    let write = filesystem::WriteFileTool::new(roots.clone(), backup_dir.clone(), ws.clone())
+        .with_denied_paths(denied.clone());
+    let edit = filesystem::EditFileTool::new(roots.clone(), backup_dir, ws.clone())
+        .with_denied_paths(denied.clone());
+    let list = filesystem::ListDirTool::new(roots, ws).with_denied_paths(denied);
 
     vec![
         Arc::new(read),
@@ -57,6 +63,18 @@ pub fn create_exec_tool(
     )?))
 }
 
+/// Create the sqlite_query tool.
+///
+/// Relative database paths resolve against `workspace`; `roots` restricts
+/// which files may be opened and `denied` is refused regardless.
+pub fn create_sqlite_tool(
+    workspace: &Path,
+    roots: Option<Vec<PathBuf>>,
+    denied: Vec<PathBuf>,
+) -> Arc<dyn Tool> {
+    Arc::new(sqlite::SqliteQueryTool::new(workspace.to_path_buf(), roots).with_denied_paths(denied))
+}
+
 /// Create the tmux tool.
 pub fn create_tmux_tool() -> Arc<dyn Tool> {
     Arc::new(tmux::TmuxTool::new())
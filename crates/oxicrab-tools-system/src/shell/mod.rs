@@ -282,7 +282,12 @@ impl Tool for ExecTool {
     }
 
     fn description(&self) -> &str {
-        "Execute a shell command and return its output. Use with caution."
+        if self.sandbox_config.enabled {
+            "Execute a shell command and return its output. Use with caution. Commands run \
+             sandboxed: only the working directory, /tmp and system directories are accessible."
+        } else {
+            "Execute a shell command and return its output. Use with caution."
+        }
     }
 
     fn parameters(&self) -> Value {
@@ -302,8 +307,8 @@ impl Tool for ExecTool {
         })
     }
 
-    fn execution_timeout(&self) -> Duration {
-        Duration::from_secs(self.timeout)
+    fn execution_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(self.timeout))
     }
 
     fn capabilities(&self) -> ToolCapabilities {
@@ -0,0 +1,277 @@
+use crate::filesystem::{check_path_allowed, check_path_not_denied, resolve_path};
+use crate::utils::path_sanitize::sanitize_error_message;
+use anyhow::Result;
+use async_trait::async_trait;
+use oxicrab_core::actions;
+use oxicrab_core::require_param;
+use oxicrab_core::tools::base::{ExecutionContext, SubagentAccess, ToolCapabilities, ToolCategory};
+use oxicrab_core::tools::base::{Tool, ToolResult};
+use rusqlite::limits::Limit;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default number of rows returned by a `query` action.
+const DEFAULT_MAX_ROWS: usize = 100;
+/// Hard cap on rows returned by a `query` action.
+const MAX_ROWS_CAP: usize = 1000;
+/// Maximum characters rendered per cell before truncation.
+const MAX_CELL_CHARS: usize = 200;
+/// How long to wait on a locked database before giving up.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Query and migrate user SQLite databases inside the workspace.
+///
+/// `query` opens the database read-only and rejects statements that would
+/// modify it. `execute` runs arbitrary SQL (migrations, inserts) but only when
+/// the caller passes `allow_write: true`, and the action is approval-gated.
+pub struct SqliteQueryTool {
+    workspace: PathBuf,
+    allowed_roots: Option<Vec<PathBuf>>,
+    denied_paths: Vec<PathBuf>,
+}
+
+impl SqliteQueryTool {
+    pub fn new(workspace: PathBuf, allowed_roots: Option<Vec<PathBuf>>) -> Self {
+        Self {
+            workspace,
+            allowed_roots,
+            denied_paths: Vec::new(),
+        }
+    }
+
+    /// Files and directories that may never be opened, even inside the
+    /// allowed roots or without any.
+    #[must_use]
+    pub fn with_denied_paths(mut self, denied: Vec<PathBuf>) -> Self {
+        self.denied_paths = denied;
+        self
+    }
+
+    /// Resolve a workspace-relative (or absolute) database path, following
+    /// symlinks, and validate it against the allowed roots and denied paths.
+    /// A lexical check alone would let a link inside the workspace point the
+    /// tool at any database.
+    fn resolve_db_path(&self, path: &str) -> Result<PathBuf> {
+        let raw = Path::new(path);
+        let joined = if raw.is_absolute() {
+            raw.to_path_buf()
+        } else {
+            self.workspace.join(raw)
+        };
+        let resolved = resolve_path(&joined);
+        check_path_allowed(&resolved, self.allowed_roots.as_ref())?;
+        check_path_not_denied(&resolved, &self.denied_paths)?;
+        Ok(resolved)
+    }
+}
+
+fn render_cell(value: ValueRef<'_>) -> String {
+    let text = match value {
+        ValueRef::Null => "NULL".to_string(),
+        ValueRef::Integer(i) => i.to_string(),
+        ValueRef::Real(f) => f.to_string(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).to_string(),
+        ValueRef::Blob(b) => format!("[blob, {} bytes]", b.len()),
+    };
+    let escaped = text.replace('|', "\\|").replace(['\n', '\r'], " ");
+    if escaped.chars().count() > MAX_CELL_CHARS {
+        let truncated: String = escaped.chars().take(MAX_CELL_CHARS).collect();
+        format!("{truncated}…")
+    } else {
+        escaped
+    }
+}
+
+/// Open `db_path` so that SQL can only touch that one file. `ATTACH` and
+/// `VACUUM` (which attaches its target internally, including `VACUUM INTO`)
+/// fail with "too many attached databases", so statements cannot read or
+/// write files outside the allowed roots.
+fn open_db(db_path: &Path, flags: OpenFlags) -> Result<Connection> {
+    let conn = Connection::open_with_flags(db_path, flags)?;
+    conn.set_limit(Limit::SQLITE_LIMIT_ATTACHED, 0)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    Ok(conn)
+}
+
+/// Run a read-only statement and render the result set as a markdown table.
+fn run_query(db_path: &Path, sql: &str, max_rows: usize) -> Result<String> {
+    let conn = open_db(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    let mut stmt = conn.prepare(sql)?;
+    if !stmt.readonly() {
+        anyhow::bail!("statement modifies the database; use action 'execute' with allow_write");
+    }
+
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    if columns.is_empty() {
+        anyhow::bail!("statement returns no columns");
+    }
+    let column_count = columns.len();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "| {} |", columns.join(" | "));
+    let _ = writeln!(out, "|{}", "---|".repeat(column_count));
+
+    let mut rows = stmt.query([])?;
+    let mut shown = 0usize;
+    let mut truncated = false;
+    while let Some(row) = rows.next()? {
+        if shown >= max_rows {
+            truncated = true;
+            break;
+        }
+        let cells: Vec<String> = (0..column_count)
+            .map(|i| row.get_ref(i).map(render_cell).unwrap_or_default())
+            .collect();
+        let _ = writeln!(out, "| {} |", cells.join(" | "));
+        shown += 1;
+    }
+
+    if shown == 0 {
+        out.push_str("\n(no rows)");
+    } else if truncated {
+        let _ = write!(
+            out,
+            "\n({shown} rows shown; more rows available — narrow the query or raise max_rows)"
+        );
+    } else {
+        let _ = write!(out, "\n({shown} rows)");
+    }
+    Ok(out)
+}
+
+/// Run one or more write statements inside a single transaction.
+fn run_execute(db_path: &Path, sql: &str) -> Result<String> {
+    let mut conn = open_db(db_path, OpenFlags::default())?;
+    let before = conn.total_changes();
+    let tx = conn.transaction()?;
+    tx.execute_batch(sql)?;
+    tx.commit()?;
+    let changed = conn.total_changes().saturating_sub(before);
+    Ok(format!("Executed successfully ({changed} rows changed)"))
+}
+
+#[async_trait]
+impl Tool for SqliteQueryTool {
+    fn name(&self) -> &str {
+        "sqlite_query"
+    }
+
+    fn description(&self) -> &str {
+        "Query or migrate a SQLite database file in the workspace. 'query' runs a single \
+         read-only statement and returns a markdown table. 'execute' runs write SQL \
+         (migrations, inserts, updates) in one transaction and requires allow_write: true."
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            subagent_access: SubagentAccess::ReadOnly,
+            actions: actions![query: ro, execute],
+            category: ToolCategory::Development,
+            ..Default::default()
+        }
+    }
+
+    fn requires_approval_for_action(&self, action: &str) -> bool {
+        action == "execute"
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["query", "execute"],
+                    "description": "'query' for read-only SELECT/PRAGMA statements, 'execute' for writes and migrations"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Database file path, relative to the workspace"
+                },
+                "sql": {
+                    "type": "string",
+                    "description": "SQL to run. 'query' accepts one statement; 'execute' accepts a batch"
+                },
+                "allow_write": {
+                    "type": "boolean",
+                    "description": "Must be true for 'execute'"
+                },
+                "max_rows": {
+                    "type": "integer",
+                    "description": "Maximum rows to return for 'query' (default 100)",
+                    "minimum": 1,
+                    "maximum": 1000
+                }
+            },
+            "required": ["action", "path", "sql"]
+        })
+    }
+
+    async fn execute(&self, params: Value, _ctx: &ExecutionContext) -> Result<ToolResult> {
+        let action = require_param!(params, "action");
+        let path = require_param!(params, "path");
+        let sql = require_param!(params, "sql").to_string();
+
+        let db_path = match self.resolve_db_path(path) {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult::error(sanitize_error_message(
+                    &e.to_string(),
+                    Some(&self.workspace),
+                )));
+            }
+        };
+
+        let result = match action {
+            "query" => {
+                if !db_path.is_file() {
+                    return Ok(ToolResult::error(format!("database not found: {path}")));
+                }
+                let max_rows = params["max_rows"]
+                    .as_u64()
+                    .map_or(DEFAULT_MAX_ROWS, |n| n as usize)
+                    .clamp(1, MAX_ROWS_CAP);
+                tokio::task::spawn_blocking(move || run_query(&db_path, &sql, max_rows)).await?
+            }
+            "execute" => {
+                if !params["allow_write"].as_bool().unwrap_or(false) {
+                    return Ok(ToolResult::error(
+                        "write refused: set allow_write to true to run 'execute'",
+                    ));
+                }
+                if let Some(parent) = db_path.parent()
+                    && !parent.is_dir()
+                {
+                    return Ok(ToolResult::error(format!(
+                        "parent directory does not exist for: {path}"
+                    )));
+                }
+                tokio::task::spawn_blocking(move || run_execute(&db_path, &sql)).await?
+            }
+            other => {
+                return Ok(ToolResult::error(format!(
+                    "unknown action '{other}'. Use 'query' or 'execute'"
+                )));
+            }
+        };
+
+        Ok(match result {
+            Ok(content) => ToolResult::new(content),
+            Err(e) => ToolResult::error(sanitize_error_message(
+                &format!("sqlite error: {e}"),
+                Some(&self.workspace),
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
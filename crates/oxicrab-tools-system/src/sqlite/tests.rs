@@ -0,0 +1,233 @@
+use super::*;
+use serde_json::json;
+
+fn setup_db(dir: &Path) {
+    let conn = Connection::open(dir.join("data.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT);
+         INSERT INTO items (name) VALUES ('alpha'), ('beta'), ('gam|ma');",
+    )
+    .unwrap();
+}
+
+fn tool_for(dir: &Path) -> SqliteQueryTool {
+    SqliteQueryTool::new(dir.to_path_buf(), Some(vec![dir.to_path_buf()]))
+}
+
+#[tokio::test]
+async fn test_query_renders_markdown_table() {
+    let tmp = tempfile::tempdir().unwrap();
+    setup_db(tmp.path());
+    let tool = tool_for(tmp.path());
+
+    let result = tool
+        .execute(
+            json!({"action": "query", "path": "data.db", "sql": "SELECT id, name FROM items ORDER BY id"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(!result.is_error, "{}", result.content);
+    assert!(result.content.starts_with("| id | name |"));
+    assert!(result.content.contains("| 1 | alpha |"));
+    assert!(result.content.contains("gam\\|ma"));
+    assert!(result.content.contains("(3 rows)"));
+}
+
+#[tokio::test]
+async fn test_query_respects_max_rows() {
+    let tmp = tempfile::tempdir().unwrap();
+    setup_db(tmp.path());
+    let tool = tool_for(tmp.path());
+
+    let result = tool
+        .execute(
+            json!({"action": "query", "path": "data.db", "sql": "SELECT name FROM items", "max_rows": 2}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(!result.is_error);
+    assert!(result.content.contains("2 rows shown; more rows available"));
+}
+
+#[tokio::test]
+async fn test_query_rejects_write_statement() {
+    let tmp = tempfile::tempdir().unwrap();
+    setup_db(tmp.path());
+    let tool = tool_for(tmp.path());
+
+    let result = tool
+        .execute(
+            json!({"action": "query", "path": "data.db", "sql": "DELETE FROM items"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(result.is_error);
+    assert!(result.content.contains("modifies the database"));
+}
+
+#[tokio::test]
+async fn test_execute_requires_allow_write() {
+    let tmp = tempfile::tempdir().unwrap();
+    setup_db(tmp.path());
+    let tool = tool_for(tmp.path());
+
+    let result = tool
+        .execute(
+            json!({"action": "execute", "path": "data.db", "sql": "DELETE FROM items"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(result.is_error);
+    assert!(result.content.contains("allow_write"));
+}
+
+#[tokio::test]
+async fn test_execute_runs_migration() {
+    let tmp = tempfile::tempdir().unwrap();
+    let tool = tool_for(tmp.path());
+
+    let result = tool
+        .execute(
+            json!({
+                "action": "execute",
+                "path": "new.db",
+                "sql": "CREATE TABLE t (v TEXT); INSERT INTO t VALUES ('a'), ('b');",
+                "allow_write": true
+            }),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(!result.is_error, "{}", result.content);
+    assert!(result.content.contains("2 rows changed"));
+    let conn = Connection::open(tmp.path().join("new.db")).unwrap();
+    let count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM t", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn test_attach_and_vacuum_into_rejected() {
+    let tmp = tempfile::tempdir().unwrap();
+    setup_db(tmp.path());
+    let outside = tempfile::tempdir().unwrap();
+    let tool = tool_for(tmp.path());
+    let other = outside.path().join("other.db");
+    Connection::open(&other)
+        .unwrap()
+        .execute_batch("CREATE TABLE secrets (v TEXT); INSERT INTO secrets VALUES ('s');")
+        .unwrap();
+    let copy = outside.path().join("copy.db");
+
+    for (action, sql) in [
+        (
+            "query",
+            format!("ATTACH DATABASE '{}' AS o", other.display()),
+        ),
+        (
+            "execute",
+            format!(
+                "ATTACH DATABASE '{}' AS o; DELETE FROM o.secrets;",
+                other.display()
+            ),
+        ),
+        ("query", format!("VACUUM INTO '{}'", copy.display())),
+        ("execute", format!("VACUUM INTO '{}'", copy.display())),
+    ] {
+        let result = tool
+            .execute(
+                json!({"action": action, "path": "data.db", "sql": sql, "allow_write": true}),
+                &ExecutionContext::default(),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error, "{action} `{sql}` ran: {}", result.content);
+    }
+
+    assert!(!copy.exists());
+    let count: i64 = Connection::open(&other)
+        .unwrap()
+        .query_row("SELECT COUNT(*) FROM secrets", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn test_path_outside_workspace_rejected() {
+    let tmp = tempfile::tempdir().unwrap();
+    let tool = tool_for(tmp.path());
+
+    let result = tool
+        .execute(
+            json!({"action": "query", "path": "../../etc/passwd.db", "sql": "SELECT 1"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(result.is_error);
+    assert!(result.content.contains("outside the allowed directories"));
+}
+
+#[tokio::test]
+async fn test_denied_memory_db_rejected() {
+    let tmp = tempfile::tempdir().unwrap();
+    let memory = tmp.path().join("memory");
+    std::fs::create_dir_all(&memory).unwrap();
+    setup_db(&memory);
+    let tool = SqliteQueryTool::new(tmp.path().to_path_buf(), None)
+        .with_denied_paths(vec![memory.clone()]);
+
+    for path in ["memory/data.db", "./memory/../memory/data.db"] {
+        let result = tool
+            .execute(
+                json!({"action": "query", "path": path, "sql": "SELECT 1"}),
+                &ExecutionContext::default(),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_error, "{path} should be rejected");
+        assert!(result.content.contains("protected location"));
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_symlink_out_of_workspace_rejected() {
+    let workspace = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+    setup_db(outside.path());
+    std::os::unix::fs::symlink(
+        outside.path().join("data.db"),
+        workspace.path().join("link.db"),
+    )
+    .unwrap();
+    let tool = tool_for(workspace.path());
+
+    let result = tool
+        .execute(
+            json!({"action": "query", "path": "link.db", "sql": "SELECT name FROM items"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("outside the allowed directories"));
+}
+
+#[test]
+fn test_execute_action_requires_approval() {
+    let tool = SqliteQueryTool::new(PathBuf::from("/tmp"), None);
+    assert!(tool.requires_approval_for_action("execute"));
+    assert!(!tool.requires_approval_for_action("query"));
+}
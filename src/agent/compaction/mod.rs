@@ -1,4 +1,5 @@
-use crate::providers::base::{ChatRequest, LLMProvider, Message};
+use crate::agent::memory::MemoryDB;
+use crate::providers::base::{ChatRequest, LLMProvider, LLMResponse, Message};
 use anyhow::Result;
 use serde_json::Value;
 use std::borrow::Cow;
@@ -8,7 +9,7 @@ use tracing::{debug, warn};
 
 const COMPACTION_PROMPT: &str = "Summarize this conversation history concisely while preserving:\n1. Key decisions made and their reasoning\n2. Important facts, names, dates, and numbers mentioned\n3. User preferences and requests\n4. Pending tasks or commitments\n5. Technical context that may be needed later\n\nPrevious summary (if any):\n{previous_summary}\n\nMessages to summarize:\n{messages}\n\nWrite a concise summary (max 500 words) that captures the essential context. Do not include preamble - just the summary.";
 
-const EXTRACTION_PROMPT: &str = "Review this conversation exchange and extract any NEW facts worth remembering long-term. Focus on:\n- User preferences, habits, or personal details shared\n- Decisions made or commitments given\n- Project names, technical choices, or configuration details\n- Anything the user would expect you to remember next time\n\nIMPORTANT: Do NOT repeat facts that are already recorded below. Only extract genuinely new information.\n\nAlready recorded today:\n{existing_facts}\n\nUser: {user_message}\n\nAssistant: {assistant_message}\n\nIf there are new facts not already recorded, respond with a short bullet list (one line per fact). If nothing new is worth remembering, respond with exactly: NOTHING";
+const EXTRACTION_PROMPT: &str = "Review these conversation exchanges and extract any NEW facts worth remembering long-term. Focus on:\n- User preferences, habits, or personal details shared\n- Decisions made or commitments given\n- Project names, technical choices, or configuration details\n- Anything the user would expect you to remember next time\n\nIMPORTANT: Do NOT repeat facts that are already recorded below. Only extract genuinely new information.\n\nAlready recorded today:\n{existing_facts}\n\n{exchanges}\n\nIf there are new facts not already recorded, respond with a short bullet list (one line per fact). If nothing new is worth remembering, respond with exactly: NOTHING";
 
 const PRE_FLUSH_PROMPT: &str = "Review these conversation messages that are about to be removed from context. Extract any important information worth preserving long-term:\n- User preferences and decisions\n- Project state and progress\n- Key facts, names, dates, or configuration details\n- Commitments or pending items\n\nRespond with a concise bullet list of important items. If nothing is worth preserving, respond with exactly: NOTHING\n\nMessages:\n{messages}";
 
@@ -19,6 +20,17 @@ const COMPACTION_TEMPERATURE: Option<f32> = Some(0.3);
 const EXTRACTION_TEMPERATURE: Option<f32> = Some(0.0);
 const PRE_FLUSH_TEMPERATURE: Option<f32> = Some(0.0);
 const CHARS_PER_TOKEN_ESTIMATE: usize = 4;
+/// Estimated-token budget for the messages sent in a single compaction call.
+/// Histories larger than this are summarized map-reduce style: each window is
+/// summarized on its own, then the partial summaries are summarized together.
+const COMPACTION_CHUNK_TOKENS: usize = 24_000;
+/// Upper bound on reduce rounds. Each round shrinks the input by roughly the
+/// ratio of chunk budget to summary length, so this is never hit in practice.
+const MAX_COMPACTION_ROUNDS: usize = 4;
+/// `caller` tags of the compactor's calls in the `llm_cost_log` table.
+const CHECKPOINT_CALLER: &str = "compaction:checkpoint";
+const FLUSH_CALLER: &str = "compaction:flush";
+const EXTRACTION_CALLER: &str = "compaction:extraction";
 
 pub fn estimate_tokens(text: &str) -> usize {
     // Use char count for better accuracy with non-ASCII text
@@ -211,6 +223,18 @@ pub struct MessageCompactor {
     /// pre-flush 0.0). Some models (e.g. Moonshot kimi-k2.5) require a
     /// fixed temperature and reject any other value.
     temperature_override: Option<f32>,
+    /// Model for fact extraction and the pre-compaction flush; `None` uses
+    /// `model`.
+    extraction_model: Option<String>,
+    /// Provider serving `extraction_model` when it belongs to another
+    /// provider; `None` uses `provider`.
+    extraction_provider: Option<Arc<dyn LLMProvider>>,
+    /// Model for checkpoint summaries; `None` uses `model`.
+    checkpoint_model: Option<String>,
+    /// Where token usage of the compactor's calls is recorded.
+    cost_db: Option<Arc<MemoryDB>>,
+    /// Estimated-token budget per compaction call (see [`COMPACTION_CHUNK_TOKENS`]).
+    chunk_tokens: usize,
 }
 
 impl MessageCompactor {
@@ -219,6 +243,11 @@ impl MessageCompactor {
             provider,
             model,
             temperature_override: None,
+            extraction_model: None,
+            extraction_provider: None,
+            checkpoint_model: None,
+            cost_db: None,
+            chunk_tokens: COMPACTION_CHUNK_TOKENS,
         }
     }
 
@@ -231,55 +260,168 @@ impl MessageCompactor {
             provider,
             model,
             temperature_override,
+            extraction_model: None,
+            extraction_provider: None,
+            checkpoint_model: None,
+            cost_db: None,
+            chunk_tokens: COMPACTION_CHUNK_TOKENS,
         }
     }
 
+    /// Use `model` for fact extraction and the pre-compaction flush instead
+    /// of the compaction model.
+    #[must_use]
+    pub fn with_extraction_model(mut self, model: Option<String>) -> Self {
+        if model.is_some() {
+            self.extraction_model = model;
+        }
+        self
+    }
+
+    /// Send fact extraction and the pre-compaction flush to `provider` and
+    /// `model`, resolved by the provider factory, when configured.
+    #[must_use]
+    pub fn with_extraction_provider(
+        mut self,
+        extraction: Option<(Arc<dyn LLMProvider>, String)>,
+    ) -> Self {
+        if let Some((provider, model)) = extraction {
+            self.extraction_provider = Some(provider);
+            self.extraction_model = Some(model);
+        }
+        self
+    }
+
+    /// Use `model` for checkpoint summaries instead of the compaction model.
+    #[must_use]
+    pub fn with_checkpoint_model(mut self, model: Option<String>) -> Self {
+        if model.is_some() {
+            self.checkpoint_model = model;
+        }
+        self
+    }
+
+    /// Record the token usage of every call in `db`'s cost log.
+    #[must_use]
+    pub fn with_cost_db(mut self, db: Arc<MemoryDB>) -> Self {
+        self.cost_db = Some(db);
+        self
+    }
+
+    /// Send `req` and record its token usage under `caller`.
+    async fn chat(&self, req: ChatRequest, caller: &'static str) -> Result<LLMResponse> {
+        self.chat_with(&self.provider, req, caller).await
+    }
+
+    /// Like [`chat`](Self::chat) on the extraction provider, if one is set.
+    async fn chat_extraction(&self, req: ChatRequest, caller: &'static str) -> Result<LLMResponse> {
+        let provider = self.extraction_provider.as_ref().unwrap_or(&self.provider);
+        self.chat_with(provider, req, caller).await
+    }
+
+    async fn chat_with(
+        &self,
+        provider: &Arc<dyn LLMProvider>,
+        req: ChatRequest,
+        caller: &'static str,
+    ) -> Result<LLMResponse> {
+        let response = provider.chat(&req).await?;
+        if let Some(db) = self.cost_db.clone() {
+            let model = response
+                .actual_model
+                .clone()
+                .or(req.model)
+                .unwrap_or_else(|| provider.default_model().to_string());
+            let input = response.input_tokens.unwrap_or(0);
+            let output = response.output_tokens.unwrap_or(0);
+            let cache_create = response.cache_creation_input_tokens.unwrap_or(0);
+            let cache_read = response.cache_read_input_tokens.unwrap_or(0);
+            let recorded = tokio::task::spawn_blocking(move || {
+                db.record_tokens(
+                    &model,
+                    input,
+                    output,
+                    cache_create,
+                    cache_read,
+                    caller,
+                    None,
+                )
+            })
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|r| r);
+            if let Err(e) = recorded {
+                warn!("failed to record {caller} token usage: {e}");
+            }
+        }
+        Ok(response)
+    }
+
     /// Summarize a slice of conversation messages into a concise summary.
     ///
     /// Uses [`estimate_tokens`] (chars/4) to gauge message size. The LLM is asked to preserve
     /// key decisions, facts, preferences, and pending tasks. `previous_summary` is included
     /// in the prompt so summaries build incrementally rather than losing earlier context.
+    ///
+    /// Histories too large for one call are compacted map-reduce style: messages are split
+    /// into windows that each fit the chunk budget, every window is summarized, and the
+    /// partial summaries are then summarized together (repeating if they still don't fit).
+    /// `previous_summary` only enters the final reduce step.
     pub async fn compact(
         &self,
         messages: &[HashMap<String, Value>],
         previous_summary: &str,
     ) -> Result<String> {
         debug!("compaction: summarizing {} messages", messages.len());
-        let formatted: Vec<String> = messages
+        let max_piece_chars = self.chunk_tokens * CHARS_PER_TOKEN_ESTIMATE;
+        let mut pieces: Vec<String> = messages
             .iter()
             .map(|m| {
                 let role = m.get("role").and_then(|v| v.as_str()).unwrap_or_default();
                 let content = extract_message_text(m.get("content"));
-                format!("{role}: {content}")
+                crate::utils::truncate_chars(
+                    &format!("{role}: {content}"),
+                    max_piece_chars,
+                    " [truncated]",
+                )
             })
             .collect();
 
-        let messages_text = formatted.join("\n");
-        let effective_summary = if previous_summary.is_empty() {
-            "(none)"
-        } else {
-            previous_summary
-        };
-        let prompt = COMPACTION_PROMPT
-            .replace("{previous_summary}", effective_summary)
-            .replace("{messages}", &messages_text);
-
-        let llm_messages = vec![Message::user(prompt)];
-
-        let response = self
-            .provider
-            .chat(&ChatRequest {
-                messages: llm_messages,
-                model: self.model.clone(),
-                max_tokens: COMPACTION_MAX_TOKENS,
-                temperature: self
-                    .temperature_override
-                    .map_or(COMPACTION_TEMPERATURE, Some),
-                ..Default::default()
-            })
-            .await?;
+        let mut round = 0;
+        loop {
+            let chunks = chunk_by_tokens(&pieces, self.chunk_tokens);
+            if chunks.len() <= 1 {
+                break;
+            }
+            round += 1;
+            if round > MAX_COMPACTION_ROUNDS {
+                return Err(anyhow::anyhow!(
+                    "compaction did not converge after {MAX_COMPACTION_ROUNDS} rounds"
+                ));
+            }
+            debug!(
+                "compaction: map-reduce round {round}, {} pieces in {} chunks",
+                pieces.len(),
+                chunks.len()
+            );
+            let total = chunks.len();
+            let mut partials = Vec::with_capacity(total);
+            for (i, chunk) in chunks.iter().enumerate() {
+                let partial = self.summarize(chunk, "").await?;
+                if partial.trim().is_empty() {
+                    warn!("compaction: chunk {}/{total} produced empty summary", i + 1);
+                    continue;
+                }
+                partials.push(format!(
+                    "Summary of part {}/{total}:\n{}",
+                    i + 1,
+                    partial.trim()
+                ));
+            }
+            pieces = partials;
+        }
 
-        let summary = response.content.unwrap_or_default();
+        let summary = self.summarize(&pieces.join("\n"), previous_summary).await?;
         if summary.trim().is_empty() {
             if !previous_summary.is_empty() {
                 warn!("compaction returned empty summary, reusing previous summary");
@@ -292,6 +434,34 @@ impl MessageCompactor {
         Ok(summary)
     }
 
+    /// Single compaction LLM call over already-formatted message text.
+    async fn summarize(&self, messages_text: &str, previous_summary: &str) -> Result<String> {
+        let effective_summary = if previous_summary.is_empty() {
+            "(none)"
+        } else {
+            previous_summary
+        };
+        let prompt = COMPACTION_PROMPT
+            .replace("{previous_summary}", effective_summary)
+            .replace("{messages}", messages_text);
+
+        let response = self
+            .chat(
+                ChatRequest {
+                    messages: vec![Message::user(prompt)],
+                    model: self.checkpoint_model.clone().or_else(|| self.model.clone()),
+                    max_tokens: COMPACTION_MAX_TOKENS,
+                    temperature: self
+                        .temperature_override
+                        .map_or(COMPACTION_TEMPERATURE, Some),
+                    ..Default::default()
+                },
+                CHECKPOINT_CALLER,
+            )
+            .await?;
+        Ok(response.content.unwrap_or_default())
+    }
+
     /// Review messages about to be compacted and extract important context.
     /// Returns extracted facts as a string, or empty if nothing worth preserving.
     pub async fn flush_to_memory(&self, messages: &[HashMap<String, Value>]) -> Result<String> {
@@ -314,16 +484,18 @@ impl MessageCompactor {
         let llm_messages = vec![Message::user(prompt)];
 
         let response = self
-            .provider
-            .chat(&ChatRequest {
-                messages: llm_messages,
-                model: self.model.clone(),
-                max_tokens: PRE_FLUSH_MAX_TOKENS,
-                temperature: self
-                    .temperature_override
-                    .map_or(PRE_FLUSH_TEMPERATURE, Some),
-                ..Default::default()
-            })
+            .chat_extraction(
+                ChatRequest {
+                    messages: llm_messages,
+                    model: self.extraction_model.clone().or_else(|| self.model.clone()),
+                    max_tokens: PRE_FLUSH_MAX_TOKENS,
+                    temperature: self
+                        .temperature_override
+                        .map_or(PRE_FLUSH_TEMPERATURE, Some),
+                    ..Default::default()
+                },
+                FLUSH_CALLER,
+            )
             .await?;
 
         // Guard: if the LLM hit max_tokens, the output is truncated and may be
@@ -345,13 +517,15 @@ impl MessageCompactor {
         }
     }
 
+    /// Extract new long-term facts from one or more `(user, assistant)`
+    /// exchanges in a single LLM call. Returns an empty string when there is
+    /// nothing new.
     pub async fn extract_facts(
         &self,
-        user_message: &str,
-        assistant_message: &str,
+        exchanges: &[(String, String)],
         existing_facts: &str,
     ) -> Result<String> {
-        debug!("extracting facts from exchange");
+        debug!("extracting facts from {} exchange(s)", exchanges.len());
         let effective_existing = if existing_facts.is_empty() {
             "(none)"
         } else {
@@ -359,28 +533,30 @@ impl MessageCompactor {
         };
         let prompt = EXTRACTION_PROMPT
             .replace("{existing_facts}", effective_existing)
-            .replace("{user_message}", user_message)
-            .replace("{assistant_message}", assistant_message);
+            .replace("{exchanges}", &format_exchanges(exchanges));
 
         let llm_messages = vec![Message::user(prompt)];
 
         let effective_temp = self
             .temperature_override
             .map_or(EXTRACTION_TEMPERATURE, Some);
+        let model = self.extraction_model.clone().or_else(|| self.model.clone());
         debug!(
             "fact extraction: model={:?}, temp_override={:?}, effective_temp={:?}",
-            self.model, self.temperature_override, effective_temp
+            model, self.temperature_override, effective_temp
         );
 
         let response = self
-            .provider
-            .chat(&ChatRequest {
-                messages: llm_messages,
-                model: self.model.clone(),
-                max_tokens: EXTRACTION_MAX_TOKENS,
-                temperature: effective_temp,
-                ..Default::default()
-            })
+            .chat_extraction(
+                ChatRequest {
+                    messages: llm_messages,
+                    model,
+                    max_tokens: EXTRACTION_MAX_TOKENS,
+                    temperature: effective_temp,
+                    ..Default::default()
+                },
+                EXTRACTION_CALLER,
+            )
             .await?;
 
         let content = response.content.unwrap_or_default();
@@ -396,6 +572,40 @@ impl MessageCompactor {
     }
 }
 
+/// Render exchanges as `User:` / `Assistant:` pairs for the extraction prompt.
+fn format_exchanges(exchanges: &[(String, String)]) -> String {
+    exchanges
+        .iter()
+        .map(|(user, assistant)| format!("User: {user}\n\nAssistant: {assistant}"))
+        .collect::<Vec<_>>()
+        .join("\n\n---\n\n")
+}
+
+/// Group formatted pieces into newline-joined chunks whose estimated token
+/// count stays within `budget`. A single piece larger than the budget gets a
+/// chunk of its own.
+fn chunk_by_tokens(pieces: &[String], budget: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_tokens = 0;
+    for piece in pieces {
+        let tokens = estimate_tokens(piece);
+        if !current.is_empty() && current_tokens + tokens > budget {
+            chunks.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(piece);
+        current_tokens += tokens;
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
 /// Find the split point in a message list that preserves the last N complete turns.
 /// A turn starts with a user message and includes all following non-user messages
 /// until the next user message. Returns the index to split at (messages before
@@ -425,5 +635,43 @@ pub fn split_at_turn_boundary(messages: &[HashMap<String, Value>], keep_turns: u
     *turn_starts.last().unwrap_or(&0)
 }
 
+/// Drop the oldest turns of `messages` until their estimated tokens fit
+/// `max_tokens`. Cuts only at user messages, so the history still starts with
+/// a user turn and tool results stay with the call that produced them. The
+/// latest turn is always kept, even when it alone is over the budget.
+#[allow(clippy::implicit_hasher)]
+pub fn trim_to_token_budget(
+    mut messages: Vec<HashMap<String, Value>>,
+    max_tokens: usize,
+) -> Vec<HashMap<String, Value>> {
+    let mut remaining = estimate_messages_tokens(&messages);
+    if remaining <= max_tokens {
+        return messages;
+    }
+    let is_user =
+        |m: &HashMap<String, Value>| m.get("role").and_then(Value::as_str) == Some("user");
+    let Some(last_turn) = messages.iter().rposition(is_user) else {
+        return messages;
+    };
+    let mut cut = 0;
+    for (i, msg) in messages.iter().enumerate() {
+        if i > 0 && is_user(msg) && (remaining <= max_tokens || i == last_turn) {
+            cut = i;
+            break;
+        }
+        remaining -= estimate_messages_tokens(std::slice::from_ref(msg));
+    }
+    if cut > 0 {
+        debug!(
+            "history over {} estimated tokens: dropped {} oldest messages",
+            max_tokens, cut
+        );
+        messages.drain(..cut);
+    }
+    // The input may itself start mid-turn (a message-count window)
+    strip_orphaned_tool_messages(&mut messages);
+    messages
+}
+
 #[cfg(test)]
 mod tests;
@@ -458,6 +458,203 @@ async fn flush_to_memory_empty_messages() {
     assert!(result.is_empty());
 }
 
+// ── Map-reduce compaction tests ──────────────────────────
+
+struct RecordingMock {
+    prompts: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl LLMProvider for RecordingMock {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        let mut prompts = self.prompts.lock().unwrap();
+        prompts.push(req.messages[0].content.clone());
+        Ok(LLMResponse {
+            content: Some(format!("S{}", prompts.len())),
+            ..Default::default()
+        })
+    }
+    fn default_model(&self) -> &'static str {
+        "mock"
+    }
+}
+
+fn recording_compactor(chunk_tokens: usize) -> (Arc<RecordingMock>, MessageCompactor) {
+    let provider = Arc::new(RecordingMock {
+        prompts: std::sync::Mutex::new(Vec::new()),
+    });
+    let mut compactor = MessageCompactor::new(provider.clone(), None);
+    compactor.chunk_tokens = chunk_tokens;
+    (provider, compactor)
+}
+
+#[test]
+fn chunk_by_tokens_respects_budget() {
+    let pieces: Vec<String> = (0..5).map(|i| format!("{i}{}", "x".repeat(39))).collect();
+    // 10 tokens per piece, budget 25 → two pieces per chunk
+    let chunks = chunk_by_tokens(&pieces, 25);
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].lines().count(), 2);
+    assert_eq!(chunks[2].lines().count(), 1);
+    assert!(chunk_by_tokens(&[], 25).is_empty());
+    // Oversized piece gets its own chunk
+    let big = vec!["y".repeat(400), "z".to_string()];
+    assert_eq!(chunk_by_tokens(&big, 25).len(), 2);
+}
+
+#[tokio::test]
+async fn compact_small_history_uses_single_call() {
+    let (provider, compactor) = recording_compactor(COMPACTION_CHUNK_TOKENS);
+    let messages = vec![user_msg("hello"), assistant_msg("hi there")];
+
+    let summary = compactor.compact(&messages, "earlier").await.unwrap();
+    assert_eq!(summary, "S1");
+    let prompts = provider.prompts.lock().unwrap();
+    assert_eq!(prompts.len(), 1);
+    assert!(prompts[0].contains("earlier"));
+    assert!(prompts[0].contains("user: hello"));
+}
+
+#[tokio::test]
+async fn compact_large_history_map_reduces() {
+    let (provider, compactor) = recording_compactor(25);
+    let messages: Vec<_> = (0..6)
+        .map(|i| user_msg(&format!("message {i} {}", "x".repeat(30))))
+        .collect();
+
+    let summary = compactor.compact(&messages, "earlier").await.unwrap();
+    let prompts = provider.prompts.lock().unwrap();
+    // 3 map calls (two messages each) + 1 reduce call
+    assert_eq!(prompts.len(), 4);
+    assert_eq!(summary, "S4");
+    for map_prompt in &prompts[..3] {
+        assert!(map_prompt.contains("(none)"));
+        assert!(!map_prompt.contains("earlier"));
+    }
+    let reduce = &prompts[3];
+    assert!(reduce.contains("earlier"));
+    assert!(reduce.contains("Summary of part 1/3:\nS1"));
+    assert!(reduce.contains("Summary of part 3/3:\nS3"));
+    assert!(!reduce.contains("message 0"));
+}
+
+// ── Per-call model and cost attribution ─────────────────
+
+struct ModelMock {
+    models: std::sync::Mutex<Vec<Option<String>>>,
+}
+
+#[async_trait]
+impl LLMProvider for ModelMock {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        self.models.lock().unwrap().push(req.model.clone());
+        Ok(LLMResponse {
+            content: Some("- likes tea".into()),
+            input_tokens: Some(100),
+            output_tokens: Some(10),
+            ..Default::default()
+        })
+    }
+    fn default_model(&self) -> &'static str {
+        "mock"
+    }
+}
+
+#[tokio::test]
+async fn each_call_uses_its_model_and_caller() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = Arc::new(MemoryDB::new(tmp.path().join("memory.db")).unwrap());
+    let provider = Arc::new(ModelMock {
+        models: std::sync::Mutex::new(Vec::new()),
+    });
+    let compactor = MessageCompactor::new(provider.clone(), Some("main-model".into()))
+        .with_extraction_model(Some("extract-model".into()))
+        .with_checkpoint_model(Some("summary-model".into()))
+        .with_cost_db(db.clone());
+
+    let messages = vec![user_msg("I like tea"), assistant_msg("Noted")];
+    compactor.compact(&messages, "").await.unwrap();
+    compactor.flush_to_memory(&messages).await.unwrap();
+    compactor
+        .extract_facts(&[("I like tea".into(), "Noted".into())], "")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *provider.models.lock().unwrap(),
+        vec![
+            Some("summary-model".to_string()),
+            Some("extract-model".to_string()),
+            Some("extract-model".to_string()),
+        ]
+    );
+    let conn = db.lock_conn().unwrap();
+    let mut stmt = conn
+        .prepare("SELECT model, caller, input_tokens FROM llm_cost_log ORDER BY id")
+        .unwrap();
+    let rows: Vec<(String, String, i64)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            ("summary-model".into(), CHECKPOINT_CALLER.into(), 100),
+            ("extract-model".into(), FLUSH_CALLER.into(), 100),
+            ("extract-model".into(), EXTRACTION_CALLER.into(), 100),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn extraction_uses_its_own_provider() {
+    let main = Arc::new(ModelMock {
+        models: std::sync::Mutex::new(Vec::new()),
+    });
+    let other = Arc::new(ModelMock {
+        models: std::sync::Mutex::new(Vec::new()),
+    });
+    let compactor = MessageCompactor::new(main.clone(), Some("main-model".into()))
+        .with_extraction_provider(Some((other.clone(), "other-model".into())));
+
+    let messages = vec![user_msg("I like tea"), assistant_msg("Noted")];
+    compactor.compact(&messages, "").await.unwrap();
+    compactor.flush_to_memory(&messages).await.unwrap();
+    compactor
+        .extract_facts(&[("I like tea".into(), "Noted".into())], "")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *main.models.lock().unwrap(),
+        vec![Some("main-model".to_string())]
+    );
+    assert_eq!(
+        *other.models.lock().unwrap(),
+        vec![
+            Some("other-model".to_string()),
+            Some("other-model".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn call_models_fall_back_to_compaction_model() {
+    let provider = Arc::new(ModelMock {
+        models: std::sync::Mutex::new(Vec::new()),
+    });
+    let compactor = MessageCompactor::new(provider.clone(), Some("main-model".into()))
+        .with_extraction_model(None)
+        .with_checkpoint_model(None);
+
+    let messages = vec![user_msg("I like tea")];
+    compactor.compact(&messages, "").await.unwrap();
+    compactor.flush_to_memory(&messages).await.unwrap();
+    let models = provider.models.lock().unwrap();
+    assert!(models.iter().all(|m| m.as_deref() == Some("main-model")));
+}
+
 // ── finish_reason guard tests ────────────────────────────
 
 fn sample_messages() -> Vec<HashMap<String, Value>> {
@@ -656,6 +853,64 @@ fn split_at_turn_boundary_tool_heavy_single_turn() {
     assert_eq!(split_at_turn_boundary(&msgs, 1), 0);
 }
 
+// ── trim_to_token_budget tests ───────────────────────────
+
+#[test]
+fn trim_to_token_budget_keeps_history_that_fits() {
+    let msgs = vec![user_msg("hello"), assistant_msg("hi there")];
+    assert_eq!(trim_to_token_budget(msgs.clone(), 100), msgs);
+}
+
+#[test]
+fn trim_to_token_budget_drops_whole_oldest_turns() {
+    let big = "x".repeat(400); // 100 tokens
+    let msgs = vec![
+        user_msg(&big),
+        assistant_with_tool_calls("", &["tc_1"]),
+        tool_result_msg("tc_1", &big),
+        assistant_msg("done"),
+        user_msg("second"),
+        assistant_msg(&big),
+        user_msg("third"),
+        assistant_msg("ok"),
+    ];
+    let trimmed = trim_to_token_budget(msgs.clone(), 150);
+    // The first turn (user, tool call and its result) goes as a unit
+    assert_eq!(trimmed, msgs[4..].to_vec());
+    assert_eq!(trimmed[0]["role"], "user");
+
+    // Budget below the second turn too: only the latest turn is left
+    let trimmed = trim_to_token_budget(msgs.clone(), 50);
+    assert_eq!(trimmed, msgs[6..].to_vec());
+}
+
+#[test]
+fn trim_to_token_budget_keeps_oversized_latest_turn() {
+    let big = "x".repeat(4000);
+    let msgs = vec![
+        user_msg("old"),
+        assistant_msg("reply"),
+        user_msg(&big),
+        assistant_msg(&big),
+    ];
+    assert_eq!(trim_to_token_budget(msgs.clone(), 10), msgs[2..].to_vec());
+}
+
+#[test]
+fn trim_to_token_budget_strips_leading_orphans() {
+    let big = "x".repeat(400);
+    // Starts mid-turn, as a message-count window can
+    let msgs = vec![
+        tool_result_msg("tc_0", "orphan"),
+        assistant_msg(&big),
+        user_msg("next"),
+        assistant_with_tool_calls("", &["tc_1"]),
+        tool_result_msg("tc_1", "result"),
+    ];
+    let trimmed = trim_to_token_budget(msgs.clone(), 50);
+    assert_eq!(trimmed, msgs[2..].to_vec());
+}
+
 // ── Anthropic-style tool_use with mixed orphans ──────────
 
 #[test]
@@ -25,6 +25,10 @@ pub struct ContextBuilder {
     bootstrap_mtimes: HashMap<String, u64>,
     providers: Option<Arc<providers::ContextProviderRunner>>,
     cached_provider_context: Option<String>,
+    /// Add the sender's saved preferences to the system prompt
+    sender_preferences: bool,
+    /// `channels.<name>.personaSuffix`, by channel name
+    persona_suffixes: HashMap<String, String>,
 }
 
 impl ContextBuilder {
@@ -78,6 +82,8 @@ impl ContextBuilder {
             bootstrap_mtimes: HashMap::new(),
             providers: None,
             cached_provider_context: None,
+            sender_preferences: false,
+            persona_suffixes: HashMap::new(),
         })
     }
 
@@ -107,6 +113,39 @@ impl ContextBuilder {
         self.providers = Some(runner);
     }
 
+    pub fn set_sender_preferences(&mut self, enabled: bool) {
+        self.sender_preferences = enabled;
+    }
+
+    pub fn set_persona_suffixes(&mut self, suffixes: HashMap<String, String>) {
+        self.persona_suffixes = suffixes;
+    }
+
+    /// System prompt section listing the preferences `sender_id` saved with
+    /// `set_preference`, or `None` when there are none.
+    fn sender_preferences_section(&self, channel: &str, sender_id: &str) -> Option<String> {
+        use std::fmt::Write as _;
+
+        let key = crate::agent::tools::set_preference::sender_key(channel, sender_id);
+        let prefs = self
+            .memory
+            .db()
+            .get_sender_preferences(&key)
+            .inspect_err(|e| warn!("failed to load preferences for {}: {}", key, e))
+            .ok()?;
+        if prefs.is_empty() {
+            return None;
+        }
+        let mut section = "\n\n## User Preferences\n\nThe person writing saved these \
+                           preferences. Follow them unless this message asks otherwise; \
+                           update them with set_preference."
+            .to_string();
+        for (key, value) in prefs {
+            let _ = write!(section, "\n- {key}: {value}");
+        }
+        Some(section)
+    }
+
     pub async fn refresh_provider_context(&mut self) {
         if let Some(ref runner) = self.providers {
             let ctx = runner.get_all_context().await;
@@ -119,15 +158,30 @@ impl ContextBuilder {
         _skill_names: Option<&[String]>,
         query: Option<&str>,
     ) -> Result<String> {
-        self.build_system_prompt_inner(query, false)
+        self.build_system_prompt_inner(query, false, None)
     }
 
-    fn build_system_prompt_inner(&mut self, query: Option<&str>, is_group: bool) -> Result<String> {
+    fn build_system_prompt_inner(
+        &mut self,
+        query: Option<&str>,
+        is_group: bool,
+        channel: Option<&str>,
+    ) -> Result<String> {
         let mut parts = Vec::new();
 
         // Core identity
         parts.push(self.get_identity());
 
+        // Per-channel identity overlay (AGENTS.{channel}.md)
+        if let Some(overlay) = channel.and_then(|ch| self.load_channel_overlay(ch)) {
+            parts.push(overlay);
+        }
+
+        // Per-channel persona from config (channels.<name>.personaSuffix)
+        if let Some(suffix) = channel.and_then(|ch| self.persona_suffixes.get(ch)) {
+            parts.push(suffix.clone());
+        }
+
         // Bootstrap files
         let bootstrap = self.load_bootstrap_files();
         if !bootstrap.is_empty() {
@@ -238,6 +292,42 @@ impl ContextBuilder {
         )
     }
 
+    /// Load the optional `AGENTS.{channel}.md` overlay for a channel. Its
+    /// content is placed right after the core identity and takes precedence
+    /// over it where the two disagree (e.g. tone on Slack vs Telegram).
+    fn load_channel_overlay(&self, channel: &str) -> Option<String> {
+        if channel.is_empty()
+            || !channel
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return None;
+        }
+        let filename = format!("AGENTS.{channel}.md");
+        let path = self.workspace.join(&filename);
+        let meta = std::fs::metadata(&path).ok()?;
+        if meta.len() > MAX_CONTEXT_FILE_SIZE {
+            warn!(
+                "{} is too large ({} bytes, max {}), skipping",
+                filename,
+                meta.len(),
+                MAX_CONTEXT_FILE_SIZE
+            );
+            return None;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(content) if !content.trim().is_empty() => Some(format!(
+                "# Channel Persona ({channel})\n\nThe following adjusts your identity for the {channel} channel. Where it conflicts with the identity above, follow these instructions.\n\n{}",
+                content.trim()
+            )),
+            Ok(_) => None,
+            Err(e) => {
+                warn!("Failed to load {filename}: {e}");
+                None
+            }
+        }
+    }
+
     fn build_identity_with_context(
         identity_content: &str,
         now: &str,
@@ -325,13 +415,13 @@ impl ContextBuilder {
     fn channel_formatting_hint(channel: &str) -> Option<&'static str> {
         match channel {
             "discord" => Some(
-                "Formatting: Markdown supported but NOT tables. Wrap URLs in <> to suppress embeds. Max 2000 chars per message.",
+                "Formatting: Markdown supported but NOT tables. Wrap URLs in <> to suppress embeds. Max 2000 chars per message. Start a reply with [REACT:✅] to react to the user's message; send only the marker when a text reply would be noise (e.g. confirming something was remembered).",
             ),
             "telegram" => Some(
-                "Formatting: Bold, italic, code, and bullet lists work. Tables NOT supported. Max 4096 chars per message.",
+                "Formatting: Bold, italic, code, and bullet lists work. Tables NOT supported. Max 4096 chars per message. Start a reply with [REACT:👍] to react to the user's message; send only the marker when a text reply would be noise (e.g. confirming something was remembered).",
             ),
             "slack" => Some(
-                "Formatting: Use Slack mrkdwn — *bold*, _italic_, `code`. Standard markdown ** does NOT work. Prefer threaded replies.",
+                "Formatting: Use Slack mrkdwn — *bold*, _italic_, `code`. Standard markdown ** does NOT work. Prefer threaded replies. Start a reply with [REACT:✅] to react to the user's message; send only the marker when a text reply would be noise (e.g. confirming something was remembered).",
             ),
             "whatsapp" => Some(
                 "Formatting: Keep messages concise. Headers/tables ignored. Bold (*text*) and italic (_text_) work.",
@@ -356,7 +446,8 @@ impl ContextBuilder {
         let mut messages = Vec::new();
 
         // System prompt
-        let mut system_prompt = self.build_system_prompt_inner(Some(current_message), is_group)?;
+        let mut system_prompt =
+            self.build_system_prompt_inner(Some(current_message), is_group, channel)?;
         if let (Some(ch), Some(cid)) = (channel, chat_id) {
             let mut session_info = format!("\n\n## Current Session\nChannel: {ch}\nChat ID: {cid}");
             if let Some(sid) = sender_id {
@@ -368,6 +459,12 @@ impl ContextBuilder {
                 let _ = write!(session_info, "\n{hint}");
             }
             system_prompt.push_str(&session_info);
+            if self.sender_preferences
+                && let Some(sid) = sender_id
+                && let Some(section) = self.sender_preferences_section(ch, sid)
+            {
+                system_prompt.push_str(&section);
+            }
         }
         // Tell the model that the history below IS its real conversation, so it
         // doesn't claim it "can't look up past messages" or needs a tool to do so.
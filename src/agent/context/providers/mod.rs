@@ -1,17 +1,25 @@
-use crate::config::ContextProviderConfig;
+use crate::agent::weather::{WeatherContext, WeatherContextProvider};
+use crate::config::{ContextProviderConfig, ContextProviderKind};
+use futures_util::future::join_all;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tracing::{debug, warn};
 
+/// Last result of a provider run. `content` is `None` when the run failed,
+/// so failing or slow providers are not retried before their TTL expires.
 struct CachedOutput {
-    content: String,
+    content: Option<String>,
     fetched_at: Instant,
 }
 
 pub struct ContextProviderRunner {
     providers: Vec<ContextProviderConfig>,
     cache: Mutex<HashMap<String, CachedOutput>>,
+    /// Current weather conditions (`tools.weather.contextConditions`)
+    weather: Option<Arc<WeatherContext>>,
+    /// Source for `kind = "weather"` providers
+    weather_provider: Option<Arc<WeatherContextProvider>>,
 }
 
 impl ContextProviderRunner {
@@ -19,17 +27,44 @@ impl ContextProviderRunner {
         Self {
             providers,
             cache: Mutex::new(HashMap::new()),
+            weather: None,
+            weather_provider: None,
         }
     }
 
+    /// Add a "Weather" section with the current conditions.
+    #[must_use]
+    pub fn with_weather(mut self, weather: Arc<WeatherContext>) -> Self {
+        self.weather = Some(weather);
+        self
+    }
+
+    /// Serve `kind = "weather"` providers. Without it they are skipped.
+    #[must_use]
+    pub fn with_weather_provider(mut self, provider: Arc<WeatherContextProvider>) -> Self {
+        self.weather_provider = Some(provider);
+        self
+    }
+
+    /// Collect output from all enabled providers. Each provider is only
+    /// re-executed once its TTL has elapsed (`ttl = 0` refreshes every
+    /// turn); providers that are due run concurrently.
     pub async fn get_all_context(&self) -> String {
-        let mut sections = Vec::new();
+        let mut active = Vec::new();
 
         for provider in &self.providers {
             if !provider.enabled {
                 continue;
             }
 
+            if provider.kind == ContextProviderKind::Weather && self.weather_provider.is_none() {
+                debug!(
+                    "context provider '{}' skipped: weather is not configured",
+                    provider.name
+                );
+                continue;
+            }
+
             // Check required binaries
             if !provider.requires_bins.is_empty()
                 && !Self::check_bins_available(&provider.requires_bins)
@@ -52,12 +87,30 @@ impl ContextProviderRunner {
                 continue;
             }
 
-            match self.get_provider_output(provider).await {
-                Some(output) if !output.trim().is_empty() => {
-                    sections.push(format!("### {}\n{}", provider.name, output));
-                }
-                _ => {}
+            active.push(provider);
+        }
+
+        let weather = async {
+            match self.weather {
+                Some(ref weather) => weather.conditions().await,
+                None => None,
             }
+        };
+        let (outputs, weather) = tokio::join!(
+            join_all(active.iter().map(|p| self.get_provider_output(p))),
+            weather
+        );
+        let mut sections: Vec<String> = active
+            .iter()
+            .zip(outputs)
+            .filter_map(|(provider, output)| {
+                output
+                    .filter(|o| !o.trim().is_empty())
+                    .map(|o| format!("### {}\n{}", provider.name, o))
+            })
+            .collect();
+        if let Some(weather) = weather {
+            sections.push(format!("### Weather\n{weather}"));
         }
 
         if sections.is_empty() {
@@ -75,14 +128,39 @@ impl ContextProviderRunner {
                 poison.into_inner()
             });
             if let Some(cached) = cache.get(&provider.name)
-                && cached.fetched_at.elapsed() < Duration::from_secs(provider.ttl)
+                && cached.fetched_at.elapsed() < Duration::from_secs(provider.effective_ttl())
             {
-                return Some(cached.content.clone());
+                return cached.content.clone();
             }
         }
 
-        // Execute command with scrubbed environment
-        let output = match tokio::time::timeout(
+        let output = match (provider.kind, &self.weather_provider) {
+            (ContextProviderKind::Weather, Some(weather)) => Some(weather.fetch().await),
+            (ContextProviderKind::Weather, None) => None,
+            (ContextProviderKind::Command, _) => Self::run_command(provider).await,
+        };
+
+        // Update cache
+        {
+            let mut cache = self.cache.lock().unwrap_or_else(|poison| {
+                warn!("context provider cache mutex was poisoned, recovering");
+                poison.into_inner()
+            });
+            cache.insert(
+                provider.name.clone(),
+                CachedOutput {
+                    content: output.clone(),
+                    fetched_at: Instant::now(),
+                },
+            );
+        }
+
+        output
+    }
+
+    /// Execute the provider's command with a scrubbed environment.
+    async fn run_command(provider: &ContextProviderConfig) -> Option<String> {
+        match tokio::time::timeout(
             Duration::from_secs(provider.timeout),
             crate::utils::subprocess::scrubbed_command(&provider.command)
                 .args(&provider.args)
@@ -99,53 +177,35 @@ impl ContextProviderRunner {
                     result.push_str("\n[stderr] ");
                     result.push_str(stderr.trim());
                 }
-                result
+                debug!(
+                    "context provider '{}' executed: {} bytes",
+                    provider.name,
+                    result.len()
+                );
+                Some(result)
             }
             Ok(Ok(output)) => {
                 warn!(
                     "context provider '{}' exited with status {}",
                     provider.name, output.status
                 );
-                return None;
+                None
             }
             Ok(Err(e)) => {
                 warn!(
                     "context provider '{}' failed to execute: {}",
                     provider.name, e
                 );
-                return None;
+                None
             }
             Err(_) => {
                 warn!(
                     "context provider '{}' timed out after {}s",
                     provider.name, provider.timeout
                 );
-                return None;
+                None
             }
-        };
-
-        debug!(
-            "context provider '{}' executed: {} bytes",
-            provider.name,
-            output.len()
-        );
-
-        // Update cache
-        {
-            let mut cache = self.cache.lock().unwrap_or_else(|poison| {
-                warn!("context provider cache mutex was poisoned, recovering");
-                poison.into_inner()
-            });
-            cache.insert(
-                provider.name.clone(),
-                CachedOutput {
-                    content: output.clone(),
-                    fetched_at: Instant::now(),
-                },
-            );
         }
-
-        Some(output)
     }
 
     fn check_bins_available(bins: &[String]) -> bool {
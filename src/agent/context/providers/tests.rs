@@ -15,11 +15,12 @@ fn test_empty_providers() {
 fn test_disabled_provider_skipped() {
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "test".to_string(),
+        kind: ContextProviderKind::Command,
         command: "echo".to_string(),
         args: vec!["hello".to_string()],
         enabled: false,
         timeout: 5,
-        ttl: 300,
+        ttl: Some(300),
         requires_bins: vec![],
         requires_env: vec![],
     }]);
@@ -35,11 +36,12 @@ fn test_disabled_provider_skipped() {
 fn test_echo_provider_returns_output() {
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "test".to_string(),
+        kind: ContextProviderKind::Command,
         command: "echo".to_string(),
         args: vec!["hello world".to_string()],
         enabled: true,
         timeout: 5,
-        ttl: 300,
+        ttl: Some(300),
         requires_bins: vec![],
         requires_env: vec![],
     }]);
@@ -57,11 +59,12 @@ fn test_echo_provider_returns_output() {
 fn test_missing_binary_skipped() {
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "test".to_string(),
+        kind: ContextProviderKind::Command,
         command: "echo".to_string(),
         args: vec![],
         enabled: true,
         timeout: 5,
-        ttl: 300,
+        ttl: Some(300),
         requires_bins: vec!["nonexistent_binary_xyz_123".to_string()],
         requires_env: vec![],
     }]);
@@ -77,11 +80,12 @@ fn test_missing_binary_skipped() {
 fn test_ttl_cache() {
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "test".to_string(),
+        kind: ContextProviderKind::Command,
         command: "echo".to_string(),
         args: vec!["cached".to_string()],
         enabled: true,
         timeout: 5,
-        ttl: 300,
+        ttl: Some(300),
         requires_bins: vec![],
         requires_env: vec![],
     }]);
@@ -101,11 +105,12 @@ fn test_ttl_cache() {
 fn test_command_timeout() {
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "slow".to_string(),
+        kind: ContextProviderKind::Command,
         command: "sleep".to_string(),
         args: vec!["10".to_string()],
         enabled: true,
         timeout: 1, // 1 second timeout
-        ttl: 300,
+        ttl: Some(300),
         requires_bins: vec![],
         requires_env: vec![],
     }]);
@@ -124,11 +129,12 @@ fn test_command_timeout() {
 fn test_missing_env_var_skipped() {
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "needs-env".to_string(),
+        kind: ContextProviderKind::Command,
         command: "echo".to_string(),
         args: vec!["hi".to_string()],
         enabled: true,
         timeout: 5,
-        ttl: 300,
+        ttl: Some(300),
         requires_bins: vec![],
         requires_env: vec!["OXICRAB_NONEXISTENT_TEST_VAR_12345".to_string()],
     }]);
@@ -145,6 +151,7 @@ fn test_stderr_included_in_output() {
     // bash -c writes to stderr then stdout
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "stderr-test".to_string(),
+        kind: ContextProviderKind::Command,
         command: "bash".to_string(),
         args: vec![
             "-c".to_string(),
@@ -152,7 +159,7 @@ fn test_stderr_included_in_output() {
         ],
         enabled: true,
         timeout: 5,
-        ttl: 300,
+        ttl: Some(300),
         requires_bins: vec![],
         requires_env: vec![],
     }]);
@@ -169,11 +176,12 @@ fn test_stderr_included_in_output() {
 fn test_nonzero_exit_code_skipped() {
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "failing".to_string(),
+        kind: ContextProviderKind::Command,
         command: "bash".to_string(),
         args: vec!["-c".to_string(), "exit 1".to_string()],
         enabled: true,
         timeout: 5,
-        ttl: 300,
+        ttl: Some(300),
         requires_bins: vec![],
         requires_env: vec![],
     }]);
@@ -190,11 +198,12 @@ fn test_cache_expiration() {
     // TTL of 0 means cache always expires
     let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
         name: "volatile".to_string(),
+        kind: ContextProviderKind::Command,
         command: "bash".to_string(),
         args: vec!["-c".to_string(), "echo $RANDOM".to_string()],
         enabled: true,
         timeout: 5,
-        ttl: 0,
+        ttl: Some(0),
         requires_bins: vec![],
         requires_env: vec![],
     }]);
@@ -216,21 +225,23 @@ fn test_multiple_providers_combined_output() {
     let runner = ContextProviderRunner::new(vec![
         ContextProviderConfig {
             name: "alpha".to_string(),
+            kind: ContextProviderKind::Command,
             command: "echo".to_string(),
             args: vec!["first".to_string()],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec![],
             requires_env: vec![],
         },
         ContextProviderConfig {
             name: "beta".to_string(),
+            kind: ContextProviderKind::Command,
             command: "echo".to_string(),
             args: vec!["second".to_string()],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec![],
             requires_env: vec![],
         },
@@ -246,3 +257,125 @@ fn test_multiple_providers_combined_output() {
     assert!(output.contains("### beta"));
     assert!(output.contains("second"));
 }
+
+fn pid_provider(name: &str, ttl: u64) -> ContextProviderConfig {
+    // Each execution is a new process, so `$$` changes on every refresh
+    ContextProviderConfig {
+        name: name.to_string(),
+        kind: ContextProviderKind::Command,
+        command: "sh".to_string(),
+        args: vec!["-c".to_string(), "echo $$".to_string()],
+        enabled: true,
+        timeout: 5,
+        ttl: Some(ttl),
+        requires_bins: vec![],
+        requires_env: vec![],
+    }
+}
+
+#[test]
+fn test_ttl_is_per_provider() {
+    let runner =
+        ContextProviderRunner::new(vec![pid_provider("cached", 600), pid_provider("live", 0)]);
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let section = |output: &str, name: &str| {
+        let header = format!("### {name}\n");
+        let start = output.find(&header).unwrap() + header.len();
+        output[start..].lines().next().unwrap().to_string()
+    };
+
+    let first = rt.block_on(runner.get_all_context());
+    let second = rt.block_on(runner.get_all_context());
+    assert_eq!(section(&first, "cached"), section(&second, "cached"));
+    assert_ne!(section(&first, "live"), section(&second, "live"));
+}
+
+#[test]
+fn test_failed_provider_not_retried_within_ttl() {
+    let runner = ContextProviderRunner::new(vec![ContextProviderConfig {
+        name: "broken".to_string(),
+        kind: ContextProviderKind::Command,
+        command: "bash".to_string(),
+        args: vec!["-c".to_string(), "exit 1".to_string()],
+        enabled: true,
+        timeout: 5,
+        ttl: Some(600),
+        requires_bins: vec![],
+        requires_env: vec![],
+    }]);
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    assert!(rt.block_on(runner.get_all_context()).is_empty());
+    let fetched_at = {
+        let cache = runner.cache.lock().unwrap();
+        let entry = cache.get("broken").unwrap();
+        assert!(entry.content.is_none());
+        entry.fetched_at
+    };
+
+    assert!(rt.block_on(runner.get_all_context()).is_empty());
+    let cache = runner.cache.lock().unwrap();
+    assert_eq!(cache.get("broken").unwrap().fetched_at, fetched_at);
+}
+
+#[tokio::test]
+async fn test_weather_kind_provider() {
+    use crate::config::WeatherConfig;
+    use oxicrab_tools_api::weather::WeatherTool;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/weather"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "main": {"temp": 12.0},
+            "weather": [{"description": "light rain"}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let provider = ContextProviderConfig {
+        name: "Outside".to_string(),
+        kind: ContextProviderKind::Weather,
+        command: String::new(),
+        args: vec![],
+        enabled: true,
+        timeout: 5,
+        ttl: None,
+        requires_bins: vec![],
+        requires_env: vec![],
+    };
+    assert_eq!(provider.effective_ttl(), 1800);
+
+    // Skipped while weather is not configured
+    let unconfigured = ContextProviderRunner::new(vec![provider.clone()]);
+    assert!(unconfigured.get_all_context().await.is_empty());
+
+    let weather = WeatherContextProvider::new(
+        WeatherTool::with_base_url("test_key".to_string(), server.uri()),
+        WeatherConfig {
+            enabled: true,
+            api_key: "test_key".to_string(),
+            locations: vec!["London".to_string()],
+            units: crate::config::WeatherUnits::Metric,
+            ..Default::default()
+        },
+    );
+    let runner =
+        ContextProviderRunner::new(vec![provider]).with_weather_provider(Arc::new(weather));
+    let output = runner.get_all_context().await;
+    assert!(
+        output.contains("### Outside\nWeather: 12°C, light rain"),
+        "{output}"
+    );
+    // Served from the cache within the default TTL
+    assert_eq!(runner.get_all_context().await, output);
+}
@@ -124,6 +124,65 @@ fn test_identity_falls_back_when_no_file() {
     );
 }
 
+#[test]
+fn test_channel_overlay_loaded_when_present() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mut ctx = create_test_context(tmp.path());
+    std::fs::write(tmp.path().join("AGENTS.md"), "# My Bot\n\nBe casual.").unwrap();
+    std::fs::write(tmp.path().join("AGENTS.slack.md"), "Be formal on Slack.").unwrap();
+
+    let slack = ctx
+        .build_system_prompt_inner(None, false, Some("slack"))
+        .unwrap();
+    assert!(slack.contains("Be casual."));
+    assert!(slack.contains("# Channel Persona (slack)"));
+    assert!(slack.contains("Be formal on Slack."));
+    assert!(
+        slack.find("Be casual.").unwrap() < slack.find("Be formal on Slack.").unwrap(),
+        "overlay should follow the core identity"
+    );
+
+    let telegram = ctx
+        .build_system_prompt_inner(None, false, Some("telegram"))
+        .unwrap();
+    assert!(!telegram.contains("Channel Persona"));
+    assert!(!telegram.contains("Be formal on Slack."));
+}
+
+#[test]
+fn test_persona_suffix_follows_identity_on_its_channel() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mut ctx = create_test_context(tmp.path());
+    std::fs::write(tmp.path().join("AGENTS.md"), "# My Bot\n\nBe helpful.").unwrap();
+    ctx.set_persona_suffixes(HashMap::from([(
+        "slack".to_string(),
+        "Use a formal, professional tone.".to_string(),
+    )]));
+
+    let slack = ctx
+        .build_system_prompt_inner(None, false, Some("slack"))
+        .unwrap();
+    let identity = slack.find("Be helpful.").unwrap();
+    let persona = slack.find("Use a formal, professional tone.").unwrap();
+    assert!(identity < persona, "persona should follow AGENTS.md");
+
+    let whatsapp = ctx
+        .build_system_prompt_inner(None, false, Some("whatsapp"))
+        .unwrap();
+    assert!(!whatsapp.contains("formal, professional"));
+}
+
+#[test]
+fn test_channel_overlay_rejects_unsafe_names() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let ctx = create_test_context(tmp.path());
+    std::fs::write(tmp.path().join("AGENTS.x.md"), "secret").unwrap();
+
+    assert!(ctx.load_channel_overlay("../x").is_none());
+    assert!(ctx.load_channel_overlay("").is_none());
+    assert!(ctx.load_channel_overlay("x").is_some());
+}
+
 #[test]
 fn test_bootstrap_loads_user_md() {
     let tmp = tempfile::TempDir::new().unwrap();
@@ -246,6 +305,40 @@ async fn test_build_messages_no_sender_id() {
     );
 }
 
+#[tokio::test]
+async fn test_build_messages_includes_sender_preferences() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mut ctx = create_test_context(tmp.path());
+    ctx.memory
+        .db()
+        .set_sender_preference("telegram:user42", "verbosity", "terse")
+        .unwrap();
+    let build = |ctx: &mut ContextBuilder, sender: &str| {
+        ctx.build_messages(
+            &[],
+            "hello",
+            Some("telegram"),
+            Some("123"),
+            Some(sender),
+            vec![],
+            false,
+            None,
+        )
+        .unwrap()[0]
+            .content
+            .clone()
+    };
+
+    // Off unless enabled
+    assert!(!build(&mut ctx, "user42").contains("## User Preferences"));
+
+    ctx.set_sender_preferences(true);
+    let prompt = build(&mut ctx, "user42");
+    assert!(prompt.contains("## User Preferences"), "{prompt}");
+    assert!(prompt.contains("- verbosity: terse"));
+    assert!(!build(&mut ctx, "user7").contains("## User Preferences"));
+}
+
 #[tokio::test]
 async fn test_build_messages_with_images() {
     let tmp = tempfile::TempDir::new().unwrap();
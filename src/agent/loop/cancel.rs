@@ -0,0 +1,109 @@
+//! `/cancel`: stop the turn running in the sender's chat. The command skips
+//! the session lock the running turn holds and sets that turn's flag; the
+//! turn checks it between iterations, stops without running further tools
+//! and replies [`CANCELLED_REPLY`]. The user message and the tools used so
+//! far are saved to the session like any other turn.
+
+use super::config::AgentLoopResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub(super) const CANCELLED_REPLY: &str = "Cancelled.";
+pub(super) const NOTHING_TO_CANCEL_REPLY: &str = "Nothing to cancel.";
+const COMMAND: &str = "/cancel";
+
+/// Whether `content` is the `/cancel` command.
+pub(super) fn is_command(content: &str) -> bool {
+    content.trim().eq_ignore_ascii_case(COMMAND)
+}
+
+/// Cancellation flags of the turns currently running, by session key.
+pub(super) struct TurnCancellations {
+    active: std::sync::Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl TurnCancellations {
+    pub fn new() -> Self {
+        Self {
+            active: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register the turn that now holds `session_key`'s lock. The flag is
+    /// dropped with the returned guard.
+    pub fn begin(&self, session_key: &str) -> TurnGuard<'_> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.lock()
+            .insert(session_key.to_string(), Arc::clone(&flag));
+        TurnGuard {
+            owner: self,
+            session_key: session_key.to_string(),
+            flag,
+        }
+    }
+
+    /// Flag of the turn running in `session_key`, if any.
+    pub fn flag(&self, session_key: &str) -> Option<Arc<AtomicBool>> {
+        self.lock().get(session_key).cloned()
+    }
+
+    /// Ask the turn running in `session_key` to stop. Returns `false` when
+    /// no turn is running there.
+    pub fn cancel(&self, session_key: &str) -> bool {
+        self.lock().get(session_key).is_some_and(|flag| {
+            flag.store(true, Ordering::Relaxed);
+            true
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<AtomicBool>>> {
+        self.active
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Unregisters a turn's cancellation flag when the turn ends.
+pub(super) struct TurnGuard<'a> {
+    owner: &'a TurnCancellations,
+    session_key: String,
+    flag: Arc<AtomicBool>,
+}
+
+impl Drop for TurnGuard<'_> {
+    fn drop(&mut self) {
+        let mut active = self.owner.lock();
+        // A later turn of the session may have registered since
+        if active
+            .get(&self.session_key)
+            .is_some_and(|flag| Arc::ptr_eq(flag, &self.flag))
+        {
+            active.remove(&self.session_key);
+        }
+    }
+}
+
+pub(super) fn is_cancelled(flag: Option<&Arc<AtomicBool>>) -> bool {
+    flag.is_some_and(|flag| flag.load(Ordering::Relaxed))
+}
+
+/// Result of a cancelled run: the work done so far and [`CANCELLED_REPLY`].
+pub(super) fn cancelled_result(
+    input_tokens: Option<u64>,
+    tools_used: Vec<String>,
+    media: Vec<String>,
+    tool_metadata: Vec<(String, HashMap<String, serde_json::Value>)>,
+) -> AgentLoopResult {
+    AgentLoopResult {
+        content: Some(CANCELLED_REPLY.to_string()),
+        input_tokens,
+        tools_used,
+        media,
+        reasoning_content: None,
+        reasoning_signature: None,
+        response_metadata: HashMap::new(),
+        tool_metadata,
+        continuation: None,
+    }
+}
@@ -1,3 +1,4 @@
+use super::session_archive::ARCHIVED_AT;
 use super::{AgentLoop, DEFAULT_HISTORY_SIZE, RECOVERY_CONTEXT_MAX_CHARS};
 use crate::agent::compaction::strip_orphaned_tool_messages;
 use anyhow::Result;
@@ -12,12 +13,16 @@ impl AgentLoop {
         session: &crate::session::Session,
     ) -> Result<Vec<HashMap<String, Value>>> {
         if self.compactor.is_none() || !self.compaction_config.enabled {
-            return Ok(session.get_history(DEFAULT_HISTORY_SIZE));
+            let mut history = session.get_history(DEFAULT_HISTORY_SIZE);
+            if let Some(max_tokens) = self.compaction_config.max_history_tokens {
+                history = crate::agent::compaction::trim_to_token_budget(history, max_tokens);
+            }
+            return Ok(with_archived_summary(session, history));
         }
 
         let full_history = session.get_full_history();
         if full_history.is_empty() {
-            return Ok(vec![]);
+            return Ok(with_archived_summary(session, vec![]));
         }
 
         let keep_recent = self.compaction_config.keep_recent;
@@ -32,8 +37,18 @@ impl AgentLoop {
                 crate::agent::compaction::estimate_messages_tokens(&full_history) as u64
             });
 
-        if token_est < threshold {
-            return Ok(session.get_history(DEFAULT_HISTORY_SIZE));
+        // Message count is an OR trigger for when token counts are unreliable.
+        // Only messages the last compaction kept or that came after it count:
+        // the stored history is capped, so its length never drops.
+        let over_message_cap = self
+            .compaction_config
+            .max_messages
+            .is_some_and(|max| messages_since_compaction(session) >= max);
+        if token_est < threshold && !over_message_cap {
+            return Ok(with_archived_summary(
+                session,
+                session.get_history(DEFAULT_HISTORY_SIZE),
+            ));
         }
 
         let split_idx = if let Some(keep_turns) = self.compaction_config.keep_recent_turns {
@@ -101,6 +116,7 @@ impl AgentLoop {
                         if filtered.trim().is_empty() {
                             debug!("pre-compaction flush: all facts filtered by quality gates");
                         } else if let Err(e) = self
+                            .profile(&session.key)
                             .memory
                             .append_to_section("Pre-compaction context", &filtered)
                         {
@@ -122,13 +138,19 @@ impl AgentLoop {
                 // Only mark flushed when content was actually persisted, so a
                 // retry can attempt extraction again if nothing was saved.
                 if flushed_content {
-                    match self.sessions.get_or_create(&session.key).await {
+                    match self
+                        .profile(&session.key)
+                        .sessions
+                        .get_or_create(&session.key)
+                        .await
+                    {
                         Ok(mut latest) => {
                             latest.metadata.insert(
                                 "pre_flush_msg_count".to_string(),
                                 Value::Number(serde_json::Number::from(old_msg_count as u64)),
                             );
-                            if let Err(e) = self.sessions.save(&latest).await {
+                            if let Err(e) = self.profile(&session.key).sessions.save(&latest).await
+                            {
                                 warn!("failed to save pre-flush marker: {}", e);
                             }
                         }
@@ -186,13 +208,26 @@ impl AgentLoop {
                     // Persist the enriched summary so the next compaction cycle
                     // builds incrementally on the same context the LLM actually saw
                     // (including checkpoint/recovery annotations).
-                    match self.sessions.get_or_create(&session.key).await {
+                    match self
+                        .profile(&session.key)
+                        .sessions
+                        .get_or_create(&session.key)
+                        .await
+                    {
                         Ok(mut latest) => {
                             latest.metadata.insert(
                                 "compaction_summary".to_string(),
                                 Value::String(recovery_summary.clone()),
                             );
-                            if let Err(e) = self.sessions.save(&latest).await {
+                            if let Some(ts) = old_messages
+                                .last()
+                                .and_then(|m| m.get("timestamp"))
+                                .cloned()
+                            {
+                                latest.metadata.insert(COMPACTED_THROUGH.to_string(), ts);
+                            }
+                            if let Err(e) = self.profile(&session.key).sessions.save(&latest).await
+                            {
                                 warn!(
                                     "failed to persist compaction summary: {} — next compaction \
                                      may re-summarize the same messages",
@@ -271,12 +306,58 @@ impl AgentLoop {
     }
 }
 
+/// Session metadata key: timestamp of the last message folded into the
+/// compaction summary.
+const COMPACTED_THROUGH: &str = "compacted_through";
+
+/// Messages after the last one summarized by a compaction. All of them when
+/// the session was never compacted or that message has since been pruned.
+fn messages_since_compaction(session: &crate::session::Session) -> usize {
+    let marker = session
+        .metadata
+        .get(COMPACTED_THROUGH)
+        .and_then(Value::as_str);
+    marker
+        .and_then(|ts| session.messages.iter().rposition(|m| m.timestamp == ts))
+        .map_or(session.messages.len(), |idx| {
+            session.messages.len() - idx - 1
+        })
+}
+
+/// Rehydrate an archived session: put its archive summary ahead of whatever
+/// was said since. Sessions that were never archived pass through unchanged.
+fn with_archived_summary(
+    session: &crate::session::Session,
+    history: Vec<HashMap<String, Value>>,
+) -> Vec<HashMap<String, Value>> {
+    if !session.metadata.contains_key(ARCHIVED_AT) {
+        return history;
+    }
+    let Some(summary) = session
+        .metadata
+        .get("compaction_summary")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+    else {
+        return history;
+    };
+    let mut result = vec![HashMap::from([
+        ("role".to_string(), Value::String("user".to_string())),
+        (
+            "content".to_string(),
+            Value::String(format!("[Previous conversation summary: {summary}]")),
+        ),
+    ])];
+    result.extend(history);
+    result
+}
+
 /// Strip checkpoint/cognitive/recovery annotations from a compaction summary.
 ///
 /// Current annotations use a SOH (`\x01`) sentinel prefix. For backward
 /// compatibility with summaries written before the sentinel was added, we
 /// also strip the legacy bare markers.
-fn strip_annotations(summary: &str) -> &str {
+pub(super) fn strip_annotations(summary: &str) -> &str {
     // Sentinel-prefixed markers (current format)
     let s = summary
         .split("\n\n\x01[Checkpoint]")
@@ -18,6 +18,9 @@ pub struct AgentRunOverrides {
     pub provider: Option<Arc<dyn LLMProvider>>,
     /// Request structured output format from the LLM (JSON mode or JSON schema).
     pub response_format: Option<crate::providers::base::ResponseFormat>,
+    /// Reasoning budget for every LLM call of this turn, replacing the
+    /// configured per-phase efforts.
+    pub reasoning_effort: Option<crate::providers::base::ReasoningEffort>,
     /// Correlation ID for tracing a single request across cost, intent, and
     /// complexity records.
     pub request_id: Option<String>,
@@ -27,6 +30,19 @@ pub struct AgentRunOverrides {
     pub action: Option<crate::dispatch::ActionDispatch>,
     /// Strict route policy for constrained turns.
     pub routing_policy: Option<crate::router::RoutingPolicy>,
+    /// Consecutive clarification-only replies earlier in this session, checked
+    /// against the clarification budget.
+    pub prior_clarifications: u32,
+    /// Publish the reply text while it is generated (`agents.defaults.streaming`).
+    pub stream: Option<Arc<super::streaming::StreamTarget>>,
+    /// Log tool calls and answer them with a synthetic success result instead
+    /// of executing them (`oxicrab agent --dry-run`).
+    pub dry_run: bool,
+    /// Send no typing indicator for this turn, even when the session maps to
+    /// a user chat. Set for background work such as cron agent turns.
+    pub suppress_typing: bool,
+    /// Set by `/cancel` to stop the turn at the next iteration.
+    pub cancel: Option<Arc<std::sync::atomic::AtomicBool>>,
 }
 
 /// Tool-specific configurations bundled together. These fields are only used
@@ -37,6 +53,9 @@ pub struct ToolConfigs {
     pub web_search_config: Option<crate::config::WebSearchConfig>,
     pub exec_timeout: u64,
     pub restrict_to_workspace: bool,
+    pub workspace_only: bool,
+    /// Gateway tenant workspaces, whose memory DBs the `sqlite` tool refuses.
+    pub tenant_workspaces: Vec<PathBuf>,
     pub allowed_commands: crate::config::AllowedCommands,
     pub sandbox_config: crate::config::SandboxConfig,
     pub channels_config: Option<crate::config::ChannelsConfig>,
@@ -51,6 +70,11 @@ pub struct ToolConfigs {
     pub mcp_config: Option<crate::config::McpConfig>,
     pub workspace_ttl: crate::config::WorkspaceTtlConfig,
     pub rss_config: Option<crate::config::RssConfig>,
+    pub rate_limits: std::collections::HashMap<String, crate::config::ToolRateLimitConfig>,
+    pub tool_timeouts: std::collections::HashMap<String, u64>,
+    pub default_tool_timeout_secs: Option<u64>,
+    pub scratchpad_config: crate::config::ScratchpadConfig,
+    pub tmux_config: Option<crate::config::TmuxConfig>,
 }
 
 /// Result of a single agent loop run.
@@ -71,6 +95,9 @@ pub struct AgentLoopResult {
     pub response_metadata: std::collections::HashMap<String, serde_json::Value>,
     /// Metadata from tool results (for directive extraction by caller).
     pub tool_metadata: Vec<(String, std::collections::HashMap<String, serde_json::Value>)>,
+    /// Resumable context when the run hit the iteration cap with tool calls
+    /// still pending (see `continuation::build_context`).
+    pub continuation: Option<String>,
 }
 
 impl AgentLoopResult {
@@ -122,6 +149,8 @@ pub struct DirectResult {
 pub struct LifecycleConfig {
     /// Session TTL in days for cleanup (default 30)
     pub session_ttl_days: u32,
+    /// Idle days before a session is archived to a summary (0 = off)
+    pub session_archive_days: u32,
     /// Media file TTL in days for cleanup (default 7)
     pub media_ttl_days: u32,
 }
@@ -132,6 +161,8 @@ pub struct SafetyConfig {
     pub exfiltration_guard: crate::config::ExfiltrationGuardConfig,
     /// Prompt injection detection configuration
     pub prompt_guard: crate::config::PromptGuardConfig,
+    /// Inbound content policy (drop/notice/flag by pattern or length)
+    pub content_filter: crate::config::ContentFilterConfig,
 }
 
 /// Configuration for creating an [`AgentLoop`] instance.
@@ -151,14 +182,46 @@ pub struct AgentLoopConfig {
     /// Per-provider temperature override (e.g. Moonshot requires temperature=1).
     /// When set, passed to the compactor to override its hardcoded internal temps.
     pub per_provider_temperature: Option<f32>,
+    /// Reasoning budgets per call kind (default: provider default everywhere)
+    pub reasoning: crate::config::ReasoningConfig,
+    /// What to do once empty-response retries are exhausted (default: generic reply)
+    pub empty_response: crate::config::EmptyResponseAction,
+    /// Detection of repeated identical tool calls
+    pub tool_loop: crate::config::ToolLoopConfig,
+    /// Summarizing large tool results before they enter the conversation
+    pub tool_result_summary: crate::config::ToolResultSummaryConfig,
+    /// Coalescing of quick successive messages from one session
+    pub message_batching: crate::config::MessageBatchingConfig,
+    /// Named tool sets selectable per session with `/focus`
+    pub tool_presets: std::collections::HashMap<String, Vec<String>>,
     /// Max tokens for LLM responses (default 8192)
     pub max_tokens: u32,
     /// Sender for typing indicator events (channel, `chat_id`)
     pub typing_tx: Option<Arc<tokio::sync::mpsc::Sender<(String, String)>>>,
     /// Maximum concurrent subagents (default 5)
     pub max_concurrent_subagents: usize,
+    /// Per-turn subagent budget, delegation hint and status lines
+    pub delegation: crate::config::DelegationConfig,
+    /// Queue messages while the provider circuit breaker is open
+    pub offline_mode: crate::config::OfflineModeConfig,
+    /// Stream reply text to channels that can edit a sent message
+    pub streaming: crate::config::StreamingConfig,
+    /// Retry policy for LLM calls (`providers.retry`)
+    pub provider_retry: crate::config::ProviderRetryConfig,
+    /// Inbound messages processed at the same time (default 4)
+    pub max_concurrent_sessions: usize,
     /// Voice transcription configuration
     pub voice_config: Option<crate::config::VoiceConfig>,
+    /// Per-message caps on inbound images and documents
+    pub inbound_media: crate::config::InboundMediaConfig,
+    /// Clarifying questions allowed in a row on action requests (0 = unlimited)
+    pub clarification_budget: u32,
+    /// Automatic continuations of a turn capped with work pending (0 = manual only)
+    pub auto_continue: u32,
+    /// Inject per-sender preferences and register `set_preference`
+    pub sender_preferences: bool,
+    /// Record per-turn token breakdowns and register `token_breakdown`
+    pub token_breakdown: bool,
     /// Memory configuration (archive/purge days)
     pub memory_config: Option<crate::config::MemoryConfig>,
     /// Cognitive routines configuration for checkpoint pressure signals
@@ -183,6 +246,8 @@ pub struct AgentLoopConfig {
     pub router_config: crate::config::RouterConfig,
     /// Operator approval workflow configuration.
     pub approval_config: crate::config::ApprovalConfig,
+    /// Workspaces selected by channel and chat (`agents.profiles`)
+    pub profiles: Vec<crate::config::ProfileConfig>,
 }
 
 /// Temperature used for tool-calling iterations (low for determinism)
@@ -205,6 +270,24 @@ pub struct AgentLoopRuntimeParams {
 }
 
 impl AgentLoopConfig {
+    /// The memory config with the `OpenAI` credentials injected when the
+    /// `openai` embeddings backend is selected.
+    pub fn memory_config(config: &crate::config::Config) -> crate::config::MemoryConfig {
+        let mut memory = config.agents.defaults.memory.clone();
+        if memory.embeddings_backend == crate::config::EmbeddingsBackend::OpenAi {
+            if !config.providers.openai.api_key.is_empty() {
+                memory.openai_api_key = Some(config.providers.openai.api_key.clone());
+            }
+            memory.openai_api_base = config
+                .providers
+                .openai
+                .api_base
+                .as_ref()
+                .map(|base| base.as_str().to_string());
+        }
+        memory
+    }
+
     /// Build an `AgentLoopConfig` from the application [`Config`](crate::config::Config)
     /// and runtime parameters that vary per invocation.
     pub fn from_config(
@@ -222,16 +305,31 @@ impl AgentLoopConfig {
             }
         }
 
-        // Resolve per-provider temperature before consuming params.model.
+        let memory = Self::memory_config(config);
+
+        // Model aliases (`models.aliases`) are resolved here for the model
+        // strings the loop sends as-is; routed models resolve in the provider
+        // factory.
+        let resolve =
+            |m: &Option<String>| m.as_deref().map(|m| config.models.resolve(m).to_string());
+        let model = resolve(&params.model);
+        let mut compaction_config = config.agents.defaults.compaction.clone();
+        compaction_config.model = resolve(&compaction_config.model);
+        compaction_config.checkpoint.model = resolve(&compaction_config.checkpoint.model);
+        let mut inbound_media = config.agents.defaults.inbound_media.clone();
+        inbound_media.description_model = resolve(&inbound_media.description_model);
+
         // When a provider sets a per-provider temperature, use it for both
         // normal and tool iterations — some models (e.g. kimi-k2.5) reject
         // any temperature other than their configured value.
-        let per_provider_temp = config.providers.get_temperature_for_model(
-            params
-                .model
-                .as_deref()
-                .unwrap_or(&config.agents.defaults.model_routing.default),
-        );
+        let per_provider_temp =
+            config
+                .providers
+                .get_temperature_for_model(model.as_deref().unwrap_or_else(|| {
+                    config
+                        .models
+                        .resolve(&config.agents.defaults.model_routing.default)
+                }));
         let resolved_temperature =
             per_provider_temp.map_or(config.agents.defaults.temperature, Some);
         let resolved_tool_temperature = if per_provider_temp.is_some() {
@@ -245,25 +343,48 @@ impl AgentLoopConfig {
             bus: params.bus,
             provider: params.provider,
             workspace: config.workspace_path(),
-            model: params.model,
+            model,
             max_iterations: config.agents.defaults.max_tool_iterations,
-            compaction_config: config.agents.defaults.compaction.clone(),
+            compaction_config,
             outbound_tx: params.outbound_tx,
             cron_service: params.cron_service,
             temperature: resolved_temperature,
             tool_temperature: resolved_tool_temperature,
             per_provider_temperature: per_provider_temp,
+            reasoning: config.agents.defaults.reasoning.clone(),
+            empty_response: config.agents.defaults.empty_response.action,
+            tool_loop: config.agents.defaults.tool_loop.clone(),
+            tool_result_summary: config.agents.defaults.tool_result_summary.clone(),
+            message_batching: config.agents.defaults.message_batching.clone(),
+            tool_presets: config.agents.defaults.tool_presets.clone(),
             max_tokens: config.agents.defaults.max_tokens,
             typing_tx: params.typing_tx,
             max_concurrent_subagents: config.agents.defaults.max_concurrent_subagents,
+            delegation: config.agents.defaults.delegation.clone(),
+            offline_mode: config.agents.defaults.offline_mode.clone(),
+            streaming: config.agents.defaults.streaming.clone(),
+            provider_retry: config.providers.retry.clone(),
+            max_concurrent_sessions: config.agents.defaults.max_concurrent_sessions,
             voice_config: Some(config.voice.clone()),
-            memory_config: Some(config.agents.defaults.memory.clone()),
+            inbound_media,
+            clarification_budget: config.agents.defaults.clarification_budget,
+            auto_continue: config.agents.defaults.auto_continue,
+            sender_preferences: config.agents.defaults.sender_preferences,
+            token_breakdown: config.agents.defaults.token_breakdown,
+            memory_config: Some(memory),
             cognitive_config: config.agents.defaults.cognitive.clone(),
             context_providers: config.agents.defaults.context_providers.clone(),
             tool_configs: ToolConfigs {
                 web_search_config: Some(config.tools.web_search.clone()),
                 exec_timeout: config.tools.exec.timeout,
                 restrict_to_workspace: config.tools.restrict_to_workspace,
+                workspace_only: config.tools.workspace_only,
+                tenant_workspaces: config
+                    .gateway
+                    .tenants
+                    .iter()
+                    .map(|(id, tenant)| config.tenant_workspace_path(id, tenant))
+                    .collect(),
                 allowed_commands: config.tools.exec.effective_allowed_commands(),
                 sandbox_config: config.tools.exec.sandbox.clone(),
                 channels_config: params.channels_config,
@@ -278,20 +399,28 @@ impl AgentLoopConfig {
                 mcp_config: Some(config.tools.mcp.clone()),
                 workspace_ttl: config.agents.defaults.workspace_ttl.clone(),
                 rss_config: Some(config.tools.rss.clone()),
+                rate_limits: config.tools.rate_limits.clone(),
+                tool_timeouts: config.tools.timeouts.clone(),
+                default_tool_timeout_secs: config.tools.default_timeout_secs,
+                scratchpad_config: config.tools.scratchpad.clone(),
+                tmux_config: Some(config.tools.tmux.clone()),
             },
             routing,
             lifecycle: LifecycleConfig {
                 session_ttl_days: config.agents.defaults.session_ttl_days,
+                session_archive_days: config.agents.defaults.session_archive_days,
                 media_ttl_days: config.agents.defaults.media_ttl_days,
             },
             safety: SafetyConfig {
                 exfiltration_guard: config.tools.exfiltration_guard.clone(),
                 prompt_guard: config.agents.defaults.prompt_guard.clone(),
+                content_filter: config.agents.defaults.content_filter.clone(),
             },
             memory_db: params.memory_db,
             leak_detector: params.leak_detector,
             router_config: config.router.clone(),
             approval_config: config.agents.defaults.approval.clone(),
+            profiles: config.agents.profiles.clone(),
         }
     }
 
@@ -314,21 +443,42 @@ impl AgentLoopConfig {
             compaction_config: crate::config::CompactionConfig {
                 enabled: false,
                 threshold_tokens: 40000,
+                max_messages: None,
                 keep_recent: 10,
                 keep_recent_turns: None,
                 extraction_enabled: false,
                 model: None,
+                extraction_model: None,
+                checkpoint: crate::config::CompactionCheckpointConfig::default(),
+                extraction_interval: crate::config::ExtractionIntervalConfig::default(),
                 pre_flush_enabled: false,
+                max_history_tokens: None,
             },
             outbound_tx,
             cron_service: None,
             temperature: Some(0.7),
             tool_temperature: Some(0.0),
             per_provider_temperature: None,
+            reasoning: crate::config::ReasoningConfig::default(),
+            empty_response: crate::config::EmptyResponseAction::default(),
+            tool_loop: crate::config::ToolLoopConfig::default(),
+            tool_result_summary: crate::config::ToolResultSummaryConfig::default(),
+            message_batching: crate::config::MessageBatchingConfig::default(),
+            tool_presets: std::collections::HashMap::new(),
             max_tokens: 8192,
             typing_tx: None,
             max_concurrent_subagents: 5,
+            delegation: crate::config::DelegationConfig::default(),
+            offline_mode: crate::config::OfflineModeConfig::default(),
+            streaming: crate::config::StreamingConfig::default(),
+            provider_retry: crate::config::ProviderRetryConfig::default(),
+            max_concurrent_sessions: 1,
             voice_config: None,
+            inbound_media: crate::config::InboundMediaConfig::default(),
+            clarification_budget: 0,
+            auto_continue: 0,
+            sender_preferences: false,
+            token_breakdown: false,
             memory_config: None,
             cognitive_config: crate::config::CognitiveConfig::default(),
             context_providers: vec![],
@@ -336,6 +486,8 @@ impl AgentLoopConfig {
                 web_search_config: None,
                 exec_timeout: 30,
                 restrict_to_workspace: true,
+                workspace_only: false,
+                tenant_workspaces: vec![],
                 allowed_commands: crate::config::AllowedCommands::new(vec![]),
                 sandbox_config: crate::config::SandboxConfig {
                     enabled: false,
@@ -353,20 +505,28 @@ impl AgentLoopConfig {
                 mcp_config: None,
                 workspace_ttl: crate::config::WorkspaceTtlConfig::default(),
                 rss_config: None,
+                rate_limits: std::collections::HashMap::new(),
+                tool_timeouts: std::collections::HashMap::new(),
+                default_tool_timeout_secs: None,
+                scratchpad_config: crate::config::ScratchpadConfig::default(),
+                tmux_config: None,
             },
             routing: None,
             lifecycle: LifecycleConfig {
                 session_ttl_days: 0,
+                session_archive_days: 0,
                 media_ttl_days: 0,
             },
             safety: SafetyConfig {
                 exfiltration_guard: crate::config::ExfiltrationGuardConfig::default(),
                 prompt_guard: crate::config::PromptGuardConfig::default(),
+                content_filter: crate::config::ContentFilterConfig::default(),
             },
             memory_db: None,
             leak_detector: None,
             router_config: crate::config::RouterConfig::default(),
             approval_config: crate::config::ApprovalConfig::default(),
+            profiles: vec![],
         }
     }
 }
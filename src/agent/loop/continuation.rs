@@ -0,0 +1,172 @@
+use crate::providers::base::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use tracing::warn;
+
+/// Session metadata key holding the [`ContinuationMarker`] of a capped turn.
+pub(super) const CONTINUATION_META_KEY: &str = "continuation";
+
+/// Tool steps kept in the continuation context (most recent last).
+const MAX_CONTEXT_STEPS: usize = 15;
+const MAX_ARGS_CHARS: usize = 150;
+const MAX_RESULT_CHARS: usize = 300;
+const MAX_CONTEXT_CHARS: usize = 6000;
+
+/// Messages that resume a capped turn when a marker is pending.
+const CONTINUE_PHRASES: &[&str] = &[
+    "continue",
+    "please continue",
+    "continue please",
+    "go on",
+    "keep going",
+    "carry on",
+    "resume",
+    "proceed",
+];
+
+/// Resumable state of a turn that hit `maxToolIterations` with tool calls
+/// still pending, stored in session metadata until the next turn.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(super) struct ContinuationMarker {
+    /// The user request that started the task (carried across continuations).
+    pub request: String,
+    /// Cognitive breadcrumb plus a digest of the tool steps taken so far.
+    pub context: String,
+    /// Consecutive automatic continuations already spent on this task.
+    #[serde(default)]
+    pub auto_runs: u32,
+}
+
+impl ContinuationMarker {
+    /// System note injected into a resumed turn.
+    pub(super) fn resume_prompt(&self) -> String {
+        format!(
+            "[Resuming a task that stopped at the iteration limit]\n\
+             Original request: {}\n\n\
+             Work already done:\n{}\n\n\
+             Continue from where you left off. Do not redo steps that already \
+             succeeded unless you need their output again.",
+            self.request, self.context
+        )
+    }
+}
+
+/// Whether `content` asks to resume the previous task.
+pub(super) fn is_continue_request(content: &str) -> bool {
+    let normalized = content
+        .trim()
+        .trim_end_matches(['.', '!'])
+        .trim()
+        .to_lowercase();
+    CONTINUE_PHRASES.contains(&normalized.as_str())
+}
+
+/// Marker to resume from, if the session has one and this message resumes it.
+/// Automatic continuations always resume; user messages only when they are a
+/// "continue" request.
+pub(super) fn pending_marker(
+    metadata: &HashMap<String, Value>,
+    content: &str,
+    auto: bool,
+) -> Option<ContinuationMarker> {
+    if !auto && !is_continue_request(content) {
+        return None;
+    }
+    let value = metadata.get(CONTINUATION_META_KEY)?;
+    match serde_json::from_value(value.clone()) {
+        Ok(marker) => Some(marker),
+        Err(e) => {
+            warn!("ignoring malformed continuation marker: {}", e);
+            None
+        }
+    }
+}
+
+/// Record the outcome of a turn in session metadata: store a fresh marker when
+/// the turn was capped (`context` is `Some`), otherwise clear any old one.
+pub(super) fn update_marker(
+    metadata: &mut HashMap<String, Value>,
+    resumed: Option<&ContinuationMarker>,
+    request: &str,
+    context: Option<String>,
+    auto: bool,
+) -> Option<ContinuationMarker> {
+    let Some(context) = context else {
+        metadata.remove(CONTINUATION_META_KEY);
+        return None;
+    };
+    let marker = ContinuationMarker {
+        request: resumed.map_or_else(|| request.to_string(), |m| m.request.clone()),
+        context,
+        auto_runs: if auto {
+            resumed.map_or(0, |m| m.auto_runs) + 1
+        } else {
+            0
+        },
+    };
+    match serde_json::to_value(&marker) {
+        Ok(value) => {
+            metadata.insert(CONTINUATION_META_KEY.to_string(), value);
+        }
+        Err(e) => warn!("failed to serialize continuation marker: {}", e),
+    }
+    Some(marker)
+}
+
+/// Summarize the tool steps in `messages` (the messages appended by one agent
+/// loop run) together with the cognitive breadcrumb.
+pub(super) fn build_context(messages: &[Message], breadcrumb: &str) -> String {
+    let results: HashMap<&str, &Message> = messages
+        .iter()
+        .filter(|m| m.role == "tool")
+        .filter_map(|m| Some((m.tool_call_id.as_deref()?, m)))
+        .collect();
+
+    let steps: Vec<String> = messages
+        .iter()
+        .filter_map(|m| m.tool_calls.as_ref())
+        .flatten()
+        .map(|tc| {
+            let args =
+                crate::utils::truncate_chars(&tc.arguments.to_string(), MAX_ARGS_CHARS, "...");
+            let outcome = match results.get(tc.id.as_str()) {
+                Some(r) => {
+                    let status = if r.is_error { "error" } else { "ok" };
+                    let snippet = crate::utils::truncate_chars(
+                        &r.content.replace('\n', " "),
+                        MAX_RESULT_CHARS,
+                        "...",
+                    );
+                    format!("{status}: {snippet}")
+                }
+                None => "no result".to_string(),
+            };
+            format!("- {}({}) -> {}", tc.name, args, outcome)
+        })
+        .collect();
+
+    // Keep the most recent steps that fit the budget
+    let mut kept: Vec<&str> = Vec::new();
+    let mut used = breadcrumb.len();
+    for step in steps.iter().rev().take(MAX_CONTEXT_STEPS) {
+        if used + step.len() > MAX_CONTEXT_CHARS {
+            break;
+        }
+        used += step.len() + 1;
+        kept.push(step);
+    }
+    kept.reverse();
+
+    let mut context = format!("{breadcrumb}\n");
+    let omitted = steps.len() - kept.len();
+    if omitted > 0 {
+        let _ = writeln!(context, "({omitted} earlier tool calls omitted)");
+    }
+    for step in kept {
+        context.push_str(step);
+        context.push('\n');
+    }
+    context.trim_end().to_string()
+}
@@ -0,0 +1,80 @@
+use crate::config::ExtractionIntervalConfig;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Sessions whose pending exchanges are tracked at once (least recently
+/// active sessions are dropped, along with their unextracted exchanges).
+const MAX_TRACKED_SESSIONS: usize = 1024;
+/// Upper bound on exchanges batched into one extraction call; the oldest are
+/// dropped beyond this.
+const MAX_BATCH_EXCHANGES: usize = 20;
+
+struct PendingExtraction {
+    exchanges: Vec<(String, String)>,
+    /// Start of the current time window: the last extraction, or when the
+    /// session was first seen.
+    window_start: Instant,
+}
+
+/// Per-session throttle for background fact extraction. Exchanges are
+/// buffered until the configured interval is reached, then handed out as one
+/// batch.
+pub(super) struct ExtractionThrottle {
+    interval: ExtractionIntervalConfig,
+    sessions: Mutex<LruCache<String, PendingExtraction>>,
+}
+
+impl ExtractionThrottle {
+    pub(super) fn new(interval: ExtractionIntervalConfig) -> Self {
+        Self {
+            interval,
+            sessions: Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_TRACKED_SESSIONS).expect("MAX_TRACKED_SESSIONS must be > 0"),
+            )),
+        }
+    }
+
+    /// Queue an exchange for `session_key`. Returns the batch to extract now
+    /// when the interval is reached, otherwise `None`.
+    pub(super) fn record(
+        &self,
+        session_key: &str,
+        user: String,
+        assistant: String,
+    ) -> Option<Vec<(String, String)>> {
+        self.record_at(session_key, user, assistant, Instant::now())
+    }
+
+    pub(super) fn record_at(
+        &self,
+        session_key: &str,
+        user: String,
+        assistant: String,
+        now: Instant,
+    ) -> Option<Vec<(String, String)>> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let pending = sessions.get_or_insert_mut(session_key.to_string(), || PendingExtraction {
+            exchanges: Vec::new(),
+            window_start: now,
+        });
+        pending.exchanges.push((user, assistant));
+        if pending.exchanges.len() > MAX_BATCH_EXCHANGES {
+            pending.exchanges.remove(0);
+        }
+
+        let turns_due = self.interval.turns > 0 && pending.exchanges.len() >= self.interval.turns;
+        let time_due = self.interval.minutes > 0
+            && now.duration_since(pending.window_start)
+                >= Duration::from_secs(self.interval.minutes * 60);
+        if !turns_due && !time_due {
+            return None;
+        }
+        pending.window_start = now;
+        Some(std::mem::take(&mut pending.exchanges))
+    }
+}
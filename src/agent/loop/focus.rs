@@ -0,0 +1,162 @@
+//! Focus mode (`agents.defaults.toolPresets`): `/focus <preset>` limits a
+//! session to a named tool set until `/focus off`. The choice lives in
+//! session metadata, so it survives restarts.
+
+use super::AgentLoop;
+use crate::router::RoutingPolicy;
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Session metadata key holding the active preset name.
+pub(super) const FOCUS_META_KEY: &str = "focus_preset";
+const COMMAND: &str = "/focus";
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum FocusCommand<'a> {
+    /// Bare `/focus`: report the current preset and the available ones.
+    Show,
+    Off,
+    Set(&'a str),
+}
+
+/// Parse a `/focus` message. Anything else (including `/focused`) is `None`.
+pub(super) fn parse_command(content: &str) -> Option<FocusCommand<'_>> {
+    let rest = content.trim().strip_prefix(COMMAND)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    match rest.trim() {
+        "" => Some(FocusCommand::Show),
+        "off" => Some(FocusCommand::Off),
+        name => Some(FocusCommand::Set(name)),
+    }
+}
+
+/// Narrow `current` (the router's policy for this turn, if any) to the
+/// preset. The preset wins when the two share no tools.
+pub(super) fn focused_allowlist(preset: &[String], current: Option<&RoutingPolicy>) -> Vec<String> {
+    let narrowed: Vec<String> = current
+        .map(|policy| {
+            preset
+                .iter()
+                .filter(|tool| policy.allowed_tools.contains(tool))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    if narrowed.is_empty() {
+        preset.to_vec()
+    } else {
+        narrowed
+    }
+}
+
+impl AgentLoop {
+    fn preset_names(&self) -> String {
+        let mut names: Vec<&str> = self.tool_presets.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names.join(", ")
+    }
+
+    /// Tools of the session's active preset, if any. A preset removed from
+    /// config since it was chosen is ignored.
+    pub(super) fn session_focus(&self, metadata: &HashMap<String, Value>) -> Option<&[String]> {
+        let name = metadata.get(FOCUS_META_KEY)?.as_str()?;
+        self.tool_presets.get(name).map(Vec::as_slice)
+    }
+
+    /// Routing policy for a focused turn, narrowing the router's own policy.
+    pub(super) fn focus_policy(
+        &self,
+        preset: &[String],
+        current: Option<RoutingPolicy>,
+    ) -> RoutingPolicy {
+        let allowed = focused_allowlist(preset, current.as_ref());
+        self.policy_from_allowlist(allowed, current.and_then(|p| p.context_hint), "focus")
+    }
+
+    /// Set (`Some`) or clear (`None`) the focus preset of a session.
+    pub async fn set_session_focus(&self, session_key: &str, preset: Option<&str>) -> Result<()> {
+        if let Some(name) = preset
+            && !self.tool_presets.contains_key(name)
+        {
+            anyhow::bail!(
+                "unknown focus preset '{name}'. Available: {}",
+                self.preset_names()
+            );
+        }
+        let sessions = &self.profile(session_key).sessions;
+        let mut session = sessions.get_or_create(session_key).await?;
+        match preset {
+            Some(name) => {
+                session
+                    .metadata
+                    .insert(FOCUS_META_KEY.to_string(), Value::String(name.to_string()));
+            }
+            None => {
+                session.metadata.remove(FOCUS_META_KEY);
+            }
+        }
+        sessions.save(&session).await?;
+        info!("focus preset for {session_key}: {preset:?}");
+        Ok(())
+    }
+
+    /// Answer a `/focus` command. `None` when the message is not one, or when
+    /// no presets are configured (the message then goes to the LLM as usual).
+    pub(super) async fn handle_focus_command(
+        &self,
+        session_key: &str,
+        content: &str,
+    ) -> Result<Option<String>> {
+        if self.tool_presets.is_empty() {
+            return Ok(None);
+        }
+        let Some(command) = parse_command(content) else {
+            return Ok(None);
+        };
+        let reply = match command {
+            FocusCommand::Show => {
+                let session = self
+                    .profile(session_key)
+                    .sessions
+                    .get_or_create(session_key)
+                    .await?;
+                let current = session
+                    .metadata
+                    .get(FOCUS_META_KEY)
+                    .and_then(Value::as_str)
+                    .filter(|name| self.tool_presets.contains_key(*name));
+                match current {
+                    Some(name) => format!(
+                        "Focus mode: {name} ({}). Presets: {}. Send /focus off to leave it.",
+                        self.tool_presets[name].join(", "),
+                        self.preset_names()
+                    ),
+                    None => format!(
+                        "Focus mode is off. Presets: {}. Send /focus <preset> to start one.",
+                        self.preset_names()
+                    ),
+                }
+            }
+            FocusCommand::Off => {
+                self.set_session_focus(session_key, None).await?;
+                "Focus mode off. All tools are available again.".to_string()
+            }
+            FocusCommand::Set(name) if !self.tool_presets.contains_key(name) => format!(
+                "Unknown focus preset '{name}'. Presets: {}.",
+                self.preset_names()
+            ),
+            FocusCommand::Set(name) => {
+                self.set_session_focus(session_key, Some(name)).await?;
+                format!(
+                    "Focus mode: {name}. Tools limited to {}. Send /focus off to leave it.",
+                    self.tool_presets[name].join(", ")
+                )
+            }
+        };
+        Ok(Some(reply))
+    }
+}
@@ -23,6 +23,10 @@ pub(super) fn record_retry_success() {
     metrics::counter!("oxicrab_agent_hallucination_retry_total", "layer" => "regex_l1", "outcome" => "succeeded").increment(1);
 }
 
+pub(super) fn record_clarification_budget_exceeded() {
+    metrics::counter!("oxicrab_agent_clarification_budget_exceeded_total").increment(1);
+}
+
 pub(super) fn record_retry_failure() {
     metrics::counter!("oxicrab_agent_hallucination_retry_total", "layer" => "regex_l1", "outcome" => "failed").increment(1);
 }
@@ -79,3 +83,61 @@ pub(super) fn handle_text_response(
 
     TextAction::Return
 }
+
+/// Whether a reply is a clarifying question rather than an answer or result:
+/// its final sentence ends with a question mark (ignoring trailing markdown).
+pub(super) fn is_clarification(content: &str) -> bool {
+    content
+        .trim_end_matches(|c: char| c.is_whitespace() || matches!(c, '*' | '_' | '`' | ')' | '"'))
+        .ends_with('?')
+}
+
+/// Whether a user message asks for something to be done rather than asking a
+/// question. Deliberately coarse: anything not phrased as a question counts.
+pub(super) fn is_action_request(user_message: &str) -> bool {
+    let trimmed = user_message.trim();
+    !trimmed.is_empty() && !trimmed.ends_with('?')
+}
+
+/// Clarification budget: once the agent has answered `budget` action requests
+/// in a row with only a clarifying question, the next clarification-only reply
+/// is rejected with a correction telling it to act or explain why it can't.
+/// `prior` is the streak recorded in session metadata before this turn. Fires
+/// at most once per turn.
+pub(super) fn enforce_clarification_budget(
+    content: &str,
+    messages: &mut Vec<Message>,
+    prior: u32,
+    budget: u32,
+    any_tools_called: bool,
+    budget_fired: &mut bool,
+    tool_names: &[String],
+) -> TextAction {
+    if budget == 0
+        || prior < budget
+        || *budget_fired
+        || any_tools_called
+        || tool_names.is_empty()
+        || !is_clarification(content)
+    {
+        return TextAction::Return;
+    }
+    let action_request = messages
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .is_some_and(|m| is_action_request(&m.content));
+    if !action_request {
+        return TextAction::Return;
+    }
+
+    warn!("clarification budget exhausted ({prior} in a row), forcing action");
+    record_clarification_budget_exceeded();
+    *budget_fired = true;
+    messages.push(Message::user(format!(
+        "You have already asked {prior} clarifying question(s) in a row without acting. \
+         Do not ask another question. Use the available tools to carry out the request \
+         with reasonable assumptions, or state explicitly why you cannot do it."
+    )));
+    TextAction::Continue
+}
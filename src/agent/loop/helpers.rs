@@ -8,7 +8,7 @@ use regex::Regex;
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 /// Context for the operator approval flow, passed into [`execute_tool_call`].
 /// When `None`, the approval gate is skipped (backward-compatible with tests).
@@ -22,11 +22,18 @@ pub(super) struct ApprovalContext<'a> {
     pub sender_id: &'a str,
 }
 
+/// Sink for the tool audit log, passed into [`execute_tool_call`].
+/// When `None`, the call is not recorded (tests, internal invocations).
+pub(super) struct ToolAuditContext<'a> {
+    pub db: &'a Arc<crate::agent::memory::MemoryDB>,
+    pub leak_detector: &'a crate::safety::LeakDetector,
+}
+
 const SAVED_TO_PREFIX: &str = "saved to: ";
+/// Maximum characters of tool output kept in the audit log.
+const AUDIT_RESULT_SUMMARY_CHARS: usize = 200;
 const AUDIO_TAG_PREFIX: &str = "[audio: ";
-const TYPING_INDICATOR_INTERVAL_SECS: u64 = 4;
 const MAX_IMAGE_SIZE: usize = 20 * 1024 * 1024; // 20MB (Anthropic limit)
-pub(super) const MAX_IMAGES: usize = 5;
 
 /// Extract media file paths from a tool result string.
 ///
@@ -130,6 +137,8 @@ pub(crate) fn validate_tool_params(
 /// validation) before delegating to the registry, which handles caching,
 /// timeout, panic isolation, truncation, and logging. Also handles the
 /// "tool not found" case and converts the result to `(String, bool)`.
+/// Every call, including blocked ones, is written to the tool audit log when
+/// `audit_ctx` is provided.
 #[allow(clippy::too_many_arguments)]
 pub(super) async fn execute_tool_call(
     registry: &ToolRegistry,
@@ -140,6 +149,95 @@ pub(super) async fn execute_tool_call(
     exfil_allow: Option<&crate::config::DenyByDefaultList>,
     workspace: Option<&std::path::Path>,
     approval_ctx: Option<ApprovalContext<'_>>,
+    audit_ctx: Option<ToolAuditContext<'_>>,
+) -> ToolResult {
+    let start = std::time::Instant::now();
+    let result = run_tool_call(
+        registry,
+        tc_name,
+        tc_args,
+        available_tools,
+        ctx,
+        exfil_allow,
+        workspace,
+        approval_ctx,
+    )
+    .await;
+    if let Some(audit) = audit_ctx {
+        record_tool_audit(&audit, tc_name, tc_args, ctx, &result, start.elapsed());
+    }
+    result
+}
+
+/// Save extracted facts to today's `Facts` section. With a digest target
+/// (`(channel, chat_id)`), each fact becomes its own entry and is queued for
+/// that chat's fact digest.
+pub(super) fn save_extracted_facts(
+    memory: &crate::agent::memory::MemoryStore,
+    facts: &str,
+    digest_target: Option<&(String, String)>,
+) -> Result<()> {
+    let Some((channel, chat_id)) = digest_target else {
+        return memory.append_to_section("Facts", facts);
+    };
+    for id in memory.append_lines_to_section("Facts", facts)? {
+        memory.db().queue_fact_digest(id, channel, chat_id)?;
+    }
+    Ok(())
+}
+
+/// Write one tool call to the audit log (fire-and-forget). Arguments and the
+/// result summary are passed through the leak detector so known secrets never
+/// reach the database.
+fn record_tool_audit(
+    audit: &ToolAuditContext<'_>,
+    tc_name: &str,
+    tc_args: &Value,
+    ctx: &ExecutionContext,
+    result: &ToolResult,
+    elapsed: Duration,
+) {
+    let meta_str = |key: &str| ctx.metadata.get(key).and_then(Value::as_str);
+    let session_key = meta_str("session_key").map_or_else(
+        || format!("{}:{}", ctx.channel, ctx.chat_id),
+        str::to_string,
+    );
+    let request_id = meta_str("request_id").map(str::to_string);
+    let arguments = audit.leak_detector.redact(&tc_args.to_string());
+    let summary = crate::utils::truncate_chars(
+        audit.leak_detector.redact(&result.content).trim(),
+        AUDIT_RESULT_SUMMARY_CHARS,
+        "...",
+    );
+    let db = audit.db.clone();
+    let tool_name = tc_name.to_string();
+    let success = !result.is_error;
+    let duration_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = db.record_tool_audit(
+            &session_key,
+            request_id.as_deref(),
+            &tool_name,
+            &arguments,
+            &summary,
+            success,
+            duration_ms,
+        ) {
+            warn!("failed to record tool audit entry: {}", e);
+        }
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_tool_call(
+    registry: &ToolRegistry,
+    tc_name: &str,
+    tc_args: &Value,
+    available_tools: &[String],
+    ctx: &ExecutionContext,
+    exfil_allow: Option<&crate::config::DenyByDefaultList>,
+    workspace: Option<&std::path::Path>,
+    approval_ctx: Option<ApprovalContext<'_>>,
 ) -> ToolResult {
     // Exfiltration guard: block network-outbound tools the LLM shouldn't call
     if let Some(allow_tools) = exfil_allow {
@@ -481,13 +579,76 @@ pub fn contains_action_claims(text: &str) -> bool {
     ACTION_CLAIM_RE.is_match(text)
 }
 
+/// Apply per-message attachment caps to inbound media paths.
+///
+/// PDFs count as documents; everything else counts as an image. Returns the
+/// paths to keep (in original order) plus a user-facing notice when any
+/// attachments were dropped.
+pub(super) fn cap_attachments(
+    media_paths: &[String],
+    max_images: usize,
+    max_documents: usize,
+) -> (Vec<String>, Option<String>) {
+    let mut kept = Vec::with_capacity(media_paths.len());
+    let (mut images, mut documents) = (0usize, 0usize);
+    for path in media_paths {
+        let is_document = std::path::Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("pdf"));
+        let (count, max) = if is_document {
+            (&mut documents, max_documents)
+        } else {
+            (&mut images, max_images)
+        };
+        *count += 1;
+        if *count <= max {
+            kept.push(path.clone());
+        }
+    }
+
+    let mut notices = Vec::new();
+    if images > max_images {
+        notices.push(attachment_cap_notice(
+            "images", "look at", images, max_images,
+        ));
+    }
+    if documents > max_documents {
+        notices.push(attachment_cap_notice(
+            "documents",
+            "read",
+            documents,
+            max_documents,
+        ));
+    }
+    if !notices.is_empty() {
+        warn!(
+            "inbound attachments over cap: {images} images (max {max_images}), {documents} documents (max {max_documents})"
+        );
+    }
+    let notice = (!notices.is_empty()).then(|| notices.join(" "));
+    (kept, notice)
+}
+
+fn attachment_cap_notice(kind: &str, verb: &str, received: usize, max: usize) -> String {
+    if max == 0 {
+        format!("I can't {verb} {kind} here; ignoring the {received} attached.")
+    } else {
+        format!("I can only {verb} {max} {kind} at once; using the first {max} of {received}.")
+    }
+}
+
 /// Load media files (images and documents) from disk and base64-encode them for LLM consumption.
-/// Skips files that are missing, too large, or have unsupported formats.
-pub(super) fn load_and_encode_images(media_paths: &[String]) -> Vec<ImageData> {
+/// Skips files that are missing, too large, or have unsupported formats. Images
+/// larger than `limits.max_image_dimension` are downscaled first.
+pub(super) fn load_and_encode_images(
+    media_paths: &[String],
+    limits: &crate::config::InboundMediaConfig,
+) -> Vec<ImageData> {
     use base64::Engine;
 
     let mut images = Vec::new();
-    for path in media_paths.iter().take(MAX_IMAGES) {
+    for path in media_paths {
         let file_path = std::path::Path::new(path);
         if !file_path.exists() {
             warn!("Media file not found: {}", path);
@@ -539,6 +700,13 @@ pub(super) fn load_and_encode_images(media_paths: &[String]) -> Vec<ImageData> {
                     );
                     continue;
                 }
+                let (data, media_type) = match ext {
+                    "png" | "jpg" | "jpeg" | "webp" => {
+                        downscale_image(&data, limits.max_image_dimension, limits.jpeg_quality)
+                            .map_or((data, media_type), |jpeg| (jpeg, "image/jpeg"))
+                    }
+                    _ => (data, media_type),
+                };
                 let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
                 info!(
                     "Encoded media for LLM: {} ({}, {} raw bytes, {} base64 chars)",
@@ -560,6 +728,52 @@ pub(super) fn load_and_encode_images(media_paths: &[String]) -> Vec<ImageData> {
     images
 }
 
+/// Shrink an image whose longer side exceeds `max_dimension` to fit it,
+/// keeping the aspect ratio, and re-encode it as JPEG. Returns `None` when
+/// the image already fits, downscaling is off (0) or the image can't be
+/// decoded, so the original is sent.
+fn downscale_image(data: &[u8], max_dimension: u32, jpeg_quality: u8) -> Option<Vec<u8>> {
+    if max_dimension == 0 {
+        return None;
+    }
+    let (width, height) = image::ImageReader::new(std::io::Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()?;
+    if width.max(height) <= max_dimension {
+        return None;
+    }
+    let decoded = match image::load_from_memory(data) {
+        Ok(img) => img,
+        Err(e) => {
+            warn!("failed to decode {width}x{height} image for downscaling: {e}");
+            return None;
+        }
+    };
+    let resized = decoded
+        .resize(
+            max_dimension,
+            max_dimension,
+            image::imageops::FilterType::Triangle,
+        )
+        .to_rgb8();
+    let mut out = Vec::new();
+    let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, jpeg_quality);
+    if let Err(e) = resized.write_with_encoder(encoder) {
+        warn!("failed to re-encode downscaled image: {e}");
+        return None;
+    }
+    debug!(
+        "downscaled image from {width}x{height} to {}x{} ({} -> {} bytes)",
+        resized.width(),
+        resized.height(),
+        data.len(),
+        out.len()
+    );
+    Some(out)
+}
+
 /// Replace `[prefix /path/to/file]` tags in content with an optional replacement string.
 /// If `replacement` is `None`, the tags are removed entirely.
 fn replace_bracketed_tags(content: &str, prefix: &str, replacement: Option<&str>) -> String {
@@ -705,17 +919,23 @@ impl Drop for TypingGuard {
     }
 }
 
-/// Periodic typing indicator: sends every 4s until the returned guard is dropped.
+/// Periodic typing indicator: sends every `intervalSecs` until the returned
+/// guard is dropped. Only the native style repeats; a status indicator is
+/// posted once when the message arrives.
 pub(super) fn start_typing(
     typing_tx: Option<&Arc<tokio::sync::mpsc::Sender<(String, String)>>>,
     ctx: Option<&(String, String)>,
+    indicator: &crate::config::TypingIndicatorConfig,
 ) -> Option<TypingGuard> {
+    if !indicator.enabled || indicator.style != crate::config::TypingIndicatorStyle::Native {
+        return None;
+    }
     if let (Some(tx), Some(ctx)) = (typing_tx, ctx) {
         let tx = tx.clone();
         let ctx = ctx.clone();
+        let period = Duration::from_secs(indicator.interval_secs.max(1));
         Some(TypingGuard(tokio::spawn(async move {
-            let mut interval =
-                tokio::time::interval(Duration::from_secs(TYPING_INDICATOR_INTERVAL_SECS));
+            let mut interval = tokio::time::interval(period);
             loop {
                 interval.tick().await;
                 if tx.send(ctx.clone()).await.is_err() {
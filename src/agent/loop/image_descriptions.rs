@@ -0,0 +1,93 @@
+use crate::agent::memory::memory_db::MemoryDB;
+use crate::providers::base::{ChatRequest, ImageData, LLMProvider, Message};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use tracing::warn;
+
+const DESCRIBE_PROMPT: &str = "Describe this image so that someone who cannot see it could \
+     answer questions about it later. Include any visible text verbatim, the people, objects, \
+     layout and notable details. Reply with the description only.";
+const DESCRIBE_MAX_TOKENS: u32 = 1024;
+
+/// Inbound images split by description-cache state.
+#[derive(Default)]
+pub(super) struct CacheLookup {
+    /// Attachments still sent to the model: new images and documents.
+    pub send: Vec<ImageData>,
+    /// Cached descriptions standing in for images that are not sent.
+    pub descriptions: Vec<String>,
+    /// Content hash and image of each image without a cached description.
+    pub uncached: Vec<(String, ImageData)>,
+}
+
+/// Content hash keying an image in the description cache.
+pub(super) fn image_hash(image: &ImageData) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(image.data.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Replace images that have a cached description with that description.
+/// Lookup errors fall back to sending the image.
+pub(super) fn lookup(db: &MemoryDB, attachments: Vec<ImageData>, ttl_days: u32) -> CacheLookup {
+    let mut result = CacheLookup::default();
+    for attachment in attachments {
+        if !attachment.media_type.starts_with("image/") {
+            result.send.push(attachment);
+            continue;
+        }
+        let hash = image_hash(&attachment);
+        match db.get_image_description(&hash, ttl_days) {
+            Ok(Some(description)) => result.descriptions.push(description),
+            Ok(None) => {
+                result.uncached.push((hash, attachment.clone()));
+                result.send.push(attachment);
+            }
+            Err(e) => {
+                warn!("image description cache lookup failed: {}", e);
+                result.send.push(attachment);
+            }
+        }
+    }
+    result
+}
+
+/// Text injected into the user message in place of cached images.
+pub(super) fn descriptions_note(descriptions: &[String]) -> String {
+    let mut note = String::new();
+    for (i, description) in descriptions.iter().enumerate() {
+        if !note.is_empty() {
+            note.push('\n');
+        }
+        let _ = write!(
+            note,
+            "[Image {} (seen before, cached description): {}]",
+            i + 1,
+            description.trim()
+        );
+    }
+    note
+}
+
+/// Ask the model for a description of `image` and cache it under `hash`.
+pub(super) async fn describe_and_cache(
+    provider: &dyn LLMProvider,
+    model: String,
+    db: &MemoryDB,
+    hash: &str,
+    image: ImageData,
+    ttl_days: u32,
+) -> anyhow::Result<()> {
+    let request = ChatRequest::builder(
+        vec![Message::user_with_images(DESCRIBE_PROMPT, vec![image])],
+        DESCRIBE_MAX_TOKENS,
+    )
+    .model(model)
+    .build();
+    let response = provider.chat(&request).await?;
+    let description = response.content.unwrap_or_default();
+    if description.trim().is_empty() {
+        anyhow::bail!("model returned an empty description");
+    }
+    db.put_image_description(hash, description.trim(), ttl_days)
+}
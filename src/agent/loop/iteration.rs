@@ -1,24 +1,65 @@
 use super::config::{AgentLoopResult, AgentRunOverrides};
 use super::hallucination::{self, TextAction};
+use super::token_breakdown;
+use super::tool_loop::{self, ToolLoopDetector};
 use super::{
     AgentLoop, EMPTY_RESPONSE_RETRIES, MAX_RETRY_DELAY_SECS, MIN_WRAPUP_ITERATION,
     RETRY_BACKOFF_BASE, WRAPUP_THRESHOLD_RATIO,
 };
 use crate::agent::cognitive::CheckpointTracker;
 use crate::agent::context::ContextBuilder;
-use crate::providers::base::{LLMProvider, Message, ToolCallRequest};
+use crate::agent::memory::memory_db::TokenBreakdown;
+use crate::config::{EmptyResponseAction, ToolLoopAction};
+use crate::providers::base::{LLMProvider, LLMResponse, Message, ToolCallRequest};
+use oxicrab_core::errors::ProviderError;
 
 use super::helpers::{
-    ApprovalContext, execute_tool_call, extract_media_paths, start_typing, strip_think_tags,
+    ApprovalContext, ToolAuditContext, execute_tool_call, extract_media_paths, start_typing,
+    strip_think_tags,
 };
 use super::metadata::{extract_display_text, merge_suggested_buttons, prepend_display_text};
 use crate::agent::tools::base::{ExecutionContext, ToolResult};
+use crate::agent::tools::scratchpad::strip_scratchpad_blocks;
 use anyhow::Result;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
 
-const SESSION_KEY_META_KEY: &str = "session_key";
+pub(super) const SESSION_KEY_META_KEY: &str = "session_key";
+
+/// Nudge for the `reprompt` recovery after repeated empty responses.
+const EMPTY_RESPONSE_REPROMPT: &str = "Your last replies were empty. Answer the user's most \
+     recent message now, briefly and in plain text.";
+
+/// Reminder once a turn reaches `delegation.suggestAfterToolCalls` tool calls.
+fn delegation_hint(tool_calls: usize) -> String {
+    format!(
+        "This task has taken {tool_calls} tool calls so far. If substantial independent work \
+         remains (research, bulk file or web operations), hand it to a subagent with `spawn` \
+         and tell the user it is running in the background."
+    )
+}
+
+/// `caller` column for a response's cost-log row: `failover:<provider>` when
+/// the failover provider served it, so failover frequency shows in the log.
+fn cost_caller(response: &LLMResponse) -> String {
+    response
+        .served_by
+        .as_deref()
+        .map_or_else(|| "main".to_string(), |p| format!("failover:{p}"))
+}
+
+/// Synthetic result for a tool call skipped by `AgentRunOverrides::dry_run`.
+fn dry_run_result(tc: &ToolCallRequest) -> ToolResult {
+    ToolResult::new(format!(
+        "[dry-run] would call {} with {}",
+        tc.name, tc.arguments
+    ))
+}
+
+/// Stands in for the content a provider blocked under its content policy.
+const CONTENT_POLICY_PLACEHOLDER: &str = "[Content withheld: the model provider's content policy \
+     blocked it. Tell the user you could not process it and why.]";
 
 impl AgentLoop {
     /// Core agent loop implementation with per-invocation overrides.
@@ -36,26 +77,47 @@ impl AgentLoop {
         exec_ctx: &ExecutionContext,
         overrides: &AgentRunOverrides,
     ) -> Result<AgentLoopResult> {
-        let effective_model = overrides.model.as_deref().unwrap_or(&self.model);
-        let effective_provider = overrides.provider.as_ref().unwrap_or(&self.provider);
+        let mut effective_model = overrides.model.as_deref().unwrap_or(&self.model);
+        let mut effective_provider = overrides.provider.as_ref().unwrap_or(&self.provider);
         let effective_max_iterations = overrides.max_iterations.unwrap_or(self.max_iterations);
         let activation_scope = overrides
             .request_id
             .clone()
             .unwrap_or_else(|| format!("run-{}", fastrand::u64(..)));
+        let typing_context = typing_context.filter(|_| !overrides.suppress_typing);
+        let typing_indicator = typing_context
+            .as_ref()
+            .map(|(channel, _)| self.typing_indicator(channel))
+            .unwrap_or_default();
         let mut empty_retries_left = EMPTY_RESPONSE_RETRIES;
+        // Set once the configured empty-response recovery has had its one attempt
+        let mut empty_recovery_used = false;
+        // Set when the loop gave up because the model kept answering with nothing
+        let mut empty_exhausted = false;
+        // Set once a content-policy block has been retried without the offending content
+        let mut policy_reprompted = false;
         let mut any_tools_called = false;
         let mut layer1_fired = false;
+        let mut clarification_budget_fired = false;
         let mut last_input_tokens: Option<u64> = None;
         let mut tools_used: Vec<String> = Vec::new();
         let mut collected_media: Vec<String> = Vec::new();
         let mut collected_tool_metadata: Vec<(String, HashMap<String, serde_json::Value>)> =
             Vec::new();
         let mut checkpoint_tracker = CheckpointTracker::new(self.cognitive_config.clone());
+        // Set when the last iteration still issued tool calls (work left unfinished)
+        let mut hit_iteration_cap = false;
+        let mut breakdown = TokenBreakdown::default();
+        let mut tool_loop = ToolLoopDetector::new(self.tool_loop.threshold);
+        // Set once the model has been told it is repeating itself
+        let mut tool_loop_nudged = false;
+        // Set once the model has been reminded that it can delegate
+        let mut delegation_hinted = false;
 
         // Clear request-scoped deferred tool activations from previous retries/reuse.
         self.tool_search_activated.clear(&activation_scope).await;
         self.pending_buttons.clear(&activation_scope);
+        self.scratchpad.clear(&activation_scope);
         let result = async {
             let mut activated_snapshot = std::collections::HashSet::new();
 
@@ -157,7 +219,19 @@ impl AgentLoop {
                 wrapup_threshold
             };
 
+            // Messages before this index predate the run (prompt, history, user turn)
+            let run_start = messages.len();
+
             for iteration in 1..=effective_max_iterations {
+            if super::cancel::is_cancelled(overrides.cancel.as_ref()) {
+                info!("turn cancelled before iteration {}", iteration);
+                return Ok(super::cancel::cancelled_result(
+                    last_input_tokens,
+                    tools_used,
+                    collected_media,
+                    collected_tool_metadata,
+                ));
+            }
             // Inject wrap-up hint when approaching iteration limit
             if iteration == wrapup_threshold && any_tools_called {
                 messages.push(Message::system(format!(
@@ -166,7 +240,11 @@ impl AgentLoop {
             }
 
             // Start periodic typing indicator before LLM call
-            let typing_guard = start_typing(self.typing_tx.as_ref(), typing_context.as_ref());
+            let typing_guard = start_typing(
+                self.typing_tx.as_ref(),
+                typing_context.as_ref(),
+                &typing_indicator,
+            );
 
             // Temperature strategy: use low temperature after any tool calls for
             // deterministic tool sequences, normal temperature before the first tool
@@ -177,30 +255,82 @@ impl AgentLoop {
             } else {
                 self.temperature
             };
+            // Reasoning budget follows the same phases as temperature unless the
+            // turn overrides it
+            let reasoning_effort = overrides
+                .reasoning_effort
+                .or_else(|| self.reasoning.for_phase(any_tools_called));
             // Let the model decide when to use tools (auto mode). Hallucination detection
             // in handle_text_response() catches false action claims as a safety net.
             let tool_choice: Option<String> = None;
 
             // Clone needed: messages is mutated after the call (tool results appended),
             // and ChatRequest takes ownership. Cost is negligible vs. the API round-trip.
-            let response = super::model_gateway::ModelGateway::invoke(
-                effective_provider.as_ref(),
-                super::model_gateway::ModelGateway::build_turn_request(
-                    messages.clone(),
-                    Arc::clone(&tools_arc),
-                    effective_model,
-                    self.max_tokens,
-                    current_temp,
-                    tool_choice,
-                    overrides.response_format.clone(),
-                ),
-            )
-            .await;
+            let request = super::model_gateway::ModelGateway::build_turn_request(
+                messages.clone(),
+                Arc::clone(&tools_arc),
+                effective_model,
+                self.max_tokens,
+                current_temp,
+                tool_choice,
+                overrides.response_format.clone(),
+                reasoning_effort,
+            );
+            let response = if let Some(stream) = &overrides.stream {
+                let (sink, text) = crate::providers::base::StreamSink::new();
+                let call = super::model_gateway::ModelGateway::invoke(
+                    effective_provider.as_ref(),
+                    request,
+                    &self.provider_retry,
+                    Some(sink),
+                );
+                let response = stream.forward(&self.bus, text, call).await;
+                // Text written before tool calls is not the reply
+                if response.as_ref().ok().is_none_or(LLMResponse::has_tool_calls) {
+                    stream.withdraw(&self.bus).await;
+                }
+                response
+            } else {
+                super::model_gateway::ModelGateway::invoke(
+                    effective_provider.as_ref(),
+                    request,
+                    &self.provider_retry,
+                    None,
+                )
+                .await
+            };
 
             // Stop typing indicator after LLM call returns (guard aborts on drop)
             drop(typing_guard);
 
-            let response = response?;
+            let response = match response {
+                Ok(response) => response,
+                Err(e) => {
+                    let policy_block =
+                        matches!(ProviderError::find(&e), Some(ProviderError::ContentPolicy(_)));
+                    if policy_block && !policy_reprompted && withhold_latest_content(&mut messages)
+                    {
+                        policy_reprompted = true;
+                        warn!(
+                            "provider blocked the request under its content policy, \
+                             retrying without the latest content: {}",
+                            e
+                        );
+                        continue;
+                    }
+                    return Err(e);
+                }
+            };
+
+            if self.token_breakdown {
+                token_breakdown::add_call(
+                    &mut breakdown,
+                    &messages,
+                    run_start,
+                    token_breakdown::tool_definition_tokens(&tools_arc),
+                    response.input_tokens,
+                );
+            }
 
             // Track provider-reported input token count for precise compaction decisions
             if response.input_tokens.is_some() {
@@ -212,6 +342,7 @@ impl AgentLoop {
             {
                 let db = self.memory.db();
                 let model = cost_model.to_string();
+                let caller = cost_caller(&response);
                 let input = response.input_tokens.unwrap_or(0);
                 let output = response.output_tokens.unwrap_or(0);
                 let cache_create = response.cache_creation_input_tokens.unwrap_or(0);
@@ -224,7 +355,7 @@ impl AgentLoop {
                         output,
                         cache_create,
                         cache_read,
-                        "main",
+                        &caller,
                         req_id.as_deref(),
                     ) {
                         warn!("failed to record token usage: {}", e);
@@ -232,8 +363,20 @@ impl AgentLoop {
                 });
             }
 
+            // Tool calls the model asked for after the cancel are not run
+            if super::cancel::is_cancelled(overrides.cancel.as_ref()) {
+                info!("turn cancelled after model call in iteration {}", iteration);
+                return Ok(super::cancel::cancelled_result(
+                    last_input_tokens,
+                    tools_used,
+                    collected_media,
+                    collected_tool_metadata,
+                ));
+            }
+
             if response.has_tool_calls() {
                 any_tools_called = true;
+                hit_iteration_cap = iteration == effective_max_iterations;
                 tools_used.extend(response.tool_calls.iter().map(|tc| tc.name.clone()));
                 ContextBuilder::add_assistant_message(
                     &mut messages,
@@ -245,22 +388,50 @@ impl AgentLoop {
                 );
 
                 // Start periodic typing indicator before tool execution
-                let typing_guard = start_typing(self.typing_tx.as_ref(), typing_context.as_ref());
+                let typing_guard = start_typing(
+                self.typing_tx.as_ref(),
+                typing_context.as_ref(),
+                &typing_indicator,
+            );
 
                 let exfil_ref = if self.exfiltration_guard.enabled {
                     Some(&self.exfiltration_guard)
                 } else {
                     None
                 };
-                let results = self
-                    .execute_tools(
+                // Identical repeats of the previous call are not run
+                let repeats = tool_loop.observe(&response.tool_calls);
+                let looping = repeats.contains(&true);
+                let results = if looping {
+                    let to_run: Vec<ToolCallRequest> = response
+                        .tool_calls
+                        .iter()
+                        .zip(&repeats)
+                        .filter(|(_, repeat)| !**repeat)
+                        .map(|(tc, _)| tc.clone())
+                        .collect();
+                    let ran = self
+                        .execute_tools(
+                            &to_run,
+                            &tool_names,
+                            exec_ctx,
+                            exfil_ref,
+                            overrides.routing_policy.as_ref(),
+                            overrides.dry_run,
+                        )
+                        .await;
+                    tool_loop::merge_results(&repeats, ran)
+                } else {
+                    self.execute_tools(
                         &response.tool_calls,
                         &tool_names,
                         exec_ctx,
                         exfil_ref,
                         overrides.routing_policy.as_ref(),
+                        overrides.dry_run,
                     )
-                    .await;
+                    .await
+                };
 
                 // Stop typing indicator after tool execution (guard aborts on drop)
                 drop(typing_guard);
@@ -276,6 +447,16 @@ impl AgentLoop {
                 )
                 .await;
 
+                if self.delegation_hint_after > 0
+                    && !delegation_hinted
+                    && tools_used.len() >= self.delegation_hint_after
+                    && tool_names.iter().any(|name| name == "spawn")
+                {
+                    debug!("suggesting delegation after {} tool calls", tools_used.len());
+                    messages.push(Message::system(delegation_hint(tools_used.len())));
+                    delegation_hinted = true;
+                }
+
                 // If tool_search activated new deferred tools, rebuild tool
                 // definitions so the LLM sees their schemas in the next iteration.
                 if self.tools.deferred_count() > 0 {
@@ -311,6 +492,46 @@ impl AgentLoop {
                         tools_arc = Arc::new(tools_defs);
                     }
                 }
+
+                if looping {
+                    let tool = response
+                        .tool_calls
+                        .iter()
+                        .zip(&repeats)
+                        .find_map(|(tc, repeat)| repeat.then_some(tc.name.as_str()))
+                        .unwrap_or_default();
+                    // A loop after the nudge ends the turn
+                    let action = if tool_loop_nudged {
+                        ToolLoopAction::Finalize
+                    } else {
+                        self.tool_loop.action
+                    };
+                    warn!(
+                        "model repeated {} with identical arguments {} times, action: {}",
+                        tool,
+                        tool_loop.count(),
+                        action
+                    );
+                    metrics::counter!(
+                        "oxicrab_agent_tool_loop_detected_total",
+                        "action" => action.to_string()
+                    )
+                    .increment(1);
+                    match action {
+                        ToolLoopAction::Nudge => {
+                            messages.push(Message::system(tool_loop::nudge(
+                                tool,
+                                tool_loop.count(),
+                            )));
+                            tool_loop_nudged = true;
+                        }
+                        ToolLoopAction::Finalize => {
+                            // The post-loop summary asks for an answer without tools
+                            hit_iteration_cap = false;
+                            break;
+                        }
+                    }
+                }
             } else if let Some(content) = response.content {
                 match hallucination::handle_text_response(
                     &content,
@@ -321,6 +542,17 @@ impl AgentLoop {
                 ) {
                     TextAction::Continue => {}
                     TextAction::Return => {
+                        if let TextAction::Continue = hallucination::enforce_clarification_budget(
+                            &content,
+                            &mut messages,
+                            overrides.prior_clarifications,
+                            self.clarification_budget,
+                            any_tools_called,
+                            &mut clarification_budget_fired,
+                            &tool_names,
+                        ) {
+                            continue;
+                        }
                         if layer1_fired {
                             if any_tools_called || !hallucination::contains_action_claims(&content)
                             {
@@ -329,7 +561,7 @@ impl AgentLoop {
                                 hallucination::record_retry_failure();
                             }
                         }
-                        let content = strip_think_tags(&content);
+                        let content = strip_scratchpad_blocks(&strip_think_tags(&content));
                         let content = prepend_display_text(
                             content,
                             &collected_tool_metadata,
@@ -350,6 +582,7 @@ impl AgentLoop {
                             reasoning_signature: response.reasoning_signature,
                             response_metadata,
                             tool_metadata: collected_tool_metadata,
+                            continuation: None,
                         });
                     }
                 }
@@ -367,11 +600,50 @@ impl AgentLoop {
                     tokio::time::sleep(tokio::time::Duration::from_secs_f64(delay)).await;
                     continue;
                 }
+                let action = if empty_recovery_used {
+                    EmptyResponseAction::Generic
+                } else {
+                    self.empty_response
+                };
+                self.record_empty_response(effective_model, action, overrides.request_id.as_deref());
+                empty_recovery_used = true;
+                match action {
+                    EmptyResponseAction::Reprompt => {
+                        warn!("LLM returned empty, no retries left - re-prompting once");
+                        messages.push(Message::system(EMPTY_RESPONSE_REPROMPT.to_string()));
+                        continue;
+                    }
+                    EmptyResponseAction::Fallback => {
+                        if let Some((provider, model)) = self.empty_fallback.as_ref() {
+                            warn!(
+                                "LLM returned empty, no retries left - trying fallback model {}",
+                                model
+                            );
+                            effective_provider = provider;
+                            effective_model = model;
+                            continue;
+                        }
+                    }
+                    EmptyResponseAction::Generic | EmptyResponseAction::Diagnostic => {}
+                }
                 warn!("LLM returned empty, no retries left - giving up");
+                empty_exhausted = true;
                 break;
             }
         }
 
+        // Capture resumable context before the summary prompt is appended
+        let continuation = hit_iteration_cap.then(|| {
+            warn!(
+                "agent loop hit the iteration cap ({}) with tool calls pending",
+                effective_max_iterations
+            );
+            super::continuation::build_context(
+                &messages[run_start..],
+                &checkpoint_tracker.breadcrumb(),
+            )
+        });
+
         // Collect pending buttons from the add_buttons tool (if any)
         let mut response_metadata = self.take_pending_buttons_metadata(&activation_scope);
         merge_suggested_buttons(&mut response_metadata, &collected_tool_metadata);
@@ -388,7 +660,7 @@ impl AgentLoop {
                 )
                 .await?
         {
-            let content = strip_think_tags(&content);
+            let content = strip_scratchpad_blocks(&strip_think_tags(&content));
             let content = prepend_display_text(
                 content,
                 &collected_tool_metadata,
@@ -406,6 +678,7 @@ impl AgentLoop {
                 reasoning_signature: None,
                 response_metadata,
                 tool_metadata: collected_tool_metadata,
+                continuation,
             });
         }
 
@@ -426,11 +699,22 @@ impl AgentLoop {
                 reasoning_signature: None,
                 response_metadata,
                 tool_metadata: collected_tool_metadata,
+                continuation,
             });
         }
 
+            // Diagnostic replaces the generic fallback reply in process_message
+            let content = (empty_exhausted && self.empty_response == EmptyResponseAction::Diagnostic)
+                .then(|| {
+                    format!(
+                        "The model ({effective_model}) returned an empty response {} times in a \
+                         row, so I have no answer. This is usually a temporary provider problem \
+                         or the provider's content filtering; please try again or rephrase.",
+                        EMPTY_RESPONSE_RETRIES + 1
+                    )
+                });
             Ok(AgentLoopResult {
-                content: None,
+                content,
                 input_tokens: last_input_tokens,
                 tools_used,
                 media: collected_media,
@@ -438,15 +722,56 @@ impl AgentLoop {
                 reasoning_signature: None,
                 response_metadata,
                 tool_metadata: collected_tool_metadata,
+                continuation,
             })
         }
         .await;
 
+        self.record_token_breakdown(
+            exec_ctx,
+            effective_model,
+            overrides.request_id.as_deref(),
+            breakdown,
+        );
         self.tool_search_activated.clear(&activation_scope).await;
         self.pending_buttons.clear(&activation_scope);
+        self.scratchpad.clear(&activation_scope);
+        if let (
+            Some(manager),
+            Ok(AgentLoopResult {
+                content: Some(content),
+                ..
+            }),
+        ) = (&self.workspace_manager, &result)
+        {
+            let manager = manager.clone();
+            let content = content.clone();
+            tokio::task::spawn_blocking(move || manager.touch_mentioned(&content));
+        }
         result
     }
 
+    /// Log an exhausted run of empty responses to `empty_response_log` (fire-and-forget).
+    fn record_empty_response(
+        &self,
+        model: &str,
+        action: EmptyResponseAction,
+        request_id: Option<&str>,
+    ) {
+        metrics::counter!("oxicrab_agent_empty_response_exhausted_total", "action" => action.to_string())
+            .increment(1);
+        let db = self.memory.db();
+        let model = model.to_string();
+        let request_id = request_id.map(ToString::to_string);
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) =
+                db.record_empty_response(&model, &action.to_string(), request_id.as_deref())
+            {
+                warn!("failed to record empty response: {}", e);
+            }
+        });
+    }
+
     /// Execute tool calls — single-tool fast-path or parallel `spawn`+`join_all`.
     async fn execute_tools(
         &self,
@@ -455,6 +780,7 @@ impl AgentLoop {
         exec_ctx: &ExecutionContext,
         exfil_guard: Option<&crate::config::ExfiltrationGuardConfig>,
         routing_policy: Option<&crate::router::RoutingPolicy>,
+        dry_run: bool,
     ) -> Vec<ToolResult> {
         let allow_tools: Option<crate::config::DenyByDefaultList> =
             exfil_guard.map(|g| g.allow_tools.clone());
@@ -470,6 +796,22 @@ impl AgentLoop {
                     .as_ref()
                     .is_some_and(|allow| !allow.contains(name))
         };
+        if dry_run {
+            return tool_calls
+                .iter()
+                .map(|tc| {
+                    if blocked_by_router(&tc.name) {
+                        crate::router::metrics::record_blocked_tool_attempt();
+                        return ToolResult::error(format!(
+                            "Tool '{}' is not allowed in this routed turn.",
+                            tc.name
+                        ));
+                    }
+                    info!("[dry-run] tool call: {} {}", tc.name, tc.arguments);
+                    dry_run_result(tc)
+                })
+                .collect();
+        }
         // Clone approval fields for spawned tasks (cheap Arc clones)
         let approval_store = self.approval_store.clone();
         let approval_config = self.approval_config.clone();
@@ -482,6 +824,7 @@ impl AgentLoop {
             .and_then(|v| v.as_str())
             .unwrap_or(&exec_ctx.channel)
             .to_string();
+        let audit_db = self.memory.db();
         if tool_calls.len() == 1 {
             let tc = &tool_calls[0];
             if blocked_by_router(&tc.name) {
@@ -509,6 +852,10 @@ impl AgentLoop {
                         chat_id: &exec_chat_id,
                         sender_id: &exec_sender_id,
                     }),
+                    Some(ToolAuditContext {
+                        db: &audit_db,
+                        leak_detector: &self.leak_detector,
+                    }),
                 )
                 .await,
             ]
@@ -532,6 +879,7 @@ impl AgentLoop {
                     let a_chat_id = exec_chat_id.clone();
                     let a_sender_id = exec_sender_id.clone();
                     let a_leak = self.leak_detector.clone();
+                    let a_db = audit_db.clone();
                     tokio::task::spawn(async move {
                         if blocked {
                             crate::router::metrics::record_blocked_tool_attempt();
@@ -556,6 +904,10 @@ impl AgentLoop {
                                 chat_id: &a_chat_id,
                                 sender_id: &a_sender_id,
                             }),
+                            Some(ToolAuditContext {
+                                db: &a_db,
+                                leak_detector: &a_leak,
+                            }),
                         )
                         .await
                     })
@@ -575,8 +927,8 @@ impl AgentLoop {
         }
     }
 
-    /// Collect media from tool results, scan for prompt injection, update
-    /// cognitive tracking, and fire periodic checkpoints.
+    /// Collect media from tool results, scan for prompt injection, summarize
+    /// large results, update cognitive tracking, and fire periodic checkpoints.
     #[allow(clippy::too_many_arguments)]
     async fn handle_tool_results(
         &self,
@@ -608,7 +960,9 @@ impl AgentLoop {
                 results.push(ToolResult::error("Tool execution result was lost"));
             }
         }
+        let mut is_error = Vec::with_capacity(tool_calls.len());
         for (tc, result) in tool_calls.iter().zip(results) {
+            is_error.push(result.is_error);
             if !result.is_error {
                 collected_media.extend(extract_media_paths(&result.content));
             }
@@ -663,6 +1017,49 @@ impl AgentLoop {
             }
         }
 
+        // Summarize large results once they are redacted and scanned; the
+        // untouched result stays in context if summarizing fails.
+        if let Some(ref summarizer) = self.tool_summary {
+            let session_key = exec_ctx
+                .metadata
+                .get(SESSION_KEY_META_KEY)
+                .and_then(serde_json::Value::as_str);
+            let pending: Vec<usize> = (0..tool_calls.len())
+                .filter(|&i| {
+                    summarizer.applies(
+                        &tool_calls[i],
+                        &messages[results_start + i].content,
+                        is_error[i],
+                    )
+                })
+                .collect();
+            let summaries = futures_util::future::join_all(pending.iter().map(|&i| {
+                summarizer.summarize(
+                    &tool_calls[i],
+                    &messages[results_start + i].content,
+                    session_key,
+                )
+            }))
+            .await;
+            for (i, summary) in pending.into_iter().zip(summaries) {
+                match summary {
+                    Ok(summary) => {
+                        debug!(
+                            "summarized '{}' result: {} -> {} chars",
+                            tool_calls[i].name,
+                            messages[results_start + i].content.len(),
+                            summary.len()
+                        );
+                        messages[results_start + i].content = summary;
+                    }
+                    Err(e) => warn!(
+                        "failed to summarize '{}' result, keeping it as is: {}",
+                        tool_calls[i].name, e
+                    ),
+                }
+            }
+        }
+
         // Record tool calls for cognitive checkpoint tracking
         let called_tool_names: Vec<&str> = tool_calls.iter().map(|tc| tc.name.as_str()).collect();
         checkpoint_tracker.record_tool_calls(&called_tool_names);
@@ -734,6 +1131,8 @@ impl AgentLoop {
                 self.max_tokens,
                 self.temperature,
             ),
+            &self.provider_retry,
+            None,
         )
         .await
         {
@@ -749,6 +1148,7 @@ impl AgentLoop {
                 let cache_create = response.cache_creation_input_tokens.unwrap_or(0);
                 let cache_read = response.cache_read_input_tokens.unwrap_or(0);
                 let req_id = request_id.map(str::to_string);
+                let caller = cost_caller(&response);
                 tokio::task::spawn_blocking(move || {
                     if let Err(e) = db.record_tokens(
                         &cost_model,
@@ -756,7 +1156,7 @@ impl AgentLoop {
                         output,
                         cache_create,
                         cache_read,
-                        "main",
+                        &caller,
                         req_id.as_deref(),
                     ) {
                         warn!("failed to record token usage: {}", e);
@@ -771,3 +1171,22 @@ impl AgentLoop {
         }
     }
 }
+
+/// Replace the most recent user message or tool result with
+/// [`CONTENT_POLICY_PLACEHOLDER`]. Returns false when there is nothing left
+/// to withhold.
+fn withhold_latest_content(messages: &mut [Message]) -> bool {
+    let Some(msg) = messages
+        .iter_mut()
+        .rev()
+        .find(|m| m.role == "user" || m.role == "tool")
+    else {
+        return false;
+    };
+    if msg.content == CONTENT_POLICY_PLACEHOLDER && msg.images.is_empty() {
+        return false;
+    }
+    msg.content = CONTENT_POLICY_PLACEHOLDER.to_string();
+    msg.images.clear();
+    true
+}
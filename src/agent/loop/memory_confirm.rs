@@ -0,0 +1,190 @@
+//! Confirmation before sensitive facts are written to memory
+//! (`agents.defaults.memory.confirmCategories`). Held-back facts wait per
+//! session until the user answers yes or no; any other message drops them.
+
+use super::AgentLoop;
+use super::helpers::save_extracted_facts;
+use crate::config::MemoryCategory;
+use anyhow::Result;
+use lru::LruCache;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+const MAX_PENDING_SESSIONS: usize = 256;
+/// How long a proposal waits for an answer.
+const PENDING_TTL: Duration = Duration::from_secs(30 * 60);
+
+const YES: &[&str] = &[
+    "yes",
+    "y",
+    "yep",
+    "yeah",
+    "yes please",
+    "confirm",
+    "confirmed",
+    "ok",
+    "okay",
+    "sure",
+    "save it",
+    "remember it",
+];
+const NO: &[&str] = &[
+    "no",
+    "n",
+    "nope",
+    "nah",
+    "no thanks",
+    "cancel",
+    "don't",
+    "dont",
+    "forget it",
+];
+
+/// Where a confirmed fact is written.
+#[derive(Debug, Clone)]
+pub(super) enum FactTarget {
+    /// Daily notes, like the remember fast path.
+    Daily,
+    /// The "Facts" section, like background extraction, queued for the fact
+    /// digest of this `(channel, chat_id)` when set.
+    Facts(Option<(String, String)>),
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct PendingFact {
+    pub content: String,
+    pub category: MemoryCategory,
+    pub target: FactTarget,
+}
+
+/// Facts awaiting the user's confirmation, per session. In-memory only;
+/// proposals are lost on restart.
+pub(super) struct PendingMemoryWrites {
+    pending: std::sync::Mutex<LruCache<String, (Instant, Vec<PendingFact>)>>,
+}
+
+impl PendingMemoryWrites {
+    pub fn new() -> Self {
+        Self {
+            pending: std::sync::Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_PENDING_SESSIONS).expect("non-zero"),
+            )),
+        }
+    }
+
+    /// Hold `facts` for `session_key`, after any already waiting there.
+    pub fn add(&self, session_key: &str, facts: Vec<PendingFact>) {
+        let mut pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match pending.get_mut(session_key) {
+            Some((since, existing)) if since.elapsed() < PENDING_TTL => {
+                *since = Instant::now();
+                existing.extend(facts);
+            }
+            _ => {
+                pending.put(session_key.to_string(), (Instant::now(), facts));
+            }
+        }
+    }
+
+    /// Remove and return the facts waiting for `session_key`, unless expired.
+    fn take(&self, session_key: &str) -> Option<Vec<PendingFact>> {
+        let (since, facts) = self
+            .pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .pop(session_key)?;
+        (since.elapsed() < PENDING_TTL).then_some(facts)
+    }
+}
+
+/// The question put to the user for held-back facts.
+pub(super) fn confirmation_prompt(facts: &[PendingFact]) -> String {
+    if let [fact] = facts {
+        return format!(
+            "I'd like to remember this ({}): {} — confirm? (yes/no)",
+            fact.category, fact.content
+        );
+    }
+    let mut prompt = "I'd like to remember these:".to_string();
+    for fact in facts {
+        let _ = write!(prompt, "\n- {} ({})", fact.content, fact.category);
+    }
+    prompt.push_str("\nConfirm? (yes/no)");
+    prompt
+}
+
+/// `Some(true)` for a yes, `Some(false)` for a no, `None` for anything else.
+fn parse_answer(content: &str) -> Option<bool> {
+    let lower = content.trim().to_lowercase();
+    let normalized = lower.trim_end_matches(|c: char| c.is_ascii_punctuation());
+    if YES.contains(&normalized) {
+        Some(true)
+    } else if NO.contains(&normalized) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+impl AgentLoop {
+    /// Answer a pending memory confirmation. Returns the reply when `content`
+    /// was a yes or no to held-back facts; otherwise the proposal is dropped
+    /// and the message is processed normally.
+    pub(super) async fn resolve_memory_confirmation(
+        &self,
+        session_key: &str,
+        content: &str,
+    ) -> Result<Option<String>> {
+        let Some(facts) = self.pending_memory.take(session_key) else {
+            return Ok(None);
+        };
+        let Some(confirmed) = parse_answer(content) else {
+            info!(
+                "memory confirmation: {} proposal(s) dropped, user moved on",
+                facts.len()
+            );
+            return Ok(None);
+        };
+
+        let outcome = if confirmed { "confirmed" } else { "declined" };
+        metrics::counter!(
+            "oxicrab_memory_remember_write_total",
+            "path" => "confirm",
+            "outcome" => outcome
+        )
+        .increment(facts.len() as u64);
+        let profile = self.profile(session_key);
+        let response = if confirmed {
+            for fact in &facts {
+                match &fact.target {
+                    FactTarget::Daily => profile.memory.append_today(&fact.content)?,
+                    FactTarget::Facts(digest_target) => save_extracted_facts(
+                        &profile.memory,
+                        &format!("- {}", fact.content),
+                        digest_target.as_ref(),
+                    )?,
+                }
+            }
+            info!("memory confirmation: wrote {} fact(s)", facts.len());
+            if let [fact] = facts.as_slice() {
+                format!("Saved. I'll remember: {}", fact.content)
+            } else {
+                format!("Saved {} facts.", facts.len())
+            }
+        } else {
+            "Okay, I won't remember that.".to_string()
+        };
+
+        let mut session = profile.sessions.get_or_create(session_key).await?;
+        session.add_message("user", content, HashMap::new());
+        session.add_message("assistant", response.clone(), HashMap::new());
+        profile.sessions.save(&session).await?;
+        Ok(Some(response))
+    }
+}
@@ -547,10 +547,11 @@ mod display_text_tests {
     fn test_display_text_prompt_guard_warn_passes() {
         use crate::config::PromptGuardConfig;
         use crate::safety::prompt_guard::PromptGuard;
-        let guard = PromptGuard::new();
+        let guard = PromptGuard::default();
         let config = PromptGuardConfig {
             enabled: true,
             action: crate::config::PromptGuardAction::Warn,
+            ..Default::default()
         };
         let meta = vec![(
             "tool".to_string(),
@@ -572,10 +573,11 @@ mod display_text_tests {
     fn test_display_text_prompt_guard_block_drops() {
         use crate::config::PromptGuardConfig;
         use crate::safety::prompt_guard::PromptGuard;
-        let guard = PromptGuard::new();
+        let guard = PromptGuard::default();
         let config = PromptGuardConfig {
             enabled: true,
             action: crate::config::PromptGuardAction::Block,
+            ..Default::default()
         };
         let meta = vec![(
             "tool".to_string(),
@@ -1,27 +1,37 @@
+mod cancel;
 mod compaction_history;
 mod complexity;
 pub mod config;
+mod continuation;
+mod extraction;
+mod focus;
 mod hallucination;
 mod helpers;
+mod image_descriptions;
 mod iteration;
+mod memory_confirm;
 mod metadata;
 mod model_gateway;
+mod offline;
 mod processing;
 mod replay;
+mod session_archive;
+mod streaming;
+mod token_breakdown;
+mod tool_loop;
+mod tool_summary;
 
 #[cfg(test)]
 use crate::agent::tools::base::ExecutionContext;
 #[cfg(test)]
 use helpers::ACTION_CLAIM_PATTERNS;
-#[cfg(test)]
-use helpers::MAX_IMAGES;
 use helpers::cleanup_old_media;
 pub use helpers::contains_action_claims;
 pub(crate) use helpers::validate_tool_params;
 #[cfg(test)]
 use helpers::{
-    execute_tool_call, extract_media_paths, load_and_encode_images, strip_document_tags,
-    strip_think_tags,
+    ToolAuditContext, cap_attachments, execute_tool_call, extract_media_paths,
+    load_and_encode_images, strip_document_tags, strip_think_tags,
 };
 
 pub use config::{
@@ -32,6 +42,7 @@ pub use config::{
 use crate::agent::compaction::MessageCompactor;
 use crate::agent::context::ContextBuilder;
 use crate::agent::memory::MemoryStore;
+use crate::agent::profile::{DEFAULT_PROFILE, Profile, ProfileResolver};
 use crate::agent::subagent::{SubagentConfig, SubagentManager};
 use crate::agent::tools::ToolRegistry;
 use crate::agent::tools::setup::ToolBuildContext;
@@ -42,14 +53,15 @@ use crate::providers::base::LLMProvider;
 use crate::safety::LeakDetector;
 use crate::session::{SessionManager, SessionStore};
 use crate::utils::task_tracker::TaskTracker;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use futures_util::stream::{FuturesUnordered, StreamExt};
 use lru::LruCache;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{Mutex, Notify};
-use tracing::{debug, error, info, warn};
+use tracing::{Instrument, debug, error, info, info_span, warn};
 
 const EMPTY_RESPONSE_RETRIES: usize = 2;
 const WRAPUP_THRESHOLD_RATIO: f64 = 0.7;
@@ -76,31 +88,84 @@ struct SessionCompactionState {
 use hallucination::TextAction;
 
 pub struct AgentLoop {
-    inbound_rx: Arc<tokio::sync::Mutex<tokio::sync::mpsc::Receiver<InboundMessage>>>,
+    /// Inbound messages, handed out by priority (see [`crate::bus::PriorityReceiver`]).
+    inbound_rx: Arc<tokio::sync::Mutex<crate::bus::PriorityReceiver>>,
     bus: Arc<crate::bus::MessageBus>,
     provider: Arc<dyn LLMProvider>,
     workspace: PathBuf,
     model: String,
     max_iterations: usize,
-    context: Arc<Mutex<ContextBuilder>>,
-    sessions: Arc<dyn SessionStore>,
+    /// Memory store of the default profile, also holding the operational
+    /// logs (token usage, tool audit) for every profile.
     memory: Arc<MemoryStore>,
+    /// Workspaces, memory and session stores selected per chat.
+    profiles: Arc<ProfileResolver>,
     tools: Arc<ToolRegistry>,
     compactor: Option<Arc<MessageCompactor>>,
+    /// Background contradiction detector (None when disabled)
+    memory_gardener: Option<Arc<crate::agent::memory::gardener::MemoryGardener>>,
+    /// Periodic digest of extracted facts (None when disabled)
+    fact_digest: Option<Arc<crate::agent::memory::digest::FactDigest>>,
+    /// Periodic severe-weather check (None when disabled)
+    weather_alerts: Option<Arc<crate::agent::weather::WeatherAlerts>>,
+    /// Queue of messages received while the provider is down (`offlineMode`)
+    offline: Option<Arc<offline::OfflineQueue>>,
+    /// Streamed replies to channels that can edit a sent message
+    streaming: crate::config::StreamingConfig,
+    /// Retry policy for LLM calls
+    provider_retry: crate::providers::base::RetryConfig,
+    /// Fact categories written to memory only after the user confirms
+    memory_confirm: Vec<crate::config::MemoryCategory>,
+    /// Facts awaiting that confirmation, per session
+    pending_memory: Arc<memory_confirm::PendingMemoryWrites>,
     compaction_config: crate::config::CompactionConfig,
+    /// Batches exchanges per session until fact extraction is due
+    extraction_throttle: extraction::ExtractionThrottle,
     _subagents: Option<Arc<SubagentManager>>,
     /// Per-session processing locks. Each session key maps to a Mutex that
     /// serializes message processing for that session while allowing independent
     /// sessions to be processed concurrently.
     session_locks: Arc<std::sync::Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
+    /// Cancellation flags of the turns holding a session lock (`/cancel`)
+    turn_cancels: cancel::TurnCancellations,
     running: Arc<tokio::sync::Mutex<bool>>,
     shutdown_notify: Arc<Notify>,
+    /// Held by [`run()`](Self::run) until it has stopped receiving and every
+    /// in-flight turn has finished. [`stop_with_drain()`](Self::stop_with_drain)
+    /// acquires it to wait for those turns.
+    processing_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Inbound messages [`run()`](Self::run) processes at the same time
+    max_concurrent_sessions: usize,
     task_tracker: Arc<TaskTracker>,
     temperature: Option<f32>,
     tool_temperature: Option<f32>,
+    /// Reasoning budgets for main-loop calls, by phase
+    reasoning: crate::config::ReasoningConfig,
+    /// Recovery once empty-response retries are exhausted
+    empty_response: crate::config::EmptyResponseAction,
+    /// Provider/model tried once by the `fallback` empty-response action
+    empty_fallback: Option<(Arc<dyn LLMProvider>, String)>,
+    /// Record an estimated per-turn token breakdown (`tokenBreakdown`)
+    token_breakdown: bool,
+    /// Handling of repeated identical tool calls
+    tool_loop: crate::config::ToolLoopConfig,
+    /// Summarizes large tool results (`toolResultSummary`)
+    tool_summary: Option<tool_summary::ToolResultSummarizer>,
+    /// Tool sets selectable per session with `/focus` (`toolPresets`)
+    tool_presets: HashMap<String, Vec<String>>,
+    /// Tool calls in a turn before the model is reminded of `spawn` (0 = never)
+    delegation_hint_after: usize,
     max_tokens: u32,
     typing_tx: Option<Arc<tokio::sync::mpsc::Sender<(String, String)>>>,
+    /// Typing indicator settings by channel name
+    typing_indicators: HashMap<String, crate::config::TypingIndicatorConfig>,
     transcriber: Option<Arc<crate::utils::transcription::LazyTranscriptionService>>,
+    /// Per-message caps on inbound images and documents
+    inbound_media: crate::config::InboundMediaConfig,
+    /// Clarifying questions allowed in a row on action requests (0 = unlimited)
+    clarification_budget: u32,
+    /// Automatic continuations of a turn capped with work pending (0 = manual only)
+    auto_continue: u32,
     event_matcher: Option<std::sync::Mutex<EventMatcher>>,
     /// Epoch-seconds timestamp of last event matcher rebuild (atomic to avoid
     /// blocking the async runtime with a `std::sync::Mutex`)
@@ -114,6 +179,8 @@ pub struct AgentLoop {
     /// Prompt injection detection guard
     prompt_guard: Option<crate::safety::prompt_guard::PromptGuard>,
     prompt_guard_config: crate::config::PromptGuardConfig,
+    /// Operator content policy for inbound messages (None when disabled)
+    content_filter: Option<crate::safety::ContentFilter>,
     /// Inbound secret leak detector — scans user messages before they reach the LLM
     leak_detector: Arc<LeakDetector>,
     /// MCP manager — kept for graceful child process shutdown via `stop()`
@@ -126,6 +193,10 @@ pub struct AgentLoop {
     tool_search_activated: crate::agent::tools::tool_search::ActivatedTools,
     /// Request-scoped state for interactive buttons (written by `add_buttons`, read after loop)
     pending_buttons: crate::agent::tools::interactive::PendingButtons,
+    /// Request-scoped working notes (written by `scratchpad_append`, cleared after each run)
+    scratchpad: crate::agent::tools::scratchpad::Scratchpad,
+    /// Workspace file manifest; files mentioned in a final answer are touched
+    workspace_manager: Option<Arc<crate::agent::workspace::WorkspaceManager>>,
     /// Priority-ordered message router for direct dispatch and guided LLM paths
     router: std::sync::Arc<crate::router::MessageRouter>,
     /// Semantic filter size (top-k tools) for no-context LLM turns.
@@ -144,6 +215,18 @@ pub struct AgentLoop {
     outbound_tx: Arc<tokio::sync::mpsc::Sender<crate::bus::OutboundMessage>>,
 }
 
+/// Approval button clicks and `/cancel`, which `process_message` handles
+/// without the session lock because they target the turn holding it.
+fn skips_session_lock(msg: &InboundMessage) -> bool {
+    match msg.action {
+        Some(ref action) => {
+            action.tool == "__approval"
+                && matches!(action.source, crate::dispatch::ActionSource::Button { .. })
+        }
+        None => cancel::is_command(&msg.content),
+    }
+}
+
 impl AgentLoop {
     pub async fn new(config: AgentLoopConfig) -> Result<Self> {
         let AgentLoopConfig {
@@ -158,10 +241,26 @@ impl AgentLoop {
             temperature,
             tool_temperature,
             per_provider_temperature,
+            reasoning,
+            empty_response,
+            tool_loop,
+            tool_result_summary,
+            message_batching,
+            tool_presets,
             max_tokens,
             typing_tx,
             max_concurrent_subagents,
+            delegation,
+            offline_mode,
+            streaming,
+            provider_retry,
+            max_concurrent_sessions,
             voice_config,
+            inbound_media,
+            clarification_budget,
+            auto_continue,
+            sender_preferences,
+            token_breakdown,
             memory_config,
             cognitive_config,
             context_providers,
@@ -170,24 +269,48 @@ impl AgentLoop {
             lifecycle:
                 LifecycleConfig {
                     session_ttl_days,
+                    session_archive_days,
                     media_ttl_days,
                 },
             safety:
                 SafetyConfig {
                     exfiltration_guard,
                     prompt_guard: prompt_guard_config,
+                    content_filter: content_filter_config,
                 },
             memory_db: shared_db,
             leak_detector: shared_leak_detector,
             router_config,
             approval_config,
+            profiles: profile_configs,
         } = config;
 
         // Extract receiver from the bus (called once at startup).
         // Receivers are !Sync, so we wrap in Arc<Mutex> for sharing.
+        let admin_target = tool_configs
+            .channels_config
+            .as_ref()
+            .and_then(|c| c.admin_target.clone());
+        let typing_indicators = tool_configs
+            .channels_config
+            .as_ref()
+            .map(crate::config::ChannelsConfig::typing_indicators)
+            .unwrap_or_default();
+        let persona_suffixes = tool_configs
+            .channels_config
+            .as_ref()
+            .map(crate::config::ChannelsConfig::persona_suffixes)
+            .unwrap_or_default();
         let inbound_rx = Arc::new(tokio::sync::Mutex::new(
-            bus.take_inbound_rx()
-                .ok_or_else(|| anyhow::anyhow!("Inbound receiver already taken"))?,
+            crate::bus::PriorityReceiver::new(
+                bus.take_inbound_rx()
+                    .ok_or_else(|| anyhow::anyhow!("Inbound receiver already taken"))?,
+            )
+            .with_admin_target(admin_target)
+            .with_batching(
+                std::time::Duration::from_millis(message_batching.window_ms),
+                std::time::Duration::from_millis(message_batching.max_wait_ms),
+            ),
         ));
         let model = model.unwrap_or_else(|| provider.default_model().to_string());
 
@@ -209,23 +332,89 @@ impl AgentLoop {
 
         // Share the (embedding-configured) memory store with context builder
         let mut context_builder = ContextBuilder::with_memory(&workspace, memory.clone())?;
-        if !context_providers.is_empty() {
-            use crate::agent::context::providers::ContextProviderRunner;
-            let runner = Arc::new(ContextProviderRunner::new(context_providers));
-            context_builder.set_providers(runner);
+        let weather_config = tool_configs
+            .weather_config
+            .as_ref()
+            .filter(|c| c.enabled && !c.api_key.is_empty() && !c.locations.is_empty());
+        let weather_context = weather_config.filter(|c| c.context_conditions).map(|c| {
+            Arc::new(crate::agent::weather::WeatherContext::new(
+                oxicrab_tools_api::weather::WeatherTool::new(c.api_key.clone()),
+                c.clone(),
+            ))
+        });
+        let weather_provider = weather_config
+            .filter(|_| {
+                context_providers
+                    .iter()
+                    .any(|p| p.enabled && p.kind == crate::config::ContextProviderKind::Weather)
+            })
+            .map(|c| {
+                Arc::new(crate::agent::weather::WeatherContextProvider::new(
+                    oxicrab_tools_api::weather::WeatherTool::new(c.api_key.clone()),
+                    c.clone(),
+                ))
+            });
+        let providers_runner =
+            (!context_providers.is_empty() || weather_context.is_some()).then(|| {
+                use crate::agent::context::providers::ContextProviderRunner;
+                let mut runner = ContextProviderRunner::new(context_providers);
+                if let Some(weather) = weather_context {
+                    runner = runner.with_weather(weather);
+                }
+                if let Some(provider) = weather_provider {
+                    runner = runner.with_weather_provider(provider);
+                }
+                Arc::new(runner)
+            });
+        if let Some(ref runner) = providers_runner {
+            context_builder.set_providers(runner.clone());
         }
+        let weather_alerts = weather_config.filter(|c| c.alerts.enabled).map(|c| {
+            Arc::new(crate::agent::weather::WeatherAlerts::new(
+                oxicrab_tools_api::weather::WeatherTool::new(c.api_key.clone()),
+                bus.clone(),
+                c.clone(),
+            ))
+        });
+        let offline = offline_mode.enabled.then(|| {
+            Arc::new(offline::OfflineQueue::new(
+                offline_mode,
+                memory.db(),
+                bus.clone(),
+            ))
+        });
+        context_builder.set_sender_preferences(sender_preferences);
+        context_builder.set_persona_suffixes(persona_suffixes.clone());
         let context = Arc::new(Mutex::new(context_builder));
 
-        // Clean up expired sessions in background (reuse shared DB)
-        if session_ttl_days > 0 {
-            let ttl = session_ttl_days;
-            let mgr_for_cleanup = SessionManager::with_db(memory.db());
-            tokio::spawn(async move {
-                if let Err(e) = mgr_for_cleanup.cleanup_old_sessions(ttl).await {
-                    warn!("Session cleanup failed: {}", e);
-                }
-            });
+        // The default profile reuses the stores opened above; each configured
+        // profile gets its own workspace, memory DB and context builder.
+        let mut profiles = ProfileResolver::new(Arc::new(Profile::from_parts(
+            DEFAULT_PROFILE,
+            workspace.clone(),
+            memory.clone(),
+            sessions.clone(),
+            context,
+        )));
+        for profile_config in profile_configs {
+            let profile_workspace = ProfileResolver::workspace_for(&profile_config, &workspace);
+            let profile = Profile::open(
+                profile_config.name.clone(),
+                profile_workspace,
+                memory_config.as_ref(),
+                sender_preferences,
+                &persona_suffixes,
+                providers_runner.clone(),
+            )
+            .with_context(|| format!("failed to open profile '{}'", profile_config.name))?;
+            info!(
+                "profile '{}' uses workspace {}",
+                profile.name,
+                profile.workspace.display()
+            );
+            profiles.add(profile_config, Arc::new(profile));
         }
+        let profiles = Arc::new(profiles);
 
         // Clean up old media files in background (blocking I/O, not on reactor)
         if media_ttl_days > 0 {
@@ -240,11 +429,14 @@ impl AgentLoop {
         // Run memory hygiene in background (search log purge, workspace file cleanup)
         {
             let db = memory.db();
+            let profile_dbs: Vec<_> = profiles.all().skip(1).map(|p| p.memory.db()).collect();
             let ws = workspace.clone();
             let ttl_map = tool_configs.workspace_ttl.to_map();
             let mem_retention_days = memory_config.as_ref().map_or(180, |c| c.retention_days);
             tokio::task::spawn_blocking(move || {
-                crate::agent::memory::hygiene::run_hygiene(&db, 90, mem_retention_days);
+                for db in std::iter::once(&db).chain(&profile_dbs) {
+                    crate::agent::memory::hygiene::run_hygiene(db, 90, mem_retention_days);
+                }
                 if let Err(e) =
                     crate::agent::memory::hygiene::cleanup_workspace_files(&db, &ws, &ttl_map)
                 {
@@ -259,12 +451,15 @@ impl AgentLoop {
         )));
 
         let pending_buttons = crate::agent::tools::interactive::new_pending_buttons();
+        let scratchpad = crate::agent::tools::scratchpad::Scratchpad::new();
 
         let leak_detector = shared_leak_detector.unwrap_or_else(|| Arc::new(LeakDetector::new()));
 
         let tool_ctx = ToolBuildContext {
             workspace: workspace.clone(),
             restrict_to_workspace: tool_configs.restrict_to_workspace,
+            workspace_only: tool_configs.workspace_only,
+            tenant_workspaces: tool_configs.tenant_workspaces,
             exec_timeout: tool_configs.exec_timeout,
             outbound_tx: outbound_tx.clone(),
             bus: bus.clone(),
@@ -279,7 +474,9 @@ impl AgentLoop {
             obsidian_config: tool_configs.obsidian_config,
             browser_config: tool_configs.browser_config,
             image_gen_config: tool_configs.image_gen_config,
+            tmux_config: tool_configs.tmux_config,
             memory: memory.clone(),
+            provider: provider.clone(),
             subagent_config: {
                 let (sa_provider, sa_model) = if let Some(ref r) = routing {
                     let o = r.resolve_overrides("subagent");
@@ -297,22 +494,37 @@ impl AgentLoop {
                     model: sa_model,
                     max_tokens,
                     tool_temperature,
+                    reasoning_effort: reasoning.for_task("subagent"),
                     max_concurrent: max_concurrent_subagents,
+                    max_per_turn: delegation.max_per_turn,
+                    announce: delegation.announce,
+                    stream_tool_activity: delegation.stream_tool_activity,
                     prompt_guard_config: prompt_guard_config.clone(),
                     exfil_guard: exfiltration_guard.clone(),
                     main_tools: None, // set after register_all_tools()
+                    allowed_tools: delegation.allowed_tools.clone(),
                     memory_db: Some(memory.db()),
                     leak_detector: leak_detector.clone(),
+                    retry: (&provider_retry).into(),
                 }
             },
             allowed_commands: tool_configs.allowed_commands,
             mcp_config: tool_configs.mcp_config,
             sandbox_config: tool_configs.sandbox_config,
             memory_db: Some(memory.db()),
-            workspace_manager,
+            workspace_manager: workspace_manager.clone(),
             workspace_ttl: tool_configs.workspace_ttl,
             pending_buttons: pending_buttons.clone(),
             rss_config: tool_configs.rss_config,
+            rate_limits: tool_configs.rate_limits,
+            tool_timeouts: tool_configs.tool_timeouts,
+            default_tool_timeout_secs: tool_configs.default_tool_timeout_secs,
+            scratchpad_config: tool_configs.scratchpad_config,
+            scratchpad: scratchpad.clone(),
+            sessions: sessions.clone(),
+            profiles: profiles.clone(),
+            sender_preferences,
+            token_breakdown,
         };
 
         let (tools, subagents, mcp_manager, tool_search_activated) =
@@ -320,6 +532,12 @@ impl AgentLoop {
         let tools = Arc::new(tools);
         subagents.set_main_tools(tools.clone());
 
+        for (preset, names) in &tool_presets {
+            for name in names.iter().filter(|n| tools.get(n).is_none()) {
+                warn!("toolPresets.{preset}: tool '{name}' is not registered");
+            }
+        }
+
         // Warn about built-in tools with mutating actions that have no approval gate.
         // Only runs when the interactive approval workflow is disabled.
         if !approval_config.enabled {
@@ -365,38 +583,142 @@ impl AgentLoop {
                 ))
             });
 
-        let compactor = if compaction_config.enabled {
-            let (comp_provider, comp_model, comp_temp_override) = if let Some(ref r) = routing {
-                let o = r.resolve_overrides("compaction");
-                if let Some(p) = o.provider {
-                    // Routing overrides the compaction provider. Still apply
-                    // per_provider_temperature — the compaction model may
-                    // require a fixed temperature (e.g. Moonshot kimi-k2.5
-                    // requires temperature=1).
-                    (p, o.model, per_provider_temperature)
-                } else {
-                    (
-                        provider.clone() as Arc<dyn LLMProvider>,
-                        compaction_config.model.clone(),
-                        per_provider_temperature,
-                    )
-                }
+        // Background LLM work (compaction, memory gardening) uses the
+        // "compaction" routing override when configured.
+        let (comp_provider, comp_model, comp_temp_override) = if let Some(ref r) = routing {
+            let o = r.resolve_overrides("compaction");
+            if let Some(p) = o.provider {
+                // Routing overrides the compaction provider. Still apply
+                // per_provider_temperature — the compaction model may
+                // require a fixed temperature (e.g. Moonshot kimi-k2.5
+                // requires temperature=1).
+                (p, o.model, per_provider_temperature)
             } else {
                 (
                     provider.clone() as Arc<dyn LLMProvider>,
                     compaction_config.model.clone(),
                     per_provider_temperature,
                 )
-            };
-            Some(Arc::new(MessageCompactor::with_temperature_override(
-                comp_provider,
-                comp_model,
-                comp_temp_override,
-            )))
+            }
+        } else {
+            (
+                provider.clone() as Arc<dyn LLMProvider>,
+                compaction_config.model.clone(),
+                per_provider_temperature,
+            )
+        };
+
+        let empty_fallback = routing.as_ref().and_then(|r| {
+            let o = r.resolve_overrides(crate::config::routing::EMPTY_RESPONSE_TASK);
+            o.provider.zip(o.model)
+        });
+
+        // `compaction.extractionModel` may name another provider's model, so
+        // it is resolved by the provider factory like other task overrides
+        let extraction = routing.as_ref().and_then(|r| {
+            let o = r.resolve_overrides(crate::config::routing::EXTRACTION_TASK);
+            o.provider.zip(o.model)
+        });
+
+        // Tool summaries use their own model when configured, else the
+        // background (compaction) model.
+        let tool_summary = tool_result_summary.enabled.then(|| {
+            let (summary_provider, summary_model) = routing
+                .as_ref()
+                .and_then(|r| {
+                    let o = r.resolve_overrides(crate::config::routing::TOOL_SUMMARY_TASK);
+                    o.provider.zip(o.model)
+                })
+                .unwrap_or_else(|| {
+                    (
+                        comp_provider.clone(),
+                        comp_model.clone().unwrap_or_else(|| model.clone()),
+                    )
+                });
+            tool_summary::ToolResultSummarizer::new(
+                summary_provider,
+                summary_model,
+                tool_result_summary.threshold_chars,
+                Arc::new(crate::agent::workspace::WorkspaceManager::new(
+                    workspace.clone(),
+                    Some(memory.db()),
+                )),
+            )
+        });
+
+        let memory_gardener = memory_config
+            .as_ref()
+            .filter(|c| c.gardener.enabled)
+            .map(|c| {
+                Arc::new(crate::agent::memory::gardener::MemoryGardener::new(
+                    memory.db(),
+                    comp_provider.clone(),
+                    comp_model.clone(),
+                    comp_temp_override,
+                    c.gardener.clone(),
+                ))
+            });
+
+        let fact_digest = memory_config
+            .as_ref()
+            .filter(|c| c.fact_digest.enabled)
+            .map(|c| {
+                Arc::new(crate::agent::memory::digest::FactDigest::new(
+                    memory.db(),
+                    bus.clone(),
+                    c.fact_digest.clone(),
+                ))
+            });
+
+        let memory_confirm = memory_config
+            .as_ref()
+            .map(|c| c.confirm_categories.clone())
+            .unwrap_or_default();
+
+        let compactor = if compaction_config.enabled {
+            Some(Arc::new(
+                MessageCompactor::with_temperature_override(
+                    comp_provider,
+                    comp_model,
+                    comp_temp_override,
+                )
+                .with_extraction_provider(extraction)
+                .with_checkpoint_model(compaction_config.checkpoint.model.clone())
+                .with_cost_db(memory.db()),
+            ))
         } else {
             None
         };
 
+        // Clean up expired sessions in background (reuse each profile's DB),
+        // then archive the idle ones that survived
+        let archive_compactor = compactor.clone().filter(|_| session_archive_days > 0);
+        if session_ttl_days > 0 || archive_compactor.is_some() {
+            let ttl = session_ttl_days;
+            let profiles = profiles.clone();
+            tokio::spawn(async move {
+                for profile in profiles.all() {
+                    let mgr_for_cleanup = SessionManager::with_db(profile.memory.db());
+                    if ttl > 0
+                        && let Err(e) = mgr_for_cleanup.cleanup_old_sessions(ttl).await
+                    {
+                        warn!("Session cleanup failed: {}", e);
+                    }
+                    if let Some(ref compactor) = archive_compactor
+                        && let Err(e) = session_archive::archive_idle_sessions(
+                            profile.sessions.as_ref(),
+                            compactor,
+                            &profile.memory,
+                            session_archive_days,
+                        )
+                        .await
+                    {
+                        warn!("Session archival failed: {}", e);
+                    }
+                }
+            });
+        }
+
         // Build event matcher from cron jobs. Always create the matcher when
         // cron_service exists so that new event-triggered jobs added after
         // startup can be picked up by the periodic rebuild.
@@ -440,14 +762,19 @@ impl AgentLoop {
         let semantic_top_k = router_config.semantic_top_k.max(1);
         let semantic_prefilter_k = router_config.semantic_prefilter_k.max(semantic_top_k);
         let semantic_threshold = router_config.semantic_threshold.clamp(-1.0, 1.0);
-        let router = std::sync::Arc::new(crate::router::MessageRouter::with_remember_checker(
-            tools.routing_rules().to_vec(),
-            config_rules,
-            router_config.prefix,
-            Some(Box::new(|msg: &str| {
-                crate::agent::memory::remember::extract_remember_content(msg).is_some()
+        let router = std::sync::Arc::new(
+            crate::router::MessageRouter::with_remember_checker(
+                tools.routing_rules().to_vec(),
+                config_rules,
+                router_config.prefix,
+                Some(Box::new(|msg: &str| {
+                    crate::agent::memory::remember::extract_remember_content(msg).is_some()
+                })),
+            )
+            .with_forget_checker(Box::new(|msg: &str| {
+                crate::agent::memory::remember::extract_forget_content(msg).is_some()
             })),
-        ));
+        );
 
         let complexity_scorer = if let Some(ref r) = routing
             && let Some(weights) = r.chat_weights()
@@ -458,6 +785,19 @@ impl AgentLoop {
             None
         };
 
+        let content_filter = if content_filter_config.enabled {
+            info!(
+                "inbound content filter enabled ({} rules)",
+                content_filter_config.rules.len()
+            );
+            Some(
+                crate::safety::ContentFilter::new(&content_filter_config)
+                    .context("invalid agents.defaults.contentFilter pattern")?,
+            )
+        } else {
+            None
+        };
+
         Ok(Self {
             inbound_rx,
             bus,
@@ -465,22 +805,47 @@ impl AgentLoop {
             workspace: workspace.clone(),
             model,
             max_iterations,
-            context,
-            sessions,
             memory,
+            profiles,
             tools,
             compactor,
+            memory_gardener,
+            fact_digest,
+            weather_alerts,
+            offline,
+            streaming,
+            provider_retry: (&provider_retry).into(),
+            memory_confirm,
+            pending_memory: Arc::new(memory_confirm::PendingMemoryWrites::new()),
+            extraction_throttle: extraction::ExtractionThrottle::new(
+                compaction_config.extraction_interval.clone(),
+            ),
             compaction_config,
             _subagents: Some(subagents),
             session_locks: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            turn_cancels: cancel::TurnCancellations::new(),
             running: Arc::new(tokio::sync::Mutex::new(false)),
             shutdown_notify: Arc::new(Notify::new()),
+            processing_lock: Arc::new(tokio::sync::Mutex::new(())),
+            max_concurrent_sessions: max_concurrent_sessions.max(1),
             task_tracker: Arc::new(TaskTracker::new()),
             temperature,
             tool_temperature,
+            reasoning,
+            empty_response,
+            empty_fallback,
+            token_breakdown,
+            tool_loop,
+            tool_summary,
+            tool_presets,
+            delegation_hint_after: delegation.suggest_after_tool_calls,
             max_tokens,
             typing_tx,
+            typing_indicators,
             transcriber,
+            inbound_media,
+            clarification_budget,
+            auto_continue,
             event_matcher,
             event_matcher_last_rebuild: Arc::new(std::sync::atomic::AtomicU64::new(
                 std::time::SystemTime::now()
@@ -495,17 +860,22 @@ impl AgentLoop {
             cognitive_config,
             exfiltration_guard,
             prompt_guard: if prompt_guard_config.enabled {
-                Some(crate::safety::prompt_guard::PromptGuard::new())
+                Some(crate::safety::prompt_guard::PromptGuard::new(
+                    &prompt_guard_config.extra_patterns,
+                ))
             } else {
                 None
             },
             prompt_guard_config,
+            content_filter,
             leak_detector,
             mcp_manager: Arc::new(tokio::sync::Mutex::new(mcp_manager)),
             routing,
             complexity_scorer,
             tool_search_activated,
             pending_buttons,
+            scratchpad,
+            workspace_manager,
             router,
             semantic_top_k,
             semantic_prefilter_k,
@@ -567,7 +937,9 @@ impl AgentLoop {
     }
 
     /// Run the agent loop, processing inbound messages until the channel closes
-    /// or [`stop()`](Self::stop) is called.
+    /// or [`stop()`](Self::stop) is called. Up to `maxConcurrentSessions`
+    /// messages are processed at once; each session still handles its
+    /// messages one at a time.
     ///
     /// **Shutdown:** Calling `stop()` signals the shutdown notify, which wakes the
     /// blocked `recv()` via `tokio::select!`.
@@ -575,6 +947,57 @@ impl AgentLoop {
         *self.running.lock().await = true;
         info!("agent loop started, waiting for messages");
 
+        if let Some(ref gardener) = self.memory_gardener {
+            self.task_tracker
+                .spawn(
+                    "memory_gardener".to_string(),
+                    gardener.clone().spawn_periodic(),
+                )
+                .await;
+        }
+
+        if let Some(ref digest) = self.fact_digest {
+            self.task_tracker
+                .spawn("fact_digest".to_string(), digest.clone().spawn_periodic())
+                .await;
+        }
+
+        if let Some(ref alerts) = self.weather_alerts {
+            self.task_tracker
+                .spawn(
+                    "weather_alerts".to_string(),
+                    alerts.clone().spawn_periodic(),
+                )
+                .await;
+        }
+
+        if let Some(ref offline) = self.offline {
+            self.task_tracker
+                .spawn(
+                    "offline_queue".to_string(),
+                    offline.clone().spawn_periodic(),
+                )
+                .await;
+        }
+
+        // Held until every in-flight turn is done, so stop_with_drain() waits
+        // for all of them. Turns run concurrently on this task, up to
+        // max_concurrent_sessions; process_message() takes the per-session
+        // lock, so messages of one session are still handled in order.
+        let _processing = self.processing_lock.lock().await;
+        let start = |msg: InboundMessage| {
+            // Correlates the turn's log lines per conversation
+            let span = info_span!(
+                "conversation",
+                channel = %msg.channel,
+                chat_id = %msg.chat_id
+            );
+            Box::pin(self.handle_inbound(msg).instrument(span))
+        };
+        let mut in_flight = FuturesUnordered::new();
+        // Messages received while every slot was busy, started in arrival
+        // order as turns finish
+        let mut waiting: VecDeque<InboundMessage> = VecDeque::new();
         loop {
             let running = {
                 let guard = self.running.lock().await;
@@ -584,90 +1007,171 @@ impl AgentLoop {
                 break;
             }
 
-            // Race inbound recv against shutdown signal so stop() wakes the loop.
-            let msg_opt = {
-                let mut rx = self.inbound_rx.lock().await;
-                tokio::select! {
-                    msg = rx.recv() => msg,
-                    () = self.shutdown_notify.notified() => {
-                        info!("agent loop received shutdown signal");
-                        break;
-                    }
-                }
-            };
+            while in_flight.len() < self.max_concurrent_sessions
+                && let Some(msg) = waiting.pop_front()
+            {
+                in_flight.push(start(msg));
+            }
 
-            if let Some(msg) = msg_opt {
-                info!(
-                    "Agent received inbound message: channel={}, sender_id={}, chat_id={}, content_len={}",
-                    msg.channel,
-                    msg.sender_id,
-                    msg.chat_id,
-                    msg.content.len()
-                );
-                // Capture fields before moving msg into process_message
-                let msg_channel = msg.channel.clone();
-                let msg_chat_id = msg.chat_id.clone();
-                let msg_metadata = msg.metadata.clone();
-                match self.process_message(msg).await {
-                    Ok(Some(outbound_msg)) => {
-                        // Send response back through the bus
-                        info!(
-                            "Agent generated outbound message: channel={}, chat_id={}, content_len={}",
-                            outbound_msg.channel,
-                            outbound_msg.chat_id,
-                            outbound_msg.content.len()
-                        );
-                        if let Err(e) = self.bus.publish_outbound(outbound_msg).await {
-                            error!("Failed to send outbound message: {}", e);
-                        } else {
-                            info!("Successfully sent outbound message to bus");
-                        }
-                    }
-                    Ok(None) => {
-                        // No response (e.g., empty after delivery tool)
-                        debug!(
-                            "No outbound message needed (content delivered via tool or suppressed)"
-                        );
-                    }
-                    Err(e) => {
-                        error!("Error processing message: {}", e);
-                        // Surface actionable errors to the user instead of a generic message
-                        let err_str = e.to_string();
-                        let user_message = if err_str.contains("credits")
-                            || err_str.contains("quota")
-                            || err_str.contains("billing")
+            // Race inbound recv against finished turns and the shutdown signal
+            // so stop() wakes the loop. Finished turns are polled first, which
+            // also starts (and queues on its session lock) each newly pushed
+            // turn before the next message is received. The inbound queue is
+            // read even when every slot is busy, since a `/cancel` or approval
+            // click may be what the turn holding the slot waits for.
+            let mut rx = self.inbound_rx.lock().await;
+            tokio::select! {
+                biased;
+                Some(()) = in_flight.next(), if !in_flight.is_empty() => {}
+                () = self.shutdown_notify.notified() => {
+                    info!("agent loop received shutdown signal");
+                    break;
+                }
+                msg = rx.recv() => {
+                    if let Some(msg) = msg {
+                        if in_flight.len() < self.max_concurrent_sessions
+                            || skips_session_lock(&msg)
                         {
-                            format!("Provider billing error: {err_str}")
-                        } else if err_str.contains("rate limit") {
-                            "Rate limited by the LLM provider — please try again in a moment."
-                                .to_string()
-                        } else if err_str.contains("model") && err_str.contains("not found") {
-                            format!("Model configuration error: {err_str}")
+                            in_flight.push(start(msg));
                         } else {
-                            "Sorry, I encountered an error processing your message.".to_string()
-                        };
-                        // Send an error outbound so channels can clean up
-                        // (e.g. Slack removes the thinking emoji on any outbound)
-                        let error_outbound =
-                            OutboundMessage::builder(msg_channel, msg_chat_id, &user_message)
-                                .metadata(msg_metadata)
-                                .build();
-                        if let Err(send_err) = self.bus.publish_outbound(error_outbound).await {
-                            error!("Failed to send error outbound message: {}", send_err);
+                            waiting.push_back(msg);
                         }
+                    } else {
+                        // Channel closed — all senders dropped
+                        info!("Inbound channel closed, stopping agent loop");
+                        break;
                     }
                 }
-            } else {
-                // Channel closed — all senders dropped
-                info!("Inbound channel closed, stopping agent loop");
-                break;
             }
         }
 
+        if !in_flight.is_empty() {
+            info!("waiting for {} in-flight turn(s)", in_flight.len());
+            while in_flight.next().await.is_some() {}
+        }
+        if !waiting.is_empty() {
+            warn!(
+                "dropping {} message(s) that were waiting for a free slot",
+                waiting.len()
+            );
+        }
         info!("Agent loop stopped");
         Ok(())
     }
 
+    /// Process one inbound message and publish the reply, or an error notice
+    /// the user can act on.
+    async fn handle_inbound(&self, msg: InboundMessage) {
+        info!(
+            "Agent received inbound message: channel={}, sender_id={}, chat_id={}, content_len={}, priority={:?}",
+            msg.channel,
+            msg.sender_id,
+            msg.chat_id,
+            msg.content.len(),
+            msg.priority
+        );
+        let replay_id = offline::replay_id(&msg);
+        if let Some(ref offline) = self.offline
+            && replay_id.is_none()
+            && msg.action.is_none()
+            && offline.has_pending(&msg.session_key())
+        {
+            // Keep the chat's order: answer it after the queued messages
+            match offline.enqueue(&msg) {
+                Ok(_) => {
+                    offline.wake();
+                    return;
+                }
+                Err(e) => warn!("message not queued behind offline backlog: {e}"),
+            }
+        }
+        // Capture fields before moving msg into process_message
+        let msg_channel = msg.channel.clone();
+        let msg_chat_id = msg.chat_id.clone();
+        let msg_metadata = msg.metadata.clone();
+        let queued_copy = self.offline.as_ref().map(|_| msg.clone());
+        let result = self.process_message(msg).await;
+        if let (Some(offline), Some(id)) = (&self.offline, replay_id) {
+            if result.as_ref().is_err_and(offline::is_provider_outage) {
+                offline.replay_deferred();
+                return;
+            }
+            offline.replay_done(id);
+        } else if let (Some(offline), Some(queued), Err(e)) = (&self.offline, &queued_copy, &result)
+            && offline::is_provider_outage(e)
+        {
+            match offline.enqueue(queued) {
+                Ok(notice) => {
+                    if let Some(notice) = notice {
+                        let outbound = OutboundMessage::builder(msg_channel, msg_chat_id, notice)
+                            .metadata(msg_metadata)
+                            .build();
+                        if let Err(send_err) = self.bus.publish_outbound(outbound).await {
+                            error!("Failed to send offline notice: {}", send_err);
+                        }
+                    }
+                    return;
+                }
+                Err(queue_err) => warn!("message not queued for offline replay: {queue_err}"),
+            }
+        }
+        match result {
+            Ok(Some(outbound_msg)) => {
+                // Send response back through the bus
+                info!(
+                    "Agent generated outbound message: channel={}, chat_id={}, content_len={}",
+                    outbound_msg.channel,
+                    outbound_msg.chat_id,
+                    outbound_msg.content.len()
+                );
+                if let Err(e) = self.bus.publish_outbound(outbound_msg).await {
+                    error!("Failed to send outbound message: {}", e);
+                } else {
+                    info!("Successfully sent outbound message to bus");
+                }
+            }
+            Ok(None) => {
+                // No response (e.g., empty after delivery tool)
+                debug!("No outbound message needed (content delivered via tool or suppressed)");
+            }
+            Err(e) => {
+                error!("Error processing message: {}", e);
+                // Surface actionable errors to the user instead of a generic message
+                let err_str = e.to_string();
+                let user_message = if let Some(msg) = oxicrab_core::errors::ProviderError::find(&e)
+                    .and_then(oxicrab_core::errors::ProviderError::user_message)
+                {
+                    msg.to_string()
+                } else if err_str.contains("credits")
+                    || err_str.contains("quota")
+                    || err_str.contains("billing")
+                {
+                    format!("Provider billing error: {err_str}")
+                } else if err_str.contains("rate limit") {
+                    "Rate limited by the LLM provider — please try again in a moment.".to_string()
+                } else if err_str.contains("model") && err_str.contains("not found") {
+                    format!("Model configuration error: {err_str}")
+                } else {
+                    "Sorry, I encountered an error processing your message.".to_string()
+                };
+                // Send an error outbound so channels can clean up
+                // (e.g. Slack removes the thinking emoji on any outbound)
+                let error_outbound =
+                    OutboundMessage::builder(msg_channel, msg_chat_id, &user_message)
+                        .metadata(msg_metadata)
+                        .build();
+                if let Err(send_err) = self.bus.publish_outbound(error_outbound).await {
+                    error!("Failed to send error outbound message: {}", send_err);
+                }
+            }
+        }
+    }
+
+    /// Profile owning `session_key` (`channel:chat_id`).
+    fn profile(&self, session_key: &str) -> &Profile {
+        self.profiles.for_session_key(session_key)
+    }
+
     pub fn memory_db(&self) -> Arc<crate::agent::memory::memory_db::MemoryDB> {
         self.memory.db()
     }
@@ -680,25 +1184,50 @@ impl AgentLoop {
         self.approval_store.clone()
     }
 
-    /// Resolve per-task overrides from the model routing configuration.
-    /// Returns default overrides when routing is not configured or the task
-    /// type has no matching rule.
+    /// Resolve per-task overrides from the model routing configuration, plus
+    /// the task's reasoning budget. Returns default overrides when routing is
+    /// not configured or the task type has no matching rule.
     pub fn resolve_overrides(&self, task_type: &str) -> AgentRunOverrides {
-        if let Some(ref routing) = self.routing {
-            let resolved = routing.resolve_overrides(task_type);
-            if resolved.provider.is_some() {
-                return resolved;
-            }
-        }
-        AgentRunOverrides::default()
+        let mut overrides = self
+            .routing
+            .as_ref()
+            .map(|routing| routing.resolve_overrides(task_type))
+            .filter(|resolved| resolved.provider.is_some())
+            .unwrap_or_default();
+        overrides.reasoning_effort = self.reasoning.for_task(task_type);
+        overrides
     }
 
+    /// Stop the loop immediately, cancelling background tasks. An in-flight
+    /// turn is not waited for.
     pub async fn stop(&self) {
+        self.stop_with_drain(std::time::Duration::ZERO).await;
+    }
+
+    /// Stop accepting inbound messages, wait up to `drain_timeout` for the
+    /// in-flight turns to finish (response published, session saved), then
+    /// cancel background tasks.
+    pub async fn stop_with_drain(&self, drain_timeout: std::time::Duration) {
         {
             let mut guard = self.running.lock().await;
             *guard = false;
         }
-        self.shutdown_notify.notify_waiters();
+        // notify_one stores a permit, so the loop still sees the signal if it
+        // is between the running check and the select.
+        self.shutdown_notify.notify_one();
+        if !drain_timeout.is_zero() {
+            if tokio::time::timeout(drain_timeout, self.processing_lock.lock())
+                .await
+                .is_ok()
+            {
+                info!("agent loop drained");
+            } else {
+                warn!(
+                    "agent turn still running after {}s drain timeout, cancelling",
+                    drain_timeout.as_secs()
+                );
+            }
+        }
         self.task_tracker.cancel_all().await;
 
         // Gracefully shut down MCP child processes
@@ -767,8 +1296,21 @@ impl AgentLoop {
         }
 
         let session_key = msg.session_key();
+        // `/cancel` must not queue behind the turn it cancels. That turn
+        // replies once it stops.
+        if msg.action.is_none() && cancel::is_command(&msg.content) {
+            if self.turn_cancels.cancel(&session_key) {
+                info!("cancelling running turn for {}", session_key);
+                return Ok(None);
+            }
+            return Ok(Some(
+                OutboundMessage::from_inbound(msg, cancel::NOTHING_TO_CANCEL_REPLY).build(),
+            ));
+        }
+
         let lock = self.session_lock(&session_key);
         let _guard = lock.lock().await;
+        let _turn = self.turn_cancels.begin(&session_key);
         self.process_message_unlocked(msg).await
     }
 
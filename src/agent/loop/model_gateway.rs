@@ -3,7 +3,8 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use crate::providers::base::{
-    ChatRequest, LLMProvider, LLMResponse, Message, ResponseFormat, RetryConfig, ToolDefinition,
+    ChatRequest, LLMProvider, LLMResponse, Message, ReasoningEffort, ResponseFormat, RetryConfig,
+    StreamSink, ToolDefinition,
 };
 
 /// Provider-facing adapter for chat request/response mapping.
@@ -13,6 +14,7 @@ use crate::providers::base::{
 pub(super) struct ModelGateway;
 
 impl ModelGateway {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn build_turn_request(
         messages: Vec<Message>,
         tools: Arc<Vec<ToolDefinition>>,
@@ -21,6 +23,7 @@ impl ModelGateway {
         temperature: Option<f32>,
         tool_choice: Option<String>,
         response_format: Option<ResponseFormat>,
+        reasoning_effort: Option<ReasoningEffort>,
     ) -> ChatRequest {
         ChatRequest {
             messages,
@@ -30,6 +33,7 @@ impl ModelGateway {
             temperature,
             tool_choice,
             response_format,
+            reasoning_effort,
         }
     }
 
@@ -48,15 +52,24 @@ impl ModelGateway {
         }
     }
 
+    /// Call the provider, retrying as `retry` says. With a `sink`, the reply
+    /// text is pushed to it as it arrives.
     pub(super) async fn invoke(
         provider: &dyn LLMProvider,
         req: ChatRequest,
+        retry: &RetryConfig,
+        sink: Option<StreamSink>,
     ) -> Result<LLMResponse> {
         let model_name = req.model.clone().unwrap_or_default();
         let start = std::time::Instant::now();
-        let result = provider
-            .chat_with_retry(&req, Some(RetryConfig::default()))
-            .await;
+        let result = match sink {
+            Some(sink) => {
+                provider
+                    .chat_stream_with_retry(&req, Some(retry.clone()), sink)
+                    .await
+            }
+            None => provider.chat_with_retry(&req, Some(retry.clone())).await,
+        };
         let duration = start.elapsed().as_secs_f64();
 
         metrics::histogram!("oxicrab_llm_request_duration_seconds",
@@ -65,11 +78,23 @@ impl ModelGateway {
         .record(duration);
 
         match &result {
-            Ok(_) => {
+            Ok(response) => {
                 metrics::counter!("oxicrab_llm_requests_total",
-                    "model" => model_name, "status" => "success"
+                    "model" => model_name.clone(), "status" => "success"
                 )
                 .increment(1);
+                // Token usage per model, for spend dashboards.
+                for (kind, tokens) in [
+                    ("input", response.input_tokens),
+                    ("output", response.output_tokens),
+                ] {
+                    if let Some(tokens) = tokens {
+                        metrics::counter!("oxicrab_llm_tokens_total",
+                            "model" => model_name.clone(), "kind" => kind
+                        )
+                        .increment(tokens);
+                    }
+                }
             }
             Err(_) => {
                 metrics::counter!("oxicrab_llm_requests_total",
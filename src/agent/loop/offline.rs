@@ -0,0 +1,151 @@
+//! Offline mode (`agents.defaults.offlineMode`): messages that fail because the
+//! circuit breaker has taken the provider out of service are kept in the
+//! memory database and replayed, oldest first and one at a time, until the
+//! provider answers again.
+
+use crate::agent::memory::memory_db::MemoryDB;
+use crate::bus::{InboundMessage, MessageBus, MessagePriority, meta};
+use crate::config::OfflineModeConfig;
+use oxicrab_core::errors::ProviderError;
+use serde_json::Value;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::{debug, info, warn};
+
+/// Whether a failed turn means the provider is out of service, as opposed to
+/// a problem with this particular request.
+pub(super) fn is_provider_outage(err: &anyhow::Error) -> bool {
+    matches!(
+        ProviderError::find(err),
+        Some(ProviderError::Unavailable(_))
+    )
+}
+
+/// Offline-queue row a replayed message came from.
+pub(super) fn replay_id(msg: &InboundMessage) -> Option<i64> {
+    msg.metadata
+        .get(meta::OFFLINE_QUEUE_ID)
+        .and_then(Value::as_i64)
+}
+
+pub(super) struct OfflineQueue {
+    config: OfflineModeConfig,
+    db: Arc<MemoryDB>,
+    bus: Arc<MessageBus>,
+    /// Set while a replayed message is being processed.
+    replaying: AtomicBool,
+    wake: Notify,
+}
+
+impl OfflineQueue {
+    pub(super) fn new(config: OfflineModeConfig, db: Arc<MemoryDB>, bus: Arc<MessageBus>) -> Self {
+        Self {
+            config,
+            db,
+            bus,
+            replaying: AtomicBool::new(false),
+            wake: Notify::new(),
+        }
+    }
+
+    /// Whether this chat has messages waiting, so a new one must queue
+    /// behind them to keep the order.
+    pub(super) fn has_pending(&self, session_key: &str) -> bool {
+        self.db
+            .count_offline_messages(Some(session_key))
+            .is_ok_and(|n| n > 0)
+    }
+
+    /// Queue `msg` for a later replay. Returns the notice to send to the chat
+    /// (only for its first queued message), or `Err` when the message was not
+    /// queued and should get the usual error reply.
+    pub(super) fn enqueue(&self, msg: &InboundMessage) -> anyhow::Result<Option<String>> {
+        if msg.action.is_some() {
+            anyhow::bail!("button actions are not queued");
+        }
+        if msg.channel == "http" {
+            // The API caller is waiting on this request and gets the error
+            anyhow::bail!("HTTP API requests are not queued");
+        }
+        if self.db.count_offline_messages(None)? >= self.config.max_queued {
+            anyhow::bail!("offline queue is full ({})", self.config.max_queued);
+        }
+        let session_key = msg.session_key();
+        let first = !self.has_pending(&session_key);
+        let mut stored = msg.clone();
+        stored.metadata.remove(meta::OFFLINE_QUEUE_ID);
+        self.db
+            .enqueue_offline_message(&session_key, &serde_json::to_string(&stored)?)?;
+        info!("provider unavailable, queued message for {session_key}");
+        Ok((first && msg.channel != "system").then(|| self.config.reply.clone()))
+    }
+
+    /// A replayed message was answered (or failed for another reason): drop
+    /// it and move on to the next one right away.
+    pub(super) fn replay_done(&self, id: i64) {
+        if let Err(e) = self.db.delete_offline_message(id) {
+            warn!("failed to remove replayed message {id} from the offline queue: {e}");
+        }
+        self.replaying.store(false, Ordering::Release);
+        self.wake.notify_one();
+    }
+
+    /// A replayed message hit the outage again: keep it for the next retry.
+    pub(super) fn replay_deferred(&self) {
+        self.replaying.store(false, Ordering::Release);
+    }
+
+    /// Retry now instead of at the next interval.
+    pub(super) fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    /// Publish the oldest queued message, unless one is already in flight.
+    async fn replay_next(&self) -> anyhow::Result<()> {
+        if self.replaying.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        let Some(entry) = self.db.oldest_offline_message()? else {
+            return Ok(());
+        };
+        let mut msg: InboundMessage = match serde_json::from_str(&entry.message) {
+            Ok(msg) => msg,
+            Err(e) => {
+                warn!("dropping unreadable offline message {}: {e}", entry.id);
+                self.db.delete_offline_message(entry.id)?;
+                return Ok(());
+            }
+        };
+        msg.metadata
+            .insert(meta::OFFLINE_QUEUE_ID.to_string(), Value::from(entry.id));
+        // Low priority keeps replays out of message batching
+        msg.priority = MessagePriority::Low;
+        debug!(
+            "replaying offline message {} for {} (queued {})",
+            entry.id, entry.session_key, entry.queued_at
+        );
+        self.replaying.store(true, Ordering::Release);
+        if let Err(e) = self.bus.publish_inbound(msg).await {
+            self.replaying.store(false, Ordering::Release);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub(super) fn spawn_periodic(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(self.config.retry_interval_secs.max(1));
+            loop {
+                if let Err(e) = self.replay_next().await {
+                    warn!("offline queue replay failed: {e}");
+                }
+                tokio::select! {
+                    () = tokio::time::sleep(interval) => {}
+                    () = self.wake.notified() => {}
+                }
+            }
+        })
+    }
+}
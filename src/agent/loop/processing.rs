@@ -1,11 +1,16 @@
 use super::AgentLoop;
 use super::config::AgentRunOverrides;
+use super::continuation;
 use super::helpers::{
-    execute_tool_call, load_and_encode_images, strip_audio_tags, strip_document_tags,
-    strip_image_tags, transcribe_audio_tags,
+    ToolAuditContext, cap_attachments, execute_tool_call, load_and_encode_images,
+    save_extracted_facts, strip_audio_tags, strip_document_tags, strip_image_tags,
+    transcribe_audio_tags,
 };
+use super::image_descriptions;
 use crate::agent::tools::base::ExecutionContext;
 use crate::bus::{InboundMessage, OutboundMessage};
+use crate::config::{ContentFilterAction, TypingIndicatorStyle};
+use crate::providers::base::Message;
 use anyhow::Result;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -14,6 +19,11 @@ use uuid::Uuid;
 
 const REQUEST_ID_META_KEY: &str = "request_id";
 const SESSION_KEY_META_KEY: &str = "session_key";
+const SENDER_ID_META_KEY: &str = "sender_id";
+/// Content of the status message used as a typing indicator.
+const TYPING_STATUS_TEXT: &str = "Working on it…";
+/// Session metadata key for the running count of clarification-only replies.
+const CLARIFICATION_STREAK_META_KEY: &str = "clarification_streak";
 
 impl AgentLoop {
     pub(super) async fn process_message_unlocked(
@@ -30,25 +40,57 @@ impl AgentLoop {
         self.handle_event_triggered_jobs(&msg);
 
         let session_key = msg.session_key();
+        let profile = self.profile(&session_key);
         // Load session early — the router needs RouterContext from session metadata
         debug!("Loading session: {}", session_key);
-        let session = self.sessions.get_or_create(&session_key).await?;
+        let session = profile.sessions.get_or_create(&session_key).await?;
+
+        // A yes/no to facts held back for confirmation
+        if let Some(reply) = self
+            .resolve_memory_confirmation(&session_key, &msg.content)
+            .await?
+        {
+            return Ok(Some(OutboundMessage::from_inbound(msg, reply).build()));
+        }
+
+        if msg.action.is_none()
+            && let Some(reply) = self
+                .handle_focus_command(&session_key, &msg.content)
+                .await?
+        {
+            return Ok(Some(OutboundMessage::from_inbound(msg, reply).build()));
+        }
+        let focus = self.session_focus(&session.metadata);
 
         // Load router context and prune expired directives
         let mut router_context =
             crate::router::context::RouterContext::from_session_metadata(&session.metadata);
         router_context.prune_expired(crate::router::now_ms());
 
-        // Compute semantic candidates up front so the router policy layer can
-        // decide between `FullLLM` and `SemanticFilter` directly.
-        let semantic_allowed_tools = self.semantic_filter_tool_subset(&msg.content).await;
-        // Router decides the processing path
-        let decision = self.router.route_with_semantic(
-            &msg.content,
-            &router_context,
-            msg.action.as_ref(),
-            semantic_allowed_tools,
-        );
+        // A "continue" after a capped turn resumes it with the full tool set
+        let auto_continuation = msg
+            .metadata
+            .get(crate::bus::meta::AUTO_CONTINUATION)
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let resumed =
+            continuation::pending_marker(&session.metadata, &msg.content, auto_continuation);
+
+        let decision = if resumed.is_some() && msg.action.is_none() {
+            debug!("resuming capped turn for {}", session_key);
+            crate::router::RoutingDecision::FullLLM
+        } else {
+            // Compute semantic candidates up front so the router policy layer can
+            // decide between `FullLLM` and `SemanticFilter` directly.
+            let semantic_allowed_tools = self.semantic_filter_tool_subset(&msg.content).await;
+            // Router decides the processing path
+            self.router.route_with_semantic(
+                &msg.content,
+                &router_context,
+                msg.action.as_ref(),
+                semantic_allowed_tools,
+            )
+        };
 
         // Capture routing constraints/hints before falling through to the normal pipeline.
         let mut routing_policy: Option<crate::router::RoutingPolicy> = None;
@@ -58,7 +100,7 @@ impl AgentLoop {
                 params,
                 source,
                 directive_index,
-            } => {
+            } if focus.is_none_or(|tools| tools.contains(tool)) => {
                 // Extract compaction_summary from the already-loaded session so
                 // handle_direct_dispatch doesn't need to reload it.
                 let context_summary = session
@@ -99,10 +141,15 @@ impl AgentLoop {
                     policy.reason,
                 ));
             }
-            crate::router::RoutingDecision::FullLLM => {
-                // Unconstrained full turn.
+            crate::router::RoutingDecision::FullLLM
+            | crate::router::RoutingDecision::DirectDispatch { .. } => {
+                // Unconstrained full turn (a dispatch outside the focus
+                // preset becomes a focused LLM turn below).
             }
         }
+        if let Some(preset) = focus {
+            routing_policy = Some(self.focus_policy(preset, routing_policy));
+        }
 
         let request_id = format!("req-{}", Uuid::new_v4());
 
@@ -112,7 +159,7 @@ impl AgentLoop {
             .get("compaction_summary")
             .and_then(|v| v.as_str())
             .map(std::string::ToString::to_string);
-        let exec_ctx = Self::build_execution_context_with_metadata(
+        let mut exec_ctx = Self::build_execution_context_with_metadata(
             &msg.channel,
             &msg.chat_id,
             context_summary,
@@ -120,6 +167,35 @@ impl AgentLoop {
             &request_id,
             &session_key,
         );
+        // Identifies the person writing to set_preference and pairing
+        exec_ctx.metadata.insert(
+            SENDER_ID_META_KEY.to_string(),
+            Value::String(msg.sender_id.clone()),
+        );
+
+        // Transcribe and sanitize inbound content before the LLM sees it.
+        let msg_content = self.prepare_inbound_content(&msg).await;
+
+        // Operator content policy: reject junk before it costs an LLM turn
+        if let Some(ref filter) = self.content_filter
+            && let Some(hit) = filter.check(&msg_content)
+        {
+            warn!(
+                "content filter matched message from {}:{} (rule: {}, action: {})",
+                msg.channel, msg.sender_id, hit.rule, hit.action
+            );
+            metrics::counter!("oxicrab_content_filter_matched_total", "action" => hit.action.to_string())
+                .increment(1);
+            match hit.action {
+                ContentFilterAction::Drop => return Ok(None),
+                ContentFilterAction::Notice => {
+                    return Ok(Some(
+                        OutboundMessage::from_inbound(msg, filter.notice()).build(),
+                    ));
+                }
+                ContentFilterAction::Flag => {}
+            }
+        }
 
         debug!("Getting compacted history");
         let (checkpoint_before, _) = self.session_checkpoint_snapshot(&session_key).await;
@@ -128,9 +204,6 @@ impl AgentLoop {
             .await?;
         debug!("Got {} history messages", history.len());
 
-        // Transcribe and sanitize inbound content before the LLM sees it.
-        let msg_content = self.prepare_inbound_content(&msg).await;
-
         // Prompt injection preflight check
         if matches!(
             check_prompt_guard(
@@ -144,17 +217,26 @@ impl AgentLoop {
             return Ok(Some(OutboundMessage::from_inbound(msg, "I can't process this message as it appears to contain prompt injection patterns.").build()));
         }
 
-        // Load and encode attached images (audio files are skipped).
-        let images = Self::encode_non_audio_media(&msg.media);
+        // Load and encode attached images (audio files are skipped). Attachments
+        // beyond the configured caps are dropped with a notice for the user.
+        let (images, attachment_notice) = self.encode_non_audio_media(&msg.media);
+        // Images seen before are replaced by their cached description
+        let (images, cached_descriptions) = self.apply_image_description_cache(images).await;
 
         // Strip [image: ...] and [document: ...] tags from content when media was
         // successfully encoded, since the LLM receives them as content blocks and
         // doesn't need the file paths (which can cause it to try read_file on binary data).
-        let content = if images.is_empty() {
+        let content = if images.is_empty() && cached_descriptions.is_empty() {
             msg_content
         } else {
             strip_document_tags(&strip_image_tags(&msg_content))
         };
+        let content = if cached_descriptions.is_empty() {
+            content
+        } else {
+            let note = image_descriptions::descriptions_note(&cached_descriptions);
+            format!("{content}\n\n{note}").trim().to_string()
+        };
 
         debug!("Acquiring context lock");
         let is_group = msg
@@ -166,11 +248,11 @@ impl AgentLoop {
         // Refresh provider context (may run external commands with 5s timeout)
         // outside the main lock to avoid blocking other sessions.
         {
-            let mut ctx = self.context.lock().await;
+            let mut ctx = profile.context.lock().await;
             ctx.refresh_provider_context().await;
         }
-        let messages = {
-            let mut ctx = self.context.lock().await;
+        let mut messages = {
+            let mut ctx = profile.context.lock().await;
             ctx.build_messages(
                 &history,
                 &content,
@@ -182,6 +264,9 @@ impl AgentLoop {
                 None,
             )?
         };
+        if let Some(ref marker) = resumed {
+            messages.push(Message::system(marker.resume_prompt()));
+        }
         debug!("Built {} messages, starting agent loop", messages.len());
 
         // Complexity-aware routing: score the message and resolve a model override
@@ -246,8 +331,21 @@ impl AgentLoop {
             },
         };
 
+        // Per-turn reasoning budget requested through the HTTP API
+        overrides.reasoning_effort = msg
+            .metadata
+            .get(crate::bus::meta::REASONING_EFFORT)
+            .and_then(Value::as_str)
+            .and_then(crate::providers::base::ReasoningEffort::parse);
+
         // Apply router-derived strict policy
         overrides.routing_policy = routing_policy;
+        overrides.cancel = self.turn_cancels.flag(&session_key);
+        overrides.prior_clarifications = session
+            .metadata
+            .get(CLARIFICATION_STREAK_META_KEY)
+            .and_then(Value::as_u64)
+            .map_or(0, |n| u32::try_from(n).unwrap_or(u32::MAX));
 
         // Record complexity event off the async runtime (fire-and-forget)
         if let (Some(score), Some(band)) = (&complexity_score, &complexity_band) {
@@ -274,10 +372,31 @@ impl AgentLoop {
             });
         }
 
+        // The HTTP API returns one response; system messages reply elsewhere
+        if self.streaming.enabled
+            && overrides.response_format.is_none()
+            && msg.channel != "http"
+            && msg.channel != "system"
+        {
+            overrides.stream = Some(std::sync::Arc::new(super::streaming::StreamTarget::new(
+                &msg,
+                self.streaming.edit_interval_ms,
+            )));
+        }
+
         let typing_ctx = Some((msg.channel.clone(), msg.chat_id.clone()));
-        let loop_result = self
+        let loop_result = match self
             .run_agent_loop_with_overrides(messages, typing_ctx, &exec_ctx, &overrides)
-            .await?;
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                if let Some(stream) = &overrides.stream {
+                    stream.withdraw(&self.bus).await;
+                }
+                return Err(e);
+            }
+        };
 
         if let Some(policy) = overrides.routing_policy.as_ref() {
             let allowed: std::collections::HashSet<&str> =
@@ -315,7 +434,7 @@ impl AgentLoop {
         let compaction_ran = checkpoint_after.is_some() && checkpoint_after != checkpoint_before;
         let mut session = if compaction_ran {
             debug!("compaction updated session, reloading");
-            self.sessions.get_or_create(&session_key).await?
+            profile.sessions.get_or_create(&session_key).await?
         } else {
             session
         };
@@ -323,6 +442,33 @@ impl AgentLoop {
         // Save router context to session metadata
         router_context.to_session_metadata(&mut session.metadata);
 
+        let continuation_marker = continuation::update_marker(
+            &mut session.metadata,
+            resumed.as_ref(),
+            &content,
+            loop_result.continuation.clone(),
+            auto_continuation,
+        );
+
+        // Track consecutive clarification-only replies for the clarification budget
+        if self.clarification_budget > 0 {
+            let clarified = loop_result.tools_used.is_empty()
+                && loop_result
+                    .content
+                    .as_deref()
+                    .is_some_and(super::hallucination::is_clarification)
+                && super::hallucination::is_action_request(&content);
+            let streak = if clarified {
+                overrides.prior_clarifications.saturating_add(1)
+            } else {
+                0
+            };
+            session.metadata.insert(
+                CLARIFICATION_STREAK_META_KEY.to_string(),
+                Value::from(streak),
+            );
+        }
+
         let mut extra = HashMap::new();
         extra.insert(
             "router_decision".to_string(),
@@ -384,36 +530,93 @@ impl AgentLoop {
                 Value::Number(serde_json::Number::from(tokens)),
             );
         }
-        self.sessions.save(&session).await?;
+        profile.sessions.save(&session).await?;
+
+        if let Some(marker) = continuation_marker
+            && marker.auto_runs < self.auto_continue
+        {
+            self.publish_auto_continuation(&msg, marker.auto_runs).await;
+        }
 
-        // Background fact extraction
+        // Background fact extraction, throttled per session
         if let (Some(compactor), Some(assistant_content)) = (&self.compactor, &loop_result.content)
             && self.compaction_config.extraction_enabled
             && msg.channel != "system"
+            && let Some(exchanges) = self.extraction_throttle.record(
+                &session_key,
+                content.clone(),
+                assistant_content.clone(),
+            )
         {
             let compactor = compactor.clone();
-            let memory = self.memory.clone();
-            let user_msg = content.clone();
-            let assistant_msg = assistant_content.clone();
+            let memory = profile.memory.clone();
             let task_tracker = self.task_tracker.clone();
+            // With the fact digest on, facts are stored one per entry and
+            // queued for the chat they came from. The digest only reads the
+            // default profile's database.
+            let digest_target = (self.fact_digest.is_some()
+                && profile.name == crate::agent::profile::DEFAULT_PROFILE)
+                .then(|| (msg.channel.clone(), msg.chat_id.clone()));
+            // Facts in these categories are proposed to the chat instead of written
+            let confirm = self.memory_confirm.clone();
+            let pending_memory = self.pending_memory.clone();
+            let bus = self.bus.clone();
+            let (confirm_channel, confirm_chat_id) = (msg.channel.clone(), msg.chat_id.clone());
+            let confirm_session = session_key.clone();
             let task_name = format!("fact_extraction_{}", chrono::Utc::now().timestamp());
             // Use spawn_auto_cleanup since this is a one-off task that should remove itself
             task_tracker
                 .spawn_auto_cleanup(task_name, async move {
                     let existing = memory.read_today_section("Facts").unwrap_or_default();
                     match compactor
-                        .extract_facts(&user_msg, &assistant_msg, &existing)
+                        .extract_facts(&exchanges, &existing)
                         .await
                     {
                         Ok(facts) => {
                             if !facts.is_empty() {
-                                let filtered =
-                                    crate::agent::memory::quality::filter_lines(&facts);
+                                let (filtered, held) =
+                                    crate::agent::memory::quality::filter_lines_with(
+                                        &facts, &confirm,
+                                    );
+                                if !held.is_empty() {
+                                    let held: Vec<_> = held
+                                        .into_iter()
+                                        .map(|(content, category)| {
+                                            super::memory_confirm::PendingFact {
+                                                content,
+                                                category,
+                                                target: super::memory_confirm::FactTarget::Facts(
+                                                    digest_target.clone(),
+                                                ),
+                                            }
+                                        })
+                                        .collect();
+                                    metrics::counter!(
+                                        "oxicrab_memory_remember_write_total",
+                                        "path" => "llm",
+                                        "outcome" => "pending_confirmation"
+                                    )
+                                    .increment(held.len() as u64);
+                                    let prompt =
+                                        super::memory_confirm::confirmation_prompt(&held);
+                                    pending_memory.add(&confirm_session, held);
+                                    let outbound = OutboundMessage::builder(
+                                        confirm_channel,
+                                        confirm_chat_id,
+                                        prompt,
+                                    )
+                                    .build();
+                                    if let Err(e) = bus.publish_outbound(outbound).await {
+                                        warn!("failed to send memory confirmation prompt: {}", e);
+                                    }
+                                }
                                 if filtered.trim().is_empty() {
                                     debug!("fact extraction: all lines filtered by quality gates");
-                                } else if let Err(e) =
-                                    memory.append_to_section("Facts", &filtered)
-                                {
+                                } else if let Err(e) = save_extracted_facts(
+                                    &memory,
+                                    &filtered,
+                                    digest_target.as_ref(),
+                                ) {
                                     metrics::counter!(
                                         "oxicrab_memory_remember_write_total",
                                         "path" => "llm",
@@ -448,37 +651,98 @@ impl AgentLoop {
             // Suppress sending if the LLM returned a [SILENT] response
             if content.starts_with("[SILENT]") {
                 debug!("Suppressing silent response");
+                if let Some(stream) = &overrides.stream {
+                    stream.withdraw(&self.bus).await;
+                }
                 return Ok(None);
             }
-            Ok(Some(
-                OutboundMessage::from_inbound(msg, content)
-                    .media(loop_result.media)
-                    .merge_metadata(loop_result.response_metadata)
-                    .build(),
-            ))
+            let content = match attachment_notice {
+                Some(notice) => format!("{notice}\n\n{content}"),
+                None => content,
+            };
+            let mut outbound = OutboundMessage::from_inbound(msg, content)
+                .media(loop_result.media)
+                .merge_metadata(loop_result.response_metadata);
+            // Replaces the streamed text in the chat
+            if let Some(stream) = &overrides.stream {
+                outbound = outbound.meta(crate::bus::meta::STREAM_ID, stream.stream_id());
+            }
+            Ok(Some(outbound.build()))
         } else {
             warn!(
                 "agent loop produced no response for {}:{}",
                 msg.channel, msg.chat_id
             );
-            Ok(Some(
-                OutboundMessage::from_inbound(
-                    msg,
-                    "I wasn't able to generate a response. Please try again.",
-                )
-                .build(),
-            ))
+            let mut outbound = OutboundMessage::from_inbound(
+                msg,
+                "I wasn't able to generate a response. Please try again.",
+            );
+            if let Some(stream) = &overrides.stream {
+                outbound = outbound.meta(crate::bus::meta::STREAM_ID, stream.stream_id());
+            }
+            Ok(Some(outbound.build()))
         }
     }
 
-    async fn send_typing_indicator(&self, msg: &InboundMessage) {
-        if let Some(ref tx) = self.typing_tx
-            && tx
-                .send((msg.channel.clone(), msg.chat_id.clone()))
-                .await
-                .is_err()
-        {
-            debug!("typing indicator channel closed");
+    /// Queue a synthetic "continue" from the same sender so a capped turn
+    /// resumes without waiting for the user.
+    async fn publish_auto_continuation(&self, msg: &InboundMessage, auto_runs: u32) {
+        info!(
+            "auto-continuing capped turn for {}:{} ({}/{})",
+            msg.channel,
+            msg.chat_id,
+            auto_runs + 1,
+            self.auto_continue
+        );
+        let next = InboundMessage::builder(
+            msg.channel.clone(),
+            msg.sender_id.clone(),
+            msg.chat_id.clone(),
+            "continue",
+        )
+        .metadata(msg.metadata.clone())
+        .meta(crate::bus::meta::AUTO_CONTINUATION, Value::Bool(true))
+        .priority(crate::bus::MessagePriority::Low)
+        .build();
+        if let Err(e) = self.bus.publish_inbound(next).await {
+            warn!("failed to queue auto-continuation: {}", e);
+        }
+    }
+
+    /// Typing indicator settings of `channel`; channels without settings use
+    /// the native default.
+    pub(super) fn typing_indicator(&self, channel: &str) -> crate::config::TypingIndicatorConfig {
+        self.typing_indicators
+            .get(channel)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub(super) async fn send_typing_indicator(&self, msg: &InboundMessage) {
+        let indicator = self.typing_indicator(&msg.channel);
+        if !indicator.enabled {
+            return;
+        }
+        match indicator.style {
+            TypingIndicatorStyle::Native => {
+                if let Some(ref tx) = self.typing_tx
+                    && tx
+                        .send((msg.channel.clone(), msg.chat_id.clone()))
+                        .await
+                        .is_err()
+                {
+                    debug!("typing indicator channel closed");
+                }
+            }
+            TypingIndicatorStyle::Status => {
+                // Edited by later status lines and deleted when the reply is sent
+                let status = OutboundMessage::from_inbound(msg.clone(), TYPING_STATUS_TEXT)
+                    .meta(crate::bus::meta::STATUS, Value::Bool(true))
+                    .build();
+                if let Err(e) = self.bus.publish_outbound(status).await {
+                    debug!("typing status message not sent: {}", e);
+                }
+            }
         }
     }
 
@@ -565,7 +829,10 @@ impl AgentLoop {
         self.leak_detector.redact(&content)
     }
 
-    fn encode_non_audio_media(media: &[String]) -> Vec<crate::providers::base::ImageData> {
+    fn encode_non_audio_media(
+        &self,
+        media: &[String],
+    ) -> (Vec<crate::providers::base::ImageData>, Option<String>) {
         let audio_extensions = ["ogg", "mp3", "mp4", "m4a", "wav", "webm", "flac", "oga"];
         let image_media: Vec<String> = media
             .iter()
@@ -580,17 +847,71 @@ impl AgentLoop {
             .collect();
 
         if image_media.is_empty() {
-            return vec![];
+            return (vec![], None);
         }
+        let (image_media, notice) = cap_attachments(
+            &image_media,
+            self.inbound_media.max_images,
+            self.inbound_media.max_documents,
+        );
 
         info!(
             "Loading {} media files for LLM: {:?}",
             image_media.len(),
             image_media
         );
-        let images = load_and_encode_images(&image_media);
+        let images = load_and_encode_images(&image_media, &self.inbound_media);
         info!("Encoded {} images for LLM", images.len());
-        images
+        (images, notice)
+    }
+
+    /// With `inboundMedia.cacheDescriptions` on, swap images that have a
+    /// cached description for that text, and describe new images in the
+    /// background so later turns can do the same.
+    async fn apply_image_description_cache(
+        &self,
+        images: Vec<crate::providers::base::ImageData>,
+    ) -> (Vec<crate::providers::base::ImageData>, Vec<String>) {
+        if !self.inbound_media.cache_descriptions || images.is_empty() {
+            return (images, Vec::new());
+        }
+        let ttl_days = self.inbound_media.description_ttl_days;
+        let db = self.memory.db();
+        let lookup = image_descriptions::lookup(&db, images, ttl_days);
+        if !lookup.descriptions.is_empty() {
+            info!(
+                "using {} cached image description(s) instead of re-sending images",
+                lookup.descriptions.len()
+            );
+        }
+        let model = self
+            .inbound_media
+            .description_model
+            .clone()
+            .unwrap_or_else(|| self.model.clone());
+        for (hash, image) in lookup.uncached {
+            let provider = self.provider.clone();
+            let model = model.clone();
+            let db = db.clone();
+            let task_name = format!("image_description_{}", &hash[..12]);
+            self.task_tracker
+                .spawn_auto_cleanup(task_name, async move {
+                    if let Err(e) = image_descriptions::describe_and_cache(
+                        provider.as_ref(),
+                        model,
+                        &db,
+                        &hash,
+                        image,
+                        ttl_days,
+                    )
+                    .await
+                    {
+                        warn!("failed to describe image for cache: {}", e);
+                    }
+                })
+                .await;
+        }
+        (lookup.send, lookup.descriptions)
     }
 
     async fn process_system_message(&self, msg: InboundMessage) -> Result<Option<OutboundMessage>> {
@@ -623,6 +944,7 @@ impl AgentLoop {
         };
 
         let session_key = format!("{origin_channel}:{origin_chat_id}");
+        let profile = self.profile(&session_key);
         // Lock the target session to prevent concurrent modification.
         // process_message() locks on msg.session_key() which is "system:{chat_id}",
         // but we modify the origin session "{origin_channel}:{origin_chat_id}".
@@ -636,7 +958,7 @@ impl AgentLoop {
             tokio::time::sleep(std::time::Duration::from_millis(50)).await;
             target_lock.lock().await
         };
-        let session = self.sessions.get_or_create(&session_key).await?;
+        let session = profile.sessions.get_or_create(&session_key).await?;
         let request_id = format!("req-{}", Uuid::new_v4());
 
         let history = self
@@ -645,11 +967,11 @@ impl AgentLoop {
 
         // Refresh provider context outside the main lock to avoid blocking other sessions
         {
-            let mut context = self.context.lock().await;
+            let mut context = profile.context.lock().await;
             context.refresh_provider_context().await;
         }
         let messages = {
-            let mut context = self.context.lock().await;
+            let mut context = profile.context.lock().await;
             context.build_messages(
                 &history,
                 &msg_content,
@@ -688,7 +1010,7 @@ impl AgentLoop {
             .content
             .unwrap_or_else(|| "Background task completed.".to_string());
 
-        let mut session = self.sessions.get_or_create(&session_key).await?;
+        let mut session = profile.sessions.get_or_create(&session_key).await?;
         let extra = HashMap::new();
         session.add_message(
             "user".to_string(),
@@ -700,7 +1022,7 @@ impl AgentLoop {
             final_content.clone(),
             assistant_extra,
         );
-        self.sessions.save(&session).await?;
+        profile.sessions.save(&session).await?;
 
         Ok(Some(
             OutboundMessage::builder(
@@ -718,16 +1040,37 @@ impl AgentLoop {
     /// Attempt to persist a "remember that..." message directly to memory,
     /// bypassing the LLM. Returns `Ok(Some(response))` if handled, `Ok(None)` if
     /// the caller should fall through to normal LLM processing.
-    async fn try_remember_fast_path(
+    pub(super) async fn try_remember_fast_path(
         &self,
         content: &str,
         session_key: &str,
     ) -> Result<Option<String>> {
-        use crate::agent::memory::quality::{QualityVerdict, check_quality};
+        use crate::agent::memory::quality::{QualityVerdict, check_quality_with, score_importance};
         use crate::agent::memory::remember::is_duplicate_of_entries;
 
+        let profile = self.profile(session_key);
         // Quality gate: reject low-signal content
-        let response = match check_quality(content) {
+        let response = match check_quality_with(content, &self.memory_confirm) {
+            QualityVerdict::NeedsConfirmation { content, category } => {
+                metrics::counter!(
+                    "oxicrab_memory_remember_write_total",
+                    "path" => "fast",
+                    "outcome" => "pending_confirmation"
+                )
+                .increment(1);
+                info!(
+                    "remember fast path: {} fact held for confirmation",
+                    category
+                );
+                let facts = vec![super::memory_confirm::PendingFact {
+                    content,
+                    category,
+                    target: super::memory_confirm::FactTarget::Daily,
+                }];
+                let prompt = super::memory_confirm::confirmation_prompt(&facts);
+                self.pending_memory.add(session_key, facts);
+                prompt
+            }
             QualityVerdict::Reject(reason) => {
                 metrics::counter!(
                     "oxicrab_memory_remember_write_total",
@@ -740,7 +1083,10 @@ impl AgentLoop {
                     .to_string()
             }
             QualityVerdict::Reframed(reframed) => {
-                let recent = self.memory.get_recent_daily_entries(50).unwrap_or_default();
+                let recent = profile
+                    .memory
+                    .get_recent_daily_entries(50)
+                    .unwrap_or_default();
                 if is_duplicate_of_entries(&reframed, &recent) {
                     metrics::counter!(
                         "oxicrab_memory_remember_write_total",
@@ -750,7 +1096,7 @@ impl AgentLoop {
                     .increment(1);
                     info!("remember fast path: duplicate detected, skipping write");
                     "I already have that noted.".to_string()
-                } else if self.memory.is_semantically_duplicate(&reframed, 0.85) {
+                } else if profile.memory.is_semantically_duplicate(&reframed, 0.85) {
                     metrics::counter!(
                         "oxicrab_memory_remember_write_total",
                         "path" => "fast",
@@ -761,7 +1107,7 @@ impl AgentLoop {
                     info!("remember fast path: duplicate detected, skipping write");
                     "I already have that noted.".to_string()
                 } else {
-                    self.memory.append_today(&reframed)?;
+                    profile.memory.append_today(&reframed)?;
                     metrics::counter!(
                         "oxicrab_memory_remember_write_total",
                         "path" => "fast",
@@ -769,14 +1115,18 @@ impl AgentLoop {
                     )
                     .increment(1);
                     info!(
-                        "remember fast path: wrote {} chars to daily notes (reframed)",
-                        reframed.len()
+                        "remember fast path: wrote {} chars to daily notes (reframed, importance {})",
+                        reframed.len(),
+                        score_importance(&reframed)
                     );
                     format!("Noted (reframed for accuracy): {reframed}")
                 }
             }
             QualityVerdict::Pass => {
-                let recent = self.memory.get_recent_daily_entries(50).unwrap_or_default();
+                let recent = profile
+                    .memory
+                    .get_recent_daily_entries(50)
+                    .unwrap_or_default();
                 if is_duplicate_of_entries(content, &recent) {
                     metrics::counter!(
                         "oxicrab_memory_remember_write_total",
@@ -786,7 +1136,7 @@ impl AgentLoop {
                     .increment(1);
                     info!("remember fast path: duplicate detected, skipping write");
                     "I already have that noted.".to_string()
-                } else if self.memory.is_semantically_duplicate(content, 0.85) {
+                } else if profile.memory.is_semantically_duplicate(content, 0.85) {
                     metrics::counter!(
                         "oxicrab_memory_remember_write_total",
                         "path" => "fast",
@@ -797,7 +1147,7 @@ impl AgentLoop {
                     info!("remember fast path: duplicate detected, skipping write");
                     "I already have that noted.".to_string()
                 } else {
-                    self.memory.append_today(content)?;
+                    profile.memory.append_today(content)?;
                     metrics::counter!(
                         "oxicrab_memory_remember_write_total",
                         "path" => "fast",
@@ -805,8 +1155,9 @@ impl AgentLoop {
                     )
                     .increment(1);
                     info!(
-                        "remember fast path: wrote {} chars to daily notes",
-                        content.len()
+                        "remember fast path: wrote {} chars to daily notes (importance {})",
+                        content.len(),
+                        score_importance(content)
                     );
                     format!("Noted! I'll remember: {content}")
                 }
@@ -814,7 +1165,7 @@ impl AgentLoop {
         };
 
         // Single session load + save for all branches
-        let mut session = self.sessions.get_or_create(session_key).await?;
+        let mut session = profile.sessions.get_or_create(session_key).await?;
         let extra = HashMap::new();
         session.add_message(
             "user".to_string(),
@@ -822,11 +1173,70 @@ impl AgentLoop {
             extra.clone(),
         );
         session.add_message("assistant".to_string(), response.clone(), extra);
-        self.sessions.save(&session).await?;
+        profile.sessions.save(&session).await?;
 
         Ok(Some(response))
     }
 
+    /// Delete the stored memory that contains every word of a "forget that..."
+    /// message, bypassing the LLM. A near match is only offered back for the
+    /// user to confirm. Only daily notes are searched; knowledge entries are
+    /// never removed this way.
+    pub(super) async fn try_forget_fast_path(
+        &self,
+        content: &str,
+        session_key: &str,
+    ) -> Result<String> {
+        let profile = self.profile(session_key);
+        let response = if let Some(entry) = profile.memory.forget_matching(content)? {
+            metrics::counter!(
+                "oxicrab_memory_forget_total",
+                "path" => "fast",
+                "outcome" => "deleted"
+            )
+            .increment(1);
+            info!(
+                "forget fast path: deleted {} chars from memory",
+                entry.len()
+            );
+            format!("Forgotten: {entry}")
+        } else if let Some((_, candidate)) = profile.memory.closest_forget_candidate(content)? {
+            metrics::counter!(
+                "oxicrab_memory_forget_total",
+                "path" => "fast",
+                "outcome" => "unconfirmed"
+            )
+            .increment(1);
+            info!("forget fast path: near match only, asking for confirmation");
+            let candidate = candidate.trim();
+            let candidate = candidate.strip_prefix("- ").unwrap_or(candidate);
+            format!(
+                "I didn't find an exact match, so nothing was deleted. The closest saved memory is: {candidate}\nSay \"forget that {candidate}\" to remove it."
+            )
+        } else {
+            metrics::counter!(
+                "oxicrab_memory_forget_total",
+                "path" => "fast",
+                "outcome" => "not_found"
+            )
+            .increment(1);
+            info!("forget fast path: no matching memory");
+            "I couldn't find a saved memory matching that.".to_string()
+        };
+
+        let mut session = profile.sessions.get_or_create(session_key).await?;
+        let extra = HashMap::new();
+        session.add_message(
+            "user".to_string(),
+            format!("forget that {content}"),
+            extra.clone(),
+        );
+        session.add_message("assistant".to_string(), response.clone(), extra);
+        profile.sessions.save(&session).await?;
+
+        Ok(response)
+    }
+
     /// Run `get_compacted_history` with timing instrumentation.
     /// Logs a warning when compaction takes more than 2 seconds.
     async fn get_compacted_history_timed(
@@ -870,7 +1280,7 @@ impl AgentLoop {
         }
     }
 
-    fn policy_from_allowlist(
+    pub(super) fn policy_from_allowlist(
         &self,
         mut allowed_tools: Vec<String>,
         context_hint: Option<String>,
@@ -1098,6 +1508,24 @@ impl AgentLoop {
                 OutboundMessage::from_inbound(msg.clone(), response).build(),
             ));
         }
+        if tool == "_forget" {
+            let forget_content =
+                crate::agent::memory::remember::extract_forget_content(&msg_content)
+                    .unwrap_or_else(|| msg_content.clone());
+            let response = match self
+                .try_forget_fast_path(&forget_content, session_key)
+                .await
+            {
+                Ok(text) => text,
+                Err(e) => {
+                    warn!("forget fast path failed: {e}");
+                    "I wasn't able to forget that. Please try again.".to_string()
+                }
+            };
+            return Ok(Some(
+                OutboundMessage::from_inbound(msg.clone(), response).build(),
+            ));
+        }
         if tool == "_router_replay" {
             let index = params.get("index").and_then(serde_json::Value::as_i64);
             let response = self.render_router_replay(session_key, index).await?;
@@ -1163,6 +1591,10 @@ impl AgentLoop {
             None,
             Some(self.workspace.as_path()),
             None, // direct dispatch: skip interactive approval
+            Some(ToolAuditContext {
+                db: &self.memory.db(),
+                leak_detector: &self.leak_detector,
+            }),
         )
         .await;
 
@@ -1192,7 +1624,8 @@ impl AgentLoop {
         }
 
         // Save router context and session history
-        let mut session = self.sessions.get_or_create(session_key).await?;
+        let profile = self.profile(session_key);
+        let mut session = profile.sessions.get_or_create(session_key).await?;
         router_context.to_session_metadata(&mut session.metadata);
         session.add_message(
             "user",
@@ -1200,7 +1633,7 @@ impl AgentLoop {
             HashMap::new(),
         );
         session.add_message("assistant", &result_content, HashMap::new());
-        if let Err(e) = self.sessions.save(&session).await {
+        if let Err(e) = profile.sessions.save(&session).await {
             warn!("failed to save session after direct dispatch: {e}");
         }
 
@@ -1248,12 +1681,7 @@ impl AgentLoop {
         let mut builder =
             OutboundMessage::builder(msg.channel.clone(), msg.chat_id.clone(), final_content)
                 .metadata(metadata);
-        let reply_to = msg
-            .metadata
-            .get(crate::bus::meta::TS)
-            .and_then(|v| v.as_str())
-            .filter(|s| !s.is_empty());
-        if let Some(id) = reply_to {
+        if let Some(id) = msg.message_id() {
             builder = builder.reply_to(id);
         }
         Ok(Some(builder.build()))
@@ -1440,6 +1868,7 @@ impl AgentLoop {
         let lock_key = session_key.to_string();
         let lock = self.session_lock(&lock_key);
         let _guard = lock.lock().await;
+        let profile = self.profile(session_key);
 
         // Inbound secret scanning for direct calls (cron, subagents)
         let redacted_content: Option<String> = {
@@ -1473,6 +1902,15 @@ impl AgentLoop {
             });
         }
 
+        if overrides.action.is_none()
+            && let Some(reply) = self.handle_focus_command(session_key, content).await?
+        {
+            return Ok(super::config::DirectResult {
+                content: reply,
+                metadata: HashMap::new(),
+            });
+        }
+
         // Short-circuit for action dispatch (button/webhook/cron with explicit tool call)
         if let Some(ref dispatch) = overrides.action {
             info!(
@@ -1531,7 +1969,7 @@ impl AgentLoop {
                 .unwrap_or_else(|| format!("req-{}", Uuid::new_v4()));
             // Extract context_summary from session so tools have compaction
             // context, matching handle_direct_dispatch behavior.
-            let session = self.sessions.get_or_create(session_key).await?;
+            let session = profile.sessions.get_or_create(session_key).await?;
             let context_summary = session
                 .metadata
                 .get("compaction_summary")
@@ -1555,6 +1993,10 @@ impl AgentLoop {
                 None,
                 Some(self.workspace.as_path()),
                 None, // process_direct_with_overrides: skip interactive approval
+                Some(ToolAuditContext {
+                    db: &self.memory.db(),
+                    leak_detector: &self.leak_detector,
+                }),
             )
             .await;
             // Secret-scan tool result output
@@ -1567,7 +2009,7 @@ impl AgentLoop {
             }
 
             // Save session history
-            let mut session = self.sessions.get_or_create(session_key).await?;
+            let mut session = profile.sessions.get_or_create(session_key).await?;
             session.add_message(
                 "user",
                 format!(
@@ -1578,7 +2020,7 @@ impl AgentLoop {
                 HashMap::new(),
             );
             session.add_message("assistant", &result_content, HashMap::new());
-            if let Err(e) = self.sessions.save(&session).await {
+            if let Err(e) = profile.sessions.save(&session).await {
                 warn!("failed to save session after direct dispatch: {e}");
             }
 
@@ -1622,18 +2064,18 @@ impl AgentLoop {
             });
         }
 
-        let session = self.sessions.get_or_create(session_key).await?;
+        let session = profile.sessions.get_or_create(session_key).await?;
         let history = self
             .get_compacted_history_timed(&session, session_key)
             .await?;
 
         // Refresh provider context outside the main lock to avoid blocking other sessions
         {
-            let mut ctx = self.context.lock().await;
+            let mut ctx = profile.context.lock().await;
             ctx.refresh_provider_context().await;
         }
-        let messages = {
-            let mut ctx = self.context.lock().await;
+        let mut messages = {
+            let mut ctx = profile.context.lock().await;
             ctx.build_messages(
                 &history,
                 content,
@@ -1645,11 +2087,15 @@ impl AgentLoop {
                 None,  // no entity context for direct processing
             )?
         };
+        let resumed = continuation::pending_marker(&session.metadata, content, false);
+        if let Some(ref marker) = resumed {
+            messages.push(Message::system(marker.resume_prompt()));
+        }
 
         let request_id = format!("req-{}", Uuid::new_v4());
         let typing_ctx = Some((channel.to_string(), chat_id.to_string()));
 
-        let effective_overrides = if overrides.request_id.is_some() {
+        let mut effective_overrides = if overrides.request_id.is_some() {
             overrides.clone()
         } else {
             AgentRunOverrides {
@@ -1657,6 +2103,10 @@ impl AgentLoop {
                 ..overrides.clone()
             }
         };
+        if let Some(preset) = self.session_focus(&session.metadata) {
+            effective_overrides.routing_policy =
+                Some(self.focus_policy(preset, effective_overrides.routing_policy.take()));
+        }
         let effective_request_id = effective_overrides
             .request_id
             .as_deref()
@@ -1683,11 +2133,18 @@ impl AgentLoop {
             .content
             .unwrap_or_else(|| "No response generated.".to_string());
 
-        let mut session = self.sessions.get_or_create(session_key).await?;
+        let mut session = profile.sessions.get_or_create(session_key).await?;
+        continuation::update_marker(
+            &mut session.metadata,
+            resumed.as_ref(),
+            content,
+            loop_result.continuation,
+            false,
+        );
         let extra = HashMap::new();
         session.add_message("user".to_string(), content.to_string(), extra.clone());
         session.add_message("assistant".to_string(), response.clone(), assistant_extra);
-        self.sessions.save(&session).await?;
+        profile.sessions.save(&session).await?;
 
         Ok(super::config::DirectResult {
             content: response,
@@ -39,7 +39,11 @@ impl AgentLoop {
         session_key: &str,
         index: Option<i64>,
     ) -> Result<String> {
-        let session = self.sessions.get_or_create(session_key).await?;
+        let session = self
+            .profile(session_key)
+            .sessions
+            .get_or_create(session_key)
+            .await?;
         let entries: Vec<(usize, &crate::session::manager::MessageData, &Value)> = session
             .messages
             .iter()
@@ -0,0 +1,103 @@
+//! Idle-session archival: sessions untouched for `sessionArchiveDays` are
+//! compacted to a summary, the summary is saved to memory, and the message
+//! history is dropped. `get_compacted_history` rehydrates archived sessions
+//! by prepending the summary.
+
+use super::compaction_history::strip_annotations;
+use crate::agent::compaction::MessageCompactor;
+use crate::agent::memory::MemoryStore;
+use crate::session::SessionStore;
+use anyhow::Result;
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+/// Session metadata key set when a session is archived (RFC 3339 timestamp).
+pub(super) const ARCHIVED_AT: &str = "archived_at";
+
+/// Memory section archived-session summaries are written under.
+const ARCHIVE_SECTION: &str = "Archived conversations";
+
+/// Archive every session idle for more than `idle_days`. Returns the number
+/// archived. Failures are logged per session and do not stop the run.
+pub(super) async fn archive_idle_sessions(
+    sessions: &dyn SessionStore,
+    compactor: &MessageCompactor,
+    memory: &MemoryStore,
+    idle_days: u32,
+) -> Result<usize> {
+    let db = memory.db();
+    let keys = tokio::task::spawn_blocking(move || db.idle_session_keys(idle_days))
+        .await
+        .map_err(|e| anyhow::anyhow!("idle session scan failed: {e}"))??;
+
+    let mut archived = 0;
+    for key in keys {
+        match archive_session(sessions, compactor, memory, &key).await {
+            Ok(true) => archived += 1,
+            Ok(false) => {}
+            Err(e) => warn!("failed to archive session {}: {}", key, e),
+        }
+    }
+    if archived > 0 {
+        info!("session archival: archived {} idle session(s)", archived);
+        metrics::counter!("oxicrab_sessions_archived_total").increment(archived as u64);
+    }
+    Ok(archived)
+}
+
+async fn archive_session(
+    sessions: &dyn SessionStore,
+    compactor: &MessageCompactor,
+    memory: &MemoryStore,
+    key: &str,
+) -> Result<bool> {
+    let session = sessions.get_or_create(key).await?;
+    // Already archived and not used since
+    if session.messages.is_empty() {
+        return Ok(false);
+    }
+
+    let previous_summary = session
+        .metadata
+        .get("compaction_summary")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let summary = compactor
+        .compact(
+            &session.get_full_history(),
+            strip_annotations(previous_summary),
+        )
+        .await?;
+    if summary.trim().is_empty() {
+        return Ok(false);
+    }
+
+    // The user may have come back while the summary was being written
+    let mut latest = sessions.get_or_create(key).await?;
+    if latest.messages.len() != session.messages.len() || latest.updated_at != session.updated_at {
+        debug!("session {} became active during archival, skipping", key);
+        return Ok(false);
+    }
+
+    if let Err(e) = memory.append_to_section(ARCHIVE_SECTION, &format!("[{key}] {summary}")) {
+        warn!(
+            "failed to write archive summary for {} to memory: {}",
+            key, e
+        );
+    }
+
+    latest.messages.clear();
+    latest
+        .metadata
+        .insert("compaction_summary".to_string(), Value::String(summary));
+    latest.metadata.insert(
+        ARCHIVED_AT.to_string(),
+        Value::String(chrono::Utc::now().to_rfc3339()),
+    );
+    // Counters describing the dropped history would misfire compaction
+    latest.metadata.remove("pre_flush_msg_count");
+    latest.metadata.remove(crate::bus::meta::LAST_INPUT_TOKENS);
+    sessions.save(&latest).await?;
+    debug!("session {} archived", key);
+    Ok(true)
+}
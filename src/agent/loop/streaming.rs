@@ -0,0 +1,168 @@
+//! Streamed replies (`agents.defaults.streaming`): while the model writes,
+//! the text so far is published as partial outbound messages, which the
+//! gateway shows by sending one message and editing it on channels that
+//! support it. The finished reply carries the same stream ID and replaces it.
+
+use crate::bus::{InboundMessage, MessageBus, OutboundMessage, meta};
+use oxicrab_core::channels::base::{REACT_MARKER_PREFIX, split_react_marker};
+use serde_json::Value;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::debug;
+
+/// Prefix of a reply that is not sent at all; held back while it may still
+/// turn into one.
+const SILENT_PREFIX: &str = "[SILENT]";
+
+/// Where the partial text of one turn goes.
+pub struct StreamTarget {
+    /// Partial message with empty content, addressed like the final reply.
+    template: OutboundMessage,
+    edit_interval: Duration,
+    /// Whether the chat currently shows partial text.
+    shown: AtomicBool,
+}
+
+impl StreamTarget {
+    pub(super) fn new(msg: &InboundMessage, edit_interval_ms: u64) -> Self {
+        let template = OutboundMessage::from_inbound(msg.clone(), "")
+            .meta(
+                meta::STREAM_ID,
+                Value::String(uuid::Uuid::new_v4().to_string()),
+            )
+            .meta(meta::STREAM_PARTIAL, Value::Bool(true))
+            .build();
+        Self {
+            template,
+            edit_interval: Duration::from_millis(edit_interval_ms.max(1)),
+            shown: AtomicBool::new(false),
+        }
+    }
+
+    /// Stream ID to stamp on the final reply.
+    pub(super) fn stream_id(&self) -> Value {
+        self.template
+            .metadata
+            .get(meta::STREAM_ID)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    async fn publish(&self, bus: &MessageBus, text: &str) {
+        let mut partial = self.template.clone();
+        partial.content = text.to_string();
+        if let Err(e) = bus.publish_outbound(partial).await {
+            debug!("failed to publish streamed text: {}", e);
+        }
+    }
+
+    /// Remove the partial text from the chat, e.g. when the model call ended
+    /// in tool calls or the turn produced no reply.
+    pub(super) async fn withdraw(&self, bus: &MessageBus) {
+        if self.shown.swap(false, Ordering::AcqRel) {
+            self.publish(bus, "").await;
+        }
+    }
+
+    /// Drive `call` while publishing the text it writes to `text`, at most
+    /// once per edit interval.
+    pub(super) async fn forward<F: Future>(
+        &self,
+        bus: &MessageBus,
+        mut text: watch::Receiver<String>,
+        call: F,
+    ) -> F::Output {
+        let publish_loop = async {
+            let mut last = String::new();
+            while text.changed().await.is_ok() {
+                let raw = text.borrow_and_update().trim().to_string();
+                // A reaction marker is applied by the gateway, never shown
+                let current = match split_react_marker(&raw) {
+                    Some((_, "")) => continue,
+                    Some((_, rest)) => rest.to_string(),
+                    None => raw,
+                };
+                if current == last || is_held_back(&current) {
+                    continue;
+                }
+                if current.is_empty() {
+                    // A failed attempt was discarded; the retry starts over
+                    self.withdraw(bus).await;
+                } else {
+                    self.publish(bus, &current).await;
+                    self.shown.store(true, Ordering::Release);
+                }
+                last = current;
+                tokio::time::sleep(self.edit_interval).await;
+            }
+        };
+        tokio::pin!(call);
+        tokio::select! {
+            biased;
+            output = &mut call => output,
+            // Only ends once the provider dropped its sink
+            () = publish_loop => call.await,
+        }
+    }
+}
+
+fn is_held_back(text: &str) -> bool {
+    !text.is_empty()
+        && (SILENT_PREFIX.starts_with(text)
+            || text.starts_with(SILENT_PREFIX)
+            || REACT_MARKER_PREFIX.starts_with(text)
+            || (text.starts_with(REACT_MARKER_PREFIX) && !text.contains(']')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silent_replies_are_held_back() {
+        assert!(is_held_back("[SIL"));
+        assert!(is_held_back("[SILENT] nothing to add"));
+        assert!(!is_held_back("[Link](https://example.com)"));
+        assert!(!is_held_back(""));
+    }
+
+    #[test]
+    fn test_partial_reaction_markers_are_held_back() {
+        assert!(is_held_back("[REA"));
+        assert!(is_held_back("[REACT:✅"));
+        assert!(!is_held_back("[REACT:✅] Noted"));
+    }
+
+    #[tokio::test]
+    async fn test_forward_publishes_text_and_withdraws() {
+        let bus = MessageBus::default();
+        let mut outbound = bus.take_outbound_rx().unwrap();
+        let msg = InboundMessage::builder("slack", "U1", "C1", "hi").build();
+        let target = StreamTarget::new(&msg, 1);
+        let (sink, rx) = crate::providers::base::StreamSink::new();
+
+        let output = target
+            .forward(&bus, rx, async move {
+                sink.push("Hello");
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                sink.push(" world");
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                7
+            })
+            .await;
+        assert_eq!(output, 7);
+        let first = outbound.recv().await.unwrap();
+        assert_eq!(first.content, "Hello");
+        assert_eq!(first.metadata[meta::STREAM_PARTIAL], Value::Bool(true));
+        assert_eq!(first.metadata[meta::STREAM_ID], target.stream_id());
+        assert_eq!(outbound.recv().await.unwrap().content, "Hello world");
+
+        target.withdraw(&bus).await;
+        assert_eq!(outbound.recv().await.unwrap().content, "");
+        // Nothing shown any more: a second withdraw publishes nothing
+        target.withdraw(&bus).await;
+        assert!(outbound.try_recv().is_err());
+    }
+}
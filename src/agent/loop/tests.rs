@@ -356,6 +356,7 @@ async fn test_parallel_tool_execution_ordering() {
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await
             })
@@ -479,6 +480,7 @@ async fn test_single_tool_no_parallel_overhead() {
         None,
         None,
         None,
+        None,
     )
     .await;
 
@@ -486,6 +488,61 @@ async fn test_single_tool_no_parallel_overhead() {
     assert!(!result.is_error);
 }
 
+#[tokio::test]
+async fn test_tool_call_written_to_audit_log_with_redaction() {
+    let registry = make_registry_with(vec![Arc::new(MockTool {
+        tool_name: "only".into(),
+        delay_ms: 0,
+        response: "only_result".into(),
+    })]);
+    let db = Arc::new(crate::agent::memory::MemoryDB::new(":memory:").unwrap());
+    let secret = "sk-audit-supersecretvalue-1234567890";
+    let mut leak_detector = crate::safety::LeakDetector::new();
+    leak_detector.add_known_secrets(&[("api_key", secret)]);
+    let mut ctx = ExecutionContext {
+        channel: "telegram".into(),
+        chat_id: "42".into(),
+        ..Default::default()
+    };
+    ctx.metadata
+        .insert("request_id".into(), serde_json::json!("req-audit"));
+
+    let result = execute_tool_call(
+        &registry,
+        "only",
+        &serde_json::json!({"token": secret}),
+        &empty_tools(),
+        &ctx,
+        None,
+        None,
+        None,
+        Some(ToolAuditContext {
+            db: &db,
+            leak_detector: &leak_detector,
+        }),
+    )
+    .await;
+    assert!(!result.is_error);
+
+    // The audit write is fire-and-forget on the blocking pool.
+    let mut entries = Vec::new();
+    for _ in 0..50 {
+        entries = db.list_tool_audit("1970-01-01 00:00:00", 10).unwrap();
+        if !entries.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.tool_name, "only");
+    assert_eq!(entry.session_key, "telegram:42");
+    assert_eq!(entry.request_id.as_deref(), Some("req-audit"));
+    assert_eq!(entry.result_summary, "only_result");
+    assert!(entry.success);
+    assert!(!entry.arguments.contains(secret));
+}
+
 #[tokio::test]
 async fn test_parallel_tool_one_panics() {
     let registry = make_registry_with(vec![
@@ -526,6 +583,7 @@ async fn test_parallel_tool_one_panics() {
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await
             })
@@ -591,6 +649,7 @@ async fn test_parallel_tool_one_errors() {
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await
             })
@@ -648,6 +707,7 @@ async fn test_unknown_tool_lists_available() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(result.is_error);
@@ -798,6 +858,7 @@ async fn test_validation_rejects_before_execution() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(result.is_error);
@@ -845,6 +906,7 @@ async fn test_requires_approval_blocks_execution() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(result.is_error);
@@ -867,6 +929,7 @@ async fn test_normal_tool_not_blocked_by_approval() {
         None,
         None,
         None,
+        None,
     )
     .await;
     assert!(!result.is_error);
@@ -894,7 +957,7 @@ fn test_load_and_encode_images_valid_jpg() {
     std::fs::write(&img_path, JPEG_MAGIC).unwrap();
 
     let paths = vec![img_path.to_string_lossy().to_string()];
-    let images = load_and_encode_images(&paths);
+    let images = load_and_encode_images(&paths, &crate::config::InboundMediaConfig::default());
 
     assert_eq!(images.len(), 1);
     assert_eq!(images[0].media_type, "image/jpeg");
@@ -919,7 +982,7 @@ fn test_load_and_encode_images_multiple_formats() {
                 .to_string()
         })
         .collect();
-    let images = load_and_encode_images(&paths);
+    let images = load_and_encode_images(&paths, &crate::config::InboundMediaConfig::default());
 
     assert_eq!(images.len(), 4);
     assert_eq!(images[0].media_type, "image/jpeg");
@@ -930,7 +993,10 @@ fn test_load_and_encode_images_multiple_formats() {
 
 #[test]
 fn test_load_and_encode_images_skips_missing() {
-    let images = load_and_encode_images(&["/nonexistent/path/image.jpg".to_string()]);
+    let images = load_and_encode_images(
+        &["/nonexistent/path/image.jpg".to_string()],
+        &crate::config::InboundMediaConfig::default(),
+    );
     assert!(images.is_empty());
 }
 
@@ -940,7 +1006,10 @@ fn test_load_and_encode_images_skips_unsupported_format() {
     let path = tmp.path().join("test.bmp");
     std::fs::write(&path, b"bmp data").unwrap();
 
-    let images = load_and_encode_images(&[path.to_string_lossy().to_string()]);
+    let images = load_and_encode_images(
+        &[path.to_string_lossy().to_string()],
+        &crate::config::InboundMediaConfig::default(),
+    );
     assert!(images.is_empty());
 }
 
@@ -951,7 +1020,10 @@ fn test_load_and_encode_images_rejects_bad_magic_bytes() {
     let path = tmp.path().join("fake.png");
     std::fs::write(&path, JPEG_MAGIC).unwrap();
 
-    let images = load_and_encode_images(&[path.to_string_lossy().to_string()]);
+    let images = load_and_encode_images(
+        &[path.to_string_lossy().to_string()],
+        &crate::config::InboundMediaConfig::default(),
+    );
     assert!(images.is_empty(), "should reject mismatched magic bytes");
 }
 
@@ -962,12 +1034,15 @@ fn test_load_and_encode_images_rejects_html_as_image() {
     let path = tmp.path().join("download.png");
     std::fs::write(&path, b"<html><body>Error</body></html>").unwrap();
 
-    let images = load_and_encode_images(&[path.to_string_lossy().to_string()]);
+    let images = load_and_encode_images(
+        &[path.to_string_lossy().to_string()],
+        &crate::config::InboundMediaConfig::default(),
+    );
     assert!(images.is_empty(), "should reject HTML content");
 }
 
 #[test]
-fn test_load_and_encode_images_max_limit() {
+fn test_cap_attachments_limits_images() {
     let tmp = tempfile::TempDir::new().unwrap();
     let mut paths = Vec::new();
     for i in 0..8 {
@@ -976,13 +1051,45 @@ fn test_load_and_encode_images_max_limit() {
         paths.push(path.to_string_lossy().to_string());
     }
 
-    let images = load_and_encode_images(&paths);
-    assert_eq!(images.len(), MAX_IMAGES); // Capped at 5
+    let (kept, notice) = cap_attachments(&paths, 5, 5);
+    assert_eq!(kept, paths[..5]);
+    assert_eq!(
+        notice.as_deref(),
+        Some("I can only look at 5 images at once; using the first 5 of 8.")
+    );
+    let images = load_and_encode_images(&kept, &crate::config::InboundMediaConfig::default());
+    assert_eq!(images.len(), 5);
+}
+
+#[test]
+fn test_cap_attachments_counts_documents_separately() {
+    let paths: Vec<String> = ["a.png", "b.pdf", "c.jpg", "d.PDF", "e.pdf"]
+        .iter()
+        .map(|p| (*p).to_string())
+        .collect();
+
+    let (kept, notice) = cap_attachments(&paths, 5, 2);
+    assert_eq!(kept, vec!["a.png", "b.pdf", "c.jpg", "d.PDF"]);
+    assert_eq!(
+        notice.as_deref(),
+        Some("I can only read 2 documents at once; using the first 2 of 3.")
+    );
+
+    let (kept, notice) = cap_attachments(&paths, 5, 5);
+    assert_eq!(kept, paths);
+    assert!(notice.is_none());
+
+    let (kept, notice) = cap_attachments(&paths, 0, 5);
+    assert_eq!(kept, vec!["b.pdf", "d.PDF", "e.pdf"]);
+    assert_eq!(
+        notice.as_deref(),
+        Some("I can't look at images here; ignoring the 2 attached.")
+    );
 }
 
 #[test]
 fn test_load_and_encode_images_empty_input() {
-    let images = load_and_encode_images(&[]);
+    let images = load_and_encode_images(&[], &crate::config::InboundMediaConfig::default());
     assert!(images.is_empty());
 }
 
@@ -996,7 +1103,10 @@ fn test_load_and_encode_images_base64_roundtrip() {
     original_data.extend_from_slice(b"extra png data here");
     std::fs::write(&img_path, &original_data).unwrap();
 
-    let images = load_and_encode_images(&[img_path.to_string_lossy().to_string()]);
+    let images = load_and_encode_images(
+        &[img_path.to_string_lossy().to_string()],
+        &crate::config::InboundMediaConfig::default(),
+    );
     assert_eq!(images.len(), 1);
 
     // Decode and verify roundtrip
@@ -1014,7 +1124,10 @@ fn test_load_and_encode_images_pdf_support() {
     pdf_data.extend_from_slice(b"fake pdf content for testing");
     std::fs::write(&path, &pdf_data).unwrap();
 
-    let images = load_and_encode_images(&[path.to_string_lossy().to_string()]);
+    let images = load_and_encode_images(
+        &[path.to_string_lossy().to_string()],
+        &crate::config::InboundMediaConfig::default(),
+    );
     assert_eq!(images.len(), 1);
     assert_eq!(images[0].media_type, "application/pdf");
 }
@@ -1043,10 +1156,47 @@ fn test_load_and_encode_images_rejects_fake_pdf() {
     // Not a real PDF (wrong magic bytes)
     std::fs::write(&path, b"this is not a pdf").unwrap();
 
-    let images = load_and_encode_images(&[path.to_string_lossy().to_string()]);
+    let images = load_and_encode_images(
+        &[path.to_string_lossy().to_string()],
+        &crate::config::InboundMediaConfig::default(),
+    );
     assert!(images.is_empty(), "should reject non-PDF content");
 }
 
+#[test]
+fn test_load_and_encode_images_downscales_large_png() {
+    use base64::Engine;
+    let tmp = tempfile::TempDir::new().unwrap();
+    let path = tmp.path().join("large.png");
+    image::RgbImage::from_pixel(3000, 1500, image::Rgb([200, 40, 40]))
+        .save(&path)
+        .unwrap();
+    let limits = crate::config::InboundMediaConfig {
+        max_image_dimension: 1000,
+        jpeg_quality: 80,
+        ..Default::default()
+    };
+
+    let images = load_and_encode_images(&[path.to_string_lossy().to_string()], &limits);
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].media_type, "image/jpeg");
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(&images[0].data)
+        .unwrap();
+    let decoded = image::load_from_memory(&bytes).unwrap();
+    assert_eq!((decoded.width(), decoded.height()), (1000, 500));
+
+    // Within the limit, or with downscaling off, the PNG is sent unchanged
+    for max_image_dimension in [3000, 0] {
+        let limits = crate::config::InboundMediaConfig {
+            max_image_dimension,
+            ..Default::default()
+        };
+        let images = load_and_encode_images(&[path.to_string_lossy().to_string()], &limits);
+        assert_eq!(images[0].media_type, "image/png");
+    }
+}
+
 // --- handle_text_response tests ---
 
 #[test]
@@ -1119,6 +1269,98 @@ fn test_action_hallucination_not_repeated_after_l1_correction() {
     );
 }
 
+#[test]
+fn test_clarification_detection() {
+    assert!(hallucination::is_clarification("Which file do you mean?"));
+    assert!(hallucination::is_clarification(
+        "Should I use **staging**?**\n"
+    ));
+    assert!(!hallucination::is_clarification(
+        "Done. The file is updated."
+    ));
+    assert!(hallucination::is_action_request("Deploy the site"));
+    assert!(!hallucination::is_action_request("What time is it?"));
+    assert!(!hallucination::is_action_request("  "));
+}
+
+#[test]
+fn test_clarification_budget_forces_action_when_exhausted() {
+    let tool_names = vec!["write_file".to_string()];
+    let mut messages = vec![Message::user("Update the config file".to_string())];
+    let mut fired = false;
+
+    // Under budget: clarification is accepted
+    let result = hallucination::enforce_clarification_budget(
+        "Which config file?",
+        &mut messages,
+        1,
+        2,
+        false,
+        &mut fired,
+        &tool_names,
+    );
+    assert!(matches!(result, TextAction::Return));
+
+    // Budget exhausted: correction injected once
+    let result = hallucination::enforce_clarification_budget(
+        "Which config file?",
+        &mut messages,
+        2,
+        2,
+        false,
+        &mut fired,
+        &tool_names,
+    );
+    assert!(matches!(result, TextAction::Continue));
+    assert!(fired);
+    assert_eq!(messages.len(), 2);
+    assert!(messages[1].content.contains("Do not ask another question"));
+
+    let result = hallucination::enforce_clarification_budget(
+        "Which config file?",
+        &mut messages,
+        2,
+        2,
+        false,
+        &mut fired,
+        &tool_names,
+    );
+    assert!(matches!(result, TextAction::Return), "fires at most once");
+}
+
+#[test]
+fn test_clarification_budget_ignores_questions_and_disabled_budget() {
+    let tool_names = vec!["write_file".to_string()];
+    let mut fired = false;
+
+    // User asked a question: clarifying back is fine
+    let mut messages = vec![Message::user("Can you update the config?".to_string())];
+    let result = hallucination::enforce_clarification_budget(
+        "Which config file?",
+        &mut messages,
+        5,
+        2,
+        false,
+        &mut fired,
+        &tool_names,
+    );
+    assert!(matches!(result, TextAction::Return));
+
+    // Budget 0 disables the check
+    let mut messages = vec![Message::user("Update the config".to_string())];
+    let result = hallucination::enforce_clarification_budget(
+        "Which config file?",
+        &mut messages,
+        5,
+        0,
+        false,
+        &mut fired,
+        &tool_names,
+    );
+    assert!(matches!(result, TextAction::Return));
+    assert!(!fired);
+}
+
 #[test]
 fn test_legitimate_tool_response_passes_through() {
     // After tools were actually called, action claims pass through (not a hallucination)
@@ -1331,3 +1573,1294 @@ fn test_strip_think_tags_empty_block() {
     let input = "<think></think>content after";
     assert_eq!(strip_think_tags(input), "content after");
 }
+
+#[test]
+fn test_continuation_marker_roundtrip() {
+    assert!(continuation::is_continue_request("Continue."));
+    assert!(continuation::is_continue_request("  keep going! "));
+    assert!(!continuation::is_continue_request(
+        "continue with the report later"
+    ));
+
+    let mut metadata = HashMap::new();
+    let marker = continuation::update_marker(
+        &mut metadata,
+        None,
+        "Audit the repo",
+        Some("ctx".to_string()),
+        false,
+    )
+    .unwrap();
+    assert_eq!(marker.auto_runs, 0);
+    assert!(continuation::pending_marker(&metadata, "what time is it?", false).is_none());
+    let resumed = continuation::pending_marker(&metadata, "continue", false).unwrap();
+    assert_eq!(resumed, marker);
+
+    // An automatic continuation keeps the original request and counts the run
+    let next = continuation::update_marker(
+        &mut metadata,
+        Some(&resumed),
+        "continue",
+        Some("ctx2".to_string()),
+        true,
+    )
+    .unwrap();
+    assert_eq!(next.request, "Audit the repo");
+    assert_eq!(next.auto_runs, 1);
+
+    // A turn that finishes clears the marker
+    assert!(
+        continuation::update_marker(&mut metadata, Some(&next), "continue", None, false).is_none()
+    );
+    assert!(!metadata.contains_key(continuation::CONTINUATION_META_KEY));
+}
+
+#[test]
+fn test_continuation_context_lists_recent_tool_steps() {
+    let mut messages = Vec::new();
+    for i in 0..20 {
+        let id = format!("tc{i}");
+        messages.push(Message {
+            role: "assistant".to_string(),
+            tool_calls: Some(vec![ToolCallRequest {
+                id: id.clone(),
+                name: "read_file".to_string(),
+                arguments: serde_json::json!({"path": format!("f{i}.md")}),
+            }]),
+            ..Default::default()
+        });
+        messages.push(Message {
+            role: "tool".to_string(),
+            content: format!("contents {i}"),
+            tool_call_id: Some(id),
+            is_error: i == 19,
+            ..Default::default()
+        });
+    }
+    let context = continuation::build_context(&messages, "[Cognitive state] 20 tool calls");
+    assert!(context.starts_with("[Cognitive state]"));
+    assert!(context.contains("(5 earlier tool calls omitted)"));
+    assert!(!context.contains("f4.md"));
+    assert!(context.contains("read_file({\"path\":\"f5.md\"}) -> ok: contents 5"));
+    assert!(context.contains("-> error: contents 19"));
+}
+
+#[test]
+fn test_extraction_throttle_batches_by_turns() {
+    let throttle = extraction::ExtractionThrottle::new(crate::config::ExtractionIntervalConfig {
+        turns: 3,
+        minutes: 0,
+    });
+    let now = std::time::Instant::now();
+    let record = |n: usize| throttle.record_at("s1", format!("u{n}"), format!("a{n}"), now);
+
+    assert!(record(1).is_none());
+    assert!(record(2).is_none());
+    let batch = record(3).expect("third turn is due");
+    assert_eq!(
+        batch,
+        vec![
+            ("u1".to_string(), "a1".to_string()),
+            ("u2".to_string(), "a2".to_string()),
+            ("u3".to_string(), "a3".to_string()),
+        ]
+    );
+    // Counter restarts after a batch; other sessions are independent
+    assert!(record(4).is_none());
+    assert!(
+        throttle
+            .record_at("s2", "x".into(), "y".into(), now)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_extraction_throttle_batches_by_minutes() {
+    let throttle = extraction::ExtractionThrottle::new(crate::config::ExtractionIntervalConfig {
+        turns: 0,
+        minutes: 10,
+    });
+    let start = std::time::Instant::now();
+    let at = |mins: u64| start + std::time::Duration::from_secs(mins * 60);
+
+    assert!(
+        throttle
+            .record_at("s", "u1".into(), "a1".into(), at(0))
+            .is_none()
+    );
+    assert!(
+        throttle
+            .record_at("s", "u2".into(), "a2".into(), at(9))
+            .is_none()
+    );
+    let batch = throttle
+        .record_at("s", "u3".into(), "a3".into(), at(10))
+        .expect("window elapsed");
+    assert_eq!(batch.len(), 3);
+    // The next window starts at the extraction
+    assert!(
+        throttle
+            .record_at("s", "u4".into(), "a4".into(), at(15))
+            .is_none()
+    );
+    assert!(
+        throttle
+            .record_at("s", "u5".into(), "a5".into(), at(20))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_extraction_throttle_default_extracts_every_turn() {
+    let throttle =
+        extraction::ExtractionThrottle::new(crate::config::ExtractionIntervalConfig::default());
+    let batch = throttle.record("s", "u".into(), "a".into()).unwrap();
+    assert_eq!(batch, vec![("u".to_string(), "a".to_string())]);
+}
+
+#[test]
+fn test_image_description_cache_lookup() {
+    use crate::providers::base::ImageData;
+
+    let db = crate::agent::memory::memory_db::MemoryDB::new(":memory:").unwrap();
+    let photo = ImageData {
+        media_type: "image/png".to_string(),
+        data: "iVBORw0KGgo=".to_string(),
+    };
+    let pdf = ImageData {
+        media_type: "application/pdf".to_string(),
+        data: "JVBERi0=".to_string(),
+    };
+
+    // First sight: the image is sent and queued for a description
+    let lookup = image_descriptions::lookup(&db, vec![photo.clone(), pdf.clone()], 30);
+    assert_eq!(lookup.send.len(), 2);
+    assert!(lookup.descriptions.is_empty());
+    assert_eq!(lookup.uncached.len(), 1);
+    assert_eq!(lookup.uncached[0].0, image_descriptions::image_hash(&photo));
+
+    // Once described, the image is replaced by its description; documents
+    // are never cached
+    db.put_image_description(&lookup.uncached[0].0, "A whiteboard diagram", 30)
+        .unwrap();
+    let lookup = image_descriptions::lookup(&db, vec![photo, pdf], 30);
+    assert_eq!(lookup.send.len(), 1);
+    assert_eq!(lookup.send[0].media_type, "application/pdf");
+    assert!(lookup.uncached.is_empty());
+    assert_eq!(
+        image_descriptions::descriptions_note(&lookup.descriptions),
+        "[Image 1 (seen before, cached description): A whiteboard diagram]"
+    );
+}
+
+#[tokio::test]
+async fn test_idle_session_archived_and_rehydrated() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let summary = "User is planning a trip to Lisbon in May.";
+    let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![LLMResponse {
+        content: Some(summary.to_string()),
+        ..Default::default()
+    }]));
+    let agent = AgentLoop::new(AgentLoopConfig::test_defaults(
+        bus,
+        provider.clone(),
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    ))
+    .await
+    .unwrap();
+
+    let key = "telegram:archive";
+    let mut session = agent
+        .profile(key)
+        .sessions
+        .get_or_create(key)
+        .await
+        .unwrap();
+    session.add_message("user", "Help me plan Lisbon in May", HashMap::new());
+    session.add_message("assistant", "Sure, flights first?", HashMap::new());
+    session.metadata.insert(
+        crate::bus::meta::LAST_INPUT_TOKENS.to_string(),
+        90_000.into(),
+    );
+    agent.profile(key).sessions.save(&session).await.unwrap();
+    let backdate = || {
+        agent
+            .memory
+            .db()
+            .lock_conn()
+            .unwrap()
+            .execute(
+                "UPDATE sessions SET updated_at = datetime('now', '-10 days') WHERE key = ?1",
+                [key],
+            )
+            .unwrap();
+    };
+    backdate();
+
+    let compactor = MessageCompactor::new(provider, None);
+    let archived = session_archive::archive_idle_sessions(
+        agent.profile(key).sessions.as_ref(),
+        &compactor,
+        &agent.memory,
+        7,
+    )
+    .await
+    .unwrap();
+    assert_eq!(archived, 1);
+
+    let session = agent
+        .profile(key)
+        .sessions
+        .get_or_create(key)
+        .await
+        .unwrap();
+    assert!(session.messages.is_empty());
+    assert!(session.metadata.contains_key(session_archive::ARCHIVED_AT));
+    assert!(
+        !session
+            .metadata
+            .contains_key(crate::bus::meta::LAST_INPUT_TOKENS)
+    );
+
+    // The next turn starts from the summary
+    let history = agent.get_compacted_history(&session).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(
+        history[0]["content"],
+        format!("[Previous conversation summary: {summary}]")
+    );
+
+    // Archived sessions are skipped until they see new messages
+    backdate();
+    let archived = session_archive::archive_idle_sessions(
+        agent.profile(key).sessions.as_ref(),
+        &compactor,
+        &agent.memory,
+        7,
+    )
+    .await
+    .unwrap();
+    assert_eq!(archived, 0);
+}
+
+/// Blocks the first request under a content policy, then answers, recording
+/// the user message each request carried.
+struct PolicyBlockingProvider {
+    seen: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl LLMProvider for PolicyBlockingProvider {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        let mut seen = self.seen.lock().unwrap();
+        let last_user = req
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.role == "user")
+            .map(|m| m.content.clone())
+            .unwrap_or_default();
+        seen.push(last_user);
+        if seen.len() == 1 {
+            return Err(crate::errors::OxicrabError::from(
+                oxicrab_core::errors::ProviderError::ContentPolicy("flagged".into()),
+            )
+            .into());
+        }
+        Ok(LLMResponse {
+            content: Some("I can't help with that one.".to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn default_model(&self) -> &'static str {
+        "mock-model"
+    }
+}
+
+#[tokio::test]
+async fn test_content_policy_block_reprompts_without_offending_content() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider = Arc::new(PolicyBlockingProvider {
+        seen: std::sync::Mutex::new(Vec::new()),
+    });
+    let agent = AgentLoop::new(AgentLoopConfig::test_defaults(
+        bus,
+        provider.clone(),
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    ))
+    .await
+    .unwrap();
+
+    let result = agent
+        .run_agent_loop_with_overrides(
+            vec![
+                Message::system("system".to_string()),
+                Message::user("something the provider refuses".to_string()),
+            ],
+            None,
+            &ExecutionContext {
+                channel: "test".to_string(),
+                chat_id: "chat".to_string(),
+                context_summary: None,
+                metadata: HashMap::new(),
+            },
+            &AgentRunOverrides::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.content.as_deref(),
+        Some("I can't help with that one.")
+    );
+    let seen = provider.seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(seen[0], "something the provider refuses");
+    assert!(seen[1].starts_with("[Content withheld"), "{}", seen[1]);
+}
+
+#[tokio::test]
+async fn test_sensitive_fact_written_only_after_confirmation() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![]));
+    let mut agent = AgentLoop::new(AgentLoopConfig::test_defaults(
+        bus,
+        provider,
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    ))
+    .await
+    .unwrap();
+    agent.memory_confirm = vec![crate::config::MemoryCategory::Medical];
+    let key = "telegram:confirm";
+
+    let prompt = agent
+        .try_remember_fast_path("Sarah is allergic to peanuts", key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(prompt.contains("(medical)"), "{prompt}");
+    assert!(
+        agent
+            .memory
+            .get_recent_daily_entries(10)
+            .unwrap()
+            .is_empty()
+    );
+
+    let reply = agent
+        .resolve_memory_confirmation(key, "Yes!")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(reply.starts_with("Saved."), "{reply}");
+    let entries = agent.memory.get_recent_daily_entries(10).unwrap();
+    assert!(entries.iter().any(|e| e.contains("allergic to peanuts")));
+    // Both the proposal and the answer are part of the conversation
+    let session = agent
+        .profile(key)
+        .sessions
+        .get_or_create(key)
+        .await
+        .unwrap();
+    assert_eq!(session.messages.len(), 4);
+
+    // Declined and ignored proposals are dropped without writing
+    agent
+        .try_remember_fast_path("My prescription is 20mg lisinopril daily", key)
+        .await
+        .unwrap();
+    assert_eq!(
+        agent
+            .resolve_memory_confirmation(key, "no")
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("Okay, I won't remember that.")
+    );
+    agent
+        .try_remember_fast_path("I was diagnosed with asthma as a child", key)
+        .await
+        .unwrap();
+    assert!(
+        agent
+            .resolve_memory_confirmation(key, "what's the weather?")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        agent
+            .resolve_memory_confirmation(key, "yes")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(agent.memory.get_recent_daily_entries(10).unwrap().len(), 1);
+
+    // Categories not listed are written directly
+    let reply = agent
+        .try_remember_fast_path("My bank account is with the credit union", key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!reply.contains("confirm"), "{reply}");
+}
+
+#[tokio::test]
+async fn test_forget_fast_path_deletes_matching_memory() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![]));
+    let agent = AgentLoop::new(AgentLoopConfig::test_defaults(
+        bus,
+        provider,
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    ))
+    .await
+    .unwrap();
+    let key = "telegram:forget";
+    agent
+        .memory
+        .append_today("User's favorite color is blue")
+        .unwrap();
+
+    let reply = agent
+        .try_forget_fast_path("my favorite color is blue", key)
+        .await
+        .unwrap();
+    assert_eq!(reply, "Forgotten: User's favorite color is blue");
+    assert!(
+        agent
+            .memory
+            .get_recent_daily_entries(10)
+            .unwrap()
+            .is_empty()
+    );
+
+    let reply = agent
+        .try_forget_fast_path("my favorite color is blue", key)
+        .await
+        .unwrap();
+    assert!(reply.starts_with("I couldn't find"), "{reply}");
+    let session = agent
+        .profile(key)
+        .sessions
+        .get_or_create(key)
+        .await
+        .unwrap();
+    assert_eq!(session.messages.len(), 4);
+}
+
+#[tokio::test]
+async fn test_forget_fast_path_keeps_near_miss() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![]));
+    let agent = AgentLoop::new(AgentLoopConfig::test_defaults(
+        bus,
+        provider,
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    ))
+    .await
+    .unwrap();
+    let key = "telegram:forget";
+    agent
+        .memory
+        .append_today("my wifi password is hunter2")
+        .unwrap();
+
+    let reply = agent
+        .try_forget_fast_path("my wifi password is abc123", key)
+        .await
+        .unwrap();
+    assert!(reply.contains("nothing was deleted"), "{reply}");
+    assert!(reply.contains("hunter2"), "{reply}");
+    assert_eq!(agent.memory.get_recent_daily_entries(10).unwrap().len(), 1);
+
+    let reply = agent
+        .try_forget_fast_path("my wifi password is hunter2", key)
+        .await
+        .unwrap();
+    assert!(reply.starts_with("Forgotten:"), "{reply}");
+    assert!(
+        agent
+            .memory
+            .get_recent_daily_entries(10)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_profiles_isolate_memory_and_sessions() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![]));
+    let mut config = AgentLoopConfig::test_defaults(
+        bus,
+        provider,
+        tmp.path().join("workspace"),
+        Arc::new(outbound_tx),
+    );
+    config.profiles = vec![crate::config::ProfileConfig {
+        name: "work".into(),
+        workspace: None,
+        channels: vec!["slack".into()],
+        chats: vec![],
+    }];
+    let agent = AgentLoop::new(config).await.unwrap();
+
+    agent
+        .try_remember_fast_path("The staging database moves to us-east-2", "slack:C042")
+        .await
+        .unwrap();
+    let work = agent.profile("slack:C042");
+    assert_eq!(work.name, "work");
+    assert_eq!(work.workspace, tmp.path().join("profiles").join("work"));
+    assert_eq!(work.memory.get_recent_daily_entries(10).unwrap().len(), 1);
+    assert_eq!(
+        work.sessions
+            .get_or_create("slack:C042")
+            .await
+            .unwrap()
+            .messages
+            .len(),
+        2
+    );
+
+    let personal = agent.profile("telegram:55512");
+    assert_eq!(personal.name, crate::agent::profile::DEFAULT_PROFILE);
+    assert!(
+        personal
+            .memory
+            .get_recent_daily_entries(10)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        personal
+            .sessions
+            .get_or_create("slack:C042")
+            .await
+            .unwrap()
+            .messages
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_default_profile_tools_cannot_read_other_profiles() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![]));
+    let workspace = tmp.path().join("workspace");
+    let mut config =
+        AgentLoopConfig::test_defaults(bus, provider, workspace.clone(), Arc::new(outbound_tx));
+    // Without workspace restriction the deny list is the only thing in the way
+    config.tool_configs.restrict_to_workspace = false;
+    config.profiles = vec![crate::config::ProfileConfig {
+        name: "work".into(),
+        workspace: None,
+        channels: vec!["slack".into()],
+        chats: vec![],
+    }];
+    let agent = AgentLoop::new(config).await.unwrap();
+    let work_db = agent
+        .profile("slack:C042")
+        .workspace
+        .join("memory")
+        .join("memory.sqlite3");
+    assert!(work_db.exists());
+    assert!(!work_db.starts_with(&workspace));
+
+    let read_file = agent.tool_registry().get("read_file").unwrap();
+    let result = read_file
+        .execute(
+            serde_json::json!({"path": work_db.to_str().unwrap()}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("protected location"));
+}
+
+#[tokio::test]
+async fn test_sqlite_tool_cannot_open_memory_db() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![]));
+    let mut config = AgentLoopConfig::test_defaults(
+        bus,
+        provider,
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    );
+    config.tool_configs.restrict_to_workspace = false;
+    let agent = AgentLoop::new(config).await.unwrap();
+    assert!(tmp.path().join("memory").join("memory.sqlite3").exists());
+
+    let sqlite = agent.tool_registry().get("sqlite_query").unwrap();
+    let result = sqlite
+        .execute(
+            serde_json::json!({
+                "action": "query",
+                "path": "memory/memory.sqlite3",
+                "sql": "SELECT name FROM sqlite_master",
+            }),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("protected location"));
+}
+
+#[tokio::test]
+async fn test_token_breakdown_recorded_per_turn() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider = Arc::new(QueuedProvider::new(vec![
+        LLMResponse {
+            tool_calls: vec![make_tool_call_with_args("1", "echo", serde_json::json!({}))],
+            input_tokens: Some(500),
+            ..Default::default()
+        },
+        LLMResponse {
+            content: Some("done".to_string()),
+            input_tokens: Some(600),
+            ..Default::default()
+        },
+    ]));
+    let mut config = AgentLoopConfig::test_defaults(
+        bus,
+        provider,
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    );
+    config.token_breakdown = true;
+    let mut agent = AgentLoop::new(config).await.unwrap();
+    agent.tools = Arc::new(make_registry_with(vec![Arc::new(MockTool {
+        tool_name: "echo".into(),
+        delay_ms: 0,
+        response: "r".repeat(200),
+    })]));
+
+    agent
+        .run_agent_loop_with_overrides(
+            vec![
+                Message::system("s".repeat(400)),
+                Message::user("h".repeat(80)),
+                Message::assistant("a".repeat(80), None),
+                Message::user("m".repeat(40)),
+            ],
+            None,
+            &ExecutionContext {
+                channel: "test".to_string(),
+                chat_id: "chat".to_string(),
+                context_summary: None,
+                metadata: HashMap::from([(
+                    "session_key".to_string(),
+                    serde_json::Value::String("test:tb".to_string()),
+                )]),
+            },
+            &AgentRunOverrides::default(),
+        )
+        .await
+        .unwrap();
+
+    // Recorded off the runtime
+    let db = agent.memory.db();
+    let mut rows = Vec::new();
+    for _ in 0..50 {
+        rows = db.get_token_breakdowns("test:tb", 10).unwrap();
+        if !rows.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(rows.len(), 1);
+    let b = rows[0].breakdown;
+    assert_eq!(b.calls, 2);
+    // Everything sent before the turn is counted once per call
+    assert!(b.system >= 200, "{b:?}");
+    assert_eq!(b.history, 2 * 40);
+    assert_eq!(b.message, 2 * 10);
+    assert!(b.tool_definitions > 0);
+    // The tool result and the tool call only reach the second call
+    assert_eq!(b.tool_results, 50);
+    assert_eq!(b.assistant, 1);
+    assert_eq!(b.provider_input, 1100);
+}
+
+struct CountingTool {
+    runs: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait]
+impl Tool for CountingTool {
+    fn name(&self) -> &'static str {
+        "lookup"
+    }
+
+    fn description(&self) -> &'static str {
+        "Counting test tool"
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({})
+    }
+
+    async fn execute(
+        &self,
+        _params: serde_json::Value,
+        _ctx: &ExecutionContext,
+    ) -> anyhow::Result<ToolResult> {
+        self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(ToolResult::new("no results"))
+    }
+}
+
+/// Provider answering from a queue and keeping every request's messages.
+struct RecordingQueuedProvider {
+    inner: QueuedProvider,
+    requests: std::sync::Mutex<Vec<Vec<Message>>>,
+}
+
+#[async_trait]
+impl LLMProvider for RecordingQueuedProvider {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        self.requests.lock().unwrap().push(req.messages.clone());
+        self.inner.chat(req).await
+    }
+
+    fn default_model(&self) -> &'static str {
+        "mock-model"
+    }
+}
+
+async fn run_tool_loop_turn(
+    action: crate::config::ToolLoopAction,
+    responses: Vec<LLMResponse>,
+) -> (AgentLoopResult, usize, Vec<Vec<Message>>) {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider = Arc::new(RecordingQueuedProvider {
+        inner: QueuedProvider::new(responses),
+        requests: std::sync::Mutex::new(Vec::new()),
+    });
+    let mut config = AgentLoopConfig::test_defaults(
+        bus,
+        provider.clone(),
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    );
+    config.tool_loop = crate::config::ToolLoopConfig {
+        threshold: 3,
+        action,
+    };
+    let mut agent = AgentLoop::new(config).await.unwrap();
+    let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    agent.tools = Arc::new(make_registry_with(vec![Arc::new(CountingTool {
+        runs: runs.clone(),
+    })]));
+
+    let result = agent
+        .run_agent_loop_with_overrides(
+            vec![
+                Message::system("system".to_string()),
+                Message::user("find the invoice"),
+            ],
+            None,
+            &ExecutionContext::default(),
+            &AgentRunOverrides::default(),
+        )
+        .await
+        .unwrap();
+    let requests = provider.requests.lock().unwrap().clone();
+    (
+        result,
+        runs.load(std::sync::atomic::Ordering::SeqCst),
+        requests,
+    )
+}
+
+fn lookup_call(id: &str, query: &str) -> LLMResponse {
+    LLMResponse {
+        tool_calls: vec![make_tool_call_with_args(
+            id,
+            "lookup",
+            serde_json::json!({"query": query}),
+        )],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_repeated_tool_call_is_skipped_and_nudged() {
+    let answer = LLMResponse {
+        content: Some("I could not find the invoice.".to_string()),
+        ..Default::default()
+    };
+    let (result, runs, requests) = run_tool_loop_turn(
+        crate::config::ToolLoopAction::Nudge,
+        vec![
+            lookup_call("1", "invoice"),
+            lookup_call("2", "invoice"),
+            lookup_call("3", "invoice"),
+            answer,
+        ],
+    )
+    .await;
+
+    assert_eq!(runs, 2);
+    assert_eq!(
+        result.content.as_deref(),
+        Some("I could not find the invoice.")
+    );
+    let last = requests.last().unwrap();
+    assert!(last.iter().any(|m| {
+        m.role == "system"
+            && m.content
+                .contains("called lookup with the same arguments 3 times")
+    }));
+    assert!(
+        last.iter()
+            .any(|m| m.role == "tool" && m.is_error && m.content.starts_with("Not run"))
+    );
+}
+
+#[tokio::test]
+async fn test_tool_loop_after_nudge_forces_answer() {
+    let answer = LLMResponse {
+        content: Some("Here is what I found so far.".to_string()),
+        ..Default::default()
+    };
+    let (result, runs, requests) = run_tool_loop_turn(
+        crate::config::ToolLoopAction::Nudge,
+        vec![
+            lookup_call("1", "invoice"),
+            lookup_call("2", "invoice"),
+            lookup_call("3", "invoice"),
+            lookup_call("4", "invoice"),
+            answer,
+        ],
+    )
+    .await;
+
+    assert_eq!(runs, 2);
+    assert_eq!(
+        result.content.as_deref(),
+        Some("Here is what I found so far.")
+    );
+    // The forced answer is requested without tools
+    assert_eq!(requests.len(), 5);
+}
+
+#[tokio::test]
+async fn test_tool_loop_finalize_and_changed_arguments() {
+    let answer = LLMResponse {
+        content: Some("done".to_string()),
+        ..Default::default()
+    };
+    let (result, runs, _) = run_tool_loop_turn(
+        crate::config::ToolLoopAction::Finalize,
+        vec![
+            lookup_call("1", "invoice"),
+            lookup_call("2", "invoice 2024"),
+            lookup_call("3", "invoice 2024"),
+            lookup_call("4", "invoice 2024"),
+            answer,
+        ],
+    )
+    .await;
+
+    // Different arguments reset the count
+    assert_eq!(runs, 3);
+    assert_eq!(result.content.as_deref(), Some("done"));
+}
+
+#[test]
+fn test_tool_loop_threshold_zero_disables_detection() {
+    let mut detector = tool_loop::ToolLoopDetector::new(0);
+    let call = make_tool_call_with_args("1", "lookup", serde_json::json!({"q": 1}));
+    for _ in 0..5 {
+        assert_eq!(detector.observe(std::slice::from_ref(&call)), vec![false]);
+    }
+    assert_eq!(detector.count(), 5);
+}
+
+#[tokio::test]
+async fn test_typing_indicator_follows_channel_config() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let mut outbound = bus.take_outbound_rx().unwrap();
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![]));
+    let mut agent = AgentLoop::new(AgentLoopConfig::test_defaults(
+        bus,
+        provider,
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    ))
+    .await
+    .unwrap();
+    let (typing_tx, mut typing_rx) = tokio::sync::mpsc::channel(4);
+    agent.typing_tx = Some(Arc::new(typing_tx));
+    agent.typing_indicators = crate::config::ChannelsConfig::default().typing_indicators();
+    let telegram = InboundMessage::builder("telegram", "u1", "100", "hi").build();
+    let slack = InboundMessage::builder("slack", "u1", "C1", "hi").build();
+
+    // Telegram: native typing action
+    agent.send_typing_indicator(&telegram).await;
+    assert_eq!(
+        typing_rx.try_recv().unwrap(),
+        ("telegram".to_string(), "100".to_string())
+    );
+
+    // Slack: off by default
+    agent.send_typing_indicator(&slack).await;
+    assert!(typing_rx.try_recv().is_err());
+    assert!(outbound.try_recv().is_err());
+
+    // Slack enabled: one status message instead of typing actions
+    agent.typing_indicators.get_mut("slack").unwrap().enabled = true;
+    agent.send_typing_indicator(&slack).await;
+    let status = outbound.try_recv().unwrap();
+    assert_eq!(
+        (status.channel.as_str(), status.chat_id.as_str()),
+        ("slack", "C1")
+    );
+    assert_eq!(
+        status.metadata.get(crate::bus::meta::STATUS),
+        Some(&serde_json::Value::Bool(true))
+    );
+    assert!(typing_rx.try_recv().is_err());
+    let ctx = ("slack".to_string(), "C1".to_string());
+    let slack_indicator = agent.typing_indicator("slack");
+    assert!(
+        helpers::start_typing(agent.typing_tx.as_ref(), Some(&ctx), &slack_indicator).is_none()
+    );
+
+    // Disabled native indicator sends nothing
+    agent.typing_indicators.get_mut("telegram").unwrap().enabled = false;
+    agent.send_typing_indicator(&telegram).await;
+    assert!(typing_rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_tool_result_summary_saves_full_result() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = Arc::new(crate::agent::memory::memory_db::MemoryDB::new(":memory:").unwrap());
+    let provider = Arc::new(QueuedProvider::new(vec![LLMResponse {
+        content: Some("  Page lists three prices: 1, 2, 3.  ".to_string()),
+        ..Default::default()
+    }]));
+    let summarizer = tool_summary::ToolResultSummarizer::new(
+        provider,
+        "cheap-model".to_string(),
+        100,
+        Arc::new(crate::agent::workspace::WorkspaceManager::new(
+            tmp.path().to_path_buf(),
+            Some(db.clone()),
+        )),
+    );
+    let fetch = make_tool_call_with_args(
+        "call_1",
+        "web_fetch",
+        serde_json::json!({"url": "https://example.com"}),
+    );
+    let long = "price ".repeat(50);
+
+    assert!(!summarizer.applies(&fetch, "short", false));
+    assert!(!summarizer.applies(&fetch, &long, true));
+    assert!(!summarizer.applies(&make_tool_call("2", "stash_retrieve"), &long, false));
+    assert!(summarizer.applies(&fetch, &long, false));
+
+    let note = summarizer
+        .summarize(&fetch, &long, Some("telegram:1"))
+        .await
+        .unwrap();
+    assert!(note.starts_with("[Summary of the 'web_fetch' result (300 chars)."));
+    assert!(note.ends_with("\n\nPage lists three prices: 1, 2, 3."));
+
+    let files = db.list_workspace_files(Some("temp"), None, None).unwrap();
+    assert_eq!(files.len(), 1);
+    let saved = tmp.path().canonicalize().unwrap().join(&files[0].path);
+    assert!(note.contains(&saved.display().to_string()), "{note}");
+    assert_eq!(std::fs::read_to_string(&saved).unwrap(), long);
+
+    // Reading the saved result back is not summarized again
+    let read_back = make_tool_call_with_args(
+        "call_2",
+        "read_file",
+        serde_json::json!({"path": saved.display().to_string()}),
+    );
+    assert!(!summarizer.applies(&read_back, &long, false));
+}
+
+#[tokio::test]
+async fn test_cancel_stops_running_turn() {
+    let tmp = tempfile::tempdir().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let slow_call = |id: &str| LLMResponse {
+        tool_calls: vec![make_tool_call(id, "slow")],
+        ..Default::default()
+    };
+    let provider = Arc::new(QueuedProvider::new(vec![
+        slow_call("1"),
+        slow_call("2"),
+        LLMResponse {
+            content: Some("done".to_string()),
+            ..Default::default()
+        },
+    ]));
+    let mut agent = AgentLoop::new(AgentLoopConfig::test_defaults(
+        bus,
+        provider.clone(),
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    ))
+    .await
+    .unwrap();
+    agent.tools = Arc::new(make_registry_with(vec![Arc::new(MockTool {
+        tool_name: "slow".into(),
+        delay_ms: 200,
+        response: "partial".into(),
+    })]));
+
+    let msg = |content: &str| InboundMessage::builder("telegram", "u1", "100", content).build();
+    let (turn, cancel) = tokio::join!(agent.process_message(msg("research this")), async {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        agent.process_message(msg("/cancel")).await
+    });
+
+    // The command itself is silent; the cancelled turn replies
+    assert!(cancel.unwrap().is_none());
+    assert_eq!(turn.unwrap().unwrap().content, cancel::CANCELLED_REPLY);
+    // The second tool round never ran
+    assert_eq!(provider.responses.lock().unwrap().len(), 2);
+
+    let session = agent
+        .profile("telegram:100")
+        .sessions
+        .get_or_create("telegram:100")
+        .await
+        .unwrap();
+    let saved: Vec<&str> = session
+        .messages
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect();
+    assert_eq!(saved, vec!["research this", cancel::CANCELLED_REPLY]);
+
+    let idle = agent
+        .process_message(msg("/cancel"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(idle.content, cancel::NOTHING_TO_CANCEL_REPLY);
+}
+
+#[test]
+fn test_cancel_command_and_turn_registration() {
+    assert!(cancel::is_command(" /cancel "));
+    assert!(cancel::is_command("/CANCEL"));
+    assert!(!cancel::is_command("/cancel that"));
+    assert!(!cancel::is_command("please cancel"));
+
+    let cancels = cancel::TurnCancellations::new();
+    assert!(!cancels.cancel("telegram:100"));
+    let first = cancels.begin("telegram:100");
+    assert!(cancels.cancel("telegram:100"));
+    assert!(cancel::is_cancelled(cancels.flag("telegram:100").as_ref()));
+    drop(first);
+    assert!(cancels.flag("telegram:100").is_none());
+
+    // A new turn starts uncancelled
+    let _second = cancels.begin("telegram:100");
+    assert!(!cancel::is_cancelled(cancels.flag("telegram:100").as_ref()));
+}
+
+#[test]
+fn test_focus_command_parsing() {
+    use focus::{FocusCommand, parse_command};
+
+    assert_eq!(parse_command("/focus"), Some(FocusCommand::Show));
+    assert_eq!(parse_command("  /focus off "), Some(FocusCommand::Off));
+    assert_eq!(
+        parse_command("/focus research"),
+        Some(FocusCommand::Set("research"))
+    );
+    assert_eq!(parse_command("/focused"), None);
+    assert_eq!(parse_command("let's focus"), None);
+}
+
+#[test]
+fn test_focused_allowlist_narrows_router_policy() {
+    let preset = vec!["web_search".to_string(), "memory_search".to_string()];
+    let policy = |tools: &[&str]| crate::router::RoutingPolicy {
+        allowed_tools: tools.iter().map(ToString::to_string).collect(),
+        blocked_tools: vec![],
+        context_hint: None,
+        reason: "semantic_filter",
+    };
+
+    assert_eq!(focus::focused_allowlist(&preset, None), preset);
+    assert_eq!(
+        focus::focused_allowlist(&preset, Some(&policy(&["web_search", "exec"]))),
+        ["web_search"]
+    );
+    // No overlap: the preset wins over the router
+    assert_eq!(
+        focus::focused_allowlist(&preset, Some(&policy(&["exec"]))),
+        preset
+    );
+}
+
+#[tokio::test]
+async fn test_suppress_typing_skips_typing_indicator() {
+    async fn typing_events(suppress_typing: bool) -> usize {
+        let tmp = tempfile::tempdir().unwrap();
+        let bus = Arc::new(crate::bus::MessageBus::default());
+        let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+        let provider: Arc<dyn LLMProvider> = Arc::new(QueuedProvider::new(vec![
+            LLMResponse {
+                tool_calls: vec![make_tool_call("1", "slow")],
+                ..Default::default()
+            },
+            LLMResponse {
+                content: Some("done".to_string()),
+                ..Default::default()
+            },
+        ]));
+        let mut agent = AgentLoop::new(AgentLoopConfig::test_defaults(
+            bus,
+            provider,
+            tmp.path().to_path_buf(),
+            Arc::new(outbound_tx),
+        ))
+        .await
+        .unwrap();
+        agent.tools = Arc::new(make_registry_with(vec![Arc::new(MockTool {
+            tool_name: "slow".into(),
+            delay_ms: 50,
+            response: "ok".into(),
+        })]));
+        let (typing_tx, mut typing_rx) = tokio::sync::mpsc::channel(16);
+        agent.typing_tx = Some(Arc::new(typing_tx));
+        agent.typing_indicators = crate::config::ChannelsConfig::default().typing_indicators();
+
+        let result = agent
+            .run_agent_loop_with_overrides(
+                vec![Message::user("hi".to_string())],
+                Some(("telegram".to_string(), "100".to_string())),
+                &ExecutionContext::default(),
+                &AgentRunOverrides {
+                    suppress_typing,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.content.as_deref(), Some("done"));
+        let mut events = 0;
+        while typing_rx.try_recv().is_ok() {
+            events += 1;
+        }
+        events
+    }
+
+    assert!(typing_events(false).await > 0);
+    assert_eq!(typing_events(true).await, 0);
+}
+
+/// Answers every request with a fixed summary, counting the calls.
+struct CountingSummaryProvider {
+    calls: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait]
+impl LLMProvider for CountingSummaryProvider {
+    async fn chat(&self, _req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(LLMResponse {
+            content: Some("Earlier chat summary.".to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn default_model(&self) -> &'static str {
+        "mock-model"
+    }
+}
+
+#[tokio::test]
+async fn test_message_cap_counts_only_messages_since_compaction() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let bus = Arc::new(crate::bus::MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider = Arc::new(CountingSummaryProvider {
+        calls: std::sync::atomic::AtomicUsize::new(0),
+    });
+    let mut config = AgentLoopConfig::test_defaults(
+        bus,
+        provider.clone(),
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    );
+    config.compaction_config.enabled = true;
+    config.compaction_config.threshold_tokens = 1_000_000;
+    config.compaction_config.max_messages = Some(6);
+    config.compaction_config.keep_recent = 2;
+    let agent = AgentLoop::new(config).await.unwrap();
+    let calls = || provider.calls.load(std::sync::atomic::Ordering::SeqCst);
+
+    let key = "telegram:cap";
+    let sessions = agent.profile(key).sessions.clone();
+    let add_turn = |session: &mut crate::session::Session, n: usize| {
+        session.add_message("user", format!("question {n}"), HashMap::new());
+        session.add_message("assistant", format!("answer {n}"), HashMap::new());
+    };
+    let mut session = sessions.get_or_create(key).await.unwrap();
+    for n in 0..3 {
+        add_turn(&mut session, n);
+    }
+    sessions.save(&session).await.unwrap();
+    agent.get_compacted_history(&session).await.unwrap();
+    assert_eq!(calls(), 1);
+
+    // The next turn leaves 4 uncompacted messages, under the cap of 6
+    let mut session = sessions.get_or_create(key).await.unwrap();
+    add_turn(&mut session, 3);
+    sessions.save(&session).await.unwrap();
+    agent.get_compacted_history(&session).await.unwrap();
+    assert_eq!(calls(), 1);
+
+    // One more turn reaches the cap again
+    let mut session = sessions.get_or_create(key).await.unwrap();
+    add_turn(&mut session, 4);
+    sessions.save(&session).await.unwrap();
+    agent.get_compacted_history(&session).await.unwrap();
+    assert_eq!(calls(), 2);
+}
@@ -0,0 +1,103 @@
+//! Per-turn token breakdown (`agents.defaults.tokenBreakdown`): the input of
+//! every LLM call in a turn is estimated per message category, summed, and
+//! written to `token_breakdown_log` when the turn ends.
+
+use super::AgentLoop;
+use crate::agent::compaction::{estimate_messages_tokens, estimate_tokens};
+use crate::agent::memory::memory_db::TokenBreakdown;
+use crate::agent::tools::base::ExecutionContext;
+use crate::providers::base::{Message, ToolDefinition};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// `message` in the shape `estimate_messages_tokens` reads.
+fn estimate_input(message: &Message) -> HashMap<String, Value> {
+    let mut map = HashMap::from([("content".to_string(), json!(message.content))]);
+    if let Some(tool_calls) = &message.tool_calls {
+        let calls = tool_calls
+            .iter()
+            .map(|tc| json!({"name": tc.name, "arguments": tc.arguments}))
+            .collect();
+        map.insert("tool_calls".to_string(), Value::Array(calls));
+    }
+    map
+}
+
+fn tokens(messages: &[HashMap<String, Value>]) -> u64 {
+    estimate_messages_tokens(messages) as u64
+}
+
+/// Estimated tokens of the tool schemas sent with each call.
+pub(super) fn tool_definition_tokens(tools: &[ToolDefinition]) -> u64 {
+    serde_json::to_string(tools).map_or(0, |s| estimate_tokens(&s) as u64)
+}
+
+/// Add one LLM call to `breakdown`. Messages before `run_start` predate the
+/// turn; the last of them is the user message that started it. System
+/// messages, and user-role nudges injected during the turn, count as system.
+pub(super) fn add_call(
+    breakdown: &mut TokenBreakdown,
+    messages: &[Message],
+    run_start: usize,
+    tool_definitions: u64,
+    provider_input: Option<u64>,
+) {
+    let mut system = Vec::new();
+    let mut history = Vec::new();
+    let mut message = Vec::new();
+    let mut tool_results = Vec::new();
+    let mut assistant = Vec::new();
+    for (i, m) in messages.iter().enumerate() {
+        let bucket = match m.role.as_str() {
+            "system" => &mut system,
+            "tool" => &mut tool_results,
+            "assistant" if i >= run_start => &mut assistant,
+            "user" if i + 1 == run_start => &mut message,
+            _ if i < run_start => &mut history,
+            _ => &mut system,
+        };
+        bucket.push(estimate_input(m));
+    }
+    breakdown.calls += 1;
+    breakdown.system += tokens(&system);
+    breakdown.tool_definitions += tool_definitions;
+    breakdown.history += tokens(&history);
+    breakdown.message += tokens(&message);
+    breakdown.tool_results += tokens(&tool_results);
+    breakdown.assistant += tokens(&assistant);
+    breakdown.provider_input += provider_input.unwrap_or(0);
+}
+
+impl AgentLoop {
+    /// Write the breakdown of a finished turn (fire-and-forget).
+    pub(super) fn record_token_breakdown(
+        &self,
+        exec_ctx: &ExecutionContext,
+        model: &str,
+        request_id: Option<&str>,
+        breakdown: TokenBreakdown,
+    ) {
+        if breakdown.calls == 0 {
+            return;
+        }
+        let session_key = exec_ctx
+            .metadata
+            .get(super::iteration::SESSION_KEY_META_KEY)
+            .and_then(Value::as_str)
+            .map_or_else(
+                || format!("{}:{}", exec_ctx.channel, exec_ctx.chat_id),
+                ToString::to_string,
+            );
+        let db = self.memory.db();
+        let model = model.to_string();
+        let request_id = request_id.map(ToString::to_string);
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) =
+                db.record_token_breakdown(&session_key, request_id.as_deref(), &model, &breakdown)
+            {
+                warn!("failed to record token breakdown: {}", e);
+            }
+        });
+    }
+}
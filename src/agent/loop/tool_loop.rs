@@ -0,0 +1,76 @@
+//! Detection of the model repeating one tool call (`agents.defaults.toolLoop`).
+//! Calls are compared by tool name and arguments, in order, across the
+//! iterations of a turn. The call that makes a run of identical calls
+//! `threshold` long is not run, nor are further repeats.
+
+use crate::agent::tools::base::ToolResult;
+use crate::providers::base::ToolCallRequest;
+
+/// Result given to a repeated call in place of running it.
+const SKIPPED: &str = "Not run: this call is identical to your previous calls of this tool.";
+
+pub(super) struct ToolLoopDetector {
+    threshold: u32,
+    last: Option<(String, String)>,
+    count: u32,
+}
+
+impl ToolLoopDetector {
+    /// `threshold` of 0 never reports a loop.
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            last: None,
+            count: 0,
+        }
+    }
+
+    /// Record `calls` in order. Returns, per call, whether it repeats the
+    /// previous call often enough that it should not be run.
+    pub fn observe(&mut self, calls: &[ToolCallRequest]) -> Vec<bool> {
+        calls
+            .iter()
+            .map(|tc| {
+                let signature = (tc.name.clone(), tc.arguments.to_string());
+                if self.last.as_ref() == Some(&signature) {
+                    self.count += 1;
+                } else {
+                    self.last = Some(signature);
+                    self.count = 1;
+                }
+                self.threshold > 0 && self.count >= self.threshold
+            })
+            .collect()
+    }
+
+    /// How many times in a row the last observed call was made.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+}
+
+/// Merge the results of the calls that ran with placeholders for the skipped
+/// repeats, keeping call order.
+pub(super) fn merge_results(repeats: &[bool], ran: Vec<ToolResult>) -> Vec<ToolResult> {
+    let mut ran = ran.into_iter();
+    repeats
+        .iter()
+        .map(|&repeat| {
+            if repeat {
+                ToolResult::error(SKIPPED)
+            } else {
+                ran.next()
+                    .unwrap_or_else(|| ToolResult::error("Tool execution result was lost"))
+            }
+        })
+        .collect()
+}
+
+/// System note telling the model it is stuck.
+pub(super) fn nudge(tool: &str, count: u32) -> String {
+    format!(
+        "You have called {tool} with the same arguments {count} times in a row; the result \
+         will not change, so the last call was not run. Try a different approach, or answer \
+         the user with what you have."
+    )
+}
@@ -0,0 +1,118 @@
+use crate::agent::workspace::{FileCategory, WorkspaceManager};
+use crate::providers::base::{ChatRequest, LLMProvider, Message, ToolCallRequest};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const SUMMARY_PROMPT: &str = "Summarize this tool result for an assistant that is in the \
+     middle of a task. Keep every fact, number, name, identifier, path, URL and error message \
+     that could matter; drop boilerplate, markup and repetition. Reply with the summary only.";
+const SUMMARY_MAX_TOKENS: u32 = 1024;
+/// File name prefix of saved full results. Reading one back is never summarized.
+const SAVED_PREFIX: &str = "tool-result-";
+
+/// Replaces large tool results with a cheap-model summary and keeps the full
+/// text in a workspace temp file (`agents.defaults.toolResultSummary`).
+pub(super) struct ToolResultSummarizer {
+    provider: Arc<dyn LLMProvider>,
+    model: String,
+    threshold_chars: usize,
+    workspace: Arc<WorkspaceManager>,
+}
+
+impl ToolResultSummarizer {
+    pub(super) fn new(
+        provider: Arc<dyn LLMProvider>,
+        model: String,
+        threshold_chars: usize,
+        workspace: Arc<WorkspaceManager>,
+    ) -> Self {
+        Self {
+            provider,
+            model,
+            threshold_chars,
+            workspace,
+        }
+    }
+
+    /// Whether this result is long enough to summarize. Errors, stash
+    /// retrievals and reads of a saved full result are left alone.
+    pub(super) fn applies(&self, tc: &ToolCallRequest, content: &str, is_error: bool) -> bool {
+        !is_error
+            && tc.name != "stash_retrieve"
+            && content.chars().count() > self.threshold_chars
+            && !tc.arguments.to_string().contains(SAVED_PREFIX)
+    }
+
+    /// Save `content` to a temp file and return the summary with a pointer to
+    /// that file, ready to stand in for the result.
+    pub(super) async fn summarize(
+        &self,
+        tc: &ToolCallRequest,
+        content: &str,
+        session_key: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let path = self.save(tc, content, session_key).await?;
+        let request = ChatRequest::builder(
+            vec![
+                Message::system(SUMMARY_PROMPT),
+                Message::user(format!(
+                    "Tool: {}\nArguments: {}\n\nResult:\n{}",
+                    tc.name, tc.arguments, content
+                )),
+            ],
+            SUMMARY_MAX_TOKENS,
+        )
+        .model(self.model.clone())
+        .build();
+        let response = self.provider.chat(&request).await?;
+        let summary = response.content.unwrap_or_default();
+        if summary.trim().is_empty() {
+            anyhow::bail!("model returned an empty summary");
+        }
+        Ok(summary_note(
+            &tc.name,
+            content.chars().count(),
+            &path,
+            summary.trim(),
+        ))
+    }
+
+    async fn save(
+        &self,
+        tc: &ToolCallRequest,
+        content: &str,
+        session_key: Option<&str>,
+    ) -> anyhow::Result<PathBuf> {
+        let id: String = tc
+            .id
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .take(24)
+            .collect();
+        let path = self.workspace.resolve_path(
+            &format!("{SAVED_PREFIX}{}-{id}.txt", tc.name),
+            Some(FileCategory::Temp),
+        );
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, content).await?;
+        self.workspace
+            .register_file(&path, Some(&tc.name), session_key)?;
+        Ok(path)
+    }
+}
+
+/// Content that replaces a summarized result in the conversation.
+pub(super) fn summary_note(
+    tool_name: &str,
+    chars: usize,
+    path: &std::path::Path,
+    summary: &str,
+) -> String {
+    format!(
+        "[Summary of the '{tool_name}' result ({chars} chars). Full result saved to {}; \
+         read it with read_file if you need details the summary leaves out.]\n\n{summary}",
+        path.display()
+    )
+}
@@ -0,0 +1,96 @@
+//! Fact digest: periodically shows the user what background fact extraction
+//! recorded about them.
+//!
+//! Extracted facts are queued per chat in `fact_digest` when they are written.
+//! Each run lists the pending facts back to the chat they came from, numbered
+//! by entry ID, so the user can reply "forget #N" (handled by the
+//! `memory_search` tool's `forget` action).
+
+use crate::agent::memory::MemoryDB;
+use crate::agent::memory::memory_db::PendingFact;
+use crate::bus::{MessageBus, OutboundMessage};
+use crate::config::FactDigestConfig;
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Build the digest message for one chat.
+pub(crate) fn format_digest(facts: &[&PendingFact]) -> String {
+    let lines: Vec<String> = facts
+        .iter()
+        .map(|f| {
+            let text = f.content.trim();
+            let text = text.strip_prefix("- ").unwrap_or(text);
+            format!("#{} {}", f.entry_id, text)
+        })
+        .collect();
+    format!(
+        "I noted these things from our recent conversations:\n{}\n\nReply \"forget #N\" to remove anything that's wrong.",
+        lines.join("\n")
+    )
+}
+
+pub struct FactDigest {
+    db: Arc<MemoryDB>,
+    bus: Arc<MessageBus>,
+    config: FactDigestConfig,
+}
+
+impl FactDigest {
+    pub fn new(db: Arc<MemoryDB>, bus: Arc<MessageBus>, config: FactDigestConfig) -> Self {
+        Self { db, bus, config }
+    }
+
+    /// Send one digest to every chat with pending facts. Returns the number of
+    /// digests sent.
+    pub async fn run_once(&self) -> Result<usize> {
+        let db = self.db.clone();
+        let pending = tokio::task::spawn_blocking(move || db.list_pending_fact_digest()).await??;
+
+        // Group by chat, keeping extraction order
+        let mut chats: Vec<((&str, &str), Vec<&PendingFact>)> = Vec::new();
+        for fact in &pending {
+            let key = (fact.channel.as_str(), fact.chat_id.as_str());
+            match chats.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, facts)) => facts.push(fact),
+                None => chats.push((key, vec![fact])),
+            }
+        }
+
+        let mut sent = 0;
+        for ((channel, chat_id), mut facts) in chats {
+            facts.truncate(self.config.max_facts.max(1));
+            let msg = OutboundMessage::builder(channel, chat_id, format_digest(&facts))
+                .meta(crate::bus::meta::BACKGROUND, serde_json::Value::Bool(true))
+                .build();
+            if let Err(e) = self.bus.publish_outbound(msg).await {
+                warn!("fact digest: failed to send to {channel}:{chat_id}: {e}");
+                continue;
+            }
+            let ids: Vec<i64> = facts.iter().map(|f| f.entry_id).collect();
+            self.db.mark_fact_digest_sent(&ids)?;
+            sent += 1;
+        }
+        Ok(sent)
+    }
+
+    /// Spawn the periodic digest loop. The first digest goes out one interval
+    /// after startup.
+    pub fn spawn_periodic(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(self.config.interval_hours.max(1) * 3600);
+            loop {
+                tokio::time::sleep(interval).await;
+                match self.run_once().await {
+                    Ok(0) => debug!("fact digest: nothing new to report"),
+                    Ok(n) => info!("fact digest: sent to {n} chat(s)"),
+                    Err(e) => warn!("fact digest run failed: {e}"),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,91 @@
+use super::*;
+
+fn digest(db: Arc<MemoryDB>, max_facts: usize) -> (FactDigest, Arc<MessageBus>) {
+    let bus = Arc::new(MessageBus::new(30, 60.0, 100, 100));
+    let digest = FactDigest::new(
+        db,
+        bus.clone(),
+        FactDigestConfig {
+            enabled: true,
+            max_facts,
+            ..Default::default()
+        },
+    );
+    (digest, bus)
+}
+
+fn queue(db: &MemoryDB, content: &str, channel: &str, chat_id: &str) -> i64 {
+    let id = db
+        .insert_memory_entry("daily:2026-01-01:Facts", content)
+        .unwrap()
+        .unwrap();
+    db.queue_fact_digest(id, channel, chat_id).unwrap();
+    id
+}
+
+#[test]
+fn test_format_digest_numbers_by_entry_id() {
+    let fact = PendingFact {
+        entry_id: 42,
+        channel: "telegram".to_string(),
+        chat_id: "1".to_string(),
+        content: "- User has a dog named Rex".to_string(),
+    };
+    let text = format_digest(&[&fact]);
+    assert!(text.contains("#42 User has a dog named Rex"));
+    assert!(text.contains("forget #N"));
+}
+
+#[tokio::test]
+async fn test_run_once_sends_one_digest_per_chat() {
+    let db = Arc::new(MemoryDB::new(":memory:").unwrap());
+    let a = queue(&db, "- User has a dog named Rex", "telegram", "1");
+    let b = queue(&db, "- User works night shifts", "telegram", "1");
+    let c = queue(&db, "- User lives in Lisbon", "slack", "C9");
+    let (digest, bus) = digest(db.clone(), 20);
+    let mut rx = bus.take_outbound_rx().unwrap();
+
+    assert_eq!(digest.run_once().await.unwrap(), 2);
+    let first = rx.try_recv().unwrap();
+    assert_eq!(
+        (first.channel.as_str(), first.chat_id.as_str()),
+        ("telegram", "1")
+    );
+    assert!(
+        first
+            .content
+            .contains(&format!("#{a} User has a dog named Rex"))
+    );
+    assert!(
+        first
+            .content
+            .contains(&format!("#{b} User works night shifts"))
+    );
+    let second = rx.try_recv().unwrap();
+    assert_eq!(second.chat_id, "C9");
+    assert!(second.content.contains(&format!("#{c} ")));
+
+    // Everything was marked sent
+    assert_eq!(digest.run_once().await.unwrap(), 0);
+    assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_run_once_caps_facts_per_chat() {
+    let db = Arc::new(MemoryDB::new(":memory:").unwrap());
+    queue(&db, "- fact number one here", "telegram", "1");
+    let second = queue(&db, "- fact number two here", "telegram", "1");
+    let (digest, bus) = digest(db.clone(), 1);
+    let mut rx = bus.take_outbound_rx().unwrap();
+
+    digest.run_once().await.unwrap();
+    assert!(!rx.try_recv().unwrap().content.contains("two"));
+    // The overflow waits for the next digest
+    digest.run_once().await.unwrap();
+    assert!(
+        rx.try_recv()
+            .unwrap()
+            .content
+            .contains(&format!("#{second} fact number two"))
+    );
+}
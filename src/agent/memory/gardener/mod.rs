@@ -0,0 +1,164 @@
+//! Memory gardener: periodically flags contradictory memory entries.
+//!
+//! Pairs of entries with highly similar embeddings are sent to the LLM, which
+//! judges whether they contradict each other. Contradictions are recorded in
+//! `memory_conflicts` and resolved by the user via the `memory_search` tool.
+
+use crate::agent::memory::MemoryDB;
+use crate::agent::memory::memory_db::{SimilarPair, conflict_status};
+use crate::config::MemoryGardenerConfig;
+use crate::providers::base::{ChatRequest, LLMProvider, Message};
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+const GARDENER_PROMPT: &str = "You maintain the long-term memory of a personal assistant. Decide whether these two stored memories contradict each other, meaning they cannot both be true now (for example, a preference or fact that changed).\n\nOlder memory: {older}\nNewer memory: {newer}\n\nReply with exactly one line, either:\nCONTRADICTION: <short explanation>\nor:\nCONSISTENT";
+
+const GARDENER_MAX_TOKENS: u32 = 200;
+const GARDENER_TEMPERATURE: Option<f32> = Some(0.0);
+/// Delay before the first run after startup, so it doesn't compete with
+/// startup indexing and hygiene.
+const GARDENER_STARTUP_DELAY: Duration = Duration::from_secs(300);
+
+/// LLM judgment for a pair of similar memories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    Contradiction(String),
+    Consistent,
+}
+
+/// Parse the LLM reply. Returns `None` when the reply matches neither form,
+/// leaving the pair unjudged so it is retried on the next run.
+pub(crate) fn parse_verdict(reply: &str) -> Option<Verdict> {
+    let line = reply.trim().lines().next()?.trim();
+    let upper = line.to_ascii_uppercase();
+    if upper.starts_with("CONTRADICTION") {
+        let explanation = line["CONTRADICTION".len()..]
+            .trim_start_matches([':', '-', ' '])
+            .trim();
+        Some(Verdict::Contradiction(explanation.to_string()))
+    } else if upper.starts_with("CONSISTENT") {
+        Some(Verdict::Consistent)
+    } else {
+        None
+    }
+}
+
+/// Summary of a single gardener run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GardenerReport {
+    pub pairs_checked: usize,
+    pub contradictions: usize,
+}
+
+pub struct MemoryGardener {
+    db: Arc<MemoryDB>,
+    provider: Arc<dyn LLMProvider>,
+    model: Option<String>,
+    temperature_override: Option<f32>,
+    config: MemoryGardenerConfig,
+}
+
+impl MemoryGardener {
+    pub fn new(
+        db: Arc<MemoryDB>,
+        provider: Arc<dyn LLMProvider>,
+        model: Option<String>,
+        temperature_override: Option<f32>,
+        config: MemoryGardenerConfig,
+    ) -> Self {
+        Self {
+            db,
+            provider,
+            model,
+            temperature_override,
+            config,
+        }
+    }
+
+    /// Run one gardening pass: find unjudged similar pairs and ask the LLM
+    /// about each one.
+    pub async fn run_once(&self) -> Result<GardenerReport> {
+        let db = self.db.clone();
+        let threshold = self.config.similarity_threshold;
+        let max_pairs = self.config.max_pairs_per_run;
+        let pairs =
+            tokio::task::spawn_blocking(move || db.find_similar_pairs(threshold, max_pairs))
+                .await??;
+
+        let mut report = GardenerReport::default();
+        for pair in &pairs {
+            match self.judge(pair).await {
+                Ok(Some(verdict)) => {
+                    report.pairs_checked += 1;
+                    let (status, explanation) = match &verdict {
+                        Verdict::Contradiction(why) => {
+                            report.contradictions += 1;
+                            (conflict_status::OPEN, Some(why.as_str()))
+                        }
+                        Verdict::Consistent => (conflict_status::CONSISTENT, None),
+                    };
+                    self.db.record_memory_conflict(pair, status, explanation)?;
+                }
+                Ok(None) => {
+                    debug!(
+                        "memory gardener: unparseable verdict for entries {} and {}",
+                        pair.older_id, pair.newer_id
+                    );
+                }
+                Err(e) => {
+                    warn!("memory gardener: LLM judgment failed: {e}");
+                    break;
+                }
+            }
+        }
+
+        metrics::counter!("oxicrab_memory_conflicts_detected_total")
+            .increment(report.contradictions as u64);
+        Ok(report)
+    }
+
+    async fn judge(&self, pair: &SimilarPair) -> Result<Option<Verdict>> {
+        let prompt = GARDENER_PROMPT
+            .replace("{older}", &pair.older_content)
+            .replace("{newer}", &pair.newer_content);
+        let response = self
+            .provider
+            .chat(&ChatRequest {
+                messages: vec![Message::user(prompt)],
+                model: self.model.clone(),
+                max_tokens: GARDENER_MAX_TOKENS,
+                temperature: self.temperature_override.map_or(GARDENER_TEMPERATURE, Some),
+                ..Default::default()
+            })
+            .await?;
+        Ok(parse_verdict(&response.content.unwrap_or_default()))
+    }
+
+    /// Spawn the periodic gardening loop. Runs once shortly after startup and
+    /// then every `intervalHours`.
+    pub fn spawn_periodic(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tokio::time::sleep(GARDENER_STARTUP_DELAY).await;
+            let interval = Duration::from_secs(self.config.interval_hours.max(1) * 3600);
+            loop {
+                match self.run_once().await {
+                    Ok(report) if report.contradictions > 0 => info!(
+                        "memory gardener: {} new contradiction(s) flagged from {} pair(s)",
+                        report.contradictions, report.pairs_checked
+                    ),
+                    Ok(report) => debug!(
+                        "memory gardener: no contradictions in {} pair(s)",
+                        report.pairs_checked
+                    ),
+                    Err(e) => warn!("memory gardener run failed: {e}"),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,114 @@
+use super::*;
+use crate::agent::memory::embeddings::serialize_embedding;
+use crate::providers::base::LLMResponse;
+use async_trait::async_trait;
+
+struct VerdictMock {
+    reply: String,
+}
+
+#[async_trait]
+impl LLMProvider for VerdictMock {
+    async fn chat(&self, _req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        Ok(LLMResponse {
+            content: Some(self.reply.clone()),
+            ..Default::default()
+        })
+    }
+    fn default_model(&self) -> &'static str {
+        "mock"
+    }
+}
+
+fn seeded_db(dir: &std::path::Path) -> Arc<MemoryDB> {
+    let db = Arc::new(MemoryDB::new(dir.join("memory.db")).unwrap());
+    db.insert_memory("daily:2026-01-01:Facts", "User prefers tea")
+        .unwrap();
+    db.insert_memory("daily:2026-02-01:Facts", "User prefers coffee")
+        .unwrap();
+    let mut missing = db.get_entries_missing_embeddings().unwrap();
+    missing.sort_by_key(|(id, _, _)| *id);
+    for ((id, _, _), emb) in missing.iter().zip([[1.0f32, 0.0], [0.99, 0.141]]) {
+        db.store_embedding(*id, &serialize_embedding(&emb)).unwrap();
+    }
+    db
+}
+
+fn gardener(db: Arc<MemoryDB>, reply: &str) -> MemoryGardener {
+    MemoryGardener::new(
+        db,
+        Arc::new(VerdictMock {
+            reply: reply.to_string(),
+        }),
+        None,
+        None,
+        MemoryGardenerConfig {
+            enabled: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn test_parse_verdict() {
+    assert_eq!(
+        parse_verdict("CONTRADICTION: tea vs coffee"),
+        Some(Verdict::Contradiction("tea vs coffee".to_string()))
+    );
+    assert_eq!(
+        parse_verdict("  contradiction - changed drink\nmore text"),
+        Some(Verdict::Contradiction("changed drink".to_string()))
+    );
+    assert_eq!(parse_verdict("CONSISTENT"), Some(Verdict::Consistent));
+    assert_eq!(parse_verdict("I am not sure"), None);
+    assert_eq!(parse_verdict(""), None);
+}
+
+#[tokio::test]
+async fn test_run_once_flags_contradiction() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(dir.path());
+    let g = gardener(db.clone(), "CONTRADICTION: drink preference changed");
+
+    let report = g.run_once().await.unwrap();
+    assert_eq!(
+        report,
+        GardenerReport {
+            pairs_checked: 1,
+            contradictions: 1
+        }
+    );
+    let open = db
+        .list_memory_conflicts(Some(conflict_status::OPEN))
+        .unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(
+        open[0].explanation.as_deref(),
+        Some("drink preference changed")
+    );
+
+    // Judged pairs are skipped on the next run.
+    let report = g.run_once().await.unwrap();
+    assert_eq!(report.pairs_checked, 0);
+}
+
+#[tokio::test]
+async fn test_run_once_records_consistent_pairs() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = seeded_db(dir.path());
+    let g = gardener(db.clone(), "CONSISTENT");
+
+    let report = g.run_once().await.unwrap();
+    assert_eq!(report.contradictions, 0);
+    assert!(
+        db.list_memory_conflicts(Some(conflict_status::OPEN))
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        db.list_memory_conflicts(Some(conflict_status::CONSISTENT))
+            .unwrap()
+            .len(),
+        1
+    );
+}
@@ -8,3 +8,6 @@ pub use oxicrab_memory::remember;
 
 pub use oxicrab_memory::MemoryDB;
 pub use oxicrab_memory::MemoryStore;
+
+pub mod digest;
+pub mod gardener;
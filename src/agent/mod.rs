@@ -5,10 +5,12 @@ pub mod cognitive;
 pub mod compaction;
 pub mod context;
 pub mod memory;
+pub mod profile;
 pub mod skills;
 pub mod subagent;
 pub mod tools;
 pub mod truncation;
+pub mod weather;
 pub mod workspace;
 
 pub use agent_loop::{
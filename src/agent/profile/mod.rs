@@ -0,0 +1,149 @@
+//! Profiles: separate workspaces selected by the channel and chat a message
+//! arrives on.
+//!
+//! Each profile has its own workspace directory, memory database (and with
+//! it the session store) and [`ContextBuilder`], so work and personal chats
+//! never see each other's history, memories or prompt files. A profile is
+//! resolved from the session key (`channel:chat_id`), which every session
+//! code path already has.
+
+use crate::agent::context::ContextBuilder;
+use crate::agent::context::providers::ContextProviderRunner;
+use crate::agent::memory::MemoryStore;
+use crate::config::{MemoryConfig, ProfileConfig};
+use crate::session::{SessionManager, SessionStore};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Name of the profile used when no configured profile matches.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// One workspace and the stores rooted in it.
+pub struct Profile {
+    pub name: String,
+    pub workspace: PathBuf,
+    pub memory: Arc<MemoryStore>,
+    pub sessions: Arc<dyn SessionStore>,
+    pub context: Arc<Mutex<ContextBuilder>>,
+}
+
+impl Profile {
+    /// Wrap stores that were already opened.
+    pub fn from_parts(
+        name: impl Into<String>,
+        workspace: PathBuf,
+        memory: Arc<MemoryStore>,
+        sessions: Arc<dyn SessionStore>,
+        context: Arc<Mutex<ContextBuilder>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            workspace,
+            memory,
+            sessions,
+            context,
+        }
+    }
+
+    /// Create the workspace directory if needed and open its memory
+    /// database and context builder.
+    pub fn open(
+        name: impl Into<String>,
+        workspace: PathBuf,
+        memory_config: Option<&MemoryConfig>,
+        sender_preferences: bool,
+        persona_suffixes: &HashMap<String, String>,
+        providers: Option<Arc<ContextProviderRunner>>,
+    ) -> Result<Self> {
+        std::fs::create_dir_all(&workspace)?;
+        let memory = Arc::new(match memory_config {
+            Some(cfg) => MemoryStore::with_config(&workspace, cfg)?,
+            None => MemoryStore::new(&workspace)?,
+        });
+        let mut context = ContextBuilder::with_memory(&workspace, memory.clone())?;
+        if let Some(runner) = providers {
+            context.set_providers(runner);
+        }
+        context.set_sender_preferences(sender_preferences);
+        context.set_persona_suffixes(persona_suffixes.clone());
+        let sessions: Arc<dyn SessionStore> = Arc::new(SessionManager::with_db(memory.db()));
+        Ok(Self::from_parts(
+            name,
+            workspace,
+            memory,
+            sessions,
+            Arc::new(Mutex::new(context)),
+        ))
+    }
+}
+
+/// Maps a channel and chat to the [`Profile`] that handles it. Profiles are
+/// checked in config order; the first match wins.
+pub struct ProfileResolver {
+    default: Arc<Profile>,
+    profiles: Vec<(ProfileConfig, Arc<Profile>)>,
+}
+
+impl ProfileResolver {
+    /// A resolver with only the default profile.
+    pub fn new(default: Arc<Profile>) -> Self {
+        Self {
+            default,
+            profiles: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, config: ProfileConfig, profile: Arc<Profile>) {
+        self.profiles.push((config, profile));
+    }
+
+    /// Workspace directory of `config`. Relative paths resolve against the
+    /// default workspace `base`; unset means `profiles/<name>` next to `base`
+    /// (`~/.oxicrab/profiles/<name>` by default), so the default profile's
+    /// workspace never contains another profile's files.
+    pub fn workspace_for(config: &ProfileConfig, base: &Path) -> PathBuf {
+        match config.workspace.as_deref() {
+            Some(ws) => {
+                let path = oxicrab_core::utils::get_workspace_path(ws);
+                if path.is_absolute() {
+                    path
+                } else {
+                    base.join(path)
+                }
+            }
+            None => base
+                .parent()
+                .unwrap_or(base)
+                .join("profiles")
+                .join(&config.name),
+        }
+    }
+
+    pub fn resolve(&self, channel: &str, chat_id: &str) -> &Arc<Profile> {
+        self.profiles
+            .iter()
+            .find(|(config, _)| config.matches(channel, chat_id))
+            .map_or(&self.default, |(_, profile)| profile)
+    }
+
+    /// Profile for a `channel:chat_id` session key.
+    pub fn for_session_key(&self, session_key: &str) -> &Arc<Profile> {
+        let (channel, chat_id) = session_key.split_once(':').unwrap_or((session_key, ""));
+        self.resolve(channel, chat_id)
+    }
+
+    pub fn default_profile(&self) -> &Arc<Profile> {
+        &self.default
+    }
+
+    /// The default profile followed by the configured ones.
+    pub fn all(&self) -> impl Iterator<Item = &Arc<Profile>> {
+        std::iter::once(&self.default).chain(self.profiles.iter().map(|(_, p)| p))
+    }
+}
+
+#[cfg(test)]
+mod tests;
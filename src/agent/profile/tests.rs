@@ -0,0 +1,97 @@
+use super::*;
+
+fn profile_config(name: &str, channels: &[&str], chats: &[&str]) -> ProfileConfig {
+    ProfileConfig {
+        name: name.to_string(),
+        workspace: None,
+        channels: channels.iter().map(ToString::to_string).collect(),
+        chats: chats.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn open(name: &str, workspace: &Path) -> Arc<Profile> {
+    Arc::new(
+        Profile::open(
+            name,
+            workspace.to_path_buf(),
+            None,
+            false,
+            &HashMap::new(),
+            None,
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn test_resolve_first_matching_profile_else_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base = &tmp.path().join("workspace");
+    let mut resolver = ProfileResolver::new(open(DEFAULT_PROFILE, base));
+    let work = profile_config("work", &["slack"], &[]);
+    let family = profile_config("family", &[], &["-100123*"]);
+    resolver.add(
+        work.clone(),
+        open("work", &ProfileResolver::workspace_for(&work, base)),
+    );
+    resolver.add(
+        family.clone(),
+        open("family", &ProfileResolver::workspace_for(&family, base)),
+    );
+
+    assert_eq!(resolver.resolve("slack", "C042").name, "work");
+    assert_eq!(resolver.resolve("telegram", "-1001234567").name, "family");
+    assert_eq!(resolver.resolve("telegram", "55512").name, DEFAULT_PROFILE);
+    assert_eq!(resolver.for_session_key("slack:C042:thread").name, "work");
+    assert_eq!(resolver.for_session_key("cli").name, DEFAULT_PROFILE);
+    assert_eq!(resolver.all().count(), 3);
+}
+
+#[test]
+fn test_profiles_have_separate_memory() {
+    let tmp = tempfile::tempdir().unwrap();
+    let base = &tmp.path().join("workspace");
+    let mut resolver = ProfileResolver::new(open(DEFAULT_PROFILE, base));
+    let work = profile_config("work", &["slack"], &[]);
+    resolver.add(
+        work.clone(),
+        open("work", &ProfileResolver::workspace_for(&work, base)),
+    );
+
+    let work = resolver.resolve("slack", "C042");
+    assert_eq!(work.workspace, tmp.path().join("profiles").join("work"));
+    assert!(!work.workspace.starts_with(base));
+    work.memory
+        .append_today("The Q3 launch slipped to May")
+        .unwrap();
+
+    let personal = resolver.resolve("telegram", "55512");
+    assert!(
+        personal
+            .memory
+            .get_recent_daily_entries(10)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(work.memory.get_recent_daily_entries(10).unwrap().len(), 1);
+}
+
+#[test]
+fn test_workspace_for_relative_and_absolute_paths() {
+    let base = Path::new("/srv/oxicrab/workspace");
+    let mut config = profile_config("work", &["slack"], &[]);
+    assert_eq!(
+        ProfileResolver::workspace_for(&config, base),
+        PathBuf::from("/srv/oxicrab/profiles/work")
+    );
+    config.workspace = Some("office".into());
+    assert_eq!(
+        ProfileResolver::workspace_for(&config, base),
+        base.join("office")
+    );
+    config.workspace = Some("/data/work".into());
+    assert_eq!(
+        ProfileResolver::workspace_for(&config, base),
+        PathBuf::from("/data/work")
+    );
+}
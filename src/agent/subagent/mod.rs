@@ -2,7 +2,7 @@ mod activity_log;
 
 use crate::agent::memory::memory_db::MemoryDB;
 use crate::agent::tools::ToolRegistry;
-use crate::bus::{InboundMessage, MessageBus};
+use crate::bus::{InboundMessage, MessageBus, OutboundMessage};
 use crate::config::PromptGuardConfig;
 use crate::providers::base::{LLMProvider, Message};
 use crate::safety::LeakDetector;
@@ -31,7 +31,15 @@ pub struct SubagentConfig {
     pub model: Option<String>,
     pub max_tokens: u32,
     pub tool_temperature: Option<f32>,
+    /// Reasoning budget for subagent LLM calls (None = provider default).
+    pub reasoning_effort: Option<crate::providers::base::ReasoningEffort>,
     pub max_concurrent: usize,
+    /// Subagents one turn of the main agent may start (0 = no limit).
+    pub max_per_turn: usize,
+    /// Post a status line in the origin chat when a subagent starts.
+    pub announce: bool,
+    /// Post each tool call of a subagent as a status line in the origin chat.
+    pub stream_tool_activity: bool,
     /// Prompt guard config for injection scanning on subagent inputs/outputs.
     pub prompt_guard_config: PromptGuardConfig,
     /// Exfiltration guard config — network-outbound tools are excluded unless allow-listed.
@@ -39,10 +47,15 @@ pub struct SubagentConfig {
     /// Main agent's tool registry, used to build subagent tools from capabilities.
     /// Set after `register_all_tools()` returns via `SubagentManager::set_main_tools()`.
     pub main_tools: Option<Arc<ToolRegistry>>,
+    /// Restrict subagents to these tool names (`None` = every tool the
+    /// capability rules allow). Never grants a tool the rules exclude.
+    pub allowed_tools: Option<Vec<String>>,
     /// Memory database for subagent activity logging.
     pub memory_db: Option<Arc<MemoryDB>>,
     /// Shared leak detector with known secrets pre-registered.
     pub leak_detector: Arc<LeakDetector>,
+    /// Retry policy for subagent LLM calls (`providers.retry`).
+    pub retry: crate::providers::base::RetryConfig,
 }
 
 pub struct SubagentManager {
@@ -50,6 +63,9 @@ pub struct SubagentManager {
     running_tasks: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
     bus: Arc<MessageBus>,
     semaphore: Arc<tokio::sync::Semaphore>,
+    max_per_turn: usize,
+    /// Subagents started per main-agent turn (request ID), for `max_per_turn`.
+    turn_spawns: std::sync::Mutex<HashMap<String, usize>>,
 }
 
 /// Resolved immutable config (model already resolved, no Option).
@@ -59,12 +75,17 @@ struct SubagentInner {
     model: String,
     max_tokens: u32,
     tool_temperature: Option<f32>,
+    reasoning_effort: Option<crate::providers::base::ReasoningEffort>,
     prompt_guard: Option<PromptGuard>,
     prompt_guard_config: PromptGuardConfig,
     leak_detector: Arc<LeakDetector>,
     exfil_guard: crate::config::ExfiltrationGuardConfig,
     main_tools: std::sync::OnceLock<Arc<ToolRegistry>>,
+    allowed_tools: Option<Vec<String>>,
     memory_db: Option<Arc<MemoryDB>>,
+    announce: bool,
+    stream_tool_activity: bool,
+    retry: crate::providers::base::RetryConfig,
 }
 
 impl SubagentManager {
@@ -74,7 +95,7 @@ impl SubagentManager {
             .unwrap_or_else(|| config.provider.default_model().to_string());
         let max_concurrent = config.max_concurrent;
         let prompt_guard = if config.prompt_guard_config.enabled {
-            Some(PromptGuard::new())
+            Some(PromptGuard::new(&config.prompt_guard_config.extra_patterns))
         } else {
             None
         };
@@ -84,6 +105,7 @@ impl SubagentManager {
             model,
             max_tokens: config.max_tokens,
             tool_temperature: config.tool_temperature,
+            reasoning_effort: config.reasoning_effort,
             prompt_guard,
             prompt_guard_config: config.prompt_guard_config,
             leak_detector: config.leak_detector,
@@ -95,16 +117,48 @@ impl SubagentManager {
                 }
                 lock
             },
+            allowed_tools: config.allowed_tools,
             memory_db: config.memory_db,
+            announce: config.announce,
+            stream_tool_activity: config.stream_tool_activity,
+            retry: config.retry,
         });
         Self {
             config: inner,
             running_tasks: Arc::new(Mutex::new(HashMap::new())),
             bus,
             semaphore: Arc::new(tokio::sync::Semaphore::new(max_concurrent)),
+            max_per_turn: config.max_per_turn,
+            turn_spawns: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
+    /// Count a subagent against the budget of the turn `request_id`. Returns
+    /// `false` when the turn has already started `max_per_turn` subagents.
+    pub fn reserve_turn_slot(&self, request_id: &str) -> bool {
+        if self.max_per_turn == 0 {
+            return true;
+        }
+        let Ok(mut spawns) = self.turn_spawns.lock() else {
+            return true;
+        };
+        // Turns are short-lived; drop old counts rather than track expiry
+        if spawns.len() > 1000 {
+            spawns.clear();
+        }
+        let count = spawns.entry(request_id.to_string()).or_default();
+        if *count >= self.max_per_turn {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    /// Subagents one turn may start (0 = no limit).
+    pub fn max_per_turn(&self) -> usize {
+        self.max_per_turn
+    }
+
     pub async fn spawn(
         &self,
         task: String,
@@ -175,6 +229,14 @@ impl SubagentManager {
             "subagent spawned: id={}, origin={}:{}",
             task_id, origin_channel, origin_chat_id
         );
+        if self.config.announce && !silent {
+            publish_status(
+                &self.bus,
+                &(origin_channel, origin_chat_id),
+                format!("Delegating \"{display_label}\" to a subagent…"),
+            )
+            .await;
+        }
         metrics::counter!("oxicrab_subagent_spawned_total").increment(1);
         Ok(format!(
             "Subagent [{display_label}] started (id: {task_id}). I'll notify you when it completes."
@@ -249,7 +311,15 @@ async fn run_subagent(
 
     let result = if let Ok(r) = tokio::time::timeout(
         SUBAGENT_TIMEOUT,
-        run_subagent_inner(config, &task_id, &task, context.as_deref(), &origin),
+        run_subagent_inner(
+            config,
+            bus,
+            &task_id,
+            &label,
+            &task,
+            context.as_deref(),
+            &origin,
+        ),
     )
     .await
     {
@@ -298,7 +368,8 @@ async fn run_subagent(
 /// main agent's tool registry. Tools with `SubagentAccess::Full` are passed
 /// through (network-outbound tools respect the exfil block list), tools with
 /// `SubagentAccess::ReadOnly` are wrapped to expose only read-only actions,
-/// and `SubagentAccess::Denied` tools are excluded.
+/// and `SubagentAccess::Denied` tools are excluded. When `allowed_tools` is
+/// set, only the tools it names are considered.
 fn build_subagent_tools(config: &SubagentInner) -> Result<ToolRegistry> {
     use crate::agent::tools::base::SubagentAccess;
     use crate::agent::tools::read_only_wrapper::ReadOnlyToolWrapper;
@@ -310,6 +381,11 @@ fn build_subagent_tools(config: &SubagentInner) -> Result<ToolRegistry> {
 
     let mut tools = ToolRegistry::new();
     for (name, tool) in main_tools.iter() {
+        if let Some(ref allowed) = config.allowed_tools
+            && !allowed.iter().any(|a| a == name)
+        {
+            continue;
+        }
         let caps = tool.capabilities();
         match caps.subagent_access {
             SubagentAccess::Full => {
@@ -340,7 +416,9 @@ fn build_subagent_tools(config: &SubagentInner) -> Result<ToolRegistry> {
 
 async fn run_subagent_inner(
     config: &SubagentInner,
+    bus: &Arc<MessageBus>,
     task_id: &str,
+    label: &str,
     task: &str,
     context: Option<&str>,
     origin: &(String, String),
@@ -413,9 +491,10 @@ async fn run_subagent_inner(
                     model: Some(config.model.clone()),
                     max_tokens: config.max_tokens,
                     temperature: config.tool_temperature,
+                    reasoning_effort: config.reasoning_effort,
                     ..Default::default()
                 },
-                Some(crate::providers::base::RetryConfig::default()),
+                Some(config.retry.clone()),
             )
             .await?;
 
@@ -455,6 +534,13 @@ async fn run_subagent_inner(
                     l.log_tool_call(&tc.name, &tc.arguments);
                 }
             }
+            if config.stream_tool_activity {
+                let names: Vec<&str> = tool_lookups
+                    .iter()
+                    .map(|(tc, _)| tc.name.as_str())
+                    .collect();
+                publish_status(bus, origin, format!("↳ {label}: {}", names.join(", "))).await;
+            }
 
             // Execute tools in parallel through the registry middleware pipeline
             // (timeout, panic isolation, truncation, caching, logging).
@@ -600,6 +686,20 @@ async fn execute_subagent_tool(
     }
 }
 
+/// Post a status line to the origin chat. Channels edit consecutive status
+/// lines into one message and remove it when the next reply is sent.
+async fn publish_status(bus: &MessageBus, origin: &(String, String), content: String) {
+    let status = OutboundMessage::builder(origin.0.clone(), origin.1.clone(), content)
+        .meta(crate::bus::meta::STATUS, Value::Bool(true))
+        .build();
+    if let Err(e) = bus.publish_outbound(status).await {
+        debug!(
+            "subagent status not sent to {}:{}: {}",
+            origin.0, origin.1, e
+        );
+    }
+}
+
 async fn announce_result(
     bus: &Arc<MessageBus>,
     task_id: &str,
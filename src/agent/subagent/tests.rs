@@ -74,12 +74,18 @@ fn make_manager(provider: Arc<dyn LLMProvider>, max_concurrent: usize) -> Subage
             model: Some("mock".to_string()),
             max_tokens: 1024,
             tool_temperature: Some(0.0),
+            reasoning_effort: None,
             max_concurrent,
+            max_per_turn: 0,
+            announce: false,
+            stream_tool_activity: false,
             prompt_guard_config: crate::config::PromptGuardConfig::default(),
             exfil_guard: crate::config::ExfiltrationGuardConfig::default(),
             main_tools: None,
+            allowed_tools: None,
             memory_db: None,
             leak_detector: Arc::new(crate::safety::LeakDetector::new()),
+            retry: crate::providers::base::RetryConfig::default(),
         },
         bus,
     );
@@ -274,12 +280,18 @@ async fn test_silent_mode_no_bus_message() {
             model: Some("mock".to_string()),
             max_tokens: 1024,
             tool_temperature: Some(0.0),
+            reasoning_effort: None,
             max_concurrent: 5,
+            max_per_turn: 0,
+            announce: false,
+            stream_tool_activity: false,
             prompt_guard_config: crate::config::PromptGuardConfig::default(),
             exfil_guard: crate::config::ExfiltrationGuardConfig::default(),
             main_tools: None,
+            allowed_tools: None,
             memory_db: None,
             leak_detector: Arc::new(crate::safety::LeakDetector::new()),
+            retry: crate::providers::base::RetryConfig::default(),
         },
         bus.clone(),
     );
@@ -325,12 +337,18 @@ async fn test_non_silent_mode_publishes_bus_message() {
             model: Some("mock".to_string()),
             max_tokens: 1024,
             tool_temperature: Some(0.0),
+            reasoning_effort: None,
             max_concurrent: 5,
+            max_per_turn: 0,
+            announce: false,
+            stream_tool_activity: false,
             prompt_guard_config: crate::config::PromptGuardConfig::default(),
             exfil_guard: crate::config::ExfiltrationGuardConfig::default(),
             main_tools: None,
+            allowed_tools: None,
             memory_db: None,
             leak_detector: Arc::new(crate::safety::LeakDetector::new()),
+            retry: crate::providers::base::RetryConfig::default(),
         },
         bus.clone(),
     );
@@ -360,6 +378,104 @@ async fn test_non_silent_mode_publishes_bus_message() {
     assert!(msg.content.contains("completed successfully"));
 }
 
+// --- Delegation tests ---
+
+fn make_delegating_manager(
+    provider: Arc<dyn LLMProvider>,
+    bus: Arc<MessageBus>,
+    max_per_turn: usize,
+) -> SubagentManager {
+    let mgr = SubagentManager::new(
+        SubagentConfig {
+            provider,
+            workspace: PathBuf::from("/tmp/test"),
+            model: Some("mock".to_string()),
+            max_tokens: 1024,
+            tool_temperature: Some(0.0),
+            reasoning_effort: None,
+            max_concurrent: 5,
+            max_per_turn,
+            announce: true,
+            stream_tool_activity: true,
+            prompt_guard_config: crate::config::PromptGuardConfig::default(),
+            exfil_guard: crate::config::ExfiltrationGuardConfig::default(),
+            main_tools: None,
+            allowed_tools: None,
+            memory_db: None,
+            leak_detector: Arc::new(crate::safety::LeakDetector::new()),
+            retry: crate::providers::base::RetryConfig::default(),
+        },
+        bus,
+    );
+    mgr.set_main_tools(make_test_main_registry());
+    mgr
+}
+
+#[test]
+fn test_reserve_turn_slot_enforces_per_turn_budget() {
+    let provider = Arc::new(MockProvider::immediate("done"));
+    let mgr = make_delegating_manager(provider, Arc::new(MessageBus::default()), 2);
+
+    assert!(mgr.reserve_turn_slot("turn-1"));
+    assert!(mgr.reserve_turn_slot("turn-1"));
+    assert!(!mgr.reserve_turn_slot("turn-1"));
+    // Another turn has its own budget
+    assert!(mgr.reserve_turn_slot("turn-2"));
+
+    let unlimited = make_manager(Arc::new(MockProvider::immediate("done")), 5);
+    for _ in 0..10 {
+        assert!(unlimited.reserve_turn_slot("turn-1"));
+    }
+}
+
+#[tokio::test]
+async fn test_delegation_and_tool_activity_published_as_status() {
+    let provider = Arc::new(MockProvider::with_responses(vec![
+        LLMResponse {
+            tool_calls: vec![crate::providers::base::ToolCallRequest {
+                id: "call-1".to_string(),
+                name: "list_dir".to_string(),
+                arguments: serde_json::json!({"path": "/tmp"}),
+            }],
+            ..Default::default()
+        },
+        LLMResponse {
+            content: Some("found it".to_string()),
+            ..Default::default()
+        },
+    ]));
+    let bus = Arc::new(MessageBus::default());
+    let mut outbound = bus.take_outbound_rx().unwrap();
+    let mgr = make_delegating_manager(provider, bus.clone(), 0);
+
+    mgr.spawn(
+        "look around".to_string(),
+        Some("research".to_string()),
+        "telegram".to_string(),
+        "chat1".to_string(),
+        false,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let mut lines = Vec::new();
+    for _ in 0..2 {
+        let msg = tokio::time::timeout(tokio::time::Duration::from_secs(2), outbound.recv())
+            .await
+            .expect("status within timeout")
+            .expect("channel open");
+        assert_eq!(msg.chat_id, "chat1");
+        assert_eq!(
+            msg.metadata.get(crate::bus::meta::STATUS),
+            Some(&Value::Bool(true))
+        );
+        lines.push(msg.content);
+    }
+    assert_eq!(lines[0], "Delegating \"research\" to a subagent…");
+    assert_eq!(lines[1], "↳ research: list_dir");
+}
+
 // --- Cancel test ---
 
 #[tokio::test]
@@ -483,12 +599,17 @@ fn make_inner_with_tools(
         model: "mock".to_string(),
         max_tokens: 1024,
         tool_temperature: Some(0.0),
+        reasoning_effort: None,
         prompt_guard: None,
         prompt_guard_config: crate::config::PromptGuardConfig::default(),
         leak_detector: std::sync::Arc::new(crate::safety::leak_detector::LeakDetector::new()),
         exfil_guard,
         main_tools: lock,
+        allowed_tools: None,
         memory_db: None,
+        announce: false,
+        stream_tool_activity: false,
+        retry: crate::providers::base::RetryConfig::default(),
     }
 }
 
@@ -557,6 +678,29 @@ fn test_subagent_tools_capability_based() {
     );
 }
 
+#[test]
+fn test_subagent_tools_restricted_to_allowed_list() {
+    let main = make_test_main_registry();
+    let guard = crate::config::ExfiltrationGuardConfig {
+        enabled: false,
+        allow_tools: crate::config::DenyByDefaultList::new(vec![]),
+    };
+    let mut config = make_inner_with_tools(guard, main);
+    config.allowed_tools = Some(vec![
+        "read_file".to_string(),
+        "web_fetch".to_string(),
+        "github".to_string(),
+        "spawn".to_string(),
+    ]);
+    let tools = super::build_subagent_tools(&config).unwrap();
+    let mut names = tools.tool_names();
+    names.sort();
+
+    // Unlisted tools (exec, write_file) are left out, and listing spawn
+    // does not override its Denied access
+    assert_eq!(names, vec!["github", "read_file", "web_fetch"]);
+}
+
 #[test]
 fn test_subagent_tools_denied_tools_excluded() {
     let main = make_test_main_registry();
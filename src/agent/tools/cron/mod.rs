@@ -13,6 +13,14 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const MAX_CRON_MESSAGE_LEN: usize = 10_000;
+/// Parameters that select a schedule kind (see `CronTool::parse_schedule`).
+const SCHEDULE_PARAMS: &[&str] = &[
+    "delay_seconds",
+    "every_seconds",
+    "cron_expr",
+    "at_time",
+    "event_pattern",
+];
 
 pub struct CronTool {
     cron_service: Arc<CronService>,
@@ -45,6 +53,7 @@ impl CronTool {
                 vec![CronTarget {
                     channel: current_channel.to_string(),
                     to: current_chat_id.to_string(),
+                    message_override: None,
                 }]
             }
             Some(channels) => {
@@ -71,7 +80,7 @@ impl CronTool {
     /// Validates `delay_seconds`, `every_seconds`, `cron_expr`, `at_time`, or
     /// `event_pattern` from the tool params and returns the appropriate schedule
     /// variant. Returns `Err(ToolResult)` for user-facing validation errors.
-    fn parse_schedule(params: &Value) -> std::result::Result<CronSchedule, ToolResult> {
+    pub(crate) fn parse_schedule(params: &Value) -> std::result::Result<CronSchedule, ToolResult> {
         // Relative delay: resolve to absolute timestamp server-side
         if let Some(delay) = params["delay_seconds"].as_u64() {
             if delay < 1 {
@@ -174,6 +183,7 @@ impl CronTool {
                         targets.push(CronTarget {
                             channel: "slack".to_string(),
                             to,
+                            message_override: None,
                         });
                     }
                 }
@@ -183,6 +193,7 @@ impl CronTool {
                         targets.push(CronTarget {
                             channel: "discord".to_string(),
                             to,
+                            message_override: None,
                         });
                     }
                 }
@@ -192,6 +203,7 @@ impl CronTool {
                         targets.push(CronTarget {
                             channel: "telegram".to_string(),
                             to,
+                            message_override: None,
                         });
                     }
                 }
@@ -202,6 +214,7 @@ impl CronTool {
                         targets.push(CronTarget {
                             channel: "whatsapp".to_string(),
                             to,
+                            message_override: None,
                         });
                     }
                 }
@@ -309,6 +322,7 @@ pub fn resolve_all_channel_targets_from_config(cfg: Option<&ChannelsConfig>) ->
             targets.push(CronTarget {
                 channel: "slack".to_string(),
                 to,
+                message_override: None,
             });
         }
     }
@@ -318,6 +332,7 @@ pub fn resolve_all_channel_targets_from_config(cfg: Option<&ChannelsConfig>) ->
             targets.push(CronTarget {
                 channel: "discord".to_string(),
                 to,
+                message_override: None,
             });
         }
     }
@@ -327,6 +342,7 @@ pub fn resolve_all_channel_targets_from_config(cfg: Option<&ChannelsConfig>) ->
             targets.push(CronTarget {
                 channel: "telegram".to_string(),
                 to,
+                message_override: None,
             });
         }
     }
@@ -337,6 +353,7 @@ pub fn resolve_all_channel_targets_from_config(cfg: Option<&ChannelsConfig>) ->
             targets.push(CronTarget {
                 channel: "whatsapp".to_string(),
                 to,
+                message_override: None,
             });
         }
     }
@@ -401,6 +418,10 @@ impl Tool for CronTool {
                 user_request: "send me a standup reminder in 5 minutes".into(),
                 params: serde_json::json!({"action": "add", "message": "Standup time!", "delay_seconds": 300, "type": "echo"}),
             },
+            crate::agent::tools::base::ToolExample {
+                user_request: "move my standup reminder to 9:30".into(),
+                params: serde_json::json!({"action": "update", "job_id": "a1b2c3d4", "cron_expr": "30 9 * * 1-5"}),
+            },
         ]
     }
 
@@ -413,10 +434,10 @@ impl Tool for CronTool {
                     "enum": ["add", "list", "get", "update", "pause", "resume", "remove", "run", "dlq_list", "dlq_replay", "dlq_clear"],
                     "description": "Action to perform. 'add' creates a new scheduled job. \
                      'get' retrieves full details of a single job by job_id. \
-                     'update' modifies an existing job (name, message, schedule). \
+                     'update' modifies an existing job (name, message, schedule, channels). \
                      'run' triggers an existing job immediately by job_id. 'list' shows all \
                      jobs. 'pause' disables a job. 'resume' re-enables a paused job. \
-                     'remove' deletes a job. dlq_list/dlq_replay/dlq_clear manage the \
+                     'remove' deletes a job after the user confirms (confirm=true). dlq_list/dlq_replay/dlq_clear manage the \
                      dead letter queue for failed executions."
                 },
                 "type": {
@@ -446,7 +467,15 @@ impl Tool for CronTool {
                 },
                 "tz": {
                     "type": "string",
-                    "description": "IANA timezone for cron_expr (e.g. 'America/New_York'). Defaults to system timezone."
+                    "description": "IANA timezone for cron_expr (e.g. 'America/New_York'). Defaults to system timezone, or to the job's current timezone on update."
+                },
+                "name": {
+                    "type": "string",
+                    "description": "New job name (for update)"
+                },
+                "confirm": {
+                    "type": "boolean",
+                    "description": "Set to true only after the user confirmed deleting the job (for remove)"
                 },
                 "job_id": {
                     "type": "string",
@@ -803,6 +832,31 @@ impl Tool for CronTool {
             "remove" => {
                 let job_id = require_param!(params, "job_id");
 
+                // Deleting is irreversible: describe the job and wait for the
+                // user's go-ahead (confirm=true, or the Delete button) first
+                if !params["confirm"].as_bool().unwrap_or(false) {
+                    let Some(job) = self.cron_service.get_job(job_id)? else {
+                        return Ok(ToolResult::error(format!("job {job_id} not found")));
+                    };
+                    let delete_button = serde_json::json!({
+                        "id": format!("remove-job-{}", job.id),
+                        "label": truncate_label("Delete: ", &job.name, 20),
+                        "style": "danger",
+                        "context": serde_json::json!({
+                            "tool": "cron",
+                            "params": {"action": "remove", "job_id": job.id, "confirm": true}
+                        }).to_string()
+                    });
+                    return Ok(ToolResult::new(format!(
+                        "Job '{}' (id: {}, schedule: {}) will be permanently deleted. Ask the user \
+                         to confirm, then call remove again with confirm=true.",
+                        job.name,
+                        job.id,
+                        job.schedule.describe()
+                    ))
+                    .with_buttons(vec![delete_button]));
+                }
+
                 match self.cron_service.remove_job(job_id)? {
                     Some(_) => Ok(ToolResult::new(format!("Removed job {job_id}"))),
                     None => Ok(ToolResult::error(format!("job {job_id} not found"))),
@@ -810,23 +864,95 @@ impl Tool for CronTool {
             }
             "update" => {
                 let job_id = require_param!(params, "job_id");
+                let Some(current) = self.cron_service.get_job(job_id)? else {
+                    return Ok(ToolResult::error(format!("job {job_id} not found")));
+                };
+
+                let schedule = if SCHEDULE_PARAMS.iter().any(|key| !params[*key].is_null()) {
+                    // A new cron_expr keeps the job's timezone unless tz is given
+                    let mut schedule_params = params.clone();
+                    if let CronSchedule::Cron { tz: Some(tz), .. } = &current.schedule
+                        && schedule_params["tz"].is_null()
+                    {
+                        schedule_params["tz"] = Value::String(tz.clone());
+                    }
+                    match Self::parse_schedule(&schedule_params) {
+                        Ok(s) => Some(s),
+                        Err(tool_err) => return Ok(tool_err),
+                    }
+                } else if let Some(tz) = params["tz"].as_str() {
+                    // Only the timezone changes: keep the current expression
+                    let CronSchedule::Cron { expr, .. } = &current.schedule else {
+                        return Ok(ToolResult::error(
+                            "tz can only be changed on cron_expr jobs".to_string(),
+                        ));
+                    };
+                    if tz.parse::<chrono_tz::Tz>().is_err() {
+                        return Ok(ToolResult::error(format!(
+                            "invalid timezone '{tz}'. Use IANA format (e.g. 'America/New_York')"
+                        )));
+                    }
+                    Some(CronSchedule::Cron {
+                        expr: expr.clone(),
+                        tz: Some(tz.to_string()),
+                    })
+                } else {
+                    None
+                };
+
+                let message = params["message"].as_str().map(String::from);
+                if let Some(ref m) = message {
+                    if m.trim().is_empty() {
+                        return Ok(ToolResult::error("message must not be empty".to_string()));
+                    }
+                    if m.len() > MAX_CRON_MESSAGE_LEN {
+                        return Ok(ToolResult::error(format!(
+                            "message too long ({} chars, max {})",
+                            m.len(),
+                            MAX_CRON_MESSAGE_LEN
+                        )));
+                    }
+                }
+
+                let targets = if let Some(channels) = params["channels"].as_array() {
+                    let targets = self.resolve_targets(Some(channels), &ctx.channel, &ctx.chat_id);
+                    if targets.is_empty() {
+                        return Ok(ToolResult::error(
+                            "no valid targets resolved. Check that the specified channels are enabled and have allowFrom configured".to_string(),
+                        ));
+                    }
+                    Some(targets)
+                } else {
+                    None
+                };
 
                 let upd = crate::cron::types::UpdateJobParams {
                     name: params["name"].as_str().map(String::from),
-                    message: params["message"].as_str().map(String::from),
+                    message,
+                    schedule,
+                    targets,
                     ..Default::default()
                 };
 
-                if upd.name.is_none() && upd.message.is_none() {
+                if upd.name.is_none()
+                    && upd.message.is_none()
+                    && upd.schedule.is_none()
+                    && upd.targets.is_none()
+                {
                     return Ok(ToolResult::error(
-                        "update requires at least one field: name or message".to_string(),
+                        "update requires at least one field: name, message, channels, or a \
+                         schedule (cron_expr, tz, every_seconds, at_time, delay_seconds, \
+                         event_pattern)"
+                            .to_string(),
                     ));
                 }
 
                 match self.cron_service.update_job(job_id, &upd)? {
                     Some(job) => Ok(ToolResult::new(format!(
-                        "Updated job '{}' (id: {})",
-                        job.name, job.id
+                        "Updated job '{}' (id: {}, schedule: {})",
+                        job.name,
+                        job.id,
+                        job.schedule.describe()
                     ))),
                     None => Ok(ToolResult::error(format!("job {job_id} not found"))),
                 }
@@ -29,6 +29,11 @@ fn make_test_channels_config() -> ChannelsConfig {
             ..Default::default()
         },
         twilio: TwilioConfig::default(),
+        admin_target: None,
+        admin_senders: Vec::new(),
+        send_retry: crate::config::SendRetryConfig::default(),
+        rate_limit: crate::config::ChannelRateLimitConfig::default(),
+        quiet_hours: crate::config::QuietHoursConfig::default(),
     }
 }
 
@@ -464,7 +469,7 @@ async fn test_cron_add_and_list_and_remove() {
     assert!(list_result.content.contains("Check email"));
 
     // Remove the job
-    let remove_params = json!({"action": "remove", "job_id": id});
+    let remove_params = json!({"action": "remove", "job_id": id, "confirm": true});
     let remove_result = tool.execute(remove_params, &ctx).await.unwrap();
     assert!(!remove_result.is_error);
     assert!(remove_result.content.contains("Removed"));
@@ -491,6 +496,119 @@ async fn test_cron_remove_nonexistent_job() {
     assert!(result.content.contains("not found"));
 }
 
+#[tokio::test]
+async fn test_cron_remove_requires_confirmation() {
+    let db = Arc::new(crate::agent::memory::memory_db::MemoryDB::new(":memory:").expect("test db"));
+    let cron_service = Arc::new(CronService::new(db));
+    let tool = CronTool::new(cron_service.clone(), None, None);
+    let ctx = ExecutionContext {
+        channel: "slack".to_string(),
+        chat_id: "U123".to_string(),
+        ..Default::default()
+    };
+    let job = make_test_job("job-1", "Standup reminder", true);
+    cron_service.add_job(job).unwrap();
+
+    let result = tool
+        .execute(json!({"action": "remove", "job_id": "job-1"}), &ctx)
+        .await
+        .unwrap();
+    assert!(!result.is_error, "{}", result.content);
+    assert!(result.content.contains("permanently deleted"));
+    assert!(cron_service.get_job("job-1").unwrap().is_some());
+    let buttons = &result.metadata.expect("delete button")["suggested_buttons"];
+    assert_eq!(buttons[0]["style"], "danger");
+    let context: Value = serde_json::from_str(buttons[0]["context"].as_str().unwrap()).unwrap();
+    assert_eq!(context["params"]["confirm"], true);
+
+    let result = tool
+        .execute(
+            json!({"action": "remove", "job_id": "job-1", "confirm": true}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error, "{}", result.content);
+    assert!(cron_service.get_job("job-1").unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_cron_update_schedule() {
+    let db = Arc::new(crate::agent::memory::memory_db::MemoryDB::new(":memory:").expect("test db"));
+    let cron_service = Arc::new(CronService::new(db));
+    let tool = CronTool::new(cron_service.clone(), None, None);
+    let ctx = ExecutionContext {
+        channel: "slack".to_string(),
+        chat_id: "U123".to_string(),
+        ..Default::default()
+    };
+    let mut job = make_test_job("job-1", "Standup reminder", true);
+    job.schedule = CronSchedule::Cron {
+        expr: Some("0 9 * * 1-5".to_string()),
+        tz: Some("Europe/Berlin".to_string()),
+    };
+    cron_service.add_job(job).unwrap();
+
+    // A new expression keeps the job's timezone
+    let result = tool
+        .execute(
+            json!({"action": "update", "job_id": "job-1", "cron_expr": "30 9 * * 1-5"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error, "{}", result.content);
+    let job = cron_service.get_job("job-1").unwrap().unwrap();
+    assert!(matches!(
+        &job.schedule,
+        CronSchedule::Cron { expr: Some(expr), tz: Some(tz) }
+            if expr == "30 9 * * 1-5" && tz == "Europe/Berlin"
+    ));
+
+    // tz alone keeps the expression
+    let result = tool
+        .execute(
+            json!({"action": "update", "job_id": "job-1", "tz": "America/New_York"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error, "{}", result.content);
+    let job = cron_service.get_job("job-1").unwrap().unwrap();
+    assert!(matches!(
+        &job.schedule,
+        CronSchedule::Cron { expr: Some(expr), tz: Some(tz) }
+            if expr == "30 9 * * 1-5" && tz == "America/New_York"
+    ));
+}
+
+#[tokio::test]
+async fn test_cron_update_rejects_invalid_input() {
+    let db = Arc::new(crate::agent::memory::memory_db::MemoryDB::new(":memory:").expect("test db"));
+    let cron_service = Arc::new(CronService::new(db));
+    let tool = CronTool::new(cron_service.clone(), None, None);
+    let ctx = ExecutionContext {
+        channel: "slack".to_string(),
+        chat_id: "U123".to_string(),
+        ..Default::default()
+    };
+    cron_service
+        .add_job(make_test_job("job-1", "Standup reminder", true))
+        .unwrap();
+
+    for params in [
+        json!({"action": "update", "job_id": "job-1"}),
+        json!({"action": "update", "job_id": "job-1", "cron_expr": "not a cron"}),
+        json!({"action": "update", "job_id": "job-1", "every_seconds": 5}),
+        json!({"action": "update", "job_id": "job-1", "message": "  "}),
+        json!({"action": "update", "job_id": "job-1", "tz": "Mars/Base"}),
+        json!({"action": "update", "job_id": "missing", "name": "x"}),
+    ] {
+        let result = tool.execute(params.clone(), &ctx).await.unwrap();
+        assert!(result.is_error, "accepted {params}");
+    }
+}
+
 #[tokio::test]
 async fn test_cron_add_with_channels_all() {
     let db = Arc::new(crate::agent::memory::memory_db::MemoryDB::new(":memory:").expect("test db"));
@@ -0,0 +1,185 @@
+use crate::actions;
+use crate::agent::tools::base::{ExecutionContext, ToolCapabilities, ToolCategory};
+use crate::agent::tools::cron::CronTool;
+use crate::agent::tools::{Tool, ToolResult};
+use crate::cron::service::CronService;
+use crate::cron::types::{CronJob, CronJobState, CronPayload, CronSchedule, CronTarget};
+use crate::require_param;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAX_FOLLOWUP_PROMPT_LEN: usize = 10_000;
+
+/// Schedules a one-shot agent turn in the current chat. The follow-up runs
+/// through the regular cron agent-job path, so the result is delivered back
+/// to the chat that scheduled it.
+pub struct ScheduleFollowupTool {
+    cron_service: Arc<CronService>,
+}
+
+impl ScheduleFollowupTool {
+    pub fn new(cron_service: Arc<CronService>) -> Self {
+        Self { cron_service }
+    }
+}
+
+/// Wrap the follow-up prompt so the future turn knows why it is running.
+/// The cron turn runs in its own session without the original conversation,
+/// so the prompt itself must carry the context.
+fn followup_message(prompt: &str) -> String {
+    format!(
+        "Scheduled follow-up check-in. Earlier in this chat you said you would check back on the following:\n\n{prompt}\n\nDo the check now and report the outcome to the user."
+    )
+}
+
+#[async_trait]
+impl Tool for ScheduleFollowupTool {
+    fn name(&self) -> &'static str {
+        "schedule_followup"
+    }
+
+    fn description(&self) -> &'static str {
+        "Schedule a future agent turn in this chat to follow up on something (e.g. 'check back in an hour to see if the deployment finished'). When it fires, you run with the given prompt and all tools, and your reply is sent to this chat. The follow-up does not see the current conversation, so include every detail needed (names, URLs, IDs). Use delay_seconds (preferred) or at_time. For static reminders that need no reasoning, use cron with type 'echo' instead."
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            network_outbound: true,
+            actions: actions![schedule],
+            category: ToolCategory::Scheduling,
+            ..Default::default()
+        }
+    }
+
+    fn usage_examples(&self) -> Vec<crate::agent::tools::base::ToolExample> {
+        vec![crate::agent::tools::base::ToolExample {
+            user_request: "check back in an hour to see if the deploy finished".into(),
+            params: serde_json::json!({
+                "prompt": "Check whether the v2.3 deployment of the api service has finished and whether its health checks pass",
+                "delay_seconds": 3600
+            }),
+        }]
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "prompt": {
+                    "type": "string",
+                    "description": "What to check or do when the follow-up fires. Must be self-contained: the follow-up runs without the current conversation."
+                },
+                "delay_seconds": {
+                    "type": "integer",
+                    "description": "Seconds from now until the follow-up (1 to 31536000). Preferred over at_time.",
+                    "minimum": 1,
+                    "maximum": 31_536_000
+                },
+                "at_time": {
+                    "type": "string",
+                    "description": "Absolute time for the follow-up in ISO 8601 (e.g. '2025-01-15T09:00:00-05:00')"
+                }
+            },
+            "required": ["prompt"]
+        })
+    }
+
+    async fn execute(&self, params: Value, ctx: &ExecutionContext) -> Result<ToolResult> {
+        // Same loop guard as the cron tool: a follow-up cannot schedule another one
+        if ctx
+            .metadata
+            .get(crate::bus::meta::IS_CRON_JOB)
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+        {
+            return Ok(ToolResult::error(
+                "cannot schedule a follow-up from within a cron job execution".to_string(),
+            ));
+        }
+
+        let prompt = require_param!(params, "prompt").trim();
+        if prompt.is_empty() {
+            return Ok(ToolResult::error("Missing 'prompt' parameter".to_string()));
+        }
+        if prompt.len() > MAX_FOLLOWUP_PROMPT_LEN {
+            return Ok(ToolResult::error(format!(
+                "prompt too long ({} chars, max {})",
+                prompt.len(),
+                MAX_FOLLOWUP_PROMPT_LEN
+            )));
+        }
+
+        if ctx.channel.is_empty() || ctx.chat_id.is_empty() {
+            return Ok(ToolResult::error(
+                "no session context (channel/chat_id)".to_string(),
+            ));
+        }
+
+        // Only one-shot schedules are accepted; recurring keys are not forwarded.
+        let schedule_params = serde_json::json!({
+            "delay_seconds": params["delay_seconds"],
+            "at_time": params["at_time"],
+        });
+        if schedule_params["delay_seconds"].is_null() && schedule_params["at_time"].is_null() {
+            return Ok(ToolResult::error(
+                "either delay_seconds or at_time is required".to_string(),
+            ));
+        }
+        let schedule = match CronTool::parse_schedule(&schedule_params) {
+            Ok(s @ CronSchedule::At { .. }) => s,
+            Ok(_) => {
+                return Ok(ToolResult::error(
+                    "either delay_seconds or at_time is required".to_string(),
+                ));
+            }
+            Err(tool_err) => return Ok(tool_err),
+        };
+
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("System time is before UNIX epoch")
+            .map(|d| d.as_millis() as i64)?;
+
+        let job = CronJob {
+            id: uuid::Uuid::new_v4().simple().to_string()[..12].to_string(),
+            name: format!(
+                "Follow-up: {}",
+                crate::utils::truncate_chars(prompt, 30, "...")
+            ),
+            enabled: true,
+            schedule,
+            payload: CronPayload {
+                kind: "agent_turn".to_string(),
+                message: followup_message(prompt),
+                agent_echo: true,
+                targets: vec![CronTarget {
+                    channel: ctx.channel.clone(),
+                    to: ctx.chat_id.clone(),
+                    message_override: None,
+                }],
+            },
+            state: CronJobState::default(),
+            created_at_ms: now_ms,
+            updated_at_ms: now_ms,
+            delete_after_run: true,
+            expires_at_ms: None,
+            max_runs: None,
+            cooldown_secs: None,
+            max_concurrent: None,
+        };
+
+        let when = job.schedule.describe();
+        self.cron_service.add_job(job.clone())?;
+        Ok(ToolResult::new(format!(
+            "Scheduled follow-up (id: {}, {}). Cancel it with cron remove if no longer needed.",
+            job.id, when
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,98 @@
+use super::*;
+use crate::agent::memory::memory_db::MemoryDB;
+use serde_json::json;
+
+fn make_tool() -> (ScheduleFollowupTool, Arc<CronService>) {
+    let db = Arc::new(MemoryDB::new(":memory:").expect("test db"));
+    let cron_service = Arc::new(CronService::new(db));
+    (
+        ScheduleFollowupTool::new(cron_service.clone()),
+        cron_service,
+    )
+}
+
+fn chat_ctx() -> ExecutionContext {
+    ExecutionContext {
+        channel: "telegram".to_string(),
+        chat_id: "42".to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_followup_creates_one_shot_agent_job_for_current_chat() {
+    let (tool, cron_service) = make_tool();
+    let result = tool
+        .execute(
+            json!({"prompt": "Check if the api deploy finished", "delay_seconds": 3600}),
+            &chat_ctx(),
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error, "{}", result.content);
+
+    let jobs = cron_service.list_jobs(true).unwrap();
+    assert_eq!(jobs.len(), 1);
+    let job = &jobs[0];
+    assert!(matches!(job.schedule, CronSchedule::At { at_ms: Some(_) }));
+    assert!(job.delete_after_run);
+    assert_eq!(job.payload.kind, "agent_turn");
+    assert!(job.payload.agent_echo);
+    assert!(
+        job.payload
+            .message
+            .contains("Check if the api deploy finished")
+    );
+    assert_eq!(job.payload.targets.len(), 1);
+    assert_eq!(job.payload.targets[0].channel, "telegram");
+    assert_eq!(job.payload.targets[0].to, "42");
+    assert!(job.name.starts_with("Follow-up: "));
+    assert!(result.content.contains(&job.id));
+}
+
+#[tokio::test]
+async fn test_followup_rejects_invalid_params() {
+    let (tool, cron_service) = make_tool();
+    let ctx = chat_ctx();
+
+    let cases = [
+        json!({"delay_seconds": 60}),
+        json!({"prompt": "   ", "delay_seconds": 60}),
+        json!({"prompt": "check"}),
+        json!({"prompt": "check", "delay_seconds": 0}),
+        json!({"prompt": "check", "at_time": "2000-01-01T00:00:00Z"}),
+        json!({"prompt": "x".repeat(MAX_FOLLOWUP_PROMPT_LEN + 1), "delay_seconds": 60}),
+    ];
+    for params in cases {
+        let result = tool.execute(params.clone(), &ctx).await;
+        let is_error = result.map_or(true, |r| r.is_error);
+        assert!(is_error, "expected error for {params}");
+    }
+
+    let no_session = ExecutionContext::default();
+    let result = tool
+        .execute(json!({"prompt": "check", "delay_seconds": 60}), &no_session)
+        .await
+        .unwrap();
+    assert!(result.is_error);
+
+    assert!(cron_service.list_jobs(true).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_followup_blocked_inside_cron_job() {
+    let (tool, cron_service) = make_tool();
+    let mut ctx = chat_ctx();
+    ctx.metadata.insert(
+        crate::bus::meta::IS_CRON_JOB.to_string(),
+        serde_json::Value::Bool(true),
+    );
+
+    let result = tool
+        .execute(json!({"prompt": "check again", "delay_seconds": 60}), &ctx)
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("cannot schedule"));
+    assert!(cron_service.list_jobs(true).unwrap().is_empty());
+}
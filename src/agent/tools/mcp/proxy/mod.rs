@@ -220,7 +220,7 @@ impl Tool for AttenuatedMcpTool {
         true
     }
 
-    fn execution_timeout(&self) -> std::time::Duration {
+    fn execution_timeout(&self) -> Option<std::time::Duration> {
         self.inner.execution_timeout()
     }
 
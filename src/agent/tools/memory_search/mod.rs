@@ -1,19 +1,46 @@
 use crate::actions;
 use crate::agent::memory::MemoryStore;
+use crate::agent::memory::memory_db::{ConflictResolution, conflict_status};
+use crate::agent::memory::memory_store::SearchTuning;
+use crate::agent::profile::ProfileResolver;
 use crate::agent::tools::base::{ExecutionContext, SubagentAccess, ToolCapabilities};
 use crate::agent::tools::{Tool, ToolResult};
 use anyhow::Result;
 use async_trait::async_trait;
+use oxicrab_core::config::schema::FusionStrategy;
 use serde_json::Value;
 use std::sync::Arc;
 
+/// Longest content snippet returned per search hit.
+const SNIPPET_CHARS: usize = 500;
+/// Most results one search may ask for.
+const MAX_SEARCH_LIMIT: u64 = 50;
+
 pub struct MemorySearchTool {
     memory: Arc<MemoryStore>,
+    profiles: Option<Arc<ProfileResolver>>,
 }
 
 impl MemorySearchTool {
     pub fn new(memory: Arc<MemoryStore>) -> Self {
-        Self { memory }
+        Self {
+            memory,
+            profiles: None,
+        }
+    }
+
+    /// Use the memory store of the chat's profile instead of `memory`.
+    #[must_use]
+    pub fn with_profiles(mut self, profiles: Arc<ProfileResolver>) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+
+    fn memory_for(&self, ctx: &ExecutionContext) -> &MemoryStore {
+        match self.profiles {
+            Some(ref profiles) => &profiles.resolve(&ctx.channel, &ctx.chat_id).memory,
+            None => &self.memory,
+        }
     }
 
     fn record_retrieval_metrics_for_sources<'a, I>(source_keys: I)
@@ -42,16 +69,57 @@ impl MemorySearchTool {
                 .increment(llm_hits);
         }
     }
+}
 
-    #[cfg(feature = "embeddings")]
-    fn record_retrieval_metrics(hits: &[oxicrab_memory::memory_db::MemoryHit]) {
-        Self::record_retrieval_metrics_for_sources(hits.iter().map(|hit| hit.source_key.as_str()));
+/// Search settings given with the call; missing ones keep the memory config.
+fn parse_tuning(params: &Value) -> Result<SearchTuning, String> {
+    let limit = match &params["limit"] {
+        Value::Null => None,
+        v => Some(
+            v.as_u64()
+                .filter(|&n| n > 0)
+                .ok_or("'limit' must be a positive integer")?
+                .min(MAX_SEARCH_LIMIT) as usize,
+        ),
+    };
+    let keyword_weight = match &params["keyword_weight"] {
+        Value::Null => None,
+        v => Some(
+            v.as_f64()
+                .filter(|w| (0.0..=1.0).contains(w))
+                .ok_or("'keyword_weight' must be a number between 0 and 1")? as f32,
+        ),
+    };
+    let fusion = match &params["fusion"] {
+        Value::Null => None,
+        v => Some(match v.as_str() {
+            Some("weighted") => FusionStrategy::WeightedScore,
+            Some("rrf") => FusionStrategy::Rrf,
+            _ => return Err("'fusion' must be 'weighted' or 'rrf'".to_string()),
+        }),
+    };
+    let highlight = match &params["highlight"] {
+        Value::Null => false,
+        v => v.as_bool().ok_or("'highlight' must be a boolean")?,
+    };
+    Ok(SearchTuning {
+        limit,
+        keyword_weight,
+        fusion,
+        highlight,
+    })
+}
+
+fn fusion_name(fusion: FusionStrategy) -> &'static str {
+    match fusion {
+        FusionStrategy::WeightedScore => "weighted",
+        FusionStrategy::Rrf => "rrf",
     }
 }
 
 impl MemorySearchTool {
-    fn action_explain_last(&self) -> Result<ToolResult> {
-        let details = self.memory.db().get_last_search_details()?;
+    fn action_explain_last(memory: &MemoryStore) -> Result<ToolResult> {
+        let details = memory.db().get_last_search_details()?;
         let Some(d) = details else {
             return Ok(ToolResult::new(
                 "No memory searches recorded yet.".to_string(),
@@ -79,8 +147,51 @@ impl MemorySearchTool {
         )))
     }
 
-    fn action_list_sources(&self) -> Result<ToolResult> {
-        let sources = self.memory.db().list_sources_with_counts()?;
+    fn action_search(memory: &MemoryStore, query: &str, params: &Value) -> Result<ToolResult> {
+        let tuning = match parse_tuning(params) {
+            Ok(tuning) => tuning,
+            Err(e) => return Ok(ToolResult::error(e)),
+        };
+        let found = match memory.ranked_search(query, tuning) {
+            Ok(found) => found,
+            Err(e) => return Ok(ToolResult::error(format!("memory search error: {e}"))),
+        };
+        Self::record_retrieval_metrics_for_sources(
+            found.hits.iter().map(|hit| hit.source_key.as_str()),
+        );
+        let results: Vec<Value> = found
+            .hits
+            .iter()
+            .enumerate()
+            .map(|(i, hit)| {
+                serde_json::json!({
+                    "rank": i + 1,
+                    "source_key": hit.source_key,
+                    "snippet": hit.snippet.clone().unwrap_or_else(|| {
+                        crate::utils::truncate_chars(&hit.content, SNIPPET_CHARS, "...")
+                    }),
+                })
+            })
+            .collect();
+        let mut output = serde_json::json!({
+            "query": query,
+            "method": if found.fusion.is_some() { "hybrid" } else { "keyword" },
+            "results": results,
+        });
+        match found.fusion {
+            Some(fusion) => output["fusion"] = Value::from(fusion_name(fusion)),
+            None => {
+                output["note"] = Value::from(
+                    "Embeddings are unavailable, so these are keyword-only results; \
+                     keyword_weight and fusion were not applied.",
+                );
+            }
+        }
+        Ok(ToolResult::new(serde_json::to_string(&output)?))
+    }
+
+    fn action_list_sources(memory: &MemoryStore) -> Result<ToolResult> {
+        let sources = memory.db().list_sources_with_counts()?;
         if sources.is_empty() {
             return Ok(ToolResult::new("No memory sources found.".to_string()));
         }
@@ -95,13 +206,98 @@ impl MemorySearchTool {
         )))
     }
 
-    fn action_delete(&self, source_key: &str) -> Result<ToolResult> {
+    fn action_conflicts(memory: &MemoryStore) -> Result<ToolResult> {
+        let conflicts = memory
+            .db()
+            .list_memory_conflicts(Some(conflict_status::OPEN))?;
+        if conflicts.is_empty() {
+            return Ok(ToolResult::new("No open memory conflicts.".to_string()));
+        }
+        let lines: Vec<String> = conflicts
+            .iter()
+            .map(|c| {
+                let why = c
+                    .explanation
+                    .as_deref()
+                    .filter(|e| !e.is_empty())
+                    .map(|e| format!("\n  Why: {e}"))
+                    .unwrap_or_default();
+                format!(
+                    "- Conflict {} (similarity {:.2})\n  Older [{}]: {}\n  Newer [{}]: {}{}",
+                    c.id,
+                    c.similarity,
+                    c.older_source_key,
+                    c.older_content,
+                    c.newer_source_key,
+                    c.newer_content,
+                    why
+                )
+            })
+            .collect();
+        Ok(ToolResult::new(format!(
+            "{} open memory conflicts (resolve with action 'resolve_conflict'):\n{}",
+            conflicts.len(),
+            lines.join("\n")
+        )))
+    }
+
+    fn action_resolve_conflict(memory: &MemoryStore, params: &Value) -> ToolResult {
+        let Some(id) = params["conflict_id"].as_i64() else {
+            return ToolResult::error(
+                "missing 'conflict_id' parameter for resolve_conflict action".to_string(),
+            );
+        };
+        let resolution = match params["keep"].as_str() {
+            Some("newer") => ConflictResolution::KeepNewer,
+            Some("older") => ConflictResolution::KeepOlder,
+            Some("both") => ConflictResolution::KeepBoth,
+            _ => {
+                return ToolResult::error("'keep' must be one of: newer, older, both".to_string());
+            }
+        };
+        match memory.db().resolve_memory_conflict(id, resolution) {
+            Ok(Some(removed)) => ToolResult::new(format!(
+                "Resolved conflict {id}; removed stale memory: {removed}"
+            )),
+            Ok(None) => ToolResult::new(format!("Dismissed conflict {id}; both memories kept.")),
+            Err(e) => ToolResult::error(format!("failed to resolve conflict: {e}")),
+        }
+    }
+
+    fn action_forget(memory: &MemoryStore, params: &Value) -> ToolResult {
+        if let Some(content) = params["content"].as_str().filter(|c| !c.trim().is_empty()) {
+            return match memory.forget_matching(content) {
+                Ok(Some(removed)) => ToolResult::new(format!("Forgot: {removed}")),
+                Ok(None) => match memory.closest_forget_candidate(content) {
+                    Ok(Some((id, candidate))) => ToolResult::new(format!(
+                        "No saved memory matches '{content}' exactly, so nothing was deleted. \
+                         Closest is #{id}: {candidate}. Confirm with the user before forgetting it by entry_id."
+                    )),
+                    Ok(None) => ToolResult::new(format!("No saved memory matches '{content}'.")),
+                    Err(e) => ToolResult::error(format!("failed to forget: {e}")),
+                },
+                Err(e) => ToolResult::error(format!("failed to forget: {e}")),
+            };
+        }
+        let Some(id) = params["entry_id"].as_i64() else {
+            return ToolResult::error(
+                "missing 'entry_id' or 'content' parameter for forget action".to_string(),
+            );
+        };
+        match memory.db().delete_memory_entry(id) {
+            Ok(Some(removed)) => ToolResult::new(format!("Forgot #{id}: {removed}")),
+            Ok(None) => ToolResult::new(format!("No memory entry #{id} found.")),
+            Err(e) => ToolResult::error(format!("failed to forget #{id}: {e}")),
+        }
+    }
+
+    fn action_delete(memory: &MemoryStore, source_key: &str) -> Result<ToolResult> {
         if source_key.starts_with("knowledge:") {
             return Ok(ToolResult::error(
                 "Cannot delete knowledge entries — they are protected from deletion.".to_string(),
             ));
         }
-        let deleted = self.memory.db().delete_by_source_key(source_key)?;
+        let deleted = memory.db().delete_by_source_key(source_key)?;
         if deleted == 0 {
             Ok(ToolResult::new(format!(
                 "No entries found for source '{source_key}'."
@@ -121,7 +317,7 @@ impl Tool for MemorySearchTool {
     }
 
     fn description(&self) -> &'static str {
-        "Search long-term memory and daily notes. Actions: 'search' (default) finds relevant memories; 'explain_last' shows provenance details of the most recent search; 'list_sources' lists all memory source keys with counts; 'delete' removes entries by source key."
+        "Search long-term memory and daily notes. Actions: 'search' (default) returns ranked memories as JSON (source key and snippet), tunable with limit, keyword_weight and fusion, with highlighted excerpts on request; 'explain_last' shows provenance details of the most recent search; 'list_sources' lists all memory source keys with counts; 'delete' removes entries by source key; 'forget' removes one entry by entry_id (the #N shown in fact digests, e.g. when the user replies 'forget #12') or the daily note containing every word of content (e.g. 'forget that my locker is 12'; a near match is only reported, not deleted); 'conflicts' lists contradictory memories flagged by the memory gardener; 'resolve_conflict' keeps the newer, older, or both entries of a conflict."
     }
 
     fn cacheable(&self) -> bool {
@@ -132,7 +328,15 @@ impl Tool for MemorySearchTool {
         ToolCapabilities {
             built_in: true,
             subagent_access: SubagentAccess::ReadOnly,
-            actions: actions![search: ro, explain_last: ro, list_sources: ro, delete],
+            actions: actions![
+                search: ro,
+                explain_last: ro,
+                list_sources: ro,
+                delete,
+                forget,
+                conflicts: ro,
+                resolve_conflict,
+            ],
             ..Default::default()
         }
     }
@@ -143,30 +347,81 @@ impl Tool for MemorySearchTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["search", "explain_last", "list_sources", "delete"],
-                    "description": "Action to perform. 'search' (default) retrieves memories; 'explain_last' returns provenance of the most recent search; 'list_sources' lists all source keys with counts; 'delete' removes entries by source key."
+                    "enum": ["search", "explain_last", "list_sources", "delete", "forget", "conflicts", "resolve_conflict"],
+                    "description": "Action to perform. 'search' (default) retrieves memories; 'explain_last' returns provenance of the most recent search; 'list_sources' lists all source keys with counts; 'delete' removes entries by source key; 'forget' removes one entry by entry_id or matching content; 'conflicts' lists open memory contradictions; 'resolve_conflict' resolves one by conflict_id."
                 },
                 "query": {
                     "type": "string",
                     "description": "Search query to find relevant memories. Required when action is 'search' (the default)."
                 },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_SEARCH_LIMIT,
+                    "description": "Most results for 'search'. Defaults to the configured memory search limit."
+                },
+                "keyword_weight": {
+                    "type": "number",
+                    "minimum": 0,
+                    "maximum": 1,
+                    "description": "Balance of keyword vs. semantic matching for 'search' with weighted fusion: 1 = keyword only, 0 = semantic only. Defaults to the memory config."
+                },
+                "fusion": {
+                    "type": "string",
+                    "enum": ["weighted", "rrf"],
+                    "description": "How 'search' merges keyword and semantic rankings: 'weighted' blends scores, 'rrf' merges by rank. Defaults to the memory config."
+                },
+                "highlight": {
+                    "type": "boolean",
+                    "description": "For 'search': return a ~200 character excerpt around the matched words, marked with <b></b>, instead of the start of each memory. Default false."
+                },
                 "source_key": {
                     "type": "string",
                     "description": "Source key for delete action. Required when action is 'delete'."
+                },
+                "entry_id": {
+                    "type": "integer",
+                    "description": "Memory entry ID (the #N from a fact digest). For 'forget', give this or 'content'."
+                },
+                "content": {
+                    "type": "string",
+                    "description": "For 'forget' without an entry_id: text describing the memory to remove. A daily note containing every word is deleted; a near match is reported with its entry_id for confirmation. Knowledge entries are never matched."
+                },
+                "conflict_id": {
+                    "type": "integer",
+                    "description": "Conflict ID from the 'conflicts' action. Required for 'resolve_conflict'."
+                },
+                "keep": {
+                    "type": "string",
+                    "enum": ["newer", "older", "both"],
+                    "description": "Which memory to keep for 'resolve_conflict'. The other one is deleted unless 'both'."
                 }
             }
         })
     }
 
-    async fn execute(&self, params: Value, _ctx: &ExecutionContext) -> Result<ToolResult> {
+    async fn execute(&self, params: Value, ctx: &ExecutionContext) -> Result<ToolResult> {
         let action = params["action"].as_str().unwrap_or("search");
+        let memory = self.memory_for(ctx);
 
         if action == "explain_last" {
-            return self.action_explain_last();
+            return Self::action_explain_last(memory);
         }
 
         if action == "list_sources" {
-            return self.action_list_sources();
+            return Self::action_list_sources(memory);
+        }
+
+        if action == "conflicts" {
+            return Self::action_conflicts(memory);
+        }
+
+        if action == "forget" {
+            return Ok(Self::action_forget(memory, &params));
+        }
+
+        if action == "resolve_conflict" {
+            return Ok(Self::action_resolve_conflict(memory, &params));
         }
 
         if action == "delete" {
@@ -178,7 +433,7 @@ impl Tool for MemorySearchTool {
                     ));
                 }
             };
-            return self.action_delete(source_key);
+            return Self::action_delete(memory, source_key);
         }
 
         let query = match params["query"].as_str() {
@@ -190,44 +445,7 @@ impl Tool for MemorySearchTool {
             }
         };
 
-        // Use hybrid search when embeddings are available
-        #[cfg(feature = "embeddings")]
-        if self.memory.has_embeddings() {
-            match self.memory.hybrid_search(query, 8, None) {
-                Ok(hits) if !hits.is_empty() => {
-                    Self::record_retrieval_metrics(&hits);
-                    let chunks: Vec<String> = hits
-                        .iter()
-                        .map(|h| format!("**{}**: {}", h.source_key, h.content))
-                        .collect();
-                    return Ok(ToolResult::new(chunks.join("\n\n---\n\n")));
-                }
-                Ok(_) => {} // empty, fall through to keyword search
-                Err(e) => {
-                    tracing::warn!("hybrid search failed, falling back to keyword: {}", e);
-                }
-            }
-        }
-
-        // Fallback to keyword-only search
-        match self.memory.get_memory_context(Some(query)) {
-            Ok(context) => {
-                if context.trim().is_empty() {
-                    Ok(ToolResult::new(
-                        "No relevant memories found for this query.".to_string(),
-                    ))
-                } else {
-                    let details = self.memory.db().get_last_search_details()?;
-                    if let Some(details) = details {
-                        Self::record_retrieval_metrics_for_sources(
-                            details.source_keys.iter().map(String::as_str),
-                        );
-                    }
-                    Ok(ToolResult::new(context))
-                }
-            }
-            Err(e) => Ok(ToolResult::error(format!("memory search error: {e}"))),
-        }
+        Self::action_search(memory, query, &params)
     }
 }
 
@@ -62,14 +62,25 @@ fn test_memory_search_capabilities() {
     assert!(caps.built_in);
     assert!(!caps.network_outbound);
     assert_eq!(caps.subagent_access, SubagentAccess::ReadOnly);
-    assert_eq!(caps.actions.len(), 4);
-    // search, explain_last, list_sources are read-only; delete is not
-    assert!(caps.actions.iter().filter(|a| a.read_only).count() >= 3);
+    assert_eq!(caps.actions.len(), 7);
+    // search, explain_last, list_sources, conflicts are read-only;
+    // delete, forget and resolve_conflict are not
+    assert!(caps.actions.iter().filter(|a| a.read_only).count() >= 4);
     assert!(
         caps.actions
             .iter()
             .any(|a| a.name == "delete" && !a.read_only)
     );
+    assert!(
+        caps.actions
+            .iter()
+            .any(|a| a.name == "forget" && !a.read_only)
+    );
+    assert!(
+        caps.actions
+            .iter()
+            .any(|a| a.name == "resolve_conflict" && !a.read_only)
+    );
 }
 
 #[test]
@@ -97,3 +108,199 @@ fn test_memory_search_actions_match_schema() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_memory_search_conflicts_empty() {
+    let tool = create_tool();
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "conflicts"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    assert!(result.content.contains("No open memory conflicts"));
+}
+
+#[tokio::test]
+async fn test_memory_search_resolve_conflict_validates_params() {
+    let tool = create_tool();
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "resolve_conflict", "conflict_id": 1, "keep": "neither"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("keep"));
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "resolve_conflict", "conflict_id": 99, "keep": "newer"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("no open memory conflict"));
+}
+
+#[tokio::test]
+async fn test_memory_search_forget_entry() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let memory = Arc::new(MemoryStore::new(tmp.path()).unwrap());
+    let ids = memory
+        .append_lines_to_section("Facts", "- User has a dog named Rex")
+        .unwrap();
+    let tool = MemorySearchTool::new(memory.clone());
+    let ctx = ExecutionContext::default();
+
+    let result = tool
+        .execute(serde_json::json!({"action": "forget"}), &ctx)
+        .await
+        .unwrap();
+    assert!(result.is_error);
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "forget", "entry_id": ids[0]}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    assert!(result.content.contains("dog named Rex"));
+    assert!(memory.read_today_section("Facts").unwrap().is_empty());
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "forget", "entry_id": ids[0]}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(result.content.contains("No memory entry"));
+}
+
+#[tokio::test]
+async fn test_memory_search_forget_by_content() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let memory = Arc::new(MemoryStore::new(tmp.path()).unwrap());
+    memory.append_today("User's locker number is 12").unwrap();
+    let tool = MemorySearchTool::new(memory.clone());
+    let ctx = ExecutionContext::default();
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "forget", "content": "my locker number is 12"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    assert_eq!(result.content, "Forgot: User's locker number is 12");
+    assert!(memory.get_recent_daily_entries(10).unwrap().is_empty());
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "forget", "content": "my locker number is 12"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("No saved memory matches"));
+}
+
+#[tokio::test]
+async fn test_memory_search_returns_ranked_json() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let memory = Arc::new(MemoryStore::new(tmp.path()).unwrap());
+    memory
+        .append_to_section("Home", "the garage code is 4512")
+        .unwrap();
+    memory
+        .append_to_section("Work", "the office door code is 9981")
+        .unwrap();
+    let tool = MemorySearchTool::new(memory);
+    let ctx = ExecutionContext::default();
+
+    let result = tool
+        .execute(serde_json::json!({"query": "code", "limit": 1}), &ctx)
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    let output: Value = serde_json::from_str(&result.content).unwrap();
+    // No embedding service here, so the search degrades to keywords
+    assert_eq!(output["method"], "keyword");
+    assert!(output["note"].as_str().unwrap().contains("keyword-only"));
+    let results = output["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["rank"], 1);
+    assert!(
+        results[0]["source_key"]
+            .as_str()
+            .unwrap()
+            .starts_with("daily:")
+    );
+    assert!(results[0]["snippet"].as_str().unwrap().contains("code is"));
+}
+
+#[tokio::test]
+async fn test_memory_search_highlights_matches() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let memory = Arc::new(MemoryStore::new(tmp.path()).unwrap());
+    let filler = "unrelated words about the weather and the garden ".repeat(10);
+    memory
+        .append_to_section("Home", &format!("{filler}the garage code is 4512"))
+        .unwrap();
+    let tool = MemorySearchTool::new(memory);
+    let ctx = ExecutionContext::default();
+
+    let result = tool
+        .execute(
+            serde_json::json!({"query": "garage", "highlight": true}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    let output: Value = serde_json::from_str(&result.content).unwrap();
+    let snippet = output["results"][0]["snippet"].as_str().unwrap();
+    assert!(snippet.contains("<b>garage</b> code"), "snippet: {snippet}");
+    assert!(snippet.chars().count() < 300, "snippet: {snippet}");
+
+    let result = tool
+        .execute(serde_json::json!({"query": "garage"}), &ctx)
+        .await
+        .unwrap();
+    let output: Value = serde_json::from_str(&result.content).unwrap();
+    assert!(
+        !output["results"][0]["snippet"]
+            .as_str()
+            .unwrap()
+            .contains("<b>")
+    );
+}
+
+#[tokio::test]
+async fn test_memory_search_validates_tuning() {
+    let tool = create_tool();
+    let ctx = ExecutionContext::default();
+    for (params, expected) in [
+        (serde_json::json!({"query": "x", "fusion": "max"}), "fusion"),
+        (
+            serde_json::json!({"query": "x", "keyword_weight": 1.5}),
+            "keyword_weight",
+        ),
+        (
+            serde_json::json!({"query": "x", "highlight": "yes"}),
+            "highlight",
+        ),
+        (serde_json::json!({"query": "x", "limit": 0}), "limit"),
+    ] {
+        let result = tool.execute(params, &ctx).await.unwrap();
+        assert!(result.is_error);
+        assert!(result.content.contains(expected), "{}", result.content);
+    }
+}
@@ -1,14 +1,21 @@
 pub mod base;
 pub mod cron;
+pub mod followup;
 pub mod interactive;
 pub mod mcp;
 pub mod memory_search;
+pub mod pairing;
 pub mod read_only_wrapper;
 pub mod registry;
+pub mod scratchpad;
+pub mod search_conversation;
+pub mod self_test;
+pub mod set_preference;
 pub mod setup;
 pub mod spawn;
 pub mod stash;
 pub mod subagent_control;
+pub mod token_breakdown;
 pub mod tool_search;
 pub mod workspace_tool;
 
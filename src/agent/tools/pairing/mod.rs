@@ -0,0 +1,187 @@
+use crate::actions;
+use crate::agent::tools::base::{ExecutionContext, SubagentAccess, ToolCapabilities, ToolCategory};
+use crate::agent::tools::{Tool, ToolResult};
+use crate::pairing::PairingStore;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+const SENDER_ID_META_KEY: &str = "sender_id";
+/// Lifetime of a pairing code, matching the pairing store.
+const CODE_TTL_SECS: u64 = 15 * 60;
+
+/// Manage pairing from chat: list pending requests, approve a code, revoke a
+/// sender. Only senders in `channels.adminSenders` may use it.
+pub struct PairingTool {
+    store: PairingStore,
+    admins: Vec<String>,
+}
+
+impl PairingTool {
+    pub fn new(store: PairingStore, admins: Vec<String>) -> Self {
+        Self { store, admins }
+    }
+
+    /// `channel:sender_id` of the sender of this message, if it is an admin.
+    fn admin(&self, ctx: &ExecutionContext) -> Option<String> {
+        let sender_id = ctx
+            .metadata
+            .get(SENDER_ID_META_KEY)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())?;
+        let sender = format!("{}:{}", ctx.channel, sender_id);
+        self.admins.contains(&sender).then_some(sender)
+    }
+
+    fn list(&self, channel: Option<&str>) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let pending = self.store.list_pending();
+        let mut out = if pending.is_empty() {
+            "No pending pairing requests.".to_string()
+        } else {
+            let mut out = "Pending pairing requests:".to_string();
+            for req in pending {
+                let remaining = CODE_TTL_SECS.saturating_sub(now.saturating_sub(req.created_at));
+                let _ = write!(
+                    out,
+                    "\n- [{}] {}:{} (expires in {}m)",
+                    req.code,
+                    req.channel,
+                    req.sender_id,
+                    remaining.div_ceil(60)
+                );
+            }
+            out
+        };
+        match channel {
+            Some(channel) => {
+                let senders = self.store.list_channel_senders(channel).unwrap_or_default();
+                if senders.is_empty() {
+                    let _ = write!(out, "\n\nNo paired senders on {channel}.");
+                } else {
+                    let _ = write!(
+                        out,
+                        "\n\nPaired senders on {channel}: {}",
+                        senders.join(", ")
+                    );
+                }
+            }
+            None => {
+                let _ = write!(out, "\n\nPaired senders: {}", self.store.paired_count());
+            }
+        }
+        out
+    }
+}
+
+#[async_trait]
+impl Tool for PairingTool {
+    fn name(&self) -> &'static str {
+        "pairing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Manage who may message you through pairing. list_requests shows pending pairing \
+         codes (and the paired senders of a channel when one is given); approve grants access \
+         to the sender who was given a code; revoke removes a paired sender. Only \
+         administrators can use this tool."
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list_requests", "approve", "revoke"],
+                    "description": "list_requests, approve a code, or revoke a sender"
+                },
+                "code": {
+                    "type": "string",
+                    "description": "8-character pairing code (for approve)"
+                },
+                "channel": {
+                    "type": "string",
+                    "description": "Channel name such as telegram or slack (for revoke; optional for list_requests)"
+                },
+                "sender_id": {
+                    "type": "string",
+                    "description": "Sender ID to revoke, as shown by list_requests"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            subagent_access: SubagentAccess::Denied,
+            actions: actions![list_requests: ro, approve, revoke],
+            category: ToolCategory::Core,
+            ..Default::default()
+        }
+    }
+
+    async fn execute(&self, params: Value, ctx: &ExecutionContext) -> anyhow::Result<ToolResult> {
+        let Some(admin) = self.admin(ctx) else {
+            return Ok(ToolResult::error(
+                "pairing can only be managed by an administrator (channels.adminSenders)",
+            ));
+        };
+        let action = params["action"].as_str().unwrap_or_default();
+        match action {
+            "list_requests" => {
+                let channel = params["channel"].as_str().filter(|c| !c.is_empty());
+                Ok(ToolResult::new(self.list(channel)))
+            }
+            "approve" => {
+                let Some(code) = params["code"]
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                else {
+                    return Ok(ToolResult::error("approve needs a 'code'"));
+                };
+                match self
+                    .store
+                    .approve_with_client(code, &format!("tool:{admin}"))
+                {
+                    Ok(Some((channel, sender_id))) => Ok(ToolResult::new(format!(
+                        "Approved {channel}:{sender_id}. They can message now."
+                    ))),
+                    Ok(None) => Ok(ToolResult::error(format!(
+                        "invalid or expired code: {code}"
+                    ))),
+                    Err(e) => Ok(ToolResult::error(e.to_string())),
+                }
+            }
+            "revoke" => {
+                let channel = params["channel"].as_str().unwrap_or_default();
+                let sender_id = params["sender_id"].as_str().unwrap_or_default();
+                if channel.is_empty() || sender_id.is_empty() {
+                    return Ok(ToolResult::error("revoke needs 'channel' and 'sender_id'"));
+                }
+                if self.store.revoke(channel, sender_id)? {
+                    info!("pairing revoked by {}: {}:{}", admin, channel, sender_id);
+                    Ok(ToolResult::new(format!("Revoked {channel}:{sender_id}.")))
+                } else {
+                    Ok(ToolResult::new(format!(
+                        "{channel}:{sender_id} was not paired."
+                    )))
+                }
+            }
+            _ => Ok(ToolResult::error(format!(
+                "unknown action '{action}' (expected list_requests, approve or revoke)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
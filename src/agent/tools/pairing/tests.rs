@@ -0,0 +1,123 @@
+use super::*;
+use crate::agent::memory::memory_db::MemoryDB;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn make_tool() -> (PairingTool, PairingStore) {
+    let db = Arc::new(MemoryDB::new(":memory:").expect("test db"));
+    let tool = PairingTool::new(
+        PairingStore::new(db.clone()),
+        vec!["telegram:admin".to_string()],
+    );
+    (tool, PairingStore::new(db))
+}
+
+fn ctx_for(channel: &str, sender_id: &str) -> ExecutionContext {
+    ExecutionContext {
+        channel: channel.to_string(),
+        chat_id: "1".to_string(),
+        metadata: HashMap::from([(
+            SENDER_ID_META_KEY.to_string(),
+            Value::String(sender_id.to_string()),
+        )]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_admin_lists_approves_and_revokes() {
+    let (tool, store) = make_tool();
+    let admin = ctx_for("telegram", "admin");
+    let code = store.request_pairing("slack", "U42").unwrap().unwrap();
+
+    let result = tool
+        .execute(serde_json::json!({"action": "list_requests"}), &admin)
+        .await
+        .unwrap();
+    assert!(
+        result.content.contains(&format!("[{code}] slack:U42")),
+        "{}",
+        result.content
+    );
+    assert!(result.content.contains("Paired senders: 0"));
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "approve", "code": code.to_lowercase()}),
+            &admin,
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error, "{}", result.content);
+    assert!(result.content.contains("Approved slack:U42"));
+    assert!(store.is_paired("slack", "U42"));
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "list_requests", "channel": "slack"}),
+            &admin,
+        )
+        .await
+        .unwrap();
+    assert!(result.content.contains("No pending pairing requests."));
+    assert!(result.content.contains("Paired senders on slack: U42"));
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "revoke", "channel": "slack", "sender_id": "U42"}),
+            &admin,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.content, "Revoked slack:U42.");
+    assert!(!store.is_paired("slack", "U42"));
+}
+
+#[tokio::test]
+async fn test_non_admin_rejected() {
+    let (tool, store) = make_tool();
+    let code = store.request_pairing("slack", "U42").unwrap().unwrap();
+
+    // Same sender id on another channel, another sender, and no sender at all
+    for ctx in [
+        ctx_for("slack", "admin"),
+        ctx_for("telegram", "someone"),
+        ExecutionContext::default(),
+    ] {
+        let result = tool
+            .execute(serde_json::json!({"action": "approve", "code": code}), &ctx)
+            .await
+            .unwrap();
+        assert!(result.is_error);
+        assert!(result.content.contains("administrator"));
+    }
+    assert!(!store.is_paired("slack", "U42"));
+}
+
+#[tokio::test]
+async fn test_approve_invalid_code() {
+    let (tool, _) = make_tool();
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "approve", "code": "ZZZZZZZZ"}),
+            &ctx_for("telegram", "admin"),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("invalid or expired code"));
+}
+
+#[test]
+fn test_pairing_capabilities() {
+    let (tool, _) = make_tool();
+    let caps = tool.capabilities();
+    assert_eq!(caps.subagent_access, SubagentAccess::Denied);
+    let read_only: Vec<&str> = caps
+        .actions
+        .iter()
+        .filter(|a| a.read_only)
+        .map(|a| a.name)
+        .collect();
+    assert_eq!(read_only, ["list_requests"]);
+}
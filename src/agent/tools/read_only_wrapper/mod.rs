@@ -109,7 +109,7 @@ impl Tool for ReadOnlyToolWrapper {
         self.inner.requires_approval_for_action(action)
     }
 
-    fn execution_timeout(&self) -> std::time::Duration {
+    fn execution_timeout(&self) -> Option<std::time::Duration> {
         self.inner.execution_timeout()
     }
 }
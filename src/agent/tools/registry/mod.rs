@@ -7,7 +7,7 @@ use serde_json::Value;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
 
@@ -147,6 +147,11 @@ pub struct ToolRegistry {
     cached_definitions: std::sync::Mutex<Option<Vec<crate::providers::base::ToolDefinition>>>,
     /// Accumulated routing rules collected from all registered tools.
     routing_rules: Vec<crate::agent::tools::base::routing_types::StaticRule>,
+    /// Configured execution timeouts keyed by tool name (`tools.timeouts`).
+    timeouts: HashMap<String, Duration>,
+    /// Configured fallback for tools without their own timeout
+    /// (`tools.defaultTimeoutSecs`).
+    default_timeout: Option<Duration>,
 }
 
 impl ToolRegistry {
@@ -167,6 +172,8 @@ impl ToolRegistry {
             definition_cache: HashMap::new(),
             cached_definitions: std::sync::Mutex::new(None),
             routing_rules: Vec::new(),
+            timeouts: HashMap::new(),
+            default_timeout: None,
         }
     }
 
@@ -189,6 +196,8 @@ impl ToolRegistry {
             definition_cache: HashMap::new(),
             cached_definitions: std::sync::Mutex::new(None),
             routing_rules: Vec::new(),
+            timeouts: HashMap::new(),
+            default_timeout: None,
         }
     }
 
@@ -222,6 +231,42 @@ impl ToolRegistry {
         &self.routing_rules
     }
 
+    /// Install per-tool token buckets. Runs after the cache middleware so
+    /// cache hits do not consume a tool's budget.
+    pub fn set_rate_limits(
+        &mut self,
+        limits: &HashMap<String, crate::config::ToolRateLimitConfig>,
+    ) {
+        if limits.is_empty() {
+            return;
+        }
+        self.middleware
+            .push(Arc::new(RateLimitMiddleware::new(limits)));
+    }
+
+    /// Install configured execution timeouts. Per-tool entries win over the
+    /// tool's built-in timeout; `default_secs` applies to tools that keep the
+    /// built-in default.
+    pub fn set_timeouts(&mut self, timeouts: &HashMap<String, u64>, default_secs: Option<u64>) {
+        self.timeouts = timeouts
+            .iter()
+            .map(|(name, secs)| (name.clone(), Duration::from_secs(*secs)))
+            .collect();
+        self.default_timeout = default_secs.map(Duration::from_secs);
+    }
+
+    /// Effective execution timeout for a tool: `tools.timeouts` entry, then
+    /// the tool's own override, then `tools.defaultTimeoutSecs`, then
+    /// [`DEFAULT_EXECUTION_TIMEOUT`](crate::agent::tools::base::DEFAULT_EXECUTION_TIMEOUT).
+    fn timeout_for(&self, name: &str, tool: &dyn Tool) -> Duration {
+        self.timeouts
+            .get(name)
+            .copied()
+            .or_else(|| tool.execution_timeout())
+            .or(self.default_timeout)
+            .unwrap_or(crate::agent::tools::base::DEFAULT_EXECUTION_TIMEOUT)
+    }
+
     /// Register a tool whose schema is hidden from LLM requests until
     /// activated via `tool_search`. The tool can still be executed.
     pub fn register_deferred(&mut self, tool: Arc<dyn Tool>) {
@@ -438,8 +483,9 @@ impl ToolRegistry {
     ) -> Result<ToolResult> {
         let tool_name = name.to_string();
         let ctx = ctx.clone();
-        let timeout = tool.execution_timeout();
-        let timeout_secs = timeout.as_secs();
+        let timeout = self.timeout_for(name, tool.as_ref());
+        let timeout_secs = timeout.as_secs_f64();
+        let start = Instant::now();
 
         let handle = tokio::task::spawn(async move {
             tokio::time::timeout(timeout, tool.execute(params, &ctx)).await
@@ -448,9 +494,13 @@ impl ToolRegistry {
         match handle.await {
             Ok(Ok(result)) => result,
             Ok(Err(_)) => {
-                warn!("Tool '{}' timed out after {}s", tool_name, timeout_secs);
+                let elapsed = start.elapsed().as_secs_f64();
+                warn!(
+                    "tool '{}' timed out after {:.1}s (limit {}s)",
+                    tool_name, elapsed, timeout_secs
+                );
                 Ok(ToolResult::error(format!(
-                    "Tool '{tool_name}' timed out after {timeout_secs}s"
+                    "Tool '{tool_name}' timed out after {elapsed:.1}s (limit {timeout_secs}s)"
                 )))
             }
             Err(join_err) => {
@@ -614,6 +664,108 @@ impl ToolMiddleware for TruncationMiddleware {
     }
 }
 
+/// Token bucket for one tool: `capacity` tokens refilled at `refill_per_sec`.
+/// Tokens may go negative while callers are queued for a future token.
+struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    max_wait: Duration,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &crate::config::ToolRateLimitConfig) -> Self {
+        let capacity = f64::from(limit.max_calls.max(1));
+        Self {
+            capacity,
+            refill_per_sec: capacity / limit.window_secs.max(1) as f64,
+            max_wait: Duration::from_secs(limit.max_wait_secs),
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token. Returns how long the caller must wait before running,
+    /// or `Err(wait)` when that exceeds `max_wait` (no token is taken).
+    fn acquire(&mut self, now: Instant) -> std::result::Result<Duration, Duration> {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        let wait = if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec)
+        };
+        if wait > self.max_wait {
+            return Err(wait);
+        }
+        self.tokens -= 1.0;
+        Ok(wait)
+    }
+}
+
+/// Rate-limit middleware — enforces per-tool call budgets from
+/// `tools.rateLimits`. Over-limit calls queue for up to the tool's
+/// `maxWaitSecs`, then fail with a retry hint instead of hitting the upstream API.
+pub struct RateLimitMiddleware {
+    buckets: std::sync::Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimitMiddleware {
+    pub fn new(limits: &HashMap<String, crate::config::ToolRateLimitConfig>) -> Self {
+        Self {
+            buckets: std::sync::Mutex::new(
+                limits
+                    .iter()
+                    .map(|(name, limit)| (name.clone(), TokenBucket::new(limit)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolMiddleware for RateLimitMiddleware {
+    async fn before_execute(
+        &self,
+        name: &str,
+        _params: &Value,
+        _ctx: &ExecutionContext,
+        _tool: &dyn Tool,
+    ) -> Option<ToolResult> {
+        // Lock held only for bookkeeping; queued callers sleep outside it
+        let acquired = {
+            let mut buckets = self
+                .buckets
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            buckets.get_mut(name)?.acquire(Instant::now())
+        };
+        match acquired {
+            Ok(wait) => {
+                if !wait.is_zero() {
+                    debug!("Tool '{}' rate limited, queued for {:?}", name, wait);
+                    tokio::time::sleep(wait).await;
+                }
+                None
+            }
+            Err(wait) => {
+                let retry_secs = wait.as_secs_f64().ceil() as u64;
+                warn!("Tool '{}' rate limited (retry in {}s)", name, retry_secs);
+                metrics::counter!("oxicrab_tool_rate_limited_total",
+                    "tool" => name.to_string()
+                )
+                .increment(1);
+                Some(ToolResult::error(format!(
+                    "Tool '{name}' is rate limited, try again in {retry_secs}s. Do not retry it immediately; continue with other work or tell the user."
+                )))
+            }
+        }
+    }
+}
+
 /// Logging middleware — logs tool execution timing and results.
 pub struct LoggingMiddleware;
 
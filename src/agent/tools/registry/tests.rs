@@ -681,3 +681,187 @@ fn test_register_rejects_control_chars_in_name() {
         "tool with control chars should be rejected"
     );
 }
+
+fn rate_limit(
+    max_calls: u32,
+    window_secs: u64,
+    max_wait_secs: u64,
+) -> crate::config::ToolRateLimitConfig {
+    crate::config::ToolRateLimitConfig {
+        max_calls,
+        window_secs,
+        max_wait_secs,
+    }
+}
+
+#[test]
+fn test_token_bucket_refills_and_queues() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(&rate_limit(2, 10, 0));
+    bucket.last_refill = start;
+    assert_eq!(bucket.acquire(start), Ok(Duration::ZERO));
+    assert_eq!(bucket.acquire(start), Ok(Duration::ZERO));
+    // Empty: one token refills every 5s, no queueing allowed
+    let wait = bucket.acquire(start).unwrap_err();
+    assert!(wait > Duration::from_secs(4) && wait <= Duration::from_secs(5));
+    // Refilled after 5s
+    assert_eq!(
+        bucket.acquire(start + Duration::from_secs(5)),
+        Ok(Duration::ZERO)
+    );
+
+    // With maxWaitSecs the over-limit caller reserves the next token
+    let mut queued = TokenBucket::new(&rate_limit(1, 2, 5));
+    queued.last_refill = start;
+    assert_eq!(queued.acquire(start), Ok(Duration::ZERO));
+    let wait = queued.acquire(start).unwrap();
+    assert!(wait > Duration::from_secs(1) && wait <= Duration::from_secs(2));
+    // The next caller queues behind it
+    let wait = queued.acquire(start).unwrap();
+    assert!(wait > Duration::from_secs(3) && wait <= Duration::from_secs(4));
+    // A third would exceed maxWaitSecs
+    assert!(queued.acquire(start).is_err());
+}
+
+#[tokio::test]
+async fn test_rate_limit_blocks_over_limit_calls() {
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingTool {
+        name: &'static str,
+        calls: Arc<AtomicUsize>,
+    }
+    #[async_trait]
+    impl Tool for CountingTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn description(&self) -> &'static str {
+            "test"
+        }
+        fn parameters(&self) -> Value {
+            json!({})
+        }
+        async fn execute(
+            &self,
+            _params: Value,
+            _ctx: &ExecutionContext,
+        ) -> anyhow::Result<ToolResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ToolResult::new("ok"))
+        }
+    }
+
+    let limited_calls = Arc::new(AtomicUsize::new(0));
+    let free_calls = Arc::new(AtomicUsize::new(0));
+    let mut registry = ToolRegistry::new();
+    registry.register(Arc::new(CountingTool {
+        name: "limited",
+        calls: limited_calls.clone(),
+    }));
+    registry.register(Arc::new(CountingTool {
+        name: "free",
+        calls: free_calls.clone(),
+    }));
+    registry.set_rate_limits(&HashMap::from([(
+        "limited".to_string(),
+        rate_limit(2, 3600, 0),
+    )]));
+
+    let ctx = ExecutionContext::default();
+    for _ in 0..2 {
+        let result = registry.execute("limited", json!({}), &ctx).await.unwrap();
+        assert!(!result.is_error);
+    }
+    let result = registry.execute("limited", json!({}), &ctx).await.unwrap();
+    assert!(result.is_error);
+    assert!(
+        result.content.contains("rate limited"),
+        "{}",
+        result.content
+    );
+    assert_eq!(limited_calls.load(Ordering::SeqCst), 2);
+
+    for _ in 0..3 {
+        let result = registry.execute("free", json!({}), &ctx).await.unwrap();
+        assert!(!result.is_error);
+    }
+    assert_eq!(free_calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_configured_timeouts_override_tool_defaults() {
+    use async_trait::async_trait;
+
+    struct SlowTool {
+        name: &'static str,
+        own_timeout: Option<Duration>,
+    }
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn description(&self) -> &'static str {
+            "test"
+        }
+        fn parameters(&self) -> Value {
+            json!({})
+        }
+        fn execution_timeout(&self) -> Option<Duration> {
+            self.own_timeout
+        }
+        async fn execute(
+            &self,
+            _params: Value,
+            _ctx: &ExecutionContext,
+        ) -> anyhow::Result<ToolResult> {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Ok(ToolResult::new("done"))
+        }
+    }
+
+    let builtin = crate::agent::tools::base::DEFAULT_EXECUTION_TIMEOUT;
+    let mut registry = ToolRegistry::new();
+    for (name, own_timeout) in [
+        ("listed", None),
+        ("plain", None),
+        ("custom", Some(Duration::from_secs(7))),
+        // Explicitly set to the built-in value: still the tool's own choice
+        ("explicit", Some(builtin)),
+    ] {
+        registry.register(Arc::new(SlowTool { name, own_timeout }));
+    }
+    registry.set_timeouts(&HashMap::from([("listed".to_string(), 1)]), Some(30));
+
+    let listed = registry.get("listed").unwrap();
+    assert_eq!(
+        registry.timeout_for("listed", listed.as_ref()),
+        Duration::from_secs(1)
+    );
+    let plain = registry.get("plain").unwrap();
+    assert_eq!(
+        registry.timeout_for("plain", plain.as_ref()),
+        Duration::from_secs(30)
+    );
+    let custom = registry.get("custom").unwrap();
+    assert_eq!(
+        registry.timeout_for("custom", custom.as_ref()),
+        Duration::from_secs(7)
+    );
+    let explicit = registry.get("explicit").unwrap();
+    assert_eq!(registry.timeout_for("explicit", explicit.as_ref()), builtin);
+
+    let result = registry
+        .execute("listed", json!({}), &ExecutionContext::default())
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(
+        result.content.contains("Tool 'listed' timed out after 1.")
+            && result.content.contains("(limit 1s)"),
+        "{}",
+        result.content
+    );
+}
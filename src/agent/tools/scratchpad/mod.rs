@@ -0,0 +1,209 @@
+use crate::actions;
+use crate::agent::tools::base::{ExecutionContext, ToolCapabilities, ToolCategory};
+use crate::agent::tools::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const REQUEST_ID_META_KEY: &str = "request_id";
+const OPEN_TAG: &str = "<scratchpad>";
+const CLOSE_TAG: &str = "</scratchpad>";
+
+/// Request-scoped working notes. The scratchpad tools write and read here;
+/// the agent loop clears a request's notes when its run starts and ends.
+#[derive(Clone, Default)]
+pub struct Scratchpad {
+    inner: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl Scratchpad {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a note, returning the total size in chars, or `Err` with the
+    /// current size when the note would exceed `max_chars`.
+    fn append(&self, request_id: &str, note: &str, max_chars: usize) -> Result<usize, usize> {
+        let mut notes = self
+            .inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let entry = notes.entry(request_id.to_string()).or_default();
+        let used = entry.chars().count();
+        let added = note.chars().count() + usize::from(!entry.is_empty());
+        if used + added > max_chars {
+            return Err(used);
+        }
+        if !entry.is_empty() {
+            entry.push('\n');
+        }
+        entry.push_str(note);
+        Ok(used + added)
+    }
+
+    fn read(&self, request_id: &str) -> Option<String> {
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(request_id)
+            .cloned()
+    }
+
+    pub fn clear(&self, request_id: &str) {
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(request_id);
+    }
+}
+
+/// Remove `<scratchpad>...</scratchpad>` blocks (as returned by
+/// `scratchpad_read`) from a final response, including an unclosed trailing
+/// block.
+pub fn strip_scratchpad_blocks(content: &str) -> String {
+    if !content.contains(OPEN_TAG) {
+        return content.to_string();
+    }
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find(OPEN_TAG) {
+        result.push_str(&rest[..start]);
+        // An unclosed block runs to the end of the response
+        let Some(end) = rest[start..].find(CLOSE_TAG) else {
+            rest = "";
+            break;
+        };
+        rest = rest[start + end + CLOSE_TAG.len()..].trim_start();
+    }
+    result.push_str(rest);
+    result.trim().to_string()
+}
+
+fn request_id(ctx: &ExecutionContext) -> Option<&str> {
+    ctx.metadata
+        .get(REQUEST_ID_META_KEY)
+        .and_then(Value::as_str)
+}
+
+/// Tool that appends a working note to the current turn's scratchpad.
+pub struct ScratchpadAppendTool {
+    scratchpad: Scratchpad,
+    max_chars: usize,
+}
+
+impl ScratchpadAppendTool {
+    pub fn new(scratchpad: Scratchpad, max_chars: usize) -> Self {
+        Self {
+            scratchpad,
+            max_chars,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ScratchpadAppendTool {
+    fn name(&self) -> &'static str {
+        "scratchpad_append"
+    }
+
+    fn description(&self) -> &'static str {
+        "Append a private working note to this turn's scratchpad: intermediate results, a plan, \
+         hypotheses, or what is left to check. Notes are never shown to the user and are discarded \
+         when you send your final reply. Use scratchpad_read to review them across tool calls."
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "note": {
+                    "type": "string",
+                    "description": "Text to append (freeform)"
+                }
+            },
+            "required": ["note"]
+        })
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            actions: actions![append: ro],
+            category: ToolCategory::Core,
+            ..Default::default()
+        }
+    }
+
+    async fn execute(&self, params: Value, ctx: &ExecutionContext) -> anyhow::Result<ToolResult> {
+        let note = params["note"].as_str().unwrap_or_default().trim();
+        if note.is_empty() {
+            return Ok(ToolResult::error("note must not be empty"));
+        }
+        let Some(request_id) = request_id(ctx) else {
+            return Ok(ToolResult::error(
+                "scratchpad is not available outside an agent turn",
+            ));
+        };
+        match self.scratchpad.append(request_id, note, self.max_chars) {
+            Ok(used) => Ok(ToolResult::new(format!(
+                "Noted ({used}/{} chars used).",
+                self.max_chars
+            ))),
+            Err(used) => Ok(ToolResult::error(format!(
+                "scratchpad full ({used}/{} chars used); summarize what matters in your reply",
+                self.max_chars
+            ))),
+        }
+    }
+}
+
+/// Tool that returns the current turn's scratchpad notes.
+pub struct ScratchpadReadTool {
+    scratchpad: Scratchpad,
+}
+
+impl ScratchpadReadTool {
+    pub fn new(scratchpad: Scratchpad) -> Self {
+        Self { scratchpad }
+    }
+}
+
+#[async_trait]
+impl Tool for ScratchpadReadTool {
+    fn name(&self) -> &'static str {
+        "scratchpad_read"
+    }
+
+    fn description(&self) -> &'static str {
+        "Read the working notes appended to this turn's scratchpad with scratchpad_append. \
+         Do not copy them into your reply."
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            actions: actions![read: ro],
+            category: ToolCategory::Core,
+            ..Default::default()
+        }
+    }
+
+    async fn execute(&self, _params: Value, ctx: &ExecutionContext) -> anyhow::Result<ToolResult> {
+        let notes = request_id(ctx).and_then(|id| self.scratchpad.read(id));
+        Ok(match notes {
+            Some(notes) => ToolResult::new(format!("{OPEN_TAG}\n{notes}\n{CLOSE_TAG}")),
+            None => ToolResult::new("The scratchpad is empty."),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
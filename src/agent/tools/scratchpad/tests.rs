@@ -0,0 +1,92 @@
+use super::*;
+
+fn ctx(request_id: &str) -> ExecutionContext {
+    ExecutionContext {
+        metadata: HashMap::from([(
+            REQUEST_ID_META_KEY.to_string(),
+            Value::String(request_id.to_string()),
+        )]),
+        ..ExecutionContext::default()
+    }
+}
+
+#[tokio::test]
+async fn test_append_and_read_are_request_scoped() {
+    let pad = Scratchpad::new();
+    let append = ScratchpadAppendTool::new(pad.clone(), 1000);
+    let read = ScratchpadReadTool::new(pad.clone());
+
+    append
+        .execute(serde_json::json!({"note": "step 1: 42"}), &ctx("req-1"))
+        .await
+        .unwrap();
+    append
+        .execute(serde_json::json!({"note": "step 2: 7"}), &ctx("req-1"))
+        .await
+        .unwrap();
+
+    let result = read
+        .execute(serde_json::json!({}), &ctx("req-1"))
+        .await
+        .unwrap();
+    assert_eq!(
+        result.content,
+        "<scratchpad>\nstep 1: 42\nstep 2: 7\n</scratchpad>"
+    );
+
+    let other = read
+        .execute(serde_json::json!({}), &ctx("req-2"))
+        .await
+        .unwrap();
+    assert_eq!(other.content, "The scratchpad is empty.");
+
+    pad.clear("req-1");
+    let cleared = read
+        .execute(serde_json::json!({}), &ctx("req-1"))
+        .await
+        .unwrap();
+    assert_eq!(cleared.content, "The scratchpad is empty.");
+}
+
+#[tokio::test]
+async fn test_append_rejects_over_limit() {
+    let pad = Scratchpad::new();
+    let append = ScratchpadAppendTool::new(pad.clone(), 10);
+
+    let ok = append
+        .execute(serde_json::json!({"note": "12345"}), &ctx("r"))
+        .await
+        .unwrap();
+    assert!(!ok.is_error);
+    assert!(ok.content.contains("5/10"));
+
+    let full = append
+        .execute(serde_json::json!({"note": "67890"}), &ctx("r"))
+        .await
+        .unwrap();
+    assert!(full.is_error);
+    assert!(full.content.contains("scratchpad full (5/10"));
+
+    let empty = append
+        .execute(serde_json::json!({"note": "  "}), &ctx("r"))
+        .await
+        .unwrap();
+    assert!(empty.is_error);
+}
+
+#[test]
+fn test_strip_scratchpad_blocks() {
+    assert_eq!(strip_scratchpad_blocks("plain answer"), "plain answer");
+    assert_eq!(
+        strip_scratchpad_blocks("<scratchpad>\nx = 1\n</scratchpad>\nThe answer is 1."),
+        "The answer is 1."
+    );
+    assert_eq!(
+        strip_scratchpad_blocks("Before <scratchpad>a</scratchpad> after"),
+        "Before after"
+    );
+    assert_eq!(
+        strip_scratchpad_blocks("Answer.\n<scratchpad>unfinished"),
+        "Answer."
+    );
+}
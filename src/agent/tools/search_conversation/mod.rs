@@ -0,0 +1,213 @@
+use crate::actions;
+use crate::agent::profile::ProfileResolver;
+use crate::agent::tools::base::{ExecutionContext, ToolCapabilities, ToolCategory};
+use crate::agent::tools::{Tool, ToolResult};
+use crate::session::SessionStore;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+const SESSION_KEY_META_KEY: &str = "session_key";
+const DEFAULT_LIMIT: usize = 5;
+const MAX_LIMIT: usize = 20;
+/// Characters of context kept on each side of the first match.
+const EXCERPT_CONTEXT_CHARS: usize = 150;
+
+/// Exact-recall search over the current session's stored message history
+/// (everything the session store keeps, including messages compaction has
+/// since summarized away).
+pub struct SearchConversationTool {
+    sessions: Arc<dyn SessionStore>,
+    profiles: Option<Arc<ProfileResolver>>,
+}
+
+impl SearchConversationTool {
+    pub fn new(sessions: Arc<dyn SessionStore>) -> Self {
+        Self {
+            sessions,
+            profiles: None,
+        }
+    }
+
+    /// Read sessions from the store of the chat's profile instead.
+    #[must_use]
+    pub fn with_profiles(mut self, profiles: Arc<ProfileResolver>) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+}
+
+/// A message matching every query term, with its position in the history.
+struct Match<'a> {
+    index: usize,
+    role: &'a str,
+    timestamp: &'a str,
+    excerpt: String,
+}
+
+/// Case-insensitive search for messages containing all `terms`, newest first.
+fn search_history<'a>(
+    history: &'a [HashMap<String, Value>],
+    terms: &[String],
+    limit: usize,
+) -> Vec<Match<'a>> {
+    let field = |m: &'a HashMap<String, Value>, key: &str| -> &'a str {
+        m.get(key).and_then(Value::as_str).unwrap_or_default()
+    };
+    history
+        .iter()
+        .enumerate()
+        .rev()
+        .filter_map(|(index, m)| {
+            let content = field(m, "content");
+            let lower = content.to_lowercase();
+            if !terms.iter().all(|t| lower.contains(t.as_str())) {
+                return None;
+            }
+            Some(Match {
+                index,
+                role: field(m, "role"),
+                timestamp: field(m, "timestamp"),
+                excerpt: excerpt(content, &lower, &terms[0]),
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Up to [`EXCERPT_CONTEXT_CHARS`] on each side of the first occurrence of
+/// `term` (already lowercased, found in `lower`).
+fn excerpt(content: &str, lower: &str, term: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    // Map the byte offset in the lowercased text to a char index. Lowercasing
+    // can change byte lengths, so count chars instead of reusing the offset.
+    let match_char = lower
+        .find(term)
+        .map_or(0, |byte| lower[..byte].chars().count())
+        .min(chars.len());
+    let start = match_char.saturating_sub(EXCERPT_CONTEXT_CHARS);
+    let end = (match_char + term.chars().count() + EXCERPT_CONTEXT_CHARS).min(chars.len());
+    let mut text: String = chars[start..end]
+        .iter()
+        .collect::<String>()
+        .replace('\n', " ");
+    if start > 0 {
+        text.insert_str(0, "...");
+    }
+    if end < chars.len() {
+        text.push_str("...");
+    }
+    text
+}
+
+/// Render an RFC 3339 timestamp as `YYYY-MM-DD HH:MM UTC`.
+fn format_timestamp(ts: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(ts).map_or_else(
+        |_| "unknown time".to_string(),
+        |dt| {
+            dt.with_timezone(&chrono::Utc)
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string()
+        },
+    )
+}
+
+#[async_trait]
+impl Tool for SearchConversationTool {
+    fn name(&self) -> &'static str {
+        "search_conversation"
+    }
+
+    fn description(&self) -> &'static str {
+        "Search the full message history of this conversation for exact words or phrases, \
+         including older messages that were summarized out of your context. Returns matching \
+         excerpts with timestamps, newest first. Use when the user refers to something said \
+         earlier that you no longer see (\"what did we decide about X?\"). For long-term facts \
+         across conversations, use memory_search instead."
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Words to find (case-insensitive). Messages must contain every word."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum excerpts to return (default 5, max 20)",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            actions: actions![search: ro],
+            category: ToolCategory::Core,
+            ..Default::default()
+        }
+    }
+
+    async fn execute(&self, params: Value, ctx: &ExecutionContext) -> anyhow::Result<ToolResult> {
+        let query = params["query"].as_str().unwrap_or_default();
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if terms.is_empty() {
+            return Ok(ToolResult::error("query must not be empty"));
+        }
+        let limit = params["limit"]
+            .as_u64()
+            .map_or(DEFAULT_LIMIT, |l| usize::try_from(l).unwrap_or(MAX_LIMIT))
+            .clamp(1, MAX_LIMIT);
+
+        let session_key = ctx
+            .metadata
+            .get(SESSION_KEY_META_KEY)
+            .and_then(Value::as_str)
+            .map_or_else(
+                || format!("{}:{}", ctx.channel, ctx.chat_id),
+                str::to_string,
+            );
+        let sessions = match self.profiles {
+            Some(ref profiles) => &profiles.for_session_key(&session_key).sessions,
+            None => &self.sessions,
+        };
+        let session = sessions.get_or_create(&session_key).await?;
+        let history = session.get_full_history();
+
+        let matches = search_history(&history, &terms, limit);
+        if matches.is_empty() {
+            return Ok(ToolResult::new(format!(
+                "No messages in this conversation ({} stored) contain \"{query}\".",
+                history.len()
+            )));
+        }
+
+        let mut out = format!(
+            "{} match(es) for \"{query}\" in {} stored messages (newest first):\n",
+            matches.len(),
+            history.len()
+        );
+        for m in &matches {
+            let _ = write!(
+                out,
+                "\n[#{} {} | {}] {}",
+                m.index + 1,
+                format_timestamp(m.timestamp),
+                m.role,
+                m.excerpt
+            );
+        }
+        Ok(ToolResult::new(out))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,148 @@
+use super::*;
+use crate::agent::memory::memory_db::MemoryDB;
+use crate::session::SessionManager;
+
+async fn make_tool(key: &str, messages: &[(&str, &str)]) -> SearchConversationTool {
+    let db = Arc::new(MemoryDB::new(":memory:").expect("test db"));
+    let sessions = Arc::new(SessionManager::with_db(db));
+    let mut session = sessions.get_or_create(key).await.unwrap();
+    for (role, content) in messages {
+        session.add_message(*role, *content, HashMap::new());
+    }
+    sessions.save(&session).await.unwrap();
+    SearchConversationTool::new(sessions)
+}
+
+fn ctx_for(key: &str) -> ExecutionContext {
+    ExecutionContext {
+        channel: "telegram".to_string(),
+        chat_id: "42".to_string(),
+        metadata: HashMap::from([(
+            SESSION_KEY_META_KEY.to_string(),
+            Value::String(key.to_string()),
+        )]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_search_returns_newest_matches_first() {
+    let tool = make_tool(
+        "telegram:42",
+        &[
+            ("user", "Let's use Postgres for the billing service"),
+            ("assistant", "Sounds good, Postgres it is."),
+            ("user", "What's the weather like?"),
+            ("user", "Actually, switch billing to SQLite"),
+        ],
+    )
+    .await;
+
+    let result = tool
+        .execute(
+            serde_json::json!({"query": "BILLING"}),
+            &ctx_for("telegram:42"),
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    let lines: Vec<&str> = result.content.lines().collect();
+    assert!(lines[0].starts_with("2 match(es)"), "{}", result.content);
+    assert!(lines[2].starts_with("[#4 "), "{}", result.content);
+    assert!(lines[2].ends_with("user] Actually, switch billing to SQLite"));
+    assert!(lines[3].starts_with("[#1 "));
+    assert!(!result.content.contains("weather"));
+}
+
+#[tokio::test]
+async fn test_search_requires_all_terms_and_respects_limit() {
+    let tool = make_tool(
+        "telegram:42",
+        &[
+            ("user", "deploy the api"),
+            ("user", "deploy the frontend"),
+            ("user", "deploy the api again"),
+        ],
+    )
+    .await;
+    let ctx = ctx_for("telegram:42");
+
+    let result = tool
+        .execute(serde_json::json!({"query": "api deploy"}), &ctx)
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("2 match(es)"));
+    assert!(!result.content.contains("frontend"));
+
+    let result = tool
+        .execute(serde_json::json!({"query": "deploy", "limit": 1}), &ctx)
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("1 match(es)"));
+    assert!(result.content.contains("deploy the api again"));
+}
+
+#[tokio::test]
+async fn test_search_is_scoped_to_current_session() {
+    let tool = make_tool("telegram:42", &[("user", "secret plan")]).await;
+
+    let result = tool
+        .execute(serde_json::json!({"query": "plan"}), &ctx_for("discord:7"))
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    assert!(
+        result.content.starts_with("No messages"),
+        "{}",
+        result.content
+    );
+
+    // Falls back to channel:chat_id when no session key is in the metadata
+    let ctx = ExecutionContext {
+        channel: "telegram".to_string(),
+        chat_id: "42".to_string(),
+        ..Default::default()
+    };
+    let result = tool
+        .execute(serde_json::json!({"query": "plan"}), &ctx)
+        .await
+        .unwrap();
+    assert!(result.content.starts_with("1 match(es)"));
+}
+
+#[tokio::test]
+async fn test_search_rejects_empty_query() {
+    let tool = make_tool("telegram:42", &[]).await;
+    let result = tool
+        .execute(serde_json::json!({"query": "   "}), &ctx_for("telegram:42"))
+        .await
+        .unwrap();
+    assert!(result.is_error);
+}
+
+#[test]
+fn test_excerpt_trims_around_match() {
+    let content = format!("{}needle{}", "a".repeat(400), "b".repeat(400));
+    let text = excerpt(&content, &content.to_lowercase(), "needle");
+    assert!(text.starts_with("..."));
+    assert!(text.ends_with("..."));
+    assert!(text.contains("needle"));
+    assert_eq!(
+        text.chars().count(),
+        EXCERPT_CONTEXT_CHARS * 2 + "needle".len() + 6
+    );
+
+    // Multi-byte text before the match must not break slicing
+    let content = "Ünïcödé ÄÖÜ then NEEDLE";
+    let text = excerpt(content, &content.to_lowercase(), "needle");
+    assert_eq!(text, content);
+}
+
+#[test]
+fn test_format_timestamp() {
+    assert_eq!(
+        format_timestamp("2026-03-01T14:05:09.123+02:00"),
+        "2026-03-01 12:05 UTC"
+    );
+    assert_eq!(format_timestamp(""), "unknown time");
+}
@@ -0,0 +1,279 @@
+use crate::actions;
+use crate::agent::memory::MemoryStore;
+use crate::agent::memory::memory_db::ToolAuditEntry;
+use crate::agent::tools::base::{ExecutionContext, ToolCapabilities, ToolCategory};
+use crate::agent::tools::{Tool, ToolResult};
+use crate::cli::doctor::{
+    CheckResult, check_channel_configs, check_mcp_config, check_provider_warmup,
+    check_workspace_path,
+};
+use crate::config::{ChannelsConfig, McpConfig};
+use crate::providers::base::LLMProvider;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const PROVIDER_TIMEOUT: Duration = Duration::from_secs(20);
+/// How far back the tool audit log is scanned for failing tools.
+const AUDIT_WINDOW_HOURS: i64 = 24;
+const AUDIT_SCAN_LIMIT: usize = 1000;
+const MAX_ERROR_CHARS: usize = 160;
+
+/// Runs the `oxicrab doctor` health checks against the live agent (provider,
+/// memory database, workspace, embeddings, channels, MCP servers) and scans
+/// the tool audit log for tools whose latest call failed.
+pub struct SelfTestTool {
+    provider: Arc<dyn LLMProvider>,
+    memory: Arc<MemoryStore>,
+    workspace: PathBuf,
+    channels_config: Option<ChannelsConfig>,
+    mcp_config: Option<McpConfig>,
+}
+
+impl SelfTestTool {
+    pub fn new(
+        provider: Arc<dyn LLMProvider>,
+        memory: Arc<MemoryStore>,
+        workspace: PathBuf,
+        channels_config: Option<ChannelsConfig>,
+        mcp_config: Option<McpConfig>,
+    ) -> Self {
+        Self {
+            provider,
+            memory,
+            workspace,
+            channels_config,
+            mcp_config,
+        }
+    }
+
+    async fn check_provider(&self) -> CheckResult {
+        match tokio::time::timeout(
+            PROVIDER_TIMEOUT,
+            check_provider_warmup(self.provider.as_ref()),
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(_) => CheckResult::Fail(format!(
+                "{} (no response within {}s)",
+                self.provider.default_model(),
+                PROVIDER_TIMEOUT.as_secs()
+            )),
+        }
+    }
+
+    async fn check_database(&self) -> CheckResult {
+        let db = self.memory.db();
+        match tokio::task::spawn_blocking(move || db.check_writable()).await {
+            Ok(Ok(())) => CheckResult::Pass("writable".to_string()),
+            Ok(Err(e)) => CheckResult::Fail(format!("not writable: {e}")),
+            Err(e) => CheckResult::Fail(format!("check panicked: {e}")),
+        }
+    }
+
+    #[cfg(feature = "embeddings")]
+    async fn check_embeddings(&self) -> CheckResult {
+        if self.memory.embedding_service().is_none() {
+            return CheckResult::Skip("not loaded (disabled or still initializing)".to_string());
+        }
+        let memory = self.memory.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            memory
+                .embedding_service()
+                .map(|service| service.embed_query("self test"))
+        })
+        .await;
+        match result {
+            Ok(Some(Ok(vector))) => {
+                CheckResult::Pass(format!("embedded test query ({} dims)", vector.len()))
+            }
+            Ok(Some(Err(e))) => CheckResult::Fail(format!("embedding failed: {e}")),
+            Ok(None) => CheckResult::Skip("not loaded".to_string()),
+            Err(e) => CheckResult::Fail(format!("check panicked: {e}")),
+        }
+    }
+
+    #[cfg(not(feature = "embeddings"))]
+    #[allow(clippy::unused_async)]
+    async fn check_embeddings(&self) -> CheckResult {
+        CheckResult::Skip("not compiled".to_string())
+    }
+
+    async fn check_tools(&self) -> Vec<(String, CheckResult)> {
+        let db = self.memory.db();
+        let since = (chrono::Utc::now() - chrono::Duration::hours(AUDIT_WINDOW_HOURS))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        match tokio::task::spawn_blocking(move || db.list_tool_audit(&since, AUDIT_SCAN_LIMIT))
+            .await
+        {
+            Ok(Ok(entries)) => tool_checks(&entries),
+            Ok(Err(e)) => vec![(
+                "tools".to_string(),
+                CheckResult::Fail(format!("cannot read tool audit log: {e}")),
+            )],
+            Err(e) => vec![(
+                "tools".to_string(),
+                CheckResult::Fail(format!("check panicked: {e}")),
+            )],
+        }
+    }
+}
+
+/// Channel checks from config, upgraded with live state when the gateway has
+/// started channels in this process (`live` is empty otherwise).
+fn channel_checks(
+    channels: &ChannelsConfig,
+    live: &BTreeMap<String, bool>,
+) -> Vec<(String, CheckResult)> {
+    check_channel_configs(channels)
+        .into_iter()
+        .map(|(name, result)| {
+            let result = match result {
+                CheckResult::Pass(detail) if !live.is_empty() => match live.get(name) {
+                    Some(true) => CheckResult::Pass("connected".to_string()),
+                    Some(false) => CheckResult::Fail(
+                        "enabled but not running (the gateway retries every 30s)".to_string(),
+                    ),
+                    None => CheckResult::Fail(format!("{detail}, but not started")),
+                },
+                other => other,
+            };
+            (format!("channel {name}"), result)
+        })
+        .collect()
+}
+
+/// One failing check per tool whose most recent audited call failed, or a
+/// single passing summary when none did. `entries` are newest first.
+fn tool_checks(entries: &[ToolAuditEntry]) -> Vec<(String, CheckResult)> {
+    // Per tool: (calls, failures, latest entry)
+    let mut stats: HashMap<&str, (usize, usize, &ToolAuditEntry)> = HashMap::new();
+    for entry in entries {
+        let stat = stats
+            .entry(entry.tool_name.as_str())
+            .or_insert((0, 0, entry));
+        stat.0 += 1;
+        if !entry.success {
+            stat.1 += 1;
+        }
+    }
+
+    let mut failing: Vec<(String, CheckResult)> = stats
+        .iter()
+        .filter(|(_, (_, _, latest))| !latest.success)
+        .map(|(name, (calls, failures, latest))| {
+            let error =
+                crate::utils::truncate_chars(&latest.result_summary, MAX_ERROR_CHARS, "...");
+            (
+                format!("tool {name}"),
+                CheckResult::Fail(format!(
+                    "last call failed ({failures}/{calls} failed in the last {AUDIT_WINDOW_HOURS}h): {error}"
+                )),
+            )
+        })
+        .collect();
+    failing.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if failing.is_empty() {
+        vec![(
+            "tools".to_string(),
+            CheckResult::Pass(format!(
+                "{} tool(s) used in the last {AUDIT_WINDOW_HOURS}h, none currently failing",
+                stats.len()
+            )),
+        )]
+    } else {
+        failing
+    }
+}
+
+/// Render results with failures first, preceded by a summary line.
+fn format_report(results: &[(String, CheckResult)]) -> String {
+    let count = |label: &str| results.iter().filter(|(_, r)| r.label() == label).count();
+    let mut lines = vec![format!(
+        "Self-test: {} passed, {} failed, {} skipped",
+        count("PASS"),
+        count("FAIL"),
+        count("SKIP")
+    )];
+    let failures = results.iter().filter(|(_, r)| r.is_fail());
+    let others = results.iter().filter(|(_, r)| !r.is_fail());
+    for (name, result) in failures.chain(others) {
+        lines.push(format!("{} {}: {}", result.label(), name, result.detail()));
+    }
+    lines.join("\n")
+}
+
+#[async_trait]
+impl Tool for SelfTestTool {
+    fn name(&self) -> &'static str {
+        "self_test"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run a diagnostic self-test: LLM provider reachability, memory database writability, workspace, embedding service, channel connections, MCP servers, and tools whose most recent call failed. Returns one PASS/FAIL/SKIP line per check, failures first. Use when the user asks whether everything is working or what is broken."
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            actions: actions![run: ro],
+            category: ToolCategory::System,
+            ..Default::default()
+        }
+    }
+
+    fn execution_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_mins(1))
+    }
+
+    async fn execute(&self, _params: Value, _ctx: &ExecutionContext) -> anyhow::Result<ToolResult> {
+        let (provider, database, embeddings, tools) = tokio::join!(
+            self.check_provider(),
+            self.check_database(),
+            self.check_embeddings(),
+            self.check_tools(),
+        );
+
+        let mut results = vec![
+            ("provider".to_string(), provider),
+            ("memory database".to_string(), database),
+            (
+                "workspace".to_string(),
+                check_workspace_path(&self.workspace),
+            ),
+            ("embeddings".to_string(), embeddings),
+        ];
+        match &self.channels_config {
+            Some(channels) => results.extend(channel_checks(
+                channels,
+                &oxicrab_channels::channel_health(),
+            )),
+            None => results.push((
+                "channels".to_string(),
+                CheckResult::Skip("no channels in this mode".to_string()),
+            )),
+        }
+        if let Some(mcp) = &self.mcp_config {
+            results.push(("MCP servers".to_string(), check_mcp_config(mcp)));
+        }
+        results.extend(tools);
+
+        Ok(ToolResult::new(format_report(&results)))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,118 @@
+use super::*;
+use crate::agent::memory::memory_db::MemoryDB;
+use crate::providers::base::{ChatRequest, LLMResponse};
+
+struct WarmupProvider {
+    fail: bool,
+}
+
+#[async_trait]
+impl LLMProvider for WarmupProvider {
+    async fn chat(&self, _req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        unreachable!()
+    }
+    fn default_model(&self) -> &'static str {
+        "test-model"
+    }
+    async fn warmup(&self) -> anyhow::Result<()> {
+        if self.fail {
+            anyhow::bail!("connection refused");
+        }
+        Ok(())
+    }
+}
+
+fn make_tool(fail: bool, workspace: PathBuf) -> (SelfTestTool, Arc<MemoryDB>) {
+    let db = Arc::new(MemoryDB::new(":memory:").expect("test db"));
+    let memory = Arc::new(MemoryStore::with_db(db.clone()));
+    let tool = SelfTestTool::new(
+        Arc::new(WarmupProvider { fail }),
+        memory,
+        workspace,
+        None,
+        None,
+    );
+    (tool, db)
+}
+
+#[tokio::test]
+async fn test_self_test_reports_failures_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let (tool, db) = make_tool(true, dir.path().to_path_buf());
+    db.record_tool_audit("s", None, "web_fetch", "{}", "ok", true, 5)
+        .unwrap();
+    db.record_tool_audit("s", None, "web_fetch", "{}", "HTTP 503", false, 5)
+        .unwrap();
+    db.record_tool_audit("s", None, "read_file", "{}", "not found", false, 5)
+        .unwrap();
+    db.record_tool_audit("s", None, "read_file", "{}", "ok", true, 5)
+        .unwrap();
+
+    let result = tool
+        .execute(serde_json::json!({}), &ExecutionContext::default())
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    let lines: Vec<&str> = result.content.lines().collect();
+    assert!(lines[0].starts_with("Self-test:"), "{}", result.content);
+    assert!(lines[0].contains("2 failed"), "{}", result.content);
+    assert!(lines[1].starts_with("FAIL provider: test-model (warmup failed"));
+    assert!(lines[2].starts_with("FAIL tool web_fetch: last call failed (1/2"));
+    assert!(lines[2].ends_with("HTTP 503"));
+    // read_file recovered after its failure
+    assert!(!result.content.contains("read_file"));
+    assert!(result.content.contains("PASS memory database: writable"));
+    assert!(result.content.contains("PASS workspace:"));
+    assert!(result.content.contains("SKIP channels:"));
+}
+
+#[tokio::test]
+async fn test_self_test_all_passing() {
+    let dir = tempfile::tempdir().unwrap();
+    let (tool, _db) = make_tool(false, dir.path().to_path_buf());
+    let result = tool
+        .execute(serde_json::json!({}), &ExecutionContext::default())
+        .await
+        .unwrap();
+    assert!(result.content.contains(" 0 failed"), "{}", result.content);
+    assert!(result.content.contains("PASS provider: test-model"));
+    assert!(result.content.contains("PASS tools: 0 tool(s) used"));
+}
+
+#[cfg(all(feature = "channel-telegram", feature = "channel-discord"))]
+#[test]
+fn test_channel_checks_use_live_state() {
+    let mut channels = ChannelsConfig::default();
+    channels.telegram.enabled = true;
+    channels.telegram.token = "t".to_string();
+    channels.discord.enabled = true;
+    channels.discord.token = "d".to_string();
+
+    let detail = |results: &[(String, CheckResult)], name: &str| {
+        results
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, r)| format!("{} {}", r.label(), r.detail()))
+            .unwrap()
+    };
+
+    // No live state (channels not running here): config result is kept
+    let results = channel_checks(&channels, &BTreeMap::new());
+    assert_eq!(
+        detail(&results, "channel telegram"),
+        "PASS enabled, token configured"
+    );
+
+    let live = BTreeMap::from([("telegram".to_string(), false)]);
+    let results = channel_checks(&channels, &live);
+    assert!(detail(&results, "channel telegram").starts_with("FAIL enabled but not running"));
+    assert!(detail(&results, "channel discord").starts_with("FAIL enabled, token configured"));
+    assert!(detail(&results, "channel slack").starts_with("SKIP"));
+
+    let live = BTreeMap::from([
+        ("telegram".to_string(), true),
+        ("discord".to_string(), true),
+    ]);
+    let results = channel_checks(&channels, &live);
+    assert_eq!(detail(&results, "channel discord"), "PASS connected");
+}
@@ -0,0 +1,176 @@
+use crate::actions;
+use crate::agent::memory::memory_db::MemoryDB;
+use crate::agent::profile::ProfileResolver;
+use crate::agent::tools::base::{ExecutionContext, ToolCapabilities, ToolCategory};
+use crate::agent::tools::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+const SENDER_ID_META_KEY: &str = "sender_id";
+/// Preference keys a sender can set, in display order.
+pub const PREFERENCE_KEYS: &[&str] = &["name", "language", "timezone", "verbosity"];
+const VERBOSITY_LEVELS: &[&str] = &["terse", "normal", "detailed"];
+const MAX_VALUE_CHARS: usize = 100;
+
+/// Key the preferences of `sender_id` on `channel` are stored under. The same
+/// person writing in different chats on one channel shares preferences.
+pub fn sender_key(channel: &str, sender_id: &str) -> String {
+    format!("{channel}:{sender_id}")
+}
+
+/// Check a preference value, returning it trimmed.
+fn validate_value(key: &str, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("value must not be empty".to_string());
+    }
+    if value.contains('\n') || value.chars().count() > MAX_VALUE_CHARS {
+        return Err(format!(
+            "value must be a single line of at most {MAX_VALUE_CHARS} characters"
+        ));
+    }
+    match key {
+        "verbosity" if !VERBOSITY_LEVELS.contains(&value) => Err(format!(
+            "verbosity must be one of: {}",
+            VERBOSITY_LEVELS.join(", ")
+        )),
+        "timezone" if value.parse::<chrono_tz::Tz>().is_err() => Err(format!(
+            "'{value}' is not an IANA timezone (e.g. Europe/Berlin, America/New_York)"
+        )),
+        _ => Ok(value.to_string()),
+    }
+}
+
+/// Stores preferences of the current sender (name, language, timezone,
+/// verbosity), which the context builder adds to the system prompt whenever
+/// that sender writes.
+pub struct SetPreferenceTool {
+    db: Arc<MemoryDB>,
+    profiles: Option<Arc<ProfileResolver>>,
+}
+
+impl SetPreferenceTool {
+    pub fn new(db: Arc<MemoryDB>) -> Self {
+        Self { db, profiles: None }
+    }
+
+    /// Store preferences in the memory DB of the chat's profile, where its
+    /// context builder reads them.
+    #[must_use]
+    pub fn with_profiles(mut self, profiles: Arc<ProfileResolver>) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for SetPreferenceTool {
+    fn name(&self) -> &'static str {
+        "set_preference"
+    }
+
+    fn description(&self) -> &'static str {
+        "Save a personal preference of the user you are talking to: their name, the language \
+         to reply in, their timezone, or how verbose replies should be (terse, normal, \
+         detailed). Saved preferences are shown to you at the start of every conversation with \
+         this user, in any chat. Use when the user states such a preference (\"call me Sam\", \
+         \"keep it short\", \"answer in Spanish\"). Actions: set, clear, list."
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["set", "clear", "list"],
+                    "description": "set a preference, clear one, or list all"
+                },
+                "key": {
+                    "type": "string",
+                    "enum": PREFERENCE_KEYS,
+                    "description": "Preference to set or clear"
+                },
+                "value": {
+                    "type": "string",
+                    "description": "New value (for set). verbosity: terse, normal or detailed; timezone: IANA name such as Europe/Berlin"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            actions: actions![set, clear, list: ro],
+            category: ToolCategory::Core,
+            ..Default::default()
+        }
+    }
+
+    async fn execute(&self, params: Value, ctx: &ExecutionContext) -> anyhow::Result<ToolResult> {
+        let Some(sender_id) = ctx
+            .metadata
+            .get(SENDER_ID_META_KEY)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+        else {
+            return Ok(ToolResult::error(
+                "no sender is known for this message, so preferences cannot be stored",
+            ));
+        };
+        let sender = sender_key(&ctx.channel, sender_id);
+        let db = match self.profiles {
+            Some(ref profiles) => profiles.resolve(&ctx.channel, &ctx.chat_id).memory.db(),
+            None => self.db.clone(),
+        };
+        let action = params["action"].as_str().unwrap_or_default();
+
+        if action == "list" {
+            let prefs = db.get_sender_preferences(&sender)?;
+            if prefs.is_empty() {
+                return Ok(ToolResult::new("No preferences saved for this user."));
+            }
+            let mut out = "Preferences for this user:".to_string();
+            for (key, value) in prefs {
+                let _ = write!(out, "\n- {key}: {value}");
+            }
+            return Ok(ToolResult::new(out));
+        }
+
+        let key = params["key"].as_str().unwrap_or_default();
+        if !PREFERENCE_KEYS.contains(&key) {
+            return Ok(ToolResult::error(format!(
+                "key must be one of: {}",
+                PREFERENCE_KEYS.join(", ")
+            )));
+        }
+        match action {
+            "set" => {
+                let value = match validate_value(key, params["value"].as_str().unwrap_or_default())
+                {
+                    Ok(value) => value,
+                    Err(e) => return Ok(ToolResult::error(e)),
+                };
+                db.set_sender_preference(&sender, key, &value)?;
+                Ok(ToolResult::new(format!("Saved {key}: {value}")))
+            }
+            "clear" => {
+                if db.delete_sender_preference(&sender, key)? {
+                    Ok(ToolResult::new(format!("Cleared {key}")))
+                } else {
+                    Ok(ToolResult::new(format!("No {key} preference was set")))
+                }
+            }
+            _ => Ok(ToolResult::error(format!(
+                "unknown action '{action}' (expected set, clear or list)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
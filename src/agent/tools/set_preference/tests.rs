@@ -0,0 +1,94 @@
+use super::*;
+use std::collections::HashMap;
+
+fn make_tool() -> (SetPreferenceTool, Arc<MemoryDB>) {
+    let db = Arc::new(MemoryDB::new(":memory:").expect("test db"));
+    (SetPreferenceTool::new(db.clone()), db)
+}
+
+fn ctx_for(sender_id: &str) -> ExecutionContext {
+    ExecutionContext {
+        channel: "slack".to_string(),
+        chat_id: "C1".to_string(),
+        metadata: HashMap::from([(
+            SENDER_ID_META_KEY.to_string(),
+            Value::String(sender_id.to_string()),
+        )]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_set_list_and_clear() {
+    let (tool, db) = make_tool();
+    let ctx = ctx_for("U1");
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "set", "key": "verbosity", "value": " terse "}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_error, "{}", result.content);
+    tool.execute(
+        serde_json::json!({"action": "set", "key": "timezone", "value": "Europe/Berlin"}),
+        &ctx,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        db.get_sender_preferences(&sender_key("slack", "U1"))
+            .unwrap(),
+        vec![
+            ("timezone".to_string(), "Europe/Berlin".to_string()),
+            ("verbosity".to_string(), "terse".to_string()),
+        ]
+    );
+
+    // Other senders do not see these preferences
+    let result = tool
+        .execute(serde_json::json!({"action": "list"}), &ctx_for("U2"))
+        .await
+        .unwrap();
+    assert_eq!(result.content, "No preferences saved for this user.");
+
+    tool.execute(
+        serde_json::json!({"action": "clear", "key": "timezone"}),
+        &ctx,
+    )
+    .await
+    .unwrap();
+    let result = tool
+        .execute(serde_json::json!({"action": "list"}), &ctx)
+        .await
+        .unwrap();
+    assert_eq!(
+        result.content,
+        "Preferences for this user:\n- verbosity: terse"
+    );
+}
+
+#[tokio::test]
+async fn test_rejects_invalid_values_and_missing_sender() {
+    let (tool, _db) = make_tool();
+    let ctx = ctx_for("U1");
+    for params in [
+        serde_json::json!({"action": "set", "key": "verbosity", "value": "chatty"}),
+        serde_json::json!({"action": "set", "key": "timezone", "value": "Mars/Base"}),
+        serde_json::json!({"action": "set", "key": "name", "value": ""}),
+        serde_json::json!({"action": "set", "key": "mood", "value": "happy"}),
+    ] {
+        let result = tool.execute(params.clone(), &ctx).await.unwrap();
+        assert!(result.is_error, "accepted {params}");
+    }
+
+    let result = tool
+        .execute(
+            serde_json::json!({"action": "set", "key": "name", "value": "Sam"}),
+            &ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+}
@@ -15,9 +15,13 @@ use tracing::{error, info, warn};
 
 /// All configuration and shared state needed to construct tools.
 /// Built once during `AgentLoop::new()` and passed to each module's `register()`.
+#[allow(clippy::struct_excessive_bools)]
 pub struct ToolBuildContext {
     pub workspace: PathBuf,
     pub restrict_to_workspace: bool,
+    /// Keep the file tools out of the oxicrab home (gateway tenants).
+    pub workspace_only: bool,
+    pub tenant_workspaces: Vec<PathBuf>,
     pub exec_timeout: u64,
     pub allowed_commands: config::AllowedCommands,
     pub sandbox_config: config::SandboxConfig,
@@ -34,7 +38,10 @@ pub struct ToolBuildContext {
     pub obsidian_config: Option<config::ObsidianConfig>,
     pub browser_config: Option<config::BrowserConfig>,
     pub image_gen_config: Option<config::ImageGenConfig>,
+    pub tmux_config: Option<config::TmuxConfig>,
     pub memory: Arc<MemoryStore>,
+    /// Main agent provider, used by `self_test` to check reachability.
+    pub provider: Arc<dyn crate::providers::base::LLMProvider>,
     pub subagent_config: SubagentConfig,
     pub mcp_config: Option<config::McpConfig>,
     pub memory_db: Option<Arc<MemoryDB>>,
@@ -42,6 +49,21 @@ pub struct ToolBuildContext {
     pub workspace_ttl: config::WorkspaceTtlConfig,
     pub pending_buttons: crate::agent::tools::interactive::PendingButtons,
     pub rss_config: Option<config::RssConfig>,
+    pub rate_limits: std::collections::HashMap<String, config::ToolRateLimitConfig>,
+    pub tool_timeouts: std::collections::HashMap<String, u64>,
+    pub default_tool_timeout_secs: Option<u64>,
+    pub scratchpad_config: config::ScratchpadConfig,
+    pub scratchpad: crate::agent::tools::scratchpad::Scratchpad,
+    /// Session store, read by `search_conversation`.
+    pub sessions: Arc<dyn crate::session::SessionStore>,
+    /// Per-chat profiles, whose memory and session stores replace the
+    /// defaults above in `memory_search`, `search_conversation` and
+    /// `set_preference`.
+    pub profiles: Arc<crate::agent::profile::ProfileResolver>,
+    /// Register `set_preference` (`agents.defaults.senderPreferences`).
+    pub sender_preferences: bool,
+    /// Register `token_breakdown` (`agents.defaults.tokenBreakdown`).
+    pub token_breakdown: bool,
 }
 
 /// Register all tools into the registry using decentralized per-module `register()` functions.
@@ -59,10 +81,13 @@ pub async fn register_all_tools(
     // Tool output stash — shared between truncation middleware and stash_retrieve tool
     let stash = Arc::new(crate::agent::tools::stash::ToolOutputStash::new());
     let mut tools = ToolRegistry::with_stash(stash.clone());
+    tools.set_rate_limits(&ctx.rate_limits);
+    tools.set_timeouts(&ctx.tool_timeouts, ctx.default_tool_timeout_secs);
 
     register_filesystem(&mut tools, ctx);
     register_shell(&mut tools, ctx)?;
-    register_tmux(&mut tools);
+    register_tmux(&mut tools, ctx);
+    register_sqlite(&mut tools, ctx);
     register_web(&mut tools, ctx);
     let subagents = register_subagents(&mut tools, ctx);
     register_browser(&mut tools, ctx);
@@ -78,6 +103,12 @@ pub async fn register_all_tools(
     register_memory_search(&mut tools, ctx);
     register_workspace(&mut tools, ctx);
     register_interactive(&mut tools, ctx);
+    register_scratchpad(&mut tools, ctx);
+    register_search_conversation(&mut tools, ctx);
+    register_set_preference(&mut tools, ctx);
+    register_pairing(&mut tools, ctx);
+    register_token_breakdown(&mut tools, ctx);
+    register_self_test(&mut tools, ctx);
     #[cfg(feature = "tool-rss")]
     register_rss(&mut tools, ctx);
 
@@ -147,7 +178,9 @@ pub async fn register_all_tools(
 fn register_filesystem(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
     let allowed_roots = if ctx.restrict_to_workspace {
         let mut roots = vec![ctx.workspace.clone()];
-        if let Ok(oxicrab_home) = crate::utils::get_oxicrab_home() {
+        if !ctx.workspace_only
+            && let Ok(oxicrab_home) = crate::utils::get_oxicrab_home()
+        {
             roots.push(oxicrab_home);
         }
         Some(roots)
@@ -155,13 +188,25 @@ fn register_filesystem(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
         None
     };
 
+    // The tools are shared by every profile, so no profile's own workspace
+    // is reachable through them; profile memory goes through the memory tools
+    let denied = ctx
+        .profiles
+        .all()
+        .skip(1)
+        .map(|p| p.workspace.clone())
+        .collect();
+
     let backup_dir = crate::utils::get_oxicrab_home()
         .ok()
         .map(|h| h.join("backups"));
 
-    for tool in
-        oxicrab_tools_system::create_filesystem_tools(&ctx.workspace, allowed_roots, backup_dir)
-    {
+    for tool in oxicrab_tools_system::create_filesystem_tools(
+        &ctx.workspace,
+        allowed_roots,
+        denied,
+        backup_dir,
+    ) {
         registry.register(tool);
     }
 }
@@ -177,8 +222,32 @@ fn register_shell(registry: &mut ToolRegistry, ctx: &ToolBuildContext) -> Result
     Ok(())
 }
 
-fn register_tmux(registry: &mut ToolRegistry) {
-    registry.register(oxicrab_tools_system::create_tmux_tool());
+fn register_tmux(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
+    if let Some(ref tmux_cfg) = ctx.tmux_config
+        && tmux_cfg.enabled
+    {
+        registry.register(oxicrab_tools_system::create_tmux_tool());
+    }
+}
+
+fn register_sqlite(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
+    let roots = ctx
+        .restrict_to_workspace
+        .then(|| vec![ctx.workspace.clone()]);
+    // The agent's own memory DB sits inside the workspace and there are no
+    // roots at all without restrictToWorkspace, so every memory directory
+    // (DB plus WAL files) of a profile or tenant is denied explicitly
+    let denied = ctx
+        .profiles
+        .all()
+        .map(|p| p.workspace.join("memory"))
+        .chain(ctx.tenant_workspaces.iter().map(|ws| ws.join("memory")))
+        .collect();
+    registry.register(oxicrab_tools_system::create_sqlite_tool(
+        &ctx.workspace,
+        roots,
+        denied,
+    ));
 }
 
 fn register_web(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
@@ -206,8 +275,14 @@ fn register_browser(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
     if let Some(ref browser_cfg) = ctx.browser_config
         && browser_cfg.enabled
     {
-        registry.register(oxicrab_tools_browser::create_browser_tool(browser_cfg));
-        info!("Browser tool registered");
+        let archive = ctx
+            .workspace_manager
+            .clone()
+            .map(|mgr| mgr as Arc<dyn oxicrab_tools_browser::ScreenshotArchive>);
+        for tool in oxicrab_tools_browser::create_browser_tools(browser_cfg, archive) {
+            registry.register(tool);
+        }
+        info!("Browser and screenshot tools registered");
     }
 }
 
@@ -269,6 +344,7 @@ fn register_image_gen(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
 
 fn register_cron(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
     use crate::agent::tools::cron::CronTool;
+    use crate::agent::tools::followup::ScheduleFollowupTool;
 
     if let Some(ref cron_svc) = ctx.cron_service {
         registry.register(Arc::new(CronTool::new(
@@ -276,6 +352,7 @@ fn register_cron(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
             ctx.channels_config.clone(),
             ctx.memory_db.clone(),
         )));
+        registry.register(Arc::new(ScheduleFollowupTool::new(cron_svc.clone())));
     }
 }
 
@@ -374,6 +451,77 @@ fn register_interactive(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
     registry.register(Arc::new(AddButtonsTool::new(ctx.pending_buttons.clone())));
 }
 
+fn register_scratchpad(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
+    use crate::agent::tools::scratchpad::{ScratchpadAppendTool, ScratchpadReadTool};
+
+    if !ctx.scratchpad_config.enabled {
+        return;
+    }
+    registry.register(Arc::new(ScratchpadAppendTool::new(
+        ctx.scratchpad.clone(),
+        ctx.scratchpad_config.max_chars,
+    )));
+    registry.register(Arc::new(ScratchpadReadTool::new(ctx.scratchpad.clone())));
+}
+
+fn register_search_conversation(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
+    use crate::agent::tools::search_conversation::SearchConversationTool;
+
+    registry.register(Arc::new(
+        SearchConversationTool::new(ctx.sessions.clone()).with_profiles(ctx.profiles.clone()),
+    ));
+}
+
+fn register_set_preference(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
+    use crate::agent::tools::set_preference::SetPreferenceTool;
+
+    if !ctx.sender_preferences {
+        return;
+    }
+    registry.register(Arc::new(
+        SetPreferenceTool::new(ctx.memory.db()).with_profiles(ctx.profiles.clone()),
+    ));
+}
+
+fn register_pairing(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
+    use crate::agent::tools::pairing::PairingTool;
+    use crate::pairing::PairingStore;
+
+    let Some(admins) = ctx
+        .channels_config
+        .as_ref()
+        .map(|c| c.admin_senders.clone())
+        .filter(|a| !a.is_empty())
+    else {
+        return;
+    };
+    registry.register(Arc::new(PairingTool::new(
+        PairingStore::new(ctx.memory.db()),
+        admins,
+    )));
+}
+
+fn register_token_breakdown(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
+    use crate::agent::tools::token_breakdown::TokenBreakdownTool;
+
+    if !ctx.token_breakdown {
+        return;
+    }
+    registry.register(Arc::new(TokenBreakdownTool::new(ctx.memory.db())));
+}
+
+fn register_self_test(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
+    use crate::agent::tools::self_test::SelfTestTool;
+
+    registry.register(Arc::new(SelfTestTool::new(
+        ctx.provider.clone(),
+        ctx.memory.clone(),
+        ctx.workspace.clone(),
+        ctx.channels_config.clone(),
+        ctx.mcp_config.clone(),
+    )));
+}
+
 #[cfg(feature = "tool-rss")]
 fn register_rss(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
     use oxicrab_tools_rss::RssTool;
@@ -394,7 +542,9 @@ fn register_rss(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
 fn register_memory_search(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
     use crate::agent::tools::memory_search::MemorySearchTool;
 
-    registry.register(Arc::new(MemorySearchTool::new(ctx.memory.clone())));
+    registry.register(Arc::new(
+        MemorySearchTool::new(ctx.memory.clone()).with_profiles(ctx.profiles.clone()),
+    ));
 }
 
 fn register_workspace(registry: &mut ToolRegistry, ctx: &ToolBuildContext) {
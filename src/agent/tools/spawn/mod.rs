@@ -8,6 +8,8 @@ use async_trait::async_trait;
 use serde_json::Value;
 use std::sync::Arc;
 
+const REQUEST_ID_META_KEY: &str = "request_id";
+
 pub struct SpawnTool {
     manager: Arc<SubagentManager>,
 }
@@ -62,6 +64,19 @@ impl Tool for SpawnTool {
             .as_str()
             .map(std::string::ToString::to_string);
 
+        if let Some(request_id) = ctx
+            .metadata
+            .get(REQUEST_ID_META_KEY)
+            .and_then(Value::as_str)
+            && !self.manager.reserve_turn_slot(request_id)
+        {
+            return Ok(ToolResult::error(format!(
+                "Subagent limit for this turn reached ({} per turn). Do the remaining work \
+                 yourself or tell the user what is left.",
+                self.manager.max_per_turn()
+            )));
+        }
+
         let result = self
             .manager
             .spawn(
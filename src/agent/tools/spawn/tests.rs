@@ -20,6 +20,10 @@ impl LLMProvider for DummyProvider {
 }
 
 fn make_tool() -> SpawnTool {
+    make_tool_with_budget(0)
+}
+
+fn make_tool_with_budget(max_per_turn: usize) -> SpawnTool {
     let bus = Arc::new(MessageBus::new(10, 1.0, 16, 16));
     let config = crate::agent::subagent::SubagentConfig {
         provider: Arc::new(DummyProvider),
@@ -27,12 +31,18 @@ fn make_tool() -> SpawnTool {
         model: None,
         max_tokens: 1024,
         tool_temperature: Some(0.0),
+        reasoning_effort: None,
         max_concurrent: 2,
+        max_per_turn,
+        announce: false,
+        stream_tool_activity: false,
         prompt_guard_config: PromptGuardConfig::default(),
         exfil_guard: crate::config::ExfiltrationGuardConfig::default(),
         main_tools: None,
+        allowed_tools: None,
         memory_db: None,
         leak_detector: Arc::new(crate::safety::LeakDetector::new()),
+        retry: crate::providers::base::RetryConfig::default(),
     };
     let manager = Arc::new(SubagentManager::new(config, bus));
     SpawnTool::new(manager)
@@ -49,3 +59,21 @@ fn test_spawn_capabilities() {
     assert_eq!(caps.actions[0].name, "spawn");
     assert!(!caps.actions[0].read_only);
 }
+
+#[tokio::test]
+async fn test_spawn_refused_when_turn_budget_used() {
+    let tool = make_tool_with_budget(1);
+    assert!(tool.manager.reserve_turn_slot("req-1"));
+
+    let mut ctx = ExecutionContext::default();
+    ctx.metadata.insert(
+        REQUEST_ID_META_KEY.to_string(),
+        Value::String("req-1".to_string()),
+    );
+    let result = tool
+        .execute(serde_json::json!({"task": "more research"}), &ctx)
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("1 per turn"));
+}
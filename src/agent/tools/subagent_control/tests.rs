@@ -27,12 +27,18 @@ fn make_tool() -> SubagentControlTool {
         model: None,
         max_tokens: 1024,
         tool_temperature: Some(0.0),
+        reasoning_effort: None,
         max_concurrent: 2,
+        max_per_turn: 0,
+        announce: false,
+        stream_tool_activity: false,
         prompt_guard_config: PromptGuardConfig::default(),
         exfil_guard: crate::config::ExfiltrationGuardConfig::default(),
         main_tools: None,
+        allowed_tools: None,
         memory_db: None,
         leak_detector: Arc::new(crate::safety::LeakDetector::new()),
+        retry: crate::providers::base::RetryConfig::default(),
     };
     let manager = Arc::new(SubagentManager::new(config, bus));
     SubagentControlTool::new(manager)
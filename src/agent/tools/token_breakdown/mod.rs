@@ -0,0 +1,144 @@
+use crate::actions;
+use crate::agent::memory::memory_db::{MemoryDB, TokenBreakdown, TokenBreakdownRow};
+use crate::agent::tools::base::{ExecutionContext, ToolCapabilities, ToolCategory};
+use crate::agent::tools::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+const SESSION_KEY_META_KEY: &str = "session_key";
+const DEFAULT_TURNS: usize = 5;
+const MAX_TURNS: usize = 20;
+
+/// Shows where the input tokens of recent turns in this conversation went,
+/// from the breakdowns the agent loop records with `tokenBreakdown` on.
+pub struct TokenBreakdownTool {
+    db: Arc<MemoryDB>,
+}
+
+impl TokenBreakdownTool {
+    pub fn new(db: Arc<MemoryDB>) -> Self {
+        Self { db }
+    }
+}
+
+fn percent(part: u64, total: u64) -> u64 {
+    if total == 0 { 0 } else { part * 100 / total }
+}
+
+/// `label N (P%)` for each category, largest first.
+fn format_shares(breakdown: &TokenBreakdown) -> String {
+    let total = breakdown.estimated_total();
+    let mut categories = breakdown.categories();
+    categories.sort_by_key(|(_, tokens)| std::cmp::Reverse(*tokens));
+    categories
+        .iter()
+        .map(|(label, tokens)| format!("{label} {tokens} ({}%)", percent(*tokens, total)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Render breakdowns (newest first) with a combined line for several turns.
+fn format_rows(rows: &[TokenBreakdownRow]) -> String {
+    let mut out = format!(
+        "Estimated input tokens of the last {} turn(s), newest first:\n",
+        rows.len()
+    );
+    let mut combined = TokenBreakdown::default();
+    for row in rows {
+        let b = &row.breakdown;
+        let _ = write!(
+            out,
+            "\n[{}] {} | {} call(s) | ~{} tokens",
+            row.timestamp,
+            row.model,
+            b.calls,
+            b.estimated_total()
+        );
+        if b.provider_input > 0 {
+            let _ = write!(out, " (provider reported {})", b.provider_input);
+        }
+        let _ = write!(out, "\n  {}", format_shares(b));
+        combined.calls += b.calls;
+        combined.system += b.system;
+        combined.tool_definitions += b.tool_definitions;
+        combined.history += b.history;
+        combined.message += b.message;
+        combined.tool_results += b.tool_results;
+        combined.assistant += b.assistant;
+    }
+    if rows.len() > 1 {
+        let _ = write!(
+            out,
+            "\n\nAll {} turns: {}",
+            rows.len(),
+            format_shares(&combined)
+        );
+    }
+    out
+}
+
+#[async_trait]
+impl Tool for TokenBreakdownTool {
+    fn name(&self) -> &'static str {
+        "token_breakdown"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show where the input tokens of recent turns in this conversation went: system prompt, \
+         tool definitions, conversation history, the current message, tool results and your \
+         own tool calls, with each category's share. Estimates, summed over every model call \
+         in a turn. Use when the user asks why a conversation is expensive or slow, or what \
+         is filling the context."
+    }
+
+    fn parameters(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "turns": {
+                    "type": "integer",
+                    "description": "How many recent turns to show (default 5, max 20)",
+                    "minimum": 1,
+                    "maximum": MAX_TURNS
+                }
+            }
+        })
+    }
+
+    fn capabilities(&self) -> ToolCapabilities {
+        ToolCapabilities {
+            built_in: true,
+            actions: actions![show: ro],
+            category: ToolCategory::Core,
+            ..Default::default()
+        }
+    }
+
+    async fn execute(&self, params: Value, ctx: &ExecutionContext) -> anyhow::Result<ToolResult> {
+        let turns = params["turns"]
+            .as_u64()
+            .map_or(DEFAULT_TURNS, |n| usize::try_from(n).unwrap_or(MAX_TURNS))
+            .clamp(1, MAX_TURNS);
+        let session_key = ctx
+            .metadata
+            .get(SESSION_KEY_META_KEY)
+            .and_then(Value::as_str)
+            .map_or_else(
+                || format!("{}:{}", ctx.channel, ctx.chat_id),
+                str::to_string,
+            );
+
+        let rows = self.db.get_token_breakdowns(&session_key, turns)?;
+        if rows.is_empty() {
+            return Ok(ToolResult::new(
+                "No token breakdowns recorded for this conversation yet.",
+            ));
+        }
+        Ok(ToolResult::new(format_rows(&rows)))
+    }
+}
+
+#[cfg(test)]
+mod tests;
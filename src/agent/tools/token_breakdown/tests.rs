@@ -0,0 +1,86 @@
+use super::*;
+use std::collections::HashMap;
+
+fn ctx_for(key: &str) -> ExecutionContext {
+    ExecutionContext {
+        channel: "telegram".to_string(),
+        chat_id: "42".to_string(),
+        metadata: HashMap::from([(
+            SESSION_KEY_META_KEY.to_string(),
+            Value::String(key.to_string()),
+        )]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_shows_recent_turns_of_this_session() {
+    let db = Arc::new(MemoryDB::new(":memory:").expect("test db"));
+    let tool = TokenBreakdownTool::new(db.clone());
+    let turn = TokenBreakdown {
+        calls: 2,
+        system: 600,
+        tool_definitions: 200,
+        history: 100,
+        message: 20,
+        tool_results: 80,
+        assistant: 0,
+        provider_input: 1050,
+    };
+    db.record_token_breakdown("telegram:42", None, "model-a", &turn)
+        .unwrap();
+    db.record_token_breakdown("telegram:42", None, "model-a", &turn)
+        .unwrap();
+    db.record_token_breakdown("telegram:7", None, "model-b", &turn)
+        .unwrap();
+
+    let result = tool
+        .execute(serde_json::json!({}), &ctx_for("telegram:42"))
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    assert!(
+        result.content.contains("last 2 turn(s)"),
+        "{}",
+        result.content
+    );
+    assert!(!result.content.contains("model-b"));
+    assert!(
+        result
+            .content
+            .contains("~1000 tokens (provider reported 1050)")
+    );
+    // Largest share first
+    assert!(
+        result
+            .content
+            .contains("system prompt 600 (60%), tool definitions 200 (20%), history 100 (10%)"),
+        "{}",
+        result.content
+    );
+    assert!(
+        result
+            .content
+            .contains("All 2 turns: system prompt 1200 (60%)")
+    );
+
+    let result = tool
+        .execute(serde_json::json!({"turns": 1}), &ctx_for("telegram:42"))
+        .await
+        .unwrap();
+    assert!(!result.content.contains("All "));
+}
+
+#[tokio::test]
+async fn test_no_breakdowns_recorded() {
+    let db = Arc::new(MemoryDB::new(":memory:").expect("test db"));
+    let tool = TokenBreakdownTool::new(db);
+    let result = tool
+        .execute(serde_json::json!({}), &ctx_for("telegram:42"))
+        .await
+        .unwrap();
+    assert_eq!(
+        result.content,
+        "No token breakdowns recorded for this conversation yet."
+    );
+}
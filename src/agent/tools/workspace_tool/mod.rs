@@ -3,14 +3,15 @@ use crate::agent::tools::base::{
     ActionDescriptor, ExecutionContext, SubagentAccess, ToolCapabilities, ToolCategory,
 };
 use crate::agent::tools::{Tool, ToolResult};
-use crate::agent::workspace::{FileCategory, WorkspaceManager};
+use crate::agent::workspace::{FileCategory, WorkspaceManager, infer_category};
 use crate::config::schema::WorkspaceTtlConfig;
 use crate::require_param;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
 use std::fmt::Write as _;
-use std::path::PathBuf;
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use walkdir::WalkDir;
@@ -18,6 +19,9 @@ use walkdir::WalkDir;
 #[cfg(test)]
 mod tests;
 
+/// Maximum bytes of a text file returned inline by the `show` action.
+const PREVIEW_BYTES: usize = 4000;
+
 pub struct WorkspaceTool {
     manager: Arc<WorkspaceManager>,
     workspace_ttl: WorkspaceTtlConfig,
@@ -89,6 +93,38 @@ impl WorkspaceTool {
         lines.join("\n")
     }
 
+    /// Format workspace file entries as a JSON object for `format: "json"`.
+    fn format_file_json(
+        entries: &[crate::agent::workspace::WorkspaceFileEntry],
+        total: usize,
+    ) -> String {
+        let files: Vec<Value> = entries
+            .iter()
+            .map(|entry| {
+                let tags: Vec<&str> = entry
+                    .tags
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .collect();
+                serde_json::json!({
+                    "path": entry.path,
+                    "category": entry.category,
+                    "size_bytes": entry.size_bytes,
+                    "original_name": entry.original_name,
+                    "tags": tags,
+                    "created_at": entry.created_at,
+                    "accessed_at": entry.accessed_at,
+                })
+            })
+            .collect();
+        serde_json::json!({ "total": total, "files": files }).to_string()
+    }
+
+    fn wants_json(params: &Value) -> bool {
+        params["format"].as_str() == Some("json")
+    }
+
     fn action_list(&self, params: &Value) -> Result<ToolResult> {
         let category = match params["category"]
             .as_str()
@@ -107,6 +143,9 @@ impl WorkspaceTool {
         let total = all_entries.len();
         let entries: Vec<_> = all_entries.into_iter().skip(offset).take(limit).collect();
 
+        if Self::wants_json(params) {
+            return Ok(ToolResult::new(Self::format_file_json(&entries, total)));
+        }
         let output = Self::format_file_table(&entries);
         if total > limit || offset > 0 {
             let end = (offset + entries.len()).min(total);
@@ -123,6 +162,12 @@ impl WorkspaceTool {
         let query = require_param!(params, "query");
 
         let entries = self.manager.search_files(query)?;
+        if Self::wants_json(params) {
+            return Ok(ToolResult::new(Self::format_file_json(
+                &entries,
+                entries.len(),
+            )));
+        }
         Ok(ToolResult::new(Self::format_file_table(&entries)))
     }
 
@@ -283,35 +328,93 @@ impl WorkspaceTool {
         Ok(ToolResult::new(format!("Tagged '{path_str}' with: {tags}")))
     }
 
-    fn action_send(&self, params: &Value) -> Result<ToolResult> {
-        let path_str = require_param!(params, "path");
-
+    /// Resolve a path for send/show, requiring an existing file under the workspace root.
+    fn resolve_outgoing_path(&self, path_str: &str) -> std::result::Result<PathBuf, ToolResult> {
         let abs_path = self.resolve_tool_path(path_str);
 
-        if !abs_path.exists() {
-            return Ok(ToolResult::error(format!("file not found: {path_str}")));
+        if !abs_path.is_file() {
+            return Err(ToolResult::error(format!("file not found: {path_str}")));
         }
         // Allow sending any file under the workspace root (including memory/, knowledge/).
         // Unlike delete/move, sending doesn't modify the file so the guard is relaxed.
         let resolved = abs_path.canonicalize().unwrap_or_else(|_| abs_path.clone());
         if !resolved.starts_with(self.manager.workspace_root()) {
-            return Ok(ToolResult::error(format!(
+            return Err(ToolResult::error(format!(
                 "path is outside the workspace: {path_str}"
             )));
         }
+        Ok(abs_path)
+    }
 
+    /// Result text that attaches `abs_path` to the reply.
+    ///
+    /// Uses the "saved to: /path" format so `extract_media_paths()` picks it up
+    /// and the agent loop attaches it to `OutboundMessage.media`.
+    fn attachment_result(abs_path: &Path, path_str: &str) -> ToolResult {
         let filename = abs_path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or(path_str);
-
-        // Return in "saved to: /path" format so extract_media_paths() picks it up
-        // and the agent loop attaches it to OutboundMessage.media
-        Ok(ToolResult::new(format!(
+        ToolResult::new(format!(
             "Sending file: {}\nsaved to: {}",
             filename,
             abs_path.display()
-        )))
+        ))
+    }
+
+    fn action_send(&self, params: &Value) -> Result<ToolResult> {
+        let path_str = require_param!(params, "path");
+
+        let abs_path = match self.resolve_outgoing_path(path_str) {
+            Ok(p) => p,
+            Err(e) => return Ok(e),
+        };
+        Ok(Self::attachment_result(&abs_path, path_str))
+    }
+
+    /// Show a file to the user: text files are previewed inline, anything else
+    /// (images, PDFs, archives) is attached to the reply. Updates `accessed_at`.
+    fn action_show(&self, params: &Value) -> Result<ToolResult> {
+        let path_str = require_param!(params, "path");
+
+        let abs_path = match self.resolve_outgoing_path(path_str) {
+            Ok(p) => p,
+            Err(e) => return Ok(e),
+        };
+        self.manager.touch_file(&abs_path)?;
+
+        if infer_category(&abs_path) == FileCategory::Images {
+            return Ok(Self::attachment_result(&abs_path, path_str));
+        }
+
+        let mut head = Vec::with_capacity(PREVIEW_BYTES);
+        std::fs::File::open(&abs_path)?
+            .take(PREVIEW_BYTES as u64 + 1)
+            .read_to_end(&mut head)?;
+        let truncated = head.len() > PREVIEW_BYTES;
+        head.truncate(PREVIEW_BYTES);
+
+        // A UTF-8 error at the very end is just a character cut by the byte limit
+        let text = match std::str::from_utf8(&head) {
+            Ok(t) => t,
+            Err(e) if e.error_len().is_none() => {
+                std::str::from_utf8(&head[..e.valid_up_to()]).unwrap_or_default()
+            }
+            Err(_) => return Ok(Self::attachment_result(&abs_path, path_str)),
+        };
+        if text.contains('\0') {
+            return Ok(Self::attachment_result(&abs_path, path_str));
+        }
+
+        let size = std::fs::metadata(&abs_path).map_or(0, |m| m.len());
+        let mut out = format!("{path_str} ({}):\n\n{text}", Self::format_size(size as i64));
+        if truncated {
+            let _ = write!(
+                out,
+                "\n\n[preview truncated at {PREVIEW_BYTES} bytes; use action 'send' to deliver the whole file]"
+            );
+        }
+        Ok(ToolResult::new(out))
     }
 
     fn action_cleanup(&self) -> Result<String> {
@@ -337,7 +440,7 @@ impl Tool for WorkspaceTool {
     }
 
     fn description(&self) -> &'static str {
-        "Manage workspace files: list, search, organize, clean up, and send files to the current conversation channel."
+        "Manage workspace files: list, search, organize, clean up, and send or show files in the current conversation."
     }
 
     fn capabilities(&self) -> ToolCapabilities {
@@ -346,8 +449,7 @@ impl Tool for WorkspaceTool {
             network_outbound: false,
             subagent_access: SubagentAccess::ReadOnly,
             actions: {
-                let mut a =
-                    actions![list: ro, search: ro, info: ro, tree: ro, delete, tag, cleanup, send];
+                let mut a = actions![list: ro, search: ro, info: ro, tree: ro, delete, tag, cleanup, send, show];
                 a.insert(
                     4,
                     ActionDescriptor {
@@ -367,11 +469,13 @@ impl Tool for WorkspaceTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["list", "search", "info", "tree", "move", "delete", "tag", "cleanup", "send"],
+                    "enum": ["list", "search", "info", "tree", "move", "delete", "tag", "cleanup", "send", "show"],
                     "description": "The workspace management action to perform. 'list' \
                      shows files (filter by category). 'search' finds files by query. 'info' \
                      shows file details. 'tree' shows directory structure. 'send' delivers a \
-                     file to a channel. 'tag'/'move'/'delete' manage files."
+                     file to a channel. 'show' displays a file to the user: images (and other \
+                     binary files) are attached, text files are previewed inline. \
+                     'tag'/'move'/'delete' manage files."
                 },
                 "category": {
                     "type": "string",
@@ -384,7 +488,7 @@ impl Tool for WorkspaceTool {
                 },
                 "path": {
                     "type": "string",
-                    "description": "File path (for info, move, delete, tag, send)"
+                    "description": "File path (for info, move, delete, tag, send, show)"
                 },
                 "date": {
                     "type": "string",
@@ -404,6 +508,13 @@ impl Tool for WorkspaceTool {
                     "type": "integer",
                     "description": "Number of files to skip (for list, default 0)",
                     "minimum": 0
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["table", "json"],
+                    "description": "Output for list and search (default table). 'json' returns \
+                     path, category, size_bytes, original_name, tags, created_at and accessed_at \
+                     for each file"
                 }
             },
             "required": ["action"]
@@ -423,6 +534,7 @@ impl Tool for WorkspaceTool {
             "tag" => self.action_tag(&params),
             "cleanup" => Ok(ToolResult::from_result(self.action_cleanup(), "workspace")),
             "send" => self.action_send(&params),
+            "show" => self.action_show(&params),
             _ => Ok(ToolResult::error(format!("unknown action: '{action}'"))),
         }
     }
@@ -28,7 +28,7 @@ fn test_workspace_tool_name_and_capabilities() {
     assert!(caps.built_in);
     assert!(!caps.network_outbound);
     assert_eq!(caps.subagent_access, SubagentAccess::ReadOnly);
-    assert_eq!(caps.actions.len(), 9);
+    assert_eq!(caps.actions.len(), 10);
 
     // Verify read-only flags
     let action_map: std::collections::HashMap<&str, bool> =
@@ -43,6 +43,7 @@ fn test_workspace_tool_name_and_capabilities() {
     assert_eq!(action_map.get("tag"), Some(&false));
     assert_eq!(action_map.get("cleanup"), Some(&false));
     assert_eq!(action_map.get("send"), Some(&false));
+    assert_eq!(action_map.get("show"), Some(&false));
 }
 
 #[tokio::test]
@@ -290,6 +291,82 @@ async fn test_workspace_tool_send_nonexistent_file() {
     assert!(result.content.contains("file not found"));
 }
 
+#[tokio::test]
+async fn test_workspace_tool_show_image_attaches_and_touches() {
+    let (tmp, tool) = test_tool();
+    let ctx = test_ctx();
+
+    let file = tmp.path().join("images/2026-03-01/screenshot.png");
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, b"\x89PNG\r\n\x1a\n\0\0").unwrap();
+    tool.manager
+        .register_file(&file, Some("screenshot"), None)
+        .unwrap();
+    let before = tool.manager.search_files("screenshot.png").unwrap();
+    assert!(before[0].accessed_at.is_none());
+
+    let params =
+        serde_json::json!({ "action": "show", "path": "images/2026-03-01/screenshot.png" });
+    let result = tool.execute(params, &ctx).await.unwrap();
+
+    assert!(!result.is_error, "show failed: {}", result.content);
+    assert!(result.content.contains("saved to: "), "{}", result.content);
+    let after = tool.manager.search_files("screenshot.png").unwrap();
+    assert!(after[0].accessed_at.is_some(), "accessed_at should be set");
+}
+
+#[tokio::test]
+async fn test_workspace_tool_show_text_previews_inline() {
+    let (tmp, tool) = test_tool();
+    let ctx = test_ctx();
+
+    let file = tmp.path().join("documents/2026-03-01/notes.md");
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, "# Notes\nbuy milk").unwrap();
+
+    let params = serde_json::json!({ "action": "show", "path": "documents/2026-03-01/notes.md" });
+    let result = tool.execute(params, &ctx).await.unwrap();
+
+    assert!(!result.is_error, "show failed: {}", result.content);
+    assert!(result.content.contains("buy milk"));
+    assert!(!result.content.contains("saved to: "));
+    assert!(!result.content.contains("truncated"));
+}
+
+#[tokio::test]
+async fn test_workspace_tool_show_truncates_long_text() {
+    let (tmp, tool) = test_tool();
+    let ctx = test_ctx();
+
+    let file = tmp.path().join("data/2026-03-01/big.csv");
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, "é,".repeat(PREVIEW_BYTES)).unwrap();
+
+    let params = serde_json::json!({ "action": "show", "path": "data/2026-03-01/big.csv" });
+    let result = tool.execute(params, &ctx).await.unwrap();
+
+    assert!(!result.is_error, "show failed: {}", result.content);
+    assert!(result.content.contains("preview truncated"));
+    assert!(!result.content.contains("saved to: "));
+}
+
+#[tokio::test]
+async fn test_workspace_tool_show_binary_attaches() {
+    let (tmp, tool) = test_tool();
+    let ctx = test_ctx();
+
+    let file = tmp.path().join("downloads/2026-03-01/archive.zip");
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, [0x50, 0x4b, 0x03, 0x04, 0xff, 0xfe, 0x00]).unwrap();
+
+    let params =
+        serde_json::json!({ "action": "show", "path": "downloads/2026-03-01/archive.zip" });
+    let result = tool.execute(params, &ctx).await.unwrap();
+
+    assert!(!result.is_error, "show failed: {}", result.content);
+    assert!(result.content.contains("saved to: "));
+}
+
 #[tokio::test]
 async fn test_workspace_tool_unknown_action() {
     let (_tmp, tool) = test_tool();
@@ -328,3 +405,33 @@ fn test_workspace_actions_match_schema() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_workspace_tool_list_and_search_json() {
+    let (tmp, tool) = test_tool();
+    let ctx = test_ctx();
+
+    let data_dir = tmp.path().join("data/2026-02-27");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    let file = data_dir.join("users.csv");
+    std::fs::write(&file, "id,name\n1,alice").unwrap();
+    tool.manager.register_file(&file, None, None).unwrap();
+    tool.manager.tag_file(&file, "export, crm").unwrap();
+
+    let params = serde_json::json!({ "action": "list", "format": "json" });
+    let result = tool.execute(params, &ctx).await.unwrap();
+    assert!(!result.is_error);
+    let value: serde_json::Value = serde_json::from_str(&result.content).unwrap();
+    assert_eq!(value["total"], 1);
+    let entry = &value["files"][0];
+    assert_eq!(entry["path"], "data/2026-02-27/users.csv");
+    assert_eq!(entry["category"], "data");
+    assert_eq!(entry["original_name"], "users.csv");
+    assert_eq!(entry["size_bytes"], 15);
+    assert_eq!(entry["tags"], serde_json::json!(["export", "crm"]));
+
+    let params = serde_json::json!({ "action": "search", "query": "users", "format": "json" });
+    let result = tool.execute(params, &ctx).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(&result.content).unwrap();
+    assert_eq!(value["files"][0]["path"], "data/2026-02-27/users.csv");
+}
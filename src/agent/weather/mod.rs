@@ -0,0 +1,174 @@
+//! Weather for the configured locations (`tools.weather.locations`):
+//! proactive severe-weather alerts sent to `tools.weather.alerts.targets`, and
+//! current conditions for the system prompt (`tools.weather.contextConditions`,
+//! or a one-line summary via a `kind = "weather"` context provider).
+//!
+//! Alerts go out through the outbound bus like cron echo jobs. Each alert is
+//! sent once per location, kind and day; the record of sent alerts is kept in
+//! memory, so a restart can repeat the day's alerts once.
+
+use crate::bus::{MessageBus, OutboundMessage};
+use crate::config::WeatherConfig;
+use oxicrab_tools_api::weather::{WeatherAlert, WeatherTool};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// How long a sent alert suppresses the same alert. Alerts are keyed by day,
+/// so this only needs to outlast the forecast window.
+const SENT_TTL: Duration = Duration::from_secs(48 * 3600);
+/// How long fetched conditions are reused in the system prompt.
+const CONDITIONS_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Build the message for new alerts.
+pub(crate) fn format_alerts(alerts: &[WeatherAlert]) -> String {
+    let lines: Vec<String> = alerts.iter().map(|a| format!("- {}", a.message)).collect();
+    format!("Weather alert:\n{}", lines.join("\n"))
+}
+
+pub struct WeatherAlerts {
+    weather: WeatherTool,
+    bus: Arc<MessageBus>,
+    config: WeatherConfig,
+    sent: Mutex<HashMap<String, Instant>>,
+}
+
+impl WeatherAlerts {
+    pub fn new(weather: WeatherTool, bus: Arc<MessageBus>, config: WeatherConfig) -> Self {
+        Self {
+            weather,
+            bus,
+            config,
+            sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Drop alerts already sent and remember the rest as sent.
+    fn take_new(&self, alerts: Vec<WeatherAlert>) -> Vec<WeatherAlert> {
+        let mut sent = self.sent.lock().unwrap_or_else(PoisonError::into_inner);
+        sent.retain(|_, at| at.elapsed() < SENT_TTL);
+        alerts
+            .into_iter()
+            .filter(|a| sent.insert(a.key(), Instant::now()).is_none())
+            .collect()
+    }
+
+    /// Check every location and send new alerts to every target. Returns the
+    /// number of new alerts.
+    pub async fn run_once(&self) -> usize {
+        let mut alerts = Vec::new();
+        for location in &self.config.locations {
+            match self
+                .weather
+                .alerts(location, self.config.units, &self.config.alerts)
+                .await
+            {
+                Ok(found) => alerts.extend(found),
+                Err(e) => warn!("weather alerts: check for {location} failed: {e}"),
+            }
+        }
+        let alerts = self.take_new(alerts);
+        if alerts.is_empty() {
+            return 0;
+        }
+
+        let content = format_alerts(&alerts);
+        for target in &self.config.alerts.targets {
+            let msg =
+                OutboundMessage::builder(target.channel_type(), target.chat_id(), content.clone())
+                    .build();
+            if let Err(e) = self.bus.publish_outbound(msg).await {
+                warn!("weather alerts: failed to send to {target}: {e}");
+            }
+        }
+        alerts.len()
+    }
+
+    /// Spawn the periodic check. The first check runs right away.
+    pub fn spawn_periodic(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(self.config.alerts.interval_minutes.max(1) * 60);
+            loop {
+                match self.run_once().await {
+                    0 => debug!("weather alerts: nothing new"),
+                    n => info!("weather alerts: sent {n} new alert(s)"),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+}
+
+/// Current conditions at the configured locations, for the system prompt.
+pub struct WeatherContext {
+    weather: WeatherTool,
+    config: WeatherConfig,
+    cache: tokio::sync::Mutex<Option<(Instant, Option<String>)>>,
+}
+
+impl WeatherContext {
+    pub fn new(weather: WeatherTool, config: WeatherConfig) -> Self {
+        Self {
+            weather,
+            config,
+            cache: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Conditions at each location, refetched every 30 minutes. `None` when
+    /// no location could be fetched.
+    pub async fn conditions(&self) -> Option<String> {
+        let mut cache = self.cache.lock().await;
+        if let Some((fetched_at, conditions)) = cache.as_ref()
+            && fetched_at.elapsed() < CONDITIONS_TTL
+        {
+            return conditions.clone();
+        }
+
+        let units = self.config.units.to_string();
+        let mut sections = Vec::new();
+        for location in &self.config.locations {
+            match self.weather.current(location, &units).await {
+                Ok(text) => sections.push(text),
+                Err(e) => warn!("weather context: conditions for {location} failed: {e}"),
+            }
+        }
+        let conditions = (!sections.is_empty()).then(|| sections.join("\n\n"));
+        *cache = Some((Instant::now(), conditions.clone()));
+        conditions
+    }
+}
+
+/// One-line current conditions for `kind = "weather"` context providers.
+/// Caching is left to the provider runner's TTL.
+pub struct WeatherContextProvider {
+    weather: WeatherTool,
+    config: WeatherConfig,
+}
+
+impl WeatherContextProvider {
+    pub fn new(weather: WeatherTool, config: WeatherConfig) -> Self {
+        Self { weather, config }
+    }
+
+    /// `Weather: 12°C, light rain` for a single location, one
+    /// `Weather in {location}: ...` line per location otherwise. Empty when
+    /// nothing could be fetched, so a failure never blocks the prompt.
+    pub async fn fetch(&self) -> String {
+        let units = self.config.units.to_string();
+        let single = self.config.locations.len() == 1;
+        let mut lines = Vec::new();
+        for location in &self.config.locations {
+            match self.weather.current_brief(location, &units).await {
+                Ok(brief) if single => lines.push(format!("Weather: {brief}")),
+                Ok(brief) => lines.push(format!("Weather in {location}: {brief}")),
+                Err(e) => debug!("weather context provider: {location} failed: {e}"),
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,170 @@
+use super::*;
+use crate::config::{ChannelTarget, WeatherAlertsConfig};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn config(locations: &[&str]) -> WeatherConfig {
+    WeatherConfig {
+        enabled: true,
+        api_key: "test_key".to_string(),
+        locations: locations.iter().map(ToString::to_string).collect(),
+        context_conditions: true,
+        alerts: WeatherAlertsConfig {
+            enabled: true,
+            targets: vec![
+                ChannelTarget::try_from("telegram:42".to_string()).unwrap(),
+                ChannelTarget::try_from("slack:C1".to_string()).unwrap(),
+            ],
+            max_temp: Some(100.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+async fn mount_forecast(server: &MockServer, location: &str, temp: f64) {
+    Mock::given(method("GET"))
+        .and(path("/forecast"))
+        .and(query_param("q", location))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "city": {"name": location},
+            "list": [{
+                "dt_txt": "2026-07-01 15:00:00",
+                "main": {"temp": temp},
+                "wind": {"speed": 3.0},
+                "weather": [{"id": 800, "description": "clear sky"}]
+            }]
+        })))
+        .mount(server)
+        .await;
+}
+
+#[test]
+fn test_format_alerts_lists_each_alert() {
+    let alert = WeatherAlert {
+        location: "Phoenix".to_string(),
+        kind: "heat",
+        at: "2026-07-01 15:00:00".to_string(),
+        message: "Phoenix: 108°F expected around 2026-07-01 15:00:00 (above 100°F)".to_string(),
+    };
+    assert_eq!(
+        format_alerts(&[alert]),
+        "Weather alert:\n- Phoenix: 108°F expected around 2026-07-01 15:00:00 (above 100°F)"
+    );
+}
+
+#[tokio::test]
+async fn test_run_once_sends_new_alerts_to_every_target_once() {
+    let server = MockServer::start().await;
+    mount_forecast(&server, "Phoenix", 108.0).await;
+    mount_forecast(&server, "Seattle", 70.0).await;
+    let bus = Arc::new(MessageBus::new(30, 60.0, 100, 100));
+    let mut rx = bus.take_outbound_rx().unwrap();
+    let alerts = WeatherAlerts::new(
+        WeatherTool::with_base_url("test_key".to_string(), server.uri()),
+        bus.clone(),
+        config(&["Phoenix", "Seattle"]),
+    );
+
+    assert_eq!(alerts.run_once().await, 1);
+    let first = rx.try_recv().unwrap();
+    let second = rx.try_recv().unwrap();
+    assert_eq!(
+        (first.channel.as_str(), first.chat_id.as_str()),
+        ("telegram", "42")
+    );
+    assert_eq!(
+        (second.channel.as_str(), second.chat_id.as_str()),
+        ("slack", "C1")
+    );
+    assert!(
+        first.content.contains("Phoenix: 108°F"),
+        "{}",
+        first.content
+    );
+    assert!(!first.content.contains("Seattle"));
+
+    // The same forecast on the next check is not sent again
+    assert_eq!(alerts.run_once().await, 0);
+    assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_context_conditions_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/weather"))
+        .and(query_param("q", "London"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "main": {"temp": 59.0, "feels_like": 57.0, "humidity": 72},
+            "weather": [{"description": "light rain"}],
+            "wind": {"speed": 11.0},
+            "name": "London",
+            "sys": {"country": "GB"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let context = WeatherContext::new(
+        WeatherTool::with_base_url("test_key".to_string(), server.uri()),
+        config(&["London"]),
+    );
+
+    let conditions = context.conditions().await.unwrap();
+    assert!(conditions.contains("Weather in London, GB"), "{conditions}");
+    assert!(conditions.contains("light rain | 59°F"));
+    assert_eq!(context.conditions().await.unwrap(), conditions);
+}
+
+#[tokio::test]
+async fn test_context_conditions_none_when_fetch_fails() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/weather"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+            "cod": 401,
+            "message": "Invalid API key"
+        })))
+        .mount(&server)
+        .await;
+    let context = WeatherContext::new(
+        WeatherTool::with_base_url("bad_key".to_string(), server.uri()),
+        config(&["London"]),
+    );
+    assert!(context.conditions().await.is_none());
+}
+
+#[tokio::test]
+async fn test_context_provider_brief_line() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/weather"))
+        .and(query_param("q", "London"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "main": {"temp": 53.6},
+            "weather": [{"description": "light rain"}],
+            "name": "London"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/weather"))
+        .and(query_param("q", "Nowhere"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "cod": "404",
+            "message": "city not found"
+        })))
+        .mount(&server)
+        .await;
+    let weather = || WeatherTool::with_base_url("test_key".to_string(), server.uri());
+
+    let single = WeatherContextProvider::new(weather(), config(&["London"]));
+    assert_eq!(single.fetch().await, "Weather: 54°F, light rain");
+
+    // Failed locations are dropped; the rest are labelled
+    let multi = WeatherContextProvider::new(weather(), config(&["London", "Nowhere"]));
+    assert_eq!(multi.fetch().await, "Weather in London: 54°F, light rain");
+
+    let failing = WeatherContextProvider::new(weather(), config(&["Nowhere"]));
+    assert_eq!(failing.fetch().await, "");
+}
@@ -2,10 +2,11 @@ use crate::agent::memory::memory_db::MemoryDB;
 pub use crate::agent::memory::memory_db::WorkspaceFileEntry;
 use anyhow::Result;
 use chrono::Utc;
+use regex::Regex;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use tracing::warn;
 use walkdir::WalkDir;
 
@@ -15,6 +16,14 @@ mod tests;
 /// Directories reserved for existing workspace subsystems (not managed by `WorkspaceManager`).
 const RESERVED_DIRS: &[&str] = &["memory", "knowledge", "skills", "sessions"];
 
+/// Category-relative file paths (`data/2026-02-27/report.csv`) mentioned in free text.
+static MENTIONED_PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?:^|[\s"'`(\[<])((?:code|documents|data|images|downloads|temp)/[^\s"'`<>()\[\]]+)"#,
+    )
+    .expect("mentioned path regex")
+});
+
 /// File categories for workspace organization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -348,6 +357,35 @@ impl WorkspaceManager {
         Ok(())
     }
 
+    /// Touch every managed file whose path is mentioned in `text`.
+    ///
+    /// Recognises both workspace-relative (`code/2026-02-27/app.py`) and
+    /// absolute paths under the workspace root. Returns the number of distinct
+    /// paths touched; paths not in the manifest are ignored.
+    pub fn touch_mentioned(&self, text: &str) -> usize {
+        let Some(db) = &self.db else {
+            return 0;
+        };
+
+        let root = format!("{}/", self.workspace_root.display());
+        let text = text.replace(&root, " ");
+        let mut seen: Vec<&str> = Vec::new();
+        for cap in MENTIONED_PATH_RE.captures_iter(&text) {
+            let path = cap
+                .get(1)
+                .map_or("", |m| m.as_str())
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', '*', '_']);
+            if !seen.contains(&path) {
+                seen.push(path);
+            }
+        }
+        for path in &seen {
+            // Fire-and-forget: don't fail if file isn't in manifest
+            let _ = db.touch_workspace_file(path);
+        }
+        seen.len()
+    }
+
     /// Clean up expired files based on per-category TTLs.
     ///
     /// `ttl_map` maps category name to optional TTL in days (`None` = no expiry).
@@ -445,6 +483,22 @@ impl WorkspaceManager {
     }
 }
 
+impl oxicrab_tools_browser::ScreenshotArchive for WorkspaceManager {
+    fn archive(&self, media_path: &Path, session_key: Option<&str>) -> Result<PathBuf> {
+        let name = media_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("screenshot.png");
+        let dest = self.resolve_path(name, Some(FileCategory::Images));
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(media_path, &dest)?;
+        self.register_file(&dest, Some("screenshot"), session_key)?;
+        Ok(dest)
+    }
+}
+
 /// Determine category from the first component of a relative path.
 fn category_from_relative(rel: &str) -> FileCategory {
     let first = Path::new(rel)
@@ -413,6 +413,26 @@ fn test_register_file_adds_to_manifest() {
     assert!(files[0].size_bytes > 0);
 }
 
+#[test]
+fn test_screenshot_archive_copies_into_images() {
+    use oxicrab_tools_browser::ScreenshotArchive;
+
+    let (dir, mgr) = test_manager();
+    let media = dir.path().join("screenshot_20260227_120000_0000abcd.png");
+    std::fs::write(&media, b"\x89PNG\r\n\x1a\n").unwrap();
+
+    let copy = mgr.archive(&media, Some("telegram:42")).unwrap();
+
+    assert!(copy.exists());
+    assert!(media.exists(), "media file stays for attachment");
+    let files = mgr
+        .list_files(Some(FileCategory::Images), None, None)
+        .unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].source_tool.as_deref(), Some("screenshot"));
+    assert_eq!(files[0].session_key.as_deref(), Some("telegram:42"));
+}
+
 #[test]
 fn test_remove_file_deletes_file_and_manifest() {
     let (_dir, mgr) = test_manager();
@@ -572,6 +592,7 @@ fn test_operations_without_db_are_graceful() {
     assert!(mgr.search_files("test").unwrap().is_empty());
     assert!(mgr.tag_file(&file_path, "foo").is_ok());
     assert!(mgr.touch_file(&file_path).is_ok());
+    assert_eq!(mgr.touch_mentioned("see code/2026-02-27/test.py"), 0);
     assert_eq!(mgr.cleanup_expired(&HashMap::new()).unwrap(), 0);
     assert_eq!(mgr.sync_manifest().unwrap(), (0, 0));
 }
@@ -598,3 +619,29 @@ fn test_tag_and_search_with_db() {
     let results = mgr.list_files(None, None, Some("important")).unwrap();
     assert_eq!(results.len(), 1);
 }
+
+#[test]
+fn test_touch_mentioned_updates_accessed_at() {
+    let (_dir, mgr) = test_manager();
+    let root = mgr.workspace_root().to_path_buf();
+
+    let report = root.join("data/2026-02-27/report.csv");
+    let chart = root.join("images/2026-02-27/chart.png");
+    for file in [&report, &chart] {
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, "x").unwrap();
+        mgr.register_file(file, None, None).unwrap();
+    }
+
+    let answer = format!(
+        "Saved `data/2026-02-27/report.csv`. See data/2026-02-27/report.csv, \
+         and the chart at {}.",
+        chart.display()
+    );
+    assert_eq!(mgr.touch_mentioned(&answer), 2);
+    assert_eq!(mgr.touch_mentioned("nothing to see in mydata/x.csv"), 0);
+
+    let files = mgr.list_files(None, None, None).unwrap();
+    assert_eq!(files.len(), 2);
+    assert!(files.iter().all(|f| f.accessed_at.is_some()));
+}
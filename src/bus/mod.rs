@@ -1,6 +1,8 @@
 pub mod events;
+pub mod priority;
 pub mod queue;
 
 pub use events::meta;
-pub use events::{InboundMessage, OutboundMessage};
+pub use events::{InboundMessage, MessagePriority, OutboundMessage};
+pub use priority::PriorityReceiver;
 pub use queue::MessageBus;
@@ -0,0 +1,244 @@
+use crate::bus::{InboundMessage, MessagePriority};
+use crate::config::ChannelTarget;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// Maximum messages pulled off the inbound channel into the priority buffer.
+/// Anything beyond stays in the bounded channel, so senders still see
+/// backpressure when the agent falls behind.
+const MAX_BUFFERED: usize = 256;
+
+/// A message ending in one of these completes a batch right away.
+const TERMINAL_PUNCTUATION: &[char] = &['.', '!', '?', '…', '。', '！', '？'];
+
+struct Pending {
+    priority: MessagePriority,
+    /// Arrival order; lower is older.
+    seq: u64,
+    msg: InboundMessage,
+}
+
+impl PartialEq for Pending {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pending {}
+
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pending {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Max-heap: higher priority first, then the older message
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// Messages of one session held back while the user may still be typing.
+struct Batch {
+    msg: InboundMessage,
+    /// Flushed when no new message arrives before this.
+    quiet_until: Instant,
+    /// Flushed at this point regardless.
+    hold_until: Instant,
+}
+
+impl Batch {
+    fn deadline(&self) -> Instant {
+        self.quiet_until.min(self.hold_until)
+    }
+}
+
+/// Inbound receiver that hands out queued messages by [`MessagePriority`]
+/// instead of strict arrival order, so interactive messages are not stuck
+/// behind a backlog of background work.
+///
+/// Channels keep sending to the plain `mpsc` sender; on each `recv()` every
+/// message already waiting is moved into a heap and the highest-priority one
+/// is returned. With batching on, user messages of a session are first
+/// coalesced until the session goes quiet or a message ends a sentence.
+pub struct PriorityReceiver {
+    rx: mpsc::Receiver<InboundMessage>,
+    pending: BinaryHeap<Pending>,
+    next_seq: u64,
+    admin_target: Option<ChannelTarget>,
+    /// `(window, max_wait)` when batching is on
+    batching: Option<(Duration, Duration)>,
+    batches: HashMap<String, Batch>,
+}
+
+impl PriorityReceiver {
+    pub fn new(rx: mpsc::Receiver<InboundMessage>) -> Self {
+        Self {
+            rx,
+            pending: BinaryHeap::new(),
+            next_seq: 0,
+            admin_target: None,
+            batching: None,
+            batches: HashMap::new(),
+        }
+    }
+
+    /// Raise normal-priority messages from `target` (`channels.adminTarget`)
+    /// to [`MessagePriority::High`].
+    #[must_use]
+    pub fn with_admin_target(mut self, target: Option<ChannelTarget>) -> Self {
+        self.admin_target = target;
+        self
+    }
+
+    /// Coalesce user messages of a session that arrive less than `window`
+    /// apart into one message (contents joined by newlines). A batch is
+    /// handed out once `window` passes without a new message, a message ends
+    /// in terminal punctuation, or `max_wait` has passed since its first
+    /// message. A zero `window` leaves batching off.
+    #[must_use]
+    pub fn with_batching(mut self, window: Duration, max_wait: Duration) -> Self {
+        self.batching = (!window.is_zero()).then_some((window, max_wait));
+        self
+    }
+
+    /// Receive the highest-priority waiting message, waiting for one if none
+    /// is queued. Returns `None` once all senders are dropped and the buffer
+    /// is empty.
+    ///
+    /// Cancel safe: a message is only taken off the channel right before it
+    /// is buffered, with no await in between.
+    pub async fn recv(&mut self) -> Option<InboundMessage> {
+        loop {
+            while self.pending.len() < MAX_BUFFERED {
+                match self.rx.try_recv() {
+                    Ok(msg) => self.push(msg),
+                    Err(_) => break,
+                }
+            }
+            self.flush_batches(|batch| batch.deadline() <= Instant::now());
+            if let Some(p) = self.pending.pop() {
+                return Some(p.msg);
+            }
+
+            let next_deadline = self.batches.values().map(Batch::deadline).min();
+            let msg = match next_deadline {
+                Some(deadline) => {
+                    tokio::select! {
+                        msg = self.rx.recv() => msg,
+                        () = tokio::time::sleep_until(deadline) => continue,
+                    }
+                }
+                None => self.rx.recv().await,
+            };
+            match msg {
+                Some(msg) => self.push(msg),
+                None if self.batches.is_empty() => return None,
+                // Senders are gone: hand out what is still held
+                None => self.flush_batches(|_| true),
+            }
+        }
+    }
+
+    fn push(&mut self, mut msg: InboundMessage) {
+        if msg.priority == MessagePriority::Normal && self.is_admin(&msg) {
+            msg.priority = MessagePriority::High;
+        }
+        let Some((window, max_wait)) = self.batching else {
+            self.enqueue(msg);
+            return;
+        };
+        let key = msg.session_key();
+        if !is_batchable(&msg) {
+            // Keep the session's order: what it sent earlier goes first
+            if let Some(batch) = self.batches.remove(&key) {
+                self.enqueue(batch.msg);
+            }
+            self.enqueue(msg);
+            return;
+        }
+        let now = Instant::now();
+        let batch = match self.batches.remove(&key) {
+            Some(mut batch) => {
+                merge_into(&mut batch.msg, msg);
+                batch.quiet_until = now + window;
+                batch
+            }
+            None => Batch {
+                msg,
+                quiet_until: now + window,
+                hold_until: now + max_wait,
+            },
+        };
+        if ends_sentence(&batch.msg.content) {
+            self.enqueue(batch.msg);
+        } else {
+            self.batches.insert(key, batch);
+        }
+    }
+
+    /// Move held batches matching `ready` to the priority queue.
+    fn flush_batches(&mut self, ready: impl Fn(&Batch) -> bool) {
+        let keys: Vec<String> = self
+            .batches
+            .iter()
+            .filter(|(_, batch)| ready(batch))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            if let Some(batch) = self.batches.remove(&key) {
+                self.enqueue(batch.msg);
+            }
+        }
+    }
+
+    fn enqueue(&mut self, msg: InboundMessage) {
+        self.pending.push(Pending {
+            priority: msg.priority,
+            seq: self.next_seq,
+            msg,
+        });
+        self.next_seq += 1;
+    }
+
+    fn is_admin(&self, msg: &InboundMessage) -> bool {
+        self.admin_target.as_ref().is_some_and(|target| {
+            target.channel_type() == msg.channel
+                && (target.chat_id() == msg.chat_id || target.chat_id() == msg.sender_id)
+        })
+    }
+}
+
+/// Background work, button actions and slash commands are never held back.
+fn is_batchable(msg: &InboundMessage) -> bool {
+    msg.priority != MessagePriority::Low
+        && msg.action.is_none()
+        && !msg.content.trim_start().starts_with('/')
+}
+
+fn ends_sentence(content: &str) -> bool {
+    content.trim_end().ends_with(TERMINAL_PUNCTUATION)
+}
+
+/// Append `next` to a held message: contents on a new line, media added, and
+/// metadata (message ID, reply context) taken from the newer message.
+fn merge_into(held: &mut InboundMessage, next: InboundMessage) {
+    if !next.content.is_empty() {
+        if !held.content.is_empty() {
+            held.content.push('\n');
+        }
+        held.content.push_str(&next.content);
+    }
+    held.media.extend(next.media);
+    held.metadata.extend(next.metadata);
+    held.priority = held.priority.max(next.priority);
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,156 @@
+use super::*;
+
+fn msg(channel: &str, chat_id: &str, content: &str) -> InboundMessage {
+    InboundMessage::builder(channel, "user1", chat_id, content).build()
+}
+
+#[tokio::test]
+async fn test_higher_priority_received_first() {
+    let (tx, rx) = mpsc::channel(16);
+    let mut inbox = PriorityReceiver::new(rx);
+
+    tx.send(msg("system", "telegram:1", "bg1")).await.unwrap();
+    tx.send(msg("system", "telegram:1", "bg2")).await.unwrap();
+    tx.send(msg("telegram", "1", "user")).await.unwrap();
+    tx.send(
+        InboundMessage::builder("discord", "u", "2", "urgent")
+            .priority(MessagePriority::High)
+            .build(),
+    )
+    .await
+    .unwrap();
+
+    let order: Vec<String> = [
+        inbox.recv().await,
+        inbox.recv().await,
+        inbox.recv().await,
+        inbox.recv().await,
+    ]
+    .into_iter()
+    .map(|m| m.unwrap().content)
+    .collect();
+    assert_eq!(order, ["urgent", "user", "bg1", "bg2"]);
+}
+
+#[tokio::test]
+async fn test_equal_priority_keeps_arrival_order() {
+    let (tx, rx) = mpsc::channel(16);
+    let mut inbox = PriorityReceiver::new(rx);
+    for i in 0..5 {
+        tx.send(msg("slack", "C1", &i.to_string())).await.unwrap();
+    }
+    for i in 0..5 {
+        assert_eq!(inbox.recv().await.unwrap().content, i.to_string());
+    }
+}
+
+#[tokio::test]
+async fn test_admin_target_raised_to_high() {
+    let (tx, rx) = mpsc::channel(16);
+    let admin = ChannelTarget::try_from("telegram:42".to_string()).unwrap();
+    let mut inbox = PriorityReceiver::new(rx).with_admin_target(Some(admin));
+
+    tx.send(msg("telegram", "7", "other")).await.unwrap();
+    tx.send(msg("telegram", "42", "admin")).await.unwrap();
+
+    let first = inbox.recv().await.unwrap();
+    assert_eq!(first.content, "admin");
+    assert_eq!(first.priority, MessagePriority::High);
+    assert_eq!(inbox.recv().await.unwrap().content, "other");
+}
+
+#[tokio::test]
+async fn test_returns_none_when_closed_and_drained() {
+    let (tx, rx) = mpsc::channel(16);
+    let mut inbox = PriorityReceiver::new(rx);
+    tx.send(msg("system", "x:1", "last")).await.unwrap();
+    drop(tx);
+    assert_eq!(inbox.recv().await.unwrap().content, "last");
+    assert!(inbox.recv().await.is_none());
+}
+
+fn batching_inbox(rx: mpsc::Receiver<InboundMessage>) -> PriorityReceiver {
+    PriorityReceiver::new(rx).with_batching(Duration::from_millis(50), Duration::from_secs(5))
+}
+
+#[tokio::test]
+async fn test_batching_coalesces_fragments_until_quiet() {
+    let (tx, rx) = mpsc::channel(16);
+    let mut inbox = batching_inbox(rx);
+
+    tx.send(msg("telegram", "1", "so I was thinking"))
+        .await
+        .unwrap();
+    tx.send(
+        InboundMessage::builder("telegram", "user1", "1", "about the trip")
+            .media(vec!["/tmp/photo.jpg".to_string()])
+            .build(),
+    )
+    .await
+    .unwrap();
+    tx.send(msg("telegram", "2", "other chat")).await.unwrap();
+
+    let started = Instant::now();
+    let first = inbox.recv().await.unwrap();
+    let second = inbox.recv().await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(50));
+    let mut contents = [first.content.as_str(), second.content.as_str()];
+    contents.sort_unstable();
+    assert_eq!(
+        contents,
+        ["other chat", "so I was thinking\nabout the trip"]
+    );
+    let merged = if first.chat_id == "1" { first } else { second };
+    assert_eq!(merged.media, ["/tmp/photo.jpg"]);
+}
+
+#[tokio::test]
+async fn test_batching_flushes_on_terminal_punctuation() {
+    let (tx, rx) = mpsc::channel(16);
+    let mut inbox =
+        PriorityReceiver::new(rx).with_batching(Duration::from_secs(60), Duration::from_secs(60));
+
+    tx.send(msg("slack", "C1", "can you check")).await.unwrap();
+    tx.send(msg("slack", "C1", "the weather tomorrow?"))
+        .await
+        .unwrap();
+    let received = tokio::time::timeout(Duration::from_secs(1), inbox.recv())
+        .await
+        .expect("a finished sentence is not held")
+        .unwrap();
+    assert_eq!(received.content, "can you check\nthe weather tomorrow?");
+}
+
+#[tokio::test]
+async fn test_batching_skips_commands_and_background_work() {
+    let (tx, rx) = mpsc::channel(16);
+    let mut inbox =
+        PriorityReceiver::new(rx).with_batching(Duration::from_secs(60), Duration::from_secs(60));
+
+    tx.send(msg("telegram", "1", "remind me")).await.unwrap();
+    tx.send(msg("telegram", "1", "/stop")).await.unwrap();
+    tx.send(msg("system", "telegram:1", "cron job"))
+        .await
+        .unwrap();
+
+    let order: Vec<String> = [inbox.recv().await, inbox.recv().await, inbox.recv().await]
+        .into_iter()
+        .map(|m| m.unwrap().content)
+        .collect();
+    // The held fragment is released ahead of the command that follows it
+    assert_eq!(order, ["remind me", "/stop", "cron job"]);
+}
+
+#[tokio::test]
+async fn test_batching_releases_held_messages_when_closed() {
+    let (tx, rx) = mpsc::channel(16);
+    let mut inbox =
+        PriorityReceiver::new(rx).with_batching(Duration::from_secs(60), Duration::from_secs(60));
+
+    tx.send(msg("discord", "9", "half a thought"))
+        .await
+        .unwrap();
+    drop(tx);
+    assert_eq!(inbox.recv().await.unwrap().content, "half a thought");
+    assert!(inbox.recv().await.is_none());
+}
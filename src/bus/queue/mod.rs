@@ -197,8 +197,15 @@ impl MessageBus {
                 .truncate(msg.content.floor_char_boundary(MAX_OUTBOUND_CONTENT_LEN));
         }
 
-        // Outbound rate limiting per destination (brief lock, no await inside)
-        {
+        // Outbound rate limiting per destination (brief lock, no await inside).
+        // Streamed partial text edits one message and must not use up the
+        // allowance of the reply that replaces it.
+        let streamed_partial = msg
+            .metadata
+            .get(crate::bus::meta::STREAM_PARTIAL)
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if !streamed_partial {
             let mut state = self
                 .rate_state
                 .lock()
@@ -0,0 +1,109 @@
+use super::cli_types::AuditCommands;
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub(super) fn audit_command(cmd: &AuditCommands) -> Result<()> {
+    let db_path = crate::utils::get_memory_db_path()?;
+
+    if !db_path.exists() {
+        anyhow::bail!(
+            "memory database not found at {}. Run the agent first to initialize it.",
+            db_path.display()
+        );
+    }
+
+    let db = crate::agent::memory::MemoryDB::new(&db_path)?;
+
+    match cmd {
+        AuditCommands::Tools { since, limit } => {
+            let cutoff = parse_since(since, Utc::now())?;
+            let entries = db.list_tool_audit(&cutoff, *limit)?;
+
+            if entries.is_empty() {
+                println!("No tool calls recorded since {cutoff} UTC.");
+                return Ok(());
+            }
+
+            println!(
+                "{:<20} {:<28} {:<24} {:>6} {:>8}",
+                "Time (UTC)", "Session", "Tool", "Status", "ms"
+            );
+            println!("{}", "\u{2500}".repeat(90));
+            for e in &entries {
+                println!(
+                    "{:<20} {:<28} {:<24} {:>6} {:>8}",
+                    e.timestamp,
+                    e.session_key,
+                    e.tool_name,
+                    if e.success { "ok" } else { "error" },
+                    e.duration_ms,
+                );
+                println!("    args:   {}", e.arguments);
+                println!("    result: {}", e.result_summary.replace('\n', " "));
+                if let Some(ref rid) = e.request_id {
+                    println!("    request: {rid}");
+                }
+            }
+            println!("{}", "\u{2500}".repeat(90));
+            println!("{} call(s) since {cutoff} UTC", entries.len());
+        }
+        AuditCommands::Outbound { since, limit } => {
+            let cutoff = parse_since(since, Utc::now())?;
+            let entries = db.list_outbound_dlq_entries(&cutoff, *limit)?;
+
+            if entries.is_empty() {
+                println!("No undelivered messages since {cutoff} UTC.");
+                return Ok(());
+            }
+
+            println!("{:<20} {:<12} {:<32}", "Time (UTC)", "Channel", "Chat");
+            println!("{}", "\u{2500}".repeat(90));
+            for e in &entries {
+                println!("{:<20} {:<12} {:<32}", e.failed_at, e.channel, e.chat_id);
+                println!(
+                    "    message: {}",
+                    crate::utils::truncate_chars(&e.content.replace('\n', " "), 200, "...")
+                );
+                println!("    error:   {}", e.error_message);
+            }
+            println!("{}", "\u{2500}".repeat(90));
+            println!(
+                "{} undelivered message(s) since {cutoff} UTC",
+                entries.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve a `--since` value to a UTC `YYYY-MM-DD HH:MM:SS` cutoff.
+/// Accepts relative durations (`30m`, `24h`, `7d`) or absolute UTC dates
+/// and date-times.
+pub(super) fn parse_since(since: &str, now: DateTime<Utc>) -> Result<String> {
+    let since = since.trim();
+    if let Some(unit) = since.chars().last()
+        && let Ok(n) = since[..since.len() - unit.len_utf8()].parse::<i64>()
+    {
+        let delta = match unit {
+            'm' => Some(chrono::Duration::minutes(n)),
+            'h' => Some(chrono::Duration::hours(n)),
+            'd' => Some(chrono::Duration::days(n)),
+            _ => None,
+        };
+        if let Some(delta) = delta {
+            return Ok((now - delta).format(TIMESTAMP_FORMAT).to_string());
+        }
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(since, TIMESTAMP_FORMAT) {
+        return Ok(dt.format(TIMESTAMP_FORMAT).to_string());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        return Ok(format!("{date} 00:00:00"));
+    }
+    anyhow::bail!(
+        "invalid --since value '{since}': use a duration like 30m, 24h, 7d or a date like 2026-01-31"
+    )
+}
@@ -0,0 +1,192 @@
+use super::gateway_setup::setup_provider;
+use crate::config::{load_config, parse_model_ref};
+use crate::providers::base::{ChatRequest, Message};
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Fixed prompt set: a near-empty reply (dominated by latency), a short
+/// answer, and a longer generation (dominated by throughput).
+const BENCH_PROMPTS: &[(&str, &str)] = &[
+    ("ping", "Reply with the single word: ready"),
+    (
+        "short",
+        "A train leaves at 09:40 and arrives at 13:15. How long is the journey? Answer in one sentence.",
+    ),
+    (
+        "long",
+        "Write a 300-word explanation of how TCP congestion control works, in plain prose.",
+    ),
+];
+const BENCH_MAX_TOKENS: u32 = 1024;
+const BENCH_PERCENTILES: &[f64] = &[50.0, 90.0, 99.0];
+
+/// Model reference to benchmark: `--provider` is prefixed onto the bare model
+/// name, replacing any provider prefix the model already carries.
+pub(super) fn bench_model_ref(
+    provider: Option<&str>,
+    model: Option<&str>,
+    default: &str,
+) -> String {
+    let model = model.unwrap_or(default);
+    match provider {
+        Some(provider) => format!("{provider}/{}", parse_model_ref(model).model),
+        None => model.to_string(),
+    }
+}
+
+/// Nearest-rank percentile of an ascending-sorted, non-empty slice.
+pub(super) fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Measurements collected for one prompt or for the whole run.
+#[derive(Default)]
+pub(super) struct BenchSamples {
+    pub latencies_ms: Vec<f64>,
+    pub tokens_per_sec: Vec<f64>,
+    pub failures: usize,
+}
+
+impl BenchSamples {
+    pub fn record(&mut self, elapsed_ms: f64, output_tokens: Option<u64>) {
+        self.latencies_ms.push(elapsed_ms);
+        // Providers that do not report usage get no throughput sample
+        if let Some(tokens) = output_tokens.filter(|t| *t > 0 && elapsed_ms > 0.0) {
+            self.tokens_per_sec
+                .push(tokens as f64 / (elapsed_ms / 1000.0));
+        }
+    }
+
+    pub fn extend(&mut self, other: &Self) {
+        self.latencies_ms.extend(&other.latencies_ms);
+        self.tokens_per_sec.extend(&other.tokens_per_sec);
+        self.failures += other.failures;
+    }
+}
+
+/// One table row: the label followed by the percentiles, min and max of
+/// `values`, or dashes when there are none.
+pub(super) fn format_row(label: &str, values: &[f64]) -> String {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mut row = format!("{label:<22}");
+    if sorted.is_empty() {
+        for _ in 0..BENCH_PERCENTILES.len() + 2 {
+            let _ = write!(row, " {:>9}", "-");
+        }
+        return row;
+    }
+    for p in BENCH_PERCENTILES {
+        let _ = write!(row, " {:>9.1}", percentile(&sorted, *p));
+    }
+    let _ = write!(row, " {:>9.1} {:>9.1}", sorted[0], sorted[sorted.len() - 1]);
+    row
+}
+
+pub(super) async fn bench_command(
+    provider: Option<&str>,
+    model: Option<&str>,
+    runs: usize,
+) -> Result<()> {
+    if runs == 0 {
+        anyhow::bail!("--runs must be at least 1");
+    }
+    let mut config = load_config(None)?;
+    config.validate()?;
+    let model_ref = bench_model_ref(
+        provider,
+        model.map(|m| config.models.resolve(m)),
+        config
+            .models
+            .resolve(&config.agents.defaults.model_routing.default),
+    );
+
+    // Measure exactly the requested model: no fallback chain, and no circuit
+    // breaker short-circuiting requests after a few failures.
+    config.agents.defaults.model_routing.fallbacks.clear();
+    config.providers.circuit_breaker.enabled = false;
+
+    // Reuse cached OAuth tokens when the agent has run before, without
+    // creating a database just for the benchmark.
+    let db_path = config
+        .workspace_path()
+        .join("memory")
+        .join("memory.sqlite3");
+    let token_store = if db_path.exists() {
+        let db = crate::agent::memory::MemoryDB::new(&db_path)
+            .with_context(|| format!("failed to open MemoryDB at: {}", db_path.display()))?;
+        Some(Arc::new(db) as Arc<dyn crate::utils::credential_store::OAuthTokenStore>)
+    } else {
+        None
+    };
+    let (llm, _) = setup_provider(&config, Some(&model_ref), token_store, None)?;
+    let bare_model = parse_model_ref(&model_ref).model.to_string();
+
+    println!(
+        "Benchmarking {model_ref}: {} prompts x {runs} runs",
+        BENCH_PROMPTS.len()
+    );
+    // Warm up so the first sample does not pay for the TLS handshake
+    if let Err(e) = llm.warmup().await {
+        println!("warmup failed (continuing): {e}");
+    }
+
+    let mut per_prompt: Vec<BenchSamples> = Vec::with_capacity(BENCH_PROMPTS.len());
+    for (name, prompt) in BENCH_PROMPTS {
+        let mut samples = BenchSamples::default();
+        for run in 1..=runs {
+            let req = ChatRequest::builder(vec![Message::user(*prompt)], BENCH_MAX_TOKENS)
+                .model(bare_model.clone())
+                .build();
+            let started = Instant::now();
+            match llm.chat_with_retry(&req, None).await {
+                Ok(response) => {
+                    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+                    samples.record(elapsed_ms, response.output_tokens);
+                }
+                Err(e) => {
+                    samples.failures += 1;
+                    println!("  {name} run {run}/{runs} failed: {e}");
+                }
+            }
+        }
+        per_prompt.push(samples);
+    }
+
+    let header = format!(
+        "{:<22} {:>9} {:>9} {:>9} {:>9} {:>9}",
+        "", "p50", "p90", "p99", "min", "max"
+    );
+    println!();
+    println!("{header}");
+    println!("{}", "\u{2500}".repeat(header.len()));
+    let mut total = BenchSamples::default();
+    for ((name, _), samples) in BENCH_PROMPTS.iter().zip(&per_prompt) {
+        println!(
+            "{}",
+            format_row(&format!("{name} latency (ms)"), &samples.latencies_ms)
+        );
+        println!(
+            "{}",
+            format_row(&format!("{name} tokens/sec"), &samples.tokens_per_sec)
+        );
+        total.extend(samples);
+    }
+    println!("{}", "\u{2500}".repeat(header.len()));
+    println!("{}", format_row("all latency (ms)", &total.latencies_ms));
+    println!("{}", format_row("all tokens/sec", &total.tokens_per_sec));
+    println!();
+    let attempted = BENCH_PROMPTS.len() * runs;
+    println!(
+        "{} of {attempted} requests succeeded",
+        attempted - total.failures
+    );
+
+    if total.failures == attempted {
+        anyhow::bail!("every benchmark request to {model_ref} failed");
+    }
+    Ok(())
+}
@@ -27,6 +27,13 @@ pub(super) enum Commands {
         message: Option<String>,
         #[arg(short, long, default_value = "cli:default")]
         session: String,
+        /// Start the session in focus mode with this tool preset
+        /// (`agents.defaults.toolPresets`)
+        #[arg(long)]
+        focus: Option<String>,
+        /// Log tool calls and return a synthetic result instead of running them
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Manage cron jobs
     Cron {
@@ -44,9 +51,17 @@ pub(super) enum Commands {
         cmd: ChannelCommands,
     },
     /// Show oxicrab status
-    Status,
+    Status {
+        /// Print a JSON object instead of human-readable text
+        #[arg(long)]
+        json: bool,
+    },
     /// Run system diagnostics
-    Doctor,
+    Doctor {
+        /// Print a JSON object instead of human-readable text
+        #[arg(long)]
+        json: bool,
+    },
     /// Manage sender pairing (authorize new users to message the bot)
     Pairing {
         #[command(subcommand)]
@@ -62,6 +77,34 @@ pub(super) enum Commands {
         #[command(subcommand)]
         cmd: StatsCommands,
     },
+    /// Inspect audit trails (tool calls executed by the agent)
+    Audit {
+        #[command(subcommand)]
+        cmd: AuditCommands,
+    },
+    /// Maintain the memory database
+    Memory {
+        #[command(subcommand)]
+        cmd: MemoryCommands,
+    },
+    /// Export and import conversation sessions
+    Session {
+        #[command(subcommand)]
+        cmd: SessionCommands,
+    },
+    /// Measure provider latency and throughput with a fixed prompt set
+    Bench {
+        /// Provider to benchmark (e.g. anthropic, openai, groq); defaults to
+        /// the provider inferred from the model
+        #[arg(long, short = 'p')]
+        provider: Option<String>,
+        /// Model to benchmark; defaults to the configured default model
+        #[arg(long, short = 'm')]
+        model: Option<String>,
+        /// How many times to send each prompt
+        #[arg(long, short = 'n', default_value = "5")]
+        runs: usize,
+    },
     /// Generate shell completion scripts
     Completion {
         /// Shell to generate completions for
@@ -91,10 +134,24 @@ pub(super) enum CronCommands {
         tz: Option<String>,
         #[arg(long)]
         at: Option<String>,
+        /// Fire daily at local sunrise or sunset (requires --lat and --lon)
+        #[arg(long, value_parser = ["sunrise", "sunset"], requires_all = ["lat", "lon"])]
+        solar: Option<String>,
+        /// Latitude in degrees for --solar (north positive)
+        #[arg(long, allow_negative_numbers = true)]
+        lat: Option<f64>,
+        /// Longitude in degrees for --solar (east positive)
+        #[arg(long, allow_negative_numbers = true)]
+        lon: Option<f64>,
+        /// Minutes to shift the --solar event by (negative fires earlier)
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        offset: i64,
         #[arg(long)]
         agent_echo: bool,
+        /// Target as `channel:id`, or just `id` with --channel. Repeatable;
+        /// append `=message` to send that target a different message.
         #[arg(long)]
-        to: Option<String>,
+        to: Vec<String>,
         #[arg(long)]
         channel: Option<String>,
         #[arg(long)]
@@ -130,8 +187,9 @@ pub(super) enum CronCommands {
         at: Option<String>,
         #[arg(long)]
         agent_echo: Option<bool>,
+        /// Replace the targets; same syntax as for `cron add`
         #[arg(long)]
-        to: Option<String>,
+        to: Vec<String>,
         #[arg(long)]
         channel: Option<String>,
         #[arg(long)]
@@ -185,11 +243,24 @@ pub(super) enum ChannelCommands {
 
 #[derive(Subcommand)]
 pub(super) enum StatsCommands {
-    /// Show LLM token usage summary
+    /// Show LLM token usage summary, or the per-turn breakdown of one session
     Tokens {
         /// Number of days to look back (default: 7)
         #[arg(long, short = 'd', default_value = "7")]
         days: u32,
+        /// First day to include (YYYY-MM-DD, UTC); overrides --days
+        #[arg(long)]
+        since: Option<String>,
+        /// Last day to include (YYYY-MM-DD, UTC, default: today)
+        #[arg(long)]
+        until: Option<String>,
+        /// Show the estimated per-turn token breakdown of this session
+        /// (e.g. "telegram:12345"); needs `agents.defaults.tokenBreakdown`
+        #[arg(long, short = 's')]
+        session: Option<String>,
+        /// Maximum number of turns to show with --session
+        #[arg(long, short = 'n', default_value = "20")]
+        limit: usize,
     },
     /// Show memory search statistics
     Search,
@@ -199,6 +270,79 @@ pub(super) enum StatsCommands {
         #[arg(long, short = 'd', default_value = "7")]
         days: u32,
     },
+    /// Show how often models returned empty responses until retries ran out
+    Empty {
+        /// Number of days to look back (default: 7)
+        #[arg(long, short = 'd', default_value = "7")]
+        days: u32,
+    },
+    /// Show per-tool call counts and failure rates from the tool audit log
+    Tools {
+        /// Number of days to look back (default: 7)
+        #[arg(long, short = 'd', default_value = "7")]
+        days: u32,
+    },
+}
+
+#[derive(Subcommand)]
+pub(super) enum AuditCommands {
+    /// Show recorded tool calls, newest first
+    Tools {
+        /// How far back to look: relative (30m, 24h, 7d) or a UTC date/time
+        /// (2026-01-31, "2026-01-31 14:00:00")
+        #[arg(long, short = 's', default_value = "24h")]
+        since: String,
+        /// Maximum number of entries to show
+        #[arg(long, short = 'n', default_value = "50")]
+        limit: usize,
+    },
+    /// Show outbound messages that could not be delivered after all retries
+    Outbound {
+        /// How far back to look: relative (30m, 24h, 7d) or a UTC date/time
+        #[arg(long, short = 's', default_value = "7d")]
+        since: String,
+        /// Maximum number of entries to show
+        #[arg(long, short = 'n', default_value = "50")]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub(super) enum MemoryCommands {
+    /// Rebuild the full-text search index; safe while the gateway is running
+    Reindex {
+        /// Also embed entries missing an embedding with the configured backend
+        /// (replacing embeddings of a different dimension)
+        #[arg(long)]
+        embeddings: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub(super) enum SessionCommands {
+    /// Write a session (history and metadata) as JSON
+    Export {
+        /// Session key, e.g. `telegram:123456`
+        key: String,
+        /// File to write; prints to stdout when omitted
+        #[arg(long, short = 'o')]
+        out: Option<std::path::PathBuf>,
+        /// Profile whose workspace holds the session; defaults to the
+        /// profile the key routes to
+        #[arg(long, short = 'p')]
+        profile: Option<String>,
+    },
+    /// Load a session from a JSON file written by `session export`
+    Import {
+        file: std::path::PathBuf,
+        /// Replace an existing session with the same key
+        #[arg(long, short = 'f')]
+        force: bool,
+        /// Profile whose workspace receives the session; defaults to the
+        /// profile the key routes to
+        #[arg(long, short = 'p')]
+        profile: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1,10 +1,42 @@
 use super::cli_types::CronCommands;
 use crate::config::load_config;
 use crate::cron::service::CronService;
-use crate::cron::types::{CronJob, CronJobState, CronPayload, CronSchedule};
+use crate::cron::types::{CronJob, CronJobState, CronPayload, CronSchedule, CronTarget};
 use anyhow::{Context, Result};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Parse `--to` values: `channel:id`, or just `id` when `channel` is given,
+/// optionally followed by `=message` to override the job's message for that
+/// target.
+pub(super) fn parse_cron_targets(channel: Option<&str>, to: &[String]) -> Result<Vec<CronTarget>> {
+    to.iter()
+        .map(|spec| {
+            let (dest, message_override) = match spec.split_once('=') {
+                Some((dest, message)) => (dest, Some(message.trim().to_string())),
+                None => (spec.as_str(), None),
+            };
+            let (target_channel, target_to) = match channel {
+                Some(ch) => (ch, dest),
+                None => dest.split_once(':').with_context(|| {
+                    format!("invalid --to '{spec}': expected channel:id[=message] or --channel")
+                })?,
+            };
+            let (target_channel, target_to) = (target_channel.trim(), target_to.trim());
+            if target_channel.is_empty() || target_to.is_empty() {
+                anyhow::bail!("invalid --to '{spec}': channel and id must not be empty");
+            }
+            if message_override.as_deref() == Some("") {
+                anyhow::bail!("invalid --to '{spec}': message after '=' must not be empty");
+            }
+            Ok(CronTarget {
+                channel: target_channel.to_string(),
+                to: target_to.to_string(),
+                message_override,
+            })
+        })
+        .collect()
+}
+
 #[allow(clippy::too_many_lines)]
 pub(super) fn cron_command(cmd: CronCommands) -> Result<()> {
     let db_path = crate::utils::get_memory_db_path()?;
@@ -43,13 +75,16 @@ pub(super) fn cron_command(cmd: CronCommands) -> Result<()> {
             cron: cron_expr,
             tz,
             at,
+            solar,
+            lat,
+            lon,
+            offset,
             agent_echo,
             to,
             channel,
             all_channels,
         } => {
             use crate::agent::tools::cron::resolve_all_channel_targets_from_config;
-            use crate::cron::types::CronTarget;
 
             let targets = if all_channels {
                 let config = load_config(None)?;
@@ -58,13 +93,10 @@ pub(super) fn cron_command(cmd: CronCommands) -> Result<()> {
                     anyhow::bail!("No enabled channels with allowFrom configured");
                 }
                 targets
-            } else if let (Some(ch), Some(to_val)) = (channel, to) {
-                vec![CronTarget {
-                    channel: ch,
-                    to: to_val,
-                }]
+            } else if !to.is_empty() {
+                parse_cron_targets(channel.as_deref(), &to)?
             } else {
-                anyhow::bail!("Either --channel + --to or --all-channels is required");
+                anyhow::bail!("Either --to or --all-channels is required");
             };
 
             let schedule = if let Some(every_sec) = every {
@@ -95,8 +127,16 @@ pub(super) fn cron_command(cmd: CronCommands) -> Result<()> {
                 CronSchedule::At {
                     at_ms: Some(dt.timestamp_millis()),
                 }
+            } else if let (Some(event), Some(lat), Some(lon)) = (solar, lat, lon) {
+                crate::cron::solar::validate_solar(lat, lon, offset)?;
+                CronSchedule::Solar {
+                    event: event.parse()?,
+                    lat,
+                    lon,
+                    offset_minutes: offset,
+                }
             } else {
-                anyhow::bail!("Must specify --every, --cron, or --at");
+                anyhow::bail!("Must specify --every, --cron, --at, or --solar");
             };
 
             let now_ms = SystemTime::now()
@@ -161,7 +201,6 @@ pub(super) fn cron_command(cmd: CronCommands) -> Result<()> {
             all_channels,
         } => {
             use crate::agent::tools::cron::resolve_all_channel_targets_from_config;
-            use crate::cron::types::CronTarget;
 
             let schedule = if let Some(every_sec) = every {
                 if !(60..=31_536_000).contains(&every_sec) {
@@ -216,11 +255,8 @@ pub(super) fn cron_command(cmd: CronCommands) -> Result<()> {
                     anyhow::bail!("No enabled channels with allowFrom configured");
                 }
                 Some(targets)
-            } else if let (Some(ch), Some(to_val)) = (channel, to) {
-                Some(vec![CronTarget {
-                    channel: ch,
-                    to: to_val,
-                }])
+            } else if !to.is_empty() {
+                Some(parse_cron_targets(channel.as_deref(), &to)?)
             } else {
                 None
             };
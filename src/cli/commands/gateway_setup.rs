@@ -3,8 +3,9 @@ use crate::bus::MessageBus;
 use crate::channels::manager::ChannelManager;
 use crate::config::{Config, load_config};
 use crate::cron::service::CronService;
-use crate::cron::types::CronJob;
+use crate::cron::types::{CronJob, CronTarget};
 use anyhow::{Context, Result};
+use oxicrab_core::providers::circuit_state::{CircuitHealth, CircuitStateStore};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -33,9 +34,11 @@ pub(super) async fn gateway(model: Option<String>) -> Result<()> {
     config.validate()?;
     crate::observability::init_metrics_exporter(&config);
     warn_if_public_gateway_without_auth(&config);
-    let effective_model = model
-        .as_deref()
-        .unwrap_or(&config.agents.defaults.model_routing.default);
+    let effective_model = config.models.resolve(
+        model
+            .as_deref()
+            .unwrap_or(&config.agents.defaults.model_routing.default),
+    );
     info!("Configuration loaded. Using model: {}", effective_model);
     debug!("Workspace: {:?}", config.workspace_path());
 
@@ -63,10 +66,11 @@ pub(super) async fn gateway(model: Option<String>) -> Result<()> {
     let memory_db = Arc::new(memory_db);
 
     // Setup components
-    let provider = setup_provider(
+    let (provider, provider_health) = setup_provider(
         &config,
         model.as_deref(),
         Some(memory_db.clone() as Arc<dyn crate::utils::credential_store::OAuthTokenStore>),
+        Some(memory_db.clone() as Arc<dyn CircuitStateStore>),
     )?;
 
     // Fire-and-forget warmup — don't block startup on a network round-trip
@@ -98,6 +102,8 @@ pub(super) async fn gateway(model: Option<String>) -> Result<()> {
     let (inbound_tx, outbound_tx, outbound_rx, bus_for_channels) =
         setup_message_bus_with_detector(leak_detector.clone())?;
     let cron = setup_cron_service(memory_db.clone());
+    let (tenants, tenant_routes) =
+        super::gateway_tenants::prepare_tenants(&config, &leak_detector)?;
     // Create typing indicator channel
     let (typing_tx, typing_rx) = tokio::sync::mpsc::channel::<(String, String)>(100);
     let typing_tx = Arc::new(typing_tx);
@@ -147,10 +153,13 @@ pub(super) async fn gateway(model: Option<String>) -> Result<()> {
                 config.gateway.webhooks.clone(),
                 a2a_config,
                 api_key,
+                tenant_routes,
                 &config.gateway.rate_limit,
                 leak_detector.clone() as Arc<dyn oxicrab_core::safety::LeakRedactor>,
                 ready.clone(),
                 status_lock.clone(),
+                provider_health,
+                crate::observability::gateway_metrics_renderer(&config),
                 false, // not echo mode
             )
             .await?;
@@ -210,7 +219,15 @@ pub(super) async fn gateway(model: Option<String>) -> Result<()> {
         error!("status OnceLock already set — this is a bug");
     }
 
-    let channels = setup_channels(&config, inbound_tx);
+    let tenant_tasks = Box::pin(super::gateway_tenants::start_tenants(
+        tenants,
+        &outbound_tx,
+        agent.memory_db(),
+        &leak_detector,
+    ))
+    .await?;
+
+    let channels = setup_channels(&config, inbound_tx, outbound_tx.clone());
 
     println!("Starting oxicrab gateway...");
     println!("Enabled channels: {:?}", channels.enabled_channels());
@@ -229,7 +246,15 @@ pub(super) async fn gateway(model: Option<String>) -> Result<()> {
     // Run agent and channels
     ready.store(true, std::sync::atomic::Ordering::SeqCst);
     let agent_task = start_agent_loop(agent.clone());
-    let channels_task = start_channels_loop(channels, outbound_rx, typing_rx, http_state);
+    let channels_task = start_channels_loop(
+        channels,
+        outbound_rx,
+        typing_rx,
+        http_state,
+        Some(agent.memory_db()),
+        config.channels.rate_limit,
+        super::quiet_hours::QuietHours::new(&config.channels.quiet_hours, agent.memory_db())?,
+    );
 
     info!("All services started, gateway is running");
 
@@ -238,12 +263,19 @@ pub(super) async fn gateway(model: Option<String>) -> Result<()> {
         _ = tokio::signal::ctrl_c() => {
             println!("\nShutting down...");
             cron.stop().await;
-            agent.stop().await;
+            let drain = std::time::Duration::from_secs(config.gateway.drain_timeout_secs);
+            let deadline = tokio::time::Instant::now() + drain;
+            agent.stop_with_drain(drain).await;
+            // Let the channels loop pick up the drained turn's response
+            wait_for_outbound_flush(&outbound_tx, deadline).await;
             // Channels will stop themselves when the task ends
         }
         _ = agent_task => {}
         _ = channels_task => {}
     }
+    for task in tenant_tasks {
+        task.abort();
+    }
 
     Ok(())
 }
@@ -289,10 +321,13 @@ pub(super) async fn gateway_echo() -> Result<()> {
             config.gateway.webhooks.clone(),
             None, // A2A not available in echo mode
             api_key,
+            vec![], // no tenant agent loops in echo mode
             &config.gateway.rate_limit,
             leak_detector as Arc<dyn oxicrab_core::safety::LeakRedactor>,
             ready,
             Arc::new(std::sync::OnceLock::new()),
+            None, // no provider in echo mode
+            crate::observability::gateway_metrics_renderer(&config),
             true, // echo mode
         )
         .await?;
@@ -302,7 +337,7 @@ pub(super) async fn gateway_echo() -> Result<()> {
         None
     };
 
-    let channels = setup_channels(&config, inbound_tx);
+    let channels = setup_channels(&config, inbound_tx, outbound_tx.clone());
 
     println!("Starting oxicrab gateway in ECHO mode (no LLM)...");
     println!("Enabled channels: {:?}", channels.enabled_channels());
@@ -338,7 +373,15 @@ pub(super) async fn gateway_echo() -> Result<()> {
         })
     };
 
-    let channels_task = start_channels_loop(channels, outbound_rx, typing_rx, http_state);
+    let channels_task = start_channels_loop(
+        channels,
+        outbound_rx,
+        typing_rx,
+        http_state,
+        None,
+        config.channels.rate_limit,
+        None,
+    );
 
     info!("Echo gateway running");
 
@@ -353,33 +396,77 @@ pub(super) async fn gateway_echo() -> Result<()> {
     Ok(())
 }
 
-fn setup_provider(
+/// The provider, and its circuit breaker when one is enabled.
+pub(super) type ProviderSetup = (
+    Arc<dyn crate::providers::base::LLMProvider>,
+    Option<Arc<dyn CircuitHealth>>,
+);
+
+pub(super) fn setup_provider(
     config: &Config,
     model: Option<&str>,
     db: Option<Arc<dyn crate::utils::credential_store::OAuthTokenStore>>,
-) -> Result<Arc<dyn crate::providers::base::LLMProvider>> {
-    let effective_model = model.unwrap_or(&config.agents.defaults.model_routing.default);
+    circuit_store: Option<Arc<dyn CircuitStateStore>>,
+) -> Result<ProviderSetup> {
+    let effective_model = config
+        .models
+        .resolve(model.unwrap_or(&config.agents.defaults.model_routing.default));
     info!("Creating LLM provider for model: {}", effective_model);
-    let provider = crate::provider_factory::create_provider(config, model, db)?;
+    let provider = crate::provider_factory::create_provider(config, model, db.clone())?;
     info!(
         "Provider created successfully. Default model: {}",
         provider.default_model()
     );
 
+    let store = circuit_store.filter(|_| config.providers.circuit_breaker.persist_state);
     // Wrap with circuit breaker if enabled
     let provider = if config.providers.circuit_breaker.enabled {
         info!(
-            "circuit breaker enabled (threshold={}, recovery={}s, probes={})",
+            "circuit breaker enabled (threshold={}, recovery={}s, probes={}, persist={})",
             config.providers.circuit_breaker.failure_threshold,
             config.providers.circuit_breaker.recovery_timeout_secs,
             config.providers.circuit_breaker.half_open_probes,
+            config.providers.circuit_breaker.persist_state,
         );
-        crate::providers::circuit_breaker::CircuitBreakerProvider::wrap(
+        let breaker = crate::providers::circuit_breaker::CircuitBreakerProvider::with_store(
             provider,
             &config.providers.circuit_breaker,
+            store.clone(),
+        );
+        (
+            breaker.clone() as Arc<dyn crate::providers::base::LLMProvider>,
+            Some(breaker as Arc<dyn CircuitHealth>),
         )
     } else {
-        provider
+        (provider, None)
+    };
+
+    // Failover wraps the breaker so an open circuit goes straight to the secondary
+    let provider = match crate::provider_factory::create_failover_provider(config, db)? {
+        Some((secondary, name)) => {
+            info!(
+                "provider failover enabled (secondary={}, model={})",
+                name,
+                secondary.default_model()
+            );
+            // Its own breaker, so a secondary that keeps failing is skipped
+            // like the primary and the failover reports the outage quickly
+            let secondary = if config.providers.circuit_breaker.enabled {
+                crate::providers::circuit_breaker::CircuitBreakerProvider::wrap_with_store(
+                    secondary,
+                    &config.providers.circuit_breaker,
+                    store,
+                )
+            } else {
+                secondary
+            };
+            let (primary, health) = provider;
+            (
+                crate::providers::failover::FailoverProvider::wrap(primary, secondary, name),
+                health,
+            )
+        }
+        None => provider,
     };
 
     Ok(provider)
@@ -392,7 +479,7 @@ pub(super) type MessageBusSetup = (
     Arc<MessageBus>,
 );
 
-fn setup_message_bus_with_detector(
+pub(super) fn setup_message_bus_with_detector(
     leak_detector: Arc<crate::safety::LeakDetector>,
 ) -> Result<MessageBusSetup> {
     debug!("Creating message bus...");
@@ -465,8 +552,10 @@ pub(super) async fn setup_agent(
         }
     };
 
+    // Boxed: the constructor's future is large enough to trip clippy's
+    // large_futures in every caller
     let agent = Arc::new(
-        AgentLoop::new(crate::agent::AgentLoopConfig::from_config(
+        Box::pin(AgentLoop::new(crate::agent::AgentLoopConfig::from_config(
             config,
             crate::agent::AgentLoopRuntimeParams {
                 bus: params.bus,
@@ -480,7 +569,7 @@ pub(super) async fn setup_agent(
                 leak_detector: params.leak_detector,
             },
             routing,
-        ))
+        )))
         .await?,
     );
     info!("Agent loop initialized");
@@ -535,8 +624,9 @@ async fn cron_job_execute(
                     crate::bus::OutboundMessage::builder(
                         target.channel.clone(),
                         target.to.clone(),
-                        job.payload.message.clone(),
+                        target.message(&job.payload.message).to_string(),
                     )
+                    .meta(crate::bus::meta::BACKGROUND, serde_json::Value::Bool(true))
                     .build(),
                 )
                 .await
@@ -550,51 +640,68 @@ async fn cron_job_execute(
         return Ok(Some(job.payload.message.clone()));
     }
 
-    // Agent mode: process as a full agent turn
-    let (ctx_channel, ctx_chat_id) = job
-        .payload
-        .targets
-        .first()
-        .map_or(("cli", "direct"), |t| (t.channel.as_str(), t.to.as_str()));
+    // Agent mode: process as a full agent turn, one per distinct message
+    // since targets with a message override ask for something else
+    let mut turns: Vec<(&str, Vec<&CronTarget>)> = Vec::new();
+    for target in &job.payload.targets {
+        let message = target.message(&job.payload.message);
+        match turns.iter_mut().find(|(m, _)| *m == message) {
+            Some((_, targets)) => targets.push(target),
+            None => turns.push((message, vec![target])),
+        }
+    }
+    if turns.is_empty() {
+        turns.push((&job.payload.message, Vec::new()));
+    }
 
     let mut cron_overrides = agent.resolve_overrides("cron");
+    // Background turn: no typing dots in the target chat while it runs
+    cron_overrides.suppress_typing = true;
     cron_overrides.metadata.insert(
         crate::bus::meta::IS_CRON_JOB.to_string(),
         serde_json::Value::Bool(true),
     );
-    let result = agent
-        .process_direct_with_overrides(
-            &job.payload.message,
-            &format!("cron:{}", job.id),
-            ctx_channel,
-            ctx_chat_id,
-            &cron_overrides,
-        )
-        .await?;
+    let mut first_content = None;
+    for (message, targets) in turns {
+        let (ctx_channel, ctx_chat_id) = targets
+            .first()
+            .map_or(("cli", "direct"), |t| (t.channel.as_str(), t.to.as_str()));
+        let result = agent
+            .process_direct_with_overrides(
+                message,
+                &format!("cron:{}", job.id),
+                ctx_channel,
+                ctx_chat_id,
+                &cron_overrides,
+            )
+            .await?;
 
-    if job.payload.agent_echo {
-        for target in &job.payload.targets {
-            if let Err(e) = bus
-                .publish_outbound(
-                    crate::bus::OutboundMessage::builder(
-                        target.channel.clone(),
-                        target.to.clone(),
-                        result.content.clone(),
+        if job.payload.agent_echo {
+            for target in targets {
+                if let Err(e) = bus
+                    .publish_outbound(
+                        crate::bus::OutboundMessage::builder(
+                            target.channel.clone(),
+                            target.to.clone(),
+                            result.content.clone(),
+                        )
+                        .merge_metadata(result.metadata.clone())
+                        .meta(crate::bus::meta::BACKGROUND, serde_json::Value::Bool(true))
+                        .build(),
                     )
-                    .merge_metadata(result.metadata.clone())
-                    .build(),
-                )
-                .await
-            {
-                error!(
-                    "Failed to publish outbound message from cron to {}:{}: {}",
-                    target.channel, target.to, e
-                );
+                    .await
+                {
+                    error!(
+                        "Failed to publish outbound message from cron to {}:{}: {}",
+                        target.channel, target.to, e
+                    );
+                }
             }
         }
+        first_content.get_or_insert(result.content);
     }
 
-    Ok(Some(result.content))
+    Ok(first_content)
 }
 
 /// Log skills that have a `schedule` frontmatter field but no active cron job.
@@ -644,9 +751,18 @@ fn log_unscheduled_skills(config: &Config, cron: &Arc<CronService>) {
 fn setup_channels(
     config: &Config,
     inbound_tx: tokio::sync::mpsc::Sender<crate::bus::InboundMessage>,
+    outbound_tx: Arc<tokio::sync::mpsc::Sender<crate::bus::OutboundMessage>>,
 ) -> ChannelManager {
     // Register the pairing requester so channels can issue pairing codes
-    oxicrab_channels::set_pairing_requester(Box::new(OxicrabPairingRequester));
+    // and forward unknown senders to the admin
+    oxicrab_channels::set_pairing_requester(Box::new(OxicrabPairingRequester {
+        admin_target: config.channels.admin_target.clone(),
+        admin_senders: config.channels.admin_senders.clone(),
+        outbound_tx,
+        notified_codes: std::sync::Mutex::new(lru::LruCache::new(
+            std::num::NonZeroUsize::new(256).expect("non-zero"),
+        )),
+    }));
 
     info!("Initializing channels...");
     let channels = ChannelManager::new(config, Arc::new(inbound_tx));
@@ -659,7 +775,16 @@ fn setup_channels(
 
 /// Adapter that implements the channels crate's `PairingRequester` trait
 /// using the main crate's `PairingStore`.
-struct OxicrabPairingRequester;
+struct OxicrabPairingRequester {
+    admin_target: Option<crate::config::ChannelTarget>,
+    /// When set, only these senders may use the notice buttons.
+    admin_senders: Vec<String>,
+    outbound_tx: Arc<tokio::sync::mpsc::Sender<crate::bus::OutboundMessage>>,
+    /// Pairing codes already forwarded to the admin. The store hands out the
+    /// same code while a request is pending, so this keeps repeat messages
+    /// from an unknown sender from re-notifying.
+    notified_codes: std::sync::Mutex<lru::LruCache<String, ()>>,
+}
 
 impl oxicrab_channels::PairingRequester for OxicrabPairingRequester {
     fn request_pairing(&self, channel: &str, sender_id: &str) -> Option<String> {
@@ -677,6 +802,132 @@ impl oxicrab_channels::PairingRequester for OxicrabPairingRequester {
             }
         }
     }
+
+    fn notify_admin(&self, channel: &str, sender_id: &str, code: &str, message: &str) -> bool {
+        let Some(ref target) = self.admin_target else {
+            return false;
+        };
+        {
+            let mut notified = self
+                .notified_codes
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if notified.put(format!("{channel}:{code}"), ()).is_some() {
+                return true;
+            }
+        }
+        let text = format_admin_pairing_notice(channel, sender_id, code, message);
+        let mut builder =
+            crate::bus::OutboundMessage::builder(target.channel_type(), target.chat_id(), text);
+        // Only Telegram resolves these buttons itself
+        if target.channel_type() == "telegram" {
+            builder = builder.meta(crate::bus::meta::BUTTONS, admin_pairing_buttons(code));
+        }
+        let msg = builder.build();
+        match self.outbound_tx.try_send(msg) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("failed to queue admin pairing notice: {}", e);
+                false
+            }
+        }
+    }
+
+    fn resolve_from_admin(
+        &self,
+        channel: &str,
+        chat_id: &str,
+        sender_id: &str,
+        code: &str,
+        approve: bool,
+    ) -> Option<String> {
+        let target = self.admin_target.as_ref()?;
+        if target.channel_type() != channel || target.chat_id() != chat_id {
+            return None;
+        }
+        let sender = format!("{channel}:{sender_id}");
+        if !self.admin_senders.is_empty() && !self.admin_senders.contains(&sender) {
+            warn!("pairing button used by non-admin sender {}", sender);
+            return None;
+        }
+        Some(match crate::pairing::PairingStore::open_default() {
+            Ok(store) => resolve_pairing_code(&store, code, &sender, approve),
+            Err(e) => {
+                warn!("failed to open pairing store: {}", e);
+                format!("Could not resolve code {code}: {e}")
+            }
+        })
+    }
+}
+
+/// Approve/Deny buttons for an admin pairing notice; the context is the code.
+pub(super) fn admin_pairing_buttons(code: &str) -> serde_json::Value {
+    serde_json::json!([
+        {"id": oxicrab_channels::PAIRING_APPROVE_ACTION, "label": "Approve", "style": "primary", "context": code},
+        {"id": oxicrab_channels::PAIRING_DENY_ACTION, "label": "Deny", "style": "danger", "context": code},
+    ])
+}
+
+/// Approve or deny `code` for `approver` and describe the outcome. A code
+/// that was already used or has expired is reported, not treated as an error.
+pub(super) fn resolve_pairing_code(
+    store: &crate::pairing::PairingStore,
+    code: &str,
+    approver: &str,
+    approve: bool,
+) -> String {
+    let result = if approve {
+        store.approve_with_client(code, approver)
+    } else {
+        store.deny(code)
+    };
+    match result {
+        Ok(Some((channel, sender_id))) if approve => {
+            format!("Approved {channel}:{sender_id}. They can message now.")
+        }
+        Ok(Some((channel, sender_id))) => format!("Denied {channel}:{sender_id}."),
+        Ok(None) => format!("Code {code} was already handled or has expired."),
+        Err(e) => format!("Could not resolve code {code}: {e}"),
+    }
+}
+
+/// Message sent to `channels.adminTarget` when an unknown sender is
+/// forwarded under `unknownSenderPolicy = "notifyAdmin"`.
+pub(super) fn format_admin_pairing_notice(
+    channel: &str,
+    sender_id: &str,
+    code: &str,
+    message: &str,
+) -> String {
+    let message = crate::utils::truncate_chars(message.trim(), 500, "...");
+    let message = if message.is_empty() {
+        "(no text)".to_string()
+    } else {
+        message
+    };
+    format!(
+        "New sender on {channel} is asking for access.\n\
+         Sender ID: {sender_id}\n\
+         Message: {message}\n\
+         Approve with: oxicrab pairing approve {channel} {code}"
+    )
+}
+
+/// Wait until the outbound queue is empty or `deadline` passes.
+async fn wait_for_outbound_flush(
+    outbound_tx: &tokio::sync::mpsc::Sender<crate::bus::OutboundMessage>,
+    deadline: tokio::time::Instant,
+) {
+    while outbound_tx.capacity() < outbound_tx.max_capacity() {
+        if tokio::time::Instant::now() >= deadline {
+            warn!(
+                "shutting down with {} outbound messages undelivered",
+                outbound_tx.max_capacity() - outbound_tx.capacity()
+            );
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
 }
 
 async fn start_services(cron: Arc<CronService>) -> Result<()> {
@@ -686,7 +937,7 @@ async fn start_services(cron: Arc<CronService>) -> Result<()> {
     Ok(())
 }
 
-fn start_agent_loop(agent: Arc<AgentLoop>) -> tokio::task::JoinHandle<()> {
+pub(super) fn start_agent_loop(agent: Arc<AgentLoop>) -> tokio::task::JoinHandle<()> {
     info!("Starting agent loop...");
     tokio::spawn(async move {
         info!("Agent loop running");
@@ -697,12 +948,47 @@ fn start_agent_loop(agent: Arc<AgentLoop>) -> tokio::task::JoinHandle<()> {
     })
 }
 
+/// Handle a leading `[REACT:<emoji>]` marker on an agent reply: react to the
+/// user's message (the reply's `reply_to`) and strip the marker. Returns
+/// `None` when the reply was only the marker and there is nothing to send.
+async fn apply_react_marker(
+    channels: &ChannelManager,
+    mut msg: crate::bus::OutboundMessage,
+) -> Option<crate::bus::OutboundMessage> {
+    let Some((emoji, rest)) = oxicrab_core::channels::base::split_react_marker(&msg.content)
+        .map(|(emoji, rest)| (emoji.to_string(), rest.to_string()))
+    else {
+        return Some(msg);
+    };
+    if let Some(ref message_id) = msg.reply_to {
+        if let Err(e) = channels
+            .react(&msg.channel, &msg.chat_id, message_id, &emoji)
+            .await
+        {
+            warn!("failed to react with {} on {}: {}", emoji, msg.channel, e);
+        }
+    } else {
+        debug!(
+            "reaction {} dropped: reply has no message to react to",
+            emoji
+        );
+    }
+    if rest.is_empty() && msg.media.is_empty() {
+        return None;
+    }
+    msg.content = rest;
+    Some(msg)
+}
+
 #[allow(clippy::too_many_lines)]
 fn start_channels_loop(
     mut channels: ChannelManager,
     mut outbound_rx: tokio::sync::mpsc::Receiver<crate::bus::OutboundMessage>,
     mut typing_rx: tokio::sync::mpsc::Receiver<(String, String)>,
     http_api_state: Option<crate::gateway::HttpApiState>,
+    outbound_dlq: Option<Arc<crate::agent::memory::memory_db::MemoryDB>>,
+    rate_limit: crate::config::ChannelRateLimitConfig,
+    quiet_hours: Option<super::quiet_hours::QuietHours>,
 ) -> tokio::task::JoinHandle<()> {
     info!("Starting all channels...");
     tokio::spawn(async move {
@@ -748,9 +1034,43 @@ fn start_channels_loop(
         // Track status messages for in-place editing
         let mut status_msg_ids: HashMap<(String, String), String> = HashMap::new();
         let mut status_content: HashMap<(String, String), String> = HashMap::new();
+        // Paces regular sends per chat; status edits are not counted
+        let mut send_rate = super::send_rate::SendRateLimiter::new(rate_limit);
+        // Sends delayed by pacing, and those whose slot has come
+        let mut paced = super::send_rate::PacedSends::default();
+        let mut paced_ready = std::collections::VecDeque::new();
+        // Messages showing streamed reply text, edited as it grows
+        let mut streamed = super::stream_edit::StreamedReplies::default();
+        // Messages held over quiet hours, sent ahead of new ones once released.
+        // The first tick fires at once, releasing anything held before a restart.
+        let mut released = std::collections::VecDeque::new();
+        let mut release_tick = tokio::time::interval(std::time::Duration::from_secs(60));
 
         loop {
-            if let Some(msg) = outbound_rx.recv().await {
+            let next_paced = paced.next_due();
+            let (next, slot_reserved) = if let Some(msg) = paced_ready.pop_front() {
+                (Some(msg), true)
+            } else if let Some(msg) = released.pop_front() {
+                (Some(msg), false)
+            } else {
+                let msg = tokio::select! {
+                    msg = outbound_rx.recv() => msg,
+                    _ = release_tick.tick(), if quiet_hours.is_some() => {
+                        if let Some(ref quiet) = quiet_hours {
+                            released.extend(quiet.release_due(chrono::Utc::now()));
+                        }
+                        continue;
+                    }
+                    () = tokio::time::sleep_until(
+                        next_paced.unwrap_or_else(tokio::time::Instant::now)
+                    ), if next_paced.is_some() => {
+                        paced_ready.extend(paced.take_due(tokio::time::Instant::now()));
+                        continue;
+                    }
+                };
+                (msg, false)
+            };
+            if let Some(msg) = next {
                 // Route HTTP API responses back to waiting HTTP handlers.
                 // Check channel first to avoid cloning for non-HTTP messages.
                 if msg.channel == "http"
@@ -759,6 +1079,11 @@ fn start_channels_loop(
                 {
                     continue;
                 }
+                if let Some(ref quiet) = quiet_hours
+                    && quiet.hold(&msg, chrono::Utc::now())
+                {
+                    continue;
+                }
                 debug!(
                     "Consumed outbound message: channel={}, chat_id={}, content_len={}",
                     msg.channel,
@@ -766,6 +1091,17 @@ fn start_channels_loop(
                     msg.content.len()
                 );
 
+                // Streamed partial text is not paced and never queued
+                if msg
+                    .metadata
+                    .get(crate::bus::meta::STREAM_PARTIAL)
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or_default()
+                {
+                    streamed.show_partial(&*channels.lock().await, &msg).await;
+                    continue;
+                }
+
                 // Evict stale status tracking entries to prevent unbounded growth
                 if status_msg_ids.len() > 1000 {
                     status_msg_ids.clear();
@@ -779,6 +1115,25 @@ fn start_channels_loop(
                     .unwrap_or_default();
                 let key = (msg.channel.clone(), msg.chat_id.clone());
 
+                // Over-allowance sends wait their turn without holding up
+                // other chats
+                if !is_status
+                    && !slot_reserved
+                    && let Some(ref mut limiter) = send_rate
+                {
+                    let wait = limiter.reserve(&key, std::time::Instant::now());
+                    if !wait.is_zero() {
+                        debug!(
+                            "pacing send to {}:{} by {}ms",
+                            key.0,
+                            key.1,
+                            wait.as_millis()
+                        );
+                        paced.push(tokio::time::Instant::now() + wait, msg);
+                        continue;
+                    }
+                }
+
                 // Lock channels for the send operation
                 let channels_guard = channels.lock().await;
 
@@ -839,8 +1194,24 @@ fn start_channels_loop(
                     }
                     status_content.remove(&key);
 
-                    if let Err(e) = channels_guard.send(&msg).await {
+                    let Some(msg) = apply_react_marker(&channels_guard, msg).await else {
+                        continue;
+                    };
+                    if streamed.finish(&channels_guard, &msg).await {
+                        debug!("streamed reply finished in place");
+                    } else if let Err(e) = channels_guard.send(&msg).await {
                         error!("Error sending message to channels: {}", e);
+                        // Retries are exhausted; keep a record of the lost reply
+                        if let Some(ref db) = outbound_dlq
+                            && let Err(dlq_err) = db.insert_outbound_dlq_entry(
+                                &msg.channel,
+                                &msg.chat_id,
+                                &msg.content,
+                                &e.to_string(),
+                            )
+                        {
+                            warn!("failed to record undelivered message: {}", dlq_err);
+                        }
                     } else {
                         info!("Successfully sent outbound message to channel manager");
                     }
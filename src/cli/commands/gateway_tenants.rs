@@ -0,0 +1,157 @@
+use super::gateway_setup::{
+    SetupAgentParams, setup_agent, setup_message_bus_with_detector, setup_provider,
+    start_agent_loop,
+};
+use crate::agent::memory::memory_db::MemoryDB;
+use crate::bus::{MessageBus, OutboundMessage};
+use crate::config::{Config, TenantConfig};
+use crate::gateway::TenantRoute;
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// A tenant whose message bus exists (so the HTTP router can dispatch to it)
+/// but whose agent loop has not been started yet.
+pub(super) struct PendingTenant {
+    id: String,
+    model: Option<String>,
+    config: Config,
+    bus: Arc<MessageBus>,
+    outbound_rx: mpsc::Receiver<OutboundMessage>,
+}
+
+/// Derive the config a tenant's agent runs with: its own workspace (and with
+/// it memory DB and sessions), file tools confined to that workspace, a
+/// sandboxed `exec` without `tmux`, and no channels or account-linked
+/// integrations inherited from the operator.
+pub(super) fn tenant_config(config: &Config, id: &str, tenant: &TenantConfig) -> Config {
+    let mut cfg = config.clone();
+    cfg.agents.defaults.workspace = config
+        .tenant_workspace_path(id, tenant)
+        .to_string_lossy()
+        .into_owned();
+    // Relative to the tenant's workspace these would name the wrong paths
+    cfg.gateway.tenants.clear();
+    cfg.tools.restrict_to_workspace = true;
+    cfg.tools.workspace_only = true;
+    // Both run as the gateway user: unsandboxed they reach the operator's
+    // config and every other tenant's workspace
+    cfg.tools.tmux.enabled = false;
+    cfg.tools.exec.sandbox = crate::config::SandboxConfig {
+        enabled: true,
+        block_network: config.tools.exec.sandbox.block_network,
+        ..crate::config::SandboxConfig::default()
+    };
+    cfg.tools.google = crate::config::GoogleConfig::default();
+    cfg.tools.github = crate::config::GitHubConfig::default();
+    cfg.tools.todoist = crate::config::TodoistConfig::default();
+    cfg.tools.obsidian = crate::config::ObsidianConfig::default();
+    cfg.tools.media = crate::config::MediaConfig::default();
+    cfg.tools.mcp = crate::config::McpConfig::default();
+    cfg.channels = crate::config::ChannelsConfig::default();
+    cfg
+}
+
+/// Create a message bus per configured tenant. Returns the pending tenants and
+/// the routes the HTTP API uses to dispatch authenticated requests to them.
+pub(super) fn prepare_tenants(
+    config: &Config,
+    leak_detector: &Arc<crate::safety::LeakDetector>,
+) -> Result<(Vec<PendingTenant>, Vec<TenantRoute>)> {
+    let mut ids: Vec<&String> = config.gateway.tenants.keys().collect();
+    ids.sort();
+
+    let mut pending = Vec::with_capacity(ids.len());
+    let mut routes = Vec::with_capacity(ids.len());
+    for id in ids {
+        let tenant = &config.gateway.tenants[id];
+        let (inbound_tx, _outbound_tx, outbound_rx, bus) =
+            setup_message_bus_with_detector(leak_detector.clone())?;
+        routes.push(TenantRoute {
+            id: id.clone(),
+            api_key: tenant.api_key.clone(),
+            inbound_tx: Arc::new(inbound_tx),
+        });
+        pending.push(PendingTenant {
+            id: id.clone(),
+            model: tenant.model.clone(),
+            config: tenant_config(config, id, tenant),
+            bus,
+            outbound_rx,
+        });
+    }
+    Ok((pending, routes))
+}
+
+/// Set up and start one agent loop per tenant. Replies are forwarded to the
+/// operator's outbound channel, where the HTTP API matches them to the
+/// waiting request.
+pub(super) async fn start_tenants(
+    pending: Vec<PendingTenant>,
+    outbound_tx: &Arc<mpsc::Sender<OutboundMessage>>,
+    token_store: Arc<MemoryDB>,
+    leak_detector: &Arc<crate::safety::LeakDetector>,
+) -> Result<Vec<JoinHandle<()>>> {
+    let mut tasks = Vec::with_capacity(pending.len() * 2);
+    for tenant in pending {
+        let workspace = tenant.config.workspace_path();
+        crate::utils::ensure_dir(&workspace)
+            .with_context(|| format!("failed to create workspace for tenant '{}'", tenant.id))?;
+        super::create_workspace_templates(&workspace)?;
+
+        let path = workspace.join("memory").join("memory.sqlite3");
+        let memory_db = tokio::task::spawn_blocking(move || {
+            MemoryDB::new(&path)
+                .with_context(|| format!("failed to create MemoryDB at: {}", path.display()))
+        })
+        .await
+        .context("MemoryDB init task panicked")??;
+
+        let (provider, _) = setup_provider(
+            &tenant.config,
+            tenant.model.as_deref(),
+            Some(token_store.clone() as Arc<dyn crate::utils::credential_store::OAuthTokenStore>),
+            None,
+        )?;
+        let agent = setup_agent(
+            SetupAgentParams {
+                bus: tenant.bus,
+                provider,
+                model: tenant.model,
+                outbound_tx: outbound_tx.clone(),
+                cron: None,
+                typing_tx: None,
+                channels_config: None,
+                memory_db: Some(Arc::new(memory_db)),
+                leak_detector: Some(leak_detector.clone()),
+            },
+            &tenant.config,
+        )
+        .await
+        .with_context(|| format!("failed to set up agent for tenant '{}'", tenant.id))?;
+
+        let mut outbound_rx = tenant.outbound_rx;
+        let forward_tx = outbound_tx.clone();
+        let id = tenant.id.clone();
+        tasks.push(tokio::spawn(async move {
+            while let Some(msg) = outbound_rx.recv().await {
+                if forward_tx.send(msg).await.is_err() {
+                    warn!(
+                        "outbound channel closed, dropping reply for tenant '{}'",
+                        id
+                    );
+                    break;
+                }
+            }
+        }));
+        tasks.push(start_agent_loop(agent));
+        info!(
+            "tenant '{}' agent started (workspace: {})",
+            tenant.id,
+            workspace.display()
+        );
+    }
+    Ok(tasks)
+}
@@ -0,0 +1,77 @@
+use super::cli_types::MemoryCommands;
+use crate::agent::memory::MemoryDB;
+use anyhow::Result;
+
+pub(super) fn memory_command(cmd: &MemoryCommands) -> Result<()> {
+    let db_path = crate::utils::get_memory_db_path()?;
+
+    if !db_path.exists() {
+        anyhow::bail!(
+            "memory database not found at {}. Run the agent first to initialize it.",
+            db_path.display()
+        );
+    }
+
+    let db = MemoryDB::new(&db_path)?;
+
+    match cmd {
+        MemoryCommands::Reindex { embeddings } => {
+            let (sources, entries) = db.rebuild_fts_index()?;
+            println!("Reindexed {entries} entries from {sources} sources");
+            if *embeddings {
+                let created = backfill_embeddings(&db)?;
+                println!("Created {created} embeddings");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Embed every entry without a stored embedding using the configured
+/// backend, first dropping embeddings of a different dimension (left behind
+/// by a previous backend or model). Returns how many embeddings were stored.
+#[cfg(feature = "embeddings")]
+fn backfill_embeddings(db: &MemoryDB) -> Result<usize> {
+    use crate::agent::memory::embeddings::{
+        EMBED_BATCH_SIZE, EmbeddingService, backend_from_config, serialize_embedding,
+    };
+    use std::io::Write;
+
+    let config = crate::config::load_config(None)?;
+    let memory = crate::agent::AgentLoopConfig::memory_config(&config);
+    if !memory.embeddings_enabled {
+        println!(
+            "Note: agents.defaults.memory.embeddingsEnabled is false, so search will not use these embeddings"
+        );
+    }
+    let svc = EmbeddingService::new(backend_from_config(&memory)?)?;
+
+    let stale = db.delete_embeddings_not_of_dimension(svc.dimensions())?;
+    if stale > 0 {
+        println!("Dropped {stale} embeddings of a different dimension");
+    }
+
+    let missing = db.get_entries_missing_embeddings()?;
+    let total = missing.len();
+    let mut created = 0;
+    for batch in missing.chunks(EMBED_BATCH_SIZE) {
+        let texts: Vec<&str> = batch.iter().map(|(_, _, c)| c.as_str()).collect();
+        let vectors = svc.embed_texts(&texts)?;
+        for ((id, _, _), vector) in batch.iter().zip(&vectors) {
+            db.store_embedding(*id, &serialize_embedding(vector))?;
+            created += 1;
+        }
+        print!("\rEmbedding entries: {created}/{total}");
+        std::io::stdout().flush()?;
+    }
+    if total > 0 {
+        println!();
+    }
+    Ok(created)
+}
+
+#[cfg(not(feature = "embeddings"))]
+fn backfill_embeddings(_db: &MemoryDB) -> Result<usize> {
+    anyhow::bail!("this build has no embeddings support (enable the `embeddings` feature)")
+}
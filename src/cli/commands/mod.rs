@@ -1,10 +1,18 @@
+mod audit_cmd;
+mod bench_cmd;
 mod channels_cmd;
 mod cli_types;
 mod credentials_cmd;
 mod cron_cmd;
 mod gateway_setup;
+mod gateway_tenants;
+mod memory_cmd;
 mod onboard;
+mod quiet_hours;
+mod send_rate;
+mod session_cmd;
 mod stats_cmd;
+mod stream_edit;
 mod subcommands;
 
 #[cfg(test)]
@@ -31,8 +39,13 @@ pub async fn run() -> Result<()> {
                 Box::pin(gateway_setup::gateway(model)).await?;
             }
         }
-        Commands::Agent { message, session } => {
-            Box::pin(subcommands::agent(message, session)).await?;
+        Commands::Agent {
+            message,
+            session,
+            focus,
+            dry_run,
+        } => {
+            Box::pin(subcommands::agent(message, session, focus, dry_run)).await?;
         }
         Commands::Cron { cmd } => {
             cron_cmd::cron_command(cmd)?;
@@ -43,11 +56,11 @@ pub async fn run() -> Result<()> {
         Commands::Channels { cmd } => {
             channels_cmd::channels_command(cmd).await?;
         }
-        Commands::Status => {
-            subcommands::status_command()?;
+        Commands::Status { json } => {
+            subcommands::status_command(json)?;
         }
-        Commands::Doctor => {
-            crate::cli::doctor::doctor_command().await?;
+        Commands::Doctor { json } => {
+            crate::cli::doctor::doctor_command(json).await?;
         }
         Commands::Pairing { cmd } => {
             subcommands::pairing_command(cmd)?;
@@ -58,6 +71,22 @@ pub async fn run() -> Result<()> {
         Commands::Stats { ref cmd } => {
             stats_cmd::stats_command(cmd)?;
         }
+        Commands::Audit { ref cmd } => {
+            audit_cmd::audit_command(cmd)?;
+        }
+        Commands::Memory { ref cmd } => {
+            memory_cmd::memory_command(cmd)?;
+        }
+        Commands::Session { cmd } => {
+            session_cmd::session_command(cmd).await?;
+        }
+        Commands::Bench {
+            provider,
+            model,
+            runs,
+        } => {
+            bench_cmd::bench_command(provider.as_deref(), model.as_deref(), runs).await?;
+        }
         Commands::Completion { shell } => {
             clap_complete::generate(
                 shell,
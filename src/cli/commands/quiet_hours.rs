@@ -0,0 +1,117 @@
+use crate::agent::memory::memory_db::MemoryDB;
+use crate::bus::OutboundMessage;
+use crate::config::QuietHoursConfig;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Holds background messages sent during quiet hours
+/// (`channels.quietHours`) in the memory database and releases them once
+/// quiet hours end, so a restart in between loses nothing.
+pub(super) struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+    tz: Tz,
+    channels: Vec<String>,
+    db: Arc<MemoryDB>,
+}
+
+impl QuietHours {
+    /// `None` when quiet hours are disabled.
+    pub(super) fn new(config: &QuietHoursConfig, db: Arc<MemoryDB>) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let (start, end) = config
+            .times()
+            .context("channels.quietHours.start and end must be HH:MM times")?;
+        let tz = match config
+            .timezone
+            .clone()
+            .or_else(crate::cron::service::detect_system_timezone)
+        {
+            Some(name) => name
+                .parse()
+                .map_err(|_| anyhow::anyhow!("unknown quiet hours timezone '{name}'"))?,
+            None => Tz::UTC,
+        };
+        Ok(Some(Self {
+            start,
+            end,
+            tz,
+            channels: config.channels.clone(),
+            db,
+        }))
+    }
+
+    /// Whether `now` falls within quiet hours. A period whose end is before
+    /// its start spans midnight.
+    pub(super) fn is_quiet(&self, now: DateTime<Utc>) -> bool {
+        let time = now.with_timezone(&self.tz).time();
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Store `msg` for later if it is a background message for a covered
+    /// channel and it is quiet time. Returns whether it was held.
+    pub(super) fn hold(&self, msg: &OutboundMessage, now: DateTime<Utc>) -> bool {
+        let background = msg
+            .metadata
+            .get(crate::bus::meta::BACKGROUND)
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or_default();
+        if !background
+            || !(self.channels.is_empty() || self.channels.contains(&msg.channel))
+            || !self.is_quiet(now)
+        {
+            return false;
+        }
+        let stored = serde_json::to_string(msg)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| self.db.enqueue_quiet_hours_message(&msg.channel, &json));
+        match stored {
+            Ok(_) => {
+                info!(
+                    "quiet hours: holding message for {}:{}",
+                    msg.channel, msg.chat_id
+                );
+                true
+            }
+            Err(e) => {
+                warn!("quiet hours: failed to hold message, sending now: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Held messages, once quiet hours are over.
+    pub(super) fn release_due(&self, now: DateTime<Utc>) -> Vec<OutboundMessage> {
+        if self.is_quiet(now) {
+            return Vec::new();
+        }
+        let held = match self.db.drain_quiet_hours_messages() {
+            Ok(held) => held,
+            Err(e) => {
+                warn!("quiet hours: failed to read held messages: {}", e);
+                return Vec::new();
+            }
+        };
+        if !held.is_empty() {
+            info!("quiet hours over: releasing {} held message(s)", held.len());
+        }
+        held.iter()
+            .filter_map(|json| match serde_json::from_str(json) {
+                Ok(msg) => Some(msg),
+                Err(e) => {
+                    warn!("quiet hours: dropping unreadable held message: {}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,103 @@
+use crate::bus::OutboundMessage;
+use crate::config::ChannelRateLimitConfig;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Chats tracked before idle buckets are evicted.
+const MAX_TRACKED_CHATS: usize = 1000;
+
+struct Bucket {
+    /// May go negative: sends already scheduled beyond the allowance.
+    tokens: f64,
+    refilled: Instant,
+}
+
+/// Token bucket per `(channel, chat_id)` pacing outbound sends
+/// (`channels.rateLimit`).
+pub(super) struct SendRateLimiter {
+    /// Tokens regained per second.
+    rate: f64,
+    burst: f64,
+    buckets: HashMap<(String, String), Bucket>,
+}
+
+impl SendRateLimiter {
+    /// `None` when pacing is disabled.
+    pub(super) fn new(config: ChannelRateLimitConfig) -> Option<Self> {
+        (config.messages_per_minute > 0).then(|| Self {
+            rate: f64::from(config.messages_per_minute) / 60.0,
+            burst: f64::from(config.burst.max(1)),
+            buckets: HashMap::new(),
+        })
+    }
+
+    /// Take a token for one send to `key` and return how long to wait
+    /// before sending.
+    pub(super) fn reserve(&mut self, key: &(String, String), now: Instant) -> Duration {
+        if self.buckets.len() >= MAX_TRACKED_CHATS && !self.buckets.contains_key(key) {
+            self.evict_idle(now);
+        }
+        let bucket = self.buckets.entry(key.clone()).or_insert(Bucket {
+            tokens: self.burst,
+            refilled: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.refilled).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.refilled = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        }
+    }
+
+    /// Drop buckets that have refilled completely; a new bucket starts full,
+    /// so forgetting them changes nothing. Falls back to clearing everything
+    /// if every tracked chat is still busy.
+    fn evict_idle(&mut self, now: Instant) {
+        let (rate, burst) = (self.rate, self.burst);
+        self.buckets.retain(|_, b| {
+            let elapsed = now.saturating_duration_since(b.refilled).as_secs_f64();
+            b.tokens + elapsed * rate < burst
+        });
+        if self.buckets.len() >= MAX_TRACKED_CHATS {
+            self.buckets.clear();
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn tracked(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+/// Messages waiting for their chat's send slot. The outbound loop keeps
+/// serving other chats and picks these up as they fall due.
+#[derive(Default)]
+pub(super) struct PacedSends {
+    /// In the order they were paced; a chat's deadlines only grow, so this
+    /// keeps each chat's messages in order.
+    waiting: Vec<(tokio::time::Instant, OutboundMessage)>,
+}
+
+impl PacedSends {
+    pub(super) fn push(&mut self, due: tokio::time::Instant, msg: OutboundMessage) {
+        self.waiting.push((due, msg));
+    }
+
+    /// Earliest deadline of a waiting message.
+    pub(super) fn next_due(&self) -> Option<tokio::time::Instant> {
+        self.waiting.iter().map(|(due, _)| *due).min()
+    }
+
+    /// Remove and return the messages due by `now`, in the order they were
+    /// paced.
+    pub(super) fn take_due(&mut self, now: tokio::time::Instant) -> Vec<OutboundMessage> {
+        let (due, waiting) = std::mem::take(&mut self.waiting)
+            .into_iter()
+            .partition(|(at, _)| *at <= now);
+        self.waiting = waiting;
+        due.into_iter().map(|(_, msg)| msg).collect()
+    }
+}
@@ -0,0 +1,124 @@
+use super::cli_types::SessionCommands;
+use crate::agent::memory::MemoryDB;
+use crate::agent::profile::{DEFAULT_PROFILE, ProfileResolver};
+use crate::config::{Config, load_config};
+use crate::session::{Session, SessionManager, SessionStore};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub(super) async fn session_command(cmd: SessionCommands) -> Result<()> {
+    let config = load_config(None)?;
+
+    match cmd {
+        SessionCommands::Export { key, out, profile } => {
+            let store = open_store(&config, &key, profile.as_deref())?;
+            let json = export_session(&store, &key).await?;
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, json)
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                    println!("Exported session {key} to {}", path.display());
+                }
+                None => println!("{json}"),
+            }
+        }
+        SessionCommands::Import {
+            file,
+            force,
+            profile,
+        } => {
+            let session = read_session_file(&file)?;
+            let store = open_store(&config, &session.key, profile.as_deref())?;
+            let session = import_session(&store, session, force).await?;
+            println!(
+                "Imported session {} ({} messages)",
+                session.key,
+                session.messages.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Session store of the profile that owns `key`, or of `profile` if named.
+fn open_store(config: &Config, key: &str, profile: Option<&str>) -> Result<SessionManager> {
+    let db_path = session_db_path(config, key, profile)?;
+    if !db_path.exists() {
+        anyhow::bail!(
+            "memory database not found at {}. Run the agent first to initialize it.",
+            db_path.display()
+        );
+    }
+    Ok(SessionManager::with_db(Arc::new(MemoryDB::new(&db_path)?)))
+}
+
+/// Memory database holding the session `key`: that of the profile named
+/// `profile`, else of the first `agents.profiles` entry matching the key's
+/// channel and chat (as the agent routes it), else the default workspace's.
+pub(super) fn session_db_path(
+    config: &Config,
+    key: &str,
+    profile: Option<&str>,
+) -> Result<PathBuf> {
+    let base = config.workspace_path();
+    let profiles = &config.agents.profiles;
+    let selected = match profile {
+        Some(DEFAULT_PROFILE) => None,
+        Some(name) => Some(
+            profiles
+                .iter()
+                .find(|p| p.name == name)
+                .with_context(|| format!("no profile named '{name}' in agents.profiles"))?,
+        ),
+        None => {
+            let (channel, chat_id) = key.split_once(':').unwrap_or((key, ""));
+            profiles.iter().find(|p| p.matches(channel, chat_id))
+        }
+    };
+    let workspace = match selected {
+        Some(p) => ProfileResolver::workspace_for(p, &base),
+        None => base,
+    };
+    Ok(workspace.join("memory").join("memory.sqlite3"))
+}
+
+/// A session that has neither history nor metadata has never been saved.
+fn is_empty(session: &Session) -> bool {
+    session.messages.is_empty() && session.metadata.is_empty()
+}
+
+/// The session stored under `key` as pretty-printed JSON.
+pub(super) async fn export_session(store: &dyn SessionStore, key: &str) -> Result<String> {
+    let session = store.get_or_create(key).await?;
+    if is_empty(&session) {
+        anyhow::bail!("no session with key '{key}'");
+    }
+    Ok(serde_json::to_string_pretty(&session)?)
+}
+
+/// Parse a file written by `session export`.
+pub(super) fn read_session_file(file: &Path) -> Result<Session> {
+    let data = std::fs::read_to_string(file)
+        .with_context(|| format!("failed to read {}", file.display()))?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("{} is not an exported session", file.display()))
+}
+
+/// Save `session` under the key it was exported with. Refuses to replace an
+/// existing session unless `force` is set.
+pub(super) async fn import_session(
+    store: &dyn SessionStore,
+    session: Session,
+    force: bool,
+) -> Result<Session> {
+    if !force && !is_empty(&store.get_or_create(&session.key).await?) {
+        anyhow::bail!(
+            "session '{}' already exists; use --force to replace it",
+            session.key
+        );
+    }
+    store.save(&session).await?;
+    Ok(session)
+}
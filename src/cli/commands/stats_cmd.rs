@@ -14,15 +14,97 @@ pub(super) fn stats_command(cmd: &StatsCommands) -> Result<()> {
     let db = crate::agent::memory::MemoryDB::new(&db_path)?;
 
     match cmd {
-        StatsCommands::Tokens { days } => {
-            let since = (chrono::Utc::now().date_naive()
-                - chrono::Duration::days(i64::from(*days)))
-            .format("%Y-%m-%d")
-            .to_string();
-            let summary = db.get_token_summary(&since)?;
+        StatsCommands::Tokens {
+            session: Some(session),
+            limit,
+            ..
+        } => {
+            let rows = db.get_token_breakdowns(session, *limit)?;
+            if rows.is_empty() {
+                println!(
+                    "No token breakdowns recorded for session {session}. Enable agents.defaults.tokenBreakdown to record them."
+                );
+                return Ok(());
+            }
+
+            println!("Estimated input tokens per turn for {session} (newest first)");
+            println!(
+                "{:<20} {:<24} {:>5} {:>8} {:>8} {:>8} {:>8} {:>8} {:>9} {:>9} {:>9}",
+                "Time",
+                "Model",
+                "Calls",
+                "System",
+                "Tools",
+                "History",
+                "Message",
+                "Results",
+                "Assistant",
+                "Total",
+                "Reported"
+            );
+            println!("{}", "\u{2500}".repeat(127));
+
+            let mut combined = crate::agent::memory::memory_db::TokenBreakdown::default();
+            for row in &rows {
+                let b = &row.breakdown;
+                println!(
+                    "{:<20} {:<24} {:>5} {:>8} {:>8} {:>8} {:>8} {:>8} {:>9} {:>9} {:>9}",
+                    row.timestamp,
+                    row.model,
+                    b.calls,
+                    b.system,
+                    b.tool_definitions,
+                    b.history,
+                    b.message,
+                    b.tool_results,
+                    b.assistant,
+                    b.estimated_total(),
+                    b.provider_input,
+                );
+                combined.system += b.system;
+                combined.tool_definitions += b.tool_definitions;
+                combined.history += b.history;
+                combined.message += b.message;
+                combined.tool_results += b.tool_results;
+                combined.assistant += b.assistant;
+            }
+
+            println!("{}", "\u{2500}".repeat(127));
+            let total = combined.estimated_total();
+            println!(
+                "Share of {total} estimated tokens across {} turns:",
+                rows.len()
+            );
+            for (label, tokens) in combined.categories() {
+                let pct = if total == 0 {
+                    0.0
+                } else {
+                    tokens as f64 / total as f64 * 100.0
+                };
+                println!(
+                    "  {:<18} {:>10} ({:>5.1}%)",
+                    format!("{label}:"),
+                    tokens,
+                    pct
+                );
+            }
+        }
+        StatsCommands::Tokens {
+            days, since, until, ..
+        } => {
+            let (since, until) = token_window(
+                *days,
+                since.as_deref(),
+                until.as_deref(),
+                chrono::Utc::now().date_naive(),
+            )?;
+            let summary = db.get_token_summary_range(
+                &since.format("%Y-%m-%d").to_string(),
+                &until.format("%Y-%m-%d").to_string(),
+            )?;
 
             if summary.is_empty() {
-                println!("No token usage data in the last {days} days.");
+                println!("No token usage data from {since} to {until}.");
                 return Ok(());
             }
 
@@ -136,7 +218,92 @@ pub(super) fn stats_command(cmd: &StatsCommands) -> Result<()> {
                 }
             }
         }
+        StatsCommands::Empty { days } => {
+            let since = (chrono::Utc::now().date_naive()
+                - chrono::Duration::days(i64::from(*days)))
+            .format("%Y-%m-%d")
+            .to_string();
+            let stats = db.get_empty_response_stats(&since)?;
+
+            if stats.is_empty() {
+                println!("No exhausted empty responses in the last {days} days.");
+                return Ok(());
+            }
+
+            println!("Empty Responses (last {days} days)");
+            println!("{:<40} {:<12} {:>6}  Last Seen", "Model", "Action", "Count");
+            println!("{}", "\u{2500}".repeat(80));
+            for row in &stats {
+                println!(
+                    "{:<40} {:<12} {:>6}  {}",
+                    row.model, row.action, row.count, row.last_seen
+                );
+            }
+            let total: u64 = stats.iter().map(|r| r.count).sum();
+            println!("{}", "\u{2500}".repeat(80));
+            println!("Total: {total}");
+        }
+        StatsCommands::Tools { days } => {
+            let since = (chrono::Utc::now().date_naive()
+                - chrono::Duration::days(i64::from(*days)))
+            .format("%Y-%m-%d")
+            .to_string();
+            let stats = db.get_tool_audit_stats(&since)?;
+
+            if stats.is_empty() {
+                println!("No tool calls recorded in the last {days} days.");
+                return Ok(());
+            }
+
+            println!("Tool Calls (last {days} days)");
+            println!(
+                "{:<32} {:>7} {:>8} {:>8} {:>10}",
+                "Tool", "Calls", "Failed", "Fail %", "Avg ms"
+            );
+            println!("{}", "\u{2500}".repeat(69));
+            for row in &stats {
+                println!(
+                    "{:<32} {:>7} {:>8} {:>7.1}% {:>10.0}",
+                    row.tool_name,
+                    row.calls,
+                    row.failures,
+                    row.failure_rate() * 100.0,
+                    row.avg_duration_ms
+                );
+            }
+            let calls: u64 = stats.iter().map(|r| r.calls).sum();
+            let failures: u64 = stats.iter().map(|r| r.failures).sum();
+            println!("{}", "\u{2500}".repeat(69));
+            println!("Total: {calls} calls, {failures} failed");
+        }
     }
 
     Ok(())
 }
+
+/// Resolve the `stats tokens` date window (inclusive). Explicit `--since` /
+/// `--until` dates win over `--days`, which counts back from `--until` (or
+/// today).
+pub(super) fn token_window(
+    days: u32,
+    since: Option<&str>,
+    until: Option<&str>,
+    today: chrono::NaiveDate,
+) -> Result<(chrono::NaiveDate, chrono::NaiveDate)> {
+    let parse = |flag: &str, value: &str| {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| anyhow::anyhow!("invalid {flag} date '{value}', expected YYYY-MM-DD"))
+    };
+    let until = until
+        .map(|u| parse("--until", u))
+        .transpose()?
+        .unwrap_or(today);
+    let since = match since {
+        Some(s) => parse("--since", s)?,
+        None => until - chrono::Duration::days(i64::from(days)),
+    };
+    if since > until {
+        anyhow::bail!("--since {since} is after --until {until}");
+    }
+    Ok((since, until))
+}
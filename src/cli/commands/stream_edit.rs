@@ -0,0 +1,101 @@
+use crate::bus::{OutboundMessage, meta};
+use crate::channels::manager::ChannelManager;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Streams tracked before the map is cleared.
+const MAX_TRACKED_STREAMS: usize = 1000;
+/// Longest text shown by editing; longer replies are sent normally once
+/// finished, since an edit cannot be split across messages.
+const MAX_EDIT_LEN: usize = 3500;
+
+/// Messages showing streamed reply text (`agents.defaults.streaming`), by
+/// stream ID.
+#[derive(Default)]
+pub(super) struct StreamedReplies {
+    message_ids: HashMap<String, String>,
+}
+
+fn stream_id(msg: &OutboundMessage) -> Option<&str> {
+    msg.metadata
+        .get(meta::STREAM_ID)
+        .and_then(serde_json::Value::as_str)
+}
+
+impl StreamedReplies {
+    /// Show the partial text in `msg`: send it once, then edit that message.
+    /// Empty text removes it. Channels that cannot edit wait for the reply.
+    pub(super) async fn show_partial(&mut self, channels: &ChannelManager, msg: &OutboundMessage) {
+        let Some(id) = stream_id(msg) else {
+            return;
+        };
+        if !channels.supports_streaming(&msg.channel) {
+            return;
+        }
+        if msg.content.is_empty() {
+            if let Some(message_id) = self.message_ids.remove(id)
+                && let Err(e) = channels
+                    .delete_message(&msg.channel, &msg.chat_id, &message_id)
+                    .await
+            {
+                debug!("failed to remove streamed text: {}", e);
+            }
+            return;
+        }
+        if msg.content.len() > MAX_EDIT_LEN {
+            return;
+        }
+        if let Some(message_id) = self.message_ids.get(id) {
+            if let Err(e) = channels
+                .edit_message(&msg.channel, &msg.chat_id, message_id, &msg.content)
+                .await
+            {
+                debug!("streamed text edit failed: {}", e);
+            }
+            return;
+        }
+        if self.message_ids.len() >= MAX_TRACKED_STREAMS {
+            self.message_ids.clear();
+        }
+        match channels.send_and_get_id(msg).await {
+            Ok(Some(message_id)) => {
+                self.message_ids.insert(id.to_string(), message_id);
+            }
+            Ok(None) => {}
+            Err(e) => debug!("streamed text send failed: {}", e),
+        }
+    }
+
+    /// Deliver a finished reply by editing the message showing its streamed
+    /// text. Returns false when the reply still has to be sent; the streamed
+    /// message is removed then.
+    pub(super) async fn finish(
+        &mut self,
+        channels: &ChannelManager,
+        msg: &OutboundMessage,
+    ) -> bool {
+        let Some(message_id) = stream_id(msg).and_then(|id| self.message_ids.remove(id)) else {
+            return false;
+        };
+        // Attachments and buttons only come with a new message
+        if msg.media.is_empty()
+            && !msg.metadata.contains_key(meta::BUTTONS)
+            && msg.content.len() <= MAX_EDIT_LEN
+        {
+            match channels
+                .edit_message(&msg.channel, &msg.chat_id, &message_id, &msg.content)
+                .await
+            {
+                Ok(()) => return true,
+                Err(e) => debug!("final streamed edit failed, sending instead: {}", e),
+            }
+        }
+        if let Err(e) = channels
+            .delete_message(&msg.channel, &msg.chat_id, &message_id)
+            .await
+        {
+            debug!("failed to remove streamed text: {}", e);
+        }
+        false
+    }
+}
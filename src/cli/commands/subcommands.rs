@@ -1,12 +1,18 @@
 use super::cli_types::{AuthCommands, PairingCommands};
 use super::gateway_setup::{SetupAgentParams, setup_agent};
-use crate::agent::AgentLoop;
+use crate::agent::{AgentLoop, AgentRunOverrides};
 use crate::bus::MessageBus;
 use crate::config::load_config;
 use anyhow::Result;
 use std::sync::Arc;
+use tracing::warn;
 
-pub(super) async fn agent(message: Option<String>, session: String) -> Result<()> {
+pub(super) async fn agent(
+    message: Option<String>,
+    session: String,
+    focus: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
     let config = load_config(None)?;
     crate::observability::init_metrics_exporter(&config);
     config.validate()?;
@@ -43,19 +49,35 @@ pub(super) async fn agent(message: Option<String>, session: String) -> Result<()
     )
     .await?;
 
+    if let Some(ref preset) = focus {
+        agent.set_session_focus(&session, Some(preset)).await?;
+    }
+
+    let overrides = AgentRunOverrides {
+        dry_run,
+        ..Default::default()
+    };
+    if dry_run {
+        println!("\u{1f916} Dry run: tool calls are logged, not executed\n");
+    }
+
     if let Some(msg) = message {
         let response = agent
-            .process_direct(&msg, &session, "cli", "direct")
+            .process_direct_with_overrides(&msg, &session, "cli", "direct", &overrides)
             .await?;
-        println!("\u{1f916} {response}");
+        println!("\u{1f916} {}", response.content);
     } else {
-        interactive_repl(&agent, &session).await?;
+        interactive_repl(&agent, &session, &overrides).await?;
     }
 
     Ok(())
 }
 
-async fn interactive_repl(agent: &AgentLoop, session: &str) -> Result<()> {
+async fn interactive_repl(
+    agent: &AgentLoop,
+    session: &str,
+    overrides: &AgentRunOverrides,
+) -> Result<()> {
     use std::io::{self, BufRead, Write};
 
     println!("\u{1f916} Interactive mode (Ctrl+C to exit)\n");
@@ -73,9 +95,9 @@ async fn interactive_repl(agent: &AgentLoop, session: &str) -> Result<()> {
         }
 
         let response = agent
-            .process_direct(input, session, "cli", "direct")
+            .process_direct_with_overrides(input, session, "cli", "direct", overrides)
             .await?;
-        println!("\n\u{1f916} {response}\n");
+        println!("\n\u{1f916} {}\n", response.content);
     }
 }
 
@@ -143,9 +165,14 @@ pub(super) async fn auth_command(cmd: AuthCommands) -> Result<()> {
     Ok(())
 }
 
-pub(super) fn status_command() -> Result<()> {
+pub(super) fn status_command(json: bool) -> Result<()> {
     let config = load_config(None)?;
     let config_path = crate::config::get_config_path()?;
+    if json {
+        let status = status_json(&config, &config_path);
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
     let workspace = config.workspace_path();
 
     println!("\u{1f916} oxicrab Status\n");
@@ -285,6 +312,51 @@ pub(super) fn status_command() -> Result<()> {
     Ok(())
 }
 
+/// Machine-readable status for `oxicrab status --json`. The memory database
+/// is only opened if it already exists; otherwise its fields are null.
+pub(super) fn status_json(
+    config: &crate::config::Config,
+    config_path: &std::path::Path,
+) -> serde_json::Value {
+    let workspace = config.workspace_path();
+    let model = &config.agents.defaults.model_routing.default;
+    let model_ref = crate::config::parse_model_ref(config.models.resolve(model));
+    let provider = model_ref.provider.map_or_else(
+        || crate::config::infer_provider_from_model(model_ref.model).map(str::to_string),
+        |p| Some(crate::config::normalize_provider(p).into_owned()),
+    );
+
+    let db_path = workspace.join("memory").join("memory.sqlite3");
+    let db = if db_path.exists() {
+        crate::agent::memory::MemoryDB::new(&db_path)
+            .map_err(|e| warn!("status: cannot open memory database: {e}"))
+            .ok()
+    } else {
+        None
+    };
+    let db_size = std::fs::metadata(&db_path).ok().map(|m| m.len());
+    let cron_jobs = db
+        .as_ref()
+        .and_then(|db| db.list_cron_jobs(true).ok())
+        .map(|jobs| jobs.len());
+
+    serde_json::json!({
+        "configPath": config_path,
+        "configExists": config_path.exists(),
+        "workspacePath": workspace,
+        "workspaceExists": workspace.exists(),
+        "channels": config.channels.enabled_names(),
+        "provider": provider,
+        "model": model,
+        "memoryDb": {
+            "path": db_path,
+            "sizeBytes": db_size,
+            "fts": db.as_ref().map(crate::agent::memory::MemoryDB::has_fts),
+        },
+        "cronJobs": cron_jobs,
+    })
+}
+
 pub(super) fn pairing_command(cmd: PairingCommands) -> Result<()> {
     let store = crate::pairing::PairingStore::open_default()?;
 
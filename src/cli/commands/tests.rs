@@ -1,6 +1,14 @@
+use super::bench_cmd::{BenchSamples, bench_model_ref, format_row, percentile};
 use super::cli_types::{Cli, Commands};
 use super::create_workspace_templates;
-use super::gateway_setup::{gateway_host_is_public, warn_if_public_gateway_without_auth};
+use super::cron_cmd::parse_cron_targets;
+use super::gateway_setup::{
+    admin_pairing_buttons, format_admin_pairing_notice, gateway_host_is_public,
+    resolve_pairing_code, warn_if_public_gateway_without_auth,
+};
+use super::gateway_tenants::tenant_config;
+use super::quiet_hours::QuietHours;
+use super::send_rate::{PacedSends, SendRateLimiter};
 use crate::config::Config;
 use clap::Parser;
 
@@ -49,7 +57,9 @@ fn test_cli_parse_gateway_echo() {
 fn test_cli_parse_agent_with_message() {
     let cli = Cli::try_parse_from(["oxicrab", "agent", "-m", "hello"]).unwrap();
     match cli.command {
-        Commands::Agent { message, session } => {
+        Commands::Agent {
+            message, session, ..
+        } => {
             assert_eq!(message.as_deref(), Some("hello"));
             assert_eq!(session, "cli:default");
         }
@@ -68,28 +78,92 @@ fn test_cli_parse_agent_with_session() {
     }
 }
 
+#[test]
+fn test_cli_parse_agent_with_focus() {
+    let cli = Cli::try_parse_from(["oxicrab", "agent", "--focus", "research"]).unwrap();
+    match cli.command {
+        Commands::Agent { focus, .. } => {
+            assert_eq!(focus.as_deref(), Some("research"));
+        }
+        _ => panic!("expected Agent"),
+    }
+}
+
 #[test]
 fn test_cli_parse_agent_default_session() {
     let cli = Cli::try_parse_from(["oxicrab", "agent"]).unwrap();
     match cli.command {
-        Commands::Agent { message, session } => {
+        Commands::Agent {
+            message,
+            session,
+            focus,
+            dry_run,
+        } => {
             assert!(message.is_none());
             assert_eq!(session, "cli:default");
+            assert!(focus.is_none());
+            assert!(!dry_run);
         }
         _ => panic!("expected Agent"),
     }
 }
 
+#[test]
+fn test_cli_parse_agent_dry_run() {
+    let cli = Cli::try_parse_from(["oxicrab", "agent", "--dry-run", "-m", "hello"]).unwrap();
+    match cli.command {
+        Commands::Agent { dry_run, .. } => assert!(dry_run),
+        _ => panic!("expected Agent"),
+    }
+}
+
 #[test]
 fn test_cli_parse_doctor() {
     let cli = Cli::try_parse_from(["oxicrab", "doctor"]).unwrap();
-    assert!(matches!(cli.command, Commands::Doctor));
+    assert!(matches!(cli.command, Commands::Doctor { json: false }));
+    let cli = Cli::try_parse_from(["oxicrab", "doctor", "--json"]).unwrap();
+    assert!(matches!(cli.command, Commands::Doctor { json: true }));
+}
+
+#[test]
+fn test_cli_parse_session() {
+    let cli = Cli::try_parse_from(["oxicrab", "session", "export", "telegram:42"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Session {
+            cmd: super::cli_types::SessionCommands::Export { ref key, out: None, profile: None }
+        } if key == "telegram:42"
+    ));
+    let cli = Cli::try_parse_from([
+        "oxicrab",
+        "session",
+        "export",
+        "slack:C042",
+        "--profile",
+        "work",
+    ])
+    .unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Session {
+            cmd: super::cli_types::SessionCommands::Export { profile: Some(ref p), .. }
+        } if p == "work"
+    ));
+    let cli = Cli::try_parse_from(["oxicrab", "session", "import", "s.json", "--force"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Session {
+            cmd: super::cli_types::SessionCommands::Import { force: true, .. }
+        }
+    ));
 }
 
 #[test]
 fn test_cli_parse_status() {
     let cli = Cli::try_parse_from(["oxicrab", "status"]).unwrap();
-    assert!(matches!(cli.command, Commands::Status));
+    assert!(matches!(cli.command, Commands::Status { json: false }));
+    let cli = Cli::try_parse_from(["oxicrab", "status", "--json"]).unwrap();
+    assert!(matches!(cli.command, Commands::Status { json: true }));
 }
 
 #[test]
@@ -104,7 +178,11 @@ fn test_cli_parse_stats_tokens() {
         Commands::Stats { cmd } => {
             assert!(matches!(
                 cmd,
-                super::cli_types::StatsCommands::Tokens { days: 7 }
+                super::cli_types::StatsCommands::Tokens {
+                    days: 7,
+                    session: None,
+                    ..
+                }
             ));
         }
         _ => panic!("expected Stats"),
@@ -118,13 +196,169 @@ fn test_cli_parse_stats_tokens_custom_days() {
         Commands::Stats { cmd } => {
             assert!(matches!(
                 cmd,
-                super::cli_types::StatsCommands::Tokens { days: 30 }
+                super::cli_types::StatsCommands::Tokens {
+                    days: 30,
+                    session: None,
+                    ..
+                }
             ));
         }
         _ => panic!("expected Stats"),
     }
 }
 
+#[test]
+fn test_cli_parse_stats_tokens_session() {
+    let cli = Cli::try_parse_from([
+        "oxicrab",
+        "stats",
+        "tokens",
+        "--session",
+        "telegram:42",
+        "-n",
+        "5",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Stats { cmd } => {
+            let super::cli_types::StatsCommands::Tokens { session, limit, .. } = cmd else {
+                panic!("expected Tokens");
+            };
+            assert_eq!(session.as_deref(), Some("telegram:42"));
+            assert_eq!(limit, 5);
+        }
+        _ => panic!("expected Stats"),
+    }
+}
+
+#[test]
+fn test_cli_parse_stats_tokens_date_window() {
+    let cli = Cli::try_parse_from([
+        "oxicrab",
+        "stats",
+        "tokens",
+        "--since",
+        "2026-09-01",
+        "--until",
+        "2026-09-30",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Stats { cmd } => {
+            let super::cli_types::StatsCommands::Tokens { since, until, .. } = cmd else {
+                panic!("expected Tokens");
+            };
+            assert_eq!(since.as_deref(), Some("2026-09-01"));
+            assert_eq!(until.as_deref(), Some("2026-09-30"));
+        }
+        _ => panic!("expected Stats"),
+    }
+}
+
+#[test]
+fn test_token_window() {
+    use super::stats_cmd::token_window;
+    let date = |s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+    let today = date("2026-10-17");
+
+    assert_eq!(
+        token_window(7, None, None, today).unwrap(),
+        (date("2026-10-10"), today)
+    );
+    // Explicit dates win over --days
+    assert_eq!(
+        token_window(7, Some("2026-09-01"), Some("2026-09-30"), today).unwrap(),
+        (date("2026-09-01"), date("2026-09-30"))
+    );
+    // --days counts back from --until
+    assert_eq!(
+        token_window(3, None, Some("2026-09-30"), today).unwrap(),
+        (date("2026-09-27"), date("2026-09-30"))
+    );
+    assert!(token_window(7, Some("2026-09-31"), None, today).is_err());
+    assert!(token_window(7, Some("2026-10-01"), Some("2026-09-30"), today).is_err());
+}
+
+#[test]
+fn test_cli_parse_audit_tools() {
+    let cli = Cli::try_parse_from(["oxicrab", "audit", "tools"]).unwrap();
+    match cli.command {
+        Commands::Audit { cmd } => {
+            let super::cli_types::AuditCommands::Tools { since, limit } = cmd else {
+                panic!("expected Tools");
+            };
+            assert_eq!(since, "24h");
+            assert_eq!(limit, 50);
+        }
+        _ => panic!("expected Audit"),
+    }
+
+    let cli = Cli::try_parse_from(["oxicrab", "audit", "tools", "--since", "7d"]).unwrap();
+    match cli.command {
+        Commands::Audit { cmd } => {
+            let super::cli_types::AuditCommands::Tools { since, .. } = cmd else {
+                panic!("expected Tools");
+            };
+            assert_eq!(since, "7d");
+        }
+        _ => panic!("expected Audit"),
+    }
+}
+
+#[test]
+fn test_cli_parse_audit_outbound() {
+    let cli = Cli::try_parse_from(["oxicrab", "audit", "outbound", "-n", "10"]).unwrap();
+    match cli.command {
+        Commands::Audit {
+            cmd: super::cli_types::AuditCommands::Outbound { since, limit },
+        } => {
+            assert_eq!(since, "7d");
+            assert_eq!(limit, 10);
+        }
+        _ => panic!("expected Audit Outbound"),
+    }
+}
+
+#[test]
+fn test_cli_parse_memory_reindex() {
+    let cli = Cli::try_parse_from(["oxicrab", "memory", "reindex"]).unwrap();
+    match cli.command {
+        Commands::Memory {
+            cmd: super::cli_types::MemoryCommands::Reindex { embeddings },
+        } => assert!(!embeddings),
+        _ => panic!("expected Memory Reindex"),
+    }
+
+    let cli = Cli::try_parse_from(["oxicrab", "memory", "reindex", "--embeddings"]).unwrap();
+    match cli.command {
+        Commands::Memory {
+            cmd: super::cli_types::MemoryCommands::Reindex { embeddings },
+        } => assert!(embeddings),
+        _ => panic!("expected Memory Reindex"),
+    }
+}
+
+#[test]
+fn test_audit_parse_since() {
+    use super::audit_cmd::parse_since;
+    let now = chrono::DateTime::parse_from_rfc3339("2026-03-10T12:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    assert_eq!(parse_since("24h", now).unwrap(), "2026-03-09 12:00:00");
+    assert_eq!(parse_since("7d", now).unwrap(), "2026-03-03 12:00:00");
+    assert_eq!(parse_since("30m", now).unwrap(), "2026-03-10 11:30:00");
+    assert_eq!(
+        parse_since("2026-01-31", now).unwrap(),
+        "2026-01-31 00:00:00"
+    );
+    assert_eq!(
+        parse_since("2026-01-31 14:05:00", now).unwrap(),
+        "2026-01-31 14:05:00"
+    );
+    assert!(parse_since("yesterday", now).is_err());
+    assert!(parse_since("5w", now).is_err());
+}
+
 #[test]
 fn test_cli_parse_credentials_list() {
     let cli = Cli::try_parse_from(["oxicrab", "credentials", "list"]).unwrap();
@@ -211,6 +445,117 @@ fn test_cli_parse_cron_remove() {
     }
 }
 
+#[test]
+fn test_cli_parse_cron_add_repeated_to() {
+    let cli = Cli::try_parse_from([
+        "oxicrab",
+        "cron",
+        "add",
+        "--name",
+        "briefing",
+        "--message",
+        "Morning briefing",
+        "--cron",
+        "0 8 * * *",
+        "--to",
+        "slack:C123",
+        "--to",
+        "whatsapp:15551234567=Short briefing please",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Cron { cmd } => match cmd {
+            super::cli_types::CronCommands::Add { to, channel, .. } => {
+                assert_eq!(channel, None);
+                let targets = parse_cron_targets(None, &to).unwrap();
+                assert_eq!(targets.len(), 2);
+                assert_eq!(targets[0].channel, "slack");
+                assert_eq!(targets[0].to, "C123");
+                assert_eq!(targets[0].message_override, None);
+                assert_eq!(targets[1].channel, "whatsapp");
+                assert_eq!(targets[1].to, "15551234567");
+                assert_eq!(
+                    targets[1].message_override.as_deref(),
+                    Some("Short briefing please")
+                );
+            }
+            _ => panic!("expected Add"),
+        },
+        _ => panic!("expected Cron"),
+    }
+}
+
+#[test]
+fn test_cli_parse_cron_add_solar() {
+    let cli = Cli::try_parse_from([
+        "oxicrab",
+        "cron",
+        "add",
+        "--name",
+        "dawn",
+        "--message",
+        "Good morning",
+        "--solar",
+        "sunrise",
+        "--lat",
+        "51.5",
+        "--lon",
+        "-0.12",
+        "--offset",
+        "-20",
+        "--to",
+        "slack:C123",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Cron { cmd } => match cmd {
+            super::cli_types::CronCommands::Add {
+                solar,
+                lat,
+                lon,
+                offset,
+                ..
+            } => {
+                assert_eq!(solar.as_deref(), Some("sunrise"));
+                assert_eq!(lat, Some(51.5));
+                assert_eq!(lon, Some(-0.12));
+                assert_eq!(offset, -20);
+            }
+            _ => panic!("expected Add"),
+        },
+        _ => panic!("expected Cron"),
+    }
+
+    // --solar needs coordinates
+    assert!(
+        Cli::try_parse_from([
+            "oxicrab",
+            "cron",
+            "add",
+            "--name",
+            "x",
+            "--message",
+            "y",
+            "--solar",
+            "sunset",
+        ])
+        .is_err()
+    );
+}
+
+#[test]
+fn test_parse_cron_targets_with_channel_flag() {
+    // The original `--channel X --to id` form keeps working
+    let targets = parse_cron_targets(Some("telegram"), &["12345".to_string()]).unwrap();
+    assert_eq!(targets[0].channel, "telegram");
+    assert_eq!(targets[0].to, "12345");
+    assert_eq!(targets[0].message_override, None);
+
+    assert!(parse_cron_targets(None, &["12345".to_string()]).is_err());
+    assert!(parse_cron_targets(None, &["slack:".to_string()]).is_err());
+    assert!(parse_cron_targets(None, &["slack:C1=".to_string()]).is_err());
+}
+
 #[test]
 fn test_cli_parse_channels_status() {
     let cli = Cli::try_parse_from(["oxicrab", "channels", "status"]).unwrap();
@@ -222,6 +567,79 @@ fn test_cli_parse_channels_status() {
     }
 }
 
+#[test]
+fn test_cli_parse_bench() {
+    let cli = Cli::try_parse_from([
+        "oxicrab",
+        "bench",
+        "--provider",
+        "groq",
+        "--model",
+        "llama-3.3-70b",
+        "--runs",
+        "3",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Bench {
+            provider,
+            model,
+            runs,
+        } => {
+            assert_eq!(provider.as_deref(), Some("groq"));
+            assert_eq!(model.as_deref(), Some("llama-3.3-70b"));
+            assert_eq!(runs, 3);
+        }
+        _ => panic!("expected Bench"),
+    }
+
+    let cli = Cli::try_parse_from(["oxicrab", "bench"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Bench {
+            provider: None,
+            model: None,
+            runs: 5
+        }
+    ));
+}
+
+#[test]
+fn test_bench_model_ref() {
+    assert_eq!(
+        bench_model_ref(None, None, "anthropic/claude-sonnet-4-6"),
+        "anthropic/claude-sonnet-4-6"
+    );
+    assert_eq!(
+        bench_model_ref(Some("openrouter"), None, "anthropic/claude-sonnet-4-6"),
+        "openrouter/claude-sonnet-4-6"
+    );
+    assert_eq!(
+        bench_model_ref(Some("groq"), Some("llama-3.3-70b"), "claude-sonnet-4-6"),
+        "groq/llama-3.3-70b"
+    );
+}
+
+#[test]
+fn test_bench_percentiles_and_rows() {
+    let sorted: Vec<f64> = (1..=10).map(f64::from).collect();
+    assert!((percentile(&sorted, 50.0) - 5.0).abs() < f64::EPSILON);
+    assert!((percentile(&sorted, 90.0) - 9.0).abs() < f64::EPSILON);
+    assert!((percentile(&sorted, 99.0) - 10.0).abs() < f64::EPSILON);
+    assert!((percentile(&[42.0], 50.0) - 42.0).abs() < f64::EPSILON);
+
+    let mut samples = BenchSamples::default();
+    samples.record(2000.0, Some(100));
+    samples.record(1000.0, None);
+    assert_eq!(samples.latencies_ms.len(), 2);
+    assert_eq!(samples.tokens_per_sec, vec![50.0]);
+
+    let row = format_row("latency (ms)", &[300.0, 100.0, 200.0]);
+    assert!(row.starts_with("latency (ms)"));
+    assert!(row.ends_with("  100.0     300.0"), "{row}");
+    assert_eq!(format_row("empty", &[]).matches('-').count(), 5);
+}
+
 #[test]
 fn test_cli_parse_no_args_fails() {
     // Running with no subcommand should fail
@@ -348,3 +766,386 @@ fn test_create_workspace_templates_content() {
     let tools = std::fs::read_to_string(workspace.join("TOOLS.md")).unwrap();
     assert!(tools.contains("Tool Notes"));
 }
+
+#[test]
+fn test_format_admin_pairing_notice() {
+    let notice =
+        format_admin_pairing_notice("telegram", "12345", "ABCD1234", "  hi, can I use this?  ");
+    assert!(notice.contains("telegram"));
+    assert!(notice.contains("Sender ID: 12345"));
+    assert!(notice.contains("Message: hi, can I use this?"));
+    assert!(notice.contains("oxicrab pairing approve telegram ABCD1234"));
+
+    let empty = format_admin_pairing_notice("slack", "U1", "CODE", "");
+    assert!(empty.contains("Message: (no text)"));
+
+    let long = format_admin_pairing_notice("slack", "U1", "CODE", &"x".repeat(2000));
+    assert!(long.len() < 800);
+}
+
+#[test]
+fn test_admin_pairing_buttons_carry_code() {
+    let buttons = admin_pairing_buttons("ABCD1234");
+    assert_eq!(buttons[0]["id"], oxicrab_channels::PAIRING_APPROVE_ACTION);
+    assert_eq!(buttons[1]["id"], oxicrab_channels::PAIRING_DENY_ACTION);
+    assert_eq!(buttons[0]["context"], "ABCD1234");
+    assert_eq!(buttons[1]["context"], "ABCD1234");
+}
+
+#[test]
+fn test_resolve_pairing_code_reports_handled_codes() {
+    let db =
+        std::sync::Arc::new(crate::agent::memory::memory_db::MemoryDB::new(":memory:").unwrap());
+    let store = crate::pairing::PairingStore::new(db);
+
+    let code = store.request_pairing("telegram", "42").unwrap().unwrap();
+    assert_eq!(
+        resolve_pairing_code(&store, &code, "telegram:1", true),
+        "Approved telegram:42. They can message now."
+    );
+    assert!(store.is_paired("telegram", "42"));
+    // A second click finds the code gone
+    assert_eq!(
+        resolve_pairing_code(&store, &code, "telegram:1", false),
+        format!("Code {code} was already handled or has expired.")
+    );
+
+    let code = store.request_pairing("slack", "U9").unwrap().unwrap();
+    assert_eq!(
+        resolve_pairing_code(&store, &code, "telegram:1", false),
+        "Denied slack:U9."
+    );
+    assert!(!store.is_paired("slack", "U9"));
+}
+
+#[test]
+fn test_tenant_config_isolates_workspace_and_integrations() {
+    let mut config = Config::default();
+    config.agents.defaults.workspace = "/srv/oxicrab".to_string();
+    config.tools.github.enabled = true;
+    config.tools.restrict_to_workspace = false;
+    let tenant = crate::config::TenantConfig {
+        api_key: "k".to_string(),
+        workspace: None,
+        model: None,
+    };
+
+    let cfg = tenant_config(&config, "alice", &tenant);
+    assert_eq!(
+        cfg.workspace_path(),
+        std::path::PathBuf::from("/srv/oxicrab/tenants/alice")
+    );
+    assert!(cfg.tools.restrict_to_workspace);
+    assert!(!cfg.tools.github.enabled);
+
+    let tenant = crate::config::TenantConfig {
+        workspace: Some("/srv/alice".to_string()),
+        ..tenant
+    };
+    let cfg = tenant_config(&config, "alice", &tenant);
+    assert_eq!(cfg.workspace_path(), std::path::PathBuf::from("/srv/alice"));
+}
+
+#[tokio::test]
+async fn test_tenant_tools_exclude_tmux_and_sandbox_exec() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut config = Config::default();
+    config.agents.defaults.workspace = tmp.path().to_string_lossy().into_owned();
+    config.agents.defaults.memory.embeddings_enabled = false;
+    config.tools.exec.sandbox.enabled = false;
+    config.tools.exec.sandbox.additional_read_paths = vec!["/".to_string()];
+    let tenant = crate::config::TenantConfig {
+        api_key: "k".to_string(),
+        workspace: None,
+        model: None,
+    };
+    let cfg = tenant_config(&config, "alice", &tenant);
+    assert!(!cfg.tools.tmux.enabled);
+    assert!(cfg.tools.workspace_only);
+    assert!(cfg.tools.exec.sandbox.enabled);
+    assert!(cfg.tools.exec.sandbox.additional_read_paths.is_empty());
+
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(4);
+    let provider =
+        crate::provider_factory::create_provider(&cfg, Some("ollama/llama3"), None).unwrap();
+    let agent = crate::agent::AgentLoop::new(crate::agent::AgentLoopConfig::from_config(
+        &cfg,
+        crate::agent::AgentLoopRuntimeParams {
+            bus: std::sync::Arc::new(crate::bus::MessageBus::default()),
+            provider,
+            model: Some("ollama/llama3".to_string()),
+            outbound_tx: std::sync::Arc::new(outbound_tx),
+            cron_service: None,
+            typing_tx: None,
+            channels_config: None,
+            memory_db: None,
+            leak_detector: None,
+        },
+        None,
+    ))
+    .await
+    .unwrap();
+    let tools = agent.tool_registry();
+    assert!(tools.get("tmux").is_none());
+    let exec = tools.get("exec").unwrap();
+    assert!(
+        exec.description().contains("sandboxed"),
+        "{}",
+        exec.description()
+    );
+    let operator_config = crate::utils::get_oxicrab_home()
+        .unwrap()
+        .join("config.toml");
+    let result = tools
+        .get("read_file")
+        .unwrap()
+        .execute(
+            serde_json::json!({"path": operator_config.to_str().unwrap()}),
+            &oxicrab_core::tools::base::ExecutionContext::default(),
+        )
+        .await
+        .unwrap();
+    assert!(result.is_error);
+    assert!(result.content.contains("outside the allowed directories"));
+}
+
+#[test]
+fn test_send_rate_limiter_paces_each_chat() {
+    use std::time::{Duration, Instant};
+
+    assert!(SendRateLimiter::new(crate::config::ChannelRateLimitConfig::default()).is_none());
+    let mut limiter = SendRateLimiter::new(crate::config::ChannelRateLimitConfig {
+        messages_per_minute: 30,
+        burst: 2,
+    })
+    .unwrap();
+    let chat = ("slack".to_string(), "C1".to_string());
+    let other = ("telegram".to_string(), "42".to_string());
+    let start = Instant::now();
+
+    assert_eq!(limiter.reserve(&chat, start), Duration::ZERO);
+    assert_eq!(limiter.reserve(&chat, start), Duration::ZERO);
+    // One token every 2s; queued sends wait their turn
+    assert_eq!(limiter.reserve(&chat, start), Duration::from_secs(2));
+    assert_eq!(limiter.reserve(&chat, start), Duration::from_secs(4));
+    assert_eq!(limiter.reserve(&other, start), Duration::ZERO);
+    assert_eq!(
+        limiter.reserve(&chat, start + Duration::from_secs(10)),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn test_paced_sends_release_in_order_when_due() {
+    use tokio::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let msg =
+        |chat: &str, text: &str| crate::bus::OutboundMessage::builder("slack", chat, text).build();
+    let mut paced = PacedSends::default();
+    assert!(paced.next_due().is_none());
+    paced.push(start + Duration::from_secs(2), msg("C1", "first"));
+    paced.push(start + Duration::from_secs(4), msg("C1", "second"));
+    paced.push(start + Duration::from_secs(1), msg("C2", "other"));
+    assert_eq!(paced.next_due(), Some(start + Duration::from_secs(1)));
+
+    assert!(paced.take_due(start).is_empty());
+    let due: Vec<String> = paced
+        .take_due(start + Duration::from_secs(3))
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(due, vec!["first".to_string(), "other".to_string()]);
+    assert_eq!(paced.next_due(), Some(start + Duration::from_secs(4)));
+    assert_eq!(paced.take_due(start + Duration::from_secs(4)).len(), 1);
+    assert!(paced.next_due().is_none());
+}
+
+#[test]
+fn test_send_rate_limiter_evicts_idle_chats() {
+    use std::time::{Duration, Instant};
+
+    let mut limiter = SendRateLimiter::new(crate::config::ChannelRateLimitConfig {
+        messages_per_minute: 60,
+        burst: 1,
+    })
+    .unwrap();
+    let start = Instant::now();
+    for i in 0..1000 {
+        limiter.reserve(&("slack".to_string(), i.to_string()), start);
+    }
+    assert_eq!(limiter.tracked(), 1000);
+    // Every bucket has refilled after a second, so all are evicted
+    limiter.reserve(
+        &("slack".to_string(), "new".to_string()),
+        start + Duration::from_secs(1),
+    );
+    assert_eq!(limiter.tracked(), 1);
+}
+
+fn quiet_hours(start: &str, end: &str) -> QuietHours {
+    let config = crate::config::QuietHoursConfig {
+        enabled: true,
+        start: start.into(),
+        end: end.into(),
+        timezone: Some("UTC".into()),
+        channels: vec!["telegram".into()],
+    };
+    let db =
+        std::sync::Arc::new(crate::agent::memory::memory_db::MemoryDB::new(":memory:").unwrap());
+    QuietHours::new(&config, db).unwrap().unwrap()
+}
+
+fn utc(time: &str) -> chrono::DateTime<chrono::Utc> {
+    format!("2026-03-01T{time}:00Z").parse().unwrap()
+}
+
+#[test]
+fn test_quiet_hours_window() {
+    let overnight = quiet_hours("22:00", "07:00");
+    assert!(overnight.is_quiet(utc("23:30")));
+    assert!(overnight.is_quiet(utc("03:00")));
+    assert!(!overnight.is_quiet(utc("07:00")));
+    assert!(!overnight.is_quiet(utc("12:00")));
+
+    let afternoon = quiet_hours("13:00", "15:00");
+    assert!(afternoon.is_quiet(utc("14:00")));
+    assert!(!afternoon.is_quiet(utc("23:00")));
+}
+
+#[test]
+fn test_quiet_hours_holds_background_messages_until_end() {
+    let quiet = quiet_hours("22:00", "07:00");
+    let background = crate::bus::OutboundMessage::builder("telegram", "42", "digest")
+        .meta(crate::bus::meta::BACKGROUND, serde_json::Value::Bool(true))
+        .build();
+    let reply = crate::bus::OutboundMessage::builder("telegram", "42", "reply").build();
+    let other_channel = crate::bus::OutboundMessage::builder("slack", "C1", "digest")
+        .meta(crate::bus::meta::BACKGROUND, serde_json::Value::Bool(true))
+        .build();
+
+    assert!(!quiet.hold(&background, utc("12:00")));
+    assert!(!quiet.hold(&reply, utc("23:00")));
+    assert!(!quiet.hold(&other_channel, utc("23:00")));
+    assert!(quiet.hold(&background, utc("23:00")));
+
+    assert!(quiet.release_due(utc("06:59")).is_empty());
+    let released = quiet.release_due(utc("07:00"));
+    assert_eq!(released.len(), 1);
+    assert_eq!(released[0].content, "digest");
+    assert_eq!(released[0].chat_id, "42");
+    assert!(quiet.release_due(utc("07:01")).is_empty());
+}
+
+#[test]
+fn test_status_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = crate::config::Config::default();
+    config.agents.defaults.workspace = dir.path().to_string_lossy().to_string();
+    config.agents.defaults.model_routing.default = "anthropic/claude-sonnet-4-5".to_string();
+    config.channels.telegram.enabled = true;
+    let config_path = dir.path().join("config.toml");
+
+    // No memory database yet: nothing is created, fields are null
+    let status = super::subcommands::status_json(&config, &config_path);
+    assert_eq!(status["channels"], serde_json::json!(["telegram"]));
+    assert_eq!(status["provider"], "anthropic");
+    assert_eq!(status["configExists"], false);
+    assert!(status["memoryDb"]["sizeBytes"].is_null());
+    assert!(status["cronJobs"].is_null());
+    assert!(!dir.path().join("memory").exists());
+
+    crate::agent::memory::MemoryDB::new(dir.path().join("memory").join("memory.sqlite3")).unwrap();
+    let status = super::subcommands::status_json(&config, &config_path);
+    assert!(status["memoryDb"]["sizeBytes"].as_u64().unwrap() > 0);
+    assert!(status["memoryDb"]["fts"].is_boolean());
+    assert_eq!(status["cronJobs"], 0);
+}
+
+#[tokio::test]
+async fn test_session_export_import_round_trip() {
+    use crate::agent::memory::MemoryDB;
+    use crate::session::SessionManager;
+    use std::sync::Arc;
+
+    let here = SessionManager::with_db(Arc::new(MemoryDB::new(":memory:").unwrap()));
+    let there = SessionManager::with_db(Arc::new(MemoryDB::new(":memory:").unwrap()));
+    assert!(
+        super::session_cmd::export_session(&here, "telegram:42")
+            .await
+            .is_err()
+    );
+
+    let mut session = here.get_or_create("telegram:42").await.unwrap();
+    session.add_message("user", "hi", std::collections::HashMap::new());
+    session.add_message("assistant", "hello", std::collections::HashMap::new());
+    session
+        .metadata
+        .insert("focus".into(), serde_json::json!({"preset": "coding"}));
+    here.save(&session).await.unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("session.json");
+    let json = super::session_cmd::export_session(&here, "telegram:42")
+        .await
+        .unwrap();
+    std::fs::write(&file, json).unwrap();
+
+    let parsed = super::session_cmd::read_session_file(&file).unwrap();
+    super::session_cmd::import_session(&there, parsed, false)
+        .await
+        .unwrap();
+    let imported = there.get_or_create("telegram:42").await.unwrap();
+    assert_eq!(imported.messages.len(), 2);
+    assert_eq!(imported.messages[1].content, "hello");
+    assert_eq!(imported.metadata, session.metadata);
+    assert_eq!(imported.created_at, session.created_at);
+
+    let parsed = super::session_cmd::read_session_file(&file).unwrap();
+    let err = super::session_cmd::import_session(&there, parsed, false)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("--force"), "error: {err}");
+    let parsed = super::session_cmd::read_session_file(&file).unwrap();
+    super::session_cmd::import_session(&there, parsed, true)
+        .await
+        .unwrap();
+}
+
+#[test]
+fn test_session_db_path_follows_profiles() {
+    use super::session_cmd::session_db_path;
+
+    let mut config = crate::config::Config::default();
+    config.agents.defaults.workspace = "/tmp/oxicrab-ws".to_string();
+    config.agents.profiles = vec![crate::config::ProfileConfig {
+        name: "work".to_string(),
+        workspace: None,
+        channels: vec!["slack".to_string()],
+        chats: vec![],
+    }];
+    let base = std::path::Path::new("/tmp/oxicrab-ws");
+    let default_db = base.join("memory").join("memory.sqlite3");
+    let work_db = std::path::Path::new("/tmp/profiles")
+        .join("work")
+        .join("memory")
+        .join("memory.sqlite3");
+
+    assert_eq!(
+        session_db_path(&config, "telegram:42", None).unwrap(),
+        default_db
+    );
+    assert_eq!(
+        session_db_path(&config, "slack:C042", None).unwrap(),
+        work_db
+    );
+    assert_eq!(
+        session_db_path(&config, "telegram:42", Some("work")).unwrap(),
+        work_db
+    );
+    assert_eq!(
+        session_db_path(&config, "slack:C042", Some("default")).unwrap(),
+        default_db
+    );
+    assert!(session_db_path(&config, "slack:C042", Some("home")).is_err());
+}
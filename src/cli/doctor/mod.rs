@@ -4,15 +4,15 @@ use tracing::debug;
 type LoadedConfig<'a> = Option<&'a crate::config::Config>;
 type LoadedPairingStore<'a> = Option<&'a crate::pairing::PairingStore>;
 
-#[derive(Debug)]
-enum CheckResult {
+#[derive(Debug, Clone)]
+pub(crate) enum CheckResult {
     Pass(String),
     Fail(String),
     Skip(String),
 }
 
 impl CheckResult {
-    fn label(&self) -> &'static str {
+    pub(crate) fn label(&self) -> &'static str {
         match self {
             Self::Pass(_) => "PASS",
             Self::Fail(_) => "FAIL",
@@ -20,13 +20,13 @@ impl CheckResult {
         }
     }
 
-    fn detail(&self) -> &str {
+    pub(crate) fn detail(&self) -> &str {
         match self {
             Self::Pass(s) | Self::Fail(s) | Self::Skip(s) => s,
         }
     }
 
-    fn is_fail(&self) -> bool {
+    pub(crate) fn is_fail(&self) -> bool {
         matches!(self, Self::Fail(_))
     }
 }
@@ -69,24 +69,24 @@ fn check_config_validates(config: &Result<crate::config::Config, anyhow::Error>)
 
 fn check_workspace(config: LoadedConfig<'_>) -> CheckResult {
     match config {
-        Some(config) => {
-            let path = config.workspace_path();
-            if path.exists() {
-                let test_file = path.join(".doctor_test");
-                match std::fs::write(&test_file, "test") {
-                    Ok(()) => {
-                        let _ = std::fs::remove_file(&test_file);
-                        CheckResult::Pass(format!("{} (writable)", path.display()))
-                    }
-                    Err(e) => {
-                        CheckResult::Fail(format!("{} (not writable: {})", path.display(), e))
-                    }
-                }
-            } else {
-                CheckResult::Fail(format!("{} (does not exist)", path.display()))
+        Some(config) => check_workspace_path(&config.workspace_path()),
+        None => CheckResult::Skip("config not available".to_string()),
+    }
+}
+
+/// Check that the workspace directory exists and is writable.
+pub(crate) fn check_workspace_path(path: &std::path::Path) -> CheckResult {
+    if path.exists() {
+        let test_file = path.join(".doctor_test");
+        match std::fs::write(&test_file, "test") {
+            Ok(()) => {
+                let _ = std::fs::remove_file(&test_file);
+                CheckResult::Pass(format!("{} (writable)", path.display()))
             }
+            Err(e) => CheckResult::Fail(format!("{} (not writable: {})", path.display(), e)),
         }
-        None => CheckResult::Skip("config not available".to_string()),
+    } else {
+        CheckResult::Fail(format!("{} (does not exist)", path.display()))
     }
 }
 
@@ -137,45 +137,56 @@ fn check_provider_keys(config: LoadedConfig<'_>) -> CheckResult {
 async fn check_provider_connectivity(config: LoadedConfig<'_>) -> CheckResult {
     match config {
         Some(config) => match crate::provider_factory::create_provider(config, None, None) {
-            Ok(provider) => {
-                let start = std::time::Instant::now();
-                match provider.warmup().await {
-                    Ok(()) => {
-                        let elapsed = start.elapsed();
-                        CheckResult::Pass(format!(
-                            "{} (warmup: {:.0}ms)",
-                            provider.default_model(),
-                            elapsed.as_secs_f64() * 1000.0
-                        ))
-                    }
-                    Err(e) => CheckResult::Fail(format!(
-                        "{} (warmup failed: {})",
-                        provider.default_model(),
-                        e
-                    )),
-                }
-            }
+            Ok(provider) => check_provider_warmup(provider.as_ref()).await,
             Err(e) => CheckResult::Fail(format!("cannot create provider: {e}")),
         },
         None => CheckResult::Skip("config not available".to_string()),
     }
 }
 
-// Config used conditionally inside #[cfg(feature)] blocks
-#[allow(unused_variables)]
-fn check_channels(config: LoadedConfig<'_>) -> Vec<(&'static str, CheckResult)> {
-    let mut results = Vec::new();
+/// Check that the provider answers a warmup request.
+pub(crate) async fn check_provider_warmup(
+    provider: &dyn crate::providers::base::LLMProvider,
+) -> CheckResult {
+    let start = std::time::Instant::now();
+    match provider.warmup().await {
+        Ok(()) => {
+            let elapsed = start.elapsed();
+            CheckResult::Pass(format!(
+                "{} (warmup: {:.0}ms)",
+                provider.default_model(),
+                elapsed.as_secs_f64() * 1000.0
+            ))
+        }
+        Err(e) => CheckResult::Fail(format!(
+            "{} (warmup failed: {})",
+            provider.default_model(),
+            e
+        )),
+    }
+}
 
-    let Some(config) = config else {
-        return vec![(
+fn check_channels(config: LoadedConfig<'_>) -> Vec<(&'static str, CheckResult)> {
+    match config {
+        Some(config) => check_channel_configs(&config.channels),
+        None => vec![(
             "channels",
             CheckResult::Skip("config not available".to_string()),
-        )];
-    };
+        )],
+    }
+}
+
+/// Check each channel's enabled state and required credentials.
+// Channels config used conditionally inside #[cfg(feature)] blocks
+#[allow(unused_variables)]
+pub(crate) fn check_channel_configs(
+    channels: &crate::config::ChannelsConfig,
+) -> Vec<(&'static str, CheckResult)> {
+    let mut results = Vec::new();
 
     #[cfg(feature = "channel-telegram")]
     {
-        let tg = &config.channels.telegram;
+        let tg = &channels.telegram;
         let result = if !tg.enabled {
             CheckResult::Skip("disabled".to_string())
         } else if tg.token.is_empty() {
@@ -190,7 +201,7 @@ fn check_channels(config: LoadedConfig<'_>) -> Vec<(&'static str, CheckResult)>
 
     #[cfg(feature = "channel-discord")]
     {
-        let dc = &config.channels.discord;
+        let dc = &channels.discord;
         let result = if !dc.enabled {
             CheckResult::Skip("disabled".to_string())
         } else if dc.token.is_empty() {
@@ -205,7 +216,7 @@ fn check_channels(config: LoadedConfig<'_>) -> Vec<(&'static str, CheckResult)>
 
     #[cfg(feature = "channel-slack")]
     {
-        let sl = &config.channels.slack;
+        let sl = &channels.slack;
         let result = if !sl.enabled {
             CheckResult::Skip("disabled".to_string())
         } else if sl.bot_token.is_empty() || sl.app_token.is_empty() {
@@ -220,7 +231,7 @@ fn check_channels(config: LoadedConfig<'_>) -> Vec<(&'static str, CheckResult)>
 
     #[cfg(feature = "channel-whatsapp")]
     {
-        let wa = &config.channels.whatsapp;
+        let wa = &channels.whatsapp;
         let result = if wa.enabled {
             CheckResult::Pass("enabled".to_string())
         } else {
@@ -233,7 +244,7 @@ fn check_channels(config: LoadedConfig<'_>) -> Vec<(&'static str, CheckResult)>
 
     #[cfg(feature = "channel-twilio")]
     {
-        let tw = &config.channels.twilio;
+        let tw = &channels.twilio;
         let result = if !tw.enabled {
             CheckResult::Skip("disabled".to_string())
         } else if tw.account_sid.is_empty() || tw.auth_token.is_empty() {
@@ -502,63 +513,122 @@ fn check_pairing_store_with_store(pairing_store: LoadedPairingStore<'_>) -> Chec
 
 fn check_mcp_servers(config: LoadedConfig<'_>) -> CheckResult {
     match config {
-        Some(config) => {
-            if config.tools.mcp.servers.is_empty() {
-                return CheckResult::Skip("no servers configured".to_string());
-            }
-            let enabled: Vec<&str> = config
-                .tools
-                .mcp
-                .servers
-                .iter()
-                .filter(|(_, s)| s.enabled)
-                .map(|(name, _)| name.as_str())
-                .collect();
-            if enabled.is_empty() {
-                CheckResult::Skip("all servers disabled".to_string())
-            } else {
-                CheckResult::Pass(format!(
-                    "{} server(s): {}",
-                    enabled.len(),
-                    enabled.join(", ")
-                ))
-            }
-        }
+        Some(config) => check_mcp_config(&config.tools.mcp),
         None => CheckResult::Skip("config not available".to_string()),
     }
 }
 
-pub async fn doctor_command() -> Result<()> {
-    println!("oxicrab doctor\n");
-    println!("{}", "=".repeat(60));
+/// Summarize the enabled MCP servers.
+pub(crate) fn check_mcp_config(mcp: &crate::config::McpConfig) -> CheckResult {
+    if mcp.servers.is_empty() {
+        return CheckResult::Skip("no servers configured".to_string());
+    }
+    let enabled: Vec<&str> = mcp
+        .servers
+        .iter()
+        .filter(|(_, s)| s.enabled)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if enabled.is_empty() {
+        CheckResult::Skip("all servers disabled".to_string())
+    } else {
+        CheckResult::Pass(format!(
+            "{} server(s): {}",
+            enabled.len(),
+            enabled.join(", ")
+        ))
+    }
+}
 
-    let mut pass_count = 0u32;
-    let mut fail_count = 0u32;
-    let mut skip_count = 0u32;
+/// Check results collected by `doctor_command`. In text mode each check is
+/// printed as it completes; in JSON mode they are emitted together at the end.
+struct DoctorReport {
+    json: bool,
+    section: &'static str,
+    checks: Vec<(&'static str, String, CheckResult)>,
+}
 
-    let mut record = |name: &str, result: &CheckResult| {
-        print_check(name, result);
-        match result {
-            CheckResult::Pass(_) => pass_count += 1,
-            CheckResult::Fail(_) => fail_count += 1,
-            CheckResult::Skip(_) => skip_count += 1,
+impl DoctorReport {
+    fn new(json: bool) -> Self {
+        Self {
+            json,
+            section: "",
+            checks: Vec::new(),
         }
-    };
+    }
+
+    fn section(&mut self, title: &'static str) {
+        self.section = title;
+        if !self.json {
+            println!("\n  {title}");
+            println!("  {}", "-".repeat(56));
+        }
+    }
+
+    fn record(&mut self, name: &str, result: &CheckResult) {
+        if !self.json {
+            print_check(name, result);
+        }
+        self.checks
+            .push((self.section, name.to_string(), result.clone()));
+    }
+
+    /// Number of (passed, failed, skipped) checks.
+    fn counts(&self) -> (usize, usize, usize) {
+        let count = |label| {
+            self.checks
+                .iter()
+                .filter(|(_, _, r)| r.label() == label)
+                .count()
+        };
+        (count("PASS"), count("FAIL"), count("SKIP"))
+    }
+
+    /// `ok` is false when a critical (config) check failed.
+    fn to_json(&self, ok: bool) -> serde_json::Value {
+        let (passed, failed, skipped) = self.counts();
+        let checks: Vec<serde_json::Value> = self
+            .checks
+            .iter()
+            .map(|(section, name, result)| {
+                serde_json::json!({
+                    "section": section,
+                    "name": name,
+                    "status": result.label().to_lowercase(),
+                    "message": result.detail(),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "ok": ok,
+            "passed": passed,
+            "failed": failed,
+            "skipped": skipped,
+            "checks": checks,
+        })
+    }
+}
+
+pub async fn doctor_command(json: bool) -> Result<()> {
+    let mut report = DoctorReport::new(json);
+    if !json {
+        println!("oxicrab doctor\n");
+        println!("{}", "=".repeat(60));
+    }
 
     // Core checks
-    println!("\n  Core");
-    println!("  {}", "-".repeat(56));
+    report.section("Core");
 
     let r_config_exists = check_config_exists();
-    record("Config file", &r_config_exists);
+    report.record("Config file", &r_config_exists);
 
     let loaded_result = crate::config::load_config(None);
 
     let r_config_parses = check_config_parses(&loaded_result);
-    record("Config parses", &r_config_parses);
+    report.record("Config parses", &r_config_parses);
 
     let r_config_validates = check_config_validates(&loaded_result);
-    record("Config validates", &r_config_validates);
+    report.record("Config validates", &r_config_validates);
 
     let loaded_config = loaded_result.ok();
     let config = loaded_config.as_ref();
@@ -567,96 +637,99 @@ pub async fn doctor_command() -> Result<()> {
     });
 
     let r = check_workspace(config);
-    record("Workspace", &r);
+    report.record("Workspace", &r);
 
     // Provider checks
-    println!("\n  Provider");
-    println!("  {}", "-".repeat(56));
+    report.section("Provider");
 
     let r = check_provider_keys(config);
-    record("API keys", &r);
+    report.record("API keys", &r);
 
     debug!("checking provider connectivity...");
     let r = check_provider_connectivity(config).await;
-    record("Provider connectivity", &r);
+    report.record("Provider connectivity", &r);
 
     // Channel checks
-    println!("\n  Channels");
-    println!("  {}", "-".repeat(56));
+    report.section("Channels");
 
     for (name, result) in check_channels(config) {
-        record(name, &result);
+        report.record(name, &result);
     }
 
     // Voice
-    println!("\n  Voice");
-    println!("  {}", "-".repeat(56));
+    report.section("Voice");
 
     let r = check_voice(config);
-    record("Transcription", &r);
+    report.record("Transcription", &r);
 
     // External tools
-    println!("\n  External Tools");
-    println!("  {}", "-".repeat(56));
+    report.section("External Tools");
 
     let r = check_external_command("ffmpeg", &["-version"]);
-    record("ffmpeg", &r);
+    report.record("ffmpeg", &r);
 
     let r = check_external_command("git", &["--version"]);
-    record("git", &r);
+    report.record("git", &r);
 
     // Security
-    println!("\n  Security");
-    println!("  {}", "-".repeat(56));
+    report.section("Security");
 
     let r = check_config_file_permissions();
-    record("Config file permissions", &r);
+    report.record("Config file permissions", &r);
 
     let r = check_config_dir_permissions();
-    record("Config dir permissions", &r);
+    report.record("Config dir permissions", &r);
 
     let r = check_keyring();
-    record("Keyring", &r);
+    report.record("Keyring", &r);
 
     let r = check_credential_helper(config);
-    record("Credential helper", &r);
+    report.record("Credential helper", &r);
 
     let r = check_sandbox();
-    record("Process sandbox", &r);
+    report.record("Process sandbox", &r);
 
     let r = match config {
         Some(config) => check_empty_allowlists_with_store(config, pairing_store.as_ref()),
         None => check_empty_allowlists(config),
     };
-    record("Empty allowlists", &r);
+    report.record("Empty allowlists", &r);
 
     let r = if pairing_store.is_some() {
         check_pairing_store_with_store(pairing_store.as_ref())
     } else {
         check_pairing_store(config)
     };
-    record("Pairing store", &r);
+    report.record("Pairing store", &r);
 
     // MCP
-    println!("\n  MCP");
-    println!("  {}", "-".repeat(56));
+    report.section("MCP");
 
     let r = check_mcp_servers(config);
-    record("MCP servers", &r);
+    report.record("MCP servers", &r);
 
-    // Summary
-    println!("\n{}", "=".repeat(60));
-    println!("  {pass_count} passed, {fail_count} failed, {skip_count} skipped");
+    // Return error if any critical checks failed (config must exist, parse, and validate)
+    let critical_fail =
+        r_config_exists.is_fail() || r_config_parses.is_fail() || r_config_validates.is_fail();
 
-    if fail_count > 0 {
-        println!("\n  Some checks failed. Review the output above.");
+    // Summary
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report.to_json(!critical_fail))?
+        );
     } else {
-        println!("\n  All checks passed!");
+        let (passed, failed, skipped) = report.counts();
+        println!("\n{}", "=".repeat(60));
+        println!("  {passed} passed, {failed} failed, {skipped} skipped");
+
+        if failed > 0 {
+            println!("\n  Some checks failed. Review the output above.");
+        } else {
+            println!("\n  All checks passed!");
+        }
     }
 
-    // Return error if any critical checks failed (config must exist, parse, and validate)
-    let critical_fail =
-        r_config_exists.is_fail() || r_config_parses.is_fail() || r_config_validates.is_fail();
     if critical_fail {
         anyhow::bail!("critical checks failed");
     }
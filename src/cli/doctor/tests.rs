@@ -104,3 +104,32 @@ fn test_print_check_does_not_panic() {
     print_check("test_fail", &fail);
     print_check("test_skip", &skip);
 }
+
+#[test]
+fn test_doctor_report_json() {
+    let mut report = DoctorReport::new(true);
+    report.section("Core");
+    report.record(
+        "Config file",
+        &CheckResult::Pass("/tmp/config.toml".to_string()),
+    );
+    report.section("External Tools");
+    report.record("ffmpeg", &CheckResult::Fail("not found".to_string()));
+    report.record("git", &CheckResult::Skip("n/a".to_string()));
+
+    let json = report.to_json(true);
+    assert_eq!(json["ok"], true);
+    assert_eq!(
+        (&json["passed"], &json["failed"], &json["skipped"]),
+        (&1.into(), &1.into(), &1.into())
+    );
+    assert_eq!(
+        json["checks"][1],
+        serde_json::json!({
+            "section": "External Tools",
+            "name": "ffmpeg",
+            "status": "fail",
+            "message": "not found",
+        })
+    );
+}
@@ -6,15 +6,23 @@ pub mod schema;
 pub use loader::{get_config_path, load_config, save_config};
 pub use schema::{
     A2aConfig, AgentDefaults, AgentsConfig, AllowedCommands, AnthropicOAuthConfig, ApprovalConfig,
-    ApprovalScope, BrowserConfig, ChannelTarget, ChannelsConfig, ChatModels, ChatRoutingConfig,
-    ChatThresholds, CircuitBreakerConfig, CognitiveConfig, CompactionConfig, Config,
-    ContextProviderConfig, CredentialHelperConfig, DenyByDefaultList, DiscordCommand,
-    DiscordCommandOption, DiscordConfig, DmPolicy, ExecToolConfig, ExfiltrationGuardConfig,
-    FusionStrategy, GatewayConfig, GitHubConfig, GoogleConfig, HttpUrl, ImageGenConfig, McpConfig,
-    McpTrust, MediaConfig, MemoryConfig, ModelRoutingConfig, ObsidianConfig, PromptGuardAction,
-    PromptGuardConfig, ProviderConfig, ProvidersConfig, RouterConfig, RssConfig, SandboxConfig,
-    SlackConfig, TaskRouting, TelegramConfig, TodoistConfig, ToolsConfig, TranscriptionConfig,
-    TwilioConfig, VoiceConfig, WeatherConfig, WebSearchConfig, WebhookConfig, WebhookTarget,
-    WhatsAppConfig, WorkspaceTtlConfig, infer_provider_from_model, normalize_provider,
-    parse_model_ref,
+    ApprovalScope, BrowserConfig, ChannelRateLimitConfig, ChannelTarget, ChannelsConfig,
+    ChatModels, ChatRoutingConfig, ChatThresholds, CircuitBreakerConfig, CognitiveConfig,
+    CompactionCheckpointConfig, CompactionConfig, Config, ContentFilterAction, ContentFilterConfig,
+    ContentFilterRule, ContextProviderConfig, ContextProviderKind, CredentialHelperConfig,
+    DelegationConfig, DenyByDefaultList, DiscordCommand, DiscordCommandOption, DiscordConfig,
+    DmPolicy, EmbeddingsBackend, EmptyResponseAction, EmptyResponseConfig, ExecToolConfig,
+    ExfiltrationGuardConfig, ExtractionIntervalConfig, FactDigestConfig, FailoverConfig,
+    FusionStrategy, GatewayConfig, GatewayMetricsConfig, GitHubConfig, GoogleConfig, HttpUrl,
+    ImageGenConfig, InboundMediaConfig, McpConfig, McpTrust, MediaConfig, MemoryCategory,
+    MemoryConfig, MemoryGardenerConfig, MessageBatchingConfig, ModelRoutingConfig, ObsidianConfig,
+    OfflineModeConfig, ProfileConfig, PromptGuardAction, PromptGuardCategory, PromptGuardConfig,
+    PromptGuardPattern, ProviderConfig, ProviderRetryConfig, ProvidersConfig, QuietHoursConfig,
+    ReasoningConfig, RouterConfig, RssConfig, SandboxConfig, ScratchpadConfig, SendRetryConfig,
+    SlackConfig, StreamingConfig, TaskRouting, TelegramConfig, TenantConfig, TmuxConfig,
+    TodoistConfig, ToolLoopAction, ToolLoopConfig, ToolRateLimitConfig, ToolResultSummaryConfig,
+    ToolsConfig, TranscriptionConfig, TwilioConfig, TypingIndicatorConfig, TypingIndicatorStyle,
+    VoiceConfig, WeatherAlertsConfig, WeatherConfig, WeatherUnits, WebSearchConfig, WebhookAuth,
+    WebhookConfig, WebhookTarget, WhatsAppConfig, WorkspaceTtlConfig, infer_provider_from_model,
+    normalize_provider, parse_model_ref,
 };
@@ -5,9 +5,20 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::debug;
 
+/// Task key holding the provider for `agents.defaults.emptyResponse.fallbackModel`.
+pub const EMPTY_RESPONSE_TASK: &str = "empty_response";
+
+/// Task key holding the provider for `agents.defaults.toolResultSummary.model`.
+pub const TOOL_SUMMARY_TASK: &str = "tool_summary";
+
+/// Task key holding the provider for `agents.defaults.compaction.extractionModel`.
+pub const EXTRACTION_TASK: &str = "extraction";
+
 /// Pre-resolved chat complexity routing with providers ready to use.
 pub struct ResolvedChatRouting {
     pub thresholds: ChatThresholds,
+    /// Optional cheap tier for scores below `thresholds.standard`.
+    pub light: Option<(Arc<dyn LLMProvider>, String)>,
     pub standard: (Arc<dyn LLMProvider>, String),
     pub heavy: (Arc<dyn LLMProvider>, String),
     pub weights: ComplexityWeights,
@@ -42,7 +53,8 @@ impl ResolvedRouting {
 
     /// Resolve chat complexity routing: given a composite complexity score,
     /// return overrides for the appropriate model tier.
-    /// Returns `None` if score is below the standard threshold (use default model).
+    /// Returns `None` if score is below the standard threshold and no light
+    /// model is configured (use default model).
     pub fn resolve_chat(&self, composite: f64) -> Option<AgentRunOverrides> {
         let chat = self.chat.as_ref()?;
         let (provider, model) = if composite >= chat.thresholds.heavy {
@@ -51,6 +63,9 @@ impl ResolvedRouting {
         } else if composite >= chat.thresholds.standard {
             debug!("chat complexity → standard (score={composite:.3})");
             &chat.standard
+        } else if let Some(ref light) = chat.light {
+            debug!("chat complexity → light (score={composite:.3})");
+            light
         } else {
             debug!("chat complexity → default (score={composite:.3})");
             return None;
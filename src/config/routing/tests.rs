@@ -26,6 +26,7 @@ fn make_chat_routing() -> ResolvedChatRouting {
             standard: 0.3,
             heavy: 0.7,
         },
+        light: None,
         standard: (mock_provider(), "standard-model".to_string()),
         heavy: (mock_provider(), "heavy-model".to_string()),
         weights: ComplexityWeights::default(),
@@ -90,6 +91,22 @@ fn resolve_chat_below_standard_returns_none() {
     assert!(routing.resolve_chat(0.29).is_none());
 }
 
+#[test]
+fn resolve_chat_below_standard_uses_light_model() {
+    let mut chat = make_chat_routing();
+    chat.light = Some((mock_provider(), "light-model".to_string()));
+    let routing = ResolvedRouting::new(HashMap::new(), Some(chat));
+
+    let overrides = routing.resolve_chat(0.1).unwrap();
+    assert_eq!(overrides.model.as_deref(), Some("light-model"));
+    assert!(overrides.provider.is_some());
+    // Higher tiers are unaffected
+    assert_eq!(
+        routing.resolve_chat(0.5).unwrap().model.as_deref(),
+        Some("standard-model")
+    );
+}
+
 #[test]
 fn resolve_chat_at_standard_threshold() {
     let routing = ResolvedRouting::new(HashMap::new(), Some(make_chat_routing()));
@@ -93,6 +93,34 @@ fn test_enabled_webhook_requires_secret_and_targets() {
     assert!(err.to_string().contains("gateway.webhooks.deploy.secret"));
 }
 
+#[test]
+fn test_webhook_github_auth_scheme() {
+    let webhook: WebhookConfig = serde_json::from_value(serde_json::json!({
+        "auth": {"scheme": "github-sha256", "secret": "gh-secret"},
+        "targets": [{"channel": "slack", "chatId": "C123"}]
+    }))
+    .unwrap();
+    assert!(matches!(
+        webhook.resolved_auth(),
+        crate::config::WebhookAuth::GithubSha256 { ref secret } if secret == "gh-secret"
+    ));
+    assert!(!format!("{webhook:?}").contains("gh-secret"));
+
+    let mut config = Config::default();
+    config
+        .gateway
+        .webhooks
+        .insert("github".into(), webhook.clone());
+    assert!(config.validate().is_ok());
+
+    // Legacy secret plus auth is ambiguous
+    let mut both = webhook;
+    both.secret = "legacy".into();
+    config.gateway.webhooks.insert("github".into(), both);
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("either secret or auth"));
+}
+
 #[test]
 fn test_enabled_webhook_rejects_agent_turn_and_dispatch_together() {
     let mut config = Config::default();
@@ -292,6 +320,59 @@ fn test_dm_policy_deserializes_from_json() {
     assert_eq!(config.channels.slack.dm_policy, DmPolicy::Allowlist);
 }
 
+#[test]
+fn test_unknown_sender_policy_resolves_from_dm_policy() {
+    assert_eq!(
+        UnknownSenderPolicy::resolve(None, &DmPolicy::Pairing),
+        UnknownSenderPolicy::Instruct
+    );
+    assert_eq!(
+        UnknownSenderPolicy::resolve(None, &DmPolicy::Allowlist),
+        UnknownSenderPolicy::Ignore
+    );
+    assert_eq!(
+        UnknownSenderPolicy::resolve(Some(UnknownSenderPolicy::NotifyAdmin), &DmPolicy::Allowlist),
+        UnknownSenderPolicy::NotifyAdmin
+    );
+}
+
+#[test]
+fn test_unknown_sender_policy_deserializes_from_json() {
+    let json = r#"{
+        "channels": {
+            "adminTarget": "telegram:42",
+            "telegram": { "enabled": false, "unknownSenderPolicy": "notifyAdmin" },
+            "slack": { "enabled": false, "unknownSenderPolicy": "ignore" }
+        }
+    }"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        config.channels.telegram.unknown_sender_policy,
+        Some(UnknownSenderPolicy::NotifyAdmin)
+    );
+    assert_eq!(
+        config.channels.slack.unknown_sender_policy,
+        Some(UnknownSenderPolicy::Ignore)
+    );
+    assert!(config.channels.discord.unknown_sender_policy.is_none());
+    let target = config.channels.admin_target.unwrap();
+    assert_eq!(target.channel_type(), "telegram");
+    assert_eq!(target.chat_id(), "42");
+}
+
+#[test]
+fn test_notify_admin_requires_admin_target() {
+    let mut config = Config::default();
+    config.channels.telegram.enabled = true;
+    config.channels.telegram.token = "tok".to_string();
+    config.channels.telegram.unknown_sender_policy = Some(UnknownSenderPolicy::NotifyAdmin);
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("channels.adminTarget"), "{err}");
+
+    config.channels.admin_target = Some("telegram:42".to_string().try_into().unwrap());
+    assert!(config.validate().is_ok());
+}
+
 #[test]
 fn test_credential_helper_config_default() {
     let config = Config::default();
@@ -441,6 +522,341 @@ fn test_invalid_compaction_keep_recent_zero() {
     );
 }
 
+// -----------------------------------------------------------------------
+// Validation: fact extraction interval with no trigger
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_invalid_extraction_interval_without_trigger() {
+    let mut config = Config::default();
+    config.agents.defaults.compaction.extraction_interval.turns = 0;
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("extractionInterval"));
+
+    config
+        .agents
+        .defaults
+        .compaction
+        .extraction_interval
+        .minutes = 30;
+    assert!(config.validate().is_ok());
+}
+
+// -----------------------------------------------------------------------
+// Validation: zero history token budget
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_invalid_max_history_tokens_zero() {
+    let mut config = Config::default();
+    config.agents.defaults.compaction.enabled = false;
+    config.agents.defaults.compaction.max_history_tokens = Some(0);
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("maxHistoryTokens must be > 0"));
+
+    config.agents.defaults.compaction.max_history_tokens = Some(8000);
+    assert!(config.validate().is_ok());
+}
+
+// -----------------------------------------------------------------------
+// Validation: enabled scratchpad with zero capacity
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_invalid_scratchpad_max_chars_zero() {
+    let mut config = Config::default();
+    config.tools.scratchpad.max_chars = 0;
+    assert!(
+        config.validate().is_ok(),
+        "disabled scratchpad is not checked"
+    );
+
+    config.tools.scratchpad.enabled = true;
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("tools.scratchpad.maxChars"));
+}
+
+// -----------------------------------------------------------------------
+// Validation: tool rate limit with zero budget
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_invalid_tool_rate_limit_zero() {
+    let mut config = Config::default();
+    config.tools.rate_limits.insert(
+        "web_search".to_string(),
+        crate::config::ToolRateLimitConfig {
+            max_calls: 0,
+            window_secs: 60,
+            max_wait_secs: 0,
+        },
+    );
+    let err = config.validate().unwrap_err();
+    let msg = err.to_string();
+    assert!(
+        msg.contains("tools.rateLimits.web_search"),
+        "expected rateLimits error in: {msg}"
+    );
+
+    let parsed: crate::config::ToolRateLimitConfig =
+        serde_json::from_value(serde_json::json!({"maxCalls": 5})).unwrap();
+    assert_eq!(parsed.window_secs, 60);
+    assert_eq!(parsed.max_wait_secs, 0);
+}
+
+// -----------------------------------------------------------------------
+// Validation: zero tool timeouts
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_invalid_tool_timeout_zero() {
+    let mut config = Config::default();
+    config.tools.timeouts.insert("web_fetch".to_string(), 0);
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(
+        msg.contains("tools.timeouts.web_fetch"),
+        "expected timeouts error in: {msg}"
+    );
+
+    let mut config = Config::default();
+    config.tools.default_timeout_secs = Some(0);
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(
+        msg.contains("tools.defaultTimeoutSecs"),
+        "expected defaultTimeoutSecs error in: {msg}"
+    );
+
+    let parsed: crate::config::ToolsConfig = serde_json::from_value(serde_json::json!({
+        "timeouts": {"browser": 45},
+        "defaultTimeoutSecs": 90
+    }))
+    .unwrap();
+    assert_eq!(parsed.timeouts.get("browser"), Some(&45));
+    assert_eq!(parsed.default_timeout_secs, Some(90));
+}
+
+#[test]
+fn test_invalid_channel_max_message_length() {
+    let mut config = Config::default();
+    config.channels.discord.max_message_length = 2001;
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(
+        msg.contains("channels.discord.maxMessageLength must be between 1 and 2000"),
+        "unexpected error: {msg}"
+    );
+
+    config.channels.discord.max_message_length = 0;
+    assert!(config.validate().is_err());
+
+    config.channels.discord.max_message_length = 1500;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_compaction_max_messages_validation() {
+    let mut config = Config::default();
+    assert!(config.agents.defaults.compaction.max_messages.is_none());
+    config.agents.defaults.compaction.keep_recent = 10;
+    config.agents.defaults.compaction.max_messages = Some(10);
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("maxMessages"), "unexpected error: {msg}");
+    config.agents.defaults.compaction.max_messages = Some(100);
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_compaction_checkpoint_model_config() {
+    let compaction: crate::config::CompactionConfig = serde_json::from_value(serde_json::json!({
+        "model": "claude-sonnet-4-5",
+        "extractionModel": "claude-haiku-4-5",
+        "checkpoint": {"model": "gpt-4o-mini"}
+    }))
+    .unwrap();
+    assert_eq!(
+        compaction.extraction_model.as_deref(),
+        Some("claude-haiku-4-5")
+    );
+    assert_eq!(compaction.checkpoint.model.as_deref(), Some("gpt-4o-mini"));
+    assert!(
+        crate::config::CompactionConfig::default()
+            .checkpoint
+            .model
+            .is_none()
+    );
+}
+
+#[test]
+fn test_inbound_media_downscale_config() {
+    let mut config = Config::default();
+    assert_eq!(
+        config.agents.defaults.inbound_media.max_image_dimension,
+        1568
+    );
+    assert_eq!(config.agents.defaults.inbound_media.jpeg_quality, 85);
+    config.agents.defaults.inbound_media.jpeg_quality = 0;
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("jpegQuality"), "unexpected error: {msg}");
+    config.agents.defaults.inbound_media.jpeg_quality = 100;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_max_concurrent_sessions_validation() {
+    let mut config = Config::default();
+    assert_eq!(config.agents.defaults.max_concurrent_sessions, 4);
+    config.agents.defaults.max_concurrent_sessions = 0;
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(
+        msg.contains("maxConcurrentSessions"),
+        "unexpected error: {msg}"
+    );
+    config.agents.defaults.max_concurrent_sessions = 65;
+    assert!(config.validate().is_err());
+    config.agents.defaults.max_concurrent_sessions = 1;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_content_filter_config() {
+    let filter: crate::config::ContentFilterConfig = serde_json::from_value(serde_json::json!({
+        "enabled": true,
+        "rules": [{"pattern": "(?i)casino"}, {"pattern": "spam", "action": "flag"}],
+        "maxLength": 2000,
+        "lengthAction": "notice"
+    }))
+    .unwrap();
+    assert_eq!(
+        filter.rules[0].action,
+        crate::config::ContentFilterAction::Drop
+    );
+    assert_eq!(
+        filter.rules[1].action,
+        crate::config::ContentFilterAction::Flag
+    );
+    assert_eq!(
+        filter.length_action,
+        crate::config::ContentFilterAction::Notice
+    );
+    assert!(!filter.notice.is_empty());
+
+    let mut config = Config::default();
+    config.agents.defaults.content_filter = filter;
+    assert!(config.validate().is_ok());
+    config.agents.defaults.content_filter.rules[0].pattern = "(unclosed".into();
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("rules[0].pattern"), "unexpected error: {msg}");
+
+    config.agents.defaults.content_filter.rules.clear();
+    config.agents.defaults.content_filter.max_length = None;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_slack_dedup_window_config() {
+    let slack: crate::config::SlackConfig =
+        serde_json::from_value(serde_json::json!({"enabled": true})).unwrap();
+    assert_eq!(slack.dedup_window_secs, 3600);
+
+    let mut config = Config::default();
+    config.channels.slack = crate::config::SlackConfig {
+        enabled: true,
+        bot_token: "xoxb-test".into(),
+        app_token: "xapp-test".into(),
+        dedup_window_secs: 0,
+        ..Default::default()
+    };
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("dedupWindowSecs"), "unexpected error: {msg}");
+
+    config.channels.slack.dedup_window_secs = 600;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_inbound_media_description_cache_config() {
+    let media: crate::config::InboundMediaConfig =
+        serde_json::from_value(serde_json::json!({"cacheDescriptions": true})).unwrap();
+    assert!(media.cache_descriptions);
+    assert_eq!(media.description_ttl_days, 30);
+    assert!(media.description_model.is_none());
+
+    let mut config = Config::default();
+    config.agents.defaults.inbound_media = media;
+    assert!(config.validate().is_ok());
+    config.agents.defaults.inbound_media.description_ttl_days = 0;
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("descriptionTtlDays"), "{msg}");
+}
+
+#[test]
+fn test_channel_send_retry_config() {
+    let mut config = Config::default();
+    assert_eq!(config.channels.send_retry.max_attempts, 3);
+    config.channels.send_retry.max_attempts = 0;
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(
+        msg.contains("channels.sendRetry.maxAttempts"),
+        "unexpected error: {msg}"
+    );
+
+    config.channels.send_retry.max_attempts = 4;
+    config.channels.send_retry.max_delay_ms = 500;
+    assert!(config.validate().is_err());
+
+    let retry: crate::config::SendRetryConfig =
+        serde_json::from_value(serde_json::json!({"initialDelayMs": 500, "maxDelayMs": 3000}))
+            .unwrap();
+    assert_eq!(retry.max_attempts, 3);
+    assert_eq!(retry.delay_after(1).as_millis(), 500);
+    assert_eq!(retry.delay_after(3).as_millis(), 2000);
+    assert_eq!(retry.delay_after(4).as_millis(), 3000);
+    assert_eq!(retry.delay_after(40).as_millis(), 3000);
+}
+
+#[test]
+fn test_channel_rate_limit_config() {
+    let mut config = Config::default();
+    assert_eq!(config.channels.rate_limit.messages_per_minute, 0);
+    assert_eq!(config.channels.rate_limit.burst, 3);
+    config.channels.rate_limit.burst = 0;
+    // Burst is only checked when pacing is on
+    assert!(config.validate().is_ok());
+    config.channels.rate_limit.messages_per_minute = 20;
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("rateLimit.burst"), "error: {err}");
+
+    let parsed: Config = serde_json::from_value(serde_json::json!({
+        "channels": {"rateLimit": {"messagesPerMinute": 20}}
+    }))
+    .unwrap();
+    assert_eq!(parsed.channels.rate_limit.messages_per_minute, 20);
+    assert_eq!(parsed.channels.rate_limit.burst, 3);
+}
+
+#[test]
+fn test_channel_quiet_hours_config() {
+    let mut config = Config::default();
+    assert!(!config.channels.quiet_hours.enabled);
+    config.channels.quiet_hours.start = "late".into();
+    // Times are only checked when quiet hours are on
+    assert!(config.validate().is_ok());
+    config.channels.quiet_hours.enabled = true;
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("HH:MM"), "error: {err}");
+    config.channels.quiet_hours.start = "07:00".into();
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("must differ"), "error: {err}");
+
+    let parsed: Config = serde_json::from_value(serde_json::json!({
+        "channels": {"quietHours": {"enabled": true, "start": "23:00", "channels": ["telegram"]}}
+    }))
+    .unwrap();
+    let quiet = &parsed.channels.quiet_hours;
+    assert_eq!(quiet.end, "07:00");
+    assert_eq!(quiet.channels, vec!["telegram".to_string()]);
+    assert!(parsed.validate().is_ok());
+}
+
 // -----------------------------------------------------------------------
 // Validation: cognitive thresholds misordered
 // -----------------------------------------------------------------------
@@ -594,6 +1010,51 @@ fn test_gateway_config_defaults() {
     assert_eq!(gw.host, "127.0.0.1");
     assert_eq!(gw.port, 18790);
     assert!(gw.webhooks.is_empty());
+    assert!(!gw.metrics.enabled);
+}
+
+#[test]
+fn test_gateway_metrics_config() {
+    let gw: GatewayConfig = serde_json::from_str(r#"{"metrics": {"enabled": true}}"#).unwrap();
+    assert!(gw.metrics.enabled);
+}
+
+#[test]
+fn test_gateway_tenants_validation() {
+    let mut config = Config::default();
+    let tenant = |key: &str| crate::config::TenantConfig {
+        api_key: key.to_string(),
+        workspace: None,
+        model: None,
+    };
+    config
+        .gateway
+        .tenants
+        .insert("alice".to_string(), tenant("alice-key"));
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("gateway.apiKey is required"), "{msg}");
+
+    config.gateway.api_key = "operator-key".to_string();
+    assert!(config.validate().is_ok());
+
+    config
+        .gateway
+        .tenants
+        .insert("bob".to_string(), tenant("alice-key"));
+    assert!(config.validate().is_err(), "duplicate tenant keys accepted");
+    config
+        .gateway
+        .tenants
+        .insert("bob".to_string(), tenant("operator-key"));
+    assert!(config.validate().is_err(), "operator key reused by tenant");
+    config.gateway.tenants.remove("bob");
+
+    config
+        .gateway
+        .tenants
+        .insert("../evil".to_string(), tenant("evil-key"));
+    let msg = config.validate().unwrap_err().to_string();
+    assert!(msg.contains("must contain only"), "{msg}");
 }
 
 // -----------------------------------------------------------------------
@@ -659,16 +1120,33 @@ fn test_prompt_guard_action_display_and_should_block() {
     let warn_guard = PromptGuardConfig {
         enabled: true,
         action: PromptGuardAction::Warn,
+        ..Default::default()
     };
     assert!(!warn_guard.should_block());
 
     let block_guard = PromptGuardConfig {
         enabled: true,
         action: PromptGuardAction::Block,
+        ..Default::default()
     };
     assert!(block_guard.should_block());
 }
 
+#[test]
+fn test_prompt_guard_extra_patterns() {
+    let json = r#"{"enabled": true, "extraPatterns": [
+        {"name": "acme_dump", "category": "secret_extraction", "regex": "(?i)acme ledger"}
+    ]}"#;
+    let config: PromptGuardConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(config.extra_patterns.len(), 1);
+    assert_eq!(config.extra_patterns[0].name, "acme_dump");
+    assert_eq!(
+        config.extra_patterns[0].category,
+        PromptGuardCategory::SecretExtraction
+    );
+    assert!(PromptGuardConfig::default().extra_patterns.is_empty());
+}
+
 // -----------------------------------------------------------------------
 // FusionStrategy default + serde
 // -----------------------------------------------------------------------
@@ -684,6 +1162,16 @@ fn test_fusion_strategy_default_and_serde() {
     assert_eq!(rrf, FusionStrategy::Rrf);
 }
 
+#[test]
+fn test_embedding_failure_mode_default_and_serde() {
+    let config = MemoryConfig::default();
+    assert_eq!(config.on_embedding_failure, EmbeddingFailureMode::Degrade);
+
+    let json = r#"{"onEmbeddingFailure": "error"}"#;
+    let config: MemoryConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(config.on_embedding_failure, EmbeddingFailureMode::Error);
+}
+
 // -----------------------------------------------------------------------
 // LocalProviderConfig: promptGuidedTools only on ollama/vllm
 // -----------------------------------------------------------------------
@@ -713,6 +1201,10 @@ fn credential_overlays() -> Vec<(&'static str, serde_json::Value)> {
     vec![
         // --- Fix f32 serialization artifacts ---
         ("/agents/defaults/temperature", json!(0.7)),
+        (
+            "/agents/defaults/memory/gardener/similarityThreshold",
+            json!(0.85),
+        ),
         // --- Credential helper example ---
         ("/credentialHelper/format", json!("json")),
         // --- Provider API keys ---
@@ -1005,12 +1497,48 @@ fn test_model_routing_chat_task_deserializes() {
             assert!((chat.thresholds.heavy - 0.65).abs() < f64::EPSILON);
             assert_eq!(chat.models.standard, "anthropic/claude-sonnet-4-5-20250929");
             assert_eq!(chat.models.heavy, "anthropic/claude-opus-4-6");
+            assert!(chat.models.light.is_none());
         }
         crate::config::TaskRouting::Model(_) => panic!("expected Chat variant"),
     }
     config.validate().expect("should validate");
 }
 
+#[test]
+fn test_model_routing_chat_light_model() {
+    let json = r#"{
+        "agents": {
+            "defaults": {
+                "modelRouting": {
+                    "tasks": {
+                        "chat": {
+                            "models": {
+                                "light": "anthropic/claude-haiku-4-5-20251001",
+                                "standard": "model-a",
+                                "heavy": "model-b"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    match &config.agents.defaults.model_routing.tasks["chat"] {
+        crate::config::TaskRouting::Chat(chat) => assert_eq!(
+            chat.models.light.as_deref(),
+            Some("anthropic/claude-haiku-4-5-20251001")
+        ),
+        crate::config::TaskRouting::Model(_) => panic!("expected Chat variant"),
+    }
+    config.validate().expect("should validate");
+
+    let json = json.replace("anthropic/claude-haiku-4-5-20251001", "");
+    let config: Config = serde_json::from_str(&json).unwrap();
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("models.light"), "error: {err}");
+}
+
 #[test]
 fn test_model_routing_default_has_model() {
     let config = Config::default();
@@ -1200,17 +1728,38 @@ fn test_context_provider_valid_config_passes() {
         .context_providers
         .push(ContextProviderConfig {
             name: "test".into(),
+            kind: ContextProviderKind::Command,
             command: "echo".into(),
             args: vec![],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec!["git".into(), "node".into()],
             requires_env: vec!["HOME".into(), "PATH".into()],
         });
     assert!(config.validate().is_ok());
 }
 
+#[test]
+fn test_context_provider_kind_command_requires_command() {
+    let json = r#"{"agents": {"defaults": {"contextProviders": [
+        {"name": "Weather", "kind": "weather"},
+        {"name": "Git"}
+    ]}}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    let providers = &config.agents.defaults.context_providers;
+    assert_eq!(providers[0].kind, ContextProviderKind::Weather);
+    assert_eq!(providers[0].effective_ttl(), 1800);
+    assert_eq!(providers[1].kind, ContextProviderKind::Command);
+    assert_eq!(providers[1].effective_ttl(), 300);
+
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("contextProviders[1].command is required")
+    );
+}
+
 #[test]
 fn test_context_provider_empty_requires_bins_entry_rejected() {
     let mut config = Config::default();
@@ -1220,11 +1769,12 @@ fn test_context_provider_empty_requires_bins_entry_rejected() {
         .context_providers
         .push(ContextProviderConfig {
             name: "test".into(),
+            kind: ContextProviderKind::Command,
             command: "echo".into(),
             args: vec![],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec![String::new()],
             requires_env: vec![],
         });
@@ -1244,11 +1794,12 @@ fn test_context_provider_path_in_requires_bins_rejected() {
         .context_providers
         .push(ContextProviderConfig {
             name: "test".into(),
+            kind: ContextProviderKind::Command,
             command: "echo".into(),
             args: vec![],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec!["/usr/bin/git".into()],
             requires_env: vec![],
         });
@@ -1268,11 +1819,12 @@ fn test_context_provider_control_chars_in_requires_bins_rejected() {
         .context_providers
         .push(ContextProviderConfig {
             name: "test".into(),
+            kind: ContextProviderKind::Command,
             command: "echo".into(),
             args: vec![],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec!["git\n".into()],
             requires_env: vec![],
         });
@@ -1289,11 +1841,12 @@ fn test_context_provider_empty_requires_env_entry_rejected() {
         .context_providers
         .push(ContextProviderConfig {
             name: "test".into(),
+            kind: ContextProviderKind::Command,
             command: "echo".into(),
             args: vec![],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec![],
             requires_env: vec![String::new()],
         });
@@ -1310,11 +1863,12 @@ fn test_context_provider_equals_in_requires_env_rejected() {
         .context_providers
         .push(ContextProviderConfig {
             name: "test".into(),
+            kind: ContextProviderKind::Command,
             command: "echo".into(),
             args: vec![],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec![],
             requires_env: vec!["FOO=bar".into()],
         });
@@ -1331,14 +1885,527 @@ fn test_context_provider_control_chars_in_requires_env_rejected() {
         .context_providers
         .push(ContextProviderConfig {
             name: "test".into(),
+            kind: ContextProviderKind::Command,
             command: "echo".into(),
             args: vec![],
             enabled: true,
             timeout: 5,
-            ttl: 300,
+            ttl: Some(300),
             requires_bins: vec![],
             requires_env: vec!["HOME\x00".into()],
         });
     let err = config.validate().unwrap_err();
     assert!(err.to_string().contains("control characters"));
 }
+
+#[test]
+fn test_memory_gardener_validation() {
+    let mut config = Config::default();
+    config.agents.defaults.memory.gardener.enabled = true;
+    assert!(config.validate().is_ok());
+
+    config.agents.defaults.memory.gardener.interval_hours = 0;
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("intervalHours"), "error: {err}");
+
+    config.agents.defaults.memory.gardener.interval_hours = 24;
+    config.agents.defaults.memory.gardener.similarity_threshold = 1.5;
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("similarityThreshold"),
+        "error: {err}"
+    );
+}
+
+#[test]
+fn test_memory_openai_embeddings_backend() {
+    let json = r#"{"agents": {"defaults": {"memory": {"embeddingsBackend": "openai", "embeddingsModel": "text-embedding-3-small"}}}}"#;
+    let mut config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        config.agents.defaults.memory.embeddings_backend,
+        EmbeddingsBackend::OpenAi
+    );
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("providers.openai.apiKey"),
+        "error: {err}"
+    );
+
+    config.providers.openai.api_key = "sk-test".into();
+    assert!(config.validate().is_ok());
+
+    config.agents.defaults.memory.embeddings_model = "BAAI/bge-small-en-v1.5".into();
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("embeddingsModel"), "error: {err}");
+
+    // Not checked while embeddings are off
+    config.agents.defaults.memory.embeddings_enabled = false;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_reasoning_config_phases_and_tasks() {
+    use oxicrab_core::providers::base::ReasoningEffort;
+
+    let json = r#"{"agents": {"defaults": {"reasoning": {"chat": "low", "tools": "high", "cron": "medium"}}}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    let reasoning = &config.agents.defaults.reasoning;
+    assert_eq!(reasoning.for_phase(false), Some(ReasoningEffort::Low));
+    assert_eq!(reasoning.for_phase(true), Some(ReasoningEffort::High));
+    assert_eq!(reasoning.for_task("cron"), Some(ReasoningEffort::Medium));
+    assert_eq!(reasoning.for_task("subagent"), None);
+
+    let chat_only = ReasoningConfig {
+        chat: Some(ReasoningEffort::Medium),
+        ..Default::default()
+    };
+    assert_eq!(chat_only.for_phase(true), Some(ReasoningEffort::Medium));
+
+    let invalid = r#"{"agents": {"defaults": {"reasoning": {"chat": "extreme"}}}}"#;
+    assert!(serde_json::from_str::<Config>(invalid).is_err());
+}
+
+#[test]
+fn test_empty_response_fallback_requires_model() {
+    let mut config = Config::default();
+    config.agents.defaults.empty_response.action = EmptyResponseAction::Fallback;
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("fallbackModel"), "error: {err}");
+
+    config.agents.defaults.empty_response.fallback_model =
+        Some("anthropic/claude-haiku-4-5-20251001".into());
+    assert!(config.validate().is_ok());
+
+    let json = r#"{"agents": {"defaults": {"emptyResponse": {"action": "reprompt"}}}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        config.agents.defaults.empty_response.action,
+        EmptyResponseAction::Reprompt
+    );
+}
+
+#[test]
+fn test_session_archive_days_validation() {
+    let mut config = Config::default();
+    config.agents.defaults.session_archive_days = 14;
+    assert!(config.validate().is_ok());
+
+    config.agents.defaults.session_archive_days = 30;
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("sessionTtlDays"), "error: {err}");
+
+    // No TTL: any archive threshold works
+    config.agents.defaults.session_ttl_days = 0;
+    assert!(config.validate().is_ok());
+
+    config.agents.defaults.compaction.enabled = false;
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("compaction"), "error: {err}");
+}
+
+#[test]
+fn test_tool_loop_config() {
+    let json = r#"{"agents": {"defaults": {"toolLoop": {"threshold": 4, "action": "finalize"}}}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(config.agents.defaults.tool_loop.threshold, 4);
+    assert_eq!(
+        config.agents.defaults.tool_loop.action,
+        crate::config::ToolLoopAction::Finalize
+    );
+    assert!(config.validate().is_ok());
+
+    let config = Config::default();
+    assert_eq!(config.agents.defaults.tool_loop.threshold, 3);
+    assert_eq!(
+        config.agents.defaults.tool_loop.action,
+        crate::config::ToolLoopAction::Nudge
+    );
+
+    let mut config = Config::default();
+    config.agents.defaults.tool_loop.threshold = 1;
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("toolLoop.threshold"),
+        "error: {err}"
+    );
+    config.agents.defaults.tool_loop.threshold = 0;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_weather_alerts_config() {
+    let json = r#"{"tools": {"weather": {
+        "enabled": true,
+        "apiKey": "k",
+        "locations": ["Oslo,NO"],
+        "units": "metric",
+        "contextConditions": true,
+        "alerts": {"enabled": true, "targets": ["telegram:42"], "minTemp": -15, "maxWind": 20}
+    }}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    let weather = &config.tools.weather;
+    assert_eq!(weather.units, crate::config::WeatherUnits::Metric);
+    assert!(weather.context_conditions);
+    assert_eq!(weather.alerts.interval_minutes, 60);
+    assert_eq!(weather.alerts.targets[0].chat_id(), "42");
+    assert_eq!(weather.alerts.min_temp, Some(-15.0));
+    assert_eq!(weather.alerts.max_temp, None);
+    assert!(weather.alerts.severe_conditions);
+    assert!(config.validate().is_ok());
+
+    let mut config = config;
+    config.tools.weather.alerts.targets.clear();
+    let err = config.validate().unwrap_err();
+    assert!(err.to_string().contains("alerts.targets"), "error: {err}");
+
+    let mut config = Config::default();
+    assert_eq!(
+        config.tools.weather.units,
+        crate::config::WeatherUnits::Imperial
+    );
+    config.tools.weather.alerts.enabled = true;
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("weather.locations"),
+        "error: {err}"
+    );
+}
+
+#[test]
+fn test_typing_indicator_config() {
+    use crate::config::TypingIndicatorStyle;
+
+    let config = Config::default();
+    let telegram = config.channels.telegram.typing_indicator;
+    assert!(telegram.enabled);
+    assert_eq!(telegram.interval_secs, 4);
+    assert_eq!(telegram.style, TypingIndicatorStyle::Native);
+    let slack = config.channels.slack.typing_indicator;
+    assert!(!slack.enabled);
+    assert_eq!(slack.style, TypingIndicatorStyle::Status);
+
+    let json = r#"{"channels": {
+        "discord": {"typingIndicator": {"enabled": false}},
+        "slack": {"typingIndicator": {"enabled": true, "style": "status"}}
+    }}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    let indicators = config.channels.typing_indicators();
+    assert!(!indicators["discord"].enabled);
+    assert_eq!(indicators["discord"].interval_secs, 4);
+    assert!(indicators["slack"].enabled);
+    assert!(config.validate().is_ok());
+
+    let mut config = Config::default();
+    config.channels.telegram.typing_indicator.interval_secs = 0;
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("channels.telegram.typingIndicator.intervalSecs"),
+        "error: {err}"
+    );
+}
+
+#[test]
+fn test_persona_suffix_config() {
+    let json = r#"{"channels": {
+        "slack": {"personaSuffix": "Be formal."},
+        "whatsapp": {"personaSuffix": "  "}
+    }}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    let suffixes = config.channels.persona_suffixes();
+    assert_eq!(suffixes.len(), 1);
+    assert_eq!(suffixes["slack"], "Be formal.");
+    assert!(Config::default().channels.persona_suffixes().is_empty());
+}
+
+#[test]
+fn test_admin_senders_validation() {
+    let json = r#"{"channels": {"adminSenders": ["telegram:123", "slack:U08G6HBC89X"]}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(config.channels.admin_senders.len(), 2);
+    assert!(config.validate().is_ok());
+
+    for bad in ["123", "telegram:", ":123"] {
+        let mut config = Config::default();
+        config.channels.admin_senders = vec![bad.to_string()];
+        let err = config.validate().unwrap_err();
+        assert!(
+            err.to_string().contains("channels.adminSenders"),
+            "error: {err}"
+        );
+    }
+}
+
+#[test]
+fn test_tool_result_summary_config() {
+    let json = r#"{"agents": {"defaults": {"toolResultSummary": {"enabled": true, "thresholdChars": 4000, "model": "anthropic/claude-haiku-4-5-20251001"}}}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    let summary = &config.agents.defaults.tool_result_summary;
+    assert!(summary.enabled);
+    assert_eq!(summary.threshold_chars, 4000);
+    assert_eq!(
+        summary.model.as_deref(),
+        Some("anthropic/claude-haiku-4-5-20251001")
+    );
+    assert!(config.validate().is_ok());
+
+    let config = Config::default();
+    assert!(!config.agents.defaults.tool_result_summary.enabled);
+    assert_eq!(
+        config.agents.defaults.tool_result_summary.threshold_chars,
+        6000
+    );
+
+    let mut config = Config::default();
+    config.agents.defaults.tool_result_summary.enabled = true;
+    config.agents.defaults.tool_result_summary.threshold_chars = 0;
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("toolResultSummary.thresholdChars"),
+        "error: {err}"
+    );
+}
+
+#[test]
+fn test_model_aliases() {
+    let json = r#"{"models": {"aliases": {"fast": "anthropic/claude-haiku-4-5-20251001"}},
+        "agents": {"defaults": {"modelRouting": {"default": "fast"}}}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert!(config.validate().is_ok());
+    assert_eq!(
+        config.models.resolve("fast"),
+        "anthropic/claude-haiku-4-5-20251001"
+    );
+    assert_eq!(config.models.resolve("gpt-4o"), "gpt-4o");
+
+    for (alias, target, expected) in [
+        ("", "gpt-4o", "non-empty name"),
+        ("openai/fast", "gpt-4o", "without '/'"),
+        ("fast", "", "must not be empty"),
+        ("fast", "smart", "another alias"),
+    ] {
+        let mut config = Config::default();
+        config
+            .models
+            .aliases
+            .insert("smart".to_string(), "claude-opus-4-6".to_string());
+        config
+            .models
+            .aliases
+            .insert(alias.to_string(), target.to_string());
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains(expected), "error: {err}");
+    }
+}
+
+#[test]
+fn test_message_batching_config() {
+    let json = r#"{"agents": {"defaults": {"messageBatching": {"windowMs": 1500}}}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(config.agents.defaults.message_batching.window_ms, 1500);
+    assert_eq!(config.agents.defaults.message_batching.max_wait_ms, 10_000);
+    assert!(config.validate().is_ok());
+
+    assert_eq!(
+        Config::default().agents.defaults.message_batching.window_ms,
+        0
+    );
+
+    let mut config = Config::default();
+    config.agents.defaults.message_batching.window_ms = 2000;
+    config.agents.defaults.message_batching.max_wait_ms = 1000;
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("messageBatching.maxWaitMs"),
+        "error: {err}"
+    );
+}
+
+#[test]
+fn test_tool_presets_config() {
+    let json = r#"{"agents": {"defaults": {"toolPresets": {
+        "research": ["web_search", "web_fetch", "memory_search"]
+    }}}}"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        config.agents.defaults.tool_presets["research"],
+        ["web_search", "web_fetch", "memory_search"]
+    );
+    assert!(config.validate().is_ok());
+    assert!(Config::default().agents.defaults.tool_presets.is_empty());
+
+    for (name, tools, expected) in [
+        (
+            "deep work",
+            vec!["read_file".to_string()],
+            "invalid preset name",
+        ),
+        ("empty", vec![], "toolPresets.empty must list"),
+    ] {
+        let mut config = Config::default();
+        config
+            .agents
+            .defaults
+            .tool_presets
+            .insert(name.to_string(), tools);
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains(expected), "error: {err}");
+    }
+}
+
+#[test]
+fn test_delegation_config_defaults_and_serde() {
+    let config = Config::default();
+    let delegation = &config.agents.defaults.delegation;
+    assert_eq!(delegation.max_per_turn, 0);
+    assert_eq!(delegation.suggest_after_tool_calls, 0);
+    assert!(delegation.announce);
+    assert!(!delegation.stream_tool_activity);
+    assert!(delegation.allowed_tools.is_none());
+
+    let parsed: Config = serde_json::from_value(serde_json::json!({
+        "agents": {"defaults": {"delegation": {
+            "maxPerTurn": 2,
+            "suggestAfterToolCalls": 8,
+            "streamToolActivity": true,
+            "allowedTools": ["read_file", "web_fetch"]
+        }}}
+    }))
+    .unwrap();
+    let delegation = &parsed.agents.defaults.delegation;
+    assert_eq!(delegation.max_per_turn, 2);
+    assert_eq!(delegation.suggest_after_tool_calls, 8);
+    assert!(delegation.announce);
+    assert!(delegation.stream_tool_activity);
+    assert_eq!(
+        delegation.allowed_tools.as_deref(),
+        Some(&["read_file".to_string(), "web_fetch".to_string()][..])
+    );
+}
+
+#[test]
+fn test_offline_mode_requires_circuit_breaker() {
+    let config = Config::default();
+    let offline = &config.agents.defaults.offline_mode;
+    assert!(!offline.enabled);
+    assert_eq!(offline.retry_interval_secs, 60);
+    assert_eq!(offline.max_queued, 100);
+
+    let mut config: Config = serde_json::from_value(serde_json::json!({
+        "agents": {"defaults": {"offlineMode": {
+            "enabled": true,
+            "reply": "Back soon",
+            "retryIntervalSecs": 30
+        }}}
+    }))
+    .unwrap();
+    assert_eq!(config.agents.defaults.offline_mode.reply, "Back soon");
+    assert_eq!(config.agents.defaults.offline_mode.retry_interval_secs, 30);
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("requires providers.circuitBreaker.enabled"),
+        "error: {err}"
+    );
+
+    config.providers.circuit_breaker.enabled = true;
+    assert!(config.validate().is_ok());
+    config.agents.defaults.offline_mode.max_queued = 0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_streaming_config() {
+    let config = Config::default();
+    assert!(!config.agents.defaults.streaming.enabled);
+    assert_eq!(config.agents.defaults.streaming.edit_interval_ms, 1000);
+
+    let mut config: Config = serde_json::from_value(serde_json::json!({
+        "agents": {"defaults": {"streaming": {"enabled": true, "editIntervalMs": 500}}}
+    }))
+    .unwrap();
+    assert_eq!(config.agents.defaults.streaming.edit_interval_ms, 500);
+    assert!(config.validate().is_ok());
+    config.agents.defaults.streaming.edit_interval_ms = 0;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_provider_retry_config() {
+    let config = Config::default();
+    assert_eq!(config.providers.retry.max_retries, 3);
+    assert_eq!(config.providers.retry.base_delay_ms, 1000);
+
+    let mut config: Config = serde_json::from_value(serde_json::json!({
+        "providers": {"retry": {"maxRetries": 5, "baseDelayMs": 500, "jitter": 0.1}}
+    }))
+    .unwrap();
+    assert_eq!(config.providers.retry.max_retries, 5);
+    assert_eq!(config.providers.retry.max_delay_ms, 10_000);
+    assert!(config.validate().is_ok());
+    config.providers.retry.jitter = 1.5;
+    assert!(config.validate().is_err());
+    config.providers.retry.jitter = 0.25;
+    config.providers.retry.base_delay_ms = 20_000;
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_provider_failover_config() {
+    let config = Config::default();
+    assert!(config.providers.failover.secondary_model_ref().is_none());
+
+    let mut config: Config = serde_json::from_value(serde_json::json!({
+        "providers": {"failover": {"secondary": "OpenAI"}}
+    }))
+    .unwrap();
+    assert_eq!(
+        config.providers.failover.secondary_model_ref().as_deref(),
+        Some("openai/gpt-4o")
+    );
+    assert!(config.validate().is_ok());
+
+    // No built-in default model for groq
+    config.providers.failover.secondary = Some("groq".into());
+    assert!(config.validate().is_err());
+    config.providers.failover.model = Some("llama-3.3-70b-versatile".into());
+    assert_eq!(
+        config.providers.failover.secondary_model_ref().as_deref(),
+        Some("groq/llama-3.3-70b-versatile")
+    );
+    assert!(config.validate().is_ok());
+
+    config.providers.failover.secondary = Some("acme".into());
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_profiles_config() {
+    let mut config: Config = serde_json::from_value(serde_json::json!({
+        "agents": {"profiles": [
+            {"name": "work", "channels": ["slack"]},
+            {"name": "family", "channels": ["telegram"], "chats": ["-100*"]}
+        ]}
+    }))
+    .unwrap();
+    assert!(config.validate().is_ok());
+    let [work, family] = config.agents.profiles.as_slice() else {
+        panic!("expected two profiles");
+    };
+    assert!(work.workspace.is_none());
+    assert!(work.matches("slack", "C042"));
+    assert!(!work.matches("telegram", "C042"));
+    assert!(family.matches("telegram", "-1001234"));
+    assert!(!family.matches("telegram", "55512"));
+
+    config.agents.profiles[1].name = "work".into();
+    assert!(config.validate().is_err());
+    config.agents.profiles[1].name = "default".into();
+    assert!(config.validate().is_err());
+    config.agents.profiles[1].name = "my family".into();
+    assert!(config.validate().is_err());
+    config.agents.profiles[1].name = "family".into();
+    config.agents.profiles[1].channels.clear();
+    config.agents.profiles[1].chats.clear();
+    assert!(config.validate().is_err());
+}
@@ -17,6 +17,7 @@ fn make_event_job(id: &str, pattern: &str, channel: Option<&str>) -> CronJob {
             targets: vec![CronTarget {
                 channel: "telegram".to_string(),
                 to: "user1".to_string(),
+                message_override: None,
             }],
         },
         state: CronJobState::default(),
@@ -1,3 +1,4 @@
 pub mod event_matcher;
 pub mod service;
+pub mod solar;
 pub mod types;
@@ -66,6 +66,12 @@ fn compute_next_run_with_last(
         }),
         // Event jobs don't poll — they fire in response to messages
         CronSchedule::Event { .. } => None,
+        CronSchedule::Solar {
+            event,
+            lat,
+            lon,
+            offset_minutes,
+        } => crate::cron::solar::next_solar_event(*event, *lat, *lon, *offset_minutes, now_ms),
         CronSchedule::Cron { expr, tz } => {
             if let Some(expr_str) = expr {
                 // validate_cron_expr normalizes and validates the expression;
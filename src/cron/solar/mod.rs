@@ -0,0 +1,83 @@
+//! Sunrise/sunset times for `CronSchedule::Solar` jobs.
+//!
+//! Uses the sunrise equation (NOAA simplification) which is accurate to
+//! about a minute at non-polar latitudes — plenty for a scheduler.
+
+use crate::cron::types::SolarEvent;
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+/// Julian date of the Unix epoch.
+const JD_UNIX_EPOCH: f64 = 2_440_587.5;
+/// Julian date of J2000.0 (2000-01-01 12:00 TT).
+const JD_J2000: f64 = 2_451_545.0;
+/// Solar altitude at rise/set: refraction plus the solar disc radius.
+const HORIZON_DEG: f64 = -0.833;
+const OBLIQUITY_DEG: f64 = 23.4397;
+/// How far ahead to look for the next event. Covers the longest polar
+/// night or midnight-sun period (about six months at the poles).
+const MAX_SEARCH_DAYS: i64 = 370;
+
+/// Validate solar schedule parameters.
+pub fn validate_solar(lat: f64, lon: f64, offset_minutes: i64) -> anyhow::Result<()> {
+    if !(-90.0..=90.0).contains(&lat) {
+        anyhow::bail!("latitude must be between -90 and 90, got {lat}");
+    }
+    if !(-180.0..=180.0).contains(&lon) {
+        anyhow::bail!("longitude must be between -180 and 180, got {lon}");
+    }
+    if !(-720..=720).contains(&offset_minutes) {
+        anyhow::bail!("offset must be between -720 and 720 minutes, got {offset_minutes}");
+    }
+    Ok(())
+}
+
+/// Time of `event` in Unix milliseconds for the solar day `day` (days since
+/// J2000.0) at the given location, or `None` when the sun does not cross the
+/// horizon that day (polar night or midnight sun).
+fn event_on_day(event: SolarEvent, lat: f64, lon: f64, day: i64) -> Option<f64> {
+    // Mean solar time at the longitude (east positive)
+    let mean_solar = day as f64 - lon / 360.0;
+    let anomaly = (357.5291 + 0.985_600_28 * mean_solar).rem_euclid(360.0);
+    let m = anomaly.to_radians();
+    let center = 1.9148 * m.sin() + 0.0200 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+    let ecliptic_lon = (anomaly + center + 180.0 + 102.9372)
+        .rem_euclid(360.0)
+        .to_radians();
+    let transit = JD_J2000 + mean_solar + 0.0053 * m.sin() - 0.0069 * (2.0 * ecliptic_lon).sin();
+    let declination = (ecliptic_lon.sin() * OBLIQUITY_DEG.to_radians().sin()).asin();
+
+    let phi = lat.to_radians();
+    let cos_hour_angle = (HORIZON_DEG.to_radians().sin() - phi.sin() * declination.sin())
+        / (phi.cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    let half_day = cos_hour_angle.acos().to_degrees() / 360.0;
+    let jd = match event {
+        SolarEvent::Sunrise => transit - half_day,
+        SolarEvent::Sunset => transit + half_day,
+    };
+    Some((jd - JD_UNIX_EPOCH) * MS_PER_DAY)
+}
+
+/// Next occurrence of `event` (shifted by `offset_minutes`) strictly after
+/// `now_ms`. Days on which the event does not occur are skipped.
+pub fn next_solar_event(
+    event: SolarEvent,
+    lat: f64,
+    lon: f64,
+    offset_minutes: i64,
+    now_ms: i64,
+) -> Option<i64> {
+    let offset_ms = offset_minutes.saturating_mul(60_000);
+    let now_day = ((now_ms as f64 / MS_PER_DAY + JD_UNIX_EPOCH) - JD_J2000).floor() as i64;
+    // Start a day early: with a large offset or a far-west longitude,
+    // yesterday's event can still be in the future.
+    (now_day - 1..now_day + MAX_SEARCH_DAYS)
+        .filter_map(|day| event_on_day(event, lat, lon, day))
+        .map(|ms| (ms.round() as i64).saturating_add(offset_ms))
+        .find(|&fire_ms| fire_ms > now_ms)
+}
+
+#[cfg(test)]
+mod tests;
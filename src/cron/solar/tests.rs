@@ -0,0 +1,70 @@
+use super::*;
+use chrono::{TimeZone, Utc};
+
+const LONDON: (f64, f64) = (51.5074, -0.1278);
+const TROMSO: (f64, f64) = (69.6496, 18.9560);
+
+fn utc_ms(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> i64 {
+    Utc.with_ymd_and_hms(y, mo, d, h, mi, 0)
+        .unwrap()
+        .timestamp_millis()
+}
+
+fn assert_near(actual: i64, expected: i64, tolerance_min: i64) {
+    let diff_min = (actual - expected).abs() / 60_000;
+    assert!(
+        diff_min <= tolerance_min,
+        "{} is {diff_min} min away from {}",
+        Utc.timestamp_millis_opt(actual).unwrap(),
+        Utc.timestamp_millis_opt(expected).unwrap()
+    );
+}
+
+#[test]
+fn test_london_midsummer_sunrise_and_sunset() {
+    let (lat, lon) = LONDON;
+    let now = utc_ms(2024, 6, 21, 0, 0);
+    let sunrise = next_solar_event(SolarEvent::Sunrise, lat, lon, 0, now).unwrap();
+    assert_near(sunrise, utc_ms(2024, 6, 21, 3, 43), 3);
+    let sunset = next_solar_event(SolarEvent::Sunset, lat, lon, 0, now).unwrap();
+    assert_near(sunset, utc_ms(2024, 6, 21, 20, 21), 3);
+}
+
+#[test]
+fn test_offset_and_strictly_after_now() {
+    let (lat, lon) = LONDON;
+    let now = utc_ms(2024, 6, 21, 0, 0);
+    let sunrise = next_solar_event(SolarEvent::Sunrise, lat, lon, 0, now).unwrap();
+    let early = next_solar_event(SolarEvent::Sunrise, lat, lon, -30, now).unwrap();
+    assert_eq!(sunrise - early, 30 * 60_000);
+
+    // At the event itself, the next fire is the following day
+    let next = next_solar_event(SolarEvent::Sunrise, lat, lon, 0, sunrise).unwrap();
+    assert_near(next, sunrise + 86_400_000, 2);
+}
+
+#[test]
+fn test_polar_night_skips_to_first_sunrise() {
+    let (lat, lon) = TROMSO;
+    let now = utc_ms(2024, 12, 15, 12, 0);
+    let sunrise = next_solar_event(SolarEvent::Sunrise, lat, lon, 0, now).unwrap();
+    assert!(sunrise > utc_ms(2025, 1, 10, 0, 0), "sunrise too early");
+    assert!(sunrise < utc_ms(2025, 1, 20, 0, 0), "sunrise too late");
+}
+
+#[test]
+fn test_midnight_sun_skips_to_first_sunset() {
+    let (lat, lon) = TROMSO;
+    let now = utc_ms(2024, 6, 1, 12, 0);
+    let sunset = next_solar_event(SolarEvent::Sunset, lat, lon, 0, now).unwrap();
+    assert!(sunset > utc_ms(2024, 7, 15, 0, 0), "sunset too early");
+    assert!(sunset < utc_ms(2024, 7, 31, 0, 0), "sunset too late");
+}
+
+#[test]
+fn test_validate_solar_ranges() {
+    assert!(validate_solar(51.5, -0.1, 0).is_ok());
+    assert!(validate_solar(91.0, 0.0, 0).is_err());
+    assert!(validate_solar(0.0, -181.0, 0).is_err());
+    assert!(validate_solar(0.0, 0.0, 721).is_err());
+}
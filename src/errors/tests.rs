@@ -1,46 +1,53 @@
 use super::*;
+use oxicrab_core::errors::ProviderError;
 
 #[test]
 fn rate_limit_is_provider_error() {
-    let err = OxicrabError::RateLimit {
+    let err = OxicrabError::from(ProviderError::RateLimit {
         retry_after: Some(30),
-    };
-    assert!(matches!(err, OxicrabError::RateLimit { .. }));
+    });
+    assert!(matches!(
+        err,
+        OxicrabError::Provider(ProviderError::RateLimit { .. })
+    ));
 }
 
 #[test]
 fn auth_error_variant() {
-    let err = OxicrabError::Auth("invalid key".into());
-    assert!(matches!(err, OxicrabError::Auth(..)));
+    let err = OxicrabError::from(ProviderError::Auth("invalid key".into()));
+    assert!(matches!(
+        err,
+        OxicrabError::Provider(ProviderError::Auth(..))
+    ));
 }
 
 #[test]
 fn is_retryable_provider() {
-    let retryable = OxicrabError::Provider {
+    let retryable = OxicrabError::from(ProviderError::Api {
         message: "timeout".into(),
         retryable: true,
-    };
+    });
     assert!(retryable.is_retryable());
 
-    let not_retryable = OxicrabError::Provider {
+    let not_retryable = OxicrabError::from(ProviderError::Api {
         message: "bad request".into(),
         retryable: false,
-    };
+    });
     assert!(!not_retryable.is_retryable());
 }
 
 #[test]
 fn is_retryable_auth_config() {
-    assert!(!OxicrabError::Auth("bad key".into()).is_retryable());
+    assert!(!OxicrabError::from(ProviderError::Auth("bad key".into())).is_retryable());
     assert!(!OxicrabError::Config("missing field".into()).is_retryable());
 }
 
 #[test]
 fn is_retryable_rate_limit() {
     assert!(
-        OxicrabError::RateLimit {
+        OxicrabError::from(ProviderError::RateLimit {
             retry_after: Some(30)
-        }
+        })
         .is_retryable()
     );
 }
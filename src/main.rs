@@ -9,7 +9,19 @@ async fn main() -> Result<()> {
         .add_directive("selectors=off".parse().unwrap())
         .add_directive("html5ever=off".parse().unwrap())
         .add_directive("hyper_util=warn".parse().unwrap());
-    tracing_subscriber::fmt().with_env_filter(filter).init();
+    // OXICRAB_LOG_FORMAT=json emits one JSON object per line (for log
+    // collectors); the span carries the conversation's channel and chat_id
+    if std::env::var("OXICRAB_LOG_FORMAT").is_ok_and(|v| v.eq_ignore_ascii_case("json")) {
+        tracing_subscriber::fmt()
+            .json()
+            .with_target(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_env_filter(filter)
+            .init();
+    } else {
+        tracing_subscriber::fmt().with_env_filter(filter).init();
+    }
 
     oxicrab::cli::run().await
 }
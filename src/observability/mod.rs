@@ -1,9 +1,10 @@
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use std::net::SocketAddr;
 use std::sync::OnceLock;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{info, warn};
 
-static METRICS_INIT: OnceLock<()> = OnceLock::new();
+static METRICS_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
 static BASELINE_LOOP_INIT: OnceLock<()> = OnceLock::new();
 static PROCESS_START_MONO: OnceLock<Instant> = OnceLock::new();
 static PROCESS_START_UNIX: OnceLock<f64> = OnceLock::new();
@@ -17,32 +18,58 @@ struct ProcessMetrics {
     cpu_seconds: Option<f64>,
 }
 
+/// Install the global Prometheus recorder when either the standalone
+/// exporter (`observability.metrics`) or the gateway's `/metrics` route
+/// (`gateway.metrics`) is enabled. Both read the same registry.
 pub fn init_metrics_exporter(config: &crate::config::Config) {
     let metrics_cfg = &config.observability.metrics;
-    if !metrics_cfg.enabled {
+    let gateway_metrics = config.gateway.enabled && config.gateway.metrics.enabled;
+    if !metrics_cfg.enabled && !gateway_metrics {
         return;
     }
 
-    if METRICS_INIT.get().is_some() {
+    if METRICS_HANDLE.get().is_some() {
         return;
     }
 
-    let Ok(addr) = metrics_cfg.bind.parse::<SocketAddr>() else {
-        warn!(
-            "metrics exporter disabled: invalid observability.metrics.bind='{}'",
-            metrics_cfg.bind
-        );
-        return;
+    let listener = if metrics_cfg.enabled {
+        if let Ok(addr) = metrics_cfg.bind.parse::<SocketAddr>() {
+            Some(addr)
+        } else {
+            warn!(
+                "metrics exporter disabled: invalid observability.metrics.bind='{}'",
+                metrics_cfg.bind
+            );
+            if !gateway_metrics {
+                return;
+            }
+            None
+        }
+    } else {
+        None
     };
 
     // Non-loopback warning is emitted by validate_observability() in config validation.
 
-    let builder = metrics_exporter_prometheus::PrometheusBuilder::new().with_http_listener(addr);
-    match builder.install() {
-        Ok(()) => {
-            let _ = METRICS_INIT.set(());
-            init_baseline_metrics(metrics_cfg.bind.clone());
-            info!("metrics exporter enabled on http://{addr}/metrics");
+    let installed = match listener {
+        Some(addr) => install_with_listener(addr),
+        None => PrometheusBuilder::new()
+            .install_recorder()
+            .map_err(|e| e.to_string()),
+    };
+    match installed {
+        Ok(handle) => {
+            let _ = METRICS_HANDLE.set(handle);
+            init_baseline_metrics(listener.map(|addr| addr.to_string()));
+            if let Some(addr) = listener {
+                info!("metrics exporter enabled on http://{addr}/metrics");
+            }
+            if gateway_metrics {
+                info!(
+                    "gateway metrics enabled on http://{}:{}/metrics",
+                    config.gateway.host, config.gateway.port
+                );
+            }
         }
         Err(e) => {
             warn!("failed to initialize metrics exporter: {}", e);
@@ -50,7 +77,37 @@ pub fn init_metrics_exporter(config: &crate::config::Config) {
     }
 }
 
-fn init_baseline_metrics(bind: String) {
+/// Build the recorder with its HTTP listener, install it globally and spawn
+/// the listener on the current runtime.
+fn install_with_listener(addr: SocketAddr) -> Result<PrometheusHandle, String> {
+    let runtime =
+        tokio::runtime::Handle::try_current().map_err(|_| "no active tokio runtime".to_string())?;
+    let (recorder, exporter) = {
+        let _guard = runtime.enter();
+        PrometheusBuilder::new()
+            .with_http_listener(addr)
+            .build()
+            .map_err(|e| e.to_string())?
+    };
+    let handle = recorder.handle();
+    metrics::set_global_recorder(recorder).map_err(|e| e.to_string())?;
+    runtime.spawn(exporter);
+    Ok(handle)
+}
+
+/// Renderer for the gateway's `/metrics` route, or `None` when
+/// `gateway.metrics.enabled` is off or no recorder is installed.
+pub fn gateway_metrics_renderer(
+    config: &crate::config::Config,
+) -> Option<crate::gateway::MetricsRenderer> {
+    if !config.gateway.metrics.enabled {
+        return None;
+    }
+    let handle = METRICS_HANDLE.get()?.clone();
+    Some(std::sync::Arc::new(move || handle.render()))
+}
+
+fn init_baseline_metrics(bind: Option<String>) {
     let start_mono = *PROCESS_START_MONO.get_or_init(Instant::now);
     let start_unix = *PROCESS_START_UNIX.get_or_init(|| {
         SystemTime::now()
@@ -68,7 +125,9 @@ fn init_baseline_metrics(bind: String) {
     .set(1.0);
     metrics::gauge!("oxicrab_up").set(1.0);
     metrics::gauge!("oxicrab_start_time_seconds").set(start_unix);
-    metrics::gauge!("oxicrab_metrics_exporter_info", "bind" => bind).set(1.0);
+    if let Some(bind) = bind {
+        metrics::gauge!("oxicrab_metrics_exporter_info", "bind" => bind).set(1.0);
+    }
     metrics::gauge!("oxicrab_runtime_threads_available")
         .set(std::thread::available_parallelism().map_or(0.0, |n| n.get() as f64));
 
@@ -95,6 +154,11 @@ fn init_baseline_metrics(bind: String) {
         loop {
             interval.tick().await;
             publish_runtime_metrics(start_mono, start_unix);
+            // Recorders installed without the HTTP listener get no upkeep
+            // task of their own; drain histogram buckets here.
+            if let Some(handle) = METRICS_HANDLE.get() {
+                handle.run_upkeep();
+            }
         }
     });
 }
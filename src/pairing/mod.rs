@@ -171,6 +171,27 @@ impl PairingStore {
         self.approve_with_client(code, "default")
     }
 
+    /// Deny a pending pairing request by code: the code is dropped without
+    /// pairing the sender, who may ask again later. Returns `(channel, sender_id)`
+    /// of the denied request, or `None` if the code is unknown or expired.
+    pub fn deny(&self, code: &str) -> Result<Option<(String, String)>> {
+        let code_upper = code.to_uppercase();
+        let all_pending = self.db.get_all_pending(CODE_TTL_SECS)?;
+        let matched = all_pending.iter().find(|r| {
+            use subtle::ConstantTimeEq;
+            r.code.as_bytes().ct_eq(code_upper.as_bytes()).into()
+        });
+        let Some(request) = matched else {
+            return Ok(None);
+        };
+        self.db.remove_pending(&request.code)?;
+        info!(
+            "pairing denied: channel={}, sender={}",
+            request.channel, request.sender_id
+        );
+        Ok(Some((request.channel.clone(), request.sender_id.clone())))
+    }
+
     /// Check if a sender is in the pairing store's allowlist for a channel.
     pub fn is_paired(&self, channel: &str, sender_id: &str) -> bool {
         self.db
@@ -52,6 +52,27 @@ fn test_approve_invalid_code() {
     assert!(result.is_none());
 }
 
+#[test]
+fn test_deny_drops_pending_request() {
+    let store = PairingStore::new(test_db());
+
+    let code = store
+        .request_pairing("telegram", "user123")
+        .unwrap()
+        .unwrap();
+    let denied = store.deny(&code.to_lowercase()).unwrap();
+    assert_eq!(
+        denied,
+        Some(("telegram".to_string(), "user123".to_string()))
+    );
+    assert!(!store.is_paired("telegram", "user123"));
+    assert!(store.list_pending().is_empty());
+
+    // Already handled: neither deny nor approve finds it again
+    assert!(store.deny(&code).unwrap().is_none());
+    assert!(store.approve(&code).unwrap().is_none());
+}
+
 #[test]
 fn test_revoke() {
     let store = PairingStore::new(test_db());
@@ -3,9 +3,11 @@
 //! These functions were extracted from `Config` methods to decouple the config
 //! schema (pure data types + validation) from provider implementations.
 
-use crate::config::TaskRouting;
-use crate::config::routing::{ResolvedChatRouting, ResolvedRouting};
+use crate::config::routing::{
+    EMPTY_RESPONSE_TASK, EXTRACTION_TASK, ResolvedChatRouting, ResolvedRouting, TOOL_SUMMARY_TASK,
+};
 use crate::config::schema::{Config, normalize_provider, parse_model_ref};
+use crate::config::{EmptyResponseAction, TaskRouting};
 use crate::providers::base::LLMProvider;
 use crate::providers::strategy::ProviderFactory;
 use crate::utils::credential_store::OAuthTokenStore;
@@ -15,14 +17,17 @@ use std::sync::Arc;
 /// Create an LLM provider instance based on configuration.
 ///
 /// Uses a 2-tier resolution strategy: prefix notation, then model-name
-/// inference. When fallbacks are configured, wraps the primary provider
-/// in a `FallbackProvider` chain.
+/// inference. Model aliases (`models.aliases`) are resolved first. When
+/// fallbacks are configured, wraps the primary provider in a
+/// `FallbackProvider` chain.
 pub fn create_provider(
     config: &Config,
     model: Option<&str>,
     db: Option<Arc<dyn OAuthTokenStore>>,
 ) -> anyhow::Result<Arc<dyn LLMProvider>> {
-    let model = model.unwrap_or(&config.agents.defaults.model_routing.default);
+    let model = config
+        .models
+        .resolve(model.unwrap_or(&config.agents.defaults.model_routing.default));
     let factory = ProviderFactory::with_db(config, db);
 
     // Build fallback chain from modelRouting.fallbacks
@@ -35,6 +40,7 @@ pub fn create_provider(
         let primary_bare = parse_model_ref(model).model.to_string();
         let mut chain = vec![(primary, primary_bare)];
         for fb_model in &routing.fallbacks {
+            let fb_model = config.models.resolve(fb_model);
             let mut fb_provider = factory.create_provider(fb_model)?;
             if should_use_prompt_guided_tools(config, fb_model) {
                 fb_provider =
@@ -56,13 +62,60 @@ pub fn create_provider(
     Ok(provider)
 }
 
+/// Create the `providers.failover` secondary provider, if configured.
+///
+/// Returns the provider and its canonical provider name. The provider is
+/// created for the secondary's default model (or `providers.failover.model`).
+pub fn create_failover_provider(
+    config: &Config,
+    db: Option<Arc<dyn OAuthTokenStore>>,
+) -> anyhow::Result<Option<(Arc<dyn LLMProvider>, String)>> {
+    let Some(model_ref) = config.providers.failover.secondary_model_ref() else {
+        return Ok(None);
+    };
+    let name = parse_model_ref(&model_ref)
+        .provider
+        .unwrap_or_default()
+        .to_string();
+    let mut provider = ProviderFactory::with_db(config, db).create_provider(&model_ref)?;
+    if should_use_prompt_guided_tools(config, &model_ref) {
+        provider = crate::providers::prompt_guided::PromptGuidedToolsProvider::wrap(provider);
+    }
+    Ok(Some((provider, name)))
+}
+
 /// Create providers for all configured model routing task overrides.
 pub fn create_routed_providers(
     config: &Config,
     db: Option<Arc<dyn OAuthTokenStore>>,
 ) -> anyhow::Result<Option<ResolvedRouting>> {
     let routing = &config.agents.defaults.model_routing;
-    if routing.tasks.is_empty() {
+    let empty_fallback = config
+        .agents
+        .defaults
+        .empty_response
+        .fallback_model
+        .as_deref()
+        .filter(|_| config.agents.defaults.empty_response.action == EmptyResponseAction::Fallback);
+    let tool_summary = config
+        .agents
+        .defaults
+        .tool_result_summary
+        .model
+        .as_deref()
+        .filter(|_| config.agents.defaults.tool_result_summary.enabled);
+    let extraction = config
+        .agents
+        .defaults
+        .compaction
+        .extraction_model
+        .as_deref()
+        .filter(|_| config.agents.defaults.compaction.enabled);
+    if routing.tasks.is_empty()
+        && empty_fallback.is_none()
+        && tool_summary.is_none()
+        && extraction.is_none()
+    {
         return Ok(None);
     }
     let factory = ProviderFactory::with_db(config, db);
@@ -71,6 +124,7 @@ pub fn create_routed_providers(
     let mut provider_cache: HashMap<String, (Arc<dyn LLMProvider>, String)> = HashMap::new();
 
     let mut get_or_create = |model_str: &str| -> anyhow::Result<(Arc<dyn LLMProvider>, String)> {
+        let model_str = config.models.resolve(model_str);
         if let Some(cached) = provider_cache.get(model_str) {
             return Ok(cached.clone());
         }
@@ -94,10 +148,17 @@ pub fn create_routed_providers(
                 tasks.insert(task_name.clone(), entry);
             }
             TaskRouting::Chat(chat_config) => {
+                let light = chat_config
+                    .models
+                    .light
+                    .as_deref()
+                    .map(&mut get_or_create)
+                    .transpose()?;
                 let standard = get_or_create(&chat_config.models.standard)?;
                 let heavy = get_or_create(&chat_config.models.heavy)?;
                 chat = Some(ResolvedChatRouting {
                     thresholds: chat_config.thresholds.clone(),
+                    light,
                     standard,
                     heavy,
                     weights: chat_config.weights.clone(),
@@ -106,6 +167,19 @@ pub fn create_routed_providers(
         }
     }
 
+    // The empty-response fallback, tool summary and extraction models ride on
+    // task routing so the agent loop resolves them like any other task
+    // override.
+    if let Some(model_str) = empty_fallback {
+        tasks.insert(EMPTY_RESPONSE_TASK.to_string(), get_or_create(model_str)?);
+    }
+    if let Some(model_str) = tool_summary {
+        tasks.insert(TOOL_SUMMARY_TASK.to_string(), get_or_create(model_str)?);
+    }
+    if let Some(model_str) = extraction {
+        tasks.insert(EXTRACTION_TASK.to_string(), get_or_create(model_str)?);
+    }
+
     Ok(Some(ResolvedRouting::new(tasks, chat)))
 }
 
@@ -114,7 +188,7 @@ pub fn create_routed_providers(
 fn should_use_prompt_guided_tools(config: &Config, model: &str) -> bool {
     use crate::config::schema::infer_provider_from_model;
 
-    let model_ref = parse_model_ref(model);
+    let model_ref = parse_model_ref(config.models.resolve(model));
     if let Some(prefix_provider) = model_ref.provider {
         let normalized = normalize_provider(prefix_provider);
         return match normalized.as_ref() {
@@ -147,6 +221,20 @@ mod tests {
         assert!(should_use_prompt_guided_tools(&config, "ollama/llama3"));
     }
 
+    #[test]
+    fn test_routed_providers_include_extraction_model() {
+        let mut config = Config::default();
+        config.agents.defaults.compaction.enabled = true;
+        config.agents.defaults.compaction.extraction_model = Some("ollama/llama3".into());
+        let routing = create_routed_providers(&config, None).unwrap().unwrap();
+        let o = routing.resolve_overrides(EXTRACTION_TASK);
+        assert!(o.provider.is_some());
+        assert_eq!(o.model.as_deref(), Some("llama3"));
+
+        config.agents.defaults.compaction.enabled = false;
+        assert!(create_routed_providers(&config, None).unwrap().is_none());
+    }
+
     #[test]
     fn test_prompt_guided_tools_known_model_returns_false() {
         let config = Config::default();
@@ -8,7 +8,8 @@ use oxicrab::config::{
     CognitiveConfig, CompactionConfig, ExfiltrationGuardConfig, PromptGuardConfig, SandboxConfig,
 };
 use oxicrab::providers::base::{
-    ChatRequest, LLMProvider, LLMResponse, Message, ResponseFormat, ToolCallRequest, ToolDefinition,
+    ChatRequest, LLMProvider, LLMResponse, Message, ReasoningEffort, ResponseFormat,
+    ToolCallRequest, ToolDefinition,
 };
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -23,6 +24,7 @@ pub struct RecordedCall {
     pub max_tokens: u32,
     pub tool_choice: Option<String>,
     pub response_format: Option<ResponseFormat>,
+    pub reasoning_effort: Option<ReasoningEffort>,
 }
 
 pub struct MockLLMProvider {
@@ -55,6 +57,7 @@ impl LLMProvider for MockLLMProvider {
                 max_tokens: req.max_tokens,
                 tool_choice: req.tool_choice.clone(),
                 response_format: req.response_format.clone(),
+                reasoning_effort: req.reasoning_effort,
             });
 
         let response = self.responses.lock().expect("lock responses").pop_front();
@@ -171,6 +174,8 @@ pub struct TestAgentOverrides {
     pub prompt_guard_config: Option<PromptGuardConfig>,
     pub sandbox_config: Option<SandboxConfig>,
     pub approval_config: Option<oxicrab::config::ApprovalConfig>,
+    pub tool_presets: Option<std::collections::HashMap<String, Vec<String>>>,
+    pub delegation: Option<oxicrab::config::DelegationConfig>,
 }
 
 pub async fn create_test_agent_with(
@@ -222,6 +227,12 @@ pub async fn create_test_agent_with(
     if let Some(v) = overrides.approval_config {
         config.approval_config = v;
     }
+    if let Some(v) = overrides.tool_presets {
+        config.tool_presets = v;
+    }
+    if let Some(v) = overrides.delegation {
+        config.delegation = v;
+    }
 
     AgentLoop::new(config)
         .await
@@ -259,6 +270,7 @@ impl LLMProvider for FailingMockProvider {
                 max_tokens: req.max_tokens,
                 tool_choice: req.tool_choice.clone(),
                 response_format: req.response_format.clone(),
+                reasoning_effort: req.reasoning_effort,
             });
         Err(anyhow::anyhow!("{}", self.error_message))
     }
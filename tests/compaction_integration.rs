@@ -33,11 +33,16 @@ async fn test_compaction_disabled_preserves_full_history() {
             compaction_config: Some(CompactionConfig {
                 enabled: false,
                 threshold_tokens: 100,
+                max_messages: None,
                 keep_recent: 2,
                 keep_recent_turns: None,
                 extraction_enabled: false,
                 model: None,
+                extraction_model: None,
+                checkpoint: Default::default(),
+                extraction_interval: Default::default(),
                 pre_flush_enabled: false,
+                max_history_tokens: None,
             }),
             ..Default::default()
         },
@@ -117,7 +122,7 @@ async fn test_extract_facts_returns_nothing() {
     let compactor = MessageCompactor::new(Arc::new(provider), None);
 
     let facts = compactor
-        .extract_facts("What time is it?", "It's 3pm.", "")
+        .extract_facts(&[("What time is it?".into(), "It's 3pm.".into())], "")
         .await
         .unwrap();
 
@@ -137,7 +142,13 @@ async fn test_extract_facts_returns_bullets() {
     let compactor = MessageCompactor::new(Arc::new(provider), None);
 
     let facts = compactor
-        .extract_facts("Call me Alice, and I like dark mode.", "Got it, Alice!", "")
+        .extract_facts(
+            &[(
+                "Call me Alice, and I like dark mode.".into(),
+                "Got it, Alice!".into(),
+            )],
+            "",
+        )
         .await
         .unwrap();
 
@@ -145,6 +156,33 @@ async fn test_extract_facts_returns_bullets() {
     assert!(facts.contains("Alice"));
 }
 
+#[tokio::test]
+async fn test_extract_facts_batches_exchanges_with_extraction_model() {
+    let provider = MockLLMProvider::with_responses(vec![text_response("NOTHING")]);
+    let calls = provider.calls.clone();
+
+    let compactor = MessageCompactor::new(Arc::new(provider), Some("big-model".into()))
+        .with_extraction_model(Some("cheap-model".into()));
+
+    compactor
+        .extract_facts(
+            &[
+                ("I live in Lisbon.".into(), "Noted.".into()),
+                ("My cat is called Miso.".into(), "Cute!".into()),
+            ],
+            "",
+        )
+        .await
+        .unwrap();
+
+    let calls = calls.lock().unwrap();
+    assert_eq!(calls.len(), 1, "one call for the whole batch");
+    assert_eq!(calls[0].model.as_deref(), Some("cheap-model"));
+    let prompt = calls[0].messages[0].content.clone();
+    assert!(prompt.contains("User: I live in Lisbon."));
+    assert!(prompt.contains("User: My cat is called Miso."));
+}
+
 #[tokio::test]
 async fn test_compaction_triggers_at_threshold() {
     let tmp = TempDir::new().expect("create temp dir");
@@ -174,11 +212,16 @@ async fn test_compaction_triggers_at_threshold() {
             compaction_config: Some(CompactionConfig {
                 enabled: true,
                 threshold_tokens: 100, // Very low threshold
+                max_messages: None,
                 keep_recent: 2,
                 keep_recent_turns: None,
                 extraction_enabled: false,
                 model: None,
+                extraction_model: None,
+                checkpoint: Default::default(),
+                extraction_interval: Default::default(),
                 pre_flush_enabled: false,
+                max_history_tokens: None,
             }),
             ..Default::default()
         },
@@ -226,3 +269,52 @@ async fn test_compaction_triggers_at_threshold() {
         recorded.len()
     );
 }
+
+#[tokio::test]
+async fn test_compaction_triggers_at_max_messages() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let mut responses: Vec<LLMResponse> = (0..4).map(|_| text_response("ok")).collect();
+    responses.push(text_response("Compacted summary of conversation."));
+    responses.push(text_response("Final response."));
+    let provider = MockLLMProvider::with_responses(responses);
+    let calls = provider.calls.clone();
+
+    let agent = create_test_agent_with(
+        provider,
+        &tmp,
+        TestAgentOverrides {
+            compaction_config: Some(CompactionConfig {
+                enabled: true,
+                // Far above anything these short messages reach
+                threshold_tokens: 1_000_000,
+                max_messages: Some(6),
+                keep_recent: 2,
+                keep_recent_turns: None,
+                extraction_enabled: false,
+                model: None,
+                extraction_model: None,
+                checkpoint: Default::default(),
+                extraction_interval: Default::default(),
+                pre_flush_enabled: false,
+                max_history_tokens: None,
+            }),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let is_compaction_call = |call: &common::RecordedCall| {
+        call.messages
+            .iter()
+            .any(|m| m.content.to_lowercase().contains("summarize"))
+    };
+    for i in 0..4 {
+        let _ = agent
+            .process_direct(&format!("short {i}"), "test:count", "telegram", "count")
+            .await;
+        let recorded = calls.lock().expect("lock recorded calls");
+        let compacted = recorded.iter().any(is_compaction_call);
+        // Three exchanges leave 6 messages, so the fourth turn compacts
+        assert_eq!(compacted, i == 3, "turn {i}: compaction={compacted}");
+    }
+}
@@ -20,6 +20,7 @@ fn make_test_job(id: &str, name: &str) -> CronJob {
             targets: vec![CronTarget {
                 channel: "telegram".to_string(),
                 to: "user1".to_string(),
+                message_override: None,
             }],
         },
         state: CronJobState::default(),
@@ -250,14 +251,17 @@ async fn test_cron_multi_target_job() {
                 CronTarget {
                     channel: "slack".to_string(),
                     to: "U08G6HBC89X".to_string(),
+                    message_override: None,
                 },
                 CronTarget {
                     channel: "discord".to_string(),
                     to: "123456789".to_string(),
+                    message_override: None,
                 },
                 CronTarget {
                     channel: "telegram".to_string(),
                     to: "987654321".to_string(),
+                    message_override: None,
                 },
             ],
         },
@@ -293,10 +297,12 @@ async fn test_cron_update_targets() {
         CronTarget {
             channel: "slack".to_string(),
             to: "U12345".to_string(),
+            message_override: None,
         },
         CronTarget {
             channel: "discord".to_string(),
             to: "999888777".to_string(),
+            message_override: None,
         },
     ];
 
@@ -649,6 +655,7 @@ fn make_event_job(id: &str, pattern: &str, channel: Option<&str>) -> CronJob {
             targets: vec![CronTarget {
                 channel: "telegram".to_string(),
                 to: "user1".to_string(),
+                message_override: None,
             }],
         },
         state: CronJobState::default(),
@@ -899,6 +906,7 @@ fn slack_target() -> CronTarget {
     CronTarget {
         channel: "slack".to_string(),
         to: "U08G6HBC89X".to_string(),
+        message_override: None,
     }
 }
 
@@ -907,10 +915,12 @@ fn multi_channel_targets() -> Vec<CronTarget> {
         CronTarget {
             channel: "slack".to_string(),
             to: "U08G6HBC89X".to_string(),
+            message_override: None,
         },
         CronTarget {
             channel: "discord".to_string(),
             to: "123456789".to_string(),
+            message_override: None,
         },
     ]
 }
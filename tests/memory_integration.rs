@@ -73,7 +73,7 @@ fn test_memory_db_insert_and_search_roundtrip() {
 
     // Search for Rust-related content
     let results = db
-        .search("Rust programming", 10, None)
+        .search("Rust programming", 10, None, false)
         .expect("search memory db");
     assert!(
         !results.is_empty(),
@@ -88,7 +88,7 @@ fn test_memory_db_search_empty_query() {
     let db_path = tmp.path().join("test_memory.sqlite3");
     let db = MemoryDB::new(&db_path).expect("create memory db");
 
-    let results = db.search("", 10, None).expect("search memory db");
+    let results = db.search("", 10, None, false).expect("search memory db");
     assert!(results.is_empty(), "Empty query should return no results");
 }
 
@@ -4,7 +4,11 @@ use common::{
     MockLLMProvider, TestAgentOverrides, ToolCapturingProvider, create_test_agent_with,
     text_response, tool_call, tool_response,
 };
+use oxicrab::agent::{AgentLoop, AgentLoopConfig};
+use oxicrab::bus::{InboundMessage, MessageBus};
+use oxicrab::providers::base::{ChatRequest, LLMProvider, LLMResponse};
 use serde_json::json;
+use std::sync::Arc;
 use tempfile::TempDir;
 
 fn default_agent(
@@ -115,6 +119,56 @@ async fn test_tool_call_and_result() {
     assert!(has_tool_result, "Second call should include tool result");
 }
 
+#[tokio::test]
+async fn test_delegation_hint_after_tool_call_threshold() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let provider = MockLLMProvider::with_responses(vec![
+        tool_response(vec![tool_call(
+            "tc1",
+            "list_dir",
+            json!({"path": tmp.path().to_str().unwrap()}),
+        )]),
+        text_response("Done."),
+    ]);
+    let calls = provider.calls.clone();
+
+    let agent = create_test_agent_with(
+        provider,
+        &tmp,
+        TestAgentOverrides {
+            delegation: Some(oxicrab::config::DelegationConfig {
+                suggest_after_tool_calls: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    agent
+        .process_direct(
+            "List the directory",
+            "test:delegate",
+            "telegram",
+            "delegate",
+        )
+        .await
+        .expect("process message");
+
+    let recorded = calls.lock().expect("lock recorded calls");
+    assert_eq!(recorded.len(), 2);
+    let hinted = |call: &common::RecordedCall| {
+        call.messages
+            .iter()
+            .any(|m| m.role == "system" && m.content.contains("hand it to a subagent with `spawn`"))
+    };
+    assert!(!hinted(&recorded[0]));
+    assert!(
+        hinted(&recorded[1]),
+        "second call should carry the delegation hint"
+    );
+}
+
 #[tokio::test]
 async fn test_unknown_tool_handled() {
     let tmp = TempDir::new().expect("create temp dir");
@@ -178,6 +232,70 @@ async fn test_provider_called_with_tools() {
     assert!(tool_names.contains(&"exec"), "Should have exec tool");
 }
 
+#[tokio::test]
+async fn test_focus_preset_limits_tools_for_session() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let provider = ToolCapturingProvider::new();
+    let tool_defs = provider.tool_defs.clone();
+    let presets = std::iter::once((
+        "research".to_string(),
+        vec!["read_file".to_string(), "list_dir".to_string()],
+    ))
+    .collect();
+    let agent = create_test_agent_with(
+        provider,
+        &tmp,
+        TestAgentOverrides {
+            tool_presets: Some(presets),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let reply = agent
+        .process_direct("/focus nope", "test:focus", "telegram", "focus")
+        .await
+        .expect("unknown preset");
+    assert!(reply.contains("Unknown focus preset 'nope'"), "{reply}");
+    let reply = agent
+        .process_direct("/focus research", "test:focus", "telegram", "focus")
+        .await
+        .expect("start focus");
+    assert!(reply.contains("Focus mode: research"), "{reply}");
+    assert!(
+        tool_defs.lock().expect("lock tool defs").is_empty(),
+        "/focus is answered without an LLM call"
+    );
+
+    agent
+        .process_direct("Hello", "test:focus", "telegram", "focus")
+        .await
+        .expect("focused turn");
+    agent
+        .process_direct("/focus off", "test:focus", "telegram", "focus")
+        .await
+        .expect("stop focus");
+    agent
+        .process_direct("Hello", "test:focus", "telegram", "focus")
+        .await
+        .expect("unfocused turn");
+
+    let recorded = tool_defs.lock().expect("lock tool defs");
+    assert_eq!(recorded.len(), 2);
+    let names = |i: usize| -> Vec<String> {
+        recorded[i]
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|t| t.name.clone())
+            .collect()
+    };
+    let focused = names(0);
+    assert!(focused.contains(&"read_file".to_string()));
+    assert!(!focused.contains(&"exec".to_string()), "{focused:?}");
+    assert!(names(1).contains(&"exec".to_string()));
+}
+
 #[tokio::test]
 async fn test_multiple_tool_calls_in_sequence() {
     let tmp = TempDir::new().expect("create temp dir");
@@ -459,6 +577,84 @@ async fn test_empty_response_exhaustion_returns_empty() {
     );
 }
 
+async fn agent_with_empty_response_action(
+    provider: MockLLMProvider,
+    tmp: &TempDir,
+    action: oxicrab::config::EmptyResponseAction,
+) -> AgentLoop {
+    let bus = Arc::new(MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(100);
+    let mut config = AgentLoopConfig::test_defaults(
+        bus,
+        Arc::new(provider),
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    );
+    config.empty_response = action;
+    AgentLoop::new(config).await.expect("create agent")
+}
+
+#[tokio::test]
+async fn test_empty_response_reprompt_recovers() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let empty_response = oxicrab::providers::base::LLMResponse::default;
+    let provider = MockLLMProvider::with_responses(vec![
+        empty_response(),
+        empty_response(),
+        empty_response(),
+        text_response("Back on track."),
+    ]);
+    let calls = provider.calls.clone();
+    let agent = agent_with_empty_response_action(
+        provider,
+        &tmp,
+        oxicrab::config::EmptyResponseAction::Reprompt,
+    )
+    .await;
+
+    let result = agent
+        .process_direct("hello", "test:reprompt", "telegram", "reprompt")
+        .await
+        .expect("process");
+
+    assert_eq!(result, "Back on track.");
+    let recorded = calls.lock().expect("lock");
+    assert_eq!(recorded.len(), 4, "three empty calls plus one re-prompt");
+    assert!(
+        recorded[3]
+            .messages
+            .iter()
+            .any(|m| m.role == "system" && m.content.contains("last replies were empty")),
+        "re-prompt call should carry the nudge"
+    );
+}
+
+#[tokio::test]
+async fn test_empty_response_diagnostic_names_model() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let empty_response = oxicrab::providers::base::LLMResponse::default;
+    let provider =
+        MockLLMProvider::with_responses(vec![empty_response(), empty_response(), empty_response()]);
+    let calls = provider.calls.clone();
+    let agent = agent_with_empty_response_action(
+        provider,
+        &tmp,
+        oxicrab::config::EmptyResponseAction::Diagnostic,
+    )
+    .await;
+
+    let result = agent
+        .process_direct("hello", "test:diag", "telegram", "diag")
+        .await
+        .expect("process");
+
+    assert!(
+        result.contains("returned an empty response 3 times"),
+        "got: {result}"
+    );
+    assert_eq!(calls.lock().expect("lock").len(), 3, "no extra attempt");
+}
+
 #[tokio::test]
 async fn test_tool_use_then_conversational_followup() {
     // Full lifecycle: turn 1 uses tools, turn 2 is conversational
@@ -625,3 +821,586 @@ async fn test_end_to_end_bus_pipeline() {
 
     assert_eq!(response, "Bus pipeline works!");
 }
+
+/// Provider that takes a while to answer, to keep a turn in flight.
+struct SlowProvider {
+    inner: MockLLMProvider,
+    started: Arc<tokio::sync::Notify>,
+    delay: std::time::Duration,
+}
+
+#[async_trait::async_trait]
+impl LLMProvider for SlowProvider {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        self.started.notify_one();
+        tokio::time::sleep(self.delay).await;
+        self.inner.chat(req).await
+    }
+
+    fn default_model(&self) -> &str {
+        "mock-model"
+    }
+}
+
+#[tokio::test]
+async fn test_stop_with_drain_finishes_in_flight_turn() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let started = Arc::new(tokio::sync::Notify::new());
+    let provider = SlowProvider {
+        inner: MockLLMProvider::with_responses(vec![text_response("Finished before shutdown")]),
+        started: started.clone(),
+        delay: std::time::Duration::from_millis(300),
+    };
+
+    let bus = MessageBus::new(30, 60.0, 1000, 1000);
+    let mut outbound_rx = bus.take_outbound_rx().expect("take outbound rx");
+    let bus = Arc::new(bus);
+    let config = AgentLoopConfig::test_defaults(
+        bus.clone(),
+        Arc::new(provider),
+        tmp.path().to_path_buf(),
+        Arc::new(bus.outbound_tx.clone()),
+    );
+    let agent = Arc::new(AgentLoop::new(config).await.expect("create agent"));
+    let agent_task = tokio::spawn({
+        let agent = agent.clone();
+        async move { agent.run().await }
+    });
+
+    bus.publish_inbound(InboundMessage::builder("telegram", "user1", "chat1", "hello").build())
+        .await
+        .expect("publish inbound");
+    tokio::time::timeout(std::time::Duration::from_secs(5), started.notified())
+        .await
+        .expect("turn started");
+
+    agent
+        .stop_with_drain(std::time::Duration::from_secs(5))
+        .await;
+
+    // The response must already be queued when stop returns
+    let outbound = outbound_rx
+        .try_recv()
+        .expect("response published before stop returned");
+    assert_eq!(outbound.content, "Finished before shutdown");
+    tokio::time::timeout(std::time::Duration::from_secs(1), agent_task)
+        .await
+        .expect("agent loop exited")
+        .expect("join agent loop")
+        .expect("agent loop result");
+}
+
+/// Provider that records how many chats are running at the same time.
+struct ConcurrencyGaugeProvider {
+    inner: MockLLMProvider,
+    active: std::sync::atomic::AtomicUsize,
+    peak: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl LLMProvider for ConcurrencyGaugeProvider {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        use std::sync::atomic::Ordering;
+        let now = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        self.inner.chat(req).await
+    }
+
+    fn default_model(&self) -> &str {
+        "mock-model"
+    }
+}
+
+/// Run two messages through the agent loop with two turns allowed at once
+/// and return the peak number of concurrent provider calls.
+async fn peak_concurrency(first_chat: &str, second_chat: &str) -> usize {
+    let tmp = TempDir::new().expect("create temp dir");
+    let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let provider = ConcurrencyGaugeProvider {
+        inner: MockLLMProvider::with_responses(vec![
+            text_response("first"),
+            text_response("second"),
+        ]),
+        active: std::sync::atomic::AtomicUsize::new(0),
+        peak: peak.clone(),
+    };
+
+    let bus = MessageBus::new(30, 60.0, 1000, 1000);
+    let mut outbound_rx = bus.take_outbound_rx().expect("take outbound rx");
+    let bus = Arc::new(bus);
+    let mut config = AgentLoopConfig::test_defaults(
+        bus.clone(),
+        Arc::new(provider),
+        tmp.path().to_path_buf(),
+        Arc::new(bus.outbound_tx.clone()),
+    );
+    config.max_concurrent_sessions = 2;
+    let agent = Arc::new(AgentLoop::new(config).await.expect("create agent"));
+    let agent_task = tokio::spawn({
+        let agent = agent.clone();
+        async move { agent.run().await }
+    });
+
+    for (chat, text) in [(first_chat, "one"), (second_chat, "two")] {
+        bus.publish_inbound(InboundMessage::builder("telegram", "user1", chat, text).build())
+            .await
+            .expect("publish inbound");
+    }
+    for _ in 0..2 {
+        tokio::time::timeout(std::time::Duration::from_secs(5), outbound_rx.recv())
+            .await
+            .expect("response before timeout")
+            .expect("outbound open");
+    }
+
+    agent.stop().await;
+    let _ = agent_task.await;
+    peak.load(std::sync::atomic::Ordering::SeqCst)
+}
+
+#[tokio::test]
+async fn test_run_processes_sessions_concurrently_but_each_in_order() {
+    assert_eq!(peak_concurrency("chat1", "chat2").await, 2);
+    assert_eq!(peak_concurrency("chat1", "chat1").await, 1);
+}
+
+#[tokio::test]
+async fn test_cancel_reaches_turn_holding_the_only_slot() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let dir = tmp.path().to_str().unwrap().to_string();
+    let started = Arc::new(tokio::sync::Notify::new());
+    let provider = SlowProvider {
+        inner: MockLLMProvider::with_responses(vec![
+            tool_response(vec![tool_call("tc1", "list_dir", json!({"path": dir}))]),
+            text_response("Second chat answered"),
+        ]),
+        started: started.clone(),
+        delay: std::time::Duration::from_millis(300),
+    };
+
+    let bus = MessageBus::new(30, 60.0, 1000, 1000);
+    let mut outbound_rx = bus.take_outbound_rx().expect("take outbound rx");
+    let bus = Arc::new(bus);
+    let mut config = AgentLoopConfig::test_defaults(
+        bus.clone(),
+        Arc::new(provider),
+        tmp.path().to_path_buf(),
+        Arc::new(bus.outbound_tx.clone()),
+    );
+    config.max_concurrent_sessions = 1;
+    let agent = Arc::new(AgentLoop::new(config).await.expect("create agent"));
+    let agent_task = tokio::spawn({
+        let agent = agent.clone();
+        async move { agent.run().await }
+    });
+
+    bus.publish_inbound(InboundMessage::builder("telegram", "user1", "chat1", "research").build())
+        .await
+        .expect("publish inbound");
+    tokio::time::timeout(std::time::Duration::from_secs(5), started.notified())
+        .await
+        .expect("turn started");
+    // Waits for the busy slot, behind the cancel
+    bus.publish_inbound(InboundMessage::builder("telegram", "user2", "chat2", "hello").build())
+        .await
+        .expect("publish inbound");
+    bus.publish_inbound(InboundMessage::builder("telegram", "user1", "chat1", "/cancel").build())
+        .await
+        .expect("publish inbound");
+
+    let mut replies = Vec::new();
+    for _ in 0..2 {
+        let outbound = tokio::time::timeout(std::time::Duration::from_secs(5), outbound_rx.recv())
+            .await
+            .expect("response before timeout")
+            .expect("outbound open");
+        replies.push((outbound.chat_id, outbound.content));
+    }
+    assert_eq!(
+        replies,
+        vec![
+            ("chat1".to_string(), "Cancelled.".to_string()),
+            ("chat2".to_string(), "Second chat answered".to_string()),
+        ]
+    );
+
+    agent.stop().await;
+    let _ = agent_task.await;
+}
+
+#[tokio::test]
+async fn test_continue_resumes_turn_capped_with_pending_tools() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let dir = tmp.path().to_str().unwrap().to_string();
+    let provider = MockLLMProvider::with_responses(vec![
+        tool_response(vec![tool_call("tc1", "list_dir", json!({"path": dir}))]),
+        tool_response(vec![tool_call("tc2", "list_dir", json!({"path": dir}))]),
+        // Post-loop summary after the cap
+        text_response("Listed the directory twice, more to do."),
+        // Resumed turn
+        text_response("All done."),
+        text_response("Nothing left to do."),
+    ]);
+    let calls = provider.calls.clone();
+    let agent = create_test_agent_with(
+        provider,
+        &tmp,
+        TestAgentOverrides {
+            max_iterations: Some(2),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let resp = agent
+        .process_direct("Audit the workspace", "test:cont", "telegram", "cont")
+        .await
+        .expect("process");
+    assert_eq!(resp, "Listed the directory twice, more to do.");
+
+    let resp = agent
+        .process_direct("continue", "test:cont", "telegram", "cont")
+        .await
+        .expect("process");
+    assert_eq!(resp, "All done.");
+
+    agent
+        .process_direct("continue", "test:cont", "telegram", "cont")
+        .await
+        .expect("process");
+
+    let recorded = calls.lock().expect("lock");
+    assert_eq!(recorded.len(), 5);
+    let resume_note = |call: usize| {
+        recorded[call]
+            .messages
+            .iter()
+            .find(|m| m.role == "system" && m.content.contains("Resuming a task"))
+            .map(|m| m.content.clone())
+    };
+    let note = resume_note(3).expect("continue turn should carry the resume note");
+    assert!(note.contains("Original request: Audit the workspace"));
+    assert!(note.contains("list_dir("));
+    // The resumed turn finished, so the marker is gone
+    assert!(resume_note(4).is_none());
+}
+
+#[tokio::test]
+async fn test_reasoning_effort_follows_phase_and_turn_override() {
+    use oxicrab::agent::AgentRunOverrides;
+    use oxicrab::providers::base::ReasoningEffort;
+
+    let tmp = TempDir::new().expect("create temp dir");
+    let dir = tmp.path().to_str().unwrap().to_string();
+    let provider = MockLLMProvider::with_responses(vec![
+        tool_response(vec![tool_call("tc1", "list_dir", json!({"path": dir}))]),
+        text_response("Listed."),
+        text_response("Thought hard."),
+    ]);
+    let calls = provider.calls.clone();
+    let bus = Arc::new(MessageBus::default());
+    let (outbound_tx, _outbound_rx) = tokio::sync::mpsc::channel(100);
+    let mut config = AgentLoopConfig::test_defaults(
+        bus,
+        Arc::new(provider),
+        tmp.path().to_path_buf(),
+        Arc::new(outbound_tx),
+    );
+    config.reasoning.chat = Some(ReasoningEffort::Low);
+    config.reasoning.tools = Some(ReasoningEffort::Medium);
+    let agent = AgentLoop::new(config).await.expect("create agent");
+
+    agent
+        .process_direct("List the workspace", "test:reason", "telegram", "reason")
+        .await
+        .expect("process");
+    agent
+        .process_direct_with_overrides(
+            "Prove it",
+            "test:reason",
+            "telegram",
+            "reason",
+            &AgentRunOverrides {
+                reasoning_effort: Some(ReasoningEffort::High),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("process");
+
+    let efforts: Vec<_> = calls
+        .lock()
+        .expect("lock")
+        .iter()
+        .map(|c| c.reasoning_effort)
+        .collect();
+    assert_eq!(
+        efforts,
+        vec![
+            Some(ReasoningEffort::Low),
+            Some(ReasoningEffort::Medium),
+            Some(ReasoningEffort::High),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_dry_run_stubs_tool_execution() {
+    use oxicrab::agent::AgentRunOverrides;
+
+    let tmp = TempDir::new().expect("create temp dir");
+    let target = tmp.path().join("dry.txt");
+    let provider = MockLLMProvider::with_responses(vec![
+        tool_response(vec![tool_call(
+            "tc1",
+            "write_file",
+            json!({"path": target.to_str().unwrap(), "content": "hello"}),
+        )]),
+        text_response("Written."),
+    ]);
+    let calls = provider.calls.clone();
+    let agent = default_agent(provider, &tmp).await;
+
+    let result = agent
+        .process_direct_with_overrides(
+            "Write hello to dry.txt",
+            "test:dry",
+            "telegram",
+            "dry",
+            &AgentRunOverrides {
+                dry_run: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("process");
+
+    assert_eq!(result.content, "Written.");
+    assert!(!target.exists(), "dry run must not execute the tool");
+    let calls = calls.lock().expect("lock");
+    let tool_msg = calls[1]
+        .messages
+        .iter()
+        .find(|m| m.role == "tool")
+        .expect("tool result message");
+    assert!(
+        tool_msg
+            .content
+            .starts_with("[dry-run] would call write_file with {"),
+        "got: {}",
+        tool_msg.content
+    );
+}
+
+/// Provider whose circuit breaker is open until `down` is cleared.
+struct OutageProvider {
+    inner: MockLLMProvider,
+    down: Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[async_trait::async_trait]
+impl LLMProvider for OutageProvider {
+    async fn chat(&self, req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        if self.down.load(std::sync::atomic::Ordering::SeqCst) {
+            return Err(
+                oxicrab_core::errors::ProviderError::Unavailable("circuit open".into()).into(),
+            );
+        }
+        self.inner.chat(req).await
+    }
+
+    fn default_model(&self) -> &str {
+        "mock-model"
+    }
+}
+
+async fn next_outbound(
+    rx: &mut tokio::sync::mpsc::Receiver<oxicrab::bus::OutboundMessage>,
+) -> oxicrab::bus::OutboundMessage {
+    tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+        .await
+        .expect("outbound before timeout")
+        .expect("outbound open")
+}
+
+#[tokio::test]
+async fn test_offline_mode_queues_and_replays_messages() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let down = Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let provider = OutageProvider {
+        inner: MockLLMProvider::with_responses(vec![
+            text_response("Answer to first"),
+            text_response("Answer to second"),
+        ]),
+        down: down.clone(),
+    };
+
+    let bus = MessageBus::new(30, 60.0, 1000, 1000);
+    let mut outbound_rx = bus.take_outbound_rx().expect("take outbound rx");
+    let bus = Arc::new(bus);
+    let mut config = AgentLoopConfig::test_defaults(
+        bus.clone(),
+        Arc::new(provider),
+        tmp.path().to_path_buf(),
+        Arc::new(bus.outbound_tx.clone()),
+    );
+    config.offline_mode.enabled = true;
+    config.offline_mode.retry_interval_secs = 1;
+    let notice = config.offline_mode.reply.clone();
+    let agent = Arc::new(AgentLoop::new(config).await.expect("create agent"));
+    let agent_task = tokio::spawn({
+        let agent = agent.clone();
+        async move { agent.run().await }
+    });
+
+    bus.publish_inbound(InboundMessage::builder("telegram", "user1", "chat1", "first").build())
+        .await
+        .expect("publish inbound");
+    // Only the first queued message of a chat gets the notice
+    assert_eq!(next_outbound(&mut outbound_rx).await.content, notice);
+    bus.publish_inbound(InboundMessage::builder("telegram", "user1", "chat1", "second").build())
+        .await
+        .expect("publish inbound");
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    assert_eq!(
+        agent
+            .memory_db()
+            .count_offline_messages(Some("telegram:chat1"))
+            .expect("count"),
+        2
+    );
+
+    down.store(false, std::sync::atomic::Ordering::SeqCst);
+    assert_eq!(
+        next_outbound(&mut outbound_rx).await.content,
+        "Answer to first"
+    );
+    assert_eq!(
+        next_outbound(&mut outbound_rx).await.content,
+        "Answer to second"
+    );
+    assert_eq!(
+        agent
+            .memory_db()
+            .count_offline_messages(None)
+            .expect("count"),
+        0
+    );
+
+    agent.stop().await;
+    let _ = agent_task.await;
+}
+
+#[tokio::test]
+async fn test_offline_mode_queues_when_failover_also_fails() {
+    let tmp = TempDir::new().expect("create temp dir");
+    let primary = OutageProvider {
+        inner: MockLLMProvider::with_responses(vec![]),
+        down: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+    };
+    let secondary = common::FailingMockProvider::new("503 Service Unavailable");
+    let provider = oxicrab::providers::failover::FailoverProvider::wrap(
+        Arc::new(primary),
+        Arc::new(secondary),
+        "openai".into(),
+    );
+
+    let bus = MessageBus::new(30, 60.0, 1000, 1000);
+    let mut outbound_rx = bus.take_outbound_rx().expect("take outbound rx");
+    let bus = Arc::new(bus);
+    let mut config = AgentLoopConfig::test_defaults(
+        bus.clone(),
+        provider,
+        tmp.path().to_path_buf(),
+        Arc::new(bus.outbound_tx.clone()),
+    );
+    config.offline_mode.enabled = true;
+    let notice = config.offline_mode.reply.clone();
+    let agent = Arc::new(AgentLoop::new(config).await.expect("create agent"));
+    let agent_task = tokio::spawn({
+        let agent = agent.clone();
+        async move { agent.run().await }
+    });
+
+    bus.publish_inbound(InboundMessage::builder("telegram", "user1", "chat1", "first").build())
+        .await
+        .expect("publish inbound");
+    assert_eq!(next_outbound(&mut outbound_rx).await.content, notice);
+    assert_eq!(
+        agent
+            .memory_db()
+            .count_offline_messages(Some("telegram:chat1"))
+            .expect("count"),
+        1
+    );
+
+    agent.stop().await;
+    let _ = agent_task.await;
+}
+
+/// Provider that writes its reply in two parts.
+struct StreamingProvider;
+
+#[async_trait::async_trait]
+impl LLMProvider for StreamingProvider {
+    async fn chat(&self, _req: &ChatRequest) -> anyhow::Result<LLMResponse> {
+        Ok(text_response("Hello world"))
+    }
+
+    async fn chat_stream(
+        &self,
+        req: &ChatRequest,
+        sink: oxicrab::providers::base::StreamSink,
+    ) -> anyhow::Result<LLMResponse> {
+        sink.push("Hello");
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        sink.push(" world");
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        self.chat(req).await
+    }
+
+    fn default_model(&self) -> &str {
+        "mock-model"
+    }
+}
+
+#[tokio::test]
+async fn test_streaming_publishes_partial_text_before_reply() {
+    use oxicrab::bus::meta;
+
+    let tmp = TempDir::new().expect("create temp dir");
+    let bus = MessageBus::new(30, 60.0, 1000, 1000);
+    let mut outbound_rx = bus.take_outbound_rx().expect("take outbound rx");
+    let bus = Arc::new(bus);
+    let mut config = AgentLoopConfig::test_defaults(
+        bus.clone(),
+        Arc::new(StreamingProvider),
+        tmp.path().to_path_buf(),
+        Arc::new(bus.outbound_tx.clone()),
+    );
+    config.streaming.enabled = true;
+    config.streaming.edit_interval_ms = 10;
+    let agent = Arc::new(AgentLoop::new(config).await.expect("create agent"));
+    let agent_task = tokio::spawn({
+        let agent = agent.clone();
+        async move { agent.run().await }
+    });
+
+    bus.publish_inbound(InboundMessage::builder("slack", "user1", "chat1", "hi").build())
+        .await
+        .expect("publish inbound");
+    let first = next_outbound(&mut outbound_rx).await;
+    assert_eq!(first.content, "Hello");
+    assert_eq!(first.metadata[meta::STREAM_PARTIAL], json!(true));
+    let stream_id = first.metadata[meta::STREAM_ID].clone();
+    assert_eq!(next_outbound(&mut outbound_rx).await.content, "Hello world");
+    let reply = next_outbound(&mut outbound_rx).await;
+    assert_eq!(reply.content, "Hello world");
+    assert!(!reply.metadata.contains_key(meta::STREAM_PARTIAL));
+    assert_eq!(reply.metadata[meta::STREAM_ID], stream_id);
+
+    agent.stop().await;
+    let _ = agent_task.await;
+}